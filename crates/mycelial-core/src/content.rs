@@ -2,23 +2,50 @@
 //!
 //! This module provides types for content-addressed data, where content is
 //! identified by its cryptographic hash rather than location.
+//!
+//! Private content can be sealed with [`Content::encrypt`] or
+//! [`Content::encrypted`]. Its ID is the hash of the ciphertext, so peers can
+//! store and serve it through the usual provider records without being able
+//! to read it. The content type travels inside the ciphertext, so storing
+//! peers don't learn it either.
 
 use blake3::Hasher;
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use rand::RngCore;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
 
 use crate::{MycelialError, Result};
 
 /// A content identifier (CID) based on Blake3 hash
+///
+/// Its canonical text form (see [`Display`](fmt::Display) and
+/// [`FromStr`]) is the Blake3 multihash of the content, multibase-encoded
+/// as base58btc. DHT keys use the raw digest from [`ContentId::to_bytes`].
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ContentId([u8; 32]);
 
 impl ContentId {
+    /// Multihash prefix: the Blake3 multicodec followed by the digest length
+    const BLAKE3_MULTIHASH: [u8; 2] = [0x1e, 0x20];
+
     /// Create a content ID from raw bytes
     pub fn from_bytes(bytes: [u8; 32]) -> Self {
         Self(bytes)
     }
 
+    /// Create a content ID from a raw digest of unchecked length
+    pub fn from_slice(bytes: &[u8]) -> Result<Self> {
+        let arr: [u8; 32] = bytes
+            .try_into()
+            .map_err(|_| MycelialError::Serialization("Invalid content ID length".into()))?;
+        Ok(Self(arr))
+    }
+
     /// Compute the content ID for some data
     pub fn hash(data: &[u8]) -> Self {
         let hash = blake3::hash(data);
@@ -26,10 +53,28 @@ impl ContentId {
     }
 
     /// Get the raw bytes
+    ///
+    /// This is the key content is stored and looked up under in the DHT.
     pub fn to_bytes(&self) -> [u8; 32] {
         self.0
     }
 
+    /// Encode as a Blake3 multihash
+    pub fn to_multihash(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(34);
+        bytes.extend_from_slice(&Self::BLAKE3_MULTIHASH);
+        bytes.extend_from_slice(&self.0);
+        bytes
+    }
+
+    /// Decode from a Blake3 multihash
+    pub fn from_multihash(bytes: &[u8]) -> Result<Self> {
+        let digest = bytes.strip_prefix(&Self::BLAKE3_MULTIHASH).ok_or_else(|| {
+            MycelialError::Serialization("Content ID is not a Blake3 multihash".into())
+        })?;
+        Self::from_slice(digest)
+    }
+
     /// Encode as hex string
     pub fn to_hex(&self) -> String {
         hex::encode(self.0)
@@ -38,16 +83,7 @@ impl ContentId {
     /// Decode from hex string
     pub fn from_hex(s: &str) -> Result<Self> {
         let bytes = hex::decode(s).map_err(|e| MycelialError::Serialization(e.to_string()))?;
-
-        if bytes.len() != 32 {
-            return Err(MycelialError::Serialization(
-                "Invalid content ID length".into(),
-            ));
-        }
-
-        let mut arr = [0u8; 32];
-        arr.copy_from_slice(&bytes);
-        Ok(Self(arr))
+        Self::from_slice(&bytes)
     }
 
     /// Encode as base58
@@ -60,16 +96,7 @@ impl ContentId {
         let bytes = bs58::decode(s)
             .into_vec()
             .map_err(|e| MycelialError::Serialization(e.to_string()))?;
-
-        if bytes.len() != 32 {
-            return Err(MycelialError::Serialization(
-                "Invalid content ID length".into(),
-            ));
-        }
-
-        let mut arr = [0u8; 32];
-        arr.copy_from_slice(&bytes);
-        Ok(Self(arr))
+        Self::from_slice(&bytes)
     }
 
     /// Verify that data matches this content ID
@@ -78,6 +105,22 @@ impl ContentId {
     }
 }
 
+/// Verify many content items at once, returning a result per item in order
+///
+/// Items are hashed in parallel when the `rayon` feature is enabled.
+pub fn verify_batch(items: &[(ContentId, &[u8])]) -> Vec<bool> {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        items.par_iter().map(|(id, data)| id.verify(data)).collect()
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    {
+        items.iter().map(|(id, data)| id.verify(data)).collect()
+    }
+}
+
 impl fmt::Debug for ContentId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "ContentId({})", &self.to_hex()[..16])
@@ -86,7 +129,24 @@ impl fmt::Debug for ContentId {
 
 impl fmt::Display for ContentId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.to_base58())
+        let encoded = multibase::encode(multibase::Base::Base58Btc, self.to_multihash());
+        write!(f, "{}", encoded)
+    }
+}
+
+impl FromStr for ContentId {
+    type Err = MycelialError;
+
+    /// Parse the canonical form produced by [`Display`](fmt::Display)
+    fn from_str(s: &str) -> Result<Self> {
+        let (base, bytes) =
+            multibase::decode(s).map_err(|e| MycelialError::Serialization(e.to_string()))?;
+        if base != multibase::Base::Base58Btc {
+            return Err(MycelialError::Serialization(
+                "Content ID must be base58btc encoded".into(),
+            ));
+        }
+        Self::from_multihash(&bytes)
     }
 }
 
@@ -115,17 +175,29 @@ impl Content {
         }
     }
 
+    /// Create content with a MIME type recorded in its metadata
+    pub fn bytes(data: Vec<u8>, mime: impl Into<String>) -> Self {
+        let mime = mime.into();
+        let mut content = Self::new(data, mime.clone());
+        content.metadata.mime_type = Some(mime);
+        content
+    }
+
     /// Create text content
     pub fn text(text: impl Into<String>) -> Self {
         let text = text.into();
-        Self::new(text.into_bytes(), "text/plain")
+        let mut content = Self::bytes(text.into_bytes(), "text/plain");
+        content.metadata.encoding = Some("utf-8".into());
+        content
     }
 
     /// Create JSON content
     pub fn json<T: Serialize>(value: &T) -> Result<Self> {
         let json =
             serde_json::to_vec(value).map_err(|e| MycelialError::Serialization(e.to_string()))?;
-        Ok(Self::new(json, "application/json"))
+        let mut content = Self::bytes(json, "application/json");
+        content.metadata.encoding = Some("utf-8".into());
+        Ok(content)
     }
 
     /// Verify content integrity
@@ -138,10 +210,207 @@ impl Content {
         std::str::from_utf8(&self.data).ok()
     }
 
+    /// The MIME type, from the metadata or else the content type
+    pub fn mime_type(&self) -> &str {
+        self.metadata
+            .mime_type
+            .as_deref()
+            .unwrap_or(&self.content_type)
+    }
+
+    /// Check whether the MIME type says this is text
+    pub fn is_text(&self) -> bool {
+        let mime = self.mime_type();
+        mime.starts_with("text/") || mime == "application/json"
+    }
+
+    /// Get text content as a string
+    ///
+    /// Returns `None` for binary content or text that isn't valid UTF-8.
+    pub fn as_str(&self) -> Option<&str> {
+        let utf8 = self
+            .metadata
+            .encoding
+            .as_deref()
+            .map_or(true, |encoding| encoding.eq_ignore_ascii_case("utf-8"));
+        if self.is_text() && utf8 {
+            self.as_text()
+        } else {
+            None
+        }
+    }
+
     /// Parse content as JSON
     pub fn parse_json<T: for<'de> Deserialize<'de>>(&self) -> Result<T> {
         serde_json::from_slice(&self.data).map_err(|e| MycelialError::Serialization(e.to_string()))
     }
+
+    /// Encrypt raw data under `key` with ChaCha20-Poly1305
+    ///
+    /// The data decrypts as `application/octet-stream`; use
+    /// [`encrypt`](Self::encrypt) to keep a content type.
+    pub fn encrypted(data: &[u8], key: &ContentKey) -> EncryptedContent {
+        Self::new(data.to_vec(), "application/octet-stream").encrypt(key)
+    }
+
+    /// Encrypt this content under `key` with ChaCha20-Poly1305
+    ///
+    /// The content type, MIME type and encoding are sealed along with the
+    /// data and restored by [`EncryptedContent::decrypt`].
+    pub fn encrypt(&self, key: &ContentKey) -> EncryptedContent {
+        let header = EnvelopeHeader {
+            content_type: self.content_type.clone(),
+            mime_type: self.metadata.mime_type.clone(),
+            encoding: self.metadata.encoding.clone(),
+        };
+        let header = serde_json::to_vec(&header).expect("envelope header serializes");
+
+        let mut envelope = Vec::with_capacity(1 + 4 + header.len() + self.data.len());
+        envelope.push(ENVELOPE_VERSION);
+        envelope.extend_from_slice(&(header.len() as u32).to_be_bytes());
+        envelope.extend_from_slice(&header);
+        envelope.extend_from_slice(&self.data);
+
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut nonce);
+
+        let ciphertext = key
+            .cipher()
+            .encrypt(Nonce::from_slice(&nonce), envelope.as_slice())
+            .expect("ChaCha20-Poly1305 encryption is infallible for in-memory data");
+
+        let mut content = Self::new(ciphertext, ENCRYPTED_CONTENT_TYPE);
+        content.metadata.nonce = Some(nonce);
+        EncryptedContent { content }
+    }
+}
+
+/// Content type of encrypted content
+pub const ENCRYPTED_CONTENT_TYPE: &str = "application/x-mycelial-encrypted";
+
+/// ChaCha20-Poly1305 nonce length
+const NONCE_LEN: usize = 12;
+
+/// Current version of the plaintext envelope sealed by [`Content::encrypt`]
+///
+/// `version (1) | header length (4, big endian) | JSON header | data`
+const ENVELOPE_VERSION: u8 = 1;
+
+/// What [`Content::encrypt`] seals alongside the data
+#[derive(Serialize, Deserialize)]
+struct EnvelopeHeader {
+    content_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mime_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    encoding: Option<String>,
+}
+
+/// A symmetric key for encrypting content at rest
+#[derive(Clone, PartialEq, Eq)]
+pub struct ContentKey([u8; 32]);
+
+impl ContentKey {
+    /// Generate a random key
+    pub fn generate() -> Self {
+        let mut key = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut key);
+        Self(key)
+    }
+
+    /// Create a key from raw bytes
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Get the raw bytes
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+
+    fn cipher(&self) -> ChaCha20Poly1305 {
+        ChaCha20Poly1305::new(Key::from_slice(&self.0))
+    }
+}
+
+impl fmt::Debug for ContentKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ContentKey(..)")
+    }
+}
+
+/// Encrypted content, addressed by the hash of its ciphertext
+///
+/// The wrapped [`Content`] holds the ciphertext and carries the nonce in
+/// its metadata, so it can be stored and provided like any other content.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedContent {
+    content: Content,
+}
+
+impl EncryptedContent {
+    /// Wrap content produced by [`Content::encrypted`], e.g. after fetching
+    /// it from another peer
+    pub fn from_content(content: Content) -> Result<Self> {
+        if content.content_type != ENCRYPTED_CONTENT_TYPE {
+            return Err(MycelialError::InvalidContentType(content.content_type));
+        }
+        if content.metadata.nonce.is_none() {
+            return Err(MycelialError::DecryptionFailed("missing nonce".into()));
+        }
+        Ok(Self { content })
+    }
+
+    /// The content ID (hash of the ciphertext)
+    pub fn id(&self) -> ContentId {
+        self.content.id
+    }
+
+    /// The ciphertext as storable content
+    pub fn content(&self) -> &Content {
+        &self.content
+    }
+
+    /// Unwrap into storable content
+    pub fn into_content(self) -> Content {
+        self.content
+    }
+
+    /// Decrypt with `key`
+    ///
+    /// Fails if the key is wrong or the ciphertext was tampered with.
+    pub fn decrypt(&self, key: &ContentKey) -> Result<Content> {
+        let nonce = self
+            .content
+            .metadata
+            .nonce
+            .ok_or_else(|| MycelialError::DecryptionFailed("missing nonce".into()))?;
+
+        let envelope = key
+            .cipher()
+            .decrypt(Nonce::from_slice(&nonce), self.content.data.as_slice())
+            .map_err(|_| MycelialError::DecryptionFailed("authentication failed".into()))?;
+
+        let malformed = || MycelialError::Deserialization("malformed encrypted envelope".into());
+        if envelope.first() != Some(&ENVELOPE_VERSION) {
+            return Err(malformed());
+        }
+        let header_len = envelope
+            .get(1..5)
+            .map(|len| u32::from_be_bytes(len.try_into().expect("4-byte slice")) as usize)
+            .ok_or_else(malformed)?;
+        let body = header_len
+            .checked_add(5)
+            .filter(|&body| body <= envelope.len())
+            .ok_or_else(malformed)?;
+        let header: EnvelopeHeader = serde_json::from_slice(&envelope[5..body])
+            .map_err(|e| MycelialError::Deserialization(e.to_string()))?;
+
+        let mut content = Content::new(envelope[body..].to_vec(), header.content_type);
+        content.metadata.mime_type = header.mime_type;
+        content.metadata.encoding = header.encoding;
+        Ok(content)
+    }
 }
 
 /// Metadata associated with content
@@ -157,6 +426,15 @@ pub struct ContentMetadata {
     pub size: Option<u64>,
     /// Creation timestamp
     pub created: Option<chrono::DateTime<chrono::Utc>>,
+    /// Media type of the data (e.g. `text/plain`, `image/png`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    /// Character encoding of text data (e.g. `utf-8`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+    /// Nonce used to encrypt the data, if encrypted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<[u8; NONCE_LEN]>,
 }
 
 impl ContentMetadata {
@@ -300,6 +578,36 @@ mod tests {
         assert_eq!(id, recovered);
     }
 
+    #[test]
+    fn test_content_id_canonical_string() {
+        let id = ContentId::hash(b"Test data");
+
+        let canonical = id.to_string();
+        assert!(canonical.starts_with('z'));
+        assert_eq!(canonical.parse::<ContentId>().unwrap(), id);
+        assert_eq!(ContentId::from_multihash(&id.to_multihash()).unwrap(), id);
+
+        // Raw digests, other hash functions and other bases are rejected
+        assert!(id.to_base58().parse::<ContentId>().is_err());
+        let mut sha256 = id.to_multihash();
+        sha256[0] = 0x12;
+        let sha256 = multibase::encode(multibase::Base::Base58Btc, sha256);
+        assert!(sha256.parse::<ContentId>().is_err());
+        let hex = multibase::encode(multibase::Base::Base16Lower, id.to_multihash());
+        assert!(hex.parse::<ContentId>().is_err());
+        assert!(ContentId::from_slice(&[0u8; 31]).is_err());
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let good = ContentId::hash(b"one");
+        let other = ContentId::hash(b"two");
+        let items: Vec<(ContentId, &[u8])> = vec![(good, b"one"), (other, b"one"), (other, b"two")];
+
+        assert_eq!(verify_batch(&items), vec![true, false, true]);
+        assert!(verify_batch(&[]).is_empty());
+    }
+
     #[test]
     fn test_content_creation() {
         let content = Content::text("Hello, Mycelial!");
@@ -307,6 +615,60 @@ mod tests {
         assert_eq!(content.as_text(), Some("Hello, Mycelial!"));
     }
 
+    #[test]
+    fn test_content_mime_type() {
+        let text = Content::text("hello");
+        assert_eq!(text.metadata.mime_type.as_deref(), Some("text/plain"));
+        assert!(text.is_text());
+        assert_eq!(text.as_str(), Some("hello"));
+
+        let image = Content::bytes(vec![0x89, b'P', b'N', b'G'], "image/png");
+        assert_eq!(image.mime_type(), "image/png");
+        assert!(!image.is_text());
+        assert_eq!(image.as_str(), None);
+    }
+
+    #[test]
+    fn test_encrypted_content_round_trip() {
+        let key = ContentKey::generate();
+        let encrypted = Content::encrypted(b"secret notes", &key);
+
+        // Addressed by the ciphertext, not the plaintext
+        assert!(encrypted.content().verify());
+        assert_ne!(encrypted.id(), ContentId::hash(b"secret notes"));
+        assert_ne!(encrypted.content().data, b"secret notes");
+
+        let restored = EncryptedContent::from_content(encrypted.into_content()).unwrap();
+        let content = restored.decrypt(&key).unwrap();
+        assert_eq!(content.data, b"secret notes");
+        assert_eq!(content.content_type, "application/octet-stream");
+        assert!(content.verify());
+    }
+
+    #[test]
+    fn test_encrypted_content_keeps_type() {
+        let key = ContentKey::generate();
+        let encrypted = Content::text("secret notes").encrypt(&key);
+        assert_eq!(encrypted.content().content_type, ENCRYPTED_CONTENT_TYPE);
+        assert!(encrypted.content().metadata.mime_type.is_none());
+
+        let content = encrypted.decrypt(&key).unwrap();
+        assert_eq!(content.as_str(), Some("secret notes"));
+        assert_eq!(content.content_type, "text/plain");
+        assert_eq!(content.metadata.encoding.as_deref(), Some("utf-8"));
+    }
+
+    #[test]
+    fn test_encrypted_content_wrong_key_fails() {
+        let encrypted = Content::encrypted(b"secret notes", &ContentKey::generate());
+
+        assert!(matches!(
+            encrypted.decrypt(&ContentKey::generate()),
+            Err(MycelialError::DecryptionFailed(_))
+        ));
+        assert!(EncryptedContent::from_content(Content::text("plain")).is_err());
+    }
+
     #[test]
     fn test_merkle_tree() {
         let mut builder = MerkleTreeBuilder::new(64);
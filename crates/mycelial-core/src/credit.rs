@@ -3,6 +3,13 @@
 use crate::peer::PeerId;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Length of the year interest rates are quoted over (365.25 days)
+const SECONDS_PER_YEAR: f64 = 365.25 * 24.0 * 60.0 * 60.0;
+
+/// Most entries kept in a credit history; older ones are dropped first
+pub const MAX_CREDIT_HISTORY: usize = 1000;
 
 /// A credit relationship between two peers
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +28,80 @@ pub struct CreditRelationship {
     pub last_transaction: DateTime<Utc>,
     /// Whether the relationship is active
     pub active: bool,
+    /// Annual interest rate charged on a positive balance (0.0 = no interest)
+    #[serde(default)]
+    pub interest_rate: f64,
+    /// Log of transfers and interest, oldest first, holding at most the
+    /// last [`MAX_CREDIT_HISTORY`] entries
+    #[serde(default)]
+    pub history: Vec<CreditEntry>,
+}
+
+/// One change to the balance of a credit relationship
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreditEntry {
+    /// When the change was applied
+    pub timestamp: DateTime<Utc>,
+    /// What caused the change
+    #[serde(default)]
+    pub kind: CreditEntryKind,
+    /// Amount added to the balance (positive = creditor gives to debtor)
+    pub amount: f64,
+    /// Balance after the transfer
+    pub balance: f64,
+    /// Memo attached to the transfer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// What caused a [`CreditEntry`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CreditEntryKind {
+    /// Credit moved between the peers
+    #[default]
+    Transfer,
+    /// Interest accrued on the outstanding balance
+    Interest,
+}
+
+/// Interest accrued on `balance` at effective annual `rate` over `elapsed`
+///
+/// A year grows the balance by exactly `rate`; shorter and longer periods
+/// compound at the same effective rate, so accruing a period in pieces
+/// charges the same as accruing it at once.
+///
+/// Only a positive balance (the debtor owes the creditor) accrues interest;
+/// zero, negative and non-finite rates accrue nothing, so the balance can
+/// never change sign.
+pub fn compound_interest(balance: f64, rate: f64, elapsed: Duration) -> f64 {
+    if balance <= 0.0 || !rate.is_finite() || rate <= 0.0 {
+        return 0.0;
+    }
+    let years = elapsed.as_secs_f64() / SECONDS_PER_YEAR;
+    balance * ((1.0 + rate).powf(years) - 1.0)
+}
+
+/// Append `entry` to a credit history, dropping the oldest entries beyond
+/// [`MAX_CREDIT_HISTORY`]
+pub fn record_entry(history: &mut Vec<CreditEntry>, entry: CreditEntry) {
+    history.push(entry);
+    if history.len() > MAX_CREDIT_HISTORY {
+        history.drain(..history.len() - MAX_CREDIT_HISTORY);
+    }
+}
+
+/// Entries of a credit history made between `from` and `to` (inclusive)
+pub fn statement(
+    history: &[CreditEntry],
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Vec<CreditEntry> {
+    history
+        .iter()
+        .filter(|entry| entry.timestamp >= from && entry.timestamp <= to)
+        .cloned()
+        .collect()
 }
 
 impl CreditRelationship {
@@ -35,9 +116,17 @@ impl CreditRelationship {
             established: now,
             last_transaction: now,
             active: true,
+            interest_rate: 0.0,
+            history: Vec::new(),
         }
     }
 
+    /// Charge `rate` annual interest on the outstanding balance
+    pub fn with_interest_rate(mut self, rate: f64) -> Self {
+        self.interest_rate = rate;
+        self
+    }
+
     /// Available credit for the debtor
     pub fn available_credit(&self) -> f64 {
         if !self.active {
@@ -48,6 +137,15 @@ impl CreditRelationship {
 
     /// Transfer credit (positive amount = creditor gives to debtor)
     pub fn transfer(&mut self, amount: f64) -> Result<(), CreditError> {
+        self.transfer_with_memo(amount, None)
+    }
+
+    /// Transfer credit, recording `memo` in the history
+    pub fn transfer_with_memo(
+        &mut self,
+        amount: f64,
+        memo: Option<String>,
+    ) -> Result<(), CreditError> {
         if !self.active {
             return Err(CreditError::InactiveRelationship);
         }
@@ -63,8 +161,62 @@ impl CreditRelationship {
 
         self.balance = new_balance;
         self.last_transaction = Utc::now();
+        record_entry(
+            &mut self.history,
+            CreditEntry {
+                timestamp: self.last_transaction,
+                kind: CreditEntryKind::Transfer,
+                amount,
+                balance: new_balance,
+                memo,
+            },
+        );
         Ok(())
     }
+
+    /// Compound interest on the outstanding balance for `elapsed`
+    ///
+    /// Records an interest entry and returns the interest added. Inactive
+    /// and zero-rate relationships, and balances the debtor doesn't owe,
+    /// accrue nothing. Interest may take the balance past the credit limit.
+    pub fn accrue_interest(&mut self, elapsed: Duration) -> f64 {
+        if !self.active {
+            return 0.0;
+        }
+        let interest = compound_interest(self.balance, self.interest_rate, elapsed);
+        if interest <= 0.0 {
+            return 0.0;
+        }
+
+        self.balance += interest;
+        record_entry(
+            &mut self.history,
+            CreditEntry {
+                timestamp: Utc::now(),
+                kind: CreditEntryKind::Interest,
+                amount: interest,
+                balance: self.balance,
+                memo: None,
+            },
+        );
+        interest
+    }
+
+    /// Total interest accrued over the retained history
+    pub fn accrued_interest(&self) -> f64 {
+        self.history
+            .iter()
+            .filter(|entry| entry.kind == CreditEntryKind::Interest)
+            .map(|entry| entry.amount)
+            .sum()
+    }
+
+    /// Transfers made between `from` and `to` (inclusive), oldest first
+    ///
+    /// Each entry carries the running balance after it was applied.
+    pub fn statement(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<CreditEntry> {
+        statement(&self.history, from, to)
+    }
 }
 
 /// Errors related to credit operations
@@ -80,11 +232,12 @@ pub enum CreditError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::identity::Keypair;
 
     #[test]
     fn test_credit_transfer() {
-        let creditor = PeerId("creditor".to_string());
-        let debtor = PeerId("debtor".to_string());
+        let creditor = PeerId::from(Keypair::generate().public_key());
+        let debtor = PeerId::from(Keypair::generate().public_key());
         let mut rel = CreditRelationship::new(creditor, debtor, 100.0);
 
         assert_eq!(rel.available_credit(), 100.0);
@@ -96,4 +249,65 @@ mod tests {
         // Should fail - exceeds limit
         assert!(rel.transfer(60.0).is_err());
     }
+
+    #[test]
+    fn test_credit_statement() {
+        let creditor = PeerId::from(Keypair::generate().public_key());
+        let debtor = PeerId::from(Keypair::generate().public_key());
+        let mut rel = CreditRelationship::new(creditor, debtor, 100.0);
+        let start = Utc::now();
+
+        rel.transfer_with_memo(40.0, Some("groceries".into()))
+            .unwrap();
+        rel.transfer(-15.0).unwrap();
+        // Rejected transfers leave no entry
+        assert!(rel.transfer(90.0).is_err());
+
+        let entries = rel.statement(start, Utc::now());
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].memo.as_deref(), Some("groceries"));
+        assert_eq!(entries[0].balance, 40.0);
+        assert_eq!(entries[1].amount, -15.0);
+        assert_eq!(entries[1].balance, 25.0);
+
+        let before = start - chrono::Duration::hours(1);
+        assert!(rel.statement(before, before).is_empty());
+
+        // Only the most recent entries are kept
+        for _ in 0..MAX_CREDIT_HISTORY {
+            rel.transfer(0.01).unwrap();
+        }
+        assert_eq!(rel.history.len(), MAX_CREDIT_HISTORY);
+        assert!(rel.history.iter().all(|entry| entry.memo.is_none()));
+    }
+
+    #[test]
+    fn test_accrue_interest() {
+        let creditor = PeerId::from(Keypair::generate().public_key());
+        let debtor = PeerId::from(Keypair::generate().public_key());
+        let year = Duration::from_secs_f64(SECONDS_PER_YEAR);
+
+        let mut rel = CreditRelationship::new(creditor.clone(), debtor.clone(), 1000.0)
+            .with_interest_rate(0.1);
+        rel.transfer(100.0).unwrap();
+
+        // Two half years compound to the same as one full year
+        let first = rel.accrue_interest(year / 2);
+        let second = rel.accrue_interest(year / 2);
+        assert!(first > 0.0 && second > first);
+        assert!((rel.balance - 110.0).abs() < 1e-9);
+        assert!((rel.accrued_interest() - 10.0).abs() < 1e-9);
+        assert_eq!(rel.history.last().unwrap().kind, CreditEntryKind::Interest);
+
+        // Zero-rate lines and balances owed to the debtor accrue nothing
+        let mut free = CreditRelationship::new(creditor.clone(), debtor.clone(), 1000.0);
+        free.transfer(100.0).unwrap();
+        assert_eq!(free.accrue_interest(year), 0.0);
+        assert_eq!(free.history.len(), 1);
+
+        let mut owed = CreditRelationship::new(creditor, debtor, 1000.0).with_interest_rate(0.1);
+        owed.transfer(-50.0).unwrap();
+        assert_eq!(owed.accrue_interest(year), 0.0);
+        assert_eq!(owed.balance, -50.0);
+    }
 }
@@ -30,6 +30,10 @@ pub enum MycelialError {
     #[error("Peer not found: {0}")]
     PeerNotFound(String),
 
+    /// Malformed peer ID
+    #[error("Invalid peer ID: {0}")]
+    InvalidPeerId(String),
+
     /// Connection to peer failed
     #[error("Connection failed to peer {peer}: {reason}")]
     ConnectionFailed { peer: String, reason: String },
@@ -63,6 +67,10 @@ pub enum MycelialError {
     #[error("Invalid content type: {0}")]
     InvalidContentType(String),
 
+    /// Decrypting content failed (wrong key or tampered ciphertext)
+    #[error("Decryption failed: {0}")]
+    DecryptionFailed(String),
+
     // ===== Credit & Economics Errors =====
     /// Insufficient credit for operation
     #[error("Insufficient credit: required {required}, available {available}")]
@@ -130,6 +138,17 @@ pub enum MycelialError {
         to: crate::module::ModuleState,
     },
 
+    /// Module took too long to reply to a request
+    #[error("Module {module} did not reply within {duration_ms}ms")]
+    ModuleRequestTimeout { module: String, duration_ms: u64 },
+
+    /// Module does not reply to this kind of request
+    #[error("Module {module} does not reply to '{message_type}' requests")]
+    NoModuleReply {
+        module: String,
+        message_type: String,
+    },
+
     // ===== Governance Errors =====
     /// Proposal not found
     #[error("Proposal not found: {0}")]
@@ -184,6 +203,7 @@ impl MycelialError {
         matches!(
             self,
             MycelialError::Timeout { .. }
+                | MycelialError::ModuleRequestTimeout { .. }
                 | MycelialError::ConnectionFailed { .. }
                 | MycelialError::RateLimited { .. }
         )
@@ -196,6 +216,7 @@ impl MycelialError {
             MycelialError::InvalidSignature
                 | MycelialError::InvalidPublicKey(_)
                 | MycelialError::InvalidDid(_)
+                | MycelialError::InvalidPeerId(_)
                 | MycelialError::InvalidMessageFormat(_)
                 | MycelialError::InvalidConfig(_)
                 | MycelialError::InvalidContentType(_)
@@ -211,6 +232,7 @@ impl MycelialError {
             MycelialError::InvalidDid(_) => "INVALID_DID",
             MycelialError::KeyGenerationFailed(_) => "KEY_GENERATION_FAILED",
             MycelialError::PeerNotFound(_) => "PEER_NOT_FOUND",
+            MycelialError::InvalidPeerId(_) => "INVALID_PEER_ID",
             MycelialError::ConnectionFailed { .. } => "CONNECTION_FAILED",
             MycelialError::Timeout { .. } => "TIMEOUT",
             MycelialError::MaxConnectionsReached { .. } => "MAX_CONNECTIONS",
@@ -219,6 +241,7 @@ impl MycelialError {
             MycelialError::ContentVerificationFailed { .. } => "CONTENT_VERIFICATION_FAILED",
             MycelialError::ContentTooLarge { .. } => "CONTENT_TOO_LARGE",
             MycelialError::InvalidContentType(_) => "INVALID_CONTENT_TYPE",
+            MycelialError::DecryptionFailed(_) => "DECRYPTION_FAILED",
             MycelialError::InsufficientCredit { .. } => "INSUFFICIENT_CREDIT",
             MycelialError::CreditRelationshipNotFound { .. } => "CREDIT_RELATIONSHIP_NOT_FOUND",
             MycelialError::CreditLimitExceeded { .. } => "CREDIT_LIMIT_EXCEEDED",
@@ -234,6 +257,8 @@ impl MycelialError {
             MycelialError::ModuleInitFailed { .. } => "MODULE_INIT_FAILED",
             MycelialError::ModuleNotRunning(_) => "MODULE_NOT_RUNNING",
             MycelialError::InvalidModuleStateTransition { .. } => "INVALID_MODULE_STATE",
+            MycelialError::ModuleRequestTimeout { .. } => "MODULE_REQUEST_TIMEOUT",
+            MycelialError::NoModuleReply { .. } => "NO_MODULE_REPLY",
             MycelialError::ProposalNotFound(_) => "PROPOSAL_NOT_FOUND",
             MycelialError::VotingPeriodEnded(_) => "VOTING_PERIOD_ENDED",
             MycelialError::AlreadyVoted(_) => "ALREADY_VOTED",
@@ -356,10 +356,11 @@ impl EventFilter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::identity::Keypair;
 
     #[test]
     fn test_event_creation() {
-        let peer = PeerId("test-peer".to_string());
+        let peer = PeerId::from(Keypair::generate().public_key());
         let event = Event::system(
             peer.clone(),
             SystemEvent::PeerJoined {
@@ -374,7 +375,7 @@ mod tests {
 
     #[test]
     fn test_event_filter() {
-        let peer = PeerId("test-peer".to_string());
+        let peer = PeerId::from(Keypair::generate().public_key());
         let event = Event::content(
             peer.clone(),
             ContentEvent::Published {
@@ -0,0 +1,126 @@
+//! Dispatching messages to handlers by message type
+//!
+//! A [`HandlerRegistry`] lets each module register only for the message
+//! types it cares about, instead of one handler matching on every type.
+
+use crate::message::{Message, MessageType};
+use crate::peer::PeerId;
+use crate::{MessageHandler, Result};
+use std::collections::HashMap;
+
+/// Registry routing messages to handlers by [`MessageType`]
+pub struct HandlerRegistry {
+    handlers: HashMap<MessageType, Vec<Box<dyn MessageHandler>>>,
+}
+
+impl HandlerRegistry {
+    /// Create a new empty registry
+    pub fn new() -> Self {
+        Self {
+            handlers: HashMap::new(),
+        }
+    }
+
+    /// Register a handler for one message type
+    ///
+    /// Several handlers may share a type; they run in registration order.
+    pub fn register(&mut self, message_type: MessageType, handler: Box<dyn MessageHandler>) {
+        self.handlers.entry(message_type).or_default().push(handler);
+    }
+
+    /// Whether any handler is registered for a message type
+    pub fn handles(&self, message_type: MessageType) -> bool {
+        self.handlers.contains_key(&message_type)
+    }
+
+    /// Number of handlers registered for a message type
+    pub fn handler_count(&self, message_type: MessageType) -> usize {
+        self.handlers.get(&message_type).map_or(0, Vec::len)
+    }
+
+    /// Route a message to every handler registered for its type
+    ///
+    /// Returns the responses the handlers produced, in registration order.
+    /// Messages of a type nobody registered for yield no responses.
+    pub async fn dispatch(&self, message: Message, from: PeerId) -> Result<Vec<Message>> {
+        let Some(handlers) = self.handlers.get(&message.message_type) else {
+            return Ok(Vec::new());
+        };
+
+        let mut responses = Vec::new();
+        for handler in handlers {
+            if let Some(response) = handler.handle(message.clone(), from.clone()).await? {
+                responses.push(response);
+            }
+        }
+        Ok(responses)
+    }
+}
+
+impl Default for HandlerRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identity::Keypair;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Counts calls and echoes the payload back
+    struct Echo(Arc<AtomicUsize>);
+
+    #[async_trait]
+    impl MessageHandler for Echo {
+        async fn handle(&self, message: Message, from: PeerId) -> Result<Option<Message>> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(Some(Message::direct(message.sender, from, message.payload)))
+        }
+    }
+
+    /// Counts calls without responding
+    struct Silent(Arc<AtomicUsize>);
+
+    #[async_trait]
+    impl MessageHandler for Silent {
+        async fn handle(&self, _message: Message, _from: PeerId) -> Result<Option<Message>> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(None)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_routes_by_message_type() {
+        let credit_calls = Arc::new(AtomicUsize::new(0));
+        let governance_calls = Arc::new(AtomicUsize::new(0));
+
+        let mut registry = HandlerRegistry::new();
+        registry.register(MessageType::Credit, Box::new(Echo(credit_calls.clone())));
+        registry.register(MessageType::Credit, Box::new(Silent(credit_calls.clone())));
+        registry.register(
+            MessageType::Governance,
+            Box::new(Silent(governance_calls.clone())),
+        );
+        assert_eq!(registry.handler_count(MessageType::Credit), 2);
+        assert!(!registry.handles(MessageType::Content));
+
+        let sender = PeerId::from(Keypair::generate().public_key());
+        let from = PeerId::from(Keypair::generate().public_key());
+        let credit = Message::new(MessageType::Credit, sender.clone(), b"100".to_vec());
+
+        let responses = registry.dispatch(credit, from.clone()).await.unwrap();
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0].payload, b"100");
+        assert_eq!(credit_calls.load(Ordering::SeqCst), 2);
+        assert_eq!(governance_calls.load(Ordering::SeqCst), 0);
+
+        // Unhandled types are ignored
+        let content = Message::new(MessageType::Content, sender, vec![]);
+        assert!(registry.dispatch(content, from).await.unwrap().is_empty());
+        assert_eq!(credit_calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,120 @@
+//! Health checks for node subsystems
+//!
+//! Each subsystem (network, storage, LoRa bridge, ...) implements
+//! [`HealthCheck`] to say whether it is working. A [`HealthReport`] gathers
+//! their answers into one overall level, which orchestrators can use for
+//! readiness and liveness probes.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// How well something is working, from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthLevel {
+    /// Working normally
+    Ok,
+    /// Working, but impaired
+    Degraded,
+    /// Not working
+    Down,
+}
+
+/// Health of one subsystem, with the reason if it is not ok
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", content = "detail", rename_all = "snake_case")]
+pub enum HealthStatus {
+    /// Working normally
+    Ok,
+    /// Working, but impaired
+    Degraded(String),
+    /// Not working
+    Down(String),
+}
+
+impl HealthStatus {
+    /// Working, but impaired for `reason`
+    pub fn degraded(reason: impl Into<String>) -> Self {
+        HealthStatus::Degraded(reason.into())
+    }
+
+    /// Not working because of `reason`
+    pub fn down(reason: impl Into<String>) -> Self {
+        HealthStatus::Down(reason.into())
+    }
+
+    /// The level, without the reason
+    pub fn level(&self) -> HealthLevel {
+        match self {
+            HealthStatus::Ok => HealthLevel::Ok,
+            HealthStatus::Degraded(_) => HealthLevel::Degraded,
+            HealthStatus::Down(_) => HealthLevel::Down,
+        }
+    }
+
+    /// Whether the subsystem is working normally
+    pub fn is_ok(&self) -> bool {
+        self.level() == HealthLevel::Ok
+    }
+}
+
+/// Something that can report whether it is working
+#[async_trait]
+pub trait HealthCheck: Send + Sync {
+    /// Check the subsystem's health
+    ///
+    /// Should answer quickly: a check that cannot tell in a second or two
+    /// should report itself degraded rather than wait.
+    async fn health(&self) -> HealthStatus;
+}
+
+/// Health of every subsystem and of the whole
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthReport {
+    /// The worst level among the components
+    pub status: HealthLevel,
+    /// Health of each subsystem, by name
+    pub components: BTreeMap<String, HealthStatus>,
+}
+
+impl HealthReport {
+    /// Build a report from named component statuses
+    ///
+    /// With no components the whole is ok.
+    pub fn new(components: impl IntoIterator<Item = (String, HealthStatus)>) -> Self {
+        let components: BTreeMap<_, _> = components.into_iter().collect();
+        let status = components
+            .values()
+            .map(HealthStatus::level)
+            .max()
+            .unwrap_or(HealthLevel::Ok);
+        Self { status, components }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_takes_worst_level() {
+        let report = HealthReport::new([
+            ("network".to_string(), HealthStatus::degraded("no peers")),
+            ("storage".to_string(), HealthStatus::Ok),
+        ]);
+        assert_eq!(report.status, HealthLevel::Degraded);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["status"], "degraded");
+        assert_eq!(json["components"]["network"]["detail"], "no peers");
+        assert_eq!(json["components"]["storage"]["status"], "ok");
+
+        let report = HealthReport::new([
+            ("network".to_string(), HealthStatus::Ok),
+            ("bridge".to_string(), HealthStatus::down("device gone")),
+        ]);
+        assert_eq!(report.status, HealthLevel::Down);
+        assert_eq!(HealthReport::new([]).status, HealthLevel::Ok);
+    }
+}
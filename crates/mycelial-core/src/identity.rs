@@ -15,7 +15,24 @@
 //! - [`Did`]: Decentralized Identifier (did:key method)
 //! - [`Signed<T>`]: Cryptographically signed data wrapper
 //! - [`SignatureBytes`]: Legacy signature format for backward compatibility
-
+//!
+//! ## Mnemonic Recovery
+//!
+//! A keypair can be derived from a BIP39 mnemonic phrase so an identity can
+//! be written down and restored. The phrase and an optional passphrase give
+//! a BIP39 seed, and the Ed25519 secret is the SLIP-0010 master key of that
+//! seed.
+//!
+//! ## Key Files
+//!
+//! [`KeypairExt::save_encrypted`] writes a keypair to disk encrypted under a
+//! passphrase: Argon2id stretches the passphrase into a ChaCha20-Poly1305
+//! key, and the KDF parameters are stored alongside the ciphertext.
+
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    ChaCha20Poly1305, Key, Nonce,
+};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -372,6 +389,215 @@ impl PublicKeyExt for PublicKey {
     }
 }
 
+/// Number of words in a generated mnemonic (256 bits of entropy)
+pub const MNEMONIC_WORDS: usize = 24;
+
+/// Generate a new random English BIP39 mnemonic of [`MNEMONIC_WORDS`] words
+pub fn generate_mnemonic() -> String {
+    use rand::RngCore;
+
+    let mut entropy = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut entropy);
+    bip39::Mnemonic::from_entropy(&entropy)
+        .expect("32 bytes is a valid BIP39 entropy length")
+        .to_string()
+}
+
+/// Derive the Ed25519 secret key bytes for a mnemonic
+///
+/// Fails if the phrase has an unknown word, a bad word count or a bad
+/// checksum. Extra whitespace between words is ignored.
+pub fn mnemonic_secret(phrase: &str, passphrase: &str) -> Result<[u8; 32]> {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha512;
+
+    let mnemonic = bip39::Mnemonic::parse_in(bip39::Language::English, phrase)
+        .map_err(|e| MycelialError::KeyGenerationFailed(format!("Invalid mnemonic: {}", e)))?;
+    let seed = mnemonic.to_seed(passphrase);
+
+    // SLIP-0010 master key for the ed25519 curve
+    let mut mac =
+        Hmac::<Sha512>::new_from_slice(b"ed25519 seed").expect("HMAC accepts keys of any length");
+    mac.update(&seed);
+    let digest = mac.finalize().into_bytes();
+
+    let mut secret = [0u8; 32];
+    secret.copy_from_slice(&digest[..32]);
+    Ok(secret)
+}
+
+/// Current version of the encrypted key file format
+const KEY_FILE_VERSION: u32 = 1;
+
+/// Largest Argon2id memory cost accepted from a key file, in KiB (1 GiB)
+const MAX_KEY_FILE_M_COST: u32 = 1 << 20;
+
+/// Most Argon2id iterations accepted from a key file
+const MAX_KEY_FILE_T_COST: u32 = 64;
+
+/// Most Argon2id lanes accepted from a key file
+const MAX_KEY_FILE_P_COST: u32 = 16;
+
+/// An encrypted keypair as stored on disk
+#[derive(Serialize, Deserialize)]
+struct EncryptedKeyFile {
+    version: u32,
+    /// Argon2id memory cost in KiB
+    m_cost: u32,
+    /// Argon2id iterations
+    t_cost: u32,
+    /// Argon2id parallelism
+    p_cost: u32,
+    /// Hex-encoded KDF salt
+    salt: String,
+    /// Hex-encoded ChaCha20-Poly1305 nonce
+    nonce: String,
+    /// Hex-encoded encrypted secret key
+    ciphertext: String,
+}
+
+impl EncryptedKeyFile {
+    /// Encrypt `secret` under `passphrase` with fresh salt and nonce
+    fn seal(secret: &[u8; 32], passphrase: &str) -> Result<Self> {
+        use rand::RngCore;
+
+        let mut salt = [0u8; 16];
+        let mut nonce = [0u8; 12];
+        rand::thread_rng().fill_bytes(&mut salt);
+        rand::thread_rng().fill_bytes(&mut nonce);
+
+        let params = argon2::Params::default();
+        let cipher = key_file_cipher(
+            passphrase,
+            &salt,
+            params.m_cost(),
+            params.t_cost(),
+            params.p_cost(),
+        )?;
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), secret.as_slice())
+            .expect("ChaCha20-Poly1305 encryption is infallible for in-memory data");
+
+        Ok(Self {
+            version: KEY_FILE_VERSION,
+            m_cost: params.m_cost(),
+            t_cost: params.t_cost(),
+            p_cost: params.p_cost(),
+            salt: hex::encode(salt),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    /// Decrypt the secret key with `passphrase`
+    fn open(&self, passphrase: &str) -> Result<Vec<u8>> {
+        if self.version != KEY_FILE_VERSION {
+            return Err(MycelialError::Deserialization(format!(
+                "unsupported key file version {}",
+                self.version
+            )));
+        }
+        let decode = |field: &str| {
+            hex::decode(field).map_err(|e| MycelialError::Deserialization(e.to_string()))
+        };
+        let salt = decode(&self.salt)?;
+        let nonce = decode(&self.nonce)?;
+        if nonce.len() != 12 {
+            return Err(MycelialError::Deserialization(
+                "invalid nonce length".into(),
+            ));
+        }
+        // The costs come from the file, so a crafted one could stall startup
+        if self.m_cost > MAX_KEY_FILE_M_COST
+            || self.t_cost > MAX_KEY_FILE_T_COST
+            || self.p_cost > MAX_KEY_FILE_P_COST
+        {
+            return Err(MycelialError::Deserialization(format!(
+                "key file KDF cost too high (m={}, t={}, p={})",
+                self.m_cost, self.t_cost, self.p_cost
+            )));
+        }
+
+        let cipher = key_file_cipher(passphrase, &salt, self.m_cost, self.t_cost, self.p_cost)?;
+        cipher
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                decode(&self.ciphertext)?.as_slice(),
+            )
+            .map_err(|_| {
+                MycelialError::DecryptionFailed("wrong passphrase or corrupted key file".into())
+            })
+    }
+}
+
+/// Stretch `passphrase` into the key file cipher with Argon2id
+fn key_file_cipher(
+    passphrase: &str,
+    salt: &[u8],
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+) -> Result<ChaCha20Poly1305> {
+    let params = argon2::Params::new(m_cost, t_cost, p_cost, Some(32))
+        .map_err(|e| MycelialError::InvalidConfig(format!("Argon2 parameters: {}", e)))?;
+    let mut key = [0u8; 32];
+    argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| MycelialError::KeyGenerationFailed(e.to_string()))?;
+    Ok(ChaCha20Poly1305::new(Key::from_slice(&key)))
+}
+
+/// Write a new file readable only by its owner (mode 0600 on Unix)
+///
+/// Fails if `path` already exists, so a secret is never silently replaced.
+pub fn write_secret_file(path: &std::path::Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(contents)
+}
+
+/// Verify many signatures at once
+///
+/// Returns the index of the first item whose signature doesn't verify. With
+/// the `batch` feature the whole set is checked in one Ed25519 batch
+/// verification, which is much faster for large batches; only when that
+/// fails are the items checked one by one to find the culprit. Without the
+/// feature every item is verified sequentially.
+pub fn batch_verify(items: &[(PublicKey, &[u8], Signature)]) -> std::result::Result<(), usize> {
+    #[cfg(feature = "batch")]
+    {
+        let keys: std::result::Result<Vec<_>, _> = items
+            .iter()
+            .map(|(key, _, _)| ed25519_dalek::VerifyingKey::from_bytes(key.as_bytes()))
+            .collect();
+        if let Ok(keys) = keys {
+            let messages: Vec<&[u8]> = items.iter().map(|(_, message, _)| *message).collect();
+            let signatures: Vec<ed25519_dalek::Signature> = items
+                .iter()
+                .map(|(_, _, sig)| ed25519_dalek::Signature::from_bytes(&sig.to_bytes()))
+                .collect();
+            if ed25519_dalek::verify_batch(&messages, &signatures, &keys).is_ok() {
+                return Ok(());
+            }
+        }
+    }
+
+    match items
+        .iter()
+        .position(|(key, message, sig)| !key.verify(message, sig))
+    {
+        Some(index) => Err(index),
+        None => Ok(()),
+    }
+}
+
 /// Extension trait for Keypair to add DID and SignatureBytes support
 pub trait KeypairExt {
     /// Create the DID for this keypair
@@ -379,6 +605,36 @@ pub trait KeypairExt {
 
     /// Sign a message and return SignatureBytes
     fn sign_bytes(&self, message: &[u8]) -> SignatureBytes;
+
+    /// Derive a keypair from a BIP39 mnemonic and passphrase
+    ///
+    /// The same phrase and passphrase always give the same keypair. An empty
+    /// passphrase is allowed.
+    fn from_mnemonic(phrase: &str, passphrase: &str) -> Result<Self>
+    where
+        Self: Sized;
+
+    /// Generate a keypair along with the mnemonic that restores it
+    ///
+    /// A mnemonic can't be recovered from an existing keypair (the seed is a
+    /// one-way hash of the phrase), so record the phrase returned here.
+    fn generate_with_mnemonic(passphrase: &str) -> (Self, String)
+    where
+        Self: Sized;
+
+    /// Write the keypair to `path`, encrypted under `passphrase`
+    ///
+    /// Fails if `path` already exists. On Unix the file is created with mode
+    /// 0600.
+    fn save_encrypted(&self, path: impl AsRef<std::path::Path>, passphrase: &str) -> Result<()>;
+
+    /// Read a keypair written by [`save_encrypted`](Self::save_encrypted)
+    ///
+    /// Fails with [`MycelialError::DecryptionFailed`] if the passphrase is
+    /// wrong or the file was tampered with.
+    fn load_encrypted(path: impl AsRef<std::path::Path>, passphrase: &str) -> Result<Self>
+    where
+        Self: Sized;
 }
 
 impl KeypairExt for Keypair {
@@ -389,6 +645,34 @@ impl KeypairExt for Keypair {
     fn sign_bytes(&self, message: &[u8]) -> SignatureBytes {
         SignatureBytes::from(self.sign(message))
     }
+
+    fn from_mnemonic(phrase: &str, passphrase: &str) -> Result<Self> {
+        let secret = mnemonic_secret(phrase, passphrase)?;
+        Keypair::from_bytes(&secret).map_err(|e| MycelialError::KeyGenerationFailed(e.to_string()))
+    }
+
+    fn generate_with_mnemonic(passphrase: &str) -> (Self, String) {
+        let phrase = generate_mnemonic();
+        let keypair = Self::from_mnemonic(&phrase, passphrase)
+            .expect("a freshly generated mnemonic is valid");
+        (keypair, phrase)
+    }
+
+    fn save_encrypted(&self, path: impl AsRef<std::path::Path>, passphrase: &str) -> Result<()> {
+        let file = EncryptedKeyFile::seal(&self.to_bytes(), passphrase)?;
+        let json = serde_json::to_vec_pretty(&file)
+            .map_err(|e| MycelialError::Serialization(e.to_string()))?;
+        write_secret_file(path.as_ref(), &json).map_err(|e| MycelialError::Storage(e.to_string()))
+    }
+
+    fn load_encrypted(path: impl AsRef<std::path::Path>, passphrase: &str) -> Result<Self> {
+        let json =
+            std::fs::read(path.as_ref()).map_err(|e| MycelialError::Storage(e.to_string()))?;
+        let file: EncryptedKeyFile = serde_json::from_slice(&json)
+            .map_err(|e| MycelialError::Deserialization(e.to_string()))?;
+        let secret = file.open(passphrase)?;
+        Keypair::from_bytes(&secret).map_err(|e| MycelialError::KeyGenerationFailed(e.to_string()))
+    }
 }
 
 /// A signed piece of data
@@ -452,6 +736,86 @@ mod tests {
         assert!(pk.verify_bytes(b"Wrong message", &sig).is_err());
     }
 
+    #[test]
+    fn test_keypair_from_mnemonic() {
+        let (kp, phrase) = Keypair::generate_with_mnemonic("");
+        assert_eq!(phrase.split_whitespace().count(), MNEMONIC_WORDS);
+
+        let restored = Keypair::from_mnemonic(&phrase, "").unwrap();
+        assert_eq!(kp.public_key().as_bytes(), restored.public_key().as_bytes());
+
+        let other = Keypair::from_mnemonic(&phrase, "hunter2").unwrap();
+        assert_ne!(kp.public_key().as_bytes(), other.public_key().as_bytes());
+    }
+
+    #[test]
+    fn test_mnemonic_validation() {
+        let valid = "abandon abandon abandon abandon abandon abandon \
+                     abandon abandon abandon abandon abandon about";
+        assert!(mnemonic_secret(valid, "").is_ok());
+
+        // Right words, wrong checksum
+        let bad_checksum = "abandon abandon abandon abandon abandon abandon \
+                            abandon abandon abandon abandon abandon abandon";
+        assert!(Keypair::from_mnemonic(bad_checksum, "").is_err());
+
+        let bad_word = "abandon abandon abandon abandon abandon abandon \
+                        abandon abandon abandon abandon abandon mycelium";
+        assert!(matches!(
+            Keypair::from_mnemonic(bad_word, ""),
+            Err(MycelialError::KeyGenerationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_encrypted_keypair_roundtrip() {
+        let path = std::env::temp_dir().join(format!("mycelial-key-{}.json", uuid::Uuid::new_v4()));
+        let kp = Keypair::generate();
+        kp.save_encrypted(&path, "correct horse").unwrap();
+
+        let loaded = Keypair::load_encrypted(&path, "correct horse").unwrap();
+        assert_eq!(kp.public_key().as_bytes(), loaded.public_key().as_bytes());
+
+        assert!(matches!(
+            Keypair::load_encrypted(&path, "battery staple"),
+            Err(MycelialError::DecryptionFailed(_))
+        ));
+
+        // An existing identity is never replaced
+        assert!(Keypair::generate()
+            .save_encrypted(&path, "correct horse")
+            .is_err());
+
+        // A crafted file can't make loading run for hours
+        let mut file: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        file["t_cost"] = u32::MAX.into();
+        std::fs::write(&path, serde_json::to_vec(&file).unwrap()).unwrap();
+        assert!(matches!(
+            Keypair::load_encrypted(&path, "correct horse"),
+            Err(MycelialError::Deserialization(_))
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_batch_verify() {
+        let keypairs: Vec<Keypair> = (0..4).map(|_| Keypair::generate()).collect();
+        let messages: Vec<Vec<u8>> = (0..4u8).map(|i| vec![i; 16]).collect();
+        let mut items: Vec<(PublicKey, &[u8], Signature)> = keypairs
+            .iter()
+            .zip(&messages)
+            .map(|(kp, msg)| (kp.public_key(), msg.as_slice(), kp.sign(msg)))
+            .collect();
+
+        assert_eq!(batch_verify(&items), Ok(()));
+        assert_eq!(batch_verify(&[]), Ok(()));
+
+        // Swap in a signature over a different message at index 2
+        items[2].2 = keypairs[2].sign(b"something else");
+        assert_eq!(batch_verify(&items), Err(2));
+    }
+
     #[test]
     fn test_did_roundtrip() {
         let kp = Keypair::generate();
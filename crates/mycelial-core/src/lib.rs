@@ -11,6 +11,8 @@
 //! - [`reputation`] - Reputation scoring and trust management
 //! - [`credit`] - Mutual credit and economic relationships
 //! - [`message`] - Network message types
+//! - [`handler`] - Routing messages to handlers by type
+//! - [`health`] - Health checks for node subsystems
 //! - [`module`] - Module trait for substrate architecture
 //! - [`event`] - Event types for cross-module communication
 //! - [`config`] - Configuration types
@@ -45,6 +47,8 @@ pub mod reputation;
 pub mod config;
 pub mod error;
 pub mod event;
+pub mod handler;
+pub mod health;
 pub mod module;
 
 // Re-exports for convenience
@@ -52,11 +56,12 @@ pub use error::{MycelialError, Result};
 
 // Identity re-exports
 pub use identity::{
-    Did, Keypair, KeypairExt, PublicKey, PublicKeyExt, Signature, SignatureBytes, Signed,
+    batch_verify, generate_mnemonic, Did, Keypair, KeypairExt, PublicKey, PublicKeyExt, Signature,
+    SignatureBytes, Signed,
 };
 
 // Content re-exports
-pub use content::{Content, ContentId, ContentMetadata};
+pub use content::{Content, ContentId, ContentKey, ContentMetadata, EncryptedContent};
 
 // Peer re-exports
 pub use peer::{PeerId, PeerInfo};
@@ -65,10 +70,16 @@ pub use peer::{PeerId, PeerInfo};
 pub use reputation::Reputation;
 
 // Credit re-exports
-pub use credit::CreditRelationship;
+pub use credit::{CreditEntry, CreditEntryKind, CreditRelationship};
 
 // Message re-exports
-pub use message::{Message, MessageType};
+pub use message::{Message, MessageId, MessagePriority, MessageType};
+
+// Handler re-exports
+pub use handler::HandlerRegistry;
+
+// Health re-exports
+pub use health::{HealthCheck, HealthLevel, HealthReport, HealthStatus};
 
 // Module re-exports
 pub use module::{
@@ -82,7 +93,7 @@ pub use event::{Event, EventFilter, EventPayload, EventType};
 pub use config::{NetworkConfig, NodeConfig, StorageConfig};
 
 // Location re-exports
-pub use location::Location;
+pub use location::{cluster_by_location, Location, PeerCluster};
 
 use async_trait::async_trait;
 
@@ -132,6 +143,24 @@ pub trait StateStore: Send + Sync {
     async fn update_reputation(&self, id: &PeerId, reputation: &Reputation) -> Result<()>;
 }
 
+/// Trait for content served to other peers
+#[async_trait]
+pub trait ContentStore: Send + Sync {
+    /// Store a piece of content, keeping its pin if it was already stored
+    async fn put_content(&self, content: &Content) -> Result<()>;
+
+    /// Retrieve a piece of content
+    async fn fetch_content(&self, id: &ContentId) -> Result<Option<Content>>;
+
+    /// Remove a piece of content, returning whether it was stored
+    async fn remove_content(&self, id: &ContentId) -> Result<bool>;
+
+    /// Pin or unpin a piece of content, returning whether it is stored
+    ///
+    /// Pinned content is never evicted by the store's GC.
+    async fn set_pinned(&self, id: &ContentId, pinned: bool) -> Result<bool>;
+}
+
 /// Version information
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -1,9 +1,22 @@
 //! Geographic and network location types
+//!
+//! Locations can be bucketed by [geohash](https://en.wikipedia.org/wiki/Geohash)
+//! cell, which [`cluster_by_location`] uses to group peers for a map view.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Characters of the geohash base32 alphabet
+const GEOHASH_ALPHABET: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// Longest geohash worth computing (cells of a few centimetres)
+pub const MAX_GEOHASH_PRECISION: usize = 12;
+
+/// Cell of the cluster holding peers without a known location
+pub const UNKNOWN_CELL: &str = "unknown";
 
 /// Geographic location with optional precision
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Location {
     /// Latitude in degrees (-90 to 90)
     pub latitude: f64,
@@ -40,6 +53,120 @@ impl Location {
 
         EARTH_RADIUS * c
     }
+
+    /// Geohash of the cell containing this location, `precision` characters
+    /// long (clamped to 1 - [`MAX_GEOHASH_PRECISION`])
+    pub fn geohash(&self, precision: usize) -> String {
+        let precision = precision.clamp(1, MAX_GEOHASH_PRECISION);
+        let mut lat = (-90.0, 90.0);
+        let mut lon = (-180.0, 180.0);
+        let mut hash = String::with_capacity(precision);
+        let mut even_bit = true;
+
+        while hash.len() < precision {
+            let mut index = 0;
+            for _ in 0..5 {
+                // Bits alternate between longitude and latitude, longitude first
+                let (range, value) = if even_bit {
+                    (&mut lon, self.longitude)
+                } else {
+                    (&mut lat, self.latitude)
+                };
+                let mid = (range.0 + range.1) / 2.0;
+                index <<= 1;
+                if value >= mid {
+                    index |= 1;
+                    range.0 = mid;
+                } else {
+                    range.1 = mid;
+                }
+                even_bit = !even_bit;
+            }
+            hash.push(GEOHASH_ALPHABET[index] as char);
+        }
+        hash
+    }
+
+    /// Geographic midpoint of some locations, or `None` if there are none
+    ///
+    /// Averages on the unit sphere, so clusters spanning the antimeridian
+    /// get a sensible centre.
+    pub fn centroid<'a>(locations: impl IntoIterator<Item = &'a Location>) -> Option<Location> {
+        let (mut x, mut y, mut z, mut count) = (0.0, 0.0, 0.0, 0usize);
+        for location in locations {
+            let (lat, lon) = (
+                location.latitude.to_radians(),
+                location.longitude.to_radians(),
+            );
+            x += lat.cos() * lon.cos();
+            y += lat.cos() * lon.sin();
+            z += lat.sin();
+            count += 1;
+        }
+        if count == 0 {
+            return None;
+        }
+
+        let (x, y, z) = (x / count as f64, y / count as f64, z / count as f64);
+        let latitude = z.atan2((x * x + y * y).sqrt()).to_degrees();
+        let longitude = y.atan2(x).to_degrees();
+        Some(Location::new(latitude, longitude))
+    }
+}
+
+/// Peers sharing a geohash cell
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerCluster {
+    /// Geohash of the cell, or [`UNKNOWN_CELL`] for peers without a location
+    pub cell: String,
+    /// Number of peers in the cell
+    pub count: usize,
+    /// Midpoint of the peers' locations (`None` for the unknown cell)
+    pub centroid: Option<Location>,
+    /// IDs of the peers in the cell
+    pub peers: Vec<String>,
+}
+
+/// Group peers by the geohash cell of their location
+///
+/// Clusters are ordered largest first; peers without a location share an
+/// [`UNKNOWN_CELL`] cluster, which comes last.
+pub fn cluster_by_location<'a>(
+    peers: impl IntoIterator<Item = (String, Option<&'a Location>)>,
+    precision: usize,
+) -> Vec<PeerCluster> {
+    let mut cells: BTreeMap<String, Vec<(String, &Location)>> = BTreeMap::new();
+    let mut unknown = Vec::new();
+    for (peer, location) in peers {
+        match location {
+            Some(location) => cells
+                .entry(location.geohash(precision))
+                .or_default()
+                .push((peer, location)),
+            None => unknown.push(peer),
+        }
+    }
+
+    let mut clusters: Vec<PeerCluster> = cells
+        .into_iter()
+        .map(|(cell, members)| PeerCluster {
+            cell,
+            count: members.len(),
+            centroid: Location::centroid(members.iter().map(|(_, location)| *location)),
+            peers: members.into_iter().map(|(peer, _)| peer).collect(),
+        })
+        .collect();
+    clusters.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.cell.cmp(&b.cell)));
+
+    if !unknown.is_empty() {
+        clusters.push(PeerCluster {
+            cell: UNKNOWN_CELL.to_string(),
+            count: unknown.len(),
+            centroid: None,
+            peers: unknown,
+        });
+    }
+    clusters
 }
 
 #[cfg(test)]
@@ -55,4 +182,38 @@ mod tests {
         // Approximately 559 km
         assert!((distance - 559_000.0).abs() < 10_000.0);
     }
+
+    #[test]
+    fn test_geohash_and_clusters() {
+        // Reference value for the geohash algorithm
+        assert_eq!(Location::new(57.64911, 10.40744).geohash(11), "u4pruydqqvj");
+
+        let sf = Location::new(37.7749, -122.4194);
+        let daly_city = Location::new(37.6879, -122.4702);
+        let la = Location::new(34.0522, -118.2437);
+        let clusters = cluster_by_location(
+            [
+                ("a".to_string(), Some(&sf)),
+                ("b".to_string(), Some(&daly_city)),
+                ("c".to_string(), Some(&la)),
+                ("d".to_string(), None),
+            ],
+            3,
+        );
+
+        assert_eq!(clusters.len(), 3);
+        assert_eq!(clusters[0].cell, "9q8");
+        assert_eq!(clusters[0].peers, vec!["a", "b"]);
+        let centroid = clusters[0].centroid.as_ref().unwrap();
+        assert!(centroid.distance_to(&sf) < 10_000.0);
+        assert_eq!(clusters[1].count, 1);
+        assert_eq!(clusters[2].cell, UNKNOWN_CELL);
+        assert_eq!(clusters[2].centroid, None);
+
+        // Averaging across the antimeridian stays near it
+        let east = Location::new(0.0, 179.0);
+        let west = Location::new(0.0, -179.0);
+        let mid = Location::centroid([&east, &west]).unwrap();
+        assert!(mid.longitude.abs() > 179.9);
+    }
 }
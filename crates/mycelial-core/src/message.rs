@@ -3,8 +3,45 @@
 use crate::peer::PeerId;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
 use uuid::Uuid;
 
+/// Domain separator for content-derived message IDs
+const MESSAGE_ID_DOMAIN: &[u8] = b"mycelial/message-id/v1";
+
+/// Content-derived message identifier
+///
+/// Unlike [`Message::id`], which is random, this is the same wherever the
+/// message is seen, so a message that crosses from LoRa to gossipsub and
+/// back is recognised as the same message.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MessageId([u8; 32]);
+
+impl MessageId {
+    /// Get the raw bytes
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Encode as hex string
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0)
+    }
+}
+
+impl fmt::Debug for MessageId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MessageId({})", &self.to_hex()[..16])
+    }
+}
+
+impl fmt::Display for MessageId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
 /// A message in the mycelial network
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Message {
@@ -25,7 +62,7 @@ pub struct Message {
 }
 
 /// Types of messages in the network
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum MessageType {
     /// Peer discovery and announcement
     Discovery,
@@ -43,6 +80,44 @@ pub enum MessageType {
     System,
 }
 
+impl MessageType {
+    /// Priority for messages of this type when bandwidth is scarce (e.g. LoRa)
+    ///
+    /// | Type                                 | Priority |
+    /// |--------------------------------------|----------|
+    /// | Credit, Governance, Reputation       | High     |
+    /// | Content, Direct, System              | Normal   |
+    /// | Discovery                            | Low      |
+    ///
+    /// Transports may override this per topic.
+    pub fn default_priority(&self) -> MessagePriority {
+        match self {
+            MessageType::Credit | MessageType::Governance | MessageType::Reputation => {
+                MessagePriority::High
+            }
+            MessageType::Content | MessageType::Direct | MessageType::System => {
+                MessagePriority::Normal
+            }
+            MessageType::Discovery => MessagePriority::Low,
+        }
+    }
+}
+
+/// Delivery priority of a message
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum MessagePriority {
+    /// May be delayed or dropped under load
+    Low,
+    /// Normal priority
+    #[default]
+    Normal,
+    /// Sent first
+    High,
+}
+
 impl Message {
     /// Create a new message
     pub fn new(message_type: MessageType, sender: PeerId, payload: Vec<u8>) -> Self {
@@ -70,6 +145,22 @@ impl Message {
         }
     }
 
+    /// Content-derived ID over the sender, timestamp (ms) and payload
+    ///
+    /// The random `id`, recipient and signature are left out, so re-encoding
+    /// or re-signing a message on another network keeps its ID.
+    pub fn content_id(&self) -> MessageId {
+        let sender = self.sender.as_str().as_bytes();
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(MESSAGE_ID_DOMAIN);
+        hasher.update(&(sender.len() as u64).to_be_bytes());
+        hasher.update(sender);
+        hasher.update(&self.timestamp.timestamp_millis().to_be_bytes());
+        hasher.update(&(self.payload.len() as u64).to_be_bytes());
+        hasher.update(&self.payload);
+        MessageId(*hasher.finalize().as_bytes())
+    }
+
     /// Check if message is expired (older than max_age seconds)
     pub fn is_expired(&self, max_age_secs: i64) -> bool {
         let age = Utc::now().signed_duration_since(self.timestamp);
@@ -77,13 +168,35 @@ impl Message {
     }
 }
 
+/// ID the gossip layer gives a published payload
+///
+/// A CBOR-encoded Mycelial message is identified by its
+/// [`Message::content_id`], so the same message bridged in from another
+/// network is deduplicated; any other payload by the SHA-256 of its bytes.
+pub fn payload_id(data: &[u8]) -> Vec<u8> {
+    if let Ok(msg) = serde_cbor::from_slice::<Message>(data) {
+        return msg.content_id().as_bytes().to_vec();
+    }
+    Sha256::digest(data).to_vec()
+}
+
+/// Short id for following one message through the logs
+///
+/// The first 8 bytes of a [`payload_id`] in hex. The LoRa bridge, network
+/// service and node record it as the `correlation_id` field of the spans
+/// they handle a message in.
+pub fn correlation_id(id: &[u8]) -> String {
+    hex::encode(&id[..id.len().min(8)])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::identity::Keypair;
 
     #[test]
     fn test_message_creation() {
-        let sender = PeerId("sender".to_string());
+        let sender = PeerId::from(Keypair::generate().public_key());
         let msg = Message::new(
             MessageType::Content,
             sender.clone(),
@@ -94,4 +207,59 @@ mod tests {
         assert_eq!(msg.message_type, MessageType::Content);
         assert!(msg.recipient.is_none());
     }
+
+    #[test]
+    fn test_default_priority() {
+        assert_eq!(
+            MessageType::Credit.default_priority(),
+            MessagePriority::High
+        );
+        assert_eq!(
+            MessageType::Content.default_priority(),
+            MessagePriority::Normal
+        );
+        assert_eq!(
+            MessageType::Discovery.default_priority(),
+            MessagePriority::Low
+        );
+        assert!(MessagePriority::High > MessagePriority::Normal);
+    }
+
+    #[test]
+    fn test_content_id_survives_round_trip() {
+        let msg = Message::new(
+            MessageType::Content,
+            PeerId::from(Keypair::generate().public_key()),
+            b"Hello, world!".to_vec(),
+        );
+
+        // A bridge re-encodes the message and gives it a fresh id
+        let mut bridged: Message =
+            serde_cbor::from_slice(&serde_cbor::to_vec(&msg).unwrap()).unwrap();
+        bridged.id = Uuid::new_v4();
+        bridged.signature = Some(vec![1, 2, 3]);
+        assert_eq!(bridged.content_id(), msg.content_id());
+
+        bridged.payload = b"Hello, world?".to_vec();
+        assert_ne!(bridged.content_id(), msg.content_id());
+    }
+
+    #[test]
+    fn test_correlation_id_matches_across_encodings() {
+        let msg = Message::new(
+            MessageType::Content,
+            PeerId::from(Keypair::generate().public_key()),
+            b"Hello, world!".to_vec(),
+        );
+        let data = serde_cbor::to_vec(&msg).unwrap();
+
+        let id = payload_id(&data);
+        assert_eq!(id, msg.content_id().as_bytes().to_vec());
+        assert_eq!(correlation_id(&id), msg.content_id().to_hex()[..16]);
+
+        // Other payloads fall back to a hash of their bytes
+        let raw = payload_id(b"{\"vouch\":1}");
+        assert_eq!(raw.len(), 32);
+        assert_eq!(correlation_id(&raw).len(), 16);
+    }
 }
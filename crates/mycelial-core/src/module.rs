@@ -2,10 +2,18 @@
 //!
 //! All functional modules (Social, Orchestration, Economics) implement this trait
 //! to integrate with the Mycelia substrate layer.
+//!
+//! Besides gossip, modules can query each other directly:
+//! [`ModuleRegistry::request`] hands a [`ModuleMessage`] carrying a fresh
+//! correlation ID to the target's [`MyceliaModule::handle_request`] and waits
+//! for the reply, so modules can ask each other questions (say, a balance
+//! from the economics module) without sharing state.
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
 
 use crate::{MycelialError, Result};
 
@@ -81,6 +89,16 @@ pub trait MyceliaModule: Send + Sync {
         source: Option<&str>,
     ) -> Result<Option<Vec<u8>>>;
 
+    /// Answer a request from another module
+    ///
+    /// Modules that serve requests return a reply, usually built with
+    /// [`ModuleMessage::response`] so it carries the request's correlation
+    /// ID. The default answers nothing, which the requester sees as
+    /// [`MycelialError::NoModuleReply`].
+    async fn handle_request(&mut self, _request: &ModuleMessage) -> Result<Option<ModuleMessage>> {
+        Ok(None)
+    }
+
     /// Periodic tick for background processing
     ///
     /// Called at regular intervals to allow modules to perform
@@ -209,6 +227,10 @@ impl ModuleMessage {
     }
 }
 
+/// How long [`ModuleRegistry::request`] waits for a reply
+#[cfg(not(target_arch = "wasm32"))]
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Registry for managing modules
 pub struct ModuleRegistry {
     modules: HashMap<String, Box<dyn MyceliaModule>>,
@@ -252,6 +274,55 @@ impl ModuleRegistry {
             .collect()
     }
 
+    /// Send a request to the `target` module and wait for its reply
+    ///
+    /// Waits at most [`DEFAULT_REQUEST_TIMEOUT`]; see
+    /// [`request_with_timeout`](Self::request_with_timeout). Needs a tokio
+    /// runtime, so it isn't available on wasm.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn request(&mut self, target: &str, message: ModuleMessage) -> Result<ModuleMessage> {
+        self.request_with_timeout(target, message, DEFAULT_REQUEST_TIMEOUT)
+            .await
+    }
+
+    /// Send a request to the `target` module and wait up to `timeout` for its reply
+    ///
+    /// The message is addressed to `target` and given a correlation ID if it
+    /// has none; the reply must carry the same ID.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn request_with_timeout(
+        &mut self,
+        target: &str,
+        mut message: ModuleMessage,
+        timeout: Duration,
+    ) -> Result<ModuleMessage> {
+        message.target_module = Some(target.to_string());
+        let request_id = *message.request_id.get_or_insert_with(uuid::Uuid::new_v4);
+
+        let module = self
+            .modules
+            .get_mut(target)
+            .ok_or_else(|| MycelialError::ModuleNotFound(target.to_string()))?;
+        let reply = tokio::time::timeout(timeout, module.handle_request(&message))
+            .await
+            .map_err(|_| MycelialError::ModuleRequestTimeout {
+                module: target.to_string(),
+                duration_ms: timeout.as_millis() as u64,
+            })??
+            .ok_or_else(|| MycelialError::NoModuleReply {
+                module: target.to_string(),
+                message_type: message.message_type.clone(),
+            })?;
+
+        if reply.request_id != Some(request_id) {
+            return Err(MycelialError::InvalidMessageFormat(format!(
+                "Reply from {} to '{}' has the wrong correlation ID",
+                target, message.message_type
+            )));
+        }
+        Ok(reply)
+    }
+
     /// Initialize all modules
     pub async fn initialize_all(&mut self) -> Result<()> {
         for module in self.modules.values_mut() {
@@ -307,4 +378,103 @@ mod tests {
         assert_eq!(response.target_module, Some("social".to_string()));
         assert_eq!(response.request_id, request.request_id);
     }
+
+    /// Answers `get_balance` requests, slowly for `slow_balance`
+    struct CreditModule;
+
+    #[async_trait]
+    impl MyceliaModule for CreditModule {
+        fn id(&self) -> &str {
+            "economics"
+        }
+
+        fn info(&self) -> ModuleInfo {
+            ModuleInfo {
+                id: "economics".to_string(),
+                name: "Credit".to_string(),
+                version: "0.1.0".to_string(),
+                description: "Test credit module".to_string(),
+                subscribed_topics: vec![],
+                published_topics: vec![],
+            }
+        }
+
+        fn subscribed_topics(&self) -> Vec<String> {
+            vec![]
+        }
+
+        async fn handle_message(
+            &mut self,
+            _topic: &str,
+            _payload: &[u8],
+            _source: Option<&str>,
+        ) -> Result<Option<Vec<u8>>> {
+            Ok(None)
+        }
+
+        async fn handle_request(
+            &mut self,
+            request: &ModuleMessage,
+        ) -> Result<Option<ModuleMessage>> {
+            match request.message_type.as_str() {
+                "get_balance" => Ok(Some(request.response(self.id(), b"100".to_vec()))),
+                "slow_balance" => {
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                    Ok(Some(request.response(self.id(), b"100".to_vec())))
+                }
+                _ => Ok(None),
+            }
+        }
+
+        async fn tick(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        fn state(&self) -> ModuleState {
+            ModuleState::Running
+        }
+
+        fn metrics(&self) -> ModuleMetrics {
+            ModuleMetrics::default()
+        }
+
+        async fn initialize(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn shutdown(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_registry_request_reply() {
+        let mut registry = ModuleRegistry::new();
+        registry.register(Box::new(CreditModule));
+
+        let request = ModuleMessage::new("social", "get_balance", vec![]);
+        let reply = registry.request("economics", request).await.unwrap();
+        assert_eq!(reply.payload, b"100");
+        assert_eq!(reply.target_module, Some("social".to_string()));
+        assert!(reply.request_id.is_some());
+
+        let unanswered = ModuleMessage::new("social", "get_votes", vec![]);
+        assert!(matches!(
+            registry.request("economics", unanswered).await,
+            Err(MycelialError::NoModuleReply { .. })
+        ));
+
+        let slow = ModuleMessage::new("social", "slow_balance", vec![]);
+        let timeout = registry
+            .request_with_timeout("economics", slow, Duration::from_secs(1))
+            .await
+            .unwrap_err();
+        assert!(timeout.is_retriable());
+
+        let missing = ModuleMessage::new("social", "get_balance", vec![]);
+        assert!(matches!(
+            registry.request("governance", missing).await,
+            Err(MycelialError::ModuleNotFound(_))
+        ));
+    }
 }
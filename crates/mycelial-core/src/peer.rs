@@ -8,18 +8,100 @@ use serde::{Deserialize, Serialize};
 
 // Use identity types from our identity module (which re-exports from univrs-identity)
 use crate::identity::{Keypair, PublicKey};
+use crate::location::Location;
 
 /// Unique identifier for a peer in the network.
 ///
-/// This is a base58-encoded Ed25519 public key, providing a human-readable
-/// identifier that can be converted back to a `PublicKey` for verification.
+/// This is the base58 form of a libp2p peer ID: a multihash that, for
+/// Ed25519 peers, inlines the public key, so it can be converted back to a
+/// `PublicKey` for verification. IDs from before the multihash encoding
+/// (the bare base58 key) and virtual LoRa IDs (`lora:{node_id}`) are also
+/// accepted. Every constructor validates, including deserialization.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub struct PeerId(pub String);
+#[serde(try_from = "String", into = "String")]
+pub struct PeerId(String);
+
+/// Multihash code for the identity hash (key inlined)
+const MULTIHASH_IDENTITY: u64 = 0x00;
+
+/// Multihash code for SHA2-256
+const MULTIHASH_SHA2_256: u64 = 0x12;
+
+/// Longest protobuf-encoded public key libp2p inlines with the identity hash
+const MAX_INLINE_KEY_LENGTH: usize = 42;
+
+/// Protobuf header of a libp2p Ed25519 public key (`KeyType::Ed25519`,
+/// then a 32-byte data field)
+const ED25519_KEY_HEADER: [u8; 4] = [0x08, 0x01, 0x12, 0x20];
+
+/// Length of a bare Ed25519 key, the peer ID encoding before multihashes
+const LEGACY_KEY_LENGTH: usize = 32;
+
+/// Prefix of virtual peer IDs standing in for LoRa nodes
+const LORA_PREFIX: &str = "lora:";
 
 impl PeerId {
+    /// Parse a peer ID, checking that it is a well-formed base58 multihash,
+    /// a legacy bare key or a virtual LoRa ID
+    pub fn parse(s: &str) -> crate::Result<Self> {
+        if s.starts_with(LORA_PREFIX) {
+            let id = Self(s.to_string());
+            return match id.lora_node_id() {
+                Some(_) => Ok(id),
+                None => Err(crate::MycelialError::InvalidPeerId(format!(
+                    "{}: invalid LoRa node ID",
+                    s
+                ))),
+            };
+        }
+
+        let bytes = bs58::decode(s)
+            .into_vec()
+            .map_err(|e| crate::MycelialError::InvalidPeerId(format!("{}: {}", s, e)))?;
+        if bytes.len() == LEGACY_KEY_LENGTH {
+            return Ok(Self(s.to_string()));
+        }
+        let (code, digest) = decode_multihash(&bytes).ok_or_else(|| {
+            crate::MycelialError::InvalidPeerId(format!("{}: not a multihash", s))
+        })?;
+
+        let valid = match code {
+            MULTIHASH_IDENTITY => digest.len() <= MAX_INLINE_KEY_LENGTH,
+            MULTIHASH_SHA2_256 => digest.len() == 32,
+            _ => false,
+        };
+        if !valid {
+            return Err(crate::MycelialError::InvalidPeerId(format!(
+                "{}: unsupported multihash",
+                s
+            )));
+        }
+
+        Ok(Self(s.to_string()))
+    }
+
     /// Create a new peer ID from a public key
     pub fn from_public_key(key: &PublicKey) -> Self {
-        Self(key.to_base58())
+        let mut bytes = Vec::with_capacity(2 + ED25519_KEY_HEADER.len() + 32);
+        bytes.push(MULTIHASH_IDENTITY as u8);
+        bytes.push((ED25519_KEY_HEADER.len() + 32) as u8);
+        bytes.extend_from_slice(&ED25519_KEY_HEADER);
+        bytes.extend_from_slice(key.as_bytes());
+        Self(bs58::encode(bytes).into_string())
+    }
+
+    /// The virtual peer ID standing in for a LoRa node
+    pub fn lora(node_id: u32) -> Self {
+        Self(format!("{}{:08x}", LORA_PREFIX, node_id))
+    }
+
+    /// The LoRa node a virtual peer ID stands in for, if it is one
+    pub fn lora_node_id(&self) -> Option<u32> {
+        let hex = self.0.strip_prefix(LORA_PREFIX)?;
+        if hex.len() != 8 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        u32::from_str_radix(hex, 16).ok()
     }
 
     /// Get the peer ID as a string
@@ -29,13 +111,56 @@ impl PeerId {
 
     /// Get a short form of the peer ID (first 8 characters)
     pub fn short(&self) -> &str {
-        &self.0[..8.min(self.0.len())]
+        match self.0.char_indices().nth(8) {
+            Some((end, _)) => &self.0[..end],
+            None => &self.0,
+        }
     }
 
     /// Try to convert back to a public key
+    ///
+    /// Only Ed25519 peer IDs inline their key; hashed IDs fail. Legacy IDs
+    /// are the bare key and decode directly.
     pub fn to_public_key(&self) -> crate::Result<PublicKey> {
-        PublicKey::from_base58(&self.0).map_err(|_| crate::MycelialError::InvalidSignature)
+        let bytes = bs58::decode(&self.0)
+            .into_vec()
+            .map_err(|_| crate::MycelialError::InvalidSignature)?;
+        let key = match decode_multihash(&bytes) {
+            Some((MULTIHASH_IDENTITY, digest)) if digest.starts_with(&ED25519_KEY_HEADER) => {
+                &digest[ED25519_KEY_HEADER.len()..]
+            }
+            _ if bytes.len() == LEGACY_KEY_LENGTH => &bytes[..],
+            _ => return Err(crate::MycelialError::InvalidSignature),
+        };
+        PublicKey::from_bytes(key).map_err(|_| crate::MycelialError::InvalidSignature)
+    }
+
+    /// Convert to a libp2p peer ID
+    #[cfg(feature = "libp2p")]
+    pub fn to_libp2p(&self) -> crate::Result<libp2p_identity::PeerId> {
+        self.0
+            .parse()
+            .map_err(|e| crate::MycelialError::InvalidPeerId(format!("{}: {}", self.0, e)))
+    }
+}
+
+/// Split a multihash into its code and digest
+fn decode_multihash(bytes: &[u8]) -> Option<(u64, &[u8])> {
+    let (code, rest) = read_varint(bytes)?;
+    let (len, digest) = read_varint(rest)?;
+    (digest.len() as u64 == len).then_some((code, digest))
+}
+
+/// Read an unsigned LEB128 varint (at most 9 bytes, as in multiformats)
+fn read_varint(bytes: &[u8]) -> Option<(u64, &[u8])> {
+    let mut value = 0u64;
+    for (i, byte) in bytes.iter().take(9).enumerate() {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((value, &bytes[i + 1..]));
+        }
     }
+    None
 }
 
 impl std::fmt::Display for PeerId {
@@ -56,6 +181,35 @@ impl From<PublicKey> for PeerId {
     }
 }
 
+#[cfg(feature = "libp2p")]
+impl From<libp2p_identity::PeerId> for PeerId {
+    fn from(peer_id: libp2p_identity::PeerId) -> Self {
+        Self(peer_id.to_base58())
+    }
+}
+
+impl std::str::FromStr for PeerId {
+    type Err = crate::MycelialError;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for PeerId {
+    type Error = crate::MycelialError;
+
+    fn try_from(s: String) -> crate::Result<Self> {
+        Self::parse(&s)
+    }
+}
+
+impl From<PeerId> for String {
+    fn from(peer_id: PeerId) -> Self {
+        peer_id.0
+    }
+}
+
 /// Information about a peer in the network
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PeerInfo {
@@ -71,6 +225,9 @@ pub struct PeerInfo {
     pub last_seen: DateTime<Utc>,
     /// Optional human-readable name
     pub name: Option<String>,
+    /// Last reported geographic location, if the peer shares one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<Location>,
 }
 
 impl PeerInfo {
@@ -87,6 +244,7 @@ impl PeerInfo {
             first_seen: now,
             last_seen: now,
             name: None,
+            location: None,
         }
     }
 
@@ -102,6 +260,7 @@ impl PeerInfo {
             first_seen: now,
             last_seen: now,
             name: None,
+            location: None,
         }
     }
 
@@ -127,6 +286,12 @@ impl PeerInfo {
         self.name = Some(name.into());
         self
     }
+
+    /// Set the peer's geographic location
+    pub fn with_location(mut self, location: Location) -> Self {
+        self.location = Some(location);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -179,6 +344,64 @@ mod tests {
         let info = PeerInfo::new(&keypair, vec!["192.168.1.1:8080".to_string()]);
 
         assert_eq!(info.public_key, keypair.public_key().to_base58());
-        assert_eq!(info.id.as_str(), keypair.public_key().to_base58());
+        assert_eq!(info.id, PeerId::from_public_key(&keypair.public_key()));
+    }
+
+    #[test]
+    fn test_peer_id_parse() {
+        let keypair = Keypair::generate();
+        let peer_id = PeerId::from_public_key(&keypair.public_key());
+
+        // Ed25519 peer IDs have the familiar libp2p prefix
+        assert!(peer_id.as_str().starts_with("12D3KooW"));
+        assert_eq!(PeerId::parse(peer_id.as_str()).unwrap(), peer_id);
+
+        assert!(PeerId::parse("").is_err());
+        assert!(PeerId::parse("not-base58!").is_err());
+        assert!(PeerId::parse("12D3KooWTestPeer").is_err());
+        assert!(PeerId::parse("lora:1234").is_err());
+        assert_eq!(
+            PeerId::parse("lora:0000abcd").unwrap(),
+            PeerId::lora(0xabcd)
+        );
+        assert_eq!(PeerId::lora(0xabcd).lora_node_id(), Some(0xabcd));
+        assert_eq!(peer_id.lora_node_id(), None);
+    }
+
+    #[test]
+    fn test_legacy_peer_id_to_public_key() {
+        let keypair = Keypair::generate();
+        let legacy = PeerId::parse(&keypair.public_key().to_base58()).unwrap();
+
+        let recovered = legacy.to_public_key().unwrap();
+        assert_eq!(keypair.public_key().as_bytes(), recovered.as_bytes());
+        assert_ne!(legacy, PeerId::from_public_key(&keypair.public_key()));
+    }
+
+    #[test]
+    fn test_peer_id_deserialize_validates() {
+        let peer_id = PeerId::from_public_key(&Keypair::generate().public_key());
+        let json = serde_json::to_string(&peer_id).unwrap();
+        assert_eq!(json, format!("\"{}\"", peer_id));
+        assert_eq!(serde_json::from_str::<PeerId>(&json).unwrap(), peer_id);
+
+        assert!(serde_json::from_str::<PeerId>("\"not-base58!\"").is_err());
+    }
+
+    #[test]
+    fn test_peer_id_short_never_panics() {
+        assert_eq!(PeerId("abc".to_string()).short(), "abc");
+        assert_eq!(PeerId("ééééééééé".to_string()).short(), "éééééééé");
+    }
+
+    #[cfg(feature = "libp2p")]
+    #[test]
+    fn test_peer_id_to_libp2p() {
+        let keypair = Keypair::generate();
+        let peer_id = PeerId::from_public_key(&keypair.public_key());
+
+        let libp2p_id = peer_id.to_libp2p().unwrap();
+        assert_eq!(PeerId::from(libp2p_id), peer_id);
+        assert!(PeerId("peer1".to_string()).to_libp2p().is_err());
     }
 }
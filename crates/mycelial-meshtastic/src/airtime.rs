@@ -0,0 +1,195 @@
+//! LoRa airtime estimation and duty-cycle budgeting
+//!
+//! Regional regulations limit how long a LoRa transmitter may occupy the
+//! channel (e.g. 10% per hour in the EU 869.4-869.65 MHz sub-band). This
+//! module estimates the time-on-air of each packet using the Semtech
+//! SX127x formula and tracks a rolling window of transmissions so the bridge
+//! can hold back traffic before the radio exceeds its allowance.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mycelial_meshtastic::airtime::AirtimeBudget;
+//! use std::time::{Duration, Instant};
+//!
+//! // SF11 / 250 kHz (Meshtastic LongFast), 10% of every hour
+//! let mut budget = AirtimeBudget::new(11, 250, 10.0, Duration::from_secs(3600));
+//!
+//! let airtime = budget.estimate(64);
+//! assert!(budget.try_consume(airtime, Instant::now()));
+//! ```
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Bytes added to every packet on air by the Meshtastic radio header
+pub const LORA_HEADER_BYTES: usize = 16;
+
+/// Preamble length used by Meshtastic radios (symbols)
+const PREAMBLE_SYMBOLS: f64 = 16.0;
+
+/// Coding rate 4/5, expressed as the `CR` term of the airtime formula
+const CODING_RATE: f64 = 1.0;
+
+/// Estimate time-on-air for a LoRa packet
+///
+/// `payload_len` is the number of bytes handed to the radio; the Meshtastic
+/// header is added here. Explicit header mode and CRC are assumed, and low
+/// data rate optimisation is enabled when a symbol lasts longer than 16 ms,
+/// as the radio does.
+pub fn estimate_airtime(payload_len: usize, spreading_factor: u8, bandwidth_khz: u32) -> Duration {
+    let sf = f64::from(spreading_factor.clamp(7, 12));
+    let bandwidth_hz = f64::from(bandwidth_khz.max(1)) * 1000.0;
+    let symbol_time = 2f64.powf(sf) / bandwidth_hz;
+    let low_data_rate = if symbol_time > 0.016 { 1.0 } else { 0.0 };
+
+    let payload_bits = 8.0 * (payload_len + LORA_HEADER_BYTES) as f64;
+    let numerator = payload_bits - 4.0 * sf + 28.0 + 16.0;
+    let denominator = 4.0 * (sf - 2.0 * low_data_rate);
+    let payload_symbols = 8.0 + ((numerator / denominator).ceil() * (CODING_RATE + 4.0)).max(0.0);
+
+    let preamble_time = (PREAMBLE_SYMBOLS + 4.25) * symbol_time;
+    Duration::from_secs_f64(preamble_time + payload_symbols * symbol_time)
+}
+
+/// Rolling duty-cycle budget for outbound LoRa transmissions
+#[derive(Debug, Clone)]
+pub struct AirtimeBudget {
+    /// Spreading factor (7-12)
+    spreading_factor: u8,
+    /// Channel bandwidth in kHz
+    bandwidth_khz: u32,
+    /// Total airtime allowed per window
+    allowance: Duration,
+    /// Length of the rolling window
+    window: Duration,
+    /// Transmissions inside the current window
+    history: VecDeque<(Instant, Duration)>,
+}
+
+impl AirtimeBudget {
+    /// Create a budget allowing `duty_cycle_percent` of every `window`
+    pub fn new(
+        spreading_factor: u8,
+        bandwidth_khz: u32,
+        duty_cycle_percent: f64,
+        window: Duration,
+    ) -> Self {
+        let fraction = (duty_cycle_percent / 100.0).clamp(0.0, 1.0);
+        Self {
+            spreading_factor: spreading_factor.clamp(7, 12),
+            bandwidth_khz,
+            allowance: window.mul_f64(fraction),
+            window,
+            history: VecDeque::new(),
+        }
+    }
+
+    /// Estimate airtime for a packet of `payload_len` bytes with this radio's settings
+    pub fn estimate(&self, payload_len: usize) -> Duration {
+        estimate_airtime(payload_len, self.spreading_factor, self.bandwidth_khz)
+    }
+
+    /// Total airtime allowed per window
+    pub fn allowance(&self) -> Duration {
+        self.allowance
+    }
+
+    /// Airtime used inside the window ending at `now`
+    pub fn used(&mut self, now: Instant) -> Duration {
+        self.expire(now);
+        self.history.iter().map(|(_, airtime)| *airtime).sum()
+    }
+
+    /// Airtime still available inside the window ending at `now`
+    pub fn remaining(&mut self, now: Instant) -> Duration {
+        self.allowance.saturating_sub(self.used(now))
+    }
+
+    /// Whether `airtime` fits while keeping `reserve` of the budget untouched
+    pub fn fits(&mut self, airtime: Duration, reserve: Duration, now: Instant) -> bool {
+        airtime + reserve <= self.remaining(now)
+    }
+
+    /// Record a transmission
+    pub fn record(&mut self, airtime: Duration, now: Instant) {
+        self.history.push_back((now, airtime));
+    }
+
+    /// Record a transmission if it fits in the remaining budget
+    pub fn try_consume(&mut self, airtime: Duration, now: Instant) -> bool {
+        if !self.fits(airtime, Duration::ZERO, now) {
+            return false;
+        }
+        self.record(airtime, now);
+        true
+    }
+
+    /// Drop transmissions that have left the window
+    fn expire(&mut self, now: Instant) {
+        while let Some((sent_at, _)) = self.history.front() {
+            if now.saturating_duration_since(*sent_at) < self.window {
+                break;
+            }
+            self.history.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_airtime_grows_with_spreading_factor() {
+        let sf7 = estimate_airtime(50, 7, 125);
+        let sf12 = estimate_airtime(50, 12, 125);
+        assert!(sf12 > sf7 * 10);
+    }
+
+    #[test]
+    fn test_airtime_grows_with_size() {
+        assert!(estimate_airtime(200, 11, 250) > estimate_airtime(20, 11, 250));
+    }
+
+    #[test]
+    fn test_airtime_known_value() {
+        // SF7 / 125 kHz, 16-symbol preamble: about 106 ms for 50 bytes on air
+        let airtime = estimate_airtime(50 - LORA_HEADER_BYTES, 7, 125);
+        assert!(airtime > Duration::from_millis(90) && airtime < Duration::from_millis(115));
+    }
+
+    #[test]
+    fn test_budget_enforces_allowance() {
+        let now = Instant::now();
+        let mut budget = AirtimeBudget::new(11, 250, 1.0, Duration::from_secs(100));
+        assert_eq!(budget.allowance(), Duration::from_secs(1));
+
+        assert!(budget.try_consume(Duration::from_millis(600), now));
+        assert!(!budget.try_consume(Duration::from_millis(600), now));
+        assert_eq!(budget.remaining(now), Duration::from_millis(400));
+    }
+
+    #[test]
+    fn test_budget_window_rolls() {
+        let start = Instant::now();
+        let mut budget = AirtimeBudget::new(11, 250, 1.0, Duration::from_secs(100));
+
+        assert!(budget.try_consume(Duration::from_secs(1), start));
+        assert!(!budget.try_consume(Duration::from_millis(1), start));
+
+        let later = start + Duration::from_secs(100);
+        assert_eq!(budget.remaining(later), Duration::from_secs(1));
+        assert!(budget.try_consume(Duration::from_millis(1), later));
+    }
+
+    #[test]
+    fn test_budget_reserve() {
+        let now = Instant::now();
+        let mut budget = AirtimeBudget::new(11, 250, 1.0, Duration::from_secs(100));
+
+        let reserve = Duration::from_millis(500);
+        assert!(budget.fits(Duration::from_millis(500), reserve, now));
+        assert!(!budget.fits(Duration::from_millis(501), reserve, now));
+    }
+}
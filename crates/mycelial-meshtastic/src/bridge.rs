@@ -39,18 +39,29 @@
 //! ```
 
 use bytes::Bytes;
+use mycelial_core::health::{HealthCheck, HealthStatus};
+use mycelial_core::message::{correlation_id, payload_id};
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::{broadcast, mpsc, oneshot};
-use tracing::{debug, error, info, trace, warn};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc, oneshot, watch};
+use tracing::{debug, error, field, info, info_span, trace, warn, Instrument, Span};
 
+use crate::airtime::AirtimeBudget;
 use crate::cache::{DeduplicationCache, DeduplicationKey, MessageDirection};
 use crate::compression::{EconomicsMessageCodec, MessageChunk};
-use crate::config::{BridgeConfig, MeshtasticConfig, LORA_MAX_PAYLOAD};
+use crate::config::{
+    BridgeConfig, LoraPriority, MeshtasticConfig, MessagePriority, ReconnectConfig,
+    LORA_MAX_PAYLOAD,
+};
 use crate::error::{MeshtasticError, Result};
-use crate::interface::MeshtasticInterface;
-use crate::mapper::{NodeIdMapper, TopicMapper};
-use crate::translator::{MeshtasticPacket, MeshtasticPort, MessageTranslator};
+use crate::interface::{ConnectionState, MeshtasticInterface};
+use crate::mapper::{ChannelIndexMapper, NodeIdMapper, TopicMapper};
+use crate::proto::{self, from_radio, mesh_packet, MeshPacket, ToRadio};
+use crate::translator::{
+    message_location, MeshtasticPacket, MeshtasticPort, MessageTranslator, TELEMETRY_TOPIC,
+};
 
 #[cfg(feature = "serial")]
 use crate::interface::SerialInterface;
@@ -100,6 +111,123 @@ pub struct BridgeStats {
     pub compressed_messages: u64,
     /// Chunked messages sent (multi-packet)
     pub chunked_messages: u64,
+    /// Reliable packets acknowledged by the mesh
+    pub acked: u64,
+    /// Reliable packets that were never acknowledged after all retransmissions
+    pub timed_out: u64,
+    /// Retransmissions of unacknowledged packets
+    pub retransmissions: u64,
+    /// Airtime left in the current duty-cycle window, in milliseconds
+    pub airtime_remaining_ms: u64,
+    /// Packets currently waiting for airtime
+    pub airtime_queued: u64,
+    /// Packets delayed because the airtime budget was tight
+    pub airtime_deferred: u64,
+    /// Gossipsub messages queued for airtime instead of sent right away
+    ///
+    /// They count towards `gossipsub_to_lora` once their last packet goes out.
+    pub airtime_deferred_messages: u64,
+    /// Packets dropped because the airtime queue was full
+    pub airtime_dropped: u64,
+    /// Attempts to reconnect to the device
+    pub reconnect_attempts: u64,
+    /// High-priority gossipsub messages waiting to be forwarded to LoRa
+    pub forward_queue_high: u64,
+    /// Normal-priority gossipsub messages waiting to be forwarded to LoRa
+    pub forward_queue_normal: u64,
+    /// Low-priority gossipsub messages waiting to be forwarded to LoRa
+    pub forward_queue_low: u64,
+    /// Gossipsub messages dropped because the forward queue was full
+    pub forward_dropped: u64,
+}
+
+/// How often outstanding acknowledgements are checked for timeouts
+const ACK_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often packets waiting for airtime are retried
+const AIRTIME_DRAIN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Share of the airtime allowance only high-priority traffic may use
+const HIGH_PRIORITY_RESERVE: f64 = 0.25;
+
+/// Share of the airtime queue low-priority packets may fill
+const LOW_PRIORITY_QUEUE_SHARE: f64 = 0.25;
+
+/// An encoded packet waiting for duty-cycle airtime
+#[derive(Debug)]
+struct QueuedPacket {
+    /// The packet to send
+    packet: MeshtasticPacket,
+    /// Priority of the topic it was published on
+    priority: MessagePriority,
+    /// Encoded `ToRadio` bytes
+    encoded: Vec<u8>,
+    /// Estimated time on air
+    airtime: Duration,
+    /// Whether this is the last packet of its gossipsub message
+    completes_message: bool,
+}
+
+/// Gossipsub messages waiting to be forwarded to LoRa
+///
+/// Messages are held in one FIFO lane per topic priority and taken highest
+/// priority first, so economics traffic overtakes a backlog of chat while
+/// messages of equal priority keep their arrival order.
+#[derive(Debug, Default)]
+struct ForwardQueue {
+    high: VecDeque<GossipsubMessage>,
+    normal: VecDeque<GossipsubMessage>,
+    low: VecDeque<GossipsubMessage>,
+}
+
+impl ForwardQueue {
+    fn lane(&mut self, priority: MessagePriority) -> &mut VecDeque<GossipsubMessage> {
+        match priority {
+            MessagePriority::High => &mut self.high,
+            MessagePriority::Normal => &mut self.normal,
+            MessagePriority::Low => &mut self.low,
+        }
+    }
+
+    /// Add a message behind others of the same priority
+    fn push(&mut self, priority: MessagePriority, msg: GossipsubMessage) {
+        self.lane(priority).push_back(msg);
+    }
+
+    /// Take the oldest message of the highest priority
+    fn pop(&mut self) -> Option<GossipsubMessage> {
+        self.high
+            .pop_front()
+            .or_else(|| self.normal.pop_front())
+            .or_else(|| self.low.pop_front())
+    }
+
+    /// Drop the oldest message of the lowest priority below `priority`
+    fn evict_below(&mut self, priority: MessagePriority) -> bool {
+        [MessagePriority::Low, MessagePriority::Normal]
+            .into_iter()
+            .filter(|lane| *lane < priority)
+            .any(|lane| self.lane(lane).pop_front().is_some())
+    }
+
+    fn len(&self) -> usize {
+        self.high.len() + self.normal.len() + self.low.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A sent `want_ack` packet awaiting its routing acknowledgement
+#[derive(Debug)]
+struct PendingAck {
+    /// The packet, kept for retransmission
+    packet: MeshtasticPacket,
+    /// Retransmissions so far
+    retransmits: u32,
+    /// When to retransmit or give up
+    deadline: Instant,
 }
 
 /// Callback for publishing messages to gossipsub
@@ -110,6 +238,7 @@ pub type PublishCallback =
 #[derive(Clone)]
 pub struct BridgeHandle {
     command_tx: mpsc::Sender<BridgeCommand>,
+    connection_rx: watch::Receiver<ConnectionState>,
 }
 
 impl BridgeHandle {
@@ -131,6 +260,16 @@ impl BridgeHandle {
         rx.await.map_err(|_| MeshtasticError::ChannelClosed)
     }
 
+    /// Get the current device connection state
+    pub fn connection_state(&self) -> ConnectionState {
+        *self.connection_rx.borrow()
+    }
+
+    /// Subscribe to device connection state changes
+    pub fn watch_connection(&self) -> watch::Receiver<ConnectionState> {
+        self.connection_rx.clone()
+    }
+
     /// Shutdown the bridge
     pub async fn shutdown(&self) -> Result<()> {
         self.command_tx
@@ -140,6 +279,25 @@ impl BridgeHandle {
     }
 }
 
+/// The bridge is down once it stops or loses its device, and degraded while
+/// it is (re)connecting
+#[async_trait::async_trait]
+impl HealthCheck for BridgeHandle {
+    async fn health(&self) -> HealthStatus {
+        if self.command_tx.is_closed() {
+            return HealthStatus::down("bridge stopped");
+        }
+        match self.connection_state() {
+            ConnectionState::Connected => HealthStatus::Ok,
+            ConnectionState::Connecting => HealthStatus::degraded("connecting to device"),
+            ConnectionState::Reconnecting { attempt } => {
+                HealthStatus::degraded(format!("reconnecting to device, attempt {}", attempt))
+            }
+            ConnectionState::Disconnected => HealthStatus::down("device disconnected"),
+        }
+    }
+}
+
 /// Main bridge service connecting Meshtastic LoRa mesh to libp2p gossipsub
 pub struct MeshtasticBridge<I: MeshtasticInterface> {
     /// Meshtastic device interface
@@ -150,8 +308,10 @@ pub struct MeshtasticBridge<I: MeshtasticInterface> {
     topic_mapper: TopicMapper,
     /// Node ID mapper
     node_mapper: NodeIdMapper,
-    /// Deduplication cache
-    dedup_cache: DeduplicationCache,
+    /// Channel index and encryption key mapper
+    channel_mapper: ChannelIndexMapper,
+    /// Deduplication cache, possibly shared with other bridges
+    dedup_cache: Arc<DeduplicationCache>,
     /// Callback for publishing to gossipsub
     publish_callback: PublishCallback,
     /// Command receiver
@@ -164,6 +324,36 @@ pub struct MeshtasticBridge<I: MeshtasticInterface> {
     running: bool,
     /// Economics message codec for compression/chunking
     economics_codec: EconomicsMessageCodec,
+    /// Whether high-priority topics request acknowledgements
+    reliable_delivery: bool,
+    /// Time to wait for an acknowledgement before retransmitting
+    ack_timeout: Duration,
+    /// Maximum retransmissions per reliable packet
+    max_retransmits: u32,
+    /// Reliable packets awaiting acknowledgement, keyed by packet id
+    pending_acks: HashMap<u32, PendingAck>,
+    /// Gossipsub messages waiting to be forwarded, by topic priority
+    forward_queue: ForwardQueue,
+    /// Duty-cycle airtime budget for outbound packets
+    airtime: AirtimeBudget,
+    /// Packets waiting for airtime, in arrival order
+    outbound_queue: VecDeque<QueuedPacket>,
+    /// Maximum packets waiting for airtime
+    outbound_queue_size: usize,
+    /// File where registered node/peer mappings are persisted
+    node_map_path: Option<PathBuf>,
+    /// Whether this node's position is bridged
+    share_position: bool,
+    /// Node number of the attached device, once it has reported it
+    attached_node: Option<u32>,
+    /// Reconnection backoff settings
+    reconnect: ReconnectConfig,
+    /// Publishes device connection state to handles
+    connection_tx: watch::Sender<ConnectionState>,
+    /// Consecutive failed reconnection attempts
+    reconnect_failures: u32,
+    /// When the next reconnection attempt is due, while disconnected
+    next_reconnect: Option<Instant>,
 }
 
 impl<I: MeshtasticInterface + Send + 'static> MeshtasticBridge<I> {
@@ -172,20 +362,52 @@ impl<I: MeshtasticInterface + Send + 'static> MeshtasticBridge<I> {
         interface: I,
         config: &MeshtasticConfig,
         publish_callback: PublishCallback,
+    ) -> (Self, BridgeHandle) {
+        let dedup_cache = Arc::new(DeduplicationCache::from_config(&config.bridge));
+        Self::with_dedup_cache(interface, config, publish_callback, dedup_cache)
+    }
+
+    /// Create a bridge that deduplicates against a cache shared with others
+    ///
+    /// Bridges attached to the same gossip network through different
+    /// interfaces (say serial and TCP) must share one cache: otherwise a
+    /// message one bridge publishes to gossipsub is new to the other, which
+    /// sends it back out to LoRa. The cache's own capacity, TTL and key
+    /// hashing apply instead of those in `config.bridge`.
+    pub fn with_dedup_cache(
+        interface: I,
+        config: &MeshtasticConfig,
+        publish_callback: PublishCallback,
+        dedup_cache: Arc<DeduplicationCache>,
     ) -> (Self, BridgeHandle) {
         let node_mapper = NodeIdMapper::new();
+        if let Some(path) = &config.bridge.node_map_path {
+            match node_mapper.load(path) {
+                Ok(count) => info!("Loaded {} node mappings from {}", count, path.display()),
+                Err(e) => warn!(
+                    "Failed to load node mappings from {}: {}",
+                    path.display(),
+                    e
+                ),
+            }
+        }
         let topic_mapper = TopicMapper::from_config(&config.channels);
+        let channel_mapper = ChannelIndexMapper::from_config(&config.channels);
         let translator = MessageTranslator::new(node_mapper.clone());
-        let dedup_cache = DeduplicationCache::from_config(&config.bridge);
 
         let (command_tx, command_rx) = mpsc::channel(256);
-        let handle = BridgeHandle { command_tx };
+        let (connection_tx, connection_rx) = watch::channel(ConnectionState::Disconnected);
+        let handle = BridgeHandle {
+            command_tx,
+            connection_rx,
+        };
 
         let bridge = Self {
             interface,
             translator,
             topic_mapper,
             node_mapper,
+            channel_mapper,
             dedup_cache,
             publish_callback,
             command_rx,
@@ -193,6 +415,26 @@ impl<I: MeshtasticInterface + Send + 'static> MeshtasticBridge<I> {
             default_hop_limit: config.bridge.max_hops,
             running: false,
             economics_codec: EconomicsMessageCodec::new(),
+            reliable_delivery: config.bridge.reliable_delivery,
+            ack_timeout: config.bridge.ack_timeout,
+            max_retransmits: config.bridge.max_retransmits,
+            pending_acks: HashMap::new(),
+            forward_queue: ForwardQueue::default(),
+            airtime: AirtimeBudget::new(
+                config.bridge.spreading_factor,
+                config.bridge.bandwidth_khz,
+                config.bridge.duty_cycle_percent,
+                config.bridge.duty_cycle_window,
+            ),
+            outbound_queue: VecDeque::new(),
+            outbound_queue_size: config.bridge.outgoing_queue_size,
+            node_map_path: config.bridge.node_map_path.clone(),
+            share_position: config.bridge.share_position,
+            attached_node: None,
+            reconnect: config.reconnect.clone(),
+            connection_tx,
+            reconnect_failures: 0,
+            next_reconnect: None,
         };
 
         (bridge, handle)
@@ -210,14 +452,21 @@ impl<I: MeshtasticInterface + Send + 'static> MeshtasticBridge<I> {
         // Connect to the device
         self.interface.connect().await?;
         info!("Connected to Meshtastic device");
+        self.set_connection_state(ConnectionState::Connected);
 
         self.running = true;
 
+        let mut ack_check = tokio::time::interval(ACK_CHECK_INTERVAL);
+        let mut airtime_drain = tokio::time::interval(AIRTIME_DRAIN_INTERVAL);
+
         // Main event loop
         loop {
+            let connected = self.next_reconnect.is_none();
+            let reconnect_at = self.next_reconnect.unwrap_or_else(Instant::now);
+
             tokio::select! {
                 // Handle incoming LoRa packets
-                packet_result = self.interface.read_packet() => {
+                packet_result = self.interface.read_packet(), if connected => {
                     match packet_result {
                         Ok(Some(data)) => {
                             if let Err(e) = self.handle_lora_packet(&data).await {
@@ -233,36 +482,68 @@ impl<I: MeshtasticInterface + Send + 'static> MeshtasticBridge<I> {
                             warn!("Error reading from LoRa device: {}", e);
                             self.stats.interface_errors += 1;
 
-                            // Try to reconnect on error
-                            if let Err(reconnect_err) = self.try_reconnect().await {
-                                error!("Failed to reconnect: {}", reconnect_err);
+                            // Schedule reconnection with backoff
+                            if let Err(reconnect_err) = self.connection_lost().await {
+                                error!("Not reconnecting: {}", reconnect_err);
                                 break;
                             }
                         }
                     }
                 }
 
-                // Handle commands
+                // Reconnect once the backoff delay has passed
+                _ = tokio::time::sleep_until(reconnect_at.into()), if !connected => {
+                    if let Err(reconnect_err) = self.try_reconnect().await {
+                        error!("Failed to reconnect: {}", reconnect_err);
+                        break;
+                    }
+                }
+
+                // Handle commands, taking everything already waiting so
+                // forwards can be reordered by priority
                 Some(cmd) = self.command_rx.recv() => {
-                    match cmd {
-                        BridgeCommand::ForwardToLora(msg) => {
-                            if let Err(e) = self.forward_to_lora(msg).await {
-                                debug!("Error forwarding to LoRa: {}", e);
-                            }
-                        }
-                        BridgeCommand::GetStats(tx) => {
-                            let _ = tx.send(self.stats.clone());
-                        }
-                        BridgeCommand::Shutdown => {
-                            info!("Bridge shutdown requested");
+                    let mut shutdown = !self.handle_command(cmd);
+                    while !shutdown {
+                        let Ok(cmd) = self.command_rx.try_recv() else {
                             break;
+                        };
+                        shutdown = !self.handle_command(cmd);
+                    }
+                    if shutdown {
+                        info!("Bridge shutdown requested");
+                        break;
+                    }
+                }
+
+                // Forward the most important waiting gossipsub message
+                _ = std::future::ready(()), if connected && !self.forward_queue.is_empty() => {
+                    if let Some(msg) = self.forward_queue.pop() {
+                        if let Err(e) = self.forward_to_lora(msg).await {
+                            debug!("Error forwarding to LoRa: {}", e);
                         }
                     }
                 }
 
+                // Retransmit or give up on unacknowledged packets
+                _ = ack_check.tick(), if connected && !self.pending_acks.is_empty() => {
+                    if let Err(e) = self.retransmit_expired().await {
+                        warn!("Error retransmitting LoRa packet: {}", e);
+                        self.stats.interface_errors += 1;
+                    }
+                }
+
+                // Send queued packets as airtime frees up
+                _ = airtime_drain.tick(), if connected && !self.outbound_queue.is_empty() => {
+                    if let Err(e) = self.drain_outbound_queue().await {
+                        warn!("Error sending queued LoRa packet: {}", e);
+                        self.stats.interface_errors += 1;
+                    }
+                }
+
                 // Periodic housekeeping
                 _ = tokio::time::sleep(Duration::from_secs(30)) => {
                     self.dedup_cache.expire_old_entries();
+                    self.save_node_map();
                     trace!(
                         "Bridge stats: lora->gossip={}, gossip->lora={}, blocked={}",
                         self.stats.lora_to_gossipsub,
@@ -278,15 +559,55 @@ impl<I: MeshtasticInterface + Send + 'static> MeshtasticBridge<I> {
             }
         }
 
+        self.save_node_map();
+
         // Disconnect from device
         if let Err(e) = self.interface.disconnect().await {
             warn!("Error disconnecting from device: {}", e);
         }
+        self.set_connection_state(ConnectionState::Disconnected);
 
         info!("Meshtastic bridge stopped");
         Ok(())
     }
 
+    /// Handle a bridge command, returning `false` on shutdown
+    ///
+    /// Forward requests are queued by topic priority rather than sent here.
+    fn handle_command(&mut self, cmd: BridgeCommand) -> bool {
+        match cmd {
+            BridgeCommand::ForwardToLora(msg) => {
+                self.enqueue_forward(msg);
+                true
+            }
+            BridgeCommand::GetStats(tx) => {
+                let _ = tx.send(self.current_stats());
+                true
+            }
+            BridgeCommand::Shutdown => false,
+        }
+    }
+
+    /// Queue a gossipsub message for forwarding, by its priority
+    ///
+    /// When the queue is full the oldest message of a lower priority makes
+    /// room; if there is none, the new message is dropped.
+    fn enqueue_forward(&mut self, msg: GossipsubMessage) {
+        let message_type = serde_cbor::from_slice::<mycelial_core::Message>(&msg.data)
+            .ok()
+            .map(|message| message.message_type);
+        let priority = self.topic_mapper.priority_for(&msg.topic, message_type);
+        if self.forward_queue.len() >= self.outbound_queue_size {
+            self.stats.forward_dropped += 1;
+            if !self.forward_queue.evict_below(priority) {
+                warn!("LoRa forward queue full, dropping message on {}", msg.topic);
+                return;
+            }
+        }
+
+        self.forward_queue.push(priority, msg);
+    }
+
     /// Handle a packet received from the LoRa device
     ///
     /// This is the LoRa → gossipsub direction:
@@ -295,9 +616,19 @@ impl<I: MeshtasticInterface + Send + 'static> MeshtasticBridge<I> {
     /// 3. Translate to Mycelial message
     /// 4. Determine gossipsub topic
     /// 5. Publish to gossipsub
+    ///
+    /// Runs in a `lora_packet` span whose `correlation_id` is filled in once
+    /// the message is known, matching the id the network and node log.
     async fn handle_lora_packet(&mut self, data: &[u8]) -> Result<()> {
-        // Parse the raw packet into a MeshtasticPacket
-        let packet = self.parse_lora_packet(data)?;
+        let span = info_span!("lora_packet", correlation_id = field::Empty);
+        self.bridge_lora_packet(data).instrument(span).await
+    }
+
+    async fn bridge_lora_packet(&mut self, data: &[u8]) -> Result<()> {
+        // Decode the FromRadio protobuf into a MeshtasticPacket
+        let Some(packet) = self.parse_lora_packet(data)? else {
+            return Ok(());
+        };
 
         debug!(
             "Received LoRa packet: from=0x{:08X}, to=0x{:08X}, port={:?}, {} bytes",
@@ -308,7 +639,8 @@ impl<I: MeshtasticInterface + Send + 'static> MeshtasticBridge<I> {
         );
 
         // Check for duplicates
-        let dedup_key = DeduplicationKey::from_meshtastic(packet.from, packet.packet_id);
+        let dedup_key = DeduplicationKey::from_meshtastic(packet.from, packet.packet_id)
+            .with_channel(packet.channel);
         if self
             .dedup_cache
             .is_duplicate(&dedup_key, MessageDirection::FromLora)
@@ -318,6 +650,20 @@ impl<I: MeshtasticInterface + Send + 'static> MeshtasticBridge<I> {
             return Ok(());
         }
 
+        // Chunks are reassembled before anything is published
+        if packet.port_num == MeshtasticPort::MycelialChunk {
+            return self.handle_lora_chunk(&packet);
+        }
+
+        // Keep the attached device's GPS private unless sharing is enabled
+        if packet.port_num == MeshtasticPort::Position
+            && !self.share_position
+            && self.attached_node == Some(packet.from)
+        {
+            debug!("Position sharing disabled, not publishing local position");
+            return Ok(());
+        }
+
         // Translate to Mycelial message
         let message = match self.translator.meshtastic_to_mycelial(&packet) {
             Ok(msg) => msg,
@@ -327,6 +673,21 @@ impl<I: MeshtasticInterface + Send + 'static> MeshtasticBridge<I> {
                 return Err(e);
             }
         };
+        Span::current().record(
+            "correlation_id",
+            correlation_id(message.content_id().as_bytes()).as_str(),
+        );
+
+        // Drop messages we already bridged the other way (LoRa→gossip→LoRa loops)
+        let content_key = DeduplicationKey::from_content(&message.content_id());
+        if self
+            .dedup_cache
+            .is_duplicate(&content_key, MessageDirection::FromLora)
+        {
+            debug!("Dropping already-bridged LoRa message: {}", content_key);
+            self.stats.duplicates_blocked += 1;
+            return Ok(());
+        }
 
         // Determine the gossipsub topic based on port number
         let topic = self.port_to_topic(packet.port_num, packet.channel);
@@ -369,6 +730,14 @@ impl<I: MeshtasticInterface + Send + 'static> MeshtasticBridge<I> {
     /// 4. Check size limits
     /// 5. Send to device
     async fn forward_to_lora(&mut self, msg: GossipsubMessage) -> Result<()> {
+        let span = info_span!(
+            "gossip_to_lora",
+            correlation_id = %correlation_id(&payload_id(&msg.data))
+        );
+        self.bridge_to_lora(msg).instrument(span).await
+    }
+
+    async fn bridge_to_lora(&mut self, msg: GossipsubMessage) -> Result<()> {
         debug!(
             "Forwarding gossipsub message to LoRa: topic={}, {} bytes",
             msg.topic,
@@ -393,14 +762,52 @@ impl<I: MeshtasticInterface + Send + 'static> MeshtasticBridge<I> {
             return Ok(());
         }
 
-        // Determine hop limit based on topic priority
-        let hop_limit = self.topic_mapper.get_hop_limit(&msg.topic);
+        let message = serde_cbor::from_slice::<mycelial_core::Message>(&msg.data).ok();
+
+        if !self.share_position && message.as_ref().and_then(message_location).is_some() {
+            debug!("Position sharing disabled, not forwarding position to LoRa");
+            return Ok(());
+        }
+
+        // Gossipsub ids differ from LoRa packet ids, so also check the
+        // content-derived id of Mycelial messages
+        if let Some(message) = &message {
+            let content_key = DeduplicationKey::from_content(&message.content_id());
+            if self
+                .dedup_cache
+                .is_duplicate(&content_key, MessageDirection::FromLibp2p)
+            {
+                debug!(
+                    "Dropping already-bridged gossipsub message: {}",
+                    content_key
+                );
+                self.stats.duplicates_blocked += 1;
+                return Ok(());
+            }
+        }
+
+        // Determine hop limit from the topic's or the message type's priority
+        let priority = self
+            .topic_mapper
+            .priority_for(&msg.topic, message.as_ref().map(|m| m.message_type));
+        let hop_limit = priority.hop_limit();
+
+        // Economics payloads that cannot be sent in one packet are chunked
+        // instead of being truncated into a text message
+        let chunk_if_untranslatable =
+            Self::is_economics_topic(&msg.topic) && msg.data.len() > LORA_MAX_PAYLOAD;
 
         // Try to decode as a Mycelial Message and translate
-        let packet = match serde_cbor::from_slice::<mycelial_core::Message>(&msg.data) {
-            Ok(message) => {
+        let mut packet = match message {
+            Some(message) => {
                 match self.translator.mycelial_to_meshtastic(&message, hop_limit) {
                     Ok(pkt) => pkt,
+                    Err(e) if chunk_if_untranslatable => {
+                        debug!("Translation failed, sending in chunks: {}", e);
+                        return self
+                            .forward_chunked_to_lora(&msg, &dedup_key, priority)
+                            .await;
+                    }
                     Err(e) => {
                         // If translation fails, try sending as raw text
                         debug!("Translation failed, sending as text: {}", e);
@@ -408,12 +815,20 @@ impl<I: MeshtasticInterface + Send + 'static> MeshtasticBridge<I> {
                     }
                 }
             }
-            Err(_) => {
+            None if chunk_if_untranslatable => {
+                return self
+                    .forward_chunked_to_lora(&msg, &dedup_key, priority)
+                    .await;
+            }
+            None => {
                 // Not a CBOR message, try to send as raw text
                 self.create_text_packet(&msg.data, hop_limit)?
             }
         };
 
+        // Send on the Meshtastic channel the topic is mapped to
+        packet.channel = self.channel_index_for_topic(&msg.topic);
+
         // Check payload size
         if packet.payload.len() > LORA_MAX_PAYLOAD {
             warn!(
@@ -428,97 +843,485 @@ impl<I: MeshtasticInterface + Send + 'static> MeshtasticBridge<I> {
             });
         }
 
-        // Encode and send to device
-        let encoded = self.encode_packet(&packet)?;
-        self.interface.write_packet(&encoded).await?;
+        // Request an acknowledgement for high-priority messages
+        packet.want_ack = self.wants_ack(priority);
+
+        // Encode and send to device (or queue it until airtime is available)
+        let sent = self.send_packet(packet, priority, true).await?;
 
         // Mark as seen to prevent echo
         self.dedup_cache
             .mark_seen(&dedup_key, MessageDirection::FromLibp2p);
 
+        match sent {
+            Some(encoded_len) => {
+                info!(
+                    "Forwarded gossipsub message to LoRa: topic={}, {} bytes, hop_limit={}",
+                    msg.topic, encoded_len, hop_limit
+                );
+                self.stats.gossipsub_to_lora += 1;
+            }
+            None => {
+                debug!(
+                    "Queued gossipsub message for LoRa airtime: topic={}",
+                    msg.topic
+                );
+                self.stats.airtime_deferred_messages += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Compress and split an oversized economics message across several packets
+    ///
+    /// The gossipsub payload is sent verbatim, prefixed with the 2-byte port of
+    /// its topic so the receiving bridge can republish it on the same topic.
+    async fn forward_chunked_to_lora(
+        &mut self,
+        msg: &GossipsubMessage,
+        dedup_key: &DeduplicationKey,
+        priority: MessagePriority,
+    ) -> Result<()> {
+        let port = Self::economics_topic_to_port(&msg.topic);
+        let hop_limit = priority.hop_limit();
+
+        let mut data = Vec::with_capacity(2 + msg.data.len());
+        data.extend_from_slice(&(u32::from(port) as u16).to_be_bytes());
+        data.extend_from_slice(&msg.data);
+
+        let chunks = match self.economics_codec.chunk(&data) {
+            Ok(chunks) => chunks,
+            Err(e) => {
+                warn!("Message too large to chunk for LoRa: {}", e);
+                self.stats.oversized_messages += 1;
+                return Err(e);
+            }
+        };
+        let compressed = chunks.iter().any(|chunk| chunk.is_compressed);
+        let chunk_count = chunks.len();
+        let channel = self.channel_index_for_topic(&msg.topic);
+        let want_ack = self.wants_ack(priority);
+
+        let from = self
+            .node_mapper
+            .local_node_id()
+            .unwrap_or_else(rand::random);
+
+        let mut deferred = false;
+        for (index, chunk) in chunks.into_iter().enumerate() {
+            let packet = MeshtasticPacket {
+                from,
+                to: 0xFFFFFFFF, // Broadcast
+                packet_id: rand::random(),
+                channel,
+                port_num: MeshtasticPort::MycelialChunk,
+                payload: chunk.encode(),
+                hop_limit,
+                want_ack,
+                rx_time: Some(chrono::Utc::now()),
+            };
+            let last = index + 1 == chunk_count;
+            deferred |= self.send_packet(packet, priority, last).await?.is_none();
+        }
+
+        // Mark as seen to prevent echo
+        self.dedup_cache
+            .mark_seen(dedup_key, MessageDirection::FromLibp2p);
+
         info!(
-            "Forwarded gossipsub message to LoRa: topic={}, {} bytes, hop_limit={}",
+            "{} gossipsub message to LoRa in {} chunks: topic={}, {} bytes, compressed={}",
+            if deferred { "Queued" } else { "Forwarded" },
+            chunk_count,
             msg.topic,
-            encoded.len(),
-            hop_limit
+            msg.data.len(),
+            compressed
         );
-        self.stats.gossipsub_to_lora += 1;
+        if deferred {
+            self.stats.airtime_deferred_messages += 1;
+        } else {
+            self.stats.gossipsub_to_lora += 1;
+        }
+        self.stats.economics_messages += 1;
+        if compressed {
+            self.stats.compressed_messages += 1;
+        }
+        if chunk_count > 1 {
+            self.stats.chunked_messages += 1;
+        }
 
         Ok(())
     }
 
-    /// Parse raw bytes into a MeshtasticPacket
-    fn parse_lora_packet(&self, data: &[u8]) -> Result<MeshtasticPacket> {
-        // Meshtastic packet header format:
-        // - Magic: 0x94C3 (2 bytes, big-endian)
-        // - Length: u16 (2 bytes, big-endian)
-        // - FromRadio protobuf payload
+    /// Encode a packet and send it if the airtime budget allows
+    ///
+    /// When the budget is tight the packet is queued until airtime frees up.
+    /// `completes_message` marks the last packet of a gossipsub message, which
+    /// counts the message as forwarded once it goes out. Returns the encoded
+    /// size in bytes if the packet was sent, or `None` if it was queued.
+    async fn send_packet(
+        &mut self,
+        packet: MeshtasticPacket,
+        priority: MessagePriority,
+        completes_message: bool,
+    ) -> Result<Option<usize>> {
+        let encoded = self.encode_packet(&packet)?;
+        let encoded_len = encoded.len();
+        let airtime = self.airtime.estimate(encoded_len);
+        let now = Instant::now();
+
+        // Keep ordering: never overtake queued packets of the same or higher priority
+        let queued_ahead = self.outbound_queue.iter().any(|q| q.priority >= priority);
+        let reserve = self.airtime_reserve(priority);
+        if !queued_ahead && self.airtime.fits(airtime, reserve, now) {
+            self.transmit(packet, &encoded, airtime, now).await?;
+            return Ok(Some(encoded_len));
+        }
+
+        self.defer_packet(QueuedPacket {
+            packet,
+            priority,
+            encoded,
+            airtime,
+            completes_message,
+        })?;
+        Ok(None)
+    }
 
-        if data.len() < 4 {
-            return Err(MeshtasticError::InvalidPacket(
-                "Packet too short".to_string(),
-            ));
+    /// Write an encoded packet, charging its airtime and tracking its acknowledgement
+    async fn transmit(
+        &mut self,
+        packet: MeshtasticPacket,
+        encoded: &[u8],
+        airtime: Duration,
+        now: Instant,
+    ) -> Result<()> {
+        self.interface.write_packet(encoded).await?;
+        self.airtime.record(airtime, now);
+
+        if packet.want_ack {
+            self.pending_acks.insert(
+                packet.packet_id,
+                PendingAck {
+                    packet,
+                    retransmits: 0,
+                    deadline: now + self.ack_timeout,
+                },
+            );
         }
 
-        // For now, we'll create a simplified packet structure
-        // In a full implementation, this would use prost to decode the protobuf
-        let from = u32::from_be_bytes([data[0], data[1], data[2], data[3]]);
-        let to = if data.len() >= 8 {
-            u32::from_be_bytes([data[4], data[5], data[6], data[7]])
-        } else {
-            0xFFFFFFFF // Broadcast
-        };
-        let packet_id = if data.len() >= 12 {
-            u32::from_be_bytes([data[8], data[9], data[10], data[11]])
-        } else {
-            rand::random()
+        Ok(())
+    }
+
+    /// Queue a packet until airtime is available
+    ///
+    /// Low-priority packets may only fill [`LOW_PRIORITY_QUEUE_SHARE`] of the
+    /// queue, so a backlog of chat can't crowd out economics traffic.
+    fn defer_packet(&mut self, queued: QueuedPacket) -> Result<()> {
+        let now = Instant::now();
+        let exhausted = MeshtasticError::AirtimeExhausted {
+            needed_ms: queued.airtime.as_millis() as u64,
+            remaining_ms: self.airtime.remaining(now).as_millis() as u64,
         };
 
-        // Determine port from first byte of payload or default to TextMessage
-        let (port_num, payload_start) = if data.len() > 12 {
-            (MeshtasticPort::from(data[12] as u32), 13)
+        if queued.priority == MessagePriority::Low {
+            let low_cap =
+                ((self.outbound_queue_size as f64 * LOW_PRIORITY_QUEUE_SHARE) as usize).max(1);
+            let low_queued = self
+                .outbound_queue
+                .iter()
+                .filter(|q| q.priority == MessagePriority::Low)
+                .count();
+            if low_queued >= low_cap {
+                debug!("Dropping low-priority LoRa packet: {}", exhausted);
+                self.stats.airtime_dropped += 1;
+                return Err(exhausted);
+            }
+        }
+
+        if self.outbound_queue.len() >= self.outbound_queue_size {
+            // Make room by evicting the oldest packet of the lowest lower priority
+            let victim = self
+                .outbound_queue
+                .iter()
+                .enumerate()
+                .filter(|(_, q)| q.priority < queued.priority)
+                .min_by_key(|(_, q)| q.priority)
+                .map(|(index, _)| index);
+            match victim {
+                Some(index) => {
+                    self.outbound_queue.remove(index);
+                    self.stats.airtime_dropped += 1;
+                }
+                None => {
+                    warn!("LoRa airtime queue full, dropping packet: {}", exhausted);
+                    self.stats.airtime_dropped += 1;
+                    return Err(exhausted);
+                }
+            }
+        }
+
+        debug!(
+            "Deferring {:?}-priority LoRa packet: {}",
+            queued.priority, exhausted
+        );
+        self.outbound_queue.push_back(queued);
+        self.stats.airtime_deferred += 1;
+        Ok(())
+    }
+
+    /// Send queued packets, highest priority first, while the budget allows
+    async fn drain_outbound_queue(&mut self) -> Result<()> {
+        loop {
+            let now = Instant::now();
+            let next = self
+                .outbound_queue
+                .iter()
+                .enumerate()
+                .rev()
+                .max_by_key(|(_, q)| q.priority)
+                .map(|(index, q)| (index, q.airtime, q.priority));
+
+            let Some((index, airtime, priority)) = next else {
+                return Ok(());
+            };
+            if !self
+                .airtime
+                .fits(airtime, self.airtime_reserve(priority), now)
+            {
+                return Ok(());
+            }
+
+            if let Some(queued) = self.outbound_queue.remove(index) {
+                self.transmit(queued.packet, &queued.encoded, queued.airtime, now)
+                    .await?;
+                if queued.completes_message {
+                    self.stats.gossipsub_to_lora += 1;
+                }
+            }
+        }
+    }
+
+    /// Airtime that must stay unused for packets of the given priority
+    fn airtime_reserve(&self, priority: MessagePriority) -> Duration {
+        if priority == MessagePriority::High {
+            Duration::ZERO
         } else {
-            (MeshtasticPort::TextMessage, 12)
-        };
+            self.airtime.allowance().mul_f64(HIGH_PRIORITY_RESERVE)
+        }
+    }
+
+    /// Persist registered node/peer mappings, if configured
+    fn save_node_map(&self) {
+        if let Some(path) = &self.node_map_path {
+            if let Err(e) = self.node_mapper.save(path) {
+                warn!("Failed to save node mappings to {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    /// Statistics with the live airtime figures filled in
+    fn current_stats(&mut self) -> BridgeStats {
+        let mut stats = self.stats.clone();
+        stats.airtime_remaining_ms = self.airtime.remaining(Instant::now()).as_millis() as u64;
+        stats.airtime_queued = self.outbound_queue.len() as u64;
+        stats.forward_queue_high = self.forward_queue.high.len() as u64;
+        stats.forward_queue_normal = self.forward_queue.normal.len() as u64;
+        stats.forward_queue_low = self.forward_queue.low.len() as u64;
+        stats
+    }
+
+    /// Whether packets of a priority should request an acknowledgement
+    fn wants_ack(&self, priority: MessagePriority) -> bool {
+        self.reliable_delivery && priority.wants_ack()
+    }
 
-        let payload = if data.len() > payload_start {
-            Bytes::copy_from_slice(&data[payload_start..])
+    /// Resolve a pending packet from a routing acknowledgement
+    fn handle_routing_ack(&mut self, request_id: u32, error_reason: i32) {
+        if error_reason != proto::ROUTING_ERROR_NONE {
+            // Leave it pending so it is retransmitted when the timeout expires
+            debug!(
+                "Mesh reported delivery failure for packet 0x{:08X} (reason {})",
+                request_id, error_reason
+            );
+            return;
+        }
+
+        if self.pending_acks.remove(&request_id).is_some() {
+            debug!("Packet 0x{:08X} acknowledged", request_id);
+            self.stats.acked += 1;
         } else {
-            Bytes::new()
-        };
+            trace!("Ignoring ACK for untracked packet 0x{:08X}", request_id);
+        }
+    }
 
-        Ok(MeshtasticPacket {
-            from,
-            to,
-            packet_id,
-            channel: 0,
-            port_num,
-            payload,
-            hop_limit: 3,
-            want_ack: false,
-            rx_time: Some(chrono::Utc::now()),
-        })
+    /// Retransmit unacknowledged packets whose timeout has expired
+    ///
+    /// Packets that have used up their retransmissions are dropped and counted
+    /// as timed out.
+    async fn retransmit_expired(&mut self) -> Result<()> {
+        let now = Instant::now();
+        let expired: Vec<u32> = self
+            .pending_acks
+            .iter()
+            .filter(|(_, pending)| pending.deadline <= now)
+            .map(|(id, _)| *id)
+            .collect();
+
+        for packet_id in expired {
+            let Some(mut pending) = self.pending_acks.remove(&packet_id) else {
+                continue;
+            };
+
+            if pending.retransmits >= self.max_retransmits {
+                warn!(
+                    "Packet 0x{:08X} not acknowledged after {} retransmissions",
+                    packet_id, pending.retransmits
+                );
+                self.stats.timed_out += 1;
+                continue;
+            }
+
+            // Retransmissions are high priority but still bound by the duty cycle
+            let encoded = self.encode_packet(&pending.packet)?;
+            let airtime = self.airtime.estimate(encoded.len());
+            if !self.airtime.fits(airtime, Duration::ZERO, now) {
+                trace!("Delaying retransmission of 0x{:08X} for airtime", packet_id);
+                self.pending_acks.insert(packet_id, pending);
+                continue;
+            }
+
+            // Same packet id, so the mesh treats it as a retry rather than a new message
+            self.interface.write_packet(&encoded).await?;
+            self.airtime.record(airtime, now);
+
+            pending.retransmits += 1;
+            pending.deadline = now + self.ack_timeout;
+            debug!(
+                "Retransmitted packet 0x{:08X} (attempt {}/{})",
+                packet_id, pending.retransmits, self.max_retransmits
+            );
+            self.stats.retransmissions += 1;
+            self.pending_acks.insert(packet_id, pending);
+        }
+
+        Ok(())
     }
 
-    /// Encode a MeshtasticPacket for sending
-    fn encode_packet(&self, packet: &MeshtasticPacket) -> Result<Vec<u8>> {
-        // Create a simple packet format for sending
-        // In a full implementation, this would use prost to encode ToRadio protobuf
-        let mut encoded = Vec::with_capacity(packet.payload.len() + 16);
+    /// Feed a received chunk to the reassembler and publish completed messages
+    fn handle_lora_chunk(&mut self, packet: &MeshtasticPacket) -> Result<()> {
+        let data = match self.economics_codec.decode(&packet.payload)? {
+            Some(data) => data,
+            None => {
+                trace!(
+                    "Waiting for more chunks ({} messages pending)",
+                    self.economics_codec.pending_count()
+                );
+                return Ok(());
+            }
+        };
+
+        if data.len() < 2 {
+            return Err(MeshtasticError::ReassemblyFailed(
+                "Reassembled message is missing its port header".to_string(),
+            ));
+        }
+        Span::current().record(
+            "correlation_id",
+            correlation_id(&payload_id(&data[2..])).as_str(),
+        );
+        let port = MeshtasticPort::from(u16::from_be_bytes([data[0], data[1]]) as u32);
+        let topic = self.port_to_topic(port, packet.channel);
+
+        // Bridge by the channel configured for the topic
+        let channel = self
+            .topic_mapper
+            .topic_to_channel(&topic)
+            .map(|mapping| mapping.channel.clone())
+            .unwrap_or_else(|| self.topic_mapper.default_channel().to_string());
+        if !self.topic_mapper.should_bridge_to_libp2p(&channel) {
+            debug!("Channel not configured for libp2p bridging, skipping");
+            return Ok(());
+        }
+
+        match (self.publish_callback)(topic.clone(), data[2..].to_vec()) {
+            Ok(()) => {
+                info!(
+                    "Forwarded reassembled LoRa message to gossipsub: topic={}, {} bytes",
+                    topic,
+                    data.len() - 2
+                );
+                self.stats.lora_to_gossipsub += 1;
+                self.stats.economics_messages += 1;
+            }
+            Err(e) => {
+                warn!("Failed to publish to gossipsub: {}", e);
+            }
+        }
+
+        Ok(())
+    }
 
-        // Header
-        encoded.extend_from_slice(&packet.from.to_be_bytes());
-        encoded.extend_from_slice(&packet.to.to_be_bytes());
-        encoded.extend_from_slice(&packet.packet_id.to_be_bytes());
-        encoded.push(packet.port_num as u8);
-        encoded.push(packet.hop_limit);
-        encoded.push(packet.channel);
-        encoded.push(if packet.want_ack { 1 } else { 0 });
+    /// Decode a `FromRadio` protobuf into a MeshtasticPacket
+    ///
+    /// Returns `None` for device messages that do not carry a mesh packet
+    /// (node info, config progress, reboot notices) and for routing
+    /// acknowledgements, which are consumed here.
+    fn parse_lora_packet(&mut self, data: &[u8]) -> Result<Option<MeshtasticPacket>> {
+        let from_radio = proto::decode_from_radio(data)?;
+
+        match from_radio.payload_variant {
+            Some(from_radio::PayloadVariant::Packet(mut mesh)) => {
+                if let Some(mesh_packet::PayloadVariant::Encrypted(_)) = mesh.payload_variant {
+                    let key = u8::try_from(mesh.channel)
+                        .ok()
+                        .and_then(|index| self.channel_mapper.key_for(index))
+                        .ok_or_else(|| {
+                            MeshtasticError::InvalidPacket(format!(
+                                "No key configured for encrypted channel {}",
+                                mesh.channel
+                            ))
+                        })?;
+                    mesh.decrypt(key)?;
+                }
+                if let Some((request_id, error_reason)) = proto::routing_ack(&mesh) {
+                    self.handle_routing_ack(request_id, error_reason);
+                    return Ok(None);
+                }
+                MeshtasticPacket::try_from(&mesh).map(Some)
+            }
+            Some(from_radio::PayloadVariant::MyInfo(info)) => {
+                debug!("Attached device node number: 0x{:08X}", info.my_node_num);
+                self.attached_node = Some(info.my_node_num);
+                Ok(None)
+            }
+            Some(other) => {
+                trace!("Ignoring non-packet FromRadio message: {:?}", other);
+                Ok(None)
+            }
+            None => Err(MeshtasticError::InvalidPacket(
+                "Empty FromRadio message".to_string(),
+            )),
+        }
+    }
 
-        // Payload
-        encoded.extend_from_slice(&packet.payload);
+    /// Encode a MeshtasticPacket as a `ToRadio` protobuf for sending
+    ///
+    /// The payload is encrypted when the packet's channel has a pre-shared key.
+    fn encode_packet(&self, packet: &MeshtasticPacket) -> Result<Vec<u8>> {
+        let mut mesh_packet = MeshPacket::from(packet);
+        if let Some(key) = self.channel_mapper.key_for(packet.channel) {
+            mesh_packet.encrypt(key)?;
+        }
+        proto::encode_to_radio(&ToRadio::packet(mesh_packet))
+    }
 
-        Ok(encoded)
+    /// Channel index a topic is mapped to (primary channel if unmapped)
+    fn channel_index_for_topic(&self, topic: &str) -> u8 {
+        self.topic_mapper
+            .topic_to_channel(topic)
+            .and_then(|mapping| self.channel_mapper.name_to_index(&mapping.channel))
+            .unwrap_or_else(|| self.channel_mapper.primary_index())
     }
 
     /// Create a text message packet from raw data
@@ -559,10 +1362,22 @@ impl<I: MeshtasticInterface + Send + 'static> MeshtasticBridge<I> {
             MeshtasticPort::MycelialResource => "/mycelial/1.0.0/resource".to_string(),
             MeshtasticPort::NodeInfo => "/mycelial/1.0.0/announce".to_string(),
             MeshtasticPort::Position => "/mycelial/1.0.0/announce".to_string(),
+            MeshtasticPort::Telemetry => TELEMETRY_TOPIC.to_string(),
             _ => "/mycelial/1.0.0/chat".to_string(), // Default to chat
         }
     }
 
+    /// Map an economics topic to its Meshtastic port
+    fn economics_topic_to_port(topic: &str) -> MeshtasticPort {
+        match topic {
+            "/mycelial/1.0.0/vouch" => MeshtasticPort::MycelialVouch,
+            "/mycelial/1.0.0/credit" => MeshtasticPort::MycelialCredit,
+            "/mycelial/1.0.0/governance" => MeshtasticPort::MycelialGovernance,
+            "/mycelial/1.0.0/resource" => MeshtasticPort::MycelialResource,
+            _ => MeshtasticPort::PrivateApp,
+        }
+    }
+
     /// Check if a topic is an economics protocol topic
     fn is_economics_topic(topic: &str) -> bool {
         matches!(
@@ -585,22 +1400,76 @@ impl<I: MeshtasticInterface + Send + 'static> MeshtasticBridge<I> {
         )
     }
 
-    /// Try to reconnect to the device
-    async fn try_reconnect(&mut self) -> Result<()> {
-        warn!("Attempting to reconnect to Meshtastic device...");
-
+    /// Handle loss of the device connection by scheduling a reconnect
+    ///
+    /// Returns an error if reconnection is disabled.
+    async fn connection_lost(&mut self) -> Result<()> {
         // Disconnect first (ignore errors)
         let _ = self.interface.disconnect().await;
 
-        // Wait before reconnecting
-        tokio::time::sleep(Duration::from_secs(2)).await;
-
-        // Try to reconnect
-        self.interface.connect().await?;
+        self.reconnect_failures = 0;
+        if !self.reconnect.should_retry(0) {
+            self.set_connection_state(ConnectionState::Disconnected);
+            return Err(MeshtasticError::Disconnected);
+        }
 
-        info!("Successfully reconnected to Meshtastic device");
+        let delay = self.reconnect.backoff_delay(0, rand::random());
+        warn!("Lost Meshtastic device, reconnecting in {:?}", delay);
+        self.next_reconnect = Some(Instant::now() + delay);
+        self.set_connection_state(ConnectionState::Reconnecting { attempt: 1 });
         Ok(())
     }
+
+    /// Try to reconnect to the device
+    ///
+    /// On failure the next attempt is scheduled with exponential backoff,
+    /// capped at the configured maximum delay. Returns an error only once the
+    /// configured attempt limit is exhausted.
+    async fn try_reconnect(&mut self) -> Result<()> {
+        let attempt = self.reconnect_failures + 1;
+        self.stats.reconnect_attempts += 1;
+        self.set_connection_state(ConnectionState::Reconnecting { attempt });
+        warn!(
+            "Attempting to reconnect to Meshtastic device (attempt {})...",
+            attempt
+        );
+
+        match self.interface.connect().await {
+            Ok(()) => {
+                self.reconnect_failures = 0;
+                self.next_reconnect = None;
+                self.set_connection_state(ConnectionState::Connected);
+                info!("Successfully reconnected to Meshtastic device");
+                Ok(())
+            }
+            Err(e) => {
+                self.reconnect_failures = attempt;
+                if !self.reconnect.should_retry(self.reconnect_failures) {
+                    self.next_reconnect = None;
+                    self.set_connection_state(ConnectionState::Disconnected);
+                    return Err(e);
+                }
+
+                let delay = self
+                    .reconnect
+                    .backoff_delay(self.reconnect_failures, rand::random());
+                warn!(
+                    "Reconnect attempt {} failed: {}; retrying in {:?}",
+                    attempt, e, delay
+                );
+                self.next_reconnect = Some(Instant::now() + delay);
+                self.set_connection_state(ConnectionState::Reconnecting {
+                    attempt: attempt + 1,
+                });
+                Ok(())
+            }
+        }
+    }
+
+    /// Record and publish the device connection state
+    fn set_connection_state(&self, state: ConnectionState) {
+        self.connection_tx.send_replace(state);
+    }
 }
 
 /// Create a bridge with a mock interface for testing
@@ -624,6 +1493,7 @@ pub struct MockInterface {
     connected: bool,
     incoming: Vec<Vec<u8>>,
     outgoing: Vec<Vec<u8>>,
+    failing_connects: u32,
 }
 
 #[cfg(test)]
@@ -641,6 +1511,7 @@ impl MockInterface {
             connected: false,
             incoming: Vec::new(),
             outgoing: Vec::new(),
+            failing_connects: 0,
         }
     }
 
@@ -654,6 +1525,10 @@ impl MockInterface {
 #[async_trait::async_trait]
 impl MeshtasticInterface for MockInterface {
     async fn connect(&mut self) -> Result<()> {
+        if self.failing_connects > 0 {
+            self.failing_connects -= 1;
+            return Err(MeshtasticError::Disconnected);
+        }
         self.connected = true;
         Ok(())
     }
@@ -688,6 +1563,26 @@ impl MeshtasticInterface for MockInterface {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use prost::Message;
+
+    fn test_packet(port_num: MeshtasticPort, payload: Bytes) -> MeshtasticPacket {
+        MeshtasticPacket {
+            from: 0x12345678,
+            to: 0xFFFFFFFF,
+            packet_id: 1,
+            channel: 0,
+            port_num,
+            payload,
+            hop_limit: 3,
+            want_ack: false,
+            rx_time: None,
+        }
+    }
+
+    fn from_radio_bytes(port_num: MeshtasticPort, payload: Bytes) -> Vec<u8> {
+        let packet = test_packet(port_num, payload);
+        proto::FromRadio::packet(MeshPacket::from(&packet)).encode_to_vec()
+    }
 
     #[tokio::test]
     async fn test_bridge_creation() {
@@ -696,6 +1591,24 @@ mod tests {
         drop(handle);
     }
 
+    #[tokio::test]
+    async fn test_bridge_health() {
+        let (bridge, handle) = create_test_bridge();
+        assert_eq!(
+            handle.health().await,
+            HealthStatus::down("device disconnected")
+        );
+
+        bridge.set_connection_state(ConnectionState::Reconnecting { attempt: 2 });
+        assert!(matches!(handle.health().await, HealthStatus::Degraded(_)));
+
+        bridge.set_connection_state(ConnectionState::Connected);
+        assert_eq!(handle.health().await, HealthStatus::Ok);
+
+        drop(bridge);
+        assert_eq!(handle.health().await, HealthStatus::down("bridge stopped"));
+    }
+
     #[tokio::test]
     async fn test_bridge_forward_to_lora() {
         let (mut bridge, _handle) = create_test_bridge();
@@ -719,13 +1632,10 @@ mod tests {
     async fn test_bridge_handle_lora_packet() {
         let (mut bridge, _handle) = create_test_bridge();
 
-        // Create a simple test packet
-        let mut packet_data = Vec::new();
-        packet_data.extend_from_slice(&0x12345678u32.to_be_bytes()); // from
-        packet_data.extend_from_slice(&0xFFFFFFFFu32.to_be_bytes()); // to (broadcast)
-        packet_data.extend_from_slice(&0x00000001u32.to_be_bytes()); // packet_id
-        packet_data.push(MeshtasticPort::TextMessage as u8); // port
-        packet_data.extend_from_slice(b"Hello from LoRa!"); // payload
+        let packet_data = from_radio_bytes(
+            MeshtasticPort::TextMessage,
+            Bytes::from_static(b"Hello from LoRa!"),
+        );
 
         let result = bridge.handle_lora_packet(&packet_data).await;
         assert!(result.is_ok());
@@ -755,6 +1665,60 @@ mod tests {
         assert_eq!(bridge.stats.duplicates_blocked, 1);
     }
 
+    #[tokio::test]
+    async fn test_shared_dedup_cache_blocks_cross_bridge_echo() {
+        use crate::config::MeshtasticConfigBuilder;
+        use std::sync::Mutex;
+
+        let config = MeshtasticConfigBuilder::new().build();
+        let published = Arc::new(Mutex::new(Vec::new()));
+        let sink = published.clone();
+        let publish_callback: PublishCallback = Arc::new(move |topic, data| {
+            sink.lock().unwrap().push((topic, data));
+            Ok(())
+        });
+        let shared = Arc::new(DeduplicationCache::from_config(&config.bridge));
+        let (mut serial, _) = MeshtasticBridge::with_dedup_cache(
+            MockInterface::new(),
+            &config,
+            publish_callback.clone(),
+            shared.clone(),
+        );
+        let (mut tcp, _) = MeshtasticBridge::with_dedup_cache(
+            MockInterface::new(),
+            &config,
+            publish_callback,
+            shared,
+        );
+        tcp.interface.connect().await.unwrap();
+
+        // The serial bridge publishes a LoRa message to gossipsub...
+        let packet_data = from_radio_bytes(
+            MeshtasticPort::TextMessage,
+            Bytes::from_static(b"Hello from LoRa!"),
+        );
+        serial.handle_lora_packet(&packet_data).await.unwrap();
+        let (topic, data) = published.lock().unwrap().pop().unwrap();
+
+        // ...and the TCP bridge must not send it back out to LoRa
+        let echo = GossipsubMessage {
+            topic,
+            source: Some("local_peer".to_string()),
+            data,
+            message_id: "gossip-echo".to_string(),
+        };
+        tcp.forward_to_lora(echo.clone()).await.unwrap();
+        assert_eq!(tcp.stats.gossipsub_to_lora, 0);
+        assert_eq!(tcp.stats.duplicates_blocked, 1);
+        assert!(tcp.interface.outgoing.is_empty());
+
+        // A bridge with its own cache has no way to tell
+        let (mut lone, _) = create_test_bridge();
+        lone.interface.connect().await.unwrap();
+        lone.forward_to_lora(echo).await.unwrap();
+        assert_eq!(lone.stats.gossipsub_to_lora, 1);
+    }
+
     #[test]
     fn test_port_to_topic_mapping() {
         let (bridge, _handle) = create_test_bridge();
@@ -775,6 +1739,10 @@ mod tests {
             bridge.port_to_topic(MeshtasticPort::MycelialGovernance, 0),
             "/mycelial/1.0.0/governance"
         );
+        assert_eq!(
+            bridge.port_to_topic(MeshtasticPort::Telemetry, 0),
+            "/mycelial/1.0.0/telemetry"
+        );
     }
 
     // ========================================================================
@@ -879,12 +1847,10 @@ mod tests {
         let (mut bridge, _handle) = create_test_bridge();
 
         // Create a Vouch economics packet
-        let mut packet_data = Vec::new();
-        packet_data.extend_from_slice(&0x12345678u32.to_be_bytes()); // from
-        packet_data.extend_from_slice(&0xFFFFFFFFu32.to_be_bytes()); // to (broadcast)
-        packet_data.extend_from_slice(&0x00000001u32.to_be_bytes()); // packet_id
-        packet_data.extend_from_slice(&(MeshtasticPort::MycelialVouch as u32).to_be_bytes()); // vouch port
-        packet_data.extend_from_slice(b"vouch_payload"); // payload
+        let packet_data = from_radio_bytes(
+            MeshtasticPort::MycelialVouch,
+            Bytes::from_static(b"vouch_payload"),
+        );
 
         let result = bridge.handle_lora_packet(&packet_data).await;
         assert!(result.is_ok());
@@ -897,4 +1863,564 @@ mod tests {
         // Verify economics codec is initialized
         assert_eq!(bridge.economics_codec.pending_count(), 0);
     }
+
+    #[tokio::test]
+    async fn test_bridge_ignores_non_packet_from_radio() {
+        let (mut bridge, _handle) = create_test_bridge();
+
+        let my_info = proto::FromRadio {
+            id: 1,
+            payload_variant: Some(from_radio::PayloadVariant::MyInfo(proto::MyNodeInfo {
+                my_node_num: 0x12345678,
+            })),
+        };
+
+        let result = bridge.handle_lora_packet(&my_info.encode_to_vec()).await;
+        assert!(result.is_ok());
+        assert_eq!(bridge.stats.lora_to_gossipsub, 0);
+    }
+
+    #[tokio::test]
+    async fn test_bridge_rejects_simplified_packet() {
+        let (mut bridge, _handle) = create_test_bridge();
+
+        let legacy = proto::simplified::encode(&test_packet(
+            MeshtasticPort::TextMessage,
+            Bytes::from_static(b"Hello from LoRa!"),
+        ));
+
+        assert!(bridge.handle_lora_packet(&legacy).await.is_err());
+        assert_eq!(bridge.stats.lora_to_gossipsub, 0);
+    }
+
+    #[tokio::test]
+    async fn test_bridge_writes_to_radio_protobuf() {
+        let (mut bridge, _handle) = create_test_bridge();
+        bridge.interface.connect().await.unwrap();
+
+        let msg = GossipsubMessage {
+            topic: "/mycelial/1.0.0/chat".to_string(),
+            data: b"Hello LoRa".to_vec(),
+            source: Some("peer".to_string()),
+            message_id: "proto-test".to_string(),
+        };
+        bridge.forward_to_lora(msg).await.unwrap();
+
+        let written = &bridge.interface.outgoing[0];
+        let to_radio = ToRadio::decode(written.as_slice()).unwrap();
+        match to_radio.payload_variant {
+            Some(proto::to_radio::PayloadVariant::Packet(mesh_packet)) => {
+                let packet = MeshtasticPacket::try_from(&mesh_packet).unwrap();
+                assert_eq!(packet.port_num, MeshtasticPort::TextMessage);
+                assert_eq!(packet.payload.as_ref(), b"Hello LoRa");
+            }
+            other => panic!("unexpected ToRadio payload: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bridge_chunks_oversized_economics_message() {
+        let (mut bridge, _handle) = create_test_bridge();
+        bridge.interface.connect().await.unwrap();
+
+        // Low-redundancy payload so compression alone cannot make it fit
+        let data: Vec<u8> = (0u32..800)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+            .collect();
+        let msg = GossipsubMessage {
+            topic: "/mycelial/1.0.0/governance".to_string(),
+            source: Some("test_peer".to_string()),
+            data: data.clone(),
+            message_id: "big-proposal".to_string(),
+        };
+
+        bridge.forward_to_lora(msg).await.unwrap();
+        assert_eq!(bridge.stats.gossipsub_to_lora, 1);
+        assert_eq!(bridge.stats.chunked_messages, 1);
+        assert_eq!(bridge.stats.oversized_messages, 0);
+        assert!(bridge.interface.outgoing.len() > 1);
+
+        // Loop the transmitted packets back in as received packets
+        let outgoing = std::mem::take(&mut bridge.interface.outgoing);
+        for written in outgoing {
+            let to_radio = ToRadio::decode(written.as_slice()).unwrap();
+            let Some(proto::to_radio::PayloadVariant::Packet(mesh_packet)) =
+                to_radio.payload_variant
+            else {
+                panic!("expected a mesh packet");
+            };
+            assert!(
+                MeshtasticPacket::try_from(&mesh_packet)
+                    .unwrap()
+                    .payload
+                    .len()
+                    <= LORA_MAX_PAYLOAD
+            );
+            let from_radio = proto::FromRadio::packet(mesh_packet).encode_to_vec();
+            bridge.handle_lora_packet(&from_radio).await.unwrap();
+        }
+
+        assert_eq!(bridge.stats.lora_to_gossipsub, 1);
+        assert_eq!(bridge.economics_codec.pending_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_chunked_message_respects_topic_channel() {
+        use crate::config::{BridgeDirection, ChannelMapping};
+
+        let (mut bridge, _handle) = create_test_bridge();
+        bridge.interface.connect().await.unwrap();
+
+        // Governance goes out over LoRa on its own channel but never comes back
+        bridge.topic_mapper.add_mapping(
+            "/mycelial/1.0.0/governance".to_string(),
+            ChannelMapping {
+                channel: "Admin".to_string(),
+                direction: BridgeDirection::Libp2pToLora,
+                priority: Some(MessagePriority::High),
+            },
+        );
+
+        let data: Vec<u8> = (0u32..800)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+            .collect();
+        let msg = GossipsubMessage {
+            topic: "/mycelial/1.0.0/governance".to_string(),
+            source: Some("test_peer".to_string()),
+            data,
+            message_id: "outbound-proposal".to_string(),
+        };
+        bridge.forward_to_lora(msg).await.unwrap();
+
+        for written in std::mem::take(&mut bridge.interface.outgoing) {
+            let to_radio = ToRadio::decode(written.as_slice()).unwrap();
+            let Some(proto::to_radio::PayloadVariant::Packet(mesh_packet)) =
+                to_radio.payload_variant
+            else {
+                panic!("expected a mesh packet");
+            };
+            let from_radio = proto::FromRadio::packet(mesh_packet).encode_to_vec();
+            bridge.handle_lora_packet(&from_radio).await.unwrap();
+        }
+
+        assert_eq!(bridge.stats.lora_to_gossipsub, 0);
+    }
+
+    #[tokio::test]
+    async fn test_bridge_encrypts_keyed_channel() {
+        use crate::config::MeshtasticConfigBuilder;
+
+        let key = [0x42u8; 32];
+        let config = MeshtasticConfigBuilder::new()
+            .channel_psk("Primary", key)
+            .build();
+        let publish_callback: PublishCallback = Arc::new(|_, _| Ok(()));
+        let (mut bridge, _handle) =
+            MeshtasticBridge::new(MockInterface::new(), &config, publish_callback);
+        bridge.interface.connect().await.unwrap();
+
+        let msg = GossipsubMessage {
+            topic: "/mycelial/1.0.0/chat".to_string(),
+            source: Some("test_peer".to_string()),
+            data: b"secret hello".to_vec(),
+            message_id: "encrypted-1".to_string(),
+        };
+        bridge.forward_to_lora(msg).await.unwrap();
+
+        let written = bridge.interface.outgoing.remove(0);
+        let to_radio = ToRadio::decode(written.as_slice()).unwrap();
+        let Some(proto::to_radio::PayloadVariant::Packet(mut mesh)) = to_radio.payload_variant
+        else {
+            panic!("expected a mesh packet");
+        };
+        assert_eq!(mesh.channel, 0);
+        assert!(matches!(
+            mesh.payload_variant,
+            Some(mesh_packet::PayloadVariant::Encrypted(_))
+        ));
+
+        // A receiving bridge with the same key decodes the packet
+        let from_radio = proto::FromRadio::packet(mesh.clone()).encode_to_vec();
+        let packet = bridge.parse_lora_packet(&from_radio).unwrap().unwrap();
+        assert_eq!(packet.payload.as_ref(), b"secret hello");
+
+        // Without a key, the payload cannot be read
+        let (mut plain_bridge, _handle) = create_test_bridge();
+        assert!(plain_bridge.parse_lora_packet(&from_radio).is_err());
+
+        mesh.decrypt(&key).unwrap();
+        assert!(matches!(
+            mesh.payload_variant,
+            Some(mesh_packet::PayloadVariant::Decoded(_))
+        ));
+    }
+
+    fn routing_ack_bytes(request_id: u32, error_reason: i32) -> Vec<u8> {
+        let routing = proto::Routing {
+            variant: Some(proto::routing::Variant::ErrorReason(error_reason)),
+        };
+        let mesh = MeshPacket {
+            from: 0x12345678,
+            to: 0x87654321,
+            id: rand::random(),
+            payload_variant: Some(mesh_packet::PayloadVariant::Decoded(proto::Data {
+                portnum: MeshtasticPort::Routing as i32,
+                payload: routing.encode_to_vec(),
+                request_id,
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        proto::FromRadio::packet(mesh).encode_to_vec()
+    }
+
+    fn sent_packet_id(written: &[u8]) -> (u32, bool) {
+        let to_radio = ToRadio::decode(written).unwrap();
+        match to_radio.payload_variant {
+            Some(proto::to_radio::PayloadVariant::Packet(mesh)) => (mesh.id, mesh.want_ack),
+            other => panic!("unexpected ToRadio payload: {:?}", other),
+        }
+    }
+
+    fn vouch_message(message_id: &str) -> GossipsubMessage {
+        GossipsubMessage {
+            topic: "/mycelial/1.0.0/vouch".to_string(),
+            source: Some("test_peer".to_string()),
+            data: b"vouch".to_vec(),
+            message_id: message_id.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_want_ack_only_for_high_priority() {
+        let (mut bridge, _handle) = create_test_bridge();
+        bridge.interface.connect().await.unwrap();
+
+        bridge
+            .forward_to_lora(vouch_message("vouch-1"))
+            .await
+            .unwrap();
+        let chat = GossipsubMessage {
+            topic: "/mycelial/1.0.0/chat".to_string(),
+            source: Some("test_peer".to_string()),
+            data: b"hi".to_vec(),
+            message_id: "chat-1".to_string(),
+        };
+        bridge.forward_to_lora(chat).await.unwrap();
+
+        assert!(sent_packet_id(&bridge.interface.outgoing[0]).1);
+        assert!(!sent_packet_id(&bridge.interface.outgoing[1]).1);
+        assert_eq!(bridge.pending_acks.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_routing_ack_resolves_pending() {
+        let (mut bridge, _handle) = create_test_bridge();
+        bridge.interface.connect().await.unwrap();
+
+        bridge
+            .forward_to_lora(vouch_message("vouch-1"))
+            .await
+            .unwrap();
+        let (packet_id, _) = sent_packet_id(&bridge.interface.outgoing[0]);
+
+        // A failure report keeps the packet pending
+        bridge
+            .handle_lora_packet(&routing_ack_bytes(packet_id, 5))
+            .await
+            .unwrap();
+        assert_eq!(bridge.pending_acks.len(), 1);
+
+        bridge
+            .handle_lora_packet(&routing_ack_bytes(packet_id, proto::ROUTING_ERROR_NONE))
+            .await
+            .unwrap();
+        assert!(bridge.pending_acks.is_empty());
+        assert_eq!(bridge.stats.acked, 1);
+        assert_eq!(bridge.stats.lora_to_gossipsub, 0);
+    }
+
+    #[tokio::test]
+    async fn test_retransmit_then_time_out() {
+        let (mut bridge, _handle) = create_test_bridge();
+        bridge.interface.connect().await.unwrap();
+        bridge.ack_timeout = Duration::ZERO;
+        bridge.max_retransmits = 2;
+
+        bridge
+            .forward_to_lora(vouch_message("vouch-1"))
+            .await
+            .unwrap();
+        let (packet_id, _) = sent_packet_id(&bridge.interface.outgoing[0]);
+
+        bridge.retransmit_expired().await.unwrap();
+        bridge.retransmit_expired().await.unwrap();
+        assert_eq!(bridge.stats.retransmissions, 2);
+        assert_eq!(bridge.interface.outgoing.len(), 3);
+        assert_eq!(sent_packet_id(&bridge.interface.outgoing[2]).0, packet_id);
+
+        bridge.retransmit_expired().await.unwrap();
+        assert_eq!(bridge.stats.timed_out, 1);
+        assert!(bridge.pending_acks.is_empty());
+        assert_eq!(bridge.interface.outgoing.len(), 3);
+    }
+
+    fn chat_message(message_id: &str) -> GossipsubMessage {
+        GossipsubMessage {
+            topic: "/mycelial/1.0.0/chat".to_string(),
+            source: Some("test_peer".to_string()),
+            data: b"hello".to_vec(),
+            message_id: message_id.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_airtime_exhausted_queues_and_drops() {
+        use crate::config::{BridgeDirection, ChannelMapping};
+
+        let (mut bridge, _handle) = create_test_bridge();
+        bridge.interface.connect().await.unwrap();
+        bridge.airtime = AirtimeBudget::new(11, 250, 0.0, Duration::from_secs(3600));
+        bridge.topic_mapper.add_mapping(
+            "/mycelial/1.0.0/status".to_string(),
+            ChannelMapping {
+                channel: "Primary".to_string(),
+                direction: BridgeDirection::Bidirectional,
+                priority: Some(MessagePriority::Low),
+            },
+        );
+
+        bridge.outbound_queue_size = 4;
+
+        // Normal priority waits for airtime, and isn't counted as forwarded
+        bridge
+            .forward_to_lora(chat_message("chat-1"))
+            .await
+            .unwrap();
+        assert!(bridge.interface.outgoing.is_empty());
+        assert_eq!(bridge.outbound_queue.len(), 1);
+        assert_eq!(bridge.stats.gossipsub_to_lora, 0);
+
+        // Low priority waits too, up to its share of the queue
+        let status = |id: &str| GossipsubMessage {
+            topic: "/mycelial/1.0.0/status".to_string(),
+            ..chat_message(id)
+        };
+        bridge.forward_to_lora(status("status-1")).await.unwrap();
+        assert!(matches!(
+            bridge.forward_to_lora(status("status-2")).await,
+            Err(MeshtasticError::AirtimeExhausted { .. })
+        ));
+
+        let stats = bridge.current_stats();
+        assert_eq!(stats.airtime_remaining_ms, 0);
+        assert_eq!(stats.airtime_queued, 2);
+        assert_eq!(stats.airtime_deferred, 2);
+        assert_eq!(stats.airtime_deferred_messages, 2);
+        assert_eq!(stats.airtime_dropped, 1);
+        assert_eq!(stats.gossipsub_to_lora, 0);
+
+        // Queued messages count as forwarded once they go out
+        bridge.airtime = AirtimeBudget::new(11, 250, 100.0, Duration::from_secs(3600));
+        bridge.drain_outbound_queue().await.unwrap();
+        assert_eq!(bridge.interface.outgoing.len(), 2);
+        assert_eq!(bridge.stats.gossipsub_to_lora, 2);
+    }
+
+    #[tokio::test]
+    async fn test_high_priority_preempts_queued_chat() {
+        let (mut bridge, _handle) = create_test_bridge();
+        bridge.interface.connect().await.unwrap();
+        bridge.airtime = AirtimeBudget::new(11, 250, 0.0, Duration::from_secs(3600));
+
+        bridge
+            .forward_to_lora(chat_message("chat-1"))
+            .await
+            .unwrap();
+        bridge
+            .forward_to_lora(vouch_message("vouch-1"))
+            .await
+            .unwrap();
+        assert_eq!(bridge.outbound_queue.len(), 2);
+
+        // Airtime becomes available: the vouch goes first
+        bridge.airtime = AirtimeBudget::new(11, 250, 100.0, Duration::from_secs(3600));
+        bridge.drain_outbound_queue().await.unwrap();
+
+        assert_eq!(bridge.interface.outgoing.len(), 2);
+        assert!(sent_packet_id(&bridge.interface.outgoing[0]).1);
+        assert!(!sent_packet_id(&bridge.interface.outgoing[1]).1);
+        assert!(bridge.outbound_queue.is_empty());
+
+        // The ACK deadline starts when the packet actually goes out
+        assert_eq!(bridge.pending_acks.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_reserve_holds_back_normal_priority() {
+        let (mut bridge, _handle) = create_test_bridge();
+        bridge.interface.connect().await.unwrap();
+
+        // 10s allowance with a 2.5s reserve; leave 2.7s, which is less than
+        // any packet at SF11 (>350ms) plus the reserve
+        bridge.airtime = AirtimeBudget::new(11, 250, 10.0, Duration::from_secs(100));
+        bridge
+            .airtime
+            .record(Duration::from_millis(7300), Instant::now());
+
+        bridge
+            .forward_to_lora(chat_message("chat-1"))
+            .await
+            .unwrap();
+        assert!(bridge.interface.outgoing.is_empty());
+
+        bridge
+            .forward_to_lora(vouch_message("vouch-1"))
+            .await
+            .unwrap();
+        assert_eq!(bridge.interface.outgoing.len(), 1);
+        assert!(sent_packet_id(&bridge.interface.outgoing[0]).1);
+    }
+
+    #[tokio::test]
+    async fn test_forward_queue_orders_by_priority() {
+        let (mut bridge, handle) = create_test_bridge();
+        bridge.interface.connect().await.unwrap();
+
+        // A burst of chat arrives ahead of a vouch
+        for i in 0..3 {
+            handle
+                .forward_to_lora(chat_message(&format!("chat-{}", i)))
+                .await
+                .unwrap();
+        }
+        handle
+            .forward_to_lora(vouch_message("vouch-1"))
+            .await
+            .unwrap();
+        while let Ok(cmd) = bridge.command_rx.try_recv() {
+            assert!(bridge.handle_command(cmd));
+        }
+
+        let stats = bridge.current_stats();
+        assert_eq!(stats.forward_queue_high, 1);
+        assert_eq!(stats.forward_queue_normal, 3);
+        assert_eq!(stats.forward_queue_low, 0);
+
+        let order: Vec<String> = std::iter::from_fn(|| bridge.forward_queue.pop())
+            .map(|msg| msg.message_id)
+            .collect();
+        assert_eq!(order, ["vouch-1", "chat-0", "chat-1", "chat-2"]);
+    }
+
+    #[tokio::test]
+    async fn test_full_forward_queue_evicts_lower_priority() {
+        let (mut bridge, _handle) = create_test_bridge();
+        bridge.outbound_queue_size = 2;
+
+        bridge.enqueue_forward(chat_message("chat-1"));
+        bridge.enqueue_forward(chat_message("chat-2"));
+
+        // Equal priority cannot displace queued chat
+        bridge.enqueue_forward(chat_message("chat-3"));
+        assert_eq!(bridge.forward_queue.len(), 2);
+        assert_eq!(bridge.stats.forward_dropped, 1);
+
+        // A vouch evicts the oldest chat
+        bridge.enqueue_forward(vouch_message("vouch-1"));
+        assert_eq!(bridge.stats.forward_dropped, 2);
+        let order: Vec<String> = std::iter::from_fn(|| bridge.forward_queue.pop())
+            .map(|msg| msg.message_id)
+            .collect();
+        assert_eq!(order, ["vouch-1", "chat-2"]);
+    }
+
+    #[test]
+    fn test_bridge_restores_node_map() {
+        use crate::config::MeshtasticConfigBuilder;
+        use mycelial_core::{Keypair, PeerId};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nodes.json");
+
+        let previous = NodeIdMapper::new();
+        let peer_id = PeerId::from_public_key(&Keypair::generate().public_key());
+        previous.register(0x0000BEEF, peer_id.clone());
+        previous.save(&path).unwrap();
+
+        let config = MeshtasticConfigBuilder::new().node_map_path(&path).build();
+        let publish_callback: PublishCallback = Arc::new(|_, _| Ok(()));
+        let (bridge, _handle) =
+            MeshtasticBridge::new(MockInterface::new(), &config, publish_callback);
+
+        // Attribution survives the restart
+        assert_eq!(
+            bridge.node_mapper.node_to_peer(0x0000BEEF).unwrap(),
+            peer_id
+        );
+
+        // New registrations are written back
+        let other = PeerId::from_public_key(&Keypair::generate().public_key());
+        bridge.node_mapper.register(0x0000CAFE, other.clone());
+        bridge.save_node_map();
+
+        let reloaded = NodeIdMapper::new();
+        assert_eq!(reloaded.load(&path).unwrap(), 2);
+        assert_eq!(reloaded.node_to_peer(0x0000CAFE).unwrap(), other);
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_backs_off_until_device_returns() {
+        use crate::config::MeshtasticConfigBuilder;
+
+        let config = MeshtasticConfigBuilder::new()
+            .reconnect_backoff(Duration::from_millis(100), Duration::from_millis(400))
+            .build();
+        let publish_callback: PublishCallback = Arc::new(|_, _| Ok(()));
+        let (mut bridge, handle) =
+            MeshtasticBridge::new(MockInterface::new(), &config, publish_callback);
+        bridge.interface.failing_connects = 4;
+
+        bridge.connection_lost().await.unwrap();
+        assert_eq!(
+            handle.connection_state(),
+            ConnectionState::Reconnecting { attempt: 1 }
+        );
+
+        // Each failure pushes the next attempt further out, up to the cap
+        let mut delays = Vec::new();
+        for attempt in 1..=4 {
+            let before = Instant::now();
+            bridge.try_reconnect().await.unwrap();
+            delays.push(bridge.next_reconnect.unwrap() - before);
+            assert_eq!(
+                handle.connection_state(),
+                ConnectionState::Reconnecting {
+                    attempt: attempt + 1
+                }
+            );
+        }
+        assert!(delays[0] <= Duration::from_millis(201));
+        assert!(delays[1] >= Duration::from_millis(320));
+        assert!(delays.iter().all(|d| *d <= Duration::from_millis(401)));
+
+        // The device comes back
+        bridge.try_reconnect().await.unwrap();
+        assert_eq!(handle.connection_state(), ConnectionState::Connected);
+        assert!(bridge.next_reconnect.is_none());
+        assert_eq!(bridge.reconnect_failures, 0);
+        assert_eq!(bridge.stats.reconnect_attempts, 5);
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_gives_up_after_max_attempts() {
+        let (mut bridge, handle) = create_test_bridge();
+        bridge.reconnect.max_attempts = 2;
+        bridge.interface.failing_connects = 5;
+
+        bridge.connection_lost().await.unwrap();
+        bridge.try_reconnect().await.unwrap();
+        assert!(bridge.try_reconnect().await.is_err());
+        assert_eq!(handle.connection_state(), ConnectionState::Disconnected);
+    }
 }
@@ -20,8 +20,22 @@
 //!
 //! This allows detecting duplicates even when the same logical message
 //! appears from different network paths.
+//!
+//! Keys may also carry the channel a packet arrived on. Whether the channel
+//! takes part in the comparison is decided by the cache's [`DedupKeyHashing`],
+//! so identical packet ids on different channels can be kept apart.
+//!
+//! # Sharing between bridges
+//!
+//! The cache is internally synchronized and its clones share entries and
+//! statistics. A node bridging one gossip network through several devices
+//! should hand every bridge the same cache (see
+//! [`MeshtasticBridge::with_dedup_cache`](crate::MeshtasticBridge::with_dedup_cache)),
+//! so a message one bridge publishes is recognised when it reaches another.
 
 use lru::LruCache;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use std::num::NonZeroUsize;
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
@@ -36,6 +50,8 @@ pub struct DeduplicationKey {
     pub source: String,
     /// Message/packet identifier
     pub message_id: String,
+    /// Meshtastic channel index, if known
+    pub channel: Option<u8>,
 }
 
 impl DeduplicationKey {
@@ -44,6 +60,7 @@ impl DeduplicationKey {
         Self {
             source: format!("lora:{:08x}", sender_node_id),
             message_id: format!("{:08x}", packet_id),
+            channel: None,
         }
     }
 
@@ -52,6 +69,18 @@ impl DeduplicationKey {
         Self {
             source: format!("p2p:{}", &peer_id[..peer_id.len().min(12)]),
             message_id: message_id.to_string(),
+            channel: None,
+        }
+    }
+
+    /// Create a key from a message's content-derived ID
+    ///
+    /// The same logical message maps to the same key on either network.
+    pub fn from_content(id: &mycelial_core::MessageId) -> Self {
+        Self {
+            source: "msg".to_string(),
+            message_id: id.to_hex(),
+            channel: None,
         }
     }
 
@@ -60,13 +89,58 @@ impl DeduplicationKey {
         Self {
             source: source.into(),
             message_id: message_id.into(),
+            channel: None,
         }
     }
+
+    /// Attach the channel the message was seen on
+    pub fn with_channel(mut self, channel: u8) -> Self {
+        self.channel = Some(channel);
+        self
+    }
 }
 
 impl std::fmt::Display for DeduplicationKey {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}:{}", self.source, self.message_id)
+        write!(f, "{}:{}", self.source, self.message_id)?;
+        if let Some(channel) = self.channel {
+            write!(f, "@ch{}", channel)?;
+        }
+        Ok(())
+    }
+}
+
+/// How a [`DeduplicationKey`] is reduced to the identity the cache compares
+#[derive(Debug, Clone, Copy, Default)]
+pub enum DedupKeyHashing {
+    /// Source and message id only; the same packet on any channel is one message
+    #[default]
+    SourceAndMessage,
+    /// Source, message id and channel; identical ids on different channels are distinct
+    WithChannel,
+    /// Caller-supplied hash function
+    Custom(fn(&DeduplicationKey) -> u64),
+}
+
+impl DedupKeyHashing {
+    /// Hash a key according to this strategy
+    pub fn hash(&self, key: &DeduplicationKey) -> u64 {
+        match self {
+            DedupKeyHashing::SourceAndMessage => {
+                let mut hasher = DefaultHasher::new();
+                key.source.hash(&mut hasher);
+                key.message_id.hash(&mut hasher);
+                hasher.finish()
+            }
+            DedupKeyHashing::WithChannel => {
+                let mut hasher = DefaultHasher::new();
+                key.source.hash(&mut hasher);
+                key.message_id.hash(&mut hasher);
+                key.channel.hash(&mut hasher);
+                hasher.finish()
+            }
+            DedupKeyHashing::Custom(hash) => hash(key),
+        }
     }
 }
 
@@ -96,10 +170,12 @@ pub enum MessageDirection {
 /// manage memory while ensuring messages aren't accidentally re-bridged.
 #[derive(Debug)]
 pub struct DeduplicationCache {
-    /// LRU cache storing seen messages
-    cache: Arc<RwLock<LruCache<DeduplicationKey, CacheEntry>>>,
+    /// LRU cache storing seen messages, keyed by hashed [`DeduplicationKey`]
+    cache: Arc<RwLock<LruCache<u64, CacheEntry>>>,
     /// Time-to-live for cache entries
     ttl: Duration,
+    /// How keys are hashed into cache entries
+    hashing: DedupKeyHashing,
     /// Statistics
     stats: Arc<RwLock<CacheStats>>,
 }
@@ -117,6 +193,12 @@ pub struct CacheStats {
     pub ttl_expirations: u64,
     /// Entries evicted by LRU
     pub lru_evictions: u64,
+    /// Lookups that found a live entry (including [`DeduplicationCache::contains`])
+    pub hits: u64,
+    /// Lookups that found no entry or only an expired one
+    pub misses: u64,
+    /// Entries dropped before being seen again, by LRU or TTL
+    pub evictions: u64,
 }
 
 impl CacheStats {
@@ -133,6 +215,16 @@ impl CacheStats {
     pub fn pass_through_count(&self) -> u64 {
         self.new_messages
     }
+
+    /// Get the lookup hit rate (0.0 to 1.0)
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
 }
 
 impl DeduplicationCache {
@@ -143,19 +235,52 @@ impl DeduplicationCache {
 
     /// Create from bridge configuration
     pub fn from_config(config: &BridgeConfig) -> Self {
-        Self::with_capacity_and_ttl(config.dedup_cache_size, config.dedup_ttl)
+        let hashing = if config.dedup_per_channel {
+            DedupKeyHashing::WithChannel
+        } else {
+            DedupKeyHashing::SourceAndMessage
+        };
+        Self::with_hashing(config.dedup_cache_size, config.dedup_ttl, hashing)
     }
 
     /// Create with custom capacity and TTL
     pub fn with_capacity_and_ttl(capacity: usize, ttl: Duration) -> Self {
+        Self::with_hashing(capacity, ttl, DedupKeyHashing::default())
+    }
+
+    /// Create with custom capacity, TTL and key hashing
+    pub fn with_hashing(capacity: usize, ttl: Duration, hashing: DedupKeyHashing) -> Self {
         let cap = NonZeroUsize::new(capacity.max(1)).unwrap();
         Self {
             cache: Arc::new(RwLock::new(LruCache::new(cap))),
             ttl,
+            hashing,
             stats: Arc::new(RwLock::new(CacheStats::default())),
         }
     }
 
+    /// Check whether a message has been seen, without recording it
+    ///
+    /// Unlike [`is_duplicate`](Self::is_duplicate), this neither inserts the
+    /// key nor refreshes its LRU position. Expired entries count as unseen.
+    pub fn contains(&self, key: &DeduplicationKey) -> bool {
+        let hash = self.hashing.hash(key);
+        let found = {
+            let cache = self.cache.read().unwrap();
+            cache
+                .peek(&hash)
+                .is_some_and(|entry| entry.first_seen.elapsed() <= self.ttl)
+        };
+
+        let mut stats = self.stats.write().unwrap();
+        if found {
+            stats.hits += 1;
+        } else {
+            stats.misses += 1;
+        }
+        found
+    }
+
     /// Check if a message is a duplicate
     ///
     /// Returns `true` if this message has been seen before (is a duplicate),
@@ -169,10 +294,11 @@ impl DeduplicationCache {
             stats.total_checks += 1;
         }
 
+        let hash = self.hashing.hash(key);
         let mut cache = self.cache.write().unwrap();
 
         // Check if entry exists and is still valid
-        if let Some(entry) = cache.get_mut(key) {
+        if let Some(entry) = cache.get_mut(&hash) {
             // Check TTL expiration
             if now.duration_since(entry.first_seen) > self.ttl {
                 // Entry expired, treat as new
@@ -180,6 +306,8 @@ impl DeduplicationCache {
                 {
                     let mut stats = self.stats.write().unwrap();
                     stats.ttl_expirations += 1;
+                    stats.evictions += 1;
+                    stats.misses += 1;
                     stats.new_messages += 1;
                 }
                 // Update entry with new timestamp
@@ -194,40 +322,53 @@ impl DeduplicationCache {
             debug!(
                 key = %key,
                 seen_count = entry.seen_count,
+                first_direction = ?entry.direction,
+                age_ms = now.duration_since(entry.first_seen).as_millis() as u64,
                 "Duplicate message detected"
             );
             {
                 let mut stats = self.stats.write().unwrap();
+                stats.hits += 1;
                 stats.duplicates_blocked += 1;
             }
             return true;
         }
 
         // Not in cache - record it
-        let was_full = cache.len() >= cache.cap().get();
-        cache.put(
-            key.clone(),
-            CacheEntry {
-                first_seen: now,
-                seen_count: 1,
-                direction,
-            },
-        );
-
-        if was_full {
-            let mut stats = self.stats.write().unwrap();
-            stats.lru_evictions += 1;
-        }
+        let evicted = Self::insert(&mut cache, hash, now, direction);
 
         trace!(key = %key, direction = ?direction, "New message recorded");
         {
             let mut stats = self.stats.write().unwrap();
+            stats.misses += 1;
             stats.new_messages += 1;
+            if evicted {
+                stats.lru_evictions += 1;
+                stats.evictions += 1;
+            }
         }
 
         false
     }
 
+    /// Insert a fresh entry, returning whether another entry was evicted for room
+    fn insert(
+        cache: &mut LruCache<u64, CacheEntry>,
+        hash: u64,
+        now: Instant,
+        direction: MessageDirection,
+    ) -> bool {
+        let displaced = cache.push(
+            hash,
+            CacheEntry {
+                first_seen: now,
+                seen_count: 1,
+                direction,
+            },
+        );
+        matches!(displaced, Some((old_hash, _)) if old_hash != hash)
+    }
+
     /// Check if a Meshtastic packet is a duplicate
     pub fn is_meshtastic_duplicate(&self, sender_node_id: u32, packet_id: u32) -> bool {
         let key = DeduplicationKey::from_meshtastic(sender_node_id, packet_id);
@@ -244,15 +385,17 @@ impl DeduplicationCache {
     ///
     /// Use this when sending a message to ensure it won't be bridged back.
     pub fn mark_seen(&self, key: &DeduplicationKey, direction: MessageDirection) {
-        let mut cache = self.cache.write().unwrap();
-        cache.put(
-            key.clone(),
-            CacheEntry {
-                first_seen: Instant::now(),
-                seen_count: 1,
-                direction,
-            },
-        );
+        let hash = self.hashing.hash(key);
+        let evicted = {
+            let mut cache = self.cache.write().unwrap();
+            Self::insert(&mut cache, hash, Instant::now(), direction)
+        };
+
+        if evicted {
+            let mut stats = self.stats.write().unwrap();
+            stats.lru_evictions += 1;
+            stats.evictions += 1;
+        }
     }
 
     /// Mark a Meshtastic packet as seen
@@ -320,6 +463,11 @@ impl DeduplicationCache {
         self.ttl
     }
 
+    /// Get the key hashing strategy
+    pub fn hashing(&self) -> DedupKeyHashing {
+        self.hashing
+    }
+
     /// Get the cache capacity
     pub fn capacity(&self) -> usize {
         let cache = self.cache.read().unwrap();
@@ -339,6 +487,7 @@ impl Clone for DeduplicationCache {
         Self {
             cache: Arc::clone(&self.cache),
             ttl: self.ttl,
+            hashing: self.hashing,
             stats: Arc::clone(&self.stats),
         }
     }
@@ -586,4 +735,87 @@ mod tests {
         let key = DeduplicationKey::from_meshtastic(0x12345678, 0x00000001);
         assert!(cache.is_duplicate(&key, MessageDirection::FromLibp2p));
     }
+
+    #[test]
+    fn test_cache_hit_miss_eviction_counters() {
+        let cache = DeduplicationCache::with_capacity_and_ttl(2, Duration::from_secs(300));
+
+        let key1 = DeduplicationKey::new("s1", "m1");
+        let key2 = DeduplicationKey::new("s2", "m2");
+        let key3 = DeduplicationKey::new("s3", "m3");
+
+        cache.is_duplicate(&key1, MessageDirection::FromLora); // miss
+        cache.is_duplicate(&key1, MessageDirection::FromLora); // hit
+        cache.is_duplicate(&key2, MessageDirection::FromLora); // miss
+        cache.mark_seen(&key3, MessageDirection::FromLibp2p); // evicts key1
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 2);
+        assert_eq!(stats.evictions, 1);
+        assert_eq!(stats.lru_evictions, 1);
+        assert!((stats.hit_rate() - 1.0 / 3.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_cache_contains_does_not_record() {
+        let cache = DeduplicationCache::with_capacity_and_ttl(2, Duration::from_secs(300));
+
+        let key1 = DeduplicationKey::new("s1", "m1");
+        let key2 = DeduplicationKey::new("s2", "m2");
+        let key3 = DeduplicationKey::new("s3", "m3");
+
+        assert!(!cache.contains(&key1));
+        assert!(cache.is_empty());
+
+        cache.is_duplicate(&key1, MessageDirection::FromLora);
+        cache.is_duplicate(&key2, MessageDirection::FromLora);
+
+        // contains() must not refresh key1, so it is still the LRU victim
+        assert!(cache.contains(&key1));
+        cache.is_duplicate(&key3, MessageDirection::FromLora);
+        assert!(!cache.contains(&key1));
+        assert!(cache.contains(&key2));
+
+        assert_eq!(cache.stats().total_checks, 3);
+    }
+
+    #[test]
+    fn test_cache_channel_hashing() {
+        let key_ch0 = DeduplicationKey::from_meshtastic(0x12345678, 1).with_channel(0);
+        let key_ch1 = DeduplicationKey::from_meshtastic(0x12345678, 1).with_channel(1);
+        assert_eq!(format!("{}", key_ch1), "lora:12345678:00000001@ch1");
+
+        // By default the channel is ignored
+        let cache = DeduplicationCache::new();
+        assert!(!cache.is_duplicate(&key_ch0, MessageDirection::FromLora));
+        assert!(cache.is_duplicate(&key_ch1, MessageDirection::FromLora));
+
+        // With channel hashing the same packet id on another channel is new
+        let cache = DeduplicationCache::with_hashing(
+            100,
+            Duration::from_secs(300),
+            DedupKeyHashing::WithChannel,
+        );
+        assert!(!cache.is_duplicate(&key_ch0, MessageDirection::FromLora));
+        assert!(!cache.is_duplicate(&key_ch1, MessageDirection::FromLora));
+        assert!(cache.is_duplicate(&key_ch1, MessageDirection::FromLora));
+    }
+
+    #[test]
+    fn test_cache_custom_hashing() {
+        fn by_message_id(key: &DeduplicationKey) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            key.message_id.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let cache = DeduplicationCache::with_hashing(
+            100,
+            Duration::from_secs(300),
+            DedupKeyHashing::Custom(by_message_id),
+        );
+        assert!(!cache.is_duplicate(&DeduplicationKey::new("a", "m"), MessageDirection::FromLora));
+        assert!(cache.is_duplicate(&DeduplicationKey::new("b", "m"), MessageDirection::FromLora));
+    }
 }
@@ -221,8 +221,8 @@ impl MessageChunker {
         let is_compressed = compressed.len() < data.len();
         let payload = if is_compressed { &compressed } else { data };
 
-        // Check if chunking is needed
-        if payload.len() <= LORA_MAX_PAYLOAD {
+        // Check if chunking is needed (the chunk header counts against the LoRa limit)
+        if payload.len() <= CHUNK_PAYLOAD_SIZE {
             // Single chunk (no chunking needed)
             return Ok(vec![MessageChunk {
                 message_id: self.next_message_id(),
@@ -450,6 +450,14 @@ impl EconomicsMessageCodec {
         Ok(chunks.into_iter().map(|c| c.encode()).collect())
     }
 
+    /// Split a message into chunks without encoding them
+    ///
+    /// Useful when the caller needs chunk metadata (e.g. whether the payload
+    /// was compressed) before transmitting.
+    pub fn chunk(&mut self, data: &[u8]) -> Result<Vec<MessageChunk>> {
+        self.chunker.chunk(data)
+    }
+
     /// Decode a received packet
     ///
     /// Returns `Some(data)` if a complete message is ready, `None` if waiting for more chunks
@@ -5,6 +5,8 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+
+pub use mycelial_core::MessagePriority;
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -95,6 +97,12 @@ pub struct ChannelConfig {
 
     /// Topic to channel mappings
     pub topic_mappings: HashMap<String, ChannelMapping>,
+
+    /// Pre-shared 256-bit AES keys by channel name
+    ///
+    /// Channels without a key are sent unencrypted.
+    #[serde(default)]
+    pub psk: HashMap<String, [u8; 32]>,
 }
 
 impl Default for ChannelConfig {
@@ -107,7 +115,7 @@ impl Default for ChannelConfig {
             ChannelMapping {
                 channel: "Primary".to_string(),
                 direction: BridgeDirection::Bidirectional,
-                priority: MessagePriority::Normal,
+                priority: None,
             },
         );
         mappings.insert(
@@ -115,15 +123,17 @@ impl Default for ChannelConfig {
             ChannelMapping {
                 channel: "LongFast".to_string(),
                 direction: BridgeDirection::LoraToLibp2p,
-                priority: MessagePriority::Low,
+                priority: Some(MessagePriority::Low),
             },
         );
+        // Economics topics carry protocol messages rather than core
+        // `Message`s, so their priority can't be derived and is set here
         mappings.insert(
             "/mycelial/1.0.0/vouch".to_string(),
             ChannelMapping {
                 channel: "Primary".to_string(),
                 direction: BridgeDirection::Bidirectional,
-                priority: MessagePriority::High,
+                priority: Some(MessagePriority::High),
             },
         );
         mappings.insert(
@@ -131,7 +141,7 @@ impl Default for ChannelConfig {
             ChannelMapping {
                 channel: "Primary".to_string(),
                 direction: BridgeDirection::Bidirectional,
-                priority: MessagePriority::High,
+                priority: Some(MessagePriority::High),
             },
         );
         mappings.insert(
@@ -139,7 +149,15 @@ impl Default for ChannelConfig {
             ChannelMapping {
                 channel: "Primary".to_string(),
                 direction: BridgeDirection::Bidirectional,
-                priority: MessagePriority::High,
+                priority: Some(MessagePriority::High),
+            },
+        );
+        mappings.insert(
+            "/mycelial/1.0.0/telemetry".to_string(),
+            ChannelMapping {
+                channel: "LongFast".to_string(),
+                direction: BridgeDirection::Bidirectional,
+                priority: Some(MessagePriority::Low),
             },
         );
         mappings.insert(
@@ -147,13 +165,14 @@ impl Default for ChannelConfig {
             ChannelMapping {
                 channel: "Direct".to_string(),
                 direction: BridgeDirection::Bidirectional,
-                priority: MessagePriority::Normal,
+                priority: None,
             },
         );
 
         Self {
             default_channel: "Primary".to_string(),
             topic_mappings: mappings,
+            psk: HashMap::new(),
         }
     }
 }
@@ -167,8 +186,12 @@ pub struct ChannelMapping {
     /// Bridge direction
     pub direction: BridgeDirection,
 
-    /// Message priority (affects hop limit)
-    pub priority: MessagePriority,
+    /// Priority for every message on this topic (affects hop limit)
+    ///
+    /// When unset, each message gets its type's
+    /// [`default_priority`](mycelial_core::MessageType::default_priority).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<MessagePriority>,
 }
 
 /// Direction of message bridging
@@ -183,27 +206,30 @@ pub enum BridgeDirection {
     Libp2pToLora,
 }
 
-/// Message priority affecting transmission parameters
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum MessagePriority {
-    /// Low priority (fewer hops, may be dropped under load)
-    Low,
-    /// Normal priority
-    Normal,
-    /// High priority (more hops, prioritized transmission)
-    High,
+/// LoRa transmission parameters for a [`MessagePriority`]
+///
+/// Low priority messages travel fewer hops and may be dropped under load;
+/// high priority ones travel further and are acknowledged.
+pub trait LoraPriority {
+    /// Get hop limit for this priority
+    fn hop_limit(&self) -> u8;
+
+    /// Whether messages of this priority request a LoRa acknowledgement
+    fn wants_ack(&self) -> bool;
 }
 
-impl MessagePriority {
-    /// Get hop limit for this priority
-    pub fn hop_limit(&self) -> u8 {
+impl LoraPriority for MessagePriority {
+    fn hop_limit(&self) -> u8 {
         match self {
             MessagePriority::Low => 2,
             MessagePriority::Normal => DEFAULT_MAX_HOPS,
             MessagePriority::High => 5,
         }
     }
+
+    fn wants_ack(&self) -> bool {
+        matches!(self, MessagePriority::High)
+    }
 }
 
 /// Bridge behavior configuration
@@ -221,6 +247,10 @@ pub struct BridgeConfig {
     #[serde(with = "humantime_serde", default = "default_dedup_ttl")]
     pub dedup_ttl: Duration,
 
+    /// Treat identical packet ids on different channels as distinct messages
+    #[serde(default)]
+    pub dedup_per_channel: bool,
+
     /// Enable message compression for economics messages
     #[serde(default = "default_compression")]
     pub enable_compression: bool,
@@ -228,6 +258,45 @@ pub struct BridgeConfig {
     /// Queue size for outgoing LoRa messages
     #[serde(default = "default_queue_size")]
     pub outgoing_queue_size: usize,
+
+    /// Request acknowledgements for high-priority topics
+    #[serde(default = "default_reliable_delivery")]
+    pub reliable_delivery: bool,
+
+    /// How long to wait for an acknowledgement before retransmitting
+    #[serde(with = "humantime_serde", default = "default_ack_timeout")]
+    pub ack_timeout: Duration,
+
+    /// Maximum retransmissions of an unacknowledged packet
+    #[serde(default = "default_max_retransmits")]
+    pub max_retransmits: u32,
+
+    /// LoRa spreading factor used to estimate airtime (7-12)
+    #[serde(default = "default_spreading_factor")]
+    pub spreading_factor: u8,
+
+    /// LoRa channel bandwidth in kHz used to estimate airtime
+    #[serde(default = "default_bandwidth_khz")]
+    pub bandwidth_khz: u32,
+
+    /// Maximum share of airtime the bridge may use, in percent
+    #[serde(default = "default_duty_cycle_percent")]
+    pub duty_cycle_percent: f64,
+
+    /// Rolling window over which the duty cycle is enforced
+    #[serde(with = "humantime_serde", default = "default_duty_cycle_window")]
+    pub duty_cycle_window: Duration,
+
+    /// File where registered node/peer mappings are kept across restarts
+    #[serde(default)]
+    pub node_map_path: Option<PathBuf>,
+
+    /// Bridge position reports of this node between LoRa and libp2p
+    ///
+    /// When disabled, positions are never sent to LoRa and the attached
+    /// device's own GPS reports are not published to gossipsub.
+    #[serde(default = "default_share_position")]
+    pub share_position: bool,
 }
 
 fn default_max_hops() -> u8 {
@@ -250,14 +319,56 @@ fn default_queue_size() -> usize {
     100
 }
 
+fn default_reliable_delivery() -> bool {
+    true
+}
+
+fn default_ack_timeout() -> Duration {
+    Duration::from_secs(15)
+}
+
+fn default_max_retransmits() -> u32 {
+    3
+}
+
+fn default_spreading_factor() -> u8 {
+    11 // LongFast
+}
+
+fn default_bandwidth_khz() -> u32 {
+    250 // LongFast
+}
+
+fn default_duty_cycle_percent() -> f64 {
+    10.0 // EU 869.4-869.65 MHz sub-band
+}
+
+fn default_duty_cycle_window() -> Duration {
+    Duration::from_secs(3600)
+}
+
+fn default_share_position() -> bool {
+    true
+}
+
 impl Default for BridgeConfig {
     fn default() -> Self {
         Self {
             max_hops: DEFAULT_MAX_HOPS,
             dedup_cache_size: 1000,
             dedup_ttl: Duration::from_secs(300),
+            dedup_per_channel: false,
             enable_compression: true,
             outgoing_queue_size: 100,
+            reliable_delivery: true,
+            ack_timeout: Duration::from_secs(15),
+            max_retransmits: 3,
+            spreading_factor: 11,
+            bandwidth_khz: 250,
+            duty_cycle_percent: 10.0,
+            duty_cycle_window: Duration::from_secs(3600),
+            node_map_path: None,
+            share_position: true,
         }
     }
 }
@@ -278,8 +389,38 @@ pub struct ReconnectConfig {
     pub max_delay: Duration,
 
     /// Maximum number of reconnection attempts (0 = infinite)
+    ///
+    /// Once the delay reaches `max_delay`, retries continue at that interval
+    /// until this limit, if any, is hit.
     #[serde(default)]
     pub max_attempts: u32,
+
+    /// Factor the delay grows by after each failed attempt
+    #[serde(default = "default_backoff_multiplier")]
+    pub multiplier: f64,
+
+    /// Fraction of the delay randomly shaved off to spread out retries (0.0-1.0)
+    #[serde(default = "default_backoff_jitter")]
+    pub jitter: f64,
+}
+
+impl ReconnectConfig {
+    /// Delay before the next attempt after `failures` consecutive failures
+    ///
+    /// `jitter_sample` is a random value in `[0, 1)`; jitter only shortens
+    /// the delay, so it never exceeds `max_delay`.
+    pub fn backoff_delay(&self, failures: u32, jitter_sample: f64) -> Duration {
+        let growth = self.multiplier.max(1.0).powi(failures.min(32) as i32);
+        let max_secs = self.max_delay.as_secs_f64();
+        let delay_secs = (self.initial_delay.as_secs_f64() * growth).min(max_secs);
+        let jitter = self.jitter.clamp(0.0, 1.0) * jitter_sample.clamp(0.0, 1.0);
+        Duration::from_secs_f64(delay_secs * (1.0 - jitter))
+    }
+
+    /// Whether another attempt is allowed after `failures` consecutive failures
+    pub fn should_retry(&self, failures: u32) -> bool {
+        self.enabled && (self.max_attempts == 0 || failures < self.max_attempts)
+    }
 }
 
 fn default_auto_reconnect() -> bool {
@@ -294,6 +435,14 @@ fn default_max_delay() -> Duration {
     Duration::from_secs(60)
 }
 
+fn default_backoff_multiplier() -> f64 {
+    2.0
+}
+
+fn default_backoff_jitter() -> f64 {
+    0.2
+}
+
 impl Default for ReconnectConfig {
     fn default() -> Self {
         Self {
@@ -301,6 +450,8 @@ impl Default for ReconnectConfig {
             initial_delay: Duration::from_secs(1),
             max_delay: Duration::from_secs(60),
             max_attempts: 0, // Infinite
+            multiplier: 2.0,
+            jitter: 0.2,
         }
     }
 }
@@ -353,12 +504,46 @@ impl MeshtasticConfigBuilder {
         self
     }
 
+    /// Configure acknowledgement timeout and retransmissions for reliable topics
+    pub fn reliable_delivery(mut self, ack_timeout: Duration, max_retransmits: u32) -> Self {
+        self.config.bridge.reliable_delivery = true;
+        self.config.bridge.ack_timeout = ack_timeout;
+        self.config.bridge.max_retransmits = max_retransmits;
+        self
+    }
+
+    /// Set the radio parameters and duty-cycle limit used for airtime budgeting
+    pub fn airtime(mut self, spreading_factor: u8, duty_cycle_percent: f64) -> Self {
+        self.config.bridge.spreading_factor = spreading_factor.clamp(7, 12);
+        self.config.bridge.duty_cycle_percent = duty_cycle_percent.clamp(0.0, 100.0);
+        self
+    }
+
+    /// Persist registered node/peer mappings to a file
+    pub fn node_map_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config.bridge.node_map_path = Some(path.into());
+        self
+    }
+
+    /// Enable or disable sharing this node's position
+    pub fn share_position(mut self, enabled: bool) -> Self {
+        self.config.bridge.share_position = enabled;
+        self
+    }
+
     /// Enable or disable auto-reconnect
     pub fn auto_reconnect(mut self, enabled: bool) -> Self {
         self.config.reconnect.enabled = enabled;
         self
     }
 
+    /// Set the reconnection backoff range
+    pub fn reconnect_backoff(mut self, initial_delay: Duration, max_delay: Duration) -> Self {
+        self.config.reconnect.initial_delay = initial_delay;
+        self.config.reconnect.max_delay = max_delay.max(initial_delay);
+        self
+    }
+
     /// Add a topic mapping
     pub fn map_topic(
         mut self,
@@ -371,12 +556,18 @@ impl MeshtasticConfigBuilder {
             ChannelMapping {
                 channel: channel.into(),
                 direction,
-                priority: MessagePriority::Normal,
+                priority: None,
             },
         );
         self
     }
 
+    /// Set the pre-shared key for a channel
+    pub fn channel_psk(mut self, channel: impl Into<String>, key: [u8; 32]) -> Self {
+        self.config.channels.psk.insert(channel.into(), key);
+        self
+    }
+
     /// Build the configuration
     pub fn build(self) -> MeshtasticConfig {
         self.config
@@ -451,4 +642,59 @@ mod tests {
 
         assert_eq!(config.bridge.max_hops, MAX_HOP_LIMIT);
     }
+
+    #[test]
+    fn test_channel_psk() {
+        let config = MeshtasticConfigBuilder::new()
+            .channel_psk("Primary", [7u8; 32])
+            .build();
+
+        assert_eq!(config.channels.psk.get("Primary"), Some(&[7u8; 32]));
+        assert!(config.channels.psk.get("LongFast").is_none());
+    }
+
+    #[test]
+    fn test_airtime_builder_clamps() {
+        let config = MeshtasticConfigBuilder::new().airtime(14, 150.0).build();
+        assert_eq!(config.bridge.spreading_factor, 12);
+        assert_eq!(config.bridge.duty_cycle_percent, 100.0);
+    }
+
+    #[test]
+    fn test_priority_ordering() {
+        assert!(MessagePriority::High > MessagePriority::Normal);
+        assert!(MessagePriority::Normal > MessagePriority::Low);
+    }
+
+    #[test]
+    fn test_reconnect_backoff_grows_and_caps() {
+        let config = MeshtasticConfigBuilder::new()
+            .reconnect_backoff(Duration::from_secs(1), Duration::from_secs(10))
+            .build();
+        let reconnect = &config.reconnect;
+
+        assert_eq!(reconnect.backoff_delay(0, 0.0), Duration::from_secs(1));
+        assert_eq!(reconnect.backoff_delay(1, 0.0), Duration::from_secs(2));
+        assert_eq!(reconnect.backoff_delay(3, 0.0), Duration::from_secs(8));
+        assert_eq!(reconnect.backoff_delay(4, 0.0), Duration::from_secs(10));
+        assert_eq!(reconnect.backoff_delay(1000, 0.0), Duration::from_secs(10));
+
+        // Jitter shortens the delay but never pushes it past the cap
+        let jittered = reconnect.backoff_delay(1000, 0.99);
+        assert!(jittered < Duration::from_secs(10));
+        assert!(jittered >= Duration::from_secs(8));
+    }
+
+    #[test]
+    fn test_reconnect_retry_limit() {
+        let mut reconnect = ReconnectConfig::default();
+        assert!(reconnect.should_retry(1_000_000));
+
+        reconnect.max_attempts = 3;
+        assert!(reconnect.should_retry(2));
+        assert!(!reconnect.should_retry(3));
+
+        reconnect.enabled = false;
+        assert!(!reconnect.should_retry(0));
+    }
 }
@@ -122,6 +122,15 @@ pub enum MeshtasticError {
         max_hops: u8,
     },
 
+    /// Not enough duty-cycle airtime left to transmit
+    #[error("Airtime budget exhausted: need {needed_ms}ms, {remaining_ms}ms remaining")]
+    AirtimeExhausted {
+        /// Estimated airtime of the packet in milliseconds
+        needed_ms: u64,
+        /// Airtime left in the current window in milliseconds
+        remaining_ms: u64,
+    },
+
     // ===== Node/Identity Errors =====
     /// Unknown node ID
     #[error("Unknown node ID: {0}")]
@@ -140,6 +149,10 @@ pub enum MeshtasticError {
         reason: String,
     },
 
+    /// Node map could not be saved or loaded
+    #[error("Node map persistence failed: {0}")]
+    NodeMapPersistence(String),
+
     // ===== Configuration Errors =====
     /// Invalid configuration
     #[error("Invalid configuration: {0}")]
@@ -214,9 +227,11 @@ impl MeshtasticError {
             MeshtasticError::BridgeAlreadyRunning => "BRIDGE_ALREADY_RUNNING",
             MeshtasticError::DuplicateMessage { .. } => "DUPLICATE_MESSAGE",
             MeshtasticError::HopLimitExceeded { .. } => "HOP_LIMIT_EXCEEDED",
+            MeshtasticError::AirtimeExhausted { .. } => "AIRTIME_EXHAUSTED",
             MeshtasticError::UnknownNode(_) => "UNKNOWN_NODE",
             MeshtasticError::InvalidNodeId(_) => "INVALID_NODE_ID",
             MeshtasticError::NodeMappingFailed { .. } => "NODE_MAPPING_FAILED",
+            MeshtasticError::NodeMapPersistence(_) => "NODE_MAP_PERSISTENCE",
             MeshtasticError::InvalidConfig(_) => "INVALID_CONFIG",
             MeshtasticError::MissingConfig(_) => "MISSING_CONFIG",
             MeshtasticError::Internal(_) => "INTERNAL_ERROR",
@@ -244,6 +259,24 @@ impl From<prost::EncodeError> for MeshtasticError {
     }
 }
 
+// Conversion from btleplug error (only when ble feature is enabled)
+#[cfg(feature = "ble")]
+impl From<btleplug::Error> for MeshtasticError {
+    fn from(err: btleplug::Error) -> Self {
+        match err {
+            btleplug::Error::DeviceNotFound => MeshtasticError::PortNotFound(err.to_string()),
+            btleplug::Error::NotConnected => MeshtasticError::Disconnected,
+            btleplug::Error::TimedOut(duration) => MeshtasticError::ConnectionTimeout {
+                duration_ms: duration.as_millis() as u64,
+            },
+            other => MeshtasticError::PortOpenFailed {
+                port: "bluetooth".to_string(),
+                reason: other.to_string(),
+            },
+        }
+    }
+}
+
 // Conversion from serialport error (only when serial feature is enabled)
 #[cfg(feature = "serial")]
 impl From<serialport::Error> for MeshtasticError {
@@ -10,13 +10,52 @@
 //! ```bash
 //! apt install libdbus-1-dev
 //! ```
+//!
+//! # Device Selection
+//!
+//! When several radios are in range, use [`BleInterface::scan`] to list
+//! them with their signal strength, then [`BleInterface::connect_to`] the
+//! one you want:
+//!
+//! ```rust,ignore
+//! use mycelial_meshtastic::interface::BleInterface;
+//! use std::time::Duration;
+//!
+//! let devices = BleInterface::scan(Duration::from_secs(5)).await;
+//! let strongest = devices.first().expect("no Meshtastic radios in range");
+//! let interface = BleInterface::connect_to(&strongest.path).await?;
+//! ```
 
-use crate::error::Result;
+use crate::error::{MeshtasticError, Result};
+use crate::test_utils::DeviceInfo;
 use async_trait::async_trait;
+use btleplug::api::{
+    Central, Characteristic, Manager as _, Peripheral as _, PeripheralProperties, ScanFilter,
+    WriteType,
+};
+use btleplug::platform::{Adapter, Manager, Peripheral};
 use bytes::Bytes;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
 
 use super::{ConnectionState, MeshtasticInterface};
 
+/// Meshtastic GATT service
+const MESHTASTIC_SERVICE_UUID: Uuid = Uuid::from_u128(0x6ba1b218_15a8_461f_9fa8_5dcae273eafd);
+
+/// Characteristic the client writes `ToRadio` protobufs to
+const TO_RADIO_UUID: Uuid = Uuid::from_u128(0xf75c76d2_129e_4dad_a1dd_7866124401e7);
+
+/// Characteristic the client reads `FromRadio` protobufs from
+const FROM_RADIO_UUID: Uuid = Uuid::from_u128(0x2c55e69e_4993_11ed_b878_0242ac120002);
+
+/// How long [`BleInterface::connect`] scans for the configured device
+const CONNECT_SCAN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Pause between polls of an empty `FromRadio` characteristic
+const READ_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 /// BLE interface for connecting to Meshtastic devices over Bluetooth
 ///
 /// Meshtastic devices expose a BLE GATT service for communication.
@@ -24,6 +63,15 @@ use super::{ConnectionState, MeshtasticInterface};
 pub struct BleInterface {
     device_name: String,
     state: ConnectionState,
+    /// Connected peripheral and its `ToRadio`/`FromRadio` characteristics
+    link: Option<BleLink>,
+}
+
+/// An open GATT connection to a Meshtastic radio
+struct BleLink {
+    peripheral: Peripheral,
+    to_radio: Characteristic,
+    from_radio: Characteristic,
 }
 
 impl BleInterface {
@@ -36,39 +84,272 @@ impl BleInterface {
         Self {
             device_name: device_name.into(),
             state: ConnectionState::Disconnected,
+            link: None,
+        }
+    }
+
+    /// Scan for advertising Meshtastic radios
+    ///
+    /// Returns the devices seen within `timeout`, strongest signal first.
+    /// Each entry's `path` is the Bluetooth address accepted by
+    /// [`connect_to`](Self::connect_to). Scan failures (no adapter, Bluetooth
+    /// off) are logged and yield an empty list, like
+    /// [`list_available_devices`](crate::test_utils::list_available_devices).
+    pub async fn scan(timeout: Duration) -> Vec<DeviceInfo> {
+        match Self::scan_peripherals(timeout).await {
+            Ok(found) => {
+                let mut devices: Vec<DeviceInfo> = found
+                    .iter()
+                    .map(|(_, properties)| device_info(properties))
+                    .collect();
+                devices.sort_by(|a, b| b.rssi.cmp(&a.rssi));
+                devices
+            }
+            Err(e) => {
+                warn!("BLE scan failed: {}", e);
+                Vec::new()
+            }
         }
     }
+
+    /// Find the Meshtastic radio with the strongest signal
+    ///
+    /// Returns its Bluetooth address, or `None` if no radio was seen within
+    /// `timeout`. The BLE counterpart of
+    /// [`find_meshtastic_device`](crate::test_utils::find_meshtastic_device).
+    pub async fn find_device(timeout: Duration) -> Option<String> {
+        let device = Self::scan(timeout).await.into_iter().next()?;
+        info!(
+            "Found Meshtastic BLE device: {} ({:?} dBm)",
+            device.path, device.rssi
+        );
+        Some(device.path)
+    }
+
+    /// Connect to a specific radio by Bluetooth address or advertised name
+    pub async fn connect_to(device_id: &str) -> Result<Self> {
+        let mut interface = Self::new(device_id);
+        interface.connect().await?;
+        Ok(interface)
+    }
+
+    /// Scan and return Meshtastic peripherals with their advertised properties
+    async fn scan_peripherals(
+        timeout: Duration,
+    ) -> Result<Vec<(Peripheral, PeripheralProperties)>> {
+        let adapter = default_adapter().await?;
+        adapter
+            .start_scan(ScanFilter {
+                services: vec![MESHTASTIC_SERVICE_UUID],
+            })
+            .await?;
+        tokio::time::sleep(timeout).await;
+        if let Err(e) = adapter.stop_scan().await {
+            debug!("Failed to stop BLE scan: {}", e);
+        }
+
+        let mut found = Vec::new();
+        for peripheral in adapter.peripherals().await? {
+            let Some(properties) = peripheral.properties().await? else {
+                continue;
+            };
+            // Some backends ignore the scan filter
+            if is_meshtastic(&properties) {
+                found.push((peripheral, properties));
+            }
+        }
+        Ok(found)
+    }
+
+    /// Whether a scanned device matches the configured name or address
+    fn matches(&self, properties: &PeripheralProperties) -> bool {
+        properties
+            .address
+            .to_string()
+            .eq_ignore_ascii_case(&self.device_name)
+            || properties.local_name.as_deref() == Some(self.device_name.as_str())
+    }
+}
+
+/// First Bluetooth adapter on this host
+async fn default_adapter() -> Result<Adapter> {
+    let manager = Manager::new().await?;
+    manager
+        .adapters()
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| MeshtasticError::PortNotFound("no Bluetooth adapter".to_string()))
+}
+
+/// Whether advertised properties belong to a Meshtastic radio
+fn is_meshtastic(properties: &PeripheralProperties) -> bool {
+    properties.services.contains(&MESHTASTIC_SERVICE_UUID)
+        || properties
+            .local_name
+            .as_deref()
+            .is_some_and(|name| name.to_lowercase().contains("meshtastic"))
+}
+
+/// Device info for an advertising BLE radio
+fn device_info(properties: &PeripheralProperties) -> DeviceInfo {
+    DeviceInfo {
+        path: properties.address.to_string(),
+        device_type: "BLE".to_string(),
+        vendor_id: None,
+        product_id: None,
+        product_name: properties.local_name.clone(),
+        manufacturer: None,
+        serial_number: None,
+        rssi: properties.rssi,
+        is_likely_meshtastic: is_meshtastic(properties),
+    }
 }
 
 #[async_trait]
 impl MeshtasticInterface for BleInterface {
     async fn connect(&mut self) -> Result<()> {
+        if self.state == ConnectionState::Connected {
+            return Ok(());
+        }
+
         self.state = ConnectionState::Connecting;
-        // TODO: Implement BLE connection
-        self.state = ConnectionState::Connected;
-        Ok(())
+        info!(device = %self.device_name, "Connecting to BLE device");
+
+        let result: Result<BleLink> = async {
+            let peripheral = Self::scan_peripherals(CONNECT_SCAN_TIMEOUT)
+                .await?
+                .into_iter()
+                .find(|(_, properties)| self.matches(properties))
+                .map(|(peripheral, _)| peripheral)
+                .ok_or_else(|| MeshtasticError::PortNotFound(self.device_name.clone()))?;
+
+            peripheral.connect().await?;
+            peripheral.discover_services().await?;
+
+            let characteristic = |uuid: Uuid| {
+                peripheral
+                    .characteristics()
+                    .into_iter()
+                    .find(|c| c.uuid == uuid)
+                    .ok_or_else(|| MeshtasticError::PortOpenFailed {
+                        port: self.device_name.clone(),
+                        reason: format!("missing characteristic {}", uuid),
+                    })
+            };
+            let to_radio = characteristic(TO_RADIO_UUID)?;
+            let from_radio = characteristic(FROM_RADIO_UUID)?;
+
+            Ok(BleLink {
+                peripheral,
+                to_radio,
+                from_radio,
+            })
+        }
+        .await;
+
+        match result {
+            Ok(link) => {
+                self.link = Some(link);
+                self.state = ConnectionState::Connected;
+                info!(device = %self.device_name, "Connected to Meshtastic device");
+                Ok(())
+            }
+            Err(e) => {
+                self.state = ConnectionState::Disconnected;
+                Err(e)
+            }
+        }
     }
 
     async fn disconnect(&mut self) -> Result<()> {
+        if let Some(link) = self.link.take() {
+            if let Err(e) = link.peripheral.disconnect().await {
+                debug!("Error disconnecting BLE device: {}", e);
+            }
+        }
         self.state = ConnectionState::Disconnected;
         Ok(())
     }
 
     fn is_connected(&self) -> bool {
-        self.state == ConnectionState::Connected
+        self.state == ConnectionState::Connected && self.link.is_some()
     }
 
     async fn read_packet(&mut self) -> Result<Option<Bytes>> {
-        // TODO: Implement BLE read
-        Ok(None)
+        let link = self.link.as_ref().ok_or(MeshtasticError::Disconnected)?;
+
+        // FromRadio returns one protobuf per read, or nothing when drained
+        let data = link.peripheral.read(&link.from_radio).await.map_err(|e| {
+            self.state = ConnectionState::Disconnected;
+            MeshtasticError::ReadError(e.to_string())
+        })?;
+
+        if data.is_empty() {
+            tokio::time::sleep(READ_POLL_INTERVAL).await;
+            return Ok(None);
+        }
+        Ok(Some(Bytes::from(data)))
     }
 
-    async fn write_packet(&mut self, _packet: &[u8]) -> Result<()> {
-        // TODO: Implement BLE write
-        Ok(())
+    async fn write_packet(&mut self, packet: &[u8]) -> Result<()> {
+        let link = self.link.as_ref().ok_or(MeshtasticError::Disconnected)?;
+
+        // BLE carries bare ToRadio protobufs, without serial framing
+        link.peripheral
+            .write(&link.to_radio, packet, WriteType::WithResponse)
+            .await
+            .map_err(|e| {
+                self.state = ConnectionState::Disconnected;
+                MeshtasticError::WriteError(e.to_string())
+            })
     }
 
     fn name(&self) -> &str {
         &self.device_name
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_device_info_from_advertisement() {
+        let properties = PeripheralProperties {
+            local_name: Some("Meshtastic_a1b2".to_string()),
+            rssi: Some(-67),
+            ..Default::default()
+        };
+
+        let info = device_info(&properties);
+        assert_eq!(info.device_type, "BLE");
+        assert_eq!(info.product_name.as_deref(), Some("Meshtastic_a1b2"));
+        assert_eq!(info.rssi, Some(-67));
+        assert!(info.is_likely_meshtastic);
+    }
+
+    #[test]
+    fn test_is_meshtastic_by_service() {
+        let mut properties = PeripheralProperties {
+            local_name: Some("T-Echo".to_string()),
+            ..Default::default()
+        };
+        assert!(!is_meshtastic(&properties));
+
+        properties.services.push(MESHTASTIC_SERVICE_UUID);
+        assert!(is_meshtastic(&properties));
+    }
+
+    #[test]
+    fn test_matches_name_or_address() {
+        let properties = PeripheralProperties {
+            local_name: Some("Meshtastic_a1b2".to_string()),
+            ..Default::default()
+        };
+
+        assert!(BleInterface::new("Meshtastic_a1b2").matches(&properties));
+        assert!(BleInterface::new("00:00:00:00:00:00").matches(&properties));
+        assert!(!BleInterface::new("Meshtastic_ffff").matches(&properties));
+    }
+}
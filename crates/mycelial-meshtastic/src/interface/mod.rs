@@ -5,6 +5,7 @@
 //!
 //! - [`serial::SerialInterface`] - Serial port communication (requires `serial` feature)
 //! - [`tcp::TcpInterface`] - TCP connection (requires `tcp` feature)
+//! - [`tcp_pool::TcpInterfacePool`] - Several TCP radios as one interface (requires `tcp` feature)
 //! - [`ble::BleInterface`] - Bluetooth LE (requires `ble` feature)
 //!
 //! # Feature Requirements
@@ -31,6 +32,11 @@ mod tcp;
 #[cfg(feature = "tcp")]
 pub use tcp::TcpInterface;
 
+#[cfg(feature = "tcp")]
+mod tcp_pool;
+#[cfg(feature = "tcp")]
+pub use tcp_pool::{PoolMemberStats, TcpInterfacePool};
+
 #[cfg(feature = "ble")]
 mod ble;
 #[cfg(feature = "ble")]
@@ -77,8 +83,11 @@ pub enum ConnectionState {
     Connecting,
     /// Successfully connected
     Connected,
-    /// Connection lost, may reconnect
-    Reconnecting,
+    /// Connection lost, reconnecting
+    Reconnecting {
+        /// Reconnection attempt number, starting at 1
+        attempt: u32,
+    },
 }
 
 impl std::fmt::Display for ConnectionState {
@@ -87,7 +96,9 @@ impl std::fmt::Display for ConnectionState {
             ConnectionState::Disconnected => write!(f, "disconnected"),
             ConnectionState::Connecting => write!(f, "connecting"),
             ConnectionState::Connected => write!(f, "connected"),
-            ConnectionState::Reconnecting => write!(f, "reconnecting"),
+            ConnectionState::Reconnecting { attempt } => {
+                write!(f, "reconnecting (attempt {})", attempt)
+            }
         }
     }
 }
@@ -100,5 +111,9 @@ mod tests {
     fn test_connection_state_display() {
         assert_eq!(ConnectionState::Connected.to_string(), "connected");
         assert_eq!(ConnectionState::Disconnected.to_string(), "disconnected");
+        assert_eq!(
+            ConnectionState::Reconnecting { attempt: 3 }.to_string(),
+            "reconnecting (attempt 3)"
+        );
     }
 }
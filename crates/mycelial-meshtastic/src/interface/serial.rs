@@ -3,10 +3,15 @@
 //! This module provides async serial communication with Meshtastic devices
 //! using tokio-serial. It handles packet framing with the Meshtastic protocol
 //! magic number (0x94C3).
+//!
+//! When the device drops off the bus (e.g. while it power-cycles),
+//! [`SerialInterface::reconnect`] reopens the port with the exponential
+//! backoff from [`ReconnectConfig`].
 
-use crate::config::{DEFAULT_BAUD_RATE, DEFAULT_TIMEOUT_MS, MESHTASTIC_MAGIC};
+use crate::config::{ReconnectConfig, DEFAULT_BAUD_RATE, DEFAULT_TIMEOUT_MS, MESHTASTIC_MAGIC};
 use crate::error::{MeshtasticError, Result};
 use crate::interface::{ConnectionState, MeshtasticInterface};
+use crate::proto::{self, MAX_FRAME_PAYLOAD};
 use async_trait::async_trait;
 use bytes::{Buf, Bytes, BytesMut};
 use std::path::{Path, PathBuf};
@@ -48,6 +53,12 @@ pub struct SerialInterface {
 
     /// Interface name for logging
     name: String,
+
+    /// Backoff between reconnection attempts
+    reconnect: ReconnectConfig,
+
+    /// Consecutive failed reconnection attempts
+    reconnect_failures: u32,
 }
 
 impl SerialInterface {
@@ -64,6 +75,8 @@ impl SerialInterface {
             state: ConnectionState::Disconnected,
             read_buffer: BytesMut::with_capacity(READ_BUFFER_SIZE * 2),
             name,
+            reconnect: ReconnectConfig::default(),
+            reconnect_failures: 0,
         }
     }
 
@@ -79,6 +92,61 @@ impl SerialInterface {
         self
     }
 
+    /// Create with custom reconnection backoff
+    pub fn with_reconnect(mut self, reconnect: ReconnectConfig) -> Self {
+        self.reconnect = reconnect;
+        self
+    }
+
+    /// Reopen the port after the connection was lost
+    ///
+    /// Waits with exponential backoff and jitter between attempts, capped at
+    /// the configured maximum delay, and keeps retrying at that delay until
+    /// the port opens or the attempt limit, if any, is reached. The state
+    /// reports [`ConnectionState::Reconnecting`] with the attempt number
+    /// meanwhile.
+    pub async fn reconnect(&mut self) -> Result<()> {
+        self.stream = None;
+        self.read_buffer.clear();
+        self.reconnect_failures = 0;
+
+        loop {
+            if !self.reconnect.should_retry(self.reconnect_failures) {
+                self.state = ConnectionState::Disconnected;
+                return Err(MeshtasticError::Disconnected);
+            }
+
+            let attempt = self.reconnect_failures + 1;
+            self.state = ConnectionState::Reconnecting { attempt };
+            let delay = self
+                .reconnect
+                .backoff_delay(self.reconnect_failures, rand::random());
+            tokio::time::sleep(delay).await;
+
+            match self.connect().await {
+                Ok(()) => {
+                    self.reconnect_failures = 0;
+                    return Ok(());
+                }
+                Err(e) => {
+                    self.reconnect_failures = attempt;
+                    if !self.reconnect.should_retry(self.reconnect_failures) {
+                        return Err(e);
+                    }
+                    warn!(
+                        port = %self.port_path.display(),
+                        attempt,
+                        error = %e,
+                        "Reconnect attempt failed"
+                    );
+                    self.state = ConnectionState::Reconnecting {
+                        attempt: attempt + 1,
+                    };
+                }
+            }
+        }
+    }
+
     /// Get the port path
     pub fn port_path(&self) -> &Path {
         &self.port_path
@@ -134,7 +202,7 @@ impl SerialInterface {
         let length = u16::from_be_bytes([self.read_buffer[2], self.read_buffer[3]]) as usize;
 
         // Sanity check length
-        if length > READ_BUFFER_SIZE {
+        if length > MAX_FRAME_PAYLOAD {
             warn!(length, "Packet length too large, likely corrupt");
             // Skip this magic and try to find next
             self.read_buffer.advance(2);
@@ -155,9 +223,9 @@ impl SerialInterface {
             return Ok(None);
         }
 
-        // Extract the packet
+        // Extract and validate the frame
         let packet = self.read_buffer.split_to(total_size);
-        let payload = Bytes::copy_from_slice(&packet[4..]);
+        let payload = proto::decode_frame(&packet)?;
 
         debug!(size = payload.len(), "Received complete packet");
         Ok(Some(payload))
@@ -174,20 +242,8 @@ impl SerialInterface {
     }
 
     /// Frame a payload with Meshtastic protocol header
-    fn frame_packet(payload: &[u8]) -> Vec<u8> {
-        let length = payload.len() as u16;
-        let mut packet = Vec::with_capacity(4 + payload.len());
-
-        // Magic number (big-endian)
-        packet.extend_from_slice(&MESHTASTIC_MAGIC.to_be_bytes());
-
-        // Length (big-endian)
-        packet.extend_from_slice(&length.to_be_bytes());
-
-        // Payload
-        packet.extend_from_slice(payload);
-
-        packet
+    fn frame_packet(payload: &[u8]) -> Result<Vec<u8>> {
+        proto::encode_frame(payload)
     }
 }
 
@@ -289,7 +345,7 @@ impl MeshtasticInterface for SerialInterface {
     async fn write_packet(&mut self, payload: &[u8]) -> Result<()> {
         let stream = self.stream.as_mut().ok_or(MeshtasticError::Disconnected)?;
 
-        let packet = Self::frame_packet(payload);
+        let packet = Self::frame_packet(payload)?;
         debug!(
             size = packet.len(),
             payload_size = payload.len(),
@@ -330,10 +386,28 @@ impl std::fmt::Debug for SerialInterface {
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_reconnect_gives_up_after_attempt_limit() {
+        let mut interface =
+            SerialInterface::new("/dev/mycelial-missing-radio").with_reconnect(ReconnectConfig {
+                initial_delay: Duration::from_millis(1),
+                max_delay: Duration::from_millis(2),
+                max_attempts: 3,
+                ..ReconnectConfig::default()
+            });
+
+        assert!(matches!(
+            interface.reconnect().await,
+            Err(MeshtasticError::PortNotFound(_))
+        ));
+        assert_eq!(interface.reconnect_failures, 3);
+        assert_eq!(interface.state(), ConnectionState::Disconnected);
+    }
+
     #[test]
     fn test_frame_packet() {
         let payload = b"hello";
-        let framed = SerialInterface::frame_packet(payload);
+        let framed = SerialInterface::frame_packet(payload).unwrap();
 
         // Check magic
         assert_eq!(framed[0], 0x94);
@@ -353,7 +427,7 @@ mod tests {
 
         // Add a complete packet to buffer
         let payload = b"test";
-        let framed = SerialInterface::frame_packet(payload);
+        let framed = SerialInterface::frame_packet(payload).unwrap();
         iface.read_buffer.extend_from_slice(&framed);
 
         // Should parse successfully
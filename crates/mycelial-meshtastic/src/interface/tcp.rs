@@ -3,16 +3,32 @@
 //! This module provides TCP socket connectivity to Meshtastic devices
 //! that expose a network interface.
 //!
+//! Networked devices listen on port 4403 and use the same framing as the
+//! serial API: a 0x94C3 magic number, a big-endian length and the protobuf
+//! payload.
+//!
 //! # Requirements
 //!
 //! Enable the `tcp` feature in Cargo.toml to use this interface.
 
-use crate::error::Result;
+use crate::config::{DEFAULT_TIMEOUT_MS, MESHTASTIC_MAGIC};
+use crate::error::{MeshtasticError, Result};
+use crate::proto::{self, FRAME_HEADER_SIZE, MAX_FRAME_PAYLOAD};
 use async_trait::async_trait;
-use bytes::Bytes;
+use bytes::{Buf, Bytes, BytesMut};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tracing::{debug, info, trace, warn};
 
 use super::{ConnectionState, MeshtasticInterface};
 
+/// Buffer size for reading from the socket
+const READ_BUFFER_SIZE: usize = 512;
+
+/// How long a read waits for data before reporting no packet
+const READ_POLL_TIMEOUT: Duration = Duration::from_millis(100);
+
 /// TCP interface for connecting to Meshtastic devices over network
 ///
 /// Some Meshtastic devices can expose a TCP socket for communication.
@@ -20,6 +36,12 @@ use super::{ConnectionState, MeshtasticInterface};
 pub struct TcpInterface {
     address: String,
     state: ConnectionState,
+    /// Connection timeout
+    timeout: Duration,
+    /// Socket (when connected)
+    stream: Option<TcpStream>,
+    /// Read buffer for accumulating partial frames
+    read_buffer: BytesMut,
 }
 
 impl TcpInterface {
@@ -32,35 +54,170 @@ impl TcpInterface {
         Self {
             address: address.into(),
             state: ConnectionState::Disconnected,
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MS),
+            stream: None,
+            read_buffer: BytesMut::with_capacity(READ_BUFFER_SIZE * 2),
         }
     }
+
+    /// Create with custom connection timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Get the current connection state
+    pub fn state(&self) -> ConnectionState {
+        self.state
+    }
+
+    /// Try to take a complete frame from the read buffer
+    fn try_parse_packet(&mut self) -> Result<Option<Bytes>> {
+        // Resynchronise on the magic number
+        let Some(start) = self
+            .read_buffer
+            .windows(2)
+            .position(|w| u16::from_be_bytes([w[0], w[1]]) == MESHTASTIC_MAGIC)
+        else {
+            // Keep a trailing first magic byte, it may be completed by the next read
+            let keep = usize::from(self.read_buffer.last() == Some(&0x94));
+            let discard = self.read_buffer.len() - keep;
+            if discard > 0 {
+                warn!(discarded = discard, "Discarding buffer without magic");
+                self.read_buffer.advance(discard);
+            }
+            return Ok(None);
+        };
+        if start > 0 {
+            warn!(discarded = start, "Discarding bytes before magic number");
+            self.read_buffer.advance(start);
+        }
+
+        if self.read_buffer.len() < FRAME_HEADER_SIZE {
+            return Ok(None);
+        }
+
+        let length = u16::from_be_bytes([self.read_buffer[2], self.read_buffer[3]]) as usize;
+        if length > MAX_FRAME_PAYLOAD {
+            // Skip this magic and try to find the next
+            self.read_buffer.advance(2);
+            return Err(MeshtasticError::InvalidPacket(format!(
+                "Packet length {} exceeds maximum",
+                length
+            )));
+        }
+
+        let total_size = FRAME_HEADER_SIZE + length;
+        if self.read_buffer.len() < total_size {
+            return Ok(None);
+        }
+
+        let frame = self.read_buffer.split_to(total_size);
+        let payload = proto::decode_frame(&frame)?;
+        debug!(size = payload.len(), "Received complete packet");
+        Ok(Some(payload))
+    }
+
+    /// Mark the connection as lost
+    fn connection_lost(&mut self) {
+        self.stream = None;
+        self.state = ConnectionState::Disconnected;
+    }
 }
 
 #[async_trait]
 impl MeshtasticInterface for TcpInterface {
     async fn connect(&mut self) -> Result<()> {
+        if self.state == ConnectionState::Connected {
+            return Ok(());
+        }
+
         self.state = ConnectionState::Connecting;
-        // TODO: Implement TCP connection
+        info!(address = %self.address, "Connecting to Meshtastic device over TCP");
+
+        let stream =
+            match tokio::time::timeout(self.timeout, TcpStream::connect(&self.address)).await {
+                Ok(Ok(stream)) => stream,
+                Ok(Err(e)) => {
+                    self.state = ConnectionState::Disconnected;
+                    return Err(MeshtasticError::PortOpenFailed {
+                        port: self.address.clone(),
+                        reason: e.to_string(),
+                    });
+                }
+                Err(_) => {
+                    self.state = ConnectionState::Disconnected;
+                    return Err(MeshtasticError::ConnectionTimeout {
+                        duration_ms: self.timeout.as_millis() as u64,
+                    });
+                }
+            };
+        // Frames are small; don't let Nagle hold them back
+        let _ = stream.set_nodelay(true);
+
+        self.stream = Some(stream);
         self.state = ConnectionState::Connected;
+        self.read_buffer.clear();
+
+        info!(address = %self.address, "Connected to Meshtastic device");
         Ok(())
     }
 
     async fn disconnect(&mut self) -> Result<()> {
+        if let Some(mut stream) = self.stream.take() {
+            let _ = stream.shutdown().await;
+        }
+
         self.state = ConnectionState::Disconnected;
+        self.read_buffer.clear();
+
+        info!(address = %self.address, "Disconnected from TCP device");
         Ok(())
     }
 
     fn is_connected(&self) -> bool {
-        self.state == ConnectionState::Connected
+        self.state == ConnectionState::Connected && self.stream.is_some()
     }
 
     async fn read_packet(&mut self) -> Result<Option<Bytes>> {
-        // TODO: Implement TCP read
-        Ok(None)
+        // First, try to parse from existing buffer
+        if let Some(packet) = self.try_parse_packet()? {
+            return Ok(Some(packet));
+        }
+
+        let stream = self.stream.as_mut().ok_or(MeshtasticError::Disconnected)?;
+        let mut buf = [0u8; READ_BUFFER_SIZE];
+
+        match tokio::time::timeout(READ_POLL_TIMEOUT, stream.read(&mut buf)).await {
+            // No data yet
+            Err(_) => Ok(None),
+            Ok(Ok(0)) => {
+                // EOF - device closed the connection
+                self.connection_lost();
+                Err(MeshtasticError::Disconnected)
+            }
+            Ok(Ok(n)) => {
+                trace!(bytes = n, "Read from TCP socket");
+                self.read_buffer.extend_from_slice(&buf[..n]);
+                self.try_parse_packet()
+            }
+            Ok(Err(e)) => {
+                warn!(error = %e, "TCP read error");
+                self.connection_lost();
+                Err(MeshtasticError::ReadError(e.to_string()))
+            }
+        }
     }
 
-    async fn write_packet(&mut self, _packet: &[u8]) -> Result<()> {
-        // TODO: Implement TCP write
+    async fn write_packet(&mut self, packet: &[u8]) -> Result<()> {
+        let frame = proto::encode_frame(packet)?;
+        let stream = self.stream.as_mut().ok_or(MeshtasticError::Disconnected)?;
+
+        if let Err(e) = stream.write_all(&frame).await {
+            warn!(error = %e, "TCP write error");
+            self.connection_lost();
+            return Err(MeshtasticError::WriteError(e.to_string()));
+        }
         Ok(())
     }
 
@@ -68,3 +225,71 @@ impl MeshtasticInterface for TcpInterface {
         &self.address
     }
 }
+
+impl std::fmt::Debug for TcpInterface {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TcpInterface")
+            .field("address", &self.address)
+            .field("state", &self.state)
+            .field("buffer_len", &self.read_buffer.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_tcp_round_trip() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let mut iface = TcpInterface::new(addr.to_string());
+        iface.connect().await.unwrap();
+        let (mut device, _) = listener.accept().await.unwrap();
+
+        // Device sends two frames with garbage in front, split across writes
+        let mut wire = b"noise".to_vec();
+        wire.extend(proto::encode_frame(b"first").unwrap());
+        wire.extend(proto::encode_frame(b"second").unwrap());
+        device.write_all(&wire[..8]).await.unwrap();
+        device.write_all(&wire[8..]).await.unwrap();
+
+        let mut received = Vec::new();
+        while received.len() < 2 {
+            if let Some(packet) = iface.read_packet().await.unwrap() {
+                received.push(packet);
+            }
+        }
+        assert_eq!(received[0].as_ref(), b"first");
+        assert_eq!(received[1].as_ref(), b"second");
+
+        // Writes are framed
+        iface.write_packet(b"reply").await.unwrap();
+        let mut frame = [0u8; FRAME_HEADER_SIZE + 5];
+        device.read_exact(&mut frame).await.unwrap();
+        assert_eq!(proto::decode_frame(&frame).unwrap().as_ref(), b"reply");
+    }
+
+    #[tokio::test]
+    async fn test_tcp_detects_closed_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let mut iface = TcpInterface::new(addr.to_string());
+        iface.connect().await.unwrap();
+        let (device, _) = listener.accept().await.unwrap();
+        drop(device);
+
+        let result = loop {
+            match iface.read_packet().await {
+                Ok(None) => continue,
+                other => break other,
+            }
+        };
+        assert!(matches!(result, Err(MeshtasticError::Disconnected)));
+        assert!(!iface.is_connected());
+    }
+}
@@ -0,0 +1,346 @@
+//! Pool of TCP connections to several networked Meshtastic radios
+//!
+//! A site with more than one IP-connected radio can bridge through all of
+//! them at once. [`TcpInterfacePool`] presents the pool as a single
+//! [`MeshtasticInterface`]: reads are taken round-robin from every healthy
+//! connection, writes are spread across them, and connections that fail are
+//! dropped from rotation until the next [`connect`](MeshtasticInterface::connect).
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use mycelial_meshtastic::interface::TcpInterfacePool;
+//!
+//! let pool = TcpInterfacePool::new(vec![
+//!     "192.168.1.10:4403".parse()?,
+//!     "192.168.1.11:4403".parse()?,
+//! ]);
+//! let (bridge, handle) = MeshtasticBridge::new(pool, &config, publish_callback);
+//! ```
+
+use crate::error::{MeshtasticError, Result};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::future::{select_all, FutureExt};
+use std::net::SocketAddr;
+use tracing::{info, warn};
+
+use super::{MeshtasticInterface, TcpInterface};
+
+/// Health and traffic counters for one pooled connection
+#[derive(Debug, Clone)]
+pub struct PoolMemberStats {
+    /// Radio address
+    pub address: SocketAddr,
+    /// Whether the connection is currently in rotation
+    pub healthy: bool,
+    /// Packets read from this radio
+    pub packets_read: u64,
+    /// Packets written to this radio
+    pub packets_written: u64,
+    /// Times this connection failed and was dropped
+    pub failures: u64,
+    /// Most recent error, if any
+    pub last_error: Option<String>,
+}
+
+/// A pooled connection and its counters
+struct PoolMember {
+    interface: TcpInterface,
+    stats: PoolMemberStats,
+}
+
+impl PoolMember {
+    /// Drop a failed connection from rotation
+    async fn mark_failed(&mut self, error: &MeshtasticError) {
+        warn!(
+            address = %self.stats.address,
+            error = %error,
+            "Dropping failed TCP connection from pool"
+        );
+        let _ = self.interface.disconnect().await;
+        self.stats.healthy = false;
+        self.stats.failures += 1;
+        self.stats.last_error = Some(error.to_string());
+    }
+}
+
+/// A [`MeshtasticInterface`] spanning several TCP-connected radios
+pub struct TcpInterfacePool {
+    members: Vec<PoolMember>,
+    /// Member to poll first on the next read
+    next_read: usize,
+    /// Member to try first on the next write
+    next_write: usize,
+    /// Interface name for logging
+    name: String,
+}
+
+impl TcpInterfacePool {
+    /// Create a pool over the given radio addresses
+    pub fn new(addrs: Vec<SocketAddr>) -> Self {
+        let name = format!("tcp-pool:{}", addrs.len());
+        let members = addrs
+            .into_iter()
+            .map(|address| PoolMember {
+                interface: TcpInterface::new(address.to_string()),
+                stats: PoolMemberStats {
+                    address,
+                    healthy: false,
+                    packets_read: 0,
+                    packets_written: 0,
+                    failures: 0,
+                    last_error: None,
+                },
+            })
+            .collect();
+
+        Self {
+            members,
+            next_read: 0,
+            next_write: 0,
+            name,
+        }
+    }
+
+    /// Per-connection health and traffic
+    pub fn stats(&self) -> Vec<PoolMemberStats> {
+        self.members.iter().map(|m| m.stats.clone()).collect()
+    }
+
+    /// Number of connections currently in rotation
+    pub fn healthy_count(&self) -> usize {
+        self.members.iter().filter(|m| m.stats.healthy).count()
+    }
+}
+
+#[async_trait]
+impl MeshtasticInterface for TcpInterfacePool {
+    /// Connect every radio not already in rotation
+    ///
+    /// Succeeds if at least one connection is healthy afterwards.
+    async fn connect(&mut self) -> Result<()> {
+        let mut last_error = None;
+
+        for member in self.members.iter_mut().filter(|m| !m.stats.healthy) {
+            match member.interface.connect().await {
+                Ok(()) => member.stats.healthy = true,
+                Err(e) => {
+                    member.stats.failures += 1;
+                    member.stats.last_error = Some(e.to_string());
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        let healthy = self.healthy_count();
+        if healthy == 0 {
+            return Err(last_error.unwrap_or(MeshtasticError::Disconnected));
+        }
+
+        info!(
+            healthy,
+            total = self.members.len(),
+            "TCP interface pool connected"
+        );
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        for member in &mut self.members {
+            let _ = member.interface.disconnect().await;
+            member.stats.healthy = false;
+        }
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.healthy_count() > 0
+    }
+
+    /// Read from whichever healthy radio has a packet first
+    ///
+    /// Connections are polled starting one past the last member polled, so
+    /// a busy radio cannot starve the others.
+    async fn read_packet(&mut self) -> Result<Option<Bytes>> {
+        let count = self.members.len();
+        if count == 0 {
+            return Err(MeshtasticError::Disconnected);
+        }
+        let start = self.next_read % count;
+        self.next_read = (start + 1) % count;
+
+        // Rotate so polling order starts at `start`
+        let (head, tail) = self.members.split_at_mut(start);
+        let reads: Vec<_> = tail
+            .iter_mut()
+            .chain(head.iter_mut())
+            .enumerate()
+            .filter(|(_, m)| m.stats.healthy)
+            .map(|(offset, m)| {
+                async move { ((start + offset) % count, m.interface.read_packet().await) }.boxed()
+            })
+            .collect();
+        if reads.is_empty() {
+            return Err(MeshtasticError::Disconnected);
+        }
+
+        let ((index, result), _, pending) = select_all(reads).await;
+        drop(pending);
+        let member = &mut self.members[index];
+        match result {
+            Ok(Some(packet)) => {
+                member.stats.packets_read += 1;
+                Ok(Some(packet))
+            }
+            Ok(None) => Ok(None),
+            Err(e) => {
+                member.mark_failed(&e).await;
+                if self.is_connected() {
+                    Ok(None)
+                } else {
+                    Err(e)
+                }
+            }
+        }
+    }
+
+    /// Write to the next healthy radio, falling through to others on failure
+    async fn write_packet(&mut self, packet: &[u8]) -> Result<()> {
+        let count = self.members.len();
+        let mut last_error = None;
+
+        for attempt in 0..count {
+            let index = (self.next_write + attempt) % count;
+            let member = &mut self.members[index];
+            if !member.stats.healthy {
+                continue;
+            }
+
+            match member.interface.write_packet(packet).await {
+                Ok(()) => {
+                    member.stats.packets_written += 1;
+                    self.next_write = (index + 1) % count;
+                    return Ok(());
+                }
+                // Oversized or malformed packets fail the same way everywhere
+                Err(e @ MeshtasticError::MessageTooLarge { .. }) => return Err(e),
+                Err(e) => {
+                    member.mark_failed(&e).await;
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or(MeshtasticError::Disconnected))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl std::fmt::Debug for TcpInterfacePool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TcpInterfacePool")
+            .field("members", &self.members.len())
+            .field("healthy", &self.healthy_count())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    async fn radio() -> (TcpListener, SocketAddr) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        (listener, addr)
+    }
+
+    async fn read_frame(device: &mut TcpStream, len: usize) -> Vec<u8> {
+        let mut frame = vec![0u8; proto::FRAME_HEADER_SIZE + len];
+        device.read_exact(&mut frame).await.unwrap();
+        proto::decode_frame(&frame).unwrap().to_vec()
+    }
+
+    async fn read_until_packet(pool: &mut TcpInterfacePool) -> Bytes {
+        loop {
+            if let Some(packet) = pool.read_packet().await.unwrap() {
+                return packet;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pool_balances_writes_and_merges_reads() {
+        let (listener_a, addr_a) = radio().await;
+        let (listener_b, addr_b) = radio().await;
+
+        let mut pool = TcpInterfacePool::new(vec![addr_a, addr_b]);
+        pool.connect().await.unwrap();
+        let (mut radio_a, _) = listener_a.accept().await.unwrap();
+        let (mut radio_b, _) = listener_b.accept().await.unwrap();
+        assert_eq!(pool.healthy_count(), 2);
+
+        pool.write_packet(b"one").await.unwrap();
+        pool.write_packet(b"two").await.unwrap();
+        assert_eq!(read_frame(&mut radio_a, 3).await, b"one");
+        assert_eq!(read_frame(&mut radio_b, 3).await, b"two");
+
+        radio_b
+            .write_all(&proto::encode_frame(b"from-b").unwrap())
+            .await
+            .unwrap();
+        assert_eq!(read_until_packet(&mut pool).await.as_ref(), b"from-b");
+
+        let stats = pool.stats();
+        assert_eq!(stats[0].packets_written, 1);
+        assert_eq!(stats[1].packets_written, 1);
+        assert_eq!(stats[1].packets_read, 1);
+    }
+
+    #[tokio::test]
+    async fn test_pool_drops_dead_connection() {
+        let (listener_a, addr_a) = radio().await;
+        let (listener_b, addr_b) = radio().await;
+
+        let mut pool = TcpInterfacePool::new(vec![addr_a, addr_b]);
+        pool.connect().await.unwrap();
+        let (radio_a, _) = listener_a.accept().await.unwrap();
+        let (mut radio_b, _) = listener_b.accept().await.unwrap();
+
+        // Radio A goes away; the pool notices on read and keeps going
+        drop(radio_a);
+        while pool.healthy_count() == 2 {
+            assert!(pool.read_packet().await.unwrap().is_none());
+        }
+        assert!(pool.is_connected());
+
+        let stats = pool.stats();
+        assert!(!stats[0].healthy);
+        assert_eq!(stats[0].failures, 1);
+        assert!(stats[0].last_error.is_some());
+
+        // All writes now go to radio B
+        pool.write_packet(b"one").await.unwrap();
+        pool.write_packet(b"two").await.unwrap();
+        assert_eq!(read_frame(&mut radio_b, 3).await, b"one");
+        assert_eq!(read_frame(&mut radio_b, 3).await, b"two");
+    }
+
+    #[tokio::test]
+    async fn test_pool_connect_fails_when_no_radio_reachable() {
+        let (listener, addr) = radio().await;
+        drop(listener);
+
+        let mut pool = TcpInterfacePool::new(vec![addr]);
+        assert!(pool.connect().await.is_err());
+        assert!(!pool.is_connected());
+        assert_eq!(pool.stats()[0].failures, 1);
+    }
+}
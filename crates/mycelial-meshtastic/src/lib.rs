@@ -97,6 +97,7 @@
 pub mod config;
 pub mod error;
 pub mod interface;
+pub mod proto;
 
 // Phase 2: Bridge components
 pub mod cache;
@@ -107,36 +108,51 @@ pub mod translator;
 pub mod bridge;
 
 // Phase 4: Economics protocol support
+pub mod airtime;
 pub mod compression;
 
 // Phase 5: Testing utilities
+pub mod simulation;
 pub mod test_utils;
 
 // Re-exports for convenience - Phase 1
 pub use config::{
-    BridgeConfig, BridgeDirection, ChannelConfig, ChannelMapping, InterfaceConfig,
+    BridgeConfig, BridgeDirection, ChannelConfig, ChannelMapping, InterfaceConfig, LoraPriority,
     MeshtasticConfig, MeshtasticConfigBuilder, MessagePriority, ReconnectConfig,
 };
 pub use error::{MeshtasticError, Result};
 pub use interface::{ConnectionState, MeshtasticInterface};
+pub use proto::{FromRadio, MeshPacket, ToRadio, MAX_FRAME_PAYLOAD};
 
 #[cfg(feature = "serial")]
 pub use interface::SerialInterface;
 
+#[cfg(feature = "ble")]
+pub use interface::BleInterface;
+
+#[cfg(feature = "tcp")]
+pub use interface::{TcpInterface, TcpInterfacePool};
+
 // Re-exports for convenience - Phase 2
-pub use cache::{CacheStats, DeduplicationCache, DeduplicationKey, MessageDirection};
+pub use cache::{
+    CacheStats, DedupKeyHashing, DeduplicationCache, DeduplicationKey, MessageDirection,
+};
 pub use mapper::{ChannelIndexMapper, NodeIdMapper, TopicMapper};
-pub use translator::{MeshtasticPacket, MeshtasticPort, MessageTranslator};
+pub use translator::{
+    MeshtasticPacket, MeshtasticPort, MessageTranslator, TelemetryData, TELEMETRY_TOPIC,
+};
 
 // Re-exports for convenience - Phase 3
 pub use bridge::{BridgeHandle, BridgeStats, GossipsubMessage, MeshtasticBridge, PublishCallback};
 
 // Re-exports for convenience - Phase 4
+pub use airtime::AirtimeBudget;
 pub use compression::{
     EconomicsMessageCodec, MessageChunk, MessageChunker, MessageCompressor, MessageReassembler,
 };
 
 // Re-exports for convenience - Phase 5 (testing)
+pub use simulation::{MeshTopology, SimulatedInterface, SimulationHandle, Transmission};
 #[cfg(feature = "serial")]
 pub use test_utils::{find_meshtastic_device, list_available_devices, HardwareTestContext};
 pub use test_utils::{DeviceInfo, MockInterface, TestFixture};
@@ -16,15 +16,24 @@
 //! Meshtastic identifies nodes with 4-byte node IDs, while libp2p uses
 //! Ed25519 public keys (PeerId). The NodeIdMapper maintains a registry
 //! of known mappings, learning new associations as messages arrive.
+//!
+//! Virtual PeerIds (`lora:{node_id}`) and hashed NodeIds are derived
+//! deterministically and can always be regenerated. Registered mappings
+//! between a real PeerId and a NodeId cannot, so they are persisted with
+//! [`NodeIdMapper::save`] and restored with [`NodeIdMapper::load`].
 
 use lru::LruCache;
-use mycelial_core::PeerId;
+use mycelial_core::{MessageType, PeerId};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::num::NonZeroUsize;
+use std::path::Path;
 use std::sync::{Arc, RwLock};
 use tracing::{debug, trace, warn};
 
-use crate::config::{BridgeDirection, ChannelConfig, ChannelMapping, MessagePriority};
+use crate::config::{
+    BridgeDirection, ChannelConfig, ChannelMapping, LoraPriority, MessagePriority,
+};
 use crate::error::{MeshtasticError, Result};
 
 // ============================================================================
@@ -119,20 +128,33 @@ impl TopicMapper {
         })
     }
 
-    /// Get the hop limit for a topic based on its priority
+    /// Get the hop limit for a topic based on its configured priority
+    #[deprecated(note = "use `hop_limit_for`, which also considers the message type")]
     pub fn get_hop_limit(&self, topic: &str) -> u8 {
-        self.topic_to_channel
-            .get(topic)
-            .map(|mapping| mapping.priority.hop_limit())
-            .unwrap_or(3)
+        self.hop_limit_for(topic, None)
     }
 
-    /// Get the message priority for a topic
+    /// Get the configured message priority for a topic
+    #[deprecated(note = "use `priority_for`, which also considers the message type")]
     pub fn get_priority(&self, topic: &str) -> MessagePriority {
+        self.priority_for(topic, None)
+    }
+
+    /// Get the hop limit for a message on a topic based on its priority
+    pub fn hop_limit_for(&self, topic: &str, message_type: Option<MessageType>) -> u8 {
+        self.priority_for(topic, message_type).hop_limit()
+    }
+
+    /// Get the priority of a message on a topic
+    ///
+    /// A priority configured for the topic wins; otherwise the message
+    /// type's default applies, and messages of unknown type are normal.
+    pub fn priority_for(&self, topic: &str, message_type: Option<MessageType>) -> MessagePriority {
         self.topic_to_channel
             .get(topic)
-            .map(|mapping| mapping.priority)
-            .unwrap_or(MessagePriority::Normal)
+            .and_then(|mapping| mapping.priority)
+            .or_else(|| message_type.map(|t| t.default_priority()))
+            .unwrap_or_default()
     }
 
     /// Get the default channel name
@@ -170,6 +192,15 @@ impl Default for TopicMapper {
 // Node ID Mapper
 // ============================================================================
 
+/// A persisted node/peer association
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PersistedMapping {
+    /// Meshtastic NodeId
+    node_id: u32,
+    /// libp2p PeerId
+    peer_id: PeerId,
+}
+
 /// Maps between Meshtastic NodeId (u32) and libp2p PeerId
 ///
 /// This mapper maintains a bidirectional registry of known node/peer
@@ -233,7 +264,7 @@ impl NodeIdMapper {
         }
         {
             let mut peer_to_node = self.peer_to_node.write().unwrap();
-            peer_to_node.insert(peer_id.0.clone(), node_id);
+            peer_to_node.insert(peer_id.to_string(), node_id);
         }
     }
 
@@ -259,8 +290,7 @@ impl NodeIdMapper {
 
         // Generate deterministic virtual PeerId
         // Format: "lora:{node_id_hex}" to distinguish from real peers
-        let virtual_id = format!("lora:{:08x}", node_id);
-        let peer_id = PeerId(virtual_id);
+        let peer_id = PeerId::lora(node_id);
 
         // Cache the mapping for consistency
         self.register(node_id, peer_id.clone());
@@ -280,17 +310,14 @@ impl NodeIdMapper {
     /// from the PeerId.
     pub fn peer_to_node(&self, peer_id: &PeerId) -> Result<u32> {
         // Check if this is a virtual LoRa PeerId
-        if peer_id.0.starts_with("lora:") {
-            // Parse the node ID from the virtual PeerId
-            let hex_str = peer_id.0.strip_prefix("lora:").unwrap();
-            return u32::from_str_radix(hex_str, 16)
-                .map_err(|_| MeshtasticError::InvalidNodeId(peer_id.0.clone()));
+        if let Some(node_id) = peer_id.lora_node_id() {
+            return Ok(node_id);
         }
 
         // Check cached mapping
         {
             let peer_to_node = self.peer_to_node.read().unwrap();
-            if let Some(&node_id) = peer_to_node.get(&peer_id.0) {
+            if let Some(&node_id) = peer_to_node.get(peer_id.as_str()) {
                 return Ok(node_id);
             }
         }
@@ -330,7 +357,7 @@ impl NodeIdMapper {
     /// Check if a PeerId is known (has been mapped to a NodeId)
     pub fn is_peer_known(&self, peer_id: &PeerId) -> bool {
         let peer_to_node = self.peer_to_node.read().unwrap();
-        peer_to_node.contains_key(&peer_id.0)
+        peer_to_node.contains_key(peer_id.as_str())
     }
 
     /// Get the number of known mappings
@@ -351,6 +378,80 @@ impl NodeIdMapper {
         }
     }
 
+    /// Save registered mappings to a JSON file
+    ///
+    /// Only mappings that cannot be derived again are written: virtual
+    /// `lora:` PeerIds and NodeIds hashed from a PeerId are regenerated on
+    /// demand. The file is replaced atomically. Returns the number of
+    /// mappings saved.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<usize> {
+        let path = path.as_ref();
+        let mut mappings: Vec<PersistedMapping> = {
+            let node_to_peer = self.node_to_peer.read().unwrap();
+            node_to_peer
+                .iter()
+                .filter(|(node_id, peer_id)| !Self::is_derivable(**node_id, peer_id))
+                .map(|(node_id, peer_id)| PersistedMapping {
+                    node_id: *node_id,
+                    peer_id: peer_id.clone(),
+                })
+                .collect()
+        };
+        mappings.sort_by_key(|mapping| mapping.node_id);
+
+        let json = serde_json::to_vec_pretty(&mappings)
+            .map_err(|e| MeshtasticError::NodeMapPersistence(e.to_string()))?;
+
+        // Write to a sibling file and rename so a crash never leaves a partial map
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, json)?;
+        std::fs::rename(&tmp_path, path)?;
+
+        debug!(
+            path = %path.display(),
+            count = mappings.len(),
+            "Saved node/peer mappings"
+        );
+        Ok(mappings.len())
+    }
+
+    /// Load mappings previously written by [`save`](Self::save)
+    ///
+    /// Loaded mappings are registered alongside any existing ones. A missing
+    /// file is not an error, since a fresh node has nothing to restore.
+    /// Returns the number of mappings loaded.
+    pub fn load(&self, path: impl AsRef<Path>) -> Result<usize> {
+        let path = path.as_ref();
+        let json = match std::fs::read(path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mappings: Vec<PersistedMapping> = serde_json::from_slice(&json)
+            .map_err(|e| MeshtasticError::NodeMapPersistence(e.to_string()))?;
+
+        for mapping in &mappings {
+            if mapping.node_id == 0xFFFFFFFF {
+                warn!(path = %path.display(), "Skipping broadcast address in node map");
+                continue;
+            }
+            self.register(mapping.node_id, mapping.peer_id.clone());
+        }
+
+        debug!(
+            path = %path.display(),
+            count = mappings.len(),
+            "Loaded node/peer mappings"
+        );
+        Ok(mappings.len())
+    }
+
+    /// Whether a mapping would be regenerated identically without persistence
+    fn is_derivable(node_id: u32, peer_id: &PeerId) -> bool {
+        peer_id.lora_node_id() == Some(node_id) || Self::hash_peer_id(peer_id) == node_id
+    }
+
     /// Generate a deterministic NodeId from a PeerId using FNV-1a hash
     fn hash_peer_id(peer_id: &PeerId) -> u32 {
         // FNV-1a hash (32-bit)
@@ -358,7 +459,7 @@ impl NodeIdMapper {
         const FNV_OFFSET: u32 = 2166136261;
 
         let mut hash = FNV_OFFSET;
-        for byte in peer_id.0.as_bytes() {
+        for byte in peer_id.as_str().as_bytes() {
             hash ^= *byte as u32;
             hash = hash.wrapping_mul(FNV_PRIME);
         }
@@ -386,13 +487,16 @@ impl Default for NodeIdMapper {
 ///
 /// Meshtastic devices support up to 8 channels, each identified by a
 /// numeric index. This mapper maintains a consistent mapping between
-/// human-readable channel names and their indices.
-#[derive(Debug, Clone)]
+/// human-readable channel names and their indices, along with the
+/// optional pre-shared key used to encrypt each channel.
+#[derive(Clone)]
 pub struct ChannelIndexMapper {
     /// Channel name to index
     name_to_index: HashMap<String, u8>,
     /// Index to channel name
     index_to_name: [Option<String>; 8],
+    /// Pre-shared key per channel index
+    keys: [Option<[u8; 32]>; 8],
 }
 
 impl ChannelIndexMapper {
@@ -401,6 +505,7 @@ impl ChannelIndexMapper {
         let mut mapper = Self {
             name_to_index: HashMap::new(),
             index_to_name: Default::default(),
+            keys: Default::default(),
         };
 
         // Default Meshtastic channel setup
@@ -444,6 +549,44 @@ impl ChannelIndexMapper {
     pub fn primary_index(&self) -> u8 {
         0
     }
+
+    /// Create a mapper with the default channels and the keys from config
+    ///
+    /// Keys for channel names that have no index are ignored.
+    pub fn from_config(config: &ChannelConfig) -> Self {
+        let mut mapper = Self::new();
+        for (name, key) in &config.psk {
+            match mapper.name_to_index(name) {
+                Some(index) => mapper.set_key(index, Some(*key)),
+                None => warn!(channel = %name, "Ignoring PSK for unknown channel"),
+            }
+        }
+        mapper
+    }
+
+    /// Set or clear the pre-shared key for a channel index
+    pub fn set_key(&mut self, index: u8, key: Option<[u8; 32]>) {
+        if index >= 8 {
+            return;
+        }
+        self.keys[index as usize] = key;
+    }
+
+    /// Get the pre-shared key for a channel index, if the channel is encrypted
+    pub fn key_for(&self, index: u8) -> Option<&[u8; 32]> {
+        self.keys.get(index as usize)?.as_ref()
+    }
+}
+
+impl std::fmt::Debug for ChannelIndexMapper {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print key material
+        let encrypted: Vec<u8> = (0..8u8).filter(|i| self.key_for(*i).is_some()).collect();
+        f.debug_struct("ChannelIndexMapper")
+            .field("index_to_name", &self.index_to_name)
+            .field("encrypted_channels", &encrypted)
+            .finish()
+    }
 }
 
 impl Default for ChannelIndexMapper {
@@ -455,6 +598,11 @@ impl Default for ChannelIndexMapper {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use mycelial_core::Keypair;
+
+    fn random_peer_id() -> PeerId {
+        PeerId::from_public_key(&Keypair::generate().public_key())
+    }
 
     // TopicMapper tests
     #[test]
@@ -509,10 +657,20 @@ mod tests {
         let mapper = TopicMapper::new();
 
         // High priority gets more hops
-        assert!(mapper.get_hop_limit("/mycelial/1.0.0/vouch") > 3);
+        assert!(mapper.hop_limit_for("/mycelial/1.0.0/vouch", None) > 3);
 
         // Normal priority gets default
-        assert_eq!(mapper.get_hop_limit("/mycelial/1.0.0/chat"), 3);
+        assert_eq!(mapper.hop_limit_for("/mycelial/1.0.0/chat", None), 3);
+
+        // Without a topic override the message type decides
+        assert_eq!(
+            mapper.hop_limit_for("/mycelial/1.0.0/chat", Some(MessageType::Credit)),
+            5
+        );
+        assert_eq!(
+            mapper.priority_for("/mycelial/1.0.0/vouch", Some(MessageType::Discovery)),
+            MessagePriority::High
+        );
     }
 
     #[test]
@@ -524,19 +682,29 @@ mod tests {
             ChannelMapping {
                 channel: "Custom".to_string(),
                 direction: BridgeDirection::Bidirectional,
-                priority: MessagePriority::High,
+                priority: Some(MessagePriority::High),
             },
         );
 
         assert!(mapper.topic_to_channel("/custom/topic").is_some());
-        assert_eq!(mapper.get_priority("/custom/topic"), MessagePriority::High);
+        assert_eq!(
+            mapper.priority_for("/custom/topic", None),
+            MessagePriority::High
+        );
+
+        // The old per-topic lookups still answer from the configured priority
+        #[allow(deprecated)]
+        {
+            assert_eq!(mapper.get_priority("/custom/topic"), MessagePriority::High);
+            assert!(mapper.get_hop_limit("/custom/topic") > 3);
+        }
     }
 
     // NodeIdMapper tests
     #[test]
     fn test_node_id_mapper_register() {
         let mapper = NodeIdMapper::new();
-        let peer_id = PeerId("test_peer".to_string());
+        let peer_id = random_peer_id();
 
         mapper.register(0x12345678, peer_id.clone());
 
@@ -548,22 +716,22 @@ mod tests {
     #[test]
     fn test_node_id_mapper_node_to_peer() {
         let mapper = NodeIdMapper::new();
-        let peer_id = PeerId("known_peer".to_string());
+        let peer_id = random_peer_id();
         mapper.register(0xDEADBEEF, peer_id.clone());
 
         // Known node
         let result = mapper.node_to_peer(0xDEADBEEF).unwrap();
-        assert_eq!(result.0, "known_peer");
+        assert_eq!(result, peer_id);
 
         // Unknown node - generates virtual PeerId
         let virtual_peer = mapper.node_to_peer(0x12345678).unwrap();
-        assert!(virtual_peer.0.starts_with("lora:"));
+        assert!(virtual_peer.as_str().starts_with("lora:"));
     }
 
     #[test]
     fn test_node_id_mapper_peer_to_node() {
         let mapper = NodeIdMapper::new();
-        let peer_id = PeerId("known_peer".to_string());
+        let peer_id = random_peer_id();
         mapper.register(0xDEADBEEF, peer_id.clone());
 
         // Known peer
@@ -571,7 +739,7 @@ mod tests {
         assert_eq!(result, 0xDEADBEEF);
 
         // Unknown peer - generates deterministic NodeId
-        let unknown_peer = PeerId("unknown_peer".to_string());
+        let unknown_peer = random_peer_id();
         let node_id = mapper.peer_to_node(&unknown_peer).unwrap();
         assert_ne!(node_id, 0xFFFFFFFF); // Not broadcast
     }
@@ -585,7 +753,7 @@ mod tests {
 
         // Convert to virtual PeerId
         let peer_id = mapper.node_to_peer(original_node_id).unwrap();
-        assert!(peer_id.0.starts_with("lora:"));
+        assert!(peer_id.as_str().starts_with("lora:"));
 
         // Convert back to NodeId
         let recovered_node_id = mapper.peer_to_node(&peer_id).unwrap();
@@ -603,14 +771,11 @@ mod tests {
 
     #[test]
     fn test_node_id_mapper_with_local() {
-        let peer_id = PeerId("local_peer".to_string());
+        let peer_id = random_peer_id();
         let mapper = NodeIdMapper::with_local(0xABCDEF00, peer_id.clone());
 
         assert_eq!(mapper.local_node_id(), Some(0xABCDEF00));
-        assert_eq!(
-            mapper.local_peer_id().map(|p| p.0.as_str()),
-            Some("local_peer")
-        );
+        assert_eq!(mapper.local_peer_id(), Some(&peer_id));
 
         // Local mapping should be registered
         assert!(mapper.is_node_known(0xABCDEF00));
@@ -620,8 +785,8 @@ mod tests {
     #[test]
     fn test_node_id_mapper_clear() {
         let mapper = NodeIdMapper::new();
-        mapper.register(0x12345678, PeerId("peer1".to_string()));
-        mapper.register(0x87654321, PeerId("peer2".to_string()));
+        mapper.register(0x12345678, random_peer_id());
+        mapper.register(0x87654321, random_peer_id());
 
         assert_eq!(mapper.mapping_count(), 2);
 
@@ -630,6 +795,46 @@ mod tests {
         assert_eq!(mapper.mapping_count(), 0);
     }
 
+    #[test]
+    fn test_node_id_mapper_save_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nodes.json");
+
+        let mapper = NodeIdMapper::new();
+        let peer_id = random_peer_id();
+        mapper.register(0x12345678, peer_id.clone());
+        mapper.node_to_peer(0xAABBCCDD).unwrap(); // virtual, derivable
+        mapper.peer_to_node(&random_peer_id()).unwrap(); // hashed, derivable
+
+        assert_eq!(mapper.save(&path).unwrap(), 1);
+
+        let restored = NodeIdMapper::new();
+        assert_eq!(restored.load(&path).unwrap(), 1);
+        assert_eq!(restored.node_to_peer(0x12345678).unwrap(), peer_id);
+        assert_eq!(restored.peer_to_node(&peer_id).unwrap(), 0x12345678);
+        assert!(!restored.is_node_known(0xAABBCCDD));
+    }
+
+    #[test]
+    fn test_node_id_mapper_load_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mapper = NodeIdMapper::new();
+        assert_eq!(mapper.load(dir.path().join("missing.json")).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_node_id_mapper_load_corrupt_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nodes.json");
+        std::fs::write(&path, b"not json").unwrap();
+
+        let mapper = NodeIdMapper::new();
+        assert!(matches!(
+            mapper.load(&path),
+            Err(MeshtasticError::NodeMapPersistence(_))
+        ));
+    }
+
     // ChannelIndexMapper tests
     #[test]
     fn test_channel_index_mapper_defaults() {
@@ -658,4 +863,29 @@ mod tests {
         mapper.set_channel(8, "Invalid");
         assert!(mapper.name_to_index("Invalid").is_none());
     }
+
+    #[test]
+    fn test_channel_index_mapper_keys() {
+        let mut mapper = ChannelIndexMapper::new();
+        assert!(mapper.key_for(0).is_none());
+
+        mapper.set_key(0, Some([1u8; 32]));
+        assert_eq!(mapper.key_for(0), Some(&[1u8; 32]));
+        assert!(mapper.key_for(8).is_none());
+
+        mapper.set_key(0, None);
+        assert!(mapper.key_for(0).is_none());
+    }
+
+    #[test]
+    fn test_channel_index_mapper_from_config() {
+        let mut config = ChannelConfig::default();
+        config.psk.insert("LongFast".to_string(), [2u8; 32]);
+        config.psk.insert("Nonexistent".to_string(), [3u8; 32]);
+
+        let mapper = ChannelIndexMapper::from_config(&config);
+        assert!(mapper.key_for(0).is_none());
+        assert_eq!(mapper.key_for(1), Some(&[2u8; 32]));
+        assert!(!format!("{:?}", mapper).contains("2, 2"));
+    }
 }
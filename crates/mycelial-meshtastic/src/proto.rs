@@ -0,0 +1,701 @@
+//! Meshtastic protobuf messages and serial framing
+//!
+//! This module contains the subset of the Meshtastic `mesh.proto` schema the
+//! bridge needs to talk to real devices, written as hand-derived prost
+//! messages so no `protoc` toolchain is required at build time. Field tags
+//! match the upstream schema, so unknown fields sent by newer firmware are
+//! skipped rather than rejected.
+//!
+//! # Framing
+//!
+//! Over serial (and the TCP API), every protobuf is wrapped in a 4-byte header:
+//!
+//! ```text
+//! ┌────────┬────────┬──────────────┬─────────────────────┐
+//! │  0x94  │  0xC3  │ length (BE)  │ FromRadio / ToRadio │
+//! └────────┴────────┴──────────────┴─────────────────────┘
+//! ```
+//!
+//! Interfaces strip the header before handing bytes to the bridge, so the
+//! bridge only ever sees the protobuf payload.
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use prost::Message;
+
+use crate::config::MESHTASTIC_MAGIC;
+use crate::error::{MeshtasticError, Result};
+use crate::translator::{MeshtasticPacket, MeshtasticPort};
+
+/// Size of the serial frame header (magic + length)
+pub const FRAME_HEADER_SIZE: usize = 4;
+
+/// Largest protobuf payload a device accepts in a single frame
+pub const MAX_FRAME_PAYLOAD: usize = 512;
+
+/// Decoded application payload carried inside a [`MeshPacket`]
+#[derive(Clone, PartialEq, Message)]
+pub struct Data {
+    /// Port number identifying the payload type
+    #[prost(int32, tag = "1")]
+    pub portnum: i32,
+    /// Application payload bytes
+    #[prost(bytes = "vec", tag = "2")]
+    pub payload: Vec<u8>,
+    /// Ask the receiver to reply
+    #[prost(bool, tag = "3")]
+    pub want_response: bool,
+    /// Final destination for multi-hop routed messages
+    #[prost(fixed32, tag = "4")]
+    pub dest: u32,
+    /// Original sender for multi-hop routed messages
+    #[prost(fixed32, tag = "5")]
+    pub source: u32,
+    /// Packet id this message is a response to
+    #[prost(fixed32, tag = "6")]
+    pub request_id: u32,
+}
+
+/// A packet sent or received over the LoRa mesh
+#[derive(Clone, PartialEq, Message)]
+pub struct MeshPacket {
+    /// Sending node number
+    #[prost(fixed32, tag = "1")]
+    pub from: u32,
+    /// Destination node number (`0xFFFFFFFF` for broadcast)
+    #[prost(fixed32, tag = "2")]
+    pub to: u32,
+    /// Channel index
+    #[prost(uint32, tag = "3")]
+    pub channel: u32,
+    /// Decoded or encrypted payload
+    #[prost(oneof = "mesh_packet::PayloadVariant", tags = "4, 5")]
+    pub payload_variant: Option<mesh_packet::PayloadVariant>,
+    /// Unique packet id
+    #[prost(fixed32, tag = "6")]
+    pub id: u32,
+    /// Receive time in seconds since the Unix epoch
+    #[prost(fixed32, tag = "7")]
+    pub rx_time: u32,
+    /// Receive signal-to-noise ratio
+    #[prost(float, tag = "8")]
+    pub rx_snr: f32,
+    /// Remaining hops
+    #[prost(uint32, tag = "9")]
+    pub hop_limit: u32,
+    /// Request an acknowledgement from the recipient
+    #[prost(bool, tag = "10")]
+    pub want_ack: bool,
+    /// Transmit priority
+    #[prost(int32, tag = "11")]
+    pub priority: i32,
+    /// Receive signal strength
+    #[prost(int32, tag = "12")]
+    pub rx_rssi: i32,
+    /// Hop limit the packet was originally sent with
+    #[prost(uint32, tag = "15")]
+    pub hop_start: u32,
+}
+
+/// Nested types for [`MeshPacket`]
+pub mod mesh_packet {
+    /// Payload of a mesh packet
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub enum PayloadVariant {
+        /// Payload the device has already decrypted
+        #[prost(message, tag = "4")]
+        Decoded(super::Data),
+        /// Payload still encrypted with the channel key
+        #[prost(bytes = "vec", tag = "5")]
+        Encrypted(Vec<u8>),
+    }
+}
+
+/// Routing control message, carried on the routing port
+///
+/// An acknowledgement is a routing message whose `Data::request_id` names the
+/// acknowledged packet and whose error reason is [`ROUTING_ERROR_NONE`].
+#[derive(Clone, PartialEq, Message)]
+pub struct Routing {
+    /// Route discovery or error report
+    #[prost(oneof = "routing::Variant", tags = "3")]
+    pub variant: Option<routing::Variant>,
+}
+
+/// Nested types for [`Routing`]
+pub mod routing {
+    /// Contents of a [`super::Routing`] message
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub enum Variant {
+        /// Delivery result for the packet named by `request_id`
+        #[prost(int32, tag = "3")]
+        ErrorReason(i32),
+    }
+}
+
+/// Routing error reason meaning the packet was delivered
+pub const ROUTING_ERROR_NONE: i32 = 0;
+
+/// GPS fix reported on the position port
+///
+/// Coordinates are fixed-point degrees scaled by [`POSITION_SCALE`].
+#[derive(Clone, PartialEq, Message)]
+pub struct Position {
+    /// Latitude in 1e-7 degrees
+    #[prost(sfixed32, optional, tag = "1")]
+    pub latitude_i: Option<i32>,
+    /// Longitude in 1e-7 degrees
+    #[prost(sfixed32, optional, tag = "2")]
+    pub longitude_i: Option<i32>,
+    /// Altitude above mean sea level in meters
+    #[prost(int32, optional, tag = "3")]
+    pub altitude: Option<i32>,
+    /// Seconds since the Unix epoch when the fix was taken
+    #[prost(fixed32, tag = "4")]
+    pub time: u32,
+}
+
+/// Degrees per unit of [`Position`] latitude and longitude
+pub const POSITION_SCALE: f64 = 1e-7;
+
+/// Periodic device report sent on the telemetry port
+///
+/// Only the device metrics variant is modelled; environment and power
+/// metrics from newer firmware decode with an empty variant.
+#[derive(Clone, PartialEq, Message)]
+pub struct Telemetry {
+    /// Seconds since the Unix epoch when the report was taken
+    #[prost(fixed32, tag = "1")]
+    pub time: u32,
+    /// Report contents
+    #[prost(oneof = "telemetry::Variant", tags = "2")]
+    pub variant: Option<telemetry::Variant>,
+}
+
+/// Nested types for [`Telemetry`]
+pub mod telemetry {
+    /// Contents of a [`super::Telemetry`] report
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub enum Variant {
+        /// Radio and battery health
+        #[prost(message, tag = "2")]
+        DeviceMetrics(super::DeviceMetrics),
+    }
+}
+
+/// Radio and battery health of a device
+#[derive(Clone, PartialEq, Message)]
+pub struct DeviceMetrics {
+    /// Battery charge in percent (101 means externally powered)
+    #[prost(uint32, optional, tag = "1")]
+    pub battery_level: Option<u32>,
+    /// Battery voltage
+    #[prost(float, optional, tag = "2")]
+    pub voltage: Option<f32>,
+    /// Percent of the current channel's airtime in use by any node
+    #[prost(float, optional, tag = "3")]
+    pub channel_utilization: Option<f32>,
+    /// Percent of the hourly transmit airtime used by this device
+    #[prost(float, optional, tag = "4")]
+    pub air_util_tx: Option<f32>,
+    /// Seconds since the device booted
+    #[prost(uint32, optional, tag = "5")]
+    pub uptime_seconds: Option<u32>,
+}
+
+/// Information about the locally attached node
+#[derive(Clone, PartialEq, Message)]
+pub struct MyNodeInfo {
+    /// Node number of the attached device
+    #[prost(uint32, tag = "1")]
+    pub my_node_num: u32,
+}
+
+/// Message sent from the device to the host
+#[derive(Clone, PartialEq, Message)]
+pub struct FromRadio {
+    /// Monotonic message id
+    #[prost(uint32, tag = "1")]
+    pub id: u32,
+    /// Message contents
+    #[prost(oneof = "from_radio::PayloadVariant", tags = "2, 3, 7, 8")]
+    pub payload_variant: Option<from_radio::PayloadVariant>,
+}
+
+/// Nested types for [`FromRadio`]
+pub mod from_radio {
+    /// Contents of a [`super::FromRadio`] message
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub enum PayloadVariant {
+        /// A packet received from the mesh
+        #[prost(message, tag = "2")]
+        Packet(super::MeshPacket),
+        /// Information about the attached node
+        #[prost(message, tag = "3")]
+        MyInfo(super::MyNodeInfo),
+        /// Config download finished for the given `want_config_id`
+        #[prost(uint32, tag = "7")]
+        ConfigCompleteId(u32),
+        /// The device rebooted
+        #[prost(bool, tag = "8")]
+        Rebooted(bool),
+    }
+}
+
+/// Message sent from the host to the device
+#[derive(Clone, PartialEq, Message)]
+pub struct ToRadio {
+    /// Message contents
+    #[prost(oneof = "to_radio::PayloadVariant", tags = "1, 3, 4")]
+    pub payload_variant: Option<to_radio::PayloadVariant>,
+}
+
+/// Nested types for [`ToRadio`]
+pub mod to_radio {
+    /// Contents of a [`super::ToRadio`] message
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub enum PayloadVariant {
+        /// A packet to transmit over the mesh
+        #[prost(message, tag = "1")]
+        Packet(super::MeshPacket),
+        /// Request the device configuration
+        #[prost(uint32, tag = "3")]
+        WantConfigId(u32),
+        /// Tell the device the host is going away
+        #[prost(bool, tag = "4")]
+        Disconnect(bool),
+    }
+}
+
+impl FromRadio {
+    /// Wrap a mesh packet in a `FromRadio` message
+    pub fn packet(packet: MeshPacket) -> Self {
+        Self {
+            id: 0,
+            payload_variant: Some(from_radio::PayloadVariant::Packet(packet)),
+        }
+    }
+}
+
+impl ToRadio {
+    /// Wrap a mesh packet in a `ToRadio` message
+    pub fn packet(packet: MeshPacket) -> Self {
+        Self {
+            payload_variant: Some(to_radio::PayloadVariant::Packet(packet)),
+        }
+    }
+}
+
+/// AES-256 in counter mode with a 128-bit big-endian counter, as used by
+/// Meshtastic channel encryption
+type Aes256Ctr = ctr::Ctr128BE<aes::Aes256>;
+
+/// Build the per-packet CTR nonce: packet id (u64 LE), sender (u32 LE), zero counter
+fn packet_nonce(packet_id: u32, from: u32) -> [u8; 16] {
+    let mut nonce = [0u8; 16];
+    nonce[0..8].copy_from_slice(&u64::from(packet_id).to_le_bytes());
+    nonce[8..12].copy_from_slice(&from.to_le_bytes());
+    nonce
+}
+
+/// Apply the channel keystream for a packet (encryption and decryption are identical)
+fn apply_channel_cipher(key: &[u8; 32], packet_id: u32, from: u32, data: &mut [u8]) -> Result<()> {
+    use ctr::cipher::{KeyIvInit, StreamCipher};
+
+    let mut cipher = Aes256Ctr::new_from_slices(key, &packet_nonce(packet_id, from))
+        .map_err(|e| MeshtasticError::Internal(format!("Invalid channel key: {}", e)))?;
+    cipher.apply_keystream(data);
+    Ok(())
+}
+
+impl MeshPacket {
+    /// Encrypt the decoded payload in place with a channel's pre-shared key
+    pub fn encrypt(&mut self, key: &[u8; 32]) -> Result<()> {
+        match self.payload_variant.take() {
+            Some(mesh_packet::PayloadVariant::Decoded(data)) => {
+                let mut bytes = data.encode_to_vec();
+                apply_channel_cipher(key, self.id, self.from, &mut bytes)?;
+                self.payload_variant = Some(mesh_packet::PayloadVariant::Encrypted(bytes));
+                Ok(())
+            }
+            other => {
+                self.payload_variant = other;
+                Err(MeshtasticError::InvalidPacket(
+                    "No decoded payload to encrypt".to_string(),
+                ))
+            }
+        }
+    }
+
+    /// Decrypt the encrypted payload in place with a channel's pre-shared key
+    pub fn decrypt(&mut self, key: &[u8; 32]) -> Result<()> {
+        match self.payload_variant.take() {
+            Some(mesh_packet::PayloadVariant::Encrypted(mut bytes)) => {
+                apply_channel_cipher(key, self.id, self.from, &mut bytes)?;
+                let data = Data::decode(bytes.as_slice())
+                    .map_err(|e| MeshtasticError::ProtobufDecode(e.to_string()))?;
+                self.payload_variant = Some(mesh_packet::PayloadVariant::Decoded(data));
+                Ok(())
+            }
+            other => {
+                self.payload_variant = other;
+                Err(MeshtasticError::InvalidPacket(
+                    "No encrypted payload to decrypt".to_string(),
+                ))
+            }
+        }
+    }
+}
+
+impl From<&MeshtasticPacket> for MeshPacket {
+    fn from(packet: &MeshtasticPacket) -> Self {
+        Self {
+            from: packet.from,
+            to: packet.to,
+            channel: u32::from(packet.channel),
+            payload_variant: Some(mesh_packet::PayloadVariant::Decoded(Data {
+                portnum: u32::from(packet.port_num) as i32,
+                payload: packet.payload.to_vec(),
+                ..Default::default()
+            })),
+            id: packet.packet_id,
+            rx_time: packet.rx_time.map(|t| t.timestamp() as u32).unwrap_or(0),
+            hop_limit: u32::from(packet.hop_limit),
+            want_ack: packet.want_ack,
+            ..Default::default()
+        }
+    }
+}
+
+impl TryFrom<&MeshPacket> for MeshtasticPacket {
+    type Error = MeshtasticError;
+
+    fn try_from(packet: &MeshPacket) -> Result<Self> {
+        let data = match &packet.payload_variant {
+            Some(mesh_packet::PayloadVariant::Decoded(data)) => data,
+            Some(mesh_packet::PayloadVariant::Encrypted(_)) => {
+                return Err(MeshtasticError::InvalidPacket(
+                    "Encrypted payload must be decrypted with the channel key first".to_string(),
+                ));
+            }
+            None => {
+                return Err(MeshtasticError::InvalidPacket(
+                    "Mesh packet has no payload".to_string(),
+                ));
+            }
+        };
+
+        let rx_time = if packet.rx_time == 0 {
+            Utc::now()
+        } else {
+            DateTime::from_timestamp(i64::from(packet.rx_time), 0).unwrap_or_else(Utc::now)
+        };
+
+        Ok(MeshtasticPacket {
+            from: packet.from,
+            to: packet.to,
+            packet_id: packet.id,
+            channel: u8::try_from(packet.channel).map_err(|_| {
+                MeshtasticError::InvalidPacket(format!("Invalid channel {}", packet.channel))
+            })?,
+            port_num: MeshtasticPort::from(data.portnum as u32),
+            payload: Bytes::copy_from_slice(&data.payload),
+            hop_limit: packet.hop_limit.min(u32::from(u8::MAX)) as u8,
+            want_ack: packet.want_ack,
+            rx_time: Some(rx_time),
+        })
+    }
+}
+
+/// Extract `(request_id, error_reason)` if this packet is a routing acknowledgement
+pub fn routing_ack(packet: &MeshPacket) -> Option<(u32, i32)> {
+    let Some(mesh_packet::PayloadVariant::Decoded(data)) = &packet.payload_variant else {
+        return None;
+    };
+    if MeshtasticPort::from(data.portnum as u32) != MeshtasticPort::Routing || data.request_id == 0
+    {
+        return None;
+    }
+
+    let routing = Routing::decode(data.payload.as_slice()).ok()?;
+    match routing.variant {
+        Some(routing::Variant::ErrorReason(reason)) => Some((data.request_id, reason)),
+        None => None,
+    }
+}
+
+/// Decode a `FromRadio` protobuf (without the serial header)
+pub fn decode_from_radio(data: &[u8]) -> Result<FromRadio> {
+    FromRadio::decode(data).map_err(|e| MeshtasticError::ProtobufDecode(e.to_string()))
+}
+
+/// Encode a `ToRadio` protobuf (without the serial header)
+pub fn encode_to_radio(message: &ToRadio) -> Result<Vec<u8>> {
+    let mut buf = Vec::with_capacity(message.encoded_len());
+    message
+        .encode(&mut buf)
+        .map_err(|e| MeshtasticError::ProtobufEncode(e.to_string()))?;
+    Ok(buf)
+}
+
+/// Wrap a protobuf payload in the `0x94C3` + length serial header
+pub fn encode_frame(payload: &[u8]) -> Result<Vec<u8>> {
+    if payload.len() > MAX_FRAME_PAYLOAD {
+        return Err(MeshtasticError::MessageTooLarge {
+            size: payload.len(),
+            max: MAX_FRAME_PAYLOAD,
+        });
+    }
+
+    let mut frame = Vec::with_capacity(FRAME_HEADER_SIZE + payload.len());
+    frame.extend_from_slice(&MESHTASTIC_MAGIC.to_be_bytes());
+    frame.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+    frame.extend_from_slice(payload);
+    Ok(frame)
+}
+
+/// Validate a complete serial frame and return its protobuf payload
+///
+/// The frame must start with the `0x94C3` magic and its length prefix must
+/// match the number of bytes that follow the header exactly.
+pub fn decode_frame(frame: &[u8]) -> Result<Bytes> {
+    if frame.len() < FRAME_HEADER_SIZE {
+        return Err(MeshtasticError::InvalidPacket(format!(
+            "Frame too short: {} bytes",
+            frame.len()
+        )));
+    }
+
+    let magic = u16::from_be_bytes([frame[0], frame[1]]);
+    if magic != MESHTASTIC_MAGIC {
+        return Err(MeshtasticError::InvalidMagic { got: magic });
+    }
+
+    let length = u16::from_be_bytes([frame[2], frame[3]]) as usize;
+    if length > MAX_FRAME_PAYLOAD {
+        return Err(MeshtasticError::InvalidPacket(format!(
+            "Frame length {} exceeds maximum {}",
+            length, MAX_FRAME_PAYLOAD
+        )));
+    }
+
+    let payload = &frame[FRAME_HEADER_SIZE..];
+    if payload.len() != length {
+        return Err(MeshtasticError::InvalidPacket(format!(
+            "Frame length prefix {} does not match payload size {}",
+            length,
+            payload.len()
+        )));
+    }
+
+    Ok(Bytes::copy_from_slice(payload))
+}
+
+/// The pre-protobuf byte layout used by early bridge prototypes
+///
+/// Kept only so tests can check that legacy packets are rejected.
+#[cfg(test)]
+pub(crate) mod simplified {
+    use super::*;
+
+    /// Encode a packet as `from | to | id` (big-endian u32) + port byte + payload
+    pub fn encode(packet: &MeshtasticPacket) -> Vec<u8> {
+        let mut encoded = Vec::with_capacity(packet.payload.len() + 13);
+        encoded.extend_from_slice(&packet.from.to_be_bytes());
+        encoded.extend_from_slice(&packet.to.to_be_bytes());
+        encoded.extend_from_slice(&packet.packet_id.to_be_bytes());
+        encoded.push(u32::from(packet.port_num) as u8);
+        encoded.extend_from_slice(&packet.payload);
+        encoded
+    }
+
+    /// Decode the layout produced by [`encode`]
+    pub fn decode(data: &[u8]) -> Result<MeshtasticPacket> {
+        if data.len() < 13 {
+            return Err(MeshtasticError::InvalidPacket(
+                "Packet too short".to_string(),
+            ));
+        }
+
+        Ok(MeshtasticPacket {
+            from: u32::from_be_bytes([data[0], data[1], data[2], data[3]]),
+            to: u32::from_be_bytes([data[4], data[5], data[6], data[7]]),
+            packet_id: u32::from_be_bytes([data[8], data[9], data[10], data[11]]),
+            channel: 0,
+            port_num: MeshtasticPort::from(data[12] as u32),
+            payload: Bytes::copy_from_slice(&data[13..]),
+            hop_limit: 3,
+            want_ack: false,
+            rx_time: Some(Utc::now()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_packet() -> MeshtasticPacket {
+        MeshtasticPacket {
+            from: 0x12345678,
+            to: 0xFFFFFFFF,
+            packet_id: 42,
+            channel: 1,
+            port_num: MeshtasticPort::MycelialVouch,
+            payload: Bytes::from_static(b"vouch_payload"),
+            hop_limit: 3,
+            want_ack: true,
+            rx_time: DateTime::from_timestamp(1_700_000_000, 0),
+        }
+    }
+
+    #[test]
+    fn test_mesh_packet_roundtrip() {
+        let packet = sample_packet();
+        let mesh = MeshPacket::from(&packet);
+        let bytes = mesh.encode_to_vec();
+
+        let decoded = MeshPacket::decode(bytes.as_slice()).unwrap();
+        let restored = MeshtasticPacket::try_from(&decoded).unwrap();
+
+        assert_eq!(restored.from, packet.from);
+        assert_eq!(restored.to, packet.to);
+        assert_eq!(restored.packet_id, packet.packet_id);
+        assert_eq!(restored.channel, packet.channel);
+        assert_eq!(restored.port_num, packet.port_num);
+        assert_eq!(restored.payload, packet.payload);
+        assert_eq!(restored.hop_limit, packet.hop_limit);
+        assert!(restored.want_ack);
+        assert_eq!(restored.rx_time, packet.rx_time);
+    }
+
+    #[test]
+    fn test_from_radio_packet_decode() {
+        let from_radio = FromRadio::packet(MeshPacket::from(&sample_packet()));
+        let decoded = decode_from_radio(&from_radio.encode_to_vec()).unwrap();
+        assert_eq!(decoded, from_radio);
+    }
+
+    #[test]
+    fn test_to_radio_encode() {
+        let to_radio = ToRadio::packet(MeshPacket::from(&sample_packet()));
+        let bytes = encode_to_radio(&to_radio).unwrap();
+        assert_eq!(ToRadio::decode(bytes.as_slice()).unwrap(), to_radio);
+    }
+
+    #[test]
+    fn test_encrypted_packet_rejected() {
+        let mesh = MeshPacket {
+            payload_variant: Some(mesh_packet::PayloadVariant::Encrypted(vec![1, 2, 3])),
+            ..Default::default()
+        };
+        assert!(MeshtasticPacket::try_from(&mesh).is_err());
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_roundtrip() {
+        let key = [9u8; 32];
+        let original = MeshPacket::from(&sample_packet());
+
+        let mut mesh = original.clone();
+        mesh.encrypt(&key).unwrap();
+        match &mesh.payload_variant {
+            Some(mesh_packet::PayloadVariant::Encrypted(bytes)) => {
+                assert!(!bytes.windows(5).any(|w| w == b"vouch"));
+            }
+            other => panic!("expected encrypted payload, got {:?}", other),
+        }
+
+        mesh.decrypt(&key).unwrap();
+        assert_eq!(mesh, original);
+    }
+
+    #[test]
+    fn test_nonce_layout() {
+        let nonce = packet_nonce(0x01020304, 0x0A0B0C0D);
+        assert_eq!(&nonce[0..8], &[4, 3, 2, 1, 0, 0, 0, 0]);
+        assert_eq!(&nonce[8..12], &[0x0D, 0x0C, 0x0B, 0x0A]);
+        assert_eq!(&nonce[12..16], &[0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_encrypt_requires_decoded_payload() {
+        let mut mesh = MeshPacket::default();
+        assert!(mesh.encrypt(&[0u8; 32]).is_err());
+        assert!(mesh.decrypt(&[0u8; 32]).is_err());
+    }
+
+    #[test]
+    fn test_routing_ack() {
+        let routing = Routing {
+            variant: Some(routing::Variant::ErrorReason(ROUTING_ERROR_NONE)),
+        };
+        let mut mesh = MeshPacket {
+            from: 0x0A0B0C0D,
+            payload_variant: Some(mesh_packet::PayloadVariant::Decoded(Data {
+                portnum: MeshtasticPort::Routing as i32,
+                payload: routing.encode_to_vec(),
+                request_id: 42,
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        assert_eq!(routing_ack(&mesh), Some((42, ROUTING_ERROR_NONE)));
+
+        // Ordinary data packets are not acknowledgements
+        assert_eq!(routing_ack(&MeshPacket::from(&sample_packet())), None);
+
+        // Routing messages without a request id are not acknowledgements
+        if let Some(mesh_packet::PayloadVariant::Decoded(data)) = &mut mesh.payload_variant {
+            data.request_id = 0;
+        }
+        assert_eq!(routing_ack(&mesh), None);
+    }
+
+    #[test]
+    fn test_frame_roundtrip() {
+        let frame = encode_frame(b"payload").unwrap();
+        assert_eq!(&frame[0..2], &[0x94, 0xC3]);
+        assert_eq!(&frame[2..4], &7u16.to_be_bytes());
+        assert_eq!(
+            decode_frame(&frame).unwrap(),
+            Bytes::from_static(b"payload")
+        );
+    }
+
+    #[test]
+    fn test_frame_rejects_bad_magic() {
+        let mut frame = encode_frame(b"payload").unwrap();
+        frame[0] = 0x00;
+        assert!(matches!(
+            decode_frame(&frame),
+            Err(MeshtasticError::InvalidMagic { got: 0x00C3 })
+        ));
+    }
+
+    #[test]
+    fn test_frame_rejects_length_mismatch() {
+        let mut frame = encode_frame(b"payload").unwrap();
+        frame.push(0);
+        assert!(decode_frame(&frame).is_err());
+        assert!(decode_frame(&[0x94, 0xC3]).is_err());
+    }
+
+    #[test]
+    fn test_frame_rejects_oversized_payload() {
+        let payload = vec![0u8; MAX_FRAME_PAYLOAD + 1];
+        assert!(matches!(
+            encode_frame(&payload),
+            Err(MeshtasticError::MessageTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_simplified_roundtrip() {
+        let packet = sample_packet();
+        let decoded = simplified::decode(&simplified::encode(&packet)).unwrap();
+        assert_eq!(decoded.from, packet.from);
+        assert_eq!(decoded.packet_id, packet.packet_id);
+        assert_eq!(decoded.payload, packet.payload);
+    }
+}
@@ -0,0 +1,453 @@
+//! Simulated LoRa mesh for running the bridge without hardware
+//!
+//! [`MeshtasticBridge::simulated`] runs the bridge against a
+//! [`SimulatedInterface`] attached to a virtual mesh described by a
+//! [`MeshTopology`], instead of a real device.
+//!
+//! - Packets injected at a virtual node flood hop by hop towards the bridge's
+//!   attached node, and each link traversal is lost with that link's
+//!   probability.
+//! - Every packet the bridge transmits is recorded with its estimated airtime
+//!   and the nodes it would reach.
+//!
+//! Loss is drawn from a seeded RNG, so a topology and seed always produce the
+//! same outcome.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use mycelial_meshtastic::simulation::MeshTopology;
+//!
+//! // Bridge node 1 hears node 2 directly and node 3 through node 2
+//! let topology = MeshTopology::new(1).link(1, 2, 0.0).link(2, 3, 0.25);
+//! let (bridge, handle, sim) = MeshtasticBridge::simulated(topology, &config, publish);
+//! tokio::spawn(bridge.run());
+//!
+//! sim.inject(MeshtasticPacket::new_outgoing(3, 0xFFFFFFFF, port, payload, 3));
+//! ```
+
+use bytes::Bytes;
+use prost::Message;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{debug, trace};
+
+use crate::airtime::estimate_airtime;
+use crate::bridge::{BridgeHandle, MeshtasticBridge, PublishCallback};
+use crate::config::MeshtasticConfig;
+use crate::error::{MeshtasticError, Result};
+use crate::interface::MeshtasticInterface;
+use crate::proto::{from_radio, to_radio, FromRadio, MeshPacket, MyNodeInfo, ToRadio};
+use crate::translator::MeshtasticPacket;
+
+/// Seed used when none is given
+const DEFAULT_SEED: u64 = 0x6d79_6365_6c69_616c;
+
+/// Nodes and lossy radio links of a virtual mesh
+#[derive(Debug, Clone)]
+pub struct MeshTopology {
+    /// Node the bridge's device is attached to
+    local_node: u32,
+    /// Loss probability of each link, in both directions
+    links: BTreeMap<u32, BTreeMap<u32, f64>>,
+    /// Seed for packet loss
+    seed: u64,
+}
+
+impl MeshTopology {
+    /// Create a mesh containing only the bridge's attached node
+    pub fn new(local_node: u32) -> Self {
+        let mut links = BTreeMap::new();
+        links.insert(local_node, BTreeMap::new());
+        Self {
+            local_node,
+            links,
+            seed: DEFAULT_SEED,
+        }
+    }
+
+    /// Connect two nodes with a link that loses packets with probability `loss`
+    pub fn link(mut self, a: u32, b: u32, loss: f64) -> Self {
+        let loss = loss.clamp(0.0, 1.0);
+        self.links.entry(a).or_default().insert(b, loss);
+        self.links.entry(b).or_default().insert(a, loss);
+        self
+    }
+
+    /// Seed the packet loss RNG
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Node the bridge's device is attached to
+    pub fn local_node(&self) -> u32 {
+        self.local_node
+    }
+
+    /// All nodes in the mesh
+    pub fn nodes(&self) -> impl Iterator<Item = u32> + '_ {
+        self.links.keys().copied()
+    }
+
+    /// Number of hops on the shortest path between two nodes
+    pub fn hops(&self, from: u32, to: u32) -> Option<u8> {
+        self.flood(from, u8::MAX, |_| true).get(&to).copied()
+    }
+
+    /// Flood a packet from `from`, returning the hop count at which each
+    /// node first received it
+    ///
+    /// Every node that receives the packet relays it once while relays
+    /// remain, so a packet with hop limit `n` reaches nodes up to `n + 1`
+    /// hops away. `deliver` decides whether a single link traversal succeeds.
+    fn flood(
+        &self,
+        from: u32,
+        hop_limit: u8,
+        mut deliver: impl FnMut(f64) -> bool,
+    ) -> BTreeMap<u32, u8> {
+        let mut reached = BTreeMap::new();
+        let mut frontier = VecDeque::from([(from, 0u8)]);
+
+        while let Some((node, hops)) = frontier.pop_front() {
+            // The originator transmits once; relays spend the hop limit
+            if hops > hop_limit {
+                continue;
+            }
+            let Some(neighbours) = self.links.get(&node) else {
+                continue;
+            };
+            for (&neighbour, &loss) in neighbours {
+                if neighbour == from || reached.contains_key(&neighbour) {
+                    continue;
+                }
+                if deliver(loss) {
+                    reached.insert(neighbour, hops + 1);
+                    frontier.push_back((neighbour, hops + 1));
+                }
+            }
+        }
+
+        reached
+    }
+}
+
+/// A packet the bridge handed to the simulated device
+#[derive(Debug, Clone)]
+pub struct Transmission {
+    /// Packet as written to the device
+    pub packet: MeshPacket,
+    /// Estimated time on air
+    pub airtime: Duration,
+    /// Nodes that received the packet, with the hop count it took
+    pub reached: BTreeMap<u32, u8>,
+}
+
+/// State shared by the simulated interface and its handle
+#[derive(Debug)]
+struct SimulatedMesh {
+    topology: MeshTopology,
+    rng: StdRng,
+    spreading_factor: u8,
+    bandwidth_khz: u32,
+    transmissions: Vec<Transmission>,
+    delivered: u64,
+    lost: u64,
+}
+
+impl SimulatedMesh {
+    /// Flood a packet through the mesh, drawing loss from the seeded RNG
+    fn propagate(&mut self, from: u32, hop_limit: u8) -> BTreeMap<u32, u8> {
+        let rng = &mut self.rng;
+        self.topology
+            .flood(from, hop_limit, |loss| !rng.gen_bool(loss))
+    }
+}
+
+/// Observes and drives a simulated mesh
+#[derive(Debug, Clone)]
+pub struct SimulationHandle {
+    mesh: Arc<Mutex<SimulatedMesh>>,
+    inbound_tx: mpsc::UnboundedSender<Bytes>,
+}
+
+impl SimulationHandle {
+    /// Transmit a packet from a virtual node
+    ///
+    /// Returns whether it reached the bridge's attached node. Delivered
+    /// packets arrive with their hop limit reduced by the relays they took.
+    pub fn inject(&self, mut packet: MeshtasticPacket) -> bool {
+        let local_node = {
+            let mut mesh = self.mesh.lock().unwrap();
+            let local_node = mesh.topology.local_node;
+            let reached = mesh.propagate(packet.from, packet.hop_limit);
+            match reached.get(&local_node) {
+                Some(&hops) => {
+                    mesh.delivered += 1;
+                    packet.hop_limit -= hops - 1;
+                    local_node
+                }
+                None => {
+                    mesh.lost += 1;
+                    debug!(
+                        "Simulated packet from 0x{:08X} lost before reaching the bridge",
+                        packet.from
+                    );
+                    return false;
+                }
+            }
+        };
+
+        trace!(
+            "Simulated packet from 0x{:08X} reached 0x{:08X}",
+            packet.from,
+            local_node
+        );
+        let frame = FromRadio::packet(MeshPacket::from(&packet)).encode_to_vec();
+        self.inbound_tx.send(Bytes::from(frame)).is_ok()
+    }
+
+    /// Packets the bridge has transmitted so far
+    pub fn transmissions(&self) -> Vec<Transmission> {
+        self.mesh.lock().unwrap().transmissions.clone()
+    }
+
+    /// Total estimated airtime of everything transmitted
+    pub fn total_airtime(&self) -> Duration {
+        self.mesh
+            .lock()
+            .unwrap()
+            .transmissions
+            .iter()
+            .map(|t| t.airtime)
+            .sum()
+    }
+
+    /// Injected packets that reached the bridge
+    pub fn delivered(&self) -> u64 {
+        self.mesh.lock().unwrap().delivered
+    }
+
+    /// Injected packets lost before reaching the bridge
+    pub fn lost(&self) -> u64 {
+        self.mesh.lock().unwrap().lost
+    }
+}
+
+/// Device interface backed by a [`MeshTopology`] instead of a radio
+#[derive(Debug)]
+pub struct SimulatedInterface {
+    connected: bool,
+    mesh: Arc<Mutex<SimulatedMesh>>,
+    inbound_tx: mpsc::UnboundedSender<Bytes>,
+    inbound_rx: mpsc::UnboundedReceiver<Bytes>,
+}
+
+impl SimulatedInterface {
+    /// Create an interface for the given mesh and radio parameters
+    pub fn new(
+        topology: MeshTopology,
+        spreading_factor: u8,
+        bandwidth_khz: u32,
+    ) -> (Self, SimulationHandle) {
+        let mesh = Arc::new(Mutex::new(SimulatedMesh {
+            rng: StdRng::seed_from_u64(topology.seed),
+            topology,
+            spreading_factor,
+            bandwidth_khz,
+            transmissions: Vec::new(),
+            delivered: 0,
+            lost: 0,
+        }));
+        let (inbound_tx, inbound_rx) = mpsc::unbounded_channel();
+
+        let handle = SimulationHandle {
+            mesh: mesh.clone(),
+            inbound_tx: inbound_tx.clone(),
+        };
+        let interface = Self {
+            connected: false,
+            mesh,
+            inbound_tx,
+            inbound_rx,
+        };
+        (interface, handle)
+    }
+}
+
+#[async_trait::async_trait]
+impl MeshtasticInterface for SimulatedInterface {
+    async fn connect(&mut self) -> Result<()> {
+        self.connected = true;
+
+        // Announce the attached node like a real device does
+        let my_node_num = self.mesh.lock().unwrap().topology.local_node;
+        let info = FromRadio {
+            id: 0,
+            payload_variant: Some(from_radio::PayloadVariant::MyInfo(MyNodeInfo {
+                my_node_num,
+            })),
+        };
+        let _ = self.inbound_tx.send(Bytes::from(info.encode_to_vec()));
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        self.connected = false;
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    async fn read_packet(&mut self) -> Result<Option<Bytes>> {
+        if !self.connected {
+            return Err(MeshtasticError::Disconnected);
+        }
+        // The interface holds a sender, so this waits instead of ending
+        Ok(self.inbound_rx.recv().await)
+    }
+
+    async fn write_packet(&mut self, data: &[u8]) -> Result<()> {
+        if !self.connected {
+            return Err(MeshtasticError::Disconnected);
+        }
+
+        let message = ToRadio::decode(data)?;
+        let Some(to_radio::PayloadVariant::Packet(packet)) = message.payload_variant else {
+            return Ok(());
+        };
+
+        let mut mesh = self.mesh.lock().unwrap();
+        let airtime = estimate_airtime(data.len(), mesh.spreading_factor, mesh.bandwidth_khz);
+        let local_node = mesh.topology.local_node;
+        let reached = mesh.propagate(local_node, packet.hop_limit.min(u8::MAX as u32) as u8);
+        mesh.transmissions.push(Transmission {
+            packet,
+            airtime,
+            reached,
+        });
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "SimulatedInterface"
+    }
+}
+
+impl MeshtasticBridge<SimulatedInterface> {
+    /// Create a bridge attached to a virtual mesh instead of a device
+    ///
+    /// Airtime is estimated with the radio parameters in `config`.
+    pub fn simulated(
+        topology: MeshTopology,
+        config: &MeshtasticConfig,
+        publish_callback: PublishCallback,
+    ) -> (Self, BridgeHandle, SimulationHandle) {
+        let (interface, simulation) = SimulatedInterface::new(
+            topology,
+            config.bridge.spreading_factor,
+            config.bridge.bandwidth_khz,
+        );
+        let (bridge, handle) = Self::new(interface, config, publish_callback);
+        (bridge, handle, simulation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translator::MeshtasticPort;
+
+    fn line() -> MeshTopology {
+        // 1 - 2 - 3 - 4
+        MeshTopology::new(1)
+            .link(1, 2, 0.0)
+            .link(2, 3, 0.0)
+            .link(3, 4, 0.0)
+    }
+
+    #[test]
+    fn test_topology_hops() {
+        let topology = line();
+        assert_eq!(topology.hops(1, 2), Some(1));
+        assert_eq!(topology.hops(1, 4), Some(3));
+        assert_eq!(topology.hops(1, 5), None);
+        assert_eq!(topology.nodes().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_inject_respects_hop_limit() {
+        let (_interface, sim) = SimulatedInterface::new(line(), 11, 250);
+
+        // Three hops away needs two relays
+        let packet = MeshtasticPacket::new_outgoing(
+            4,
+            0xFFFFFFFF,
+            MeshtasticPort::TextMessage,
+            Bytes::from("far"),
+            1,
+        );
+        assert!(!sim.inject(packet.clone()));
+
+        let packet = MeshtasticPacket {
+            hop_limit: 3,
+            ..packet
+        };
+        assert!(sim.inject(packet));
+        assert_eq!((sim.delivered(), sim.lost()), (1, 1));
+    }
+
+    #[test]
+    fn test_loss_is_deterministic() {
+        let lossy = || {
+            let topology = MeshTopology::new(1).link(1, 2, 0.5).with_seed(7);
+            let (_interface, sim) = SimulatedInterface::new(topology, 11, 250);
+            (0..32)
+                .map(|i| {
+                    sim.inject(MeshtasticPacket::new_outgoing(
+                        2,
+                        0xFFFFFFFF,
+                        MeshtasticPort::TextMessage,
+                        Bytes::from(format!("msg {}", i)),
+                        3,
+                    ))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let first = lossy();
+        assert_eq!(first, lossy());
+        assert!(first.contains(&true) && first.contains(&false));
+    }
+
+    #[tokio::test]
+    async fn test_write_records_transmission() {
+        let (mut interface, sim) = SimulatedInterface::new(line(), 11, 250);
+        interface.connect().await.unwrap();
+
+        let packet = MeshtasticPacket::new_outgoing(
+            1,
+            0xFFFFFFFF,
+            MeshtasticPort::TextMessage,
+            Bytes::from("hello mesh"),
+            1,
+        );
+        let frame = ToRadio::packet(MeshPacket::from(&packet)).encode_to_vec();
+        interface.write_packet(&frame).await.unwrap();
+
+        let transmissions = sim.transmissions();
+        assert_eq!(transmissions.len(), 1);
+        assert!(transmissions[0].airtime > Duration::ZERO);
+        // One relay reaches two hops out
+        let reached: Vec<_> = transmissions[0].reached.keys().copied().collect();
+        assert_eq!(reached, vec![2, 3]);
+        assert_eq!(sim.total_airtime(), transmissions[0].airtime);
+    }
+}
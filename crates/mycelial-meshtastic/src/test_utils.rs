@@ -36,6 +36,9 @@ use tracing::{debug, info, warn};
 use crate::config::{MeshtasticConfig, MeshtasticConfigBuilder, DEFAULT_BAUD_RATE};
 use crate::error::{MeshtasticError, Result};
 use crate::interface::MeshtasticInterface;
+use crate::proto::{mesh_packet, Data, FromRadio, MeshPacket};
+use crate::translator::MeshtasticPort;
+use prost::Message;
 
 #[cfg(feature = "serial")]
 use crate::interface::SerialInterface;
@@ -182,7 +185,7 @@ pub fn list_available_devices() -> Vec<DeviceInfo> {
     }
 }
 
-/// Information about a detected serial device
+/// Information about a detected serial or BLE device
 #[derive(Debug, Clone)]
 pub struct DeviceInfo {
     /// Path to the device (e.g., /dev/ttyUSB0)
@@ -199,6 +202,8 @@ pub struct DeviceInfo {
     pub manufacturer: Option<String>,
     /// Serial number (if available)
     pub serial_number: Option<String>,
+    /// Signal strength in dBm (BLE devices only)
+    pub rssi: Option<i16>,
     /// Whether this is likely a Meshtastic device
     pub is_likely_meshtastic: bool,
 }
@@ -234,6 +239,7 @@ impl DeviceInfo {
             product_name,
             manufacturer,
             serial_number,
+            rssi: None,
             is_likely_meshtastic: is_likely_meshtastic_port(port),
         }
     }
@@ -249,6 +255,7 @@ impl DeviceInfo {
             product_name: None,
             manufacturer: None,
             serial_number: None,
+            rssi: None,
             is_likely_meshtastic: false,
         }
     }
@@ -434,26 +441,31 @@ impl MockInterface {
         self.read_count = 0;
     }
 
-    /// Create a mock text message packet
+    /// Create a mock text message packet (encoded `FromRadio` protobuf)
     pub fn create_text_packet(from: u32, text: &str) -> Vec<u8> {
-        let mut packet = Vec::new();
-        packet.extend_from_slice(&from.to_be_bytes());
-        packet.extend_from_slice(&0xFFFFFFFFu32.to_be_bytes()); // Broadcast
-        packet.extend_from_slice(&rand::random::<u32>().to_be_bytes()); // Packet ID
-        packet.push(1); // TextMessage port
-        packet.extend_from_slice(text.as_bytes());
-        packet
+        Self::create_from_radio(from, MeshtasticPort::TextMessage as u32, text.as_bytes())
     }
 
     /// Create a mock economics packet (vouch, credit, etc.)
-    pub fn create_economics_packet(from: u32, port: u8, payload: &[u8]) -> Vec<u8> {
-        let mut packet = Vec::new();
-        packet.extend_from_slice(&from.to_be_bytes());
-        packet.extend_from_slice(&0xFFFFFFFFu32.to_be_bytes());
-        packet.extend_from_slice(&rand::random::<u32>().to_be_bytes());
-        packet.push(port);
-        packet.extend_from_slice(payload);
-        packet
+    pub fn create_economics_packet(from: u32, port: u32, payload: &[u8]) -> Vec<u8> {
+        Self::create_from_radio(from, port, payload)
+    }
+
+    /// Encode a broadcast `FromRadio` packet as a device would deliver it
+    fn create_from_radio(from: u32, port: u32, payload: &[u8]) -> Vec<u8> {
+        let packet = MeshPacket {
+            from,
+            to: 0xFFFFFFFF, // Broadcast
+            id: rand::random(),
+            hop_limit: 3,
+            payload_variant: Some(mesh_packet::PayloadVariant::Decoded(Data {
+                portnum: port as i32,
+                payload: payload.to_vec(),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        FromRadio::packet(packet).encode_to_vec()
     }
 }
 
@@ -557,7 +569,7 @@ impl TestFixture {
             .interface
             .queue_incoming(MockInterface::create_economics_packet(
                 0xAAAA0001,
-                0x00, // Vouch port byte
+                MeshtasticPort::MycelialVouch as u32,
                 &[
                     0x01, 0xAA, 0xAA, 0x00, 0x01, 0xBB, 0xBB, 0x00, 0x01, 0x00, 0x64,
                 ],
@@ -568,7 +580,7 @@ impl TestFixture {
             .interface
             .queue_incoming(MockInterface::create_economics_packet(
                 0xCCCC0001,
-                0x01, // Credit port byte
+                MeshtasticPort::MycelialCredit as u32,
                 &[
                     0x03, 0xCC, 0xCC, 0x00, 0x01, 0xDD, 0xDD, 0x00, 0x01, 0x00, 0x00, 0x01, 0xF4,
                 ],
@@ -636,8 +648,13 @@ mod tests {
     #[test]
     fn test_create_text_packet() {
         let packet = MockInterface::create_text_packet(0x12345678, "Hello");
-        assert!(packet.len() >= 13); // Header + payload
-        assert_eq!(&packet[0..4], &0x12345678u32.to_be_bytes());
+        let from_radio = crate::proto::decode_from_radio(&packet).unwrap();
+        match from_radio.payload_variant {
+            Some(crate::proto::from_radio::PayloadVariant::Packet(mesh)) => {
+                assert_eq!(mesh.from, 0x12345678);
+            }
+            other => panic!("unexpected FromRadio payload: {:?}", other),
+        }
     }
 
     #[test]
@@ -22,21 +22,25 @@
 
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 use chrono::{DateTime, TimeZone, Utc};
-use mycelial_core::{Message, MessageType, PeerId};
+use mycelial_core::{Location, Message, MessageType, PeerId, SignatureBytes};
 use mycelial_protocol::{
     CastVote, CreateCreditLine, CreateProposal, CreditLineAck, CreditLineUpdate, CreditMessage,
     CreditTransfer, CreditTransferAck, GovernanceMessage, ProposalExecuted, ProposalStatus,
     ProposalType, ProposalUpdate, ReputationChangeReason, ReputationUpdate, ResourceContribution,
-    ResourceMessage, ResourceMetrics, ResourcePoolUpdate, ResourceType, Vote, VouchAck,
-    VouchMessage, VouchRequest,
+    ResourceMessage, ResourceMetrics, ResourcePoolUpdate, ResourceReceipt, ResourceType, Vote,
+    VouchAck, VouchMessage, VouchRequest, VouchRevoke,
 };
 use serde::{Deserialize, Serialize};
 use tracing::{debug, trace, warn};
 use uuid::Uuid;
 
-use crate::config::LORA_MAX_PAYLOAD;
+use crate::config::{LoraPriority, LORA_MAX_PAYLOAD};
 use crate::error::{MeshtasticError, Result};
 use crate::mapper::NodeIdMapper;
+use crate::proto;
+
+/// Gossipsub topic that LoRa telemetry is published on
+pub const TELEMETRY_TOPIC: &str = "/mycelial/1.0.0/telemetry";
 
 /// Port numbers for Meshtastic data payloads
 /// Based on Meshtastic PortNum enum from portnums.proto
@@ -69,6 +73,8 @@ pub enum MeshtasticPort {
     MycelialGovernance = 514,
     /// Mycelial resource protocol
     MycelialResource = 515,
+    /// Chunk of a compressed/split mycelial message
+    MycelialChunk = 516,
 }
 
 impl From<u32> for MeshtasticPort {
@@ -87,6 +93,7 @@ impl From<u32> for MeshtasticPort {
             513 => Self::MycelialCredit,
             514 => Self::MycelialGovernance,
             515 => Self::MycelialResource,
+            516 => Self::MycelialChunk,
             _ => Self::Unknown,
         }
     }
@@ -98,6 +105,76 @@ impl From<MeshtasticPort> for u32 {
     }
 }
 
+/// Location carried by a position report, if `message` is one
+///
+/// Positions travel as [`MessageType::Discovery`] messages whose payload is a
+/// CBOR-encoded [`Location`].
+pub fn message_location(message: &Message) -> Option<Location> {
+    if message.message_type != MessageType::Discovery {
+        return None;
+    }
+    serde_cbor::from_slice(&message.payload).ok()
+}
+
+/// Radio health reported on the Meshtastic telemetry port
+///
+/// Carried CBOR-encoded as the payload of a [`MessageType::System`] message
+/// on [`TELEMETRY_TOPIC`]. Metrics the device did not report are `None`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TelemetryData {
+    /// When the device took the reading
+    pub time: DateTime<Utc>,
+    /// Battery charge in percent (101 means externally powered)
+    pub battery_level: Option<u32>,
+    /// Battery voltage
+    pub voltage: Option<f32>,
+    /// Percent of the channel's airtime in use by any node
+    pub channel_utilization: Option<f32>,
+    /// Percent of the hourly transmit airtime used by the device
+    pub air_util_tx: Option<f32>,
+    /// Seconds since the device booted
+    pub uptime_seconds: Option<u32>,
+}
+
+impl TelemetryData {
+    /// Build from a decoded telemetry report
+    ///
+    /// Returns `None` for reports that carry no device metrics.
+    pub fn from_proto(telemetry: &proto::Telemetry) -> Option<Self> {
+        let Some(proto::telemetry::Variant::DeviceMetrics(metrics)) = &telemetry.variant else {
+            return None;
+        };
+        Some(Self {
+            time: Utc
+                .timestamp_opt(telemetry.time as i64, 0)
+                .single()
+                .unwrap_or_else(Utc::now),
+            battery_level: metrics.battery_level,
+            voltage: metrics.voltage,
+            channel_utilization: metrics.channel_utilization,
+            air_util_tx: metrics.air_util_tx,
+            uptime_seconds: metrics.uptime_seconds,
+        })
+    }
+
+    /// Convert to a telemetry report for the radio
+    pub fn to_proto(&self) -> proto::Telemetry {
+        proto::Telemetry {
+            time: self.time.timestamp().clamp(0, u32::MAX as i64) as u32,
+            variant: Some(proto::telemetry::Variant::DeviceMetrics(
+                proto::DeviceMetrics {
+                    battery_level: self.battery_level,
+                    voltage: self.voltage,
+                    channel_utilization: self.channel_utilization,
+                    air_util_tx: self.air_util_tx,
+                    uptime_seconds: self.uptime_seconds,
+                },
+            )),
+        }
+    }
+}
+
 /// A decoded Meshtastic packet ready for translation
 #[derive(Debug, Clone)]
 pub struct MeshtasticPacket {
@@ -200,7 +277,9 @@ impl MessageTranslator {
 
     /// Translate a Mycelial Message to a Meshtastic packet
     ///
-    /// This is the libp2p → LoRa direction
+    /// This is the libp2p → LoRa direction. The packet requests an
+    /// acknowledgement if the message type's default priority calls for one;
+    /// the bridge may override this per topic.
     pub fn mycelial_to_meshtastic(
         &self,
         message: &Message,
@@ -231,7 +310,7 @@ impl MessageTranslator {
             port_num,
             payload,
             hop_limit,
-            want_ack: false,
+            want_ack: message.message_type.default_priority().wants_ack(),
             rx_time: Some(message.timestamp),
         })
     }
@@ -272,6 +351,20 @@ impl MessageTranslator {
                     .map_err(|e| MeshtasticError::TranslationFailed(e.to_string()))?;
                 Ok((MessageType::System, payload))
             }
+            MeshtasticPort::Position => {
+                let location = self.decode_position(&packet.payload)?;
+                trace!(?location, "Translating position from LoRa");
+                let payload = serde_cbor::to_vec(&location)
+                    .map_err(|e| MeshtasticError::TranslationFailed(e.to_string()))?;
+                Ok((MessageType::Discovery, payload))
+            }
+            MeshtasticPort::Telemetry => {
+                let telemetry = self.decode_telemetry(&packet.payload)?;
+                trace!(?telemetry, "Translating telemetry from LoRa");
+                let payload = serde_cbor::to_vec(&telemetry)
+                    .map_err(|e| MeshtasticError::TranslationFailed(e.to_string()))?;
+                Ok((MessageType::System, payload))
+            }
             _ => {
                 warn!(port = ?packet.port_num, "Unknown Meshtastic port, treating as raw payload");
                 Ok((MessageType::System, packet.payload.to_vec()))
@@ -284,6 +377,10 @@ impl MessageTranslator {
         &self,
         message: &Message,
     ) -> Result<(MeshtasticPort, Bytes)> {
+        if let Some(location) = message_location(message) {
+            return Ok((MeshtasticPort::Position, self.encode_position(&location)));
+        }
+
         match message.message_type {
             MessageType::Content | MessageType::Discovery => {
                 // Text/content messages go as TextMessage
@@ -319,7 +416,12 @@ impl MessageTranslator {
                 Ok((MeshtasticPort::MycelialGovernance, payload))
             }
             MessageType::System => {
-                // System messages as private app port
+                // Telemetry goes back to the radio's own port so other
+                // Meshtastic clients can display it
+                if let Ok(telemetry) = serde_cbor::from_slice::<TelemetryData>(&message.payload) {
+                    return Ok((MeshtasticPort::Telemetry, self.encode_telemetry(&telemetry)));
+                }
+                // Other system messages as private app port
                 Ok((
                     MeshtasticPort::PrivateApp,
                     Bytes::from(message.payload.clone()),
@@ -356,6 +458,12 @@ impl MessageTranslator {
                 buf.put_u8(0x00); // Null terminator
                 buf.put_f32((update.score * 100.0) as f32); // Score as percentage
             }
+            VouchMessage::VouchRevoke(revoke) => {
+                buf.put_u8(0x04);
+                buf.put_slice(revoke.vouch_id.as_bytes());
+                self.encode_short_string(&mut buf, &revoke.voucher);
+                buf.put_u32(revoke.timestamp.timestamp() as u32);
+            }
         }
 
         Ok(buf.freeze())
@@ -413,6 +521,10 @@ impl MessageTranslator {
                 let update = self.decode_reputation_update(&mut buf)?;
                 Ok(VouchMessage::ReputationUpdate(update))
             }
+            0x04 => {
+                let revoke = self.decode_vouch_revoke(&mut buf)?;
+                Ok(VouchMessage::VouchRevoke(revoke))
+            }
             _ => Err(MeshtasticError::TranslationFailed(format!(
                 "Unknown vouch message type: 0x{:02X}",
                 msg_type
@@ -520,6 +632,31 @@ impl MessageTranslator {
         })
     }
 
+    fn decode_vouch_revoke(&self, buf: &mut Bytes) -> Result<VouchRevoke> {
+        if buf.remaining() < 16 {
+            return Err(MeshtasticError::TranslationFailed(
+                "Vouch revoke too short".to_string(),
+            ));
+        }
+
+        let mut uuid_bytes = [0u8; 16];
+        buf.copy_to_slice(&mut uuid_bytes);
+        let vouch_id = Uuid::from_bytes(uuid_bytes);
+
+        let voucher = self.decode_short_string(buf)?;
+        let timestamp_secs = buf.get_u32() as i64;
+        let timestamp = Utc
+            .timestamp_opt(timestamp_secs, 0)
+            .single()
+            .unwrap_or_else(Utc::now);
+
+        Ok(VouchRevoke {
+            vouch_id,
+            voucher,
+            timestamp,
+        })
+    }
+
     /// Encode a CreditMessage to compact binary format
     fn encode_credit_message(&self, msg: &CreditMessage) -> Result<Bytes> {
         let mut buf = BytesMut::with_capacity(128);
@@ -862,11 +999,64 @@ impl MessageTranslator {
                 buf.put_u32(update.active_contributors);
                 buf.put_f32(update.total_bandwidth as f32);
             }
+            ResourceMessage::Receipt(receipt) => {
+                // The signature covers every field, so nothing may be
+                // truncated or rounded: full peer IDs, f64 amount, ms timestamp
+                buf.put_u8(0x04);
+                buf.put_slice(receipt.contribution_id.as_bytes());
+                self.encode_exact_string(&mut buf, &receipt.contributor)?;
+                self.encode_exact_string(&mut buf, &receipt.consumer)?;
+                buf.put_f64(receipt.amount);
+                buf.put_i64(receipt.timestamp.timestamp_millis());
+                buf.put_slice(&receipt.signature.to_bytes());
+            }
         }
 
         Ok(buf.freeze())
     }
 
+    /// Decode a GPS fix from its protobuf form
+    fn decode_position(&self, data: &[u8]) -> Result<Location> {
+        let position = <proto::Position as prost::Message>::decode(data)?;
+        let (Some(latitude_i), Some(longitude_i)) = (position.latitude_i, position.longitude_i)
+        else {
+            return Err(MeshtasticError::TranslationFailed(
+                "Position without coordinates".to_string(),
+            ));
+        };
+
+        Ok(Location {
+            latitude: latitude_i as f64 * proto::POSITION_SCALE,
+            longitude: longitude_i as f64 * proto::POSITION_SCALE,
+            altitude: position.altitude.map(f64::from),
+            precision: None,
+        })
+    }
+
+    /// Encode a location as a GPS fix
+    fn encode_position(&self, location: &Location) -> Bytes {
+        let position = proto::Position {
+            latitude_i: Some((location.latitude / proto::POSITION_SCALE).round() as i32),
+            longitude_i: Some((location.longitude / proto::POSITION_SCALE).round() as i32),
+            altitude: location.altitude.map(|altitude| altitude.round() as i32),
+            time: Utc::now().timestamp().clamp(0, u32::MAX as i64) as u32,
+        };
+        Bytes::from(prost::Message::encode_to_vec(&position))
+    }
+
+    /// Decode a telemetry report from its protobuf form
+    fn decode_telemetry(&self, data: &[u8]) -> Result<TelemetryData> {
+        let telemetry = <proto::Telemetry as prost::Message>::decode(data)?;
+        TelemetryData::from_proto(&telemetry).ok_or_else(|| {
+            MeshtasticError::TranslationFailed("Telemetry without device metrics".to_string())
+        })
+    }
+
+    /// Encode a telemetry report to its protobuf form
+    fn encode_telemetry(&self, telemetry: &TelemetryData) -> Bytes {
+        Bytes::from(prost::Message::encode_to_vec(&telemetry.to_proto()))
+    }
+
     /// Decode a ResourceMessage from compact binary format
     fn decode_resource_message(&self, data: &[u8]) -> Result<ResourceMessage> {
         if data.is_empty() {
@@ -936,6 +1126,41 @@ impl MessageTranslator {
                     timestamp: Utc::now(),
                 }))
             }
+            0x04 => {
+                // Receipt
+                if buf.remaining() < 16 {
+                    return Err(MeshtasticError::TranslationFailed(
+                        "Resource receipt too short".to_string(),
+                    ));
+                }
+                let mut uuid_bytes = [0u8; 16];
+                buf.copy_to_slice(&mut uuid_bytes);
+                let contribution_id = Uuid::from_bytes(uuid_bytes);
+
+                let contributor = self.decode_short_string(&mut buf)?;
+                let consumer = self.decode_short_string(&mut buf)?;
+                if buf.remaining() < 8 + 8 + 64 {
+                    return Err(MeshtasticError::TranslationFailed(
+                        "Resource receipt too short".to_string(),
+                    ));
+                }
+                let amount = buf.get_f64();
+                let timestamp = Utc
+                    .timestamp_millis_opt(buf.get_i64())
+                    .single()
+                    .unwrap_or_else(Utc::now);
+                let mut signature = [0u8; 64];
+                buf.copy_to_slice(&mut signature);
+
+                Ok(ResourceMessage::Receipt(ResourceReceipt {
+                    contribution_id,
+                    contributor,
+                    consumer,
+                    amount,
+                    timestamp,
+                    signature: SignatureBytes::from_bytes(signature),
+                }))
+            }
             _ => Err(MeshtasticError::TranslationFailed(format!(
                 "Unknown resource message type: 0x{:02X}",
                 msg_type
@@ -950,6 +1175,16 @@ impl MessageTranslator {
         buf.put_slice(truncated.as_bytes());
     }
 
+    /// Encode a string that must arrive intact (up to 255 bytes)
+    fn encode_exact_string(&self, buf: &mut BytesMut, s: &str) -> Result<()> {
+        let len = u8::try_from(s.len()).map_err(|_| {
+            MeshtasticError::TranslationFailed(format!("String too long to encode: {}", s))
+        })?;
+        buf.put_u8(len);
+        buf.put_slice(s.as_bytes());
+        Ok(())
+    }
+
     fn decode_short_string(&self, buf: &mut Bytes) -> Result<String> {
         let len = buf.get_u8() as usize;
         if buf.remaining() < len {
@@ -1097,6 +1332,54 @@ mod tests {
         assert!(encoded.len() < LORA_MAX_PAYLOAD);
     }
 
+    #[test]
+    fn test_position_roundtrip() {
+        let translator = MessageTranslator::default();
+
+        let mut original = Location::new(37.7749295, -122.4194155);
+        original.altitude = Some(16.0);
+
+        let encoded = translator.encode_position(&original);
+        assert!(encoded.len() < LORA_MAX_PAYLOAD);
+
+        let decoded = translator.decode_position(&encoded).unwrap();
+        assert!((decoded.latitude - original.latitude).abs() < 1e-6);
+        assert!((decoded.longitude - original.longitude).abs() < 1e-6);
+        assert_eq!(decoded.altitude, Some(16.0));
+
+        // A fix without coordinates carries no location
+        let empty = prost::Message::encode_to_vec(&proto::Position::default());
+        assert!(translator.decode_position(&empty).is_err());
+    }
+
+    #[test]
+    fn test_telemetry_roundtrip() {
+        let translator = MessageTranslator::default();
+
+        let original = TelemetryData {
+            time: Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+            battery_level: Some(87),
+            voltage: Some(4.05),
+            channel_utilization: Some(12.5),
+            air_util_tx: Some(1.25),
+            uptime_seconds: None,
+        };
+
+        let encoded = translator.encode_telemetry(&original);
+        assert!(encoded.len() < LORA_MAX_PAYLOAD);
+        assert_eq!(translator.decode_telemetry(&encoded).unwrap(), original);
+
+        // Resource payloads share MessageType::System but are not telemetry
+        let resource = ResourceMessage::Contribution(ResourceContribution::new(
+            "alice".to_string(),
+            ResourceType::Bandwidth,
+            1000.0,
+            "Mbps".to_string(),
+        ));
+        let cbor = serde_cbor::to_vec(&resource).unwrap();
+        assert!(serde_cbor::from_slice::<TelemetryData>(&cbor).is_err());
+    }
+
     // ========================================================================
     // Phase 4: Comprehensive Economics Protocol Tests
     // ========================================================================
@@ -1160,6 +1443,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_vouch_revoke_encoding_roundtrip() {
+        let translator = MessageTranslator::default();
+
+        let original =
+            VouchMessage::VouchRevoke(VouchRevoke::new(Uuid::new_v4(), "alice".to_string()));
+
+        let encoded = translator.encode_vouch_message(&original).unwrap();
+        assert!(encoded.len() < LORA_MAX_PAYLOAD);
+
+        let decoded = translator.decode_vouch_message(&encoded).unwrap();
+
+        if let (VouchMessage::VouchRevoke(orig), VouchMessage::VouchRevoke(dec)) =
+            (&original, &decoded)
+        {
+            assert_eq!(orig.vouch_id, dec.vouch_id);
+            assert_eq!(orig.voucher, dec.voucher);
+        } else {
+            panic!("Wrong variant after decode");
+        }
+    }
+
     #[test]
     fn test_create_credit_line_encoding_roundtrip() {
         let translator = MessageTranslator::default();
@@ -1434,6 +1739,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_resource_receipt_encoding_roundtrip() {
+        use mycelial_core::Keypair;
+
+        let translator = MessageTranslator::default();
+
+        let contribution = ResourceContribution::new(
+            PeerId::from_public_key(&Keypair::generate().public_key()).to_string(),
+            ResourceType::Bandwidth,
+            100.0,
+            "MB".to_string(),
+        );
+        let original = ResourceMessage::Receipt(ResourceReceipt::sign(
+            &contribution,
+            42.5,
+            &Keypair::generate(),
+        ));
+
+        let encoded = translator.encode_resource_message(&original).unwrap();
+        assert!(encoded.len() < LORA_MAX_PAYLOAD);
+
+        let decoded = translator.decode_resource_message(&encoded).unwrap();
+
+        if let (ResourceMessage::Receipt(orig), ResourceMessage::Receipt(dec)) =
+            (&original, &decoded)
+        {
+            assert_eq!(orig.contribution_id, dec.contribution_id);
+            assert_eq!(orig.consumer, dec.consumer);
+            assert_eq!(orig.amount, dec.amount);
+            assert!(dec.verify().is_ok());
+        } else {
+            panic!("Wrong variant after decode");
+        }
+    }
+
     #[test]
     fn test_resource_metrics_encoding_roundtrip() {
         use mycelial_protocol::{BandwidthMetrics, ComputeMetrics, StorageMetrics};
@@ -8,97 +8,27 @@
 //! - Error handling and recovery
 
 use bytes::Bytes;
+use mycelial_meshtastic::proto::to_radio;
 use mycelial_meshtastic::{
     BridgeConfig, BridgeDirection, BridgeHandle, BridgeStats, CacheStats, ChannelConfig,
-    ChannelIndexMapper, DeduplicationCache, DeduplicationKey, EconomicsMessageCodec,
-    GossipsubMessage, MeshtasticBridge, MeshtasticConfig, MeshtasticConfigBuilder, MeshtasticError,
-    MeshtasticPacket, MeshtasticPort, MessageChunk, MessageChunker, MessageCompressor,
-    MessageDirection, MessageReassembler, MessageTranslator, NodeIdMapper, PublishCallback,
-    TopicMapper, BRIDGE_PROTOCOL_VERSION, DEFAULT_BAUD_RATE, LORA_MAX_PAYLOAD, MESHTASTIC_MAGIC,
-    VERSION,
+    ChannelIndexMapper, DeduplicationCache, DeduplicationKey, EconomicsMessageCodec, FromRadio,
+    GossipsubMessage, MeshTopology, MeshtasticBridge, MeshtasticConfig, MeshtasticConfigBuilder,
+    MeshtasticError, MeshtasticPacket, MeshtasticPort, MessageChunk, MessageChunker,
+    MessageCompressor, MessageDirection, MessageReassembler, MessageTranslator, NodeIdMapper,
+    PublishCallback, ToRadio, TopicMapper, BRIDGE_PROTOCOL_VERSION, DEFAULT_BAUD_RATE,
+    LORA_MAX_PAYLOAD, MESHTASTIC_MAGIC, VERSION,
 };
+use mycelial_protocol::{CreateProposal, GovernanceMessage};
+use prost::Message as _;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use tokio::sync::mpsc;
 
 // ============================================================================
 // Mock Infrastructure for Integration Testing
 // ============================================================================
 
-/// Mock interface that simulates a Meshtastic device
-struct MockMeshtasticDevice {
-    connected: bool,
-    incoming_packets: Vec<Vec<u8>>,
-    outgoing_packets: Arc<Mutex<Vec<Vec<u8>>>>,
-    simulate_disconnect: bool,
-}
-
-impl MockMeshtasticDevice {
-    fn new() -> Self {
-        Self {
-            connected: false,
-            incoming_packets: Vec::new(),
-            outgoing_packets: Arc::new(Mutex::new(Vec::new())),
-            simulate_disconnect: false,
-        }
-    }
-
-    fn queue_incoming_packet(&mut self, packet: Vec<u8>) {
-        self.incoming_packets.push(packet);
-    }
-
-    fn get_outgoing_packets(&self) -> Vec<Vec<u8>> {
-        self.outgoing_packets.lock().unwrap().clone()
-    }
-
-    fn set_simulate_disconnect(&mut self, disconnect: bool) {
-        self.simulate_disconnect = disconnect;
-    }
-}
-
-#[async_trait::async_trait]
-impl mycelial_meshtastic::MeshtasticInterface for MockMeshtasticDevice {
-    async fn connect(&mut self) -> mycelial_meshtastic::Result<()> {
-        if self.simulate_disconnect {
-            return Err(MeshtasticError::ConnectionTimeout { duration_ms: 5000 });
-        }
-        self.connected = true;
-        Ok(())
-    }
-
-    async fn disconnect(&mut self) -> mycelial_meshtastic::Result<()> {
-        self.connected = false;
-        Ok(())
-    }
-
-    fn is_connected(&self) -> bool {
-        self.connected
-    }
-
-    async fn read_packet(&mut self) -> mycelial_meshtastic::Result<Option<Bytes>> {
-        if self.simulate_disconnect {
-            return Err(MeshtasticError::Disconnected);
-        }
-        if self.incoming_packets.is_empty() {
-            Ok(None)
-        } else {
-            Ok(Some(Bytes::from(self.incoming_packets.remove(0))))
-        }
-    }
-
-    async fn write_packet(&mut self, data: &[u8]) -> mycelial_meshtastic::Result<()> {
-        if self.simulate_disconnect {
-            return Err(MeshtasticError::WriteError("Disconnected".to_string()));
-        }
-        self.outgoing_packets.lock().unwrap().push(data.to_vec());
-        Ok(())
-    }
-
-    fn name(&self) -> &str {
-        "MockMeshtasticDevice"
-    }
-}
-
 /// Mock gossipsub network that captures published messages
 struct MockGossipsubNetwork {
     published_messages: Arc<Mutex<Vec<(String, Vec<u8>)>>>,
@@ -125,20 +55,19 @@ impl MockGossipsubNetwork {
 }
 
 // ============================================================================
-// Integration Tests: Bridge Lifecycle
-// NOTE: These tests are ignored because they require running a full event loop
-// with a mock device that properly simulates async behavior. The bridge
-// functionality is tested in the bridge module's unit tests instead.
+// Integration Tests: Bridge Lifecycle (simulated mesh)
 // ============================================================================
 
 #[tokio::test]
-#[ignore = "Requires hardware or properly async mock - see bridge module unit tests"]
 async fn test_bridge_connect_and_disconnect() {
-    let device = MockMeshtasticDevice::new();
     let network = MockGossipsubNetwork::new();
     let config = MeshtasticConfigBuilder::new().build();
 
-    let (bridge, handle) = MeshtasticBridge::new(device, &config, network.get_publish_callback());
+    let (bridge, handle, _sim) = MeshtasticBridge::simulated(
+        MeshTopology::new(0x1000),
+        &config,
+        network.get_publish_callback(),
+    );
 
     // Shutdown the bridge immediately
     tokio::spawn(async move {
@@ -152,13 +81,15 @@ async fn test_bridge_connect_and_disconnect() {
 }
 
 #[tokio::test]
-#[ignore = "Requires hardware or properly async mock - see bridge module unit tests"]
 async fn test_bridge_stats_tracking() {
-    let device = MockMeshtasticDevice::new();
     let network = MockGossipsubNetwork::new();
     let config = MeshtasticConfigBuilder::new().build();
 
-    let (bridge, handle) = MeshtasticBridge::new(device, &config, network.get_publish_callback());
+    let (bridge, handle, _sim) = MeshtasticBridge::simulated(
+        MeshTopology::new(0x1000),
+        &config,
+        network.get_publish_callback(),
+    );
 
     // Get stats before running
     let handle_clone = handle.clone();
@@ -173,6 +104,57 @@ async fn test_bridge_stats_tracking() {
     let _ = bridge.run().await;
 }
 
+#[tokio::test]
+async fn test_simulated_mesh_round_trip() {
+    let network = MockGossipsubNetwork::new();
+    let config = MeshtasticConfigBuilder::new().build();
+
+    // Bridge 0x1000 hears 0x2000 directly and 0x3000 through it
+    let topology = MeshTopology::new(0x1000)
+        .link(0x1000, 0x2000, 0.0)
+        .link(0x2000, 0x3000, 0.0);
+    let (bridge, handle, sim) =
+        MeshtasticBridge::simulated(topology, &config, network.get_publish_callback());
+    let task = tokio::spawn(bridge.run());
+
+    // LoRa -> gossipsub across a relay
+    assert!(sim.inject(MeshtasticPacket::new_outgoing(
+        0x3000,
+        0xFFFFFFFF,
+        MeshtasticPort::TextMessage,
+        Bytes::from("Hello from two hops out"),
+        3,
+    )));
+
+    // gossipsub -> LoRa
+    handle
+        .forward_to_lora(GossipsubMessage {
+            topic: "/mycelial/1.0.0/chat".to_string(),
+            source: Some("12D3KooWSender".to_string()),
+            data: b"Hello mesh".to_vec(),
+            message_id: "sim-1".to_string(),
+        })
+        .await
+        .unwrap();
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let stats = handle.stats().await.unwrap();
+    handle.shutdown().await.unwrap();
+    task.await.unwrap().unwrap();
+
+    assert_eq!(stats.lora_to_gossipsub, 1);
+    assert_eq!(stats.gossipsub_to_lora, 1);
+    assert_eq!(
+        network.get_published_messages()[0].0,
+        "/mycelial/1.0.0/chat"
+    );
+
+    let transmissions = sim.transmissions();
+    assert_eq!(transmissions.len(), 1);
+    assert!(transmissions[0].reached.contains_key(&0x3000));
+    assert_eq!(sim.total_airtime(), transmissions[0].airtime);
+}
+
 // ============================================================================
 // Integration Tests: Full Message Flow
 // ============================================================================
@@ -242,7 +224,7 @@ async fn test_full_gossipsub_to_lora_flow() {
     assert!(dedup_cache.is_duplicate(&dedup_key, MessageDirection::FromLibp2p));
 
     // Verify hop limit calculation
-    let hop_limit = topic_mapper.get_hop_limit(&msg.topic);
+    let hop_limit = topic_mapper.hop_limit_for(&msg.topic, None);
     assert!(hop_limit >= 1 && hop_limit <= 7);
 }
 
@@ -343,6 +325,143 @@ async fn test_large_message_compression() {
     assert_eq!(result.unwrap(), proposal_content);
 }
 
+/// Radio link that carries `ToRadio` packets written by one bridge to the
+/// interface of another as `FromRadio` packets
+struct RadioLink {
+    connected: bool,
+    air_tx: Option<mpsc::UnboundedSender<Vec<u8>>>,
+    air_rx: Option<mpsc::UnboundedReceiver<Vec<u8>>>,
+}
+
+impl RadioLink {
+    fn pair() -> (Self, Self) {
+        let (air_tx, air_rx) = mpsc::unbounded_channel();
+        (
+            Self {
+                connected: false,
+                air_tx: Some(air_tx),
+                air_rx: None,
+            },
+            Self {
+                connected: false,
+                air_tx: None,
+                air_rx: Some(air_rx),
+            },
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl mycelial_meshtastic::MeshtasticInterface for RadioLink {
+    async fn connect(&mut self) -> mycelial_meshtastic::Result<()> {
+        self.connected = true;
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> mycelial_meshtastic::Result<()> {
+        self.connected = false;
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    async fn read_packet(&mut self) -> mycelial_meshtastic::Result<Option<Bytes>> {
+        match self.air_rx.as_mut() {
+            Some(rx) => Ok(rx.recv().await.map(Bytes::from)),
+            None => std::future::pending().await,
+        }
+    }
+
+    async fn write_packet(&mut self, data: &[u8]) -> mycelial_meshtastic::Result<()> {
+        let to_radio =
+            ToRadio::decode(data).map_err(|e| MeshtasticError::ProtobufDecode(e.to_string()))?;
+        let Some(to_radio::PayloadVariant::Packet(packet)) = to_radio.payload_variant else {
+            return Ok(());
+        };
+        if let Some(tx) = &self.air_tx {
+            let _ = tx.send(FromRadio::packet(packet).encode_to_vec());
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "RadioLink"
+    }
+}
+
+#[tokio::test]
+async fn test_oversized_proposal_round_trip() {
+    let (transmitter, receiver) = RadioLink::pair();
+    let network = MockGossipsubNetwork::new();
+    let config = MeshtasticConfigBuilder::new().build();
+
+    let (tx_bridge, tx_handle) =
+        MeshtasticBridge::new(transmitter, &config, Arc::new(|_, _| Ok(())));
+    let (rx_bridge, rx_handle) =
+        MeshtasticBridge::new(receiver, &config, network.get_publish_callback());
+    let tx_task = tokio::spawn(tx_bridge.run());
+    let rx_task = tokio::spawn(rx_bridge.run());
+
+    // 800-byte description with little redundancy so it needs several packets
+    let description: String = (0u32..800)
+        .map(|i| (b'a' + ((i.wrapping_mul(2_654_435_761) >> 24) % 26) as u8) as char)
+        .collect();
+    let proposal = GovernanceMessage::CreateProposal(CreateProposal::new(
+        "12D3KooWProposer".to_string(),
+        "Expand the community credit pool".to_string(),
+        description.clone(),
+    ));
+    let data = serde_cbor::to_vec(&proposal).unwrap();
+    assert!(data.len() > LORA_MAX_PAYLOAD);
+
+    tx_handle
+        .forward_to_lora(GossipsubMessage {
+            topic: "/mycelial/1.0.0/governance".to_string(),
+            source: Some("12D3KooWProposer".to_string()),
+            data: data.clone(),
+            message_id: "proposal-800".to_string(),
+        })
+        .await
+        .unwrap();
+
+    // Wait for the receiving bridge to reassemble and publish
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while network.get_published_messages().is_empty() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("reassembled proposal was not published");
+
+    let published = network.get_published_messages();
+    assert_eq!(published.len(), 1);
+    assert_eq!(published[0].0, "/mycelial/1.0.0/governance");
+    assert_eq!(published[0].1, data);
+
+    match serde_cbor::from_slice::<GovernanceMessage>(&published[0].1).unwrap() {
+        GovernanceMessage::CreateProposal(received) => {
+            assert_eq!(received.description, description);
+        }
+        other => panic!("unexpected governance message: {:?}", other),
+    }
+
+    let tx_stats = tx_handle.stats().await.unwrap();
+    assert_eq!(tx_stats.gossipsub_to_lora, 1);
+    assert_eq!(tx_stats.chunked_messages, 1);
+    assert_eq!(tx_stats.compressed_messages, 1);
+    assert_eq!(tx_stats.oversized_messages, 0);
+
+    let rx_stats = rx_handle.stats().await.unwrap();
+    assert_eq!(rx_stats.lora_to_gossipsub, 1);
+
+    tx_handle.shutdown().await.unwrap();
+    rx_handle.shutdown().await.unwrap();
+    tx_task.await.unwrap().unwrap();
+    rx_task.await.unwrap().unwrap();
+}
+
 #[tokio::test]
 async fn test_chunk_reassembly_out_of_order() {
     let mut reassembler = MessageReassembler::new();
@@ -456,7 +575,8 @@ async fn test_node_id_peer_id_round_trip() {
     let mapper = NodeIdMapper::new();
 
     // Create a PeerId and register it
-    let peer_id = mycelial_core::PeerId("test_peer_12345678".to_string());
+    let peer_id =
+        mycelial_core::PeerId::from_public_key(&mycelial_core::Keypair::generate().public_key());
     mapper.register(0x12345678, peer_id.clone());
 
     // Verify round trip
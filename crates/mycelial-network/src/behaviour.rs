@@ -1,21 +1,26 @@
 //! Network behaviour combining multiple libp2p protocols
 //!
 //! This module provides the composite network behaviour that combines
-//! gossipsub, kademlia, identify, and mDNS protocols.
+//! gossipsub, kademlia, identify, ping, mDNS, AutoNAT, relay client and
+//! content exchange protocols.
 
 use libp2p::{
-    gossipsub::{self, IdentTopic, MessageAuthenticity, MessageId, ValidationMode},
+    autonat,
+    gossipsub::{
+        self, IdentTopic, MessageAcceptance, MessageAuthenticity, MessageId, ValidationMode,
+    },
     identify,
     identity::Keypair,
     kad::{self, store::MemoryStore},
-    mdns,
+    mdns, ping, relay, request_response,
     swarm::NetworkBehaviour,
     PeerId,
 };
-use sha2::{Digest, Sha256};
+use mycelial_core::content::ContentId;
 use std::time::Duration;
 
 use crate::config::NetworkConfig;
+use crate::content::{self, ContentRequest, ContentResponse};
 use crate::error::NetworkError;
 
 /// Combined network behaviour for the mycelial network
@@ -28,8 +33,16 @@ pub struct MycelialBehaviour {
     pub kademlia: kad::Behaviour<MemoryStore>,
     /// Identify protocol for peer identification
     pub identify: identify::Behaviour,
+    /// Ping for measuring round-trip times
+    pub ping: ping::Behaviour,
     /// mDNS for local peer discovery
     pub mdns: mdns::tokio::Behaviour,
+    /// AutoNAT for probing our public reachability
+    pub autonat: autonat::Behaviour,
+    /// Relay client for reservations when we are behind a NAT
+    pub relay_client: relay::client::Behaviour,
+    /// Request-response for fetching content from its providers
+    pub content: request_response::cbor::Behaviour<ContentRequest, ContentResponse>,
 }
 
 /// Events emitted by the network behaviour
@@ -41,8 +54,16 @@ pub enum MycelialBehaviourEvent {
     Kademlia(kad::Event),
     /// Identify event
     Identify(identify::Event),
+    /// Ping event
+    Ping(ping::Event),
     /// mDNS event
     Mdns(mdns::Event),
+    /// AutoNAT event
+    Autonat(autonat::Event),
+    /// Relay client event
+    RelayClient(relay::client::Event),
+    /// Content exchange event
+    Content(request_response::Event<ContentRequest, ContentResponse>),
 }
 
 impl From<gossipsub::Event> for MycelialBehaviourEvent {
@@ -63,15 +84,46 @@ impl From<identify::Event> for MycelialBehaviourEvent {
     }
 }
 
+impl From<ping::Event> for MycelialBehaviourEvent {
+    fn from(event: ping::Event) -> Self {
+        MycelialBehaviourEvent::Ping(event)
+    }
+}
+
 impl From<mdns::Event> for MycelialBehaviourEvent {
     fn from(event: mdns::Event) -> Self {
         MycelialBehaviourEvent::Mdns(event)
     }
 }
 
+impl From<autonat::Event> for MycelialBehaviourEvent {
+    fn from(event: autonat::Event) -> Self {
+        MycelialBehaviourEvent::Autonat(event)
+    }
+}
+
+impl From<relay::client::Event> for MycelialBehaviourEvent {
+    fn from(event: relay::client::Event) -> Self {
+        MycelialBehaviourEvent::RelayClient(event)
+    }
+}
+
+impl From<request_response::Event<ContentRequest, ContentResponse>> for MycelialBehaviourEvent {
+    fn from(event: request_response::Event<ContentRequest, ContentResponse>) -> Self {
+        MycelialBehaviourEvent::Content(event)
+    }
+}
+
 impl MycelialBehaviour {
     /// Create a new network behaviour
-    pub fn new(keypair: &Keypair, config: &NetworkConfig) -> crate::error::Result<Self> {
+    ///
+    /// `relay_client` comes from [`relay::client::new`], whose transport half
+    /// must be part of the swarm's transport.
+    pub fn new(
+        keypair: &Keypair,
+        config: &NetworkConfig,
+        relay_client: relay::client::Behaviour,
+    ) -> crate::error::Result<Self> {
         let local_peer_id = keypair.public().to_peer_id();
 
         // Create gossipsub behaviour
@@ -87,11 +139,18 @@ impl MycelialBehaviour {
         let mdns = mdns::tokio::Behaviour::new(mdns::Config::default(), local_peer_id)
             .map_err(|e| NetworkError::Config(e.to_string()))?;
 
+        // Create AutoNAT behaviour (probes via connected peers)
+        let autonat = autonat::Behaviour::new(local_peer_id, autonat::Config::default());
+
         Ok(Self {
             gossipsub,
             kademlia,
             identify,
+            ping: ping::Behaviour::new(ping::Config::new().with_interval(config.ping_interval())),
             mdns,
+            autonat,
+            relay_client,
+            content: content::create_content_exchange(),
         })
     }
 
@@ -114,11 +173,12 @@ impl MycelialBehaviour {
     }
 
     /// Publish a message to a gossipsub topic
-    pub fn publish(&mut self, topic: &str, data: Vec<u8>) -> crate::error::Result<MessageId> {
-        let topic = IdentTopic::new(topic);
-        self.gossipsub
-            .publish(topic, data)
-            .map_err(|e| NetworkError::Gossipsub(format!("Failed to publish: {:?}", e)))
+    pub fn publish(
+        &mut self,
+        topic: &str,
+        data: Vec<u8>,
+    ) -> Result<MessageId, gossipsub::PublishError> {
+        self.gossipsub.publish(IdentTopic::new(topic), data)
     }
 
     /// Get the mesh peers for a specific topic
@@ -138,6 +198,43 @@ impl MycelialBehaviour {
             .collect()
     }
 
+    /// Report whether a received message should be relayed
+    ///
+    /// Gossipsub holds every message until it is reported here; accepted
+    /// messages are forwarded to the mesh, rejected ones are dropped and
+    /// count against the peer that sent them.
+    pub fn report_message(
+        &mut self,
+        message_id: &MessageId,
+        propagation_source: &PeerId,
+        acceptance: MessageAcceptance,
+    ) {
+        let _ = self.gossipsub.report_message_validation_result(
+            message_id,
+            propagation_source,
+            acceptance,
+        );
+    }
+
+    /// Tell gossipsub how good a peer's connection is
+    ///
+    /// Quality is the peer's application-specific gossipsub score, so better
+    /// peers are kept when the mesh is pruned and preferred by opportunistic
+    /// grafting.
+    pub fn set_peer_quality(&mut self, peer_id: &PeerId, quality: f64) {
+        self.gossipsub.set_application_score(peer_id, quality);
+    }
+
+    /// Always send this peer our messages, whatever its score
+    pub fn add_explicit_peer(&mut self, peer_id: &PeerId) {
+        self.gossipsub.add_explicit_peer(peer_id);
+    }
+
+    /// Treat a former explicit peer like any other
+    pub fn remove_explicit_peer(&mut self, peer_id: &PeerId) {
+        self.gossipsub.remove_explicit_peer(peer_id);
+    }
+
     /// Log mesh status for debugging
     pub fn log_mesh_status(&self, topic: &str) {
         let topic_hash = IdentTopic::new(topic).hash();
@@ -172,6 +269,14 @@ impl MycelialBehaviour {
             .map_err(|e| NetworkError::Kademlia(format!("Bootstrap failed: {:?}", e)))
     }
 
+    /// Number of peers in the Kademlia routing table
+    pub fn routing_table_size(&mut self) -> usize {
+        self.kademlia
+            .kbuckets()
+            .map(|bucket| bucket.num_entries())
+            .sum()
+    }
+
     /// Get closest peers to a key
     pub fn get_closest_peers(&mut self, key: Vec<u8>) -> kad::QueryId {
         self.kademlia.get_closest_peers(key)
@@ -189,11 +294,64 @@ impl MycelialBehaviour {
             .map_err(|e| NetworkError::Kademlia(format!("Put record failed: {:?}", e)))
     }
 
+    /// Store a record a remote peer put, once it has been vetted
+    ///
+    /// Kademlia runs with record filtering, so inbound puts are only
+    /// stored through here.
+    pub fn store_inbound_record(&mut self, record: kad::Record) -> crate::error::Result<()> {
+        use kad::store::RecordStore;
+
+        self.kademlia
+            .store_mut()
+            .put(record)
+            .map_err(|e| NetworkError::Kademlia(format!("Store record failed: {:?}", e)))
+    }
+
+    /// Store a provider record a remote peer announced, once it has been
+    /// vetted
+    ///
+    /// Like puts, inbound provider records are only stored through here.
+    pub fn store_inbound_provider(
+        &mut self,
+        record: kad::ProviderRecord,
+    ) -> crate::error::Result<()> {
+        use kad::store::RecordStore;
+
+        self.kademlia
+            .store_mut()
+            .add_provider(record)
+            .map_err(|e| NetworkError::Kademlia(format!("Store provider failed: {:?}", e)))
+    }
+
     /// Get a value from the DHT
     pub fn get_record(&mut self, key: Vec<u8>) -> kad::QueryId {
         let key = kad::RecordKey::new(&key);
         self.kademlia.get_record(key)
     }
+
+    /// Announce in the DHT that we provide a piece of content
+    pub fn start_providing(&mut self, id: &ContentId) -> crate::error::Result<kad::QueryId> {
+        self.kademlia
+            .start_providing(content::provider_key(id))
+            .map_err(|e| NetworkError::Kademlia(format!("Start providing failed: {:?}", e)))
+    }
+
+    /// Withdraw our provider record for a piece of content
+    pub fn stop_providing(&mut self, id: &ContentId) {
+        self.kademlia.stop_providing(&content::provider_key(id));
+    }
+
+    /// Look up the providers of a piece of content in the DHT
+    pub fn get_providers(&mut self, id: &ContentId) -> kad::QueryId {
+        self.kademlia.get_providers(content::provider_key(id))
+    }
+
+    /// End a Kademlia query early, reporting what it has found so far
+    pub fn finish_query(&mut self, id: &kad::QueryId) {
+        if let Some(mut query) = self.kademlia.query_mut(id) {
+            query.finish();
+        }
+    }
 }
 
 /// Create a gossipsub behaviour with the given configuration
@@ -201,49 +359,70 @@ fn create_gossipsub(
     keypair: &Keypair,
     config: &NetworkConfig,
 ) -> crate::error::Result<gossipsub::Behaviour> {
-    // Message ID function based on content hash
+    // Message ID function based on content hash. Mycelial messages use their
+    // content-derived ID so the same message bridged in from another network
+    // (e.g. LoRa) is deduplicated; anything else is hashed as raw bytes.
     let message_id_fn = |message: &gossipsub::Message| {
-        let mut hasher = Sha256::new();
-        hasher.update(&message.data);
-        MessageId::from(hasher.finalize().to_vec())
+        MessageId::from(mycelial_core::message::payload_id(&message.data))
     };
 
     // Build gossipsub config
-    // Use smaller mesh parameters suitable for small test networks (2-3 nodes)
-    // Constraint: mesh_outbound_min <= mesh_n_low <= mesh_n <= mesh_n_high
-    // mesh_outbound_min: minimum outbound mesh peers (default=2, set to 0 for flexibility)
-    // mesh_n: target number of peers in the mesh (default=6, lowered to 2)
-    // mesh_n_low: minimum mesh peers before trying to add more (default=4, lowered to 1)
-    // mesh_n_high: maximum mesh peers before pruning (default=12, lowered to 4)
+    // Mesh sizes and timings come from the network config, whose defaults
+    // suit small networks; `NetworkConfig::validate` has checked them
     let gossipsub_config = gossipsub::ConfigBuilder::default()
-        .heartbeat_interval(Duration::from_secs(1))
+        .heartbeat_interval(config.heartbeat_interval())
         .validation_mode(ValidationMode::Strict)
         .message_id_fn(message_id_fn)
         .max_transmit_size(config.max_message_size)
-        .mesh_outbound_min(0) // Allow 0 outbound (for 2-node networks)
-        .mesh_n(2) // Target 2 mesh peers
-        .mesh_n_low(1) // Minimum 1 peer to maintain mesh
-        .mesh_n_high(4) // Maximum 4 before pruning
+        .mesh_outbound_min(config.mesh_outbound_min)
+        .mesh_n(config.mesh_n)
+        .mesh_n_low(config.mesh_n_low)
+        .mesh_n_high(config.mesh_n_high)
+        .gossip_factor(config.gossip_factor)
         .gossip_lazy(2) // Reduced for smaller networks
         .fanout_ttl(Duration::from_secs(60))
-        .history_length(5)
-        .history_gossip(3)
-        .duplicate_cache_time(Duration::from_secs(60))
+        .history_length(config.history_length)
+        .history_gossip(config.history_gossip)
+        .duplicate_cache_time(config.duplicate_cache_time())
+        // Messages are only relayed once the service has vetted them
+        .validate_messages()
         .build()
         .map_err(|e| NetworkError::Config(format!("Gossipsub config error: {}", e)))?;
 
     // Create behaviour with signing using the keypair
-    gossipsub::Behaviour::new(
+    let mut gossipsub = gossipsub::Behaviour::new(
         MessageAuthenticity::Signed(keypair.clone()),
         gossipsub_config,
     )
-    .map_err(|e| NetworkError::Config(format!("Gossipsub creation error: {}", e)))
+    .map_err(|e| NetworkError::Config(format!("Gossipsub creation error: {}", e)))?;
+
+    // Score peers only by connection quality (see `set_peer_quality`). No
+    // IP colocation penalty: local clusters share an address.
+    let score_params = gossipsub::PeerScoreParams {
+        app_specific_weight: 1.0,
+        ip_colocation_factor_weight: 0.0,
+        ..Default::default()
+    };
+    let score_thresholds = gossipsub::PeerScoreThresholds {
+        opportunistic_graft_threshold: crate::peer::NEUTRAL_QUALITY,
+        ..Default::default()
+    };
+    gossipsub
+        .with_peer_score(score_params, score_thresholds)
+        .map_err(|e| NetworkError::Config(format!("Gossipsub scoring error: {}", e)))?;
+
+    Ok(gossipsub)
 }
 
 /// Create a Kademlia behaviour
 fn create_kademlia(local_peer_id: PeerId, _config: &NetworkConfig) -> kad::Behaviour<MemoryStore> {
     let store = MemoryStore::new(local_peer_id);
-    let mut kademlia = kad::Behaviour::new(local_peer_id, store);
+
+    // Hand inbound puts and provider announcements to the service instead
+    // of storing them, so the sender's reputation can be checked first
+    let mut kad_config = kad::Config::new(kad::PROTOCOL_NAME);
+    kad_config.set_record_filtering(kad::StoreInserts::FilterBoth);
+    let mut kademlia = kad::Behaviour::with_config(local_peer_id, store, kad_config);
 
     // Set Kademlia to server mode for full participation
     kademlia.set_mode(Some(kad::Mode::Server));
@@ -277,6 +456,8 @@ pub mod topics {
     pub const GOVERNANCE: &str = "/mycelial/1.0.0/governance";
     /// System messages (peer discovery, health)
     pub const SYSTEM: &str = "/mycelial/1.0.0/system";
+    /// LoRa radio telemetry bridged from Meshtastic devices
+    pub const TELEMETRY: &str = "/mycelial/1.0.0/telemetry";
 
     /// Get all standard topics
     pub fn all() -> Vec<&'static str> {
@@ -289,6 +470,7 @@ pub mod topics {
             ECONOMICS,
             GOVERNANCE,
             SYSTEM,
+            TELEMETRY,
         ]
     }
 }
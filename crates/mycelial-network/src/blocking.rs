@@ -0,0 +1,199 @@
+//! Synchronous wrapper around [`NetworkHandle`] for non-async callers
+//!
+//! [`BlockingNetworkHandle::start`] creates a dedicated multi-threaded Tokio
+//! runtime, runs the [`NetworkService`] on it and returns a handle whose
+//! methods block the calling thread until the service answers. The network
+//! keeps running on the runtime's worker threads between calls, so callers
+//! only need a thread to call from.
+//!
+//! # Threading model
+//!
+//! - The handle is `Clone + Send + Sync`; clones share the runtime and can be
+//!   used from any number of threads at once.
+//! - Methods must not be called from inside an async context (a Tokio task or
+//!   `block_on`): blocking on the runtime from there panics. Async code should
+//!   use [`handle`](BlockingNetworkHandle::handle) instead.
+//! - The runtime stops when the last clone is dropped, which must also happen
+//!   outside an async context.
+//!
+//! Each `start` builds its own runtime with its own worker threads. Start one
+//! network per process and share clones of its handle, rather than starting
+//! one per caller.
+//!
+//! ```rust,no_run
+//! use mycelial_network::blocking::BlockingNetworkHandle;
+//! use mycelial_network::{Keypair, NetworkConfig};
+//!
+//! let (network, mut events) =
+//!     BlockingNetworkHandle::start(Keypair::generate_ed25519(), NetworkConfig::default())?;
+//! network.subscribe("/mycelial/1.0.0/chat")?;
+//! network.publish("/mycelial/1.0.0/chat", b"hello".to_vec())?;
+//!
+//! while let Ok(event) = events.blocking_recv() {
+//!     println!("{:?}", event);
+//! }
+//! # Ok::<(), mycelial_network::NetworkError>(())
+//! ```
+
+use libp2p::{Multiaddr, PeerId};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use crate::config::NetworkConfig;
+use crate::error::{NetworkError, Result};
+use crate::event::{NetworkEvent, NetworkStats};
+use crate::service::{NetworkHandle, NetworkService};
+
+/// Worker threads in the runtime a blocking network runs on
+const WORKER_THREADS: usize = 2;
+
+/// A [`NetworkHandle`] with blocking methods, driving its own runtime
+#[derive(Clone)]
+pub struct BlockingNetworkHandle {
+    runtime: Arc<Runtime>,
+    handle: NetworkHandle,
+}
+
+impl BlockingNetworkHandle {
+    /// Start a network service on a dedicated runtime
+    ///
+    /// Returns the handle and the service's events; read them with
+    /// [`broadcast::Receiver::blocking_recv`]. Events are dropped for a
+    /// receiver that falls too far behind, which then reports how many it
+    /// missed.
+    pub fn start(
+        keypair: libp2p::identity::Keypair,
+        config: NetworkConfig,
+    ) -> Result<(Self, broadcast::Receiver<NetworkEvent>)> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(WORKER_THREADS)
+            .thread_name("mycelial-network")
+            .enable_all()
+            .build()
+            .map_err(|e| NetworkError::Internal(format!("Failed to start runtime: {}", e)))?;
+
+        // Building the swarm needs the runtime's reactor
+        let (handle, events) = {
+            let _entered = runtime.enter();
+            #[cfg(feature = "univrs-compat")]
+            let (service, handle, events, _enr_bridge) = NetworkService::new(keypair, config)?;
+            #[cfg(not(feature = "univrs-compat"))]
+            let (service, handle, events) = NetworkService::new(keypair, config)?;
+
+            runtime.spawn(async move {
+                if let Err(e) = service.run().await {
+                    warn!("Network service stopped with an error: {}", e);
+                }
+            });
+            (handle, events)
+        };
+
+        Ok((Self::with_runtime(handle, Arc::new(runtime)), events))
+    }
+
+    /// Wrap a handle to a service already running on `runtime`
+    pub fn with_runtime(handle: NetworkHandle, runtime: Arc<Runtime>) -> Self {
+        Self { runtime, handle }
+    }
+
+    /// The async handle, for use from tasks on the runtime
+    pub fn handle(&self) -> NetworkHandle {
+        self.handle.clone()
+    }
+
+    /// The runtime the network runs on
+    pub fn runtime(&self) -> &Arc<Runtime> {
+        &self.runtime
+    }
+
+    /// Get the local peer ID
+    pub fn local_peer_id(&self) -> PeerId {
+        self.handle.local_peer_id()
+    }
+
+    /// Whether the network service is still running
+    pub fn is_alive(&self) -> bool {
+        self.handle.is_alive()
+    }
+
+    /// Dial a peer
+    pub fn dial(&self, address: Multiaddr) -> Result<()> {
+        self.runtime.block_on(self.handle.dial(address))
+    }
+
+    /// Subscribe to a topic
+    pub fn subscribe(&self, topic: impl Into<String>) -> Result<()> {
+        self.runtime.block_on(self.handle.subscribe(topic))
+    }
+
+    /// Unsubscribe from a topic
+    pub fn unsubscribe(&self, topic: impl Into<String>) -> Result<()> {
+        self.runtime.block_on(self.handle.unsubscribe(topic))
+    }
+
+    /// Publish a message to a topic
+    ///
+    /// Returns once the service has the message, not once peers have it.
+    pub fn publish(&self, topic: impl Into<String>, data: Vec<u8>) -> Result<()> {
+        self.runtime.block_on(self.handle.publish(topic, data))
+    }
+
+    /// Get list of connected peers
+    pub fn get_peers(&self) -> Result<Vec<PeerId>> {
+        self.runtime.block_on(self.handle.get_peers())
+    }
+
+    /// Get network statistics
+    pub fn get_stats(&self) -> Result<NetworkStats> {
+        self.runtime.block_on(self.handle.get_stats())
+    }
+
+    /// Get the topics we are subscribed to, sorted
+    pub fn subscribed_topics(&self) -> Result<Vec<String>> {
+        self.runtime.block_on(self.handle.subscribed_topics())
+    }
+
+    /// Disconnect from peers and stop the service, waiting at most `timeout`
+    pub fn shutdown(&self, timeout: Duration) -> Result<()> {
+        self.runtime
+            .block_on(self.handle.shutdown_graceful(timeout))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockNetworkHandle;
+
+    #[test]
+    fn test_blocking_calls_reach_the_network() {
+        let runtime = Arc::new(Runtime::new().unwrap());
+        let mock = {
+            let _entered = runtime.enter();
+            MockNetworkHandle::new()
+        };
+        let network = BlockingNetworkHandle::with_runtime(mock.handle(), runtime.clone());
+
+        network.subscribe("chat").unwrap();
+        network.publish("chat", b"hello".to_vec()).unwrap();
+        assert_eq!(network.subscribed_topics().unwrap(), vec!["chat"]);
+        assert_eq!(network.get_stats().unwrap().messages_sent, 1);
+        assert!(network.get_peers().unwrap().is_empty());
+        mock.assert_published("chat", b"hello");
+
+        // Clones share the runtime across threads
+        let clone = network.clone();
+        std::thread::spawn(move || clone.publish("chat", b"again".to_vec()).unwrap())
+            .join()
+            .unwrap();
+        // Commands are answered in order, so the stats include that publish
+        assert_eq!(network.get_stats().unwrap().messages_sent, 2);
+        assert_eq!(mock.published_to("chat").len(), 2);
+
+        network.shutdown(Duration::from_secs(1)).unwrap();
+        assert!(!network.is_alive());
+    }
+}
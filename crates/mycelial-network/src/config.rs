@@ -1,8 +1,18 @@
 //! Network configuration types
 
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
 use std::time::Duration;
 
+use crate::error::{NetworkError, Result};
+
+/// Shortest gossipsub heartbeat interval accepted, in milliseconds
+pub const MIN_HEARTBEAT_INTERVAL_MS: u64 = 100;
+
+/// Longest gossipsub heartbeat interval accepted, in milliseconds
+pub const MAX_HEARTBEAT_INTERVAL_MS: u64 = 60_000;
+
 /// Network configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkConfig {
@@ -14,16 +24,268 @@ pub struct NetworkConfig {
     pub enable_mdns: bool,
     /// Enable Kademlia DHT
     pub enable_kademlia: bool,
+    /// How often the Kademlia routing table is rebuilt with a bootstrap
+    /// query, in seconds; 0 only bootstraps once a bootstrap peer connects
+    pub kademlia_bootstrap_interval_secs: u64,
+    /// How often a lookup of a random peer ID refreshes the routing table's
+    /// buckets between bootstraps, in seconds; 0 disables it
+    pub kademlia_refresh_interval_secs: u64,
     /// Maximum number of connections
     pub max_connections: u32,
     /// Maximum message size in bytes
+    ///
+    /// Larger publishes are split into fragments that fit, up to
+    /// `max_fragmented_message_size`.
     pub max_message_size: usize,
+    /// Largest payload that is fragmented on publish and reassembled on
+    /// receipt, in bytes; 0 disables fragmentation
+    pub max_fragmented_message_size: usize,
+    /// Fragment bytes buffered across all partly received messages before
+    /// the oldest are dropped
+    pub fragment_buffer_size: usize,
+    /// How long a partly received message waits for its remaining
+    /// fragments, in seconds
+    pub fragment_timeout_secs: u64,
     /// Connection idle timeout in seconds
     pub idle_timeout_secs: u64,
+    /// Time allowed for the Noise/Yamux (or QUIC) handshake, in seconds
+    pub handshake_timeout_secs: u64,
+    /// How often each connected peer is pinged, in seconds
+    pub ping_interval_secs: u64,
+    /// Consecutive failed pings after which a peer is disconnected and
+    /// reported to its septal gate
+    pub ping_max_failures: u32,
     /// Enable TCP transport
     pub enable_tcp: bool,
     /// Enable QUIC transport
     pub enable_quic: bool,
+    /// Enable the WebRTC direct transport (requires the `webrtc` feature)
+    ///
+    /// Add a `/ip4/.../udp/<port>/webrtc-direct` listen address to accept
+    /// browser peers.
+    pub enable_webrtc: bool,
+    /// Join the ENR economics topics (gradients, credits, elections, septal
+    /// gates and, with `openraft`, the Raft ledger) and feed them to the
+    /// `EnrBridge`
+    ///
+    /// Defaults to on when the `univrs-compat` feature is enabled.
+    pub enable_economics: bool,
+    /// File the ENR bridge records seen credit transfer nonces in, so
+    /// replayed transfers stay rejected across restarts
+    ///
+    /// `None` keeps the nonces in memory only.
+    pub credit_nonce_path: Option<PathBuf>,
+    /// How often the Raft leader pays the revival pool out to the cluster,
+    /// in seconds; 0 disables it
+    ///
+    /// Only used with `openraft` and a ledger attached to the `EnrBridge`.
+    pub revival_distribution_interval_secs: u64,
+    /// Initial delay before redialing an unreachable bootstrap peer, in seconds
+    pub bootstrap_retry_initial_secs: u64,
+    /// Upper bound for the bootstrap redial delay, in seconds
+    pub bootstrap_retry_max_secs: u64,
+    /// Relays (multiaddrs ending in `/p2p/<id>`) to reserve a slot on when
+    /// AutoNAT finds we are not publicly reachable
+    pub relay_addresses: Vec<String>,
+    /// Peers (multiaddrs ending in `/p2p/<id>`) to always exchange messages
+    /// with, whatever their score
+    ///
+    /// Gossipsub sends these explicit peers every message on the topics they
+    /// share and never prunes them. They are redialed whenever they
+    /// disconnect, so they suit trusted infrastructure nodes.
+    pub explicit_peers: Vec<String>,
+    /// Minimum reputation (0.0 - 1.0) a peer needs for us to store DHT
+    /// records it puts or content it announces providing; 0.0 accepts
+    /// everyone
+    ///
+    /// Our own puts and provider records are always stored.
+    pub min_record_reputation: f64,
+    /// Minimum reputation (0.0 - 1.0) a peer needs for its messages on
+    /// `reputation_filtered_topics` to be delivered
+    pub min_message_reputation: f64,
+    /// Topics whose messages are not delivered, with a
+    /// [`NetworkEvent::MessageFiltered`](crate::NetworkEvent::MessageFiltered),
+    /// when the source's reputation is below `min_message_reputation`
+    ///
+    /// They are still relayed, for peers that trust the source more.
+    ///
+    /// Typically the credit and governance topics.
+    pub reputation_filtered_topics: BTreeSet<String>,
+    /// Reputation (0.0 - 1.0) below which a peer that sends us a message is
+    /// banned; 0.0 never bans
+    pub ban_reputation: f64,
+    /// How long reputation bans, and bans on peers whose septal gate we
+    /// closed, last in seconds
+    pub ban_duration_secs: u64,
+    /// Target number of peers in each gossipsub topic mesh
+    ///
+    /// The defaults suit networks of a handful of nodes; deployments with
+    /// hundreds of nodes should raise the mesh sizes towards the gossipsub
+    /// defaults (6, 4, 12 and 2 outbound).
+    pub mesh_n: usize,
+    /// Mesh size below which gossipsub grafts more peers
+    pub mesh_n_low: usize,
+    /// Mesh size above which gossipsub prunes peers
+    pub mesh_n_high: usize,
+    /// Minimum outbound connections kept in each mesh
+    pub mesh_outbound_min: usize,
+    /// Share (0.0 - 1.0) of non-mesh peers gossip is emitted to each heartbeat
+    pub gossip_factor: f64,
+    /// Time between gossipsub heartbeats, in milliseconds
+    ///
+    /// Each heartbeat repairs the mesh and emits gossip. A longer interval
+    /// means less control traffic on slow or high-latency links (such as
+    /// those bridged to LoRa) at the cost of slower mesh repair and gossip
+    /// recovery; a shorter one speeds propagation in large, fast meshes.
+    pub heartbeat_interval_ms: u64,
+    /// Heartbeats a sent message stays available to peers that request it
+    /// after hearing about it in gossip
+    pub history_length: usize,
+    /// Of the `history_length` most recent heartbeats, how many are
+    /// advertised in gossip
+    ///
+    /// More windows let peers that missed a message recover it later, with
+    /// larger gossip messages as the price.
+    pub history_gossip: usize,
+    /// How long message IDs are remembered to drop duplicates, in seconds
+    ///
+    /// Must outlast the message history, or a message gossiped late could
+    /// be delivered twice. Longer times cost memory on busy topics.
+    pub duplicate_cache_time_secs: u64,
+    /// Topics to rejoin on start, on top of the built-in ones
+    ///
+    /// Typically loaded from the node's store so runtime subscriptions
+    /// survive a restart.
+    pub subscribed_topics: BTreeSet<String>,
+    /// Seconds without traffic after which a topic is unsubscribed until
+    /// we next publish to it; 0 keeps every topic subscribed
+    ///
+    /// The built-in core and economics topics are never unsubscribed.
+    pub topic_idle_timeout_secs: u64,
+    /// Who may publish on each listed topic
+    ///
+    /// Messages on these topics from anyone else are rejected before they
+    /// are relayed or delivered. Topics not listed are open to everyone.
+    pub topic_acls: BTreeMap<String, TopicAcl>,
+    /// Limit on how fast we publish to each topic without its own entry in
+    /// `topic_rate_limits`; `None` leaves them unlimited
+    ///
+    /// Publishes over the limit fail with
+    /// [`NetworkError::RateLimited`] instead of going out, so a runaway
+    /// local component can't get the node graylisted by peer scoring.
+    pub publish_rate_limit: Option<RateLimit>,
+    /// Publish rate limits for particular topics, overriding
+    /// `publish_rate_limit`
+    ///
+    /// Typically higher for the economics topics than for chat.
+    pub topic_rate_limits: BTreeMap<String, RateLimit>,
+}
+
+/// A token-bucket rate limit
+///
+/// Up to `burst` messages can go out at once; after that the bucket refills
+/// at `per_second` messages a second.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RateLimit {
+    /// Sustained messages per second
+    pub per_second: f64,
+    /// Messages that can be sent back to back
+    pub burst: u32,
+}
+
+impl RateLimit {
+    /// `per_second` messages a second, in bursts of up to `burst`
+    pub fn new(per_second: f64, burst: u32) -> Self {
+        Self { per_second, burst }
+    }
+}
+
+/// IP versions a node listens on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IpStack {
+    /// IPv4 only
+    #[default]
+    V4,
+    /// IPv6 only, for IPv6-only networks
+    V6,
+    /// IPv4 and IPv6 side by side
+    Dual,
+}
+
+impl IpStack {
+    /// Wildcard listen addresses for each IP version, followed by `suffix`
+    ///
+    /// `IpStack::Dual.listen_addresses("tcp/4001")` gives
+    /// `/ip4/0.0.0.0/tcp/4001` and `/ip6/::/tcp/4001`.
+    pub fn listen_addresses(self, suffix: &str) -> Vec<String> {
+        let hosts: &[&str] = match self {
+            IpStack::V4 => &["/ip4/0.0.0.0"],
+            IpStack::V6 => &["/ip6/::"],
+            IpStack::Dual => &["/ip4/0.0.0.0", "/ip6/::"],
+        };
+        let suffix = suffix.trim_start_matches('/');
+        hosts
+            .iter()
+            .map(|host| format!("{}/{}", host, suffix))
+            .collect()
+    }
+}
+
+/// Publishers accepted on an access-controlled topic
+///
+/// A source is accepted if it is on the allowlist, or if `min_reputation`
+/// is set and its reputation reaches it. With an empty allowlist and no
+/// reputation threshold nobody else can publish on the topic.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TopicAcl {
+    /// Base58 peer IDs allowed to publish
+    pub allowed_peers: BTreeSet<String>,
+    /// Reputation (0.0 - 1.0) that lets peers off the allowlist publish
+    pub min_reputation: Option<f64>,
+}
+
+impl TopicAcl {
+    /// Only let the given peers publish
+    pub fn allowlist(peers: impl IntoIterator<Item = libp2p::PeerId>) -> Self {
+        Self {
+            allowed_peers: peers.into_iter().map(|p| p.to_base58()).collect(),
+            min_reputation: None,
+        }
+    }
+
+    /// Also let peers publish once their reputation reaches `min_reputation`
+    pub fn with_min_reputation(mut self, min_reputation: f64) -> Self {
+        self.min_reputation = Some(min_reputation);
+        self
+    }
+
+    /// Check whether `source` may publish, returning why not if it can't
+    ///
+    /// `reputation` is only called when the source isn't on the allowlist.
+    pub fn check(
+        &self,
+        source: &libp2p::PeerId,
+        reputation: impl FnOnce() -> f64,
+    ) -> std::result::Result<(), String> {
+        if self.allowed_peers.contains(&source.to_base58()) {
+            return Ok(());
+        }
+        match self.min_reputation {
+            Some(min) => {
+                let reputation = reputation();
+                if reputation >= min {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "not on the allowlist and reputation {:.2} < {:.2}",
+                        reputation, min
+                    ))
+                }
+            }
+            None => Err("not on the allowlist".to_string()),
+        }
+    }
 }
 
 impl Default for NetworkConfig {
@@ -36,16 +298,64 @@ impl Default for NetworkConfig {
             bootstrap_peers: Vec::new(),
             enable_mdns: true,
             enable_kademlia: true,
+            kademlia_bootstrap_interval_secs: 300,
+            kademlia_refresh_interval_secs: 120,
             max_connections: 100,
             max_message_size: 1024 * 1024, // 1 MB
+            max_fragmented_message_size: 16 * 1024 * 1024,
+            fragment_buffer_size: 64 * 1024 * 1024,
+            fragment_timeout_secs: 30,
             idle_timeout_secs: 30,
+            handshake_timeout_secs: 20,
+            ping_interval_secs: 15,
+            ping_max_failures: 3,
             enable_tcp: true,
             enable_quic: true,
+            enable_webrtc: false,
+            enable_economics: cfg!(feature = "univrs-compat"),
+            credit_nonce_path: None,
+            revival_distribution_interval_secs: 24 * 3600,
+            bootstrap_retry_initial_secs: 1,
+            bootstrap_retry_max_secs: 60,
+            relay_addresses: Vec::new(),
+            explicit_peers: Vec::new(),
+            min_record_reputation: 0.0,
+            min_message_reputation: 0.0,
+            reputation_filtered_topics: BTreeSet::new(),
+            ban_reputation: 0.0,
+            ban_duration_secs: 3600,
+            mesh_n: 2,
+            mesh_n_low: 1,
+            mesh_n_high: 4,
+            mesh_outbound_min: 0,
+            gossip_factor: 0.25,
+            heartbeat_interval_ms: 1000,
+            history_length: 5,
+            history_gossip: 3,
+            duplicate_cache_time_secs: 60,
+            subscribed_topics: BTreeSet::new(),
+            topic_idle_timeout_secs: 0,
+            topic_acls: BTreeMap::new(),
+            publish_rate_limit: None,
+            topic_rate_limits: BTreeMap::new(),
         }
     }
 }
 
 impl NetworkConfig {
+    /// Listen on TCP and QUIC on every interface of the given IP versions
+    ///
+    /// Replaces the configured listen addresses. On a dual stack each IP
+    /// version gets its own socket, so both can use the same port.
+    pub fn with_ip_stack(mut self, stack: IpStack, tcp_port: u16, quic_port: u16) -> Self {
+        self.listen_addresses = stack
+            .listen_addresses(&format!("tcp/{}", tcp_port))
+            .into_iter()
+            .chain(stack.listen_addresses(&format!("udp/{}/quic-v1", quic_port)))
+            .collect();
+        self
+    }
+
     /// Create a configuration for local testing
     pub fn local_test(port: u16) -> Self {
         Self {
@@ -53,11 +363,46 @@ impl NetworkConfig {
             bootstrap_peers: Vec::new(),
             enable_mdns: true,
             enable_kademlia: true,
+            kademlia_bootstrap_interval_secs: 300,
+            kademlia_refresh_interval_secs: 120,
             max_connections: 50,
             max_message_size: 1024 * 1024,
+            max_fragmented_message_size: 16 * 1024 * 1024,
+            fragment_buffer_size: 64 * 1024 * 1024,
+            fragment_timeout_secs: 30,
             idle_timeout_secs: 30,
+            handshake_timeout_secs: 20,
+            ping_interval_secs: 15,
+            ping_max_failures: 3,
             enable_tcp: true,
             enable_quic: false, // Simpler for testing
+            enable_webrtc: false,
+            enable_economics: cfg!(feature = "univrs-compat"),
+            credit_nonce_path: None,
+            revival_distribution_interval_secs: 24 * 3600,
+            bootstrap_retry_initial_secs: 1,
+            bootstrap_retry_max_secs: 10,
+            relay_addresses: Vec::new(),
+            explicit_peers: Vec::new(),
+            min_record_reputation: 0.0,
+            min_message_reputation: 0.0,
+            reputation_filtered_topics: BTreeSet::new(),
+            ban_reputation: 0.0,
+            ban_duration_secs: 3600,
+            mesh_n: 2,
+            mesh_n_low: 1,
+            mesh_n_high: 4,
+            mesh_outbound_min: 0,
+            gossip_factor: 0.25,
+            heartbeat_interval_ms: 1000,
+            history_length: 5,
+            history_gossip: 3,
+            duplicate_cache_time_secs: 60,
+            subscribed_topics: BTreeSet::new(),
+            topic_idle_timeout_secs: 0,
+            topic_acls: BTreeMap::new(),
+            publish_rate_limit: None,
+            topic_rate_limits: BTreeMap::new(),
         }
     }
 
@@ -65,4 +410,295 @@ impl NetworkConfig {
     pub fn idle_timeout(&self) -> Duration {
         Duration::from_secs(self.idle_timeout_secs)
     }
+
+    /// Get the handshake timeout as a Duration
+    pub fn handshake_timeout(&self) -> Duration {
+        Duration::from_secs(self.handshake_timeout_secs)
+    }
+
+    /// Get the ping interval as a Duration
+    pub fn ping_interval(&self) -> Duration {
+        Duration::from_secs(self.ping_interval_secs)
+    }
+
+    /// Interval between Kademlia bootstraps, or `None` if disabled
+    pub fn kademlia_bootstrap_interval(&self) -> Option<Duration> {
+        (self.kademlia_bootstrap_interval_secs > 0)
+            .then(|| Duration::from_secs(self.kademlia_bootstrap_interval_secs))
+    }
+
+    /// Interval between Kademlia bucket refreshes, or `None` if disabled
+    pub fn kademlia_refresh_interval(&self) -> Option<Duration> {
+        (self.kademlia_refresh_interval_secs > 0)
+            .then(|| Duration::from_secs(self.kademlia_refresh_interval_secs))
+    }
+
+    /// Get the gossipsub heartbeat interval as a Duration
+    pub fn heartbeat_interval(&self) -> Duration {
+        Duration::from_millis(self.heartbeat_interval_ms)
+    }
+
+    /// Get the gossipsub duplicate cache time as a Duration
+    pub fn duplicate_cache_time(&self) -> Duration {
+        Duration::from_secs(self.duplicate_cache_time_secs)
+    }
+
+    /// Idle period after which topics are unsubscribed, or `None` if disabled
+    pub fn topic_idle_timeout(&self) -> Option<Duration> {
+        (self.topic_idle_timeout_secs > 0)
+            .then(|| Duration::from_secs(self.topic_idle_timeout_secs))
+    }
+
+    /// Interval between revival pool payouts, or `None` if disabled
+    pub fn revival_distribution_interval(&self) -> Option<Duration> {
+        (self.revival_distribution_interval_secs > 0)
+            .then(|| Duration::from_secs(self.revival_distribution_interval_secs))
+    }
+
+    /// Get the reputation ban duration as a Duration
+    pub fn ban_duration(&self) -> Duration {
+        Duration::from_secs(self.ban_duration_secs)
+    }
+
+    /// Get the fragment reassembly timeout as a Duration
+    pub fn fragment_timeout(&self) -> Duration {
+        Duration::from_secs(self.fragment_timeout_secs)
+    }
+
+    /// Payload bytes carried by each fragment of an oversized publish
+    pub fn fragment_size(&self) -> usize {
+        self.max_message_size
+            .saturating_sub(crate::fragment::ENVELOPE_OVERHEAD)
+    }
+
+    /// Delay before the next bootstrap dial after `attempts` failed attempts
+    ///
+    /// Doubles with each attempt, capped at `bootstrap_retry_max_secs`.
+    pub fn bootstrap_backoff(&self, attempts: u32) -> Duration {
+        let factor = 1u64 << attempts.saturating_sub(1).min(16);
+        let secs = self
+            .bootstrap_retry_initial_secs
+            .saturating_mul(factor)
+            .min(self.bootstrap_retry_max_secs);
+        Duration::from_secs(secs)
+    }
+
+    /// The publish rate limit for `topic`, if it has one
+    pub fn rate_limit_for(&self, topic: &str) -> Option<RateLimit> {
+        self.topic_rate_limits
+            .get(topic)
+            .copied()
+            .or(self.publish_rate_limit)
+    }
+
+    /// Peer ID and address of each explicit peer
+    pub fn explicit_peer_addresses(&self) -> Result<Vec<(libp2p::PeerId, libp2p::Multiaddr)>> {
+        self.explicit_peers
+            .iter()
+            .map(|addr_str| {
+                let addr = crate::transport::parse_multiaddr(addr_str)?;
+                let peer_id = crate::transport::extract_peer_id(&addr).ok_or_else(|| {
+                    NetworkError::Config(format!(
+                        "Explicit peer address '{}' has no /p2p peer ID",
+                        addr_str
+                    ))
+                })?;
+                Ok((peer_id, addr))
+            })
+            .collect()
+    }
+
+    /// Names of the enabled transports, e.g. `["tcp", "quic"]`
+    pub fn transports(&self) -> Vec<&'static str> {
+        [
+            (self.enable_tcp, "tcp"),
+            (self.enable_quic, "quic"),
+            (self.enable_webrtc, "webrtc"),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, name)| enabled.then_some(name))
+        .collect()
+    }
+
+    /// Check the configuration for obvious mistakes
+    ///
+    /// Ensures at least one transport is enabled, WebRTC listen addresses
+    /// have the WebRTC transport behind them, the connection timeouts and
+    /// ping settings are non-zero, the reputation thresholds are in range,
+    /// listen addresses parse, the gossipsub mesh sizes and timings are
+    /// consistent,
+    /// fragmented messages fit the fragment buffer, explicit peers carry a
+    /// peer ID and topic ACLs name valid peer IDs.
+    pub fn validate(&self) -> Result<()> {
+        if !self.enable_tcp && !self.enable_quic {
+            return Err(NetworkError::Config(
+                "At least one transport (TCP or QUIC) must be enabled".into(),
+            ));
+        }
+        if self.enable_webrtc && !cfg!(feature = "webrtc") {
+            return Err(NetworkError::Config(
+                "WebRTC transport requires the `webrtc` feature".into(),
+            ));
+        }
+        if self.enable_economics && !cfg!(feature = "univrs-compat") {
+            return Err(NetworkError::Config(
+                "Economics topics require the `univrs-compat` feature".into(),
+            ));
+        }
+        if !self.enable_webrtc
+            && self
+                .listen_addresses
+                .iter()
+                .any(|addr| addr.contains("/webrtc-direct"))
+        {
+            return Err(NetworkError::Config(
+                "WebRTC listen address configured but WebRTC transport is disabled".into(),
+            ));
+        }
+        if let Some(addr) = self
+            .listen_addresses
+            .iter()
+            .find(|addr| crate::transport::parse_multiaddr(addr).is_err())
+        {
+            return Err(NetworkError::Config(format!(
+                "Invalid listen address '{}'",
+                addr
+            )));
+        }
+        if self.handshake_timeout_secs == 0 || self.idle_timeout_secs == 0 {
+            return Err(NetworkError::Config(
+                "Handshake and idle timeouts must be non-zero".into(),
+            ));
+        }
+        if self.ping_interval_secs == 0 || self.ping_max_failures == 0 {
+            return Err(NetworkError::Config(
+                "ping_interval_secs and ping_max_failures must be non-zero".into(),
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.min_record_reputation) {
+            return Err(NetworkError::Config(
+                "min_record_reputation must be between 0.0 and 1.0".into(),
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.min_message_reputation) {
+            return Err(NetworkError::Config(
+                "min_message_reputation must be between 0.0 and 1.0".into(),
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.ban_reputation) {
+            return Err(NetworkError::Config(
+                "ban_reputation must be between 0.0 and 1.0".into(),
+            ));
+        }
+        if self.ban_reputation > 0.0 && self.ban_duration_secs == 0 {
+            return Err(NetworkError::Config(
+                "ban_duration_secs must be non-zero when ban_reputation is set".into(),
+            ));
+        }
+        if !(self.mesh_outbound_min <= self.mesh_n_low
+            && self.mesh_n_low <= self.mesh_n
+            && self.mesh_n <= self.mesh_n_high)
+        {
+            return Err(NetworkError::Config(
+                "Gossipsub mesh sizes must satisfy mesh_outbound_min <= mesh_n_low <= mesh_n <= mesh_n_high"
+                    .into(),
+            ));
+        }
+        if self.mesh_n == 0 || self.mesh_outbound_min * 2 > self.mesh_n {
+            return Err(NetworkError::Config(
+                "mesh_n must be non-zero and at least twice mesh_outbound_min".into(),
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.gossip_factor) {
+            return Err(NetworkError::Config(
+                "gossip_factor must be between 0.0 and 1.0".into(),
+            ));
+        }
+        if !(MIN_HEARTBEAT_INTERVAL_MS..=MAX_HEARTBEAT_INTERVAL_MS)
+            .contains(&self.heartbeat_interval_ms)
+        {
+            return Err(NetworkError::Config(format!(
+                "heartbeat_interval_ms must be between {} and {}",
+                MIN_HEARTBEAT_INTERVAL_MS, MAX_HEARTBEAT_INTERVAL_MS
+            )));
+        }
+        if self.history_gossip == 0 || self.history_gossip > self.history_length {
+            return Err(NetworkError::Config(
+                "Gossipsub history must satisfy 0 < history_gossip <= history_length".into(),
+            ));
+        }
+        if self.duplicate_cache_time() < self.heartbeat_interval() * self.history_length as u32 {
+            return Err(NetworkError::Config(
+                "duplicate_cache_time_secs must cover history_length heartbeats".into(),
+            ));
+        }
+        if self.max_fragmented_message_size > 0 {
+            if self.fragment_size() == 0 {
+                return Err(NetworkError::Config(format!(
+                    "max_message_size must exceed {} bytes to fragment messages",
+                    crate::fragment::ENVELOPE_OVERHEAD
+                )));
+            }
+            if self.max_fragmented_message_size > self.fragment_buffer_size {
+                return Err(NetworkError::Config(
+                    "fragment_buffer_size must hold at least one max_fragmented_message_size message"
+                        .into(),
+                ));
+            }
+            if self
+                .max_fragmented_message_size
+                .div_ceil(self.fragment_size())
+                > u16::MAX as usize
+            {
+                return Err(NetworkError::Config(
+                    "max_fragmented_message_size needs more than 65535 fragments".into(),
+                ));
+            }
+            if self.fragment_timeout_secs == 0 {
+                return Err(NetworkError::Config(
+                    "fragment_timeout_secs must be non-zero".into(),
+                ));
+            }
+        }
+        self.explicit_peer_addresses()?;
+        for (topic, acl) in &self.topic_acls {
+            if let Some(peer) = acl
+                .allowed_peers
+                .iter()
+                .find(|peer| peer.parse::<libp2p::PeerId>().is_err())
+            {
+                return Err(NetworkError::Config(format!(
+                    "Invalid peer ID '{}' in ACL for {}",
+                    peer, topic
+                )));
+            }
+            if acl
+                .min_reputation
+                .is_some_and(|min| !(0.0..=1.0).contains(&min))
+            {
+                return Err(NetworkError::Config(format!(
+                    "ACL min_reputation for {} must be between 0.0 and 1.0",
+                    topic
+                )));
+            }
+        }
+        let limits = self
+            .publish_rate_limit
+            .iter()
+            .map(|limit| ("publish_rate_limit", limit))
+            .chain(
+                self.topic_rate_limits
+                    .iter()
+                    .map(|(topic, limit)| (topic.as_str(), limit)),
+            );
+        for (name, limit) in limits {
+            if !(limit.per_second.is_finite() && limit.per_second > 0.0) || limit.burst == 0 {
+                return Err(NetworkError::Config(format!(
+                    "Rate limit for {} needs a positive per_second and a non-zero burst",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,202 @@
+//! Content exchange between peers
+//!
+//! Providers of a piece of content are found through Kademlia provider
+//! records. The content itself then moves over a request-response protocol,
+//! one fixed-size chunk per request, so large content never needs a single
+//! oversized message.
+//!
+//! What we serve is kept in a [`ContentStore`]: in memory by default, or one
+//! set with [`NetworkService::set_content_store`] that bounds it, e.g. the
+//! node's GC-managed SQLite store.
+//!
+//! [`NetworkService::set_content_store`]: crate::NetworkService::set_content_store
+
+use async_trait::async_trait;
+use libp2p::{
+    request_response::{self, ProtocolSupport},
+    StreamProtocol,
+};
+use mycelial_core::content::{Content, ContentId, ContentMetadata};
+use mycelial_core::ContentStore;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+/// Protocol name for content requests
+pub const CONTENT_PROTOCOL: &str = "/mycelial/content/1.0.0";
+
+/// Bytes of content carried by one response
+pub const CONTENT_CHUNK_SIZE: usize = 256 * 1024;
+
+/// Most chunks a fetch will accept (1 GiB of content)
+pub const MAX_CONTENT_CHUNKS: u32 = 4096;
+
+/// How long a provider has to answer one chunk request
+const CONTENT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Request for one chunk of a piece of content
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentRequest {
+    /// The content wanted
+    pub id: ContentId,
+    /// Index of the chunk wanted, starting at 0
+    pub chunk: u32,
+}
+
+/// A provider's answer to a [`ContentRequest`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ContentResponse {
+    /// One chunk of the content
+    Chunk {
+        /// Index of this chunk
+        index: u32,
+        /// Number of chunks in the content
+        total: u32,
+        /// Content type (MIME type) of the whole content
+        content_type: String,
+        /// Metadata of the whole content
+        metadata: ContentMetadata,
+        /// The chunk's bytes
+        data: Vec<u8>,
+    },
+    /// The provider doesn't have the content, or the chunk is out of range
+    NotFound,
+}
+
+/// Number of chunks `len` bytes of content split into
+///
+/// Empty content is still sent as one (empty) chunk.
+pub fn chunk_count(len: usize) -> u32 {
+    len.div_ceil(CONTENT_CHUNK_SIZE).max(1) as u32
+}
+
+/// Answer a request for a chunk of `content`
+pub fn chunk_response(content: &Content, index: u32) -> ContentResponse {
+    let total = chunk_count(content.data.len());
+    if index >= total {
+        return ContentResponse::NotFound;
+    }
+
+    let start = index as usize * CONTENT_CHUNK_SIZE;
+    let end = (start + CONTENT_CHUNK_SIZE).min(content.data.len());
+    ContentResponse::Chunk {
+        index,
+        total,
+        content_type: content.content_type.clone(),
+        metadata: content.metadata.clone(),
+        data: content.data[start..end].to_vec(),
+    }
+}
+
+/// DHT key under which providers of a piece of content are announced
+pub fn provider_key(id: &ContentId) -> libp2p::kad::RecordKey {
+    libp2p::kad::RecordKey::new(&id.to_bytes())
+}
+
+/// Content kept in memory, never evicted
+#[derive(Debug, Default)]
+pub struct MemoryContentStore {
+    content: RwLock<HashMap<ContentId, Content>>,
+    pinned: RwLock<HashSet<ContentId>>,
+}
+
+#[async_trait]
+impl ContentStore for MemoryContentStore {
+    async fn put_content(&self, content: &Content) -> mycelial_core::Result<()> {
+        self.content.write().insert(content.id, content.clone());
+        Ok(())
+    }
+
+    async fn fetch_content(&self, id: &ContentId) -> mycelial_core::Result<Option<Content>> {
+        Ok(self.content.read().get(id).cloned())
+    }
+
+    async fn remove_content(&self, id: &ContentId) -> mycelial_core::Result<bool> {
+        self.pinned.write().remove(id);
+        Ok(self.content.write().remove(id).is_some())
+    }
+
+    async fn set_pinned(&self, id: &ContentId, pinned: bool) -> mycelial_core::Result<bool> {
+        if !self.content.read().contains_key(id) {
+            return Ok(false);
+        }
+        let mut pins = self.pinned.write();
+        if pinned {
+            pins.insert(*id);
+        } else {
+            pins.remove(id);
+        }
+        Ok(true)
+    }
+}
+
+/// Create the request-response behaviour for content exchange
+pub(crate) fn create_content_exchange(
+) -> request_response::cbor::Behaviour<ContentRequest, ContentResponse> {
+    request_response::cbor::Behaviour::new(
+        [(StreamProtocol::new(CONTENT_PROTOCOL), ProtocolSupport::Full)],
+        request_response::Config::default().with_request_timeout(CONTENT_REQUEST_TIMEOUT),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_count() {
+        assert_eq!(chunk_count(0), 1);
+        assert_eq!(chunk_count(1), 1);
+        assert_eq!(chunk_count(CONTENT_CHUNK_SIZE), 1);
+        assert_eq!(chunk_count(CONTENT_CHUNK_SIZE + 1), 2);
+    }
+
+    #[test]
+    fn test_chunk_response_splits_content() {
+        let content = Content::new(
+            vec![7u8; CONTENT_CHUNK_SIZE + 10],
+            "application/octet-stream",
+        );
+
+        let mut data = Vec::new();
+        for index in 0..2 {
+            match chunk_response(&content, index) {
+                ContentResponse::Chunk {
+                    index: i,
+                    total,
+                    data: chunk,
+                    ..
+                } => {
+                    assert_eq!(i, index);
+                    assert_eq!(total, 2);
+                    data.extend(chunk);
+                }
+                ContentResponse::NotFound => panic!("Expected chunk {}", index),
+            }
+        }
+        assert!(content.id.verify(&data));
+
+        assert!(matches!(
+            chunk_response(&content, 2),
+            ContentResponse::NotFound
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_memory_content_store() {
+        let store = MemoryContentStore::default();
+        let content = Content::text("hello");
+
+        assert!(!store.set_pinned(&content.id, true).await.unwrap());
+        store.put_content(&content).await.unwrap();
+        assert!(store.set_pinned(&content.id, true).await.unwrap());
+
+        let stored = store.fetch_content(&content.id).await.unwrap().unwrap();
+        assert_eq!(stored.as_str(), Some("hello"));
+
+        assert!(store.remove_content(&content.id).await.unwrap());
+        assert!(store.fetch_content(&content.id).await.unwrap().is_none());
+        assert!(!store.remove_content(&content.id).await.unwrap());
+    }
+}
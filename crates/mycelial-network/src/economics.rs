@@ -6,6 +6,7 @@
 //! - Governance: Proposals and voting
 //! - Resource: Resource sharing metrics
 
+use libp2p::PeerId;
 use mycelial_protocol::{topics, CreditMessage, GovernanceMessage, ResourceMessage, VouchMessage};
 use tokio::sync::broadcast;
 use tracing::{debug, warn};
@@ -27,6 +28,46 @@ pub enum EconomicsEvent {
     Resource(ResourceMessage),
 }
 
+impl EconomicsEvent {
+    /// Peer the message claims to be sent by, if it speaks for one
+    pub fn author(&self) -> Option<&str> {
+        match self {
+            Self::Vouch(msg) => msg.author(),
+            Self::Credit(msg) => msg.author(),
+            Self::Governance(msg) => msg.author(),
+            Self::Resource(msg) => msg.author(),
+        }
+    }
+
+    /// Check the message against the peer that signed it on gossipsub
+    ///
+    /// A message that names its author must come from that peer, so nobody
+    /// can vouch, vote or spend credit in someone else's name. Resource
+    /// receipts are checked against the consumer's signature instead, since
+    /// anyone may relay them.
+    ///
+    /// Messages without an author (reputation, proposal, pool and credit line
+    /// updates, transfer acks) pass from anyone, so receivers must not trust
+    /// the values they carry and should recompute them from local state.
+    pub fn verify_source(&self, source: Option<&PeerId>) -> std::result::Result<(), String> {
+        if let Self::Resource(ResourceMessage::Receipt(receipt)) = self {
+            return receipt
+                .verify()
+                .map_err(|e| format!("invalid receipt signature: {}", e));
+        }
+
+        match (self.author(), source) {
+            (None, _) => Ok(()),
+            (Some(author), Some(source)) if author == source.to_base58() => Ok(()),
+            (Some(author), Some(source)) => Err(format!(
+                "claims to be from {} but was signed by {}",
+                author, source
+            )),
+            (Some(author), None) => Err(format!("claims to be from {} but is unsigned", author)),
+        }
+    }
+}
+
 /// Handler for economics protocol messages
 pub struct EconomicsHandler {
     /// Network handle for publishing
@@ -162,7 +203,8 @@ pub fn economics_topics() -> &'static [&'static str] {
 mod tests {
     use super::*;
     use mycelial_protocol::{
-        CreateCreditLine, CreateProposal, ResourceContribution, ResourceType, VouchRequest,
+        CreateCreditLine, CreateProposal, CreditTransferAck, ResourceContribution, ResourceType,
+        VouchRequest,
     };
 
     #[test]
@@ -252,6 +294,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_verify_source() {
+        let alice = PeerId::random();
+        let mallory = PeerId::random();
+        let event = EconomicsEvent::Vouch(VouchMessage::VouchRequest(VouchRequest::new(
+            alice.to_base58(),
+            "bob".to_string(),
+            0.5,
+        )));
+
+        assert!(event.verify_source(Some(&alice)).is_ok());
+        assert!(event.verify_source(Some(&mallory)).is_err());
+        assert!(event.verify_source(None).is_err());
+
+        // Messages that don't speak for a peer pass whoever relays them
+        let update = EconomicsEvent::Credit(CreditMessage::TransferAck(CreditTransferAck {
+            transfer_id: uuid::Uuid::new_v4(),
+            success: true,
+            new_balance: None,
+            error: None,
+            timestamp: chrono::Utc::now(),
+        }));
+        assert!(update.verify_source(Some(&mallory)).is_ok());
+    }
+
     #[test]
     fn test_parse_invalid_topic() {
         let data = b"some data";
@@ -3,23 +3,36 @@
 //! MVP implementation uses a local HashMap as the ledger.
 //! Transfers are broadcast via gossip and applied optimistically.
 //! Full consensus (OpenRaft) deferred to Phase 3+.
+//!
+//! Each sender numbers its transfers with a strictly increasing nonce and
+//! receivers drop anything at or below the last nonce they accepted. With
+//! [`CreditSynchronizer::with_nonce_store`] the nonce table is written to
+//! disk, so replays stay rejected after a restart.
 
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, oneshot, RwLock};
 use tracing::{debug, info, warn};
-use univrs_enr::{
-    core::{AccountId, CreditTransfer, Credits, NodeId, Timestamp},
-    revival::calculate_entropy_tax,
-};
+use univrs_enr::core::{AccountId, CreditTransfer, Credits, NodeId, Timestamp};
 
 use crate::enr_bridge::messages::{
     BalanceQueryMsg, BalanceResponseMsg, CreditTransferMsg, EnrMessage, CREDIT_TOPIC,
 };
+use crate::enr_bridge::signing::{self, SignatureError, SigningKey};
 
 /// Initial credit grant for new nodes
 pub const INITIAL_NODE_CREDITS: u64 = 1000;
 
+/// How long a balance query waits for its response
+pub const BALANCE_QUERY_TIMEOUT_MS: u64 = 5_000;
+
+/// Entropy tax rate applied when none is configured (2% per ENR spec)
+pub const DEFAULT_ENTROPY_TAX_RATE: f64 = 0.02;
+
 /// Callback type for publishing to gossipsub
 pub type PublishFn = Box<dyn Fn(String, Vec<u8>) -> Result<(), String> + Send + Sync>;
 
@@ -27,6 +40,8 @@ pub type PublishFn = Box<dyn Fn(String, Vec<u8>) -> Result<(), String> + Send +
 pub struct CreditSynchronizer {
     /// This node's ID
     local_node: NodeId,
+    /// Key that signs our transfers
+    signing_key: SigningKey,
     /// Local ledger: AccountId -> balance
     ledger: Arc<RwLock<HashMap<AccountId, Credits>>>,
     /// Processed transfer nonces (replay protection)
@@ -35,14 +50,72 @@ pub struct CreditSynchronizer {
     next_nonce: Arc<RwLock<u64>>,
     /// Callback to publish to gossipsub
     publish_fn: PublishFn,
+    /// Outstanding balance queries: request_id -> waiting caller
+    pending_queries: Arc<RwLock<HashMap<u64, PendingQuery>>>,
+    /// How long a balance query waits for its response
+    query_timeout: Duration,
+    /// On-disk copy of the nonce table, if persistence is enabled
+    nonce_store: Option<NonceStore>,
+    /// Share of each outgoing transfer paid to the revival pool
+    tax_rate: Arc<RwLock<EntropyTaxRate>>,
+    /// Transfers applied to the ledger, ours and others'
+    events: broadcast::Sender<CreditTransferMsg>,
+}
+
+/// Fraction of each transfer paid to the revival pool, in `[0, 1)`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EntropyTaxRate(f64);
+
+impl EntropyTaxRate {
+    /// Validate a tax rate
+    pub fn new(rate: f64) -> Result<Self, InvalidTaxRate> {
+        if (0.0..1.0).contains(&rate) {
+            Ok(Self(rate))
+        } else {
+            Err(InvalidTaxRate(rate))
+        }
+    }
+
+    /// The rate as a fraction
+    pub fn get(self) -> f64 {
+        self.0
+    }
+
+    /// Tax owed on a transfer of `amount`, rounded down
+    ///
+    /// Worked out in parts per million so the result doesn't depend on how
+    /// the float product happens to round.
+    pub fn tax(self, amount: Credits) -> Credits {
+        let ppm = (self.0 * 1_000_000.0).round() as u128;
+        Credits::new((u128::from(amount.amount) * ppm / 1_000_000) as u64)
+    }
+}
+
+impl Default for EntropyTaxRate {
+    fn default() -> Self {
+        Self(DEFAULT_ENTROPY_TAX_RATE)
+    }
+}
+
+/// A balance query awaiting its response
+struct PendingQuery {
+    /// Node that was queried
+    target: NodeId,
+    /// Resolved with the balance when the response arrives
+    sender: oneshot::Sender<Credits>,
+    /// When the query was sent
+    sent_at: Instant,
 }
 
 impl CreditSynchronizer {
     /// Create a new credit synchronizer with initial balance
-    pub fn new<F>(local_node: NodeId, publish_fn: F) -> Self
+    ///
+    /// The local node ID is the public half of `signing_key`.
+    pub fn new<F>(signing_key: SigningKey, publish_fn: F) -> Self
     where
         F: Fn(String, Vec<u8>) -> Result<(), String> + Send + Sync + 'static,
     {
+        let local_node = signing::node_id(&signing_key);
         let mut ledger = HashMap::new();
         // Initialize local node with starting credits
         let local_account = AccountId::node_account(local_node);
@@ -56,11 +129,76 @@ impl CreditSynchronizer {
 
         Self {
             local_node,
+            signing_key,
             ledger: Arc::new(RwLock::new(ledger)),
             processed_nonces: Arc::new(RwLock::new(HashMap::new())),
             next_nonce: Arc::new(RwLock::new(1)),
             publish_fn: Box::new(publish_fn),
+            pending_queries: Arc::new(RwLock::new(HashMap::new())),
+            query_timeout: Duration::from_millis(BALANCE_QUERY_TIMEOUT_MS),
+            nonce_store: None,
+            tax_rate: Arc::new(RwLock::new(EntropyTaxRate::default())),
+            events: broadcast::channel(64).0,
+        }
+    }
+
+    /// Keep the nonce table in a JSON file at `path`
+    ///
+    /// Nonces saved by a previous run are loaded, so transfers accepted
+    /// before a restart are still rejected as replays and our own outgoing
+    /// nonces keep increasing. A missing file starts an empty table.
+    pub fn with_nonce_store(mut self, path: impl AsRef<Path>) -> Result<Self, NonceStoreError> {
+        let store = NonceStore::open(path.as_ref())?;
+        {
+            let state = store.state.lock();
+            self.processed_nonces = Arc::new(RwLock::new(state.peers.iter().copied().collect()));
+            self.next_nonce = Arc::new(RwLock::new(state.next_nonce.max(1)));
         }
+        self.nonce_store = Some(store);
+        Ok(self)
+    }
+
+    /// Set how long balance queries wait for a response
+    pub fn with_query_timeout(mut self, timeout: Duration) -> Self {
+        self.query_timeout = timeout;
+        self
+    }
+
+    /// Charge `rate` on outgoing transfers instead of the default
+    pub fn with_entropy_tax_rate(mut self, rate: EntropyTaxRate) -> Self {
+        self.tax_rate = Arc::new(RwLock::new(rate));
+        self
+    }
+
+    /// The entropy tax rate charged on outgoing transfers
+    pub async fn entropy_tax_rate(&self) -> EntropyTaxRate {
+        *self.tax_rate.read().await
+    }
+
+    /// Change the entropy tax rate, e.g. after a governance vote
+    ///
+    /// Only outgoing transfers are affected: incoming ones carry the tax
+    /// their sender charged.
+    pub async fn set_entropy_tax_rate(&self, rate: EntropyTaxRate) {
+        let previous = std::mem::replace(&mut *self.tax_rate.write().await, rate);
+        info!(
+            from = previous.get(),
+            to = rate.get(),
+            "Changed entropy tax rate"
+        );
+    }
+
+    /// Subscribe to transfers as they are applied to the ledger
+    ///
+    /// Covers our own transfers and incoming ones that passed signature and
+    /// replay checks; rejected transfers never show up.
+    pub fn subscribe_events(&self) -> broadcast::Receiver<CreditTransferMsg> {
+        self.events.subscribe()
+    }
+
+    /// This node's ID
+    pub fn local_node(&self) -> NodeId {
+        self.local_node
     }
 
     /// Get balance for an account
@@ -92,8 +230,7 @@ impl CreditSynchronizer {
         let from_account = AccountId::node_account(self.local_node);
         let to_account = AccountId::node_account(to);
 
-        // Calculate entropy tax (2% per ENR spec)
-        let entropy_cost = calculate_entropy_tax(amount);
+        let entropy_cost = self.entropy_tax_rate().await.tax(amount);
         let total_cost = amount.saturating_add(entropy_cost);
 
         // Check and debit balance atomically
@@ -129,16 +266,21 @@ impl CreditSynchronizer {
             *n += 1;
             current
         };
+        if let Some(store) = &self.nonce_store {
+            store.record_outgoing(nonce + 1);
+        }
 
-        let msg = CreditTransferMsg {
+        let mut msg = CreditTransferMsg {
             transfer: transfer.clone(),
             nonce,
-            signature: vec![], // TODO: Sign with Ed25519
+            signature: vec![],
         };
+        msg.sign(&self.signing_key).map_err(TransferError::Encode)?;
 
-        let envelope = EnrMessage::CreditTransfer(msg);
+        let envelope = EnrMessage::CreditTransfer(msg.clone());
         let bytes = envelope.encode().map_err(TransferError::Encode)?;
         (self.publish_fn)(CREDIT_TOPIC.to_string(), bytes).map_err(TransferError::Publish)?;
+        let _ = self.events.send(msg);
 
         info!(
             to = %to,
@@ -159,6 +301,31 @@ impl CreditSynchronizer {
             return Ok(());
         }
 
+        // Verify before touching nonces so forgeries can't burn them
+        if let Err(e) = msg.verify() {
+            warn!(
+                from = %transfer.from.node,
+                error = %e,
+                "Rejecting unsigned or forged transfer"
+            );
+            return Err(HandleTransferError::InvalidSignature(e));
+        }
+
+        // Governance applies rate changes per node, so peers may charge a
+        // different rate than ours; only reject a tax no rate below 1 yields
+        if transfer.entropy_cost.amount > transfer.amount.amount {
+            warn!(
+                from = %transfer.from.node,
+                paid = transfer.entropy_cost.amount,
+                amount = transfer.amount.amount,
+                "Rejecting transfer whose entropy tax exceeds its amount"
+            );
+            return Err(HandleTransferError::ExcessiveTax {
+                paid: transfer.entropy_cost,
+                amount: transfer.amount,
+            });
+        }
+
         // Check for replay
         {
             let mut nonces = self.processed_nonces.write().await;
@@ -174,11 +341,12 @@ impl CreditSynchronizer {
             }
             nonces.insert(transfer.from.node, msg.nonce);
         }
-
-        // TODO: Verify signature
+        if let Some(store) = &self.nonce_store {
+            store.record_incoming(transfer.from.node, msg.nonce);
+        }
 
         // Apply transfer optimistically
-        // In MVP, we trust broadcasts. Consensus comes in Phase 3+.
+        // In MVP, we trust signed broadcasts. Consensus comes in Phase 3+.
         // Credit receiver if this transfer is TO us
         if transfer.to.node == self.local_node {
             let mut ledger = self.ledger.write().await;
@@ -223,6 +391,7 @@ impl CreditSynchronizer {
             );
         }
 
+        let _ = self.events.send(msg);
         Ok(())
     }
 
@@ -257,6 +426,97 @@ impl CreditSynchronizer {
         Ok(())
     }
 
+    /// Ask a remote node for its balance and wait for the answer
+    ///
+    /// Publishes a `BalanceQuery` and resolves when the matching
+    /// `BalanceResponse` is passed to [`handle_balance_response`](Self::handle_balance_response).
+    pub async fn query_balance(&self, target: NodeId) -> Result<Credits, QueryError> {
+        if target == self.local_node {
+            return Ok(self.local_balance().await);
+        }
+
+        let request_id = rand::random();
+        let (sender, receiver) = oneshot::channel();
+        self.pending_queries.write().await.insert(
+            request_id,
+            PendingQuery {
+                target,
+                sender,
+                sent_at: Instant::now(),
+            },
+        );
+
+        let query = BalanceQueryMsg {
+            requester: self.local_node,
+            target,
+            request_id,
+        };
+        let published = EnrMessage::BalanceQuery(query)
+            .encode()
+            .map_err(QueryError::Encode)
+            .and_then(|bytes| {
+                (self.publish_fn)(CREDIT_TOPIC.to_string(), bytes).map_err(QueryError::Publish)
+            });
+        if let Err(e) = published {
+            self.pending_queries.write().await.remove(&request_id);
+            return Err(e);
+        }
+
+        debug!(target = %target, request_id, "Sent balance query");
+
+        match tokio::time::timeout(self.query_timeout, receiver).await {
+            Ok(Ok(balance)) => Ok(balance),
+            // Sender dropped by pruning
+            Ok(Err(_)) | Err(_) => {
+                self.pending_queries.write().await.remove(&request_id);
+                Err(QueryError::Timeout {
+                    target,
+                    timeout_ms: self.query_timeout.as_millis() as u64,
+                })
+            }
+        }
+    }
+
+    /// Resolve the pending query a balance response answers
+    ///
+    /// Returns false if no query with that request id is outstanding, which
+    /// is the normal case for responses to other nodes' queries.
+    pub async fn handle_balance_response(&self, response: BalanceResponseMsg) -> bool {
+        let Some(pending) = self
+            .pending_queries
+            .write()
+            .await
+            .remove(&response.request_id)
+        else {
+            return false;
+        };
+
+        debug!(
+            target = %pending.target,
+            request_id = response.request_id,
+            balance = response.balance.amount,
+            "Received balance response"
+        );
+        // The caller may have given up already
+        let _ = pending.sender.send(response.balance);
+        true
+    }
+
+    /// Drop queries that have outlived the timeout or whose caller went away
+    ///
+    /// Returns the number of queries removed.
+    pub async fn prune_stale_queries(&self) -> usize {
+        let mut pending = self.pending_queries.write().await;
+        let before = pending.len();
+        pending.retain(|_, q| q.sent_at.elapsed() < self.query_timeout && !q.sender.is_closed());
+        before - pending.len()
+    }
+
+    /// Number of balance queries awaiting a response
+    pub async fn pending_query_count(&self) -> usize {
+        self.pending_queries.read().await.len()
+    }
+
     /// Ensure account exists with minimum balance (for new nodes joining)
     pub async fn ensure_account(&self, node: NodeId) {
         let account = AccountId::node_account(node);
@@ -281,6 +541,88 @@ impl CreditSynchronizer {
     }
 }
 
+/// Nonce table as written to disk
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedNonces {
+    /// Next nonce for our own transfers
+    next_nonce: u64,
+    /// Highest accepted nonce per sender
+    peers: Vec<(NodeId, u64)>,
+}
+
+/// JSON file holding the nonce table
+///
+/// The in-memory copy and the file are updated together under one lock, so
+/// concurrent writers can neither interleave nor move a nonce backwards.
+struct NonceStore {
+    path: PathBuf,
+    state: Mutex<PersistedNonces>,
+}
+
+impl NonceStore {
+    /// Load the table at `path`, or start empty if there is none
+    fn open(path: &Path) -> Result<Self, NonceStoreError> {
+        let state = match std::fs::read(path) {
+            Ok(json) => {
+                serde_json::from_slice(&json).map_err(|e| NonceStoreError::Format(e.to_string()))?
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => PersistedNonces::default(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            state: Mutex::new(state),
+        })
+    }
+
+    /// Record that our next outgoing transfer will use `next_nonce`
+    fn record_outgoing(&self, next_nonce: u64) {
+        let mut state = self.state.lock();
+        state.next_nonce = state.next_nonce.max(next_nonce);
+        self.save(&state);
+    }
+
+    /// Record the highest nonce accepted from `sender`
+    fn record_incoming(&self, sender: NodeId, nonce: u64) {
+        let mut state = self.state.lock();
+        match state.peers.iter_mut().find(|(node, _)| *node == sender) {
+            Some((_, last)) => *last = (*last).max(nonce),
+            None => state.peers.push((sender, nonce)),
+        }
+        self.save(&state);
+    }
+
+    /// Replace the file atomically with `state`
+    ///
+    /// A failed write is logged rather than returned: the transfer has
+    /// already been applied, and the next successful write catches up.
+    fn save(&self, state: &PersistedNonces) {
+        let result = serde_json::to_vec(state)
+            .map_err(|e| NonceStoreError::Format(e.to_string()))
+            .and_then(|json| {
+                let tmp_path = self.path.with_extension("tmp");
+                std::fs::write(&tmp_path, json)?;
+                std::fs::rename(&tmp_path, &self.path)?;
+                Ok(())
+            });
+        if let Err(e) = result {
+            warn!(path = %self.path.display(), error = %e, "Failed to persist nonce table");
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum NonceStoreError {
+    #[error("Nonce table I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Malformed nonce table: {0}")]
+    Format(String),
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Entropy tax rate must be in [0, 1), got {0}")]
+pub struct InvalidTaxRate(pub f64);
+
 #[derive(Debug, thiserror::Error)]
 pub enum TransferError {
     #[error("Cannot transfer zero credits")]
@@ -302,8 +644,10 @@ pub enum TransferError {
 pub enum HandleTransferError {
     #[error("Replayed nonce")]
     ReplayedNonce,
-    #[error("Invalid signature")]
-    InvalidSignature,
+    #[error("Entropy tax {paid} exceeds transfer amount {amount}")]
+    ExcessiveTax { paid: Credits, amount: Credits },
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(#[from] SignatureError),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -314,6 +658,16 @@ pub enum HandleQueryError {
     Publish(String),
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum QueryError {
+    #[error("Encoding error: {0}")]
+    Encode(#[from] crate::enr_bridge::messages::EncodeError),
+    #[error("Publish error: {0}")]
+    Publish(String),
+    #[error("No balance response from {target} within {timeout_ms}ms")]
+    Timeout { target: NodeId, timeout_ms: u64 },
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -332,11 +686,17 @@ mod tests {
         (f, counter)
     }
 
+    fn node(seed: u8) -> (SigningKey, NodeId) {
+        let key = SigningKey::from_bytes(&[seed; 32]);
+        let id = signing::node_id(&key);
+        (key, id)
+    }
+
     #[tokio::test]
     async fn test_initial_balance() {
-        let node = NodeId::from_bytes([1u8; 32]);
+        let key = node(1).0;
         let (publish, _) = mock_publish();
-        let sync = CreditSynchronizer::new(node, publish);
+        let sync = CreditSynchronizer::new(key, publish);
 
         let balance = sync.local_balance().await;
         assert_eq!(balance.amount, INITIAL_NODE_CREDITS);
@@ -344,10 +704,10 @@ mod tests {
 
     #[tokio::test]
     async fn test_transfer_success() {
-        let node1 = NodeId::from_bytes([1u8; 32]);
-        let node2 = NodeId::from_bytes([2u8; 32]);
+        let key1 = node(1).0;
+        let node2 = node(2).1;
         let (publish, counter) = mock_publish();
-        let sync = CreditSynchronizer::new(node1, publish);
+        let sync = CreditSynchronizer::new(key1, publish);
 
         // Transfer 100 credits
         let transfer = sync.transfer(node2, Credits::new(100)).await.unwrap();
@@ -364,12 +724,43 @@ mod tests {
         assert_eq!(balance.amount, 898);
     }
 
+    #[tokio::test]
+    async fn test_entropy_tax_rate() {
+        assert!(EntropyTaxRate::new(0.0).is_ok());
+        assert!(EntropyTaxRate::new(1.0).is_err());
+        assert!(EntropyTaxRate::new(-0.01).is_err());
+        assert!(EntropyTaxRate::new(f64::NAN).is_err());
+        assert_eq!(
+            EntropyTaxRate::new(0.29)
+                .unwrap()
+                .tax(Credits::new(100))
+                .amount,
+            29
+        );
+
+        let key1 = node(1).0;
+        let node2 = node(2).1;
+        let (publish, _) = mock_publish();
+        let sync = CreditSynchronizer::new(key1, publish)
+            .with_entropy_tax_rate(EntropyTaxRate::new(0.05).unwrap());
+        assert_eq!(sync.entropy_tax_rate().await.get(), 0.05);
+
+        let transfer = sync.transfer(node2, Credits::new(100)).await.unwrap();
+        assert_eq!(transfer.entropy_cost.amount, 5);
+
+        sync.set_entropy_tax_rate(EntropyTaxRate::new(0.0).unwrap())
+            .await;
+        let transfer = sync.transfer(node2, Credits::new(100)).await.unwrap();
+        assert_eq!(transfer.entropy_cost.amount, 0);
+        assert_eq!(sync.local_balance().await.amount, 1000 - 105 - 100);
+    }
+
     #[tokio::test]
     async fn test_transfer_insufficient() {
-        let node1 = NodeId::from_bytes([1u8; 32]);
-        let node2 = NodeId::from_bytes([2u8; 32]);
+        let key1 = node(1).0;
+        let node2 = node(2).1;
         let (publish, _) = mock_publish();
-        let sync = CreditSynchronizer::new(node1, publish);
+        let sync = CreditSynchronizer::new(key1, publish);
 
         // Try to transfer more than we have
         let result = sync.transfer(node2, Credits::new(2000)).await;
@@ -385,10 +776,10 @@ mod tests {
 
     #[tokio::test]
     async fn test_transfer_zero() {
-        let node1 = NodeId::from_bytes([1u8; 32]);
-        let node2 = NodeId::from_bytes([2u8; 32]);
+        let key1 = node(1).0;
+        let node2 = node(2).1;
         let (publish, _) = mock_publish();
-        let sync = CreditSynchronizer::new(node1, publish);
+        let sync = CreditSynchronizer::new(key1, publish);
 
         let result = sync.transfer(node2, Credits::ZERO).await;
         assert!(matches!(result, Err(TransferError::ZeroAmount)));
@@ -396,9 +787,9 @@ mod tests {
 
     #[tokio::test]
     async fn test_transfer_self() {
-        let node = NodeId::from_bytes([1u8; 32]);
+        let (key, node) = node(1);
         let (publish, _) = mock_publish();
-        let sync = CreditSynchronizer::new(node, publish);
+        let sync = CreditSynchronizer::new(key, publish);
 
         let result = sync.transfer(node, Credits::new(100)).await;
         assert!(matches!(result, Err(TransferError::SelfTransfer)));
@@ -406,10 +797,10 @@ mod tests {
 
     #[tokio::test]
     async fn test_handle_incoming_transfer() {
-        let node1 = NodeId::from_bytes([1u8; 32]);
-        let node2 = NodeId::from_bytes([2u8; 32]);
+        let (key1, node1) = node(1);
+        let (key2, node2) = node(2);
         let (publish, _) = mock_publish();
-        let sync = CreditSynchronizer::new(node1, publish);
+        let sync = CreditSynchronizer::new(key1, publish);
 
         // Simulate incoming transfer from node2 to node1
         let transfer = CreditTransfer::new(
@@ -419,11 +810,12 @@ mod tests {
             Credits::new(1), // tax
         );
 
-        let msg = CreditTransferMsg {
+        let mut msg = CreditTransferMsg {
             transfer,
             nonce: 1,
             signature: vec![],
         };
+        msg.sign(&key2).unwrap();
 
         // Ensure node2 has balance first
         sync.ensure_account(node2).await;
@@ -437,10 +829,11 @@ mod tests {
 
     #[tokio::test]
     async fn test_replay_protection() {
-        let node1 = NodeId::from_bytes([1u8; 32]);
-        let node2 = NodeId::from_bytes([2u8; 32]);
+        let (key1, node1) = node(1);
+        let (key2, node2) = node(2);
         let (publish, _) = mock_publish();
-        let sync = CreditSynchronizer::new(node1, publish);
+        let sync = CreditSynchronizer::new(key1, publish);
+        let mut events = sync.subscribe_events();
 
         sync.ensure_account(node2).await;
 
@@ -451,17 +844,189 @@ mod tests {
             Credits::new(1),
         );
 
-        let msg = CreditTransferMsg {
+        let mut msg = CreditTransferMsg {
             transfer: transfer.clone(),
             nonce: 1,
             signature: vec![],
         };
+        msg.sign(&key2).unwrap();
 
         // First should succeed
         sync.handle_transfer(msg.clone()).await.unwrap();
+        assert_eq!(sync.local_balance().await.amount, INITIAL_NODE_CREDITS + 50);
+
+        // Replay should fail and leave the ledger alone
+        let result = sync.handle_transfer(msg).await;
+        assert!(matches!(result, Err(HandleTransferError::ReplayedNonce)));
+        assert_eq!(sync.local_balance().await.amount, INITIAL_NODE_CREDITS + 50);
+
+        // Only the applied transfer is announced
+        assert_eq!(events.try_recv().unwrap().nonce, 1);
+        assert!(events.try_recv().is_err());
+    }
 
-        // Replay should fail
+    #[tokio::test]
+    async fn test_nonces_survive_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nonces.json");
+        let (key1, node1) = node(1);
+        let (key2, node2) = node(2);
+
+        let mut msg = CreditTransferMsg {
+            transfer: CreditTransfer::new(
+                AccountId::node_account(node2),
+                AccountId::node_account(node1),
+                Credits::new(50),
+                Credits::new(1),
+            ),
+            nonce: 1,
+            signature: vec![],
+        };
+        msg.sign(&key2).unwrap();
+
+        {
+            let (publish, _) = mock_publish();
+            let sync = CreditSynchronizer::new(key1.clone(), publish)
+                .with_nonce_store(&path)
+                .unwrap();
+            sync.ensure_account(node2).await;
+            sync.handle_transfer(msg.clone()).await.unwrap();
+            sync.transfer(node2, Credits::new(10)).await.unwrap();
+        }
+
+        // After a restart the same message is still a replay
+        let (publish, _) = mock_publish();
+        let sync = CreditSynchronizer::new(key1, publish)
+            .with_nonce_store(&path)
+            .unwrap();
+        sync.ensure_account(node2).await;
         let result = sync.handle_transfer(msg).await;
         assert!(matches!(result, Err(HandleTransferError::ReplayedNonce)));
+        assert_eq!(sync.local_balance().await.amount, INITIAL_NODE_CREDITS);
+
+        // And our own nonces carry on from where they stopped
+        assert_eq!(*sync.next_nonce.read().await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_balance_query_times_out() {
+        let key1 = node(1).0;
+        let node2 = node(2).1;
+        let (publish, counter) = mock_publish();
+        let sync =
+            CreditSynchronizer::new(key1, publish).with_query_timeout(Duration::from_millis(20));
+
+        // Nobody answers
+        let result = sync.query_balance(node2).await;
+        assert!(matches!(result, Err(QueryError::Timeout { .. })));
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+        assert_eq!(sync.pending_query_count().await, 0);
+
+        // A late response is ignored
+        let late = BalanceResponseMsg {
+            request_id: 42,
+            balance: Credits::new(7),
+            as_of: Timestamp::now(),
+        };
+        assert!(!sync.handle_balance_response(late).await);
+    }
+
+    #[tokio::test]
+    async fn test_reject_tampered_transfer() {
+        let (key1, node1) = node(1);
+        let (key2, node2) = node(2);
+        let (publish, _) = mock_publish();
+        let sync = CreditSynchronizer::new(key1, publish);
+        sync.ensure_account(node2).await;
+
+        let mut msg = CreditTransferMsg {
+            transfer: CreditTransfer::new(
+                AccountId::node_account(node2),
+                AccountId::node_account(node1),
+                Credits::new(50),
+                Credits::new(1),
+            ),
+            nonce: 1,
+            signature: vec![],
+        };
+
+        // Unsigned transfers are rejected
+        let result = sync.handle_transfer(msg.clone()).await;
+        assert!(matches!(
+            result,
+            Err(HandleTransferError::InvalidSignature(
+                SignatureError::Missing
+            ))
+        ));
+
+        // Inflating the amount after signing breaks the signature
+        msg.sign(&key2).unwrap();
+        msg.transfer.amount = Credits::new(500);
+        let result = sync.handle_transfer(msg.clone()).await;
+        assert!(matches!(
+            result,
+            Err(HandleTransferError::InvalidSignature(
+                SignatureError::Invalid
+            ))
+        ));
+        assert_eq!(sync.local_balance().await.amount, INITIAL_NODE_CREDITS);
+
+        // The forgery did not consume the nonce
+        msg.transfer.amount = Credits::new(50);
+        sync.handle_transfer(msg).await.unwrap();
+        assert_eq!(sync.local_balance().await.amount, INITIAL_NODE_CREDITS + 50);
+
+        // Signed transfers taxing more than they move are rejected too
+        let mut overtaxed = CreditTransferMsg {
+            transfer: CreditTransfer::new(
+                AccountId::node_account(node2),
+                AccountId::node_account(node1),
+                Credits::new(100),
+                Credits::new(101),
+            ),
+            nonce: 2,
+            signature: vec![],
+        };
+        overtaxed.sign(&key2).unwrap();
+        let result = sync.handle_transfer(overtaxed).await;
+        assert!(matches!(
+            result,
+            Err(HandleTransferError::ExcessiveTax { paid, amount })
+                if paid.amount == 101 && amount.amount == 100
+        ));
+        assert_eq!(sync.local_balance().await.amount, INITIAL_NODE_CREDITS + 50);
+    }
+
+    #[tokio::test]
+    async fn test_transfer_between_different_tax_rates() {
+        let (key1, node1) = node(1);
+        let (key2, node2) = node(2);
+        let (publish1, _) = mock_publish();
+        let (publish2, _) = mock_publish();
+        let sender = CreditSynchronizer::new(key1, publish1)
+            .with_entropy_tax_rate(EntropyTaxRate::new(0.0).unwrap());
+        let receiver = CreditSynchronizer::new(key2, publish2)
+            .with_entropy_tax_rate(EntropyTaxRate::new(0.05).unwrap());
+
+        let transfer = sender.transfer(node2, Credits::new(100)).await.unwrap();
+        assert!(transfer.entropy_cost.is_zero());
+
+        let mut msg = CreditTransferMsg {
+            transfer,
+            nonce: 1,
+            signature: vec![],
+        };
+        msg.sign(&key1).unwrap();
+        receiver.ensure_account(node1).await;
+        receiver.handle_transfer(msg).await.unwrap();
+
+        assert_eq!(
+            receiver.local_balance().await.amount,
+            INITIAL_NODE_CREDITS + 100
+        );
+        assert_eq!(
+            sender.local_balance().await.amount,
+            INITIAL_NODE_CREDITS - 100
+        );
     }
 }
@@ -2,8 +2,12 @@
 //!
 //! Broadcasts local resource availability and aggregates
 //! gradients from other nodes in the network.
+//!
+//! The last [`GRADIENT_HISTORY_LEN`] gradients from each node are kept so
+//! that [`GradientBroadcaster::trend`] can report whether each resource is
+//! becoming more or less available, not just where it stands now.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, warn};
@@ -13,6 +17,7 @@ use univrs_enr::{
 };
 
 use crate::enr_bridge::messages::{EnrMessage, GradientUpdate, GRADIENT_TOPIC};
+use crate::enr_bridge::signing::{self, SignatureError, SigningKey};
 
 /// Maximum age of gradient before considered stale (15 seconds)
 pub const MAX_GRADIENT_AGE_MS: u64 = 15_000;
@@ -20,6 +25,9 @@ pub const MAX_GRADIENT_AGE_MS: u64 = 15_000;
 /// Maximum clock drift tolerance (5 seconds into future)
 pub const MAX_FUTURE_TOLERANCE_MS: u64 = 5_000;
 
+/// Number of past gradients kept per node for trend reporting
+pub const GRADIENT_HISTORY_LEN: usize = 16;
+
 /// Callback type for publishing to gossipsub
 pub type PublishFn = Box<dyn Fn(String, Vec<u8>) -> Result<(), String> + Send + Sync>;
 
@@ -27,21 +35,27 @@ pub type PublishFn = Box<dyn Fn(String, Vec<u8>) -> Result<(), String> + Send +
 pub struct GradientBroadcaster {
     /// This node's ID
     local_node: NodeId,
+    /// Key that signs our updates
+    signing_key: SigningKey,
     /// Received gradients from other nodes
     gradients: Arc<RwLock<HashMap<NodeId, GradientUpdate>>>,
+    /// Recent gradients per node, oldest first
+    history: Arc<RwLock<HashMap<NodeId, VecDeque<(Timestamp, ResourceGradient)>>>>,
     /// Callback to publish to gossipsub
     publish_fn: PublishFn,
 }
 
 impl GradientBroadcaster {
-    /// Create a new gradient broadcaster
-    pub fn new<F>(local_node: NodeId, publish_fn: F) -> Self
+    /// Create a new gradient broadcaster signing as `signing_key`
+    pub fn new<F>(signing_key: SigningKey, publish_fn: F) -> Self
     where
         F: Fn(String, Vec<u8>) -> Result<(), String> + Send + Sync + 'static,
     {
         Self {
-            local_node,
+            local_node: signing::node_id(&signing_key),
+            signing_key,
             gradients: Arc::new(RwLock::new(HashMap::new())),
+            history: Arc::new(RwLock::new(HashMap::new())),
             publish_fn: Box::new(publish_fn),
         }
     }
@@ -53,12 +67,15 @@ impl GradientBroadcaster {
             return Err(BroadcastError::InvalidGradient);
         }
 
-        let update = GradientUpdate {
+        let mut update = GradientUpdate {
             source: self.local_node,
             gradient,
             timestamp: Timestamp::now(),
-            signature: vec![], // TODO: Sign with Ed25519
+            signature: vec![],
         };
+        update
+            .sign(&self.signing_key)
+            .map_err(BroadcastError::Encode)?;
 
         let msg = EnrMessage::GradientUpdate(update);
         let bytes = msg.encode().map_err(BroadcastError::Encode)?;
@@ -93,10 +110,10 @@ impl GradientBroadcaster {
             return Err(HandleError::TooOld);
         }
 
-        // TODO: Verify signature
-        // if !verify_signature(&update) {
-        //     return Err(HandleError::InvalidSignature);
-        // }
+        if let Err(e) = update.verify() {
+            warn!(source = %update.source, error = %e, "Rejecting unsigned or forged gradient");
+            return Err(HandleError::InvalidSignature(e));
+        }
 
         let mut gradients = self.gradients.write().await;
 
@@ -114,47 +131,132 @@ impl GradientBroadcaster {
             "Received gradient update"
         );
 
+        {
+            let mut history = self.history.write().await;
+            let samples = history.entry(update.source).or_default();
+            if samples.len() == GRADIENT_HISTORY_LEN {
+                samples.pop_front();
+            }
+            samples.push_back((update.timestamp, update.gradient));
+        }
+
         gradients.insert(update.source, update);
         Ok(())
     }
 
+    /// How a node's resource availability has been changing
+    ///
+    /// Slopes are fitted over the node's recent gradient history. A node
+    /// with fewer than two samples has a flat trend.
+    pub async fn trend(&self, node: &NodeId) -> GradientTrend {
+        let history = self.history.read().await;
+        history
+            .get(node)
+            .map(GradientTrend::from_samples)
+            .unwrap_or_default()
+    }
+
+    /// Average trend across nodes with fresh gradients
+    pub async fn network_trend(&self) -> GradientTrend {
+        let history = self.history.read().await;
+        let now = Timestamp::now();
+
+        let trends: Vec<GradientTrend> = history
+            .values()
+            .filter(|samples| {
+                samples.back().is_some_and(|(timestamp, _)| {
+                    now.millis.saturating_sub(timestamp.millis) < MAX_GRADIENT_AGE_MS
+                })
+            })
+            .map(GradientTrend::from_samples)
+            .filter(|trend| trend.samples >= 2)
+            .collect();
+
+        if trends.is_empty() {
+            return GradientTrend::default();
+        }
+
+        let count = trends.len() as f64;
+        let mean = |f: fn(&GradientTrend) -> f64| trends.iter().map(f).sum::<f64>() / count;
+        GradientTrend {
+            cpu_per_sec: mean(|t| t.cpu_per_sec),
+            memory_per_sec: mean(|t| t.memory_per_sec),
+            gpu_per_sec: mean(|t| t.gpu_per_sec),
+            storage_per_sec: mean(|t| t.storage_per_sec),
+            bandwidth_per_sec: mean(|t| t.bandwidth_per_sec),
+            credit_balance_per_sec: mean(|t| t.credit_balance_per_sec),
+            samples: trends.iter().map(|t| t.samples).sum(),
+        }
+    }
+
     /// Get aggregated view of network gradients
+    ///
+    /// Every node with a fresh gradient counts equally.
     pub async fn get_network_gradient(&self) -> ResourceGradient {
+        self.get_weighted_network_gradient(|_| 1.0).await
+    }
+
+    /// Get aggregated view of network gradients weighted per node
+    ///
+    /// `weight` is typically a node's reputation. Nodes weighted zero or
+    /// below are left out, which is how isolated nodes are discarded.
+    pub async fn get_weighted_network_gradient<W>(&self, weight: W) -> ResourceGradient
+    where
+        W: Fn(&NodeId) -> f64,
+    {
+        self.get_network_gradient_summary(weight).await.mean
+    }
+
+    /// Weighted mean plus per-dimension min, max and median
+    ///
+    /// Min, max and median are taken over the same nodes as the mean but
+    /// are not weighted.
+    pub async fn get_network_gradient_summary<W>(&self, weight: W) -> GradientSummary
+    where
+        W: Fn(&NodeId) -> f64,
+    {
         let gradients = self.gradients.read().await;
         let now = Timestamp::now();
 
-        // Filter stale gradients and collect fresh ones
-        let fresh: Vec<&GradientUpdate> = gradients
+        // Filter stale gradients and excluded nodes, keeping the rest with their weight
+        let fresh: Vec<(f64, &ResourceGradient)> = gradients
             .values()
             .filter(|g| now.millis.saturating_sub(g.timestamp.millis) < MAX_GRADIENT_AGE_MS)
+            .map(|g| (weight(&g.source), &g.gradient))
+            .filter(|(w, _)| w.is_finite() && *w > 0.0)
             .collect();
 
         if fresh.is_empty() {
-            return ResourceGradient::zero();
+            return GradientSummary::default();
         }
 
-        // Simple average aggregation
-        // TODO: Weight by reputation or stake
-        let count = fresh.len() as f64;
-        ResourceGradient {
-            cpu_available: fresh.iter().map(|g| g.gradient.cpu_available).sum::<f64>() / count,
-            memory_available: fresh
-                .iter()
-                .map(|g| g.gradient.memory_available)
-                .sum::<f64>()
-                / count,
-            gpu_available: fresh.iter().map(|g| g.gradient.gpu_available).sum::<f64>() / count,
-            storage_available: fresh
-                .iter()
-                .map(|g| g.gradient.storage_available)
-                .sum::<f64>()
-                / count,
-            bandwidth_available: fresh
-                .iter()
-                .map(|g| g.gradient.bandwidth_available)
-                .sum::<f64>()
-                / count,
-            credit_balance: fresh.iter().map(|g| g.gradient.credit_balance).sum::<f64>() / count,
+        let total_weight: f64 = fresh.iter().map(|(w, _)| w).sum();
+        let mean =
+            map_dimensions(|dim| fresh.iter().map(|(w, g)| w * dim(g)).sum::<f64>() / total_weight);
+
+        let sorted = |dim: fn(&ResourceGradient) -> f64| {
+            let mut values: Vec<f64> = fresh.iter().map(|(_, g)| dim(g)).collect();
+            values.sort_by(f64::total_cmp);
+            values
+        };
+        let min = map_dimensions(|dim| sorted(dim)[0]);
+        let max = map_dimensions(|dim| sorted(dim)[fresh.len() - 1]);
+        let median = map_dimensions(|dim| {
+            let values = sorted(dim);
+            let mid = values.len() / 2;
+            if values.len() % 2 == 0 {
+                (values[mid - 1] + values[mid]) / 2.0
+            } else {
+                values[mid]
+            }
+        });
+
+        GradientSummary {
+            mean,
+            min,
+            max,
+            median,
+            nodes: fresh.len(),
         }
     }
 
@@ -192,10 +294,116 @@ impl GradientBroadcaster {
         gradients
             .retain(|_, g| now.millis.saturating_sub(g.timestamp.millis) < MAX_GRADIENT_AGE_MS * 2);
 
+        let mut history = self.history.write().await;
+        history.retain(|node, _| gradients.contains_key(node));
+
         before_count - gradients.len()
     }
 }
 
+/// Statistics over the network's fresh gradients
+#[derive(Debug, Clone, Copy)]
+pub struct GradientSummary {
+    /// Weighted mean of each dimension
+    pub mean: ResourceGradient,
+    /// Lowest value of each dimension
+    pub min: ResourceGradient,
+    /// Highest value of each dimension
+    pub max: ResourceGradient,
+    /// Median of each dimension
+    pub median: ResourceGradient,
+    /// Number of nodes that contributed
+    pub nodes: usize,
+}
+
+impl Default for GradientSummary {
+    fn default() -> Self {
+        Self {
+            mean: ResourceGradient::zero(),
+            min: ResourceGradient::zero(),
+            max: ResourceGradient::zero(),
+            median: ResourceGradient::zero(),
+            nodes: 0,
+        }
+    }
+}
+
+/// Build a gradient by computing each dimension from its accessor
+fn map_dimensions<F>(f: F) -> ResourceGradient
+where
+    F: Fn(fn(&ResourceGradient) -> f64) -> f64,
+{
+    ResourceGradient {
+        cpu_available: f(|g| g.cpu_available),
+        memory_available: f(|g| g.memory_available),
+        gpu_available: f(|g| g.gpu_available),
+        storage_available: f(|g| g.storage_available),
+        bandwidth_available: f(|g| g.bandwidth_available),
+        credit_balance: f(|g| g.credit_balance),
+    }
+}
+
+/// Rate of change of each gradient dimension, in units per second
+///
+/// Positive values mean the resource is becoming more available.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GradientTrend {
+    /// Change in `cpu_available`
+    pub cpu_per_sec: f64,
+    /// Change in `memory_available`
+    pub memory_per_sec: f64,
+    /// Change in `gpu_available`
+    pub gpu_per_sec: f64,
+    /// Change in `storage_available`
+    pub storage_per_sec: f64,
+    /// Change in `bandwidth_available`
+    pub bandwidth_per_sec: f64,
+    /// Change in `credit_balance`
+    pub credit_balance_per_sec: f64,
+    /// Number of gradients the trend was fitted to
+    pub samples: usize,
+}
+
+impl GradientTrend {
+    /// Least-squares slope of each dimension over time
+    fn from_samples(samples: &VecDeque<(Timestamp, ResourceGradient)>) -> Self {
+        let Some((first, _)) = samples.front() else {
+            return Self::default();
+        };
+        // Seconds since the first sample, to keep the sums well conditioned
+        let points: Vec<(f64, &ResourceGradient)> = samples
+            .iter()
+            .map(|(t, g)| (t.millis.saturating_sub(first.millis) as f64 / 1000.0, g))
+            .collect();
+
+        let n = points.len() as f64;
+        let mean_t = points.iter().map(|(t, _)| t).sum::<f64>() / n;
+        let var_t: f64 = points.iter().map(|(t, _)| (t - mean_t).powi(2)).sum();
+
+        let slope = |f: fn(&ResourceGradient) -> f64| {
+            if var_t == 0.0 {
+                return 0.0;
+            }
+            let mean_v = points.iter().map(|(_, g)| f(g)).sum::<f64>() / n;
+            points
+                .iter()
+                .map(|(t, g)| (t - mean_t) * (f(g) - mean_v))
+                .sum::<f64>()
+                / var_t
+        };
+
+        Self {
+            cpu_per_sec: slope(|g| g.cpu_available),
+            memory_per_sec: slope(|g| g.memory_available),
+            gpu_per_sec: slope(|g| g.gpu_available),
+            storage_per_sec: slope(|g| g.storage_available),
+            bandwidth_per_sec: slope(|g| g.bandwidth_available),
+            credit_balance_per_sec: slope(|g| g.credit_balance),
+            samples: samples.len(),
+        }
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum BroadcastError {
     #[error("Invalid gradient values")]
@@ -212,8 +420,8 @@ pub enum HandleError {
     FutureTimestamp,
     #[error("Gradient is too old")]
     TooOld,
-    #[error("Invalid signature")]
-    InvalidSignature,
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(#[from] SignatureError),
 }
 
 #[cfg(test)]
@@ -234,11 +442,30 @@ mod tests {
         (f, counter)
     }
 
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    /// A gradient update signed by `key`
+    fn signed_update(
+        key: &SigningKey,
+        gradient: ResourceGradient,
+        timestamp: Timestamp,
+    ) -> GradientUpdate {
+        let mut update = GradientUpdate {
+            source: signing::node_id(key),
+            gradient,
+            timestamp,
+            signature: vec![],
+        };
+        update.sign(key).unwrap();
+        update
+    }
+
     #[tokio::test]
     async fn test_broadcast_gradient() {
-        let node = NodeId::from_bytes([1u8; 32]);
         let (publish, counter) = mock_publish();
-        let broadcaster = GradientBroadcaster::new(node, publish);
+        let broadcaster = GradientBroadcaster::new(key(1), publish);
 
         let gradient = ResourceGradient {
             cpu_available: 0.5,
@@ -255,21 +482,18 @@ mod tests {
 
     #[tokio::test]
     async fn test_handle_gradient() {
-        let local = NodeId::from_bytes([1u8; 32]);
-        let remote = NodeId::from_bytes([2u8; 32]);
         let (publish, _) = mock_publish();
-        let broadcaster = GradientBroadcaster::new(local, publish);
+        let broadcaster = GradientBroadcaster::new(key(1), publish);
 
-        let update = GradientUpdate {
-            source: remote,
-            gradient: ResourceGradient {
+        let update = signed_update(
+            &key(2),
+            ResourceGradient {
                 cpu_available: 0.42,
                 memory_available: 0.73,
                 ..Default::default()
             },
-            timestamp: Timestamp::now(),
-            signature: vec![],
-        };
+            Timestamp::now(),
+        );
 
         broadcaster.handle_gradient(update).await.unwrap();
 
@@ -279,39 +503,114 @@ mod tests {
 
     #[tokio::test]
     async fn test_reject_future_timestamp() {
-        let local = NodeId::from_bytes([1u8; 32]);
-        let remote = NodeId::from_bytes([2u8; 32]);
         let (publish, _) = mock_publish();
-        let broadcaster = GradientBroadcaster::new(local, publish);
+        let broadcaster = GradientBroadcaster::new(key(1), publish);
 
-        let update = GradientUpdate {
-            source: remote,
-            gradient: ResourceGradient::default(),
-            timestamp: Timestamp::new(Timestamp::now().millis + 60_000), // 1 minute in future
-            signature: vec![],
-        };
+        let update = signed_update(
+            &key(2),
+            ResourceGradient::default(),
+            Timestamp::new(Timestamp::now().millis + 60_000), // 1 minute in future
+        );
 
         let result = broadcaster.handle_gradient(update).await;
         assert!(matches!(result, Err(HandleError::FutureTimestamp)));
     }
 
+    #[tokio::test]
+    async fn test_reject_unsigned_or_forged_gradient() {
+        let (publish, _) = mock_publish();
+        let broadcaster = GradientBroadcaster::new(key(1), publish);
+
+        let mut unsigned = signed_update(&key(2), ResourceGradient::default(), Timestamp::now());
+        unsigned.signature.clear();
+        let result = broadcaster.handle_gradient(unsigned).await;
+        assert!(matches!(
+            result,
+            Err(HandleError::InvalidSignature(SignatureError::Missing))
+        ));
+
+        // Signed by node 3 but claiming to come from node 2
+        let mut forged = signed_update(&key(3), ResourceGradient::default(), Timestamp::now());
+        forged.source = signing::node_id(&key(2));
+        let result = broadcaster.handle_gradient(forged).await;
+        assert!(matches!(
+            result,
+            Err(HandleError::InvalidSignature(SignatureError::Invalid))
+        ));
+
+        assert_eq!(broadcaster.active_node_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_trend_follows_history() {
+        let remote = key(2);
+        let (publish, _) = mock_publish();
+        let broadcaster = GradientBroadcaster::new(key(1), publish);
+
+        // CPU frees up by 0.1 per second while memory fills by 0.05 per second
+        let start = Timestamp::now().millis - 5_000;
+        for i in 0..5u64 {
+            let update = signed_update(
+                &remote,
+                ResourceGradient {
+                    cpu_available: 0.2 + 0.1 * i as f64,
+                    memory_available: 0.8 - 0.05 * i as f64,
+                    ..Default::default()
+                },
+                Timestamp::new(start + i * 1000),
+            );
+            broadcaster.handle_gradient(update).await.unwrap();
+        }
+
+        let trend = broadcaster.trend(&signing::node_id(&remote)).await;
+        assert_eq!(trend.samples, 5);
+        assert!((trend.cpu_per_sec - 0.1).abs() < 1e-9);
+        assert!((trend.memory_per_sec + 0.05).abs() < 1e-9);
+        assert_eq!(trend.gpu_per_sec, 0.0);
+
+        let network = broadcaster.network_trend().await;
+        assert!((network.cpu_per_sec - 0.1).abs() < 1e-9);
+
+        // Unknown nodes have a flat trend
+        let unknown = broadcaster.trend(&signing::node_id(&key(3))).await;
+        assert_eq!(unknown, GradientTrend::default());
+    }
+
+    #[tokio::test]
+    async fn test_history_is_bounded() {
+        let remote = key(2);
+        let (publish, _) = mock_publish();
+        let broadcaster = GradientBroadcaster::new(key(1), publish);
+
+        let start = Timestamp::now().millis - 10_000;
+        for i in 0..(GRADIENT_HISTORY_LEN as u64 + 4) {
+            let update = signed_update(
+                &remote,
+                ResourceGradient::default(),
+                Timestamp::new(start + i * 100),
+            );
+            broadcaster.handle_gradient(update).await.unwrap();
+        }
+
+        let trend = broadcaster.trend(&signing::node_id(&remote)).await;
+        assert_eq!(trend.samples, GRADIENT_HISTORY_LEN);
+    }
+
     #[tokio::test]
     async fn test_aggregation() {
-        let local = NodeId::from_bytes([0u8; 32]);
         let (publish, _) = mock_publish();
-        let broadcaster = GradientBroadcaster::new(local, publish);
+        let broadcaster = GradientBroadcaster::new(key(0), publish);
 
         // Add gradients from 2 nodes
         for i in 1..=2u8 {
-            let update = GradientUpdate {
-                source: NodeId::from_bytes([i; 32]),
-                gradient: ResourceGradient {
+            let update = signed_update(
+                &key(i),
+                ResourceGradient {
                     cpu_available: i as f64 * 0.3,
                     ..Default::default()
                 },
-                timestamp: Timestamp::now(),
-                signature: vec![],
-            };
+                Timestamp::now(),
+            );
             broadcaster.handle_gradient(update).await.unwrap();
         }
 
@@ -320,41 +619,82 @@ mod tests {
         assert!((net.cpu_available - 0.45).abs() < 0.001);
     }
 
+    #[tokio::test]
+    async fn test_weighted_aggregation_and_summary() {
+        let (publish, _) = mock_publish();
+        let broadcaster = GradientBroadcaster::new(key(0), publish);
+
+        for (i, cpu) in [(1u8, 0.2), (2, 0.4), (3, 0.9)] {
+            let update = signed_update(
+                &key(i),
+                ResourceGradient {
+                    cpu_available: cpu,
+                    ..Default::default()
+                },
+                Timestamp::now(),
+            );
+            broadcaster.handle_gradient(update).await.unwrap();
+        }
+
+        // Node 1 is trusted three times as much as node 2; node 3 is isolated
+        let trusted = signing::node_id(&key(1));
+        let isolated = signing::node_id(&key(3));
+        let reputation = |node: &NodeId| {
+            if *node == trusted {
+                0.9
+            } else if *node == isolated {
+                0.0
+            } else {
+                0.3
+            }
+        };
+
+        let net = broadcaster.get_weighted_network_gradient(reputation).await;
+        // (0.9 * 0.2 + 0.3 * 0.4) / 1.2 = 0.25
+        assert!((net.cpu_available - 0.25).abs() < 0.001);
+
+        let summary = broadcaster.get_network_gradient_summary(|_| 1.0).await;
+        assert_eq!(summary.nodes, 3);
+        assert!((summary.min.cpu_available - 0.2).abs() < 0.001);
+        assert!((summary.max.cpu_available - 0.9).abs() < 0.001);
+        assert!((summary.median.cpu_available - 0.4).abs() < 0.001);
+        assert!((summary.mean.cpu_available - 0.5).abs() < 0.001);
+    }
+
     #[tokio::test]
     async fn test_only_keeps_newer() {
-        let local = NodeId::from_bytes([1u8; 32]);
-        let remote = NodeId::from_bytes([2u8; 32]);
+        let remote = key(2);
         let (publish, _) = mock_publish();
-        let broadcaster = GradientBroadcaster::new(local, publish);
+        let broadcaster = GradientBroadcaster::new(key(1), publish);
 
         let now = Timestamp::now();
 
         // First update (recent timestamp)
-        let update1 = GradientUpdate {
-            source: remote,
-            gradient: ResourceGradient {
+        let update1 = signed_update(
+            &remote,
+            ResourceGradient {
                 cpu_available: 0.5,
                 ..Default::default()
             },
-            timestamp: Timestamp::new(now.millis - 1000), // 1 second ago
-            signature: vec![],
-        };
+            Timestamp::new(now.millis - 1000), // 1 second ago
+        );
         broadcaster.handle_gradient(update1).await.unwrap();
 
         // Older update should be ignored
-        let update2 = GradientUpdate {
-            source: remote,
-            gradient: ResourceGradient {
+        let update2 = signed_update(
+            &remote,
+            ResourceGradient {
                 cpu_available: 0.1,
                 ..Default::default()
             },
-            timestamp: Timestamp::new(now.millis - 2000), // 2 seconds ago (older)
-            signature: vec![],
-        };
+            Timestamp::new(now.millis - 2000), // 2 seconds ago (older)
+        );
         // This should succeed but the older timestamp should be ignored
         broadcaster.handle_gradient(update2).await.unwrap();
 
-        let grad = broadcaster.get_node_gradient(&remote).await;
+        let grad = broadcaster
+            .get_node_gradient(&signing::node_id(&remote))
+            .await;
         // Should still have 0.5, not 0.1 (newer value preserved)
         assert!(grad.is_some());
         assert!((grad.unwrap().cpu_available - 0.5).abs() < 0.001);
@@ -10,6 +10,8 @@ use univrs_enr::{
     septal::SeptalGateState,
 };
 
+use crate::enr_bridge::signing::{self, SignatureError, SigningKey};
+
 /// Gossipsub topic for gradient updates
 pub const GRADIENT_TOPIC: &str = "/vudo/enr/gradient/1.0.0";
 
@@ -52,6 +54,25 @@ pub struct GradientUpdate {
     pub signature: Vec<u8>,
 }
 
+impl GradientUpdate {
+    /// Canonical bytes covered by the signature
+    pub fn signing_bytes(&self) -> Result<Vec<u8>, EncodeError> {
+        serde_cbor::to_vec(&(&self.source, &self.gradient, &self.timestamp))
+            .map_err(EncodeError::Cbor)
+    }
+
+    /// Sign with the key of `source`
+    pub fn sign(&mut self, key: &SigningKey) -> Result<(), EncodeError> {
+        self.signature = signing::sign(key, &self.signing_bytes()?);
+        Ok(())
+    }
+
+    /// Check the signature was made by `source`
+    pub fn verify(&self) -> Result<(), SignatureError> {
+        signing::verify(&self.source, &self.signing_bytes()?, &self.signature)
+    }
+}
+
 /// Credit transfer announcement
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreditTransferMsg {
@@ -59,10 +80,32 @@ pub struct CreditTransferMsg {
     pub transfer: CreditTransfer,
     /// Unique nonce to prevent replay
     pub nonce: u64,
-    /// Ed25519 signature from sender
+    /// Ed25519 signature from sender over (transfer || nonce)
     pub signature: Vec<u8>,
 }
 
+impl CreditTransferMsg {
+    /// Canonical bytes covered by the signature
+    pub fn signing_bytes(&self) -> Result<Vec<u8>, EncodeError> {
+        serde_cbor::to_vec(&(&self.transfer, self.nonce)).map_err(EncodeError::Cbor)
+    }
+
+    /// Sign with the key of the sending node
+    pub fn sign(&mut self, key: &SigningKey) -> Result<(), EncodeError> {
+        self.signature = signing::sign(key, &self.signing_bytes()?);
+        Ok(())
+    }
+
+    /// Check the signature was made by the sending node
+    pub fn verify(&self) -> Result<(), SignatureError> {
+        signing::verify(
+            &self.transfer.from.node,
+            &self.signing_bytes()?,
+            &self.signature,
+        )
+    }
+}
+
 /// Balance query request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BalanceQueryMsg {
@@ -96,6 +139,8 @@ pub enum ElectionMessage {
     Vote(ElectionVote),
     /// Election result announcement
     Result(ElectionResult),
+    /// Liveness signal from the current nexus
+    Heartbeat(NexusHeartbeat),
 }
 
 /// Election announcement - initiates a new election
@@ -148,6 +193,17 @@ pub struct ElectionResult {
     pub timestamp: Timestamp,
 }
 
+/// Periodic liveness signal from an elected nexus
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NexusHeartbeat {
+    /// Nexus sending the heartbeat
+    pub nexus: NodeId,
+    /// Region the node is nexus for
+    pub region_id: String,
+    /// When the heartbeat was sent
+    pub timestamp: Timestamp,
+}
+
 /// Septal gate message variants
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SeptalMessage {
@@ -8,6 +8,9 @@
 //! - **Nexus Election**: Distributed election for hub nodes
 //! - **Septal Gates**: Circuit breakers for isolating unhealthy nodes
 //!
+//! Gradients and transfers are signed with the node's Ed25519 key, and a
+//! node's `NodeId` is the matching public key.
+//!
 //! ## MVP Scope (Phase 0)
 //!
 //! - Local ledger with optimistic updates
@@ -26,7 +29,7 @@
 //! use univrs_enr::{Credits, NodeId, ResourceGradient};
 //!
 //! // Create bridge with gossipsub publish callback
-//! let bridge = EnrBridge::new(local_node_id, |topic, bytes| {
+//! let bridge = EnrBridge::new(signing_key, |topic, bytes| {
 //!     swarm.behaviour_mut().gossipsub.publish(topic.into(), bytes)
 //!         .map_err(|e| e.to_string())
 //! });
@@ -50,13 +53,29 @@ pub mod gradient;
 pub mod messages;
 pub mod nexus;
 pub mod septal;
+pub mod signing;
 
-pub use credits::{CreditSynchronizer, TransferError, INITIAL_NODE_CREDITS};
-pub use gradient::{BroadcastError, GradientBroadcaster, MAX_GRADIENT_AGE_MS};
-pub use messages::{EnrMessage, CREDIT_TOPIC, ELECTION_TOPIC, GRADIENT_TOPIC, SEPTAL_TOPIC};
-pub use nexus::{DistributedElection, ElectionError, LocalNodeMetrics};
-pub use septal::{SeptalError, SeptalGateManager, SeptalStats};
+pub use credits::{
+    CreditSynchronizer, EntropyTaxRate, InvalidTaxRate, NonceStoreError, QueryError, TransferError,
+    BALANCE_QUERY_TIMEOUT_MS, DEFAULT_ENTROPY_TAX_RATE, INITIAL_NODE_CREDITS,
+};
+pub use gradient::{
+    BroadcastError, GradientBroadcaster, GradientSummary, GradientTrend, GRADIENT_HISTORY_LEN,
+    MAX_GRADIENT_AGE_MS,
+};
+pub use messages::{
+    CreditTransferMsg, EnrMessage, SeptalStateMsg, CREDIT_TOPIC, ELECTION_TOPIC, GRADIENT_TOPIC,
+    SEPTAL_TOPIC,
+};
+pub use nexus::{DistributedElection, ElectionError, ElectionEvent, LocalNodeMetrics};
+pub use septal::{SeptalError, SeptalGateManager, SeptalRecoveryConfig, SeptalStats};
+pub use signing::{
+    node_id, node_id_from_peer_id, node_key_from_keypair, peer_id_from_node_id, SignatureError,
+    SigningKey,
+};
 
+use mycelial_core::health::{HealthCheck, HealthStatus};
+use std::collections::HashSet;
 use tracing::{debug, error, warn};
 use univrs_enr::{
     core::{Credits, NodeId},
@@ -77,6 +96,9 @@ pub struct EnrBridge {
     pub election: DistributedElection,
     /// Septal gate (circuit breaker) manager
     pub septal: SeptalGateManager,
+    /// Raft ledger running alongside the optimistic one, for auditing
+    #[cfg(feature = "openraft")]
+    raft_ledger: std::sync::OnceLock<std::sync::Arc<crate::raft::RaftCreditLedger>>,
 }
 
 impl EnrBridge {
@@ -84,29 +106,41 @@ impl EnrBridge {
     ///
     /// # Arguments
     ///
-    /// * `local_node` - This node's identity
+    /// * `signing_key` - This node's Ed25519 key; its public half is the node's identity
     /// * `publish_fn` - Callback to publish messages to gossipsub
     ///
     /// # Example
     ///
     /// ```rust,ignore
-    /// let bridge = EnrBridge::new(node_id, |topic, bytes| {
+    /// let bridge = EnrBridge::new(signing_key, |topic, bytes| {
     ///     // Publish to libp2p gossipsub
     ///     swarm.behaviour_mut().gossipsub.publish(topic, bytes)
     /// });
     /// ```
-    pub fn new<F>(local_node: NodeId, publish_fn: F) -> Self
+    pub fn new<F>(signing_key: SigningKey, publish_fn: F) -> Self
     where
         F: Fn(String, Vec<u8>) -> Result<(), String> + Send + Sync + Clone + 'static,
     {
+        let local_node = node_id(&signing_key);
         Self {
-            gradient: GradientBroadcaster::new(local_node, publish_fn.clone()),
-            credits: CreditSynchronizer::new(local_node, publish_fn.clone()),
+            gradient: GradientBroadcaster::new(signing_key.clone(), publish_fn.clone()),
+            credits: CreditSynchronizer::new(signing_key, publish_fn.clone()),
             election: DistributedElection::new(local_node, publish_fn.clone()),
             septal: SeptalGateManager::new(local_node, publish_fn),
+            #[cfg(feature = "openraft")]
+            raft_ledger: std::sync::OnceLock::new(),
         }
     }
 
+    /// Persist the credit nonce table at `path` so replays stay rejected across restarts
+    pub fn with_nonce_store(
+        mut self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<Self, NonceStoreError> {
+        self.credits = self.credits.with_nonce_store(path)?;
+        Ok(self)
+    }
+
     /// Handle incoming ENR message from gossip
     ///
     /// Routes message to appropriate handler based on type.
@@ -132,12 +166,7 @@ impl EnrBridge {
                 }
             }
             EnrMessage::BalanceResponse(response) => {
-                // TODO: Store for pending queries
-                debug!(
-                    request_id = response.request_id,
-                    balance = response.balance.amount,
-                    "Received balance response"
-                );
+                self.credits.handle_balance_response(response).await;
             }
             EnrMessage::Election(election_msg) => {
                 if let Err(e) = self.election.handle_election_message(election_msg).await {
@@ -168,14 +197,88 @@ impl EnrBridge {
         Ok(())
     }
 
+    /// Query a remote node's credit balance over gossip
+    ///
+    /// Waits up to [`BALANCE_QUERY_TIMEOUT_MS`] for the node to answer.
+    pub async fn query_balance(&self, node: NodeId) -> Result<Credits, QueryError> {
+        self.credits.query_balance(node).await
+    }
+
+    /// This node's ENR identity
+    pub fn local_node_id(&self) -> NodeId {
+        self.credits.local_node()
+    }
+
     /// Get local credit balance
     pub async fn local_balance(&self) -> Credits {
         self.credits.local_balance().await
     }
 
+    /// Subscribe to credit transfers as they are applied to the ledger
+    pub fn subscribe_credit_events(&self) -> tokio::sync::broadcast::Receiver<CreditTransferMsg> {
+        self.credits.subscribe_events()
+    }
+
+    /// Entropy tax rate charged on this node's transfers
+    pub async fn entropy_tax_rate(&self) -> EntropyTaxRate {
+        self.credits.entropy_tax_rate().await
+    }
+
+    /// Change the entropy tax rate
+    ///
+    /// Applies to the attached Raft ledger too, if there is one.
+    pub async fn set_entropy_tax_rate(&self, rate: EntropyTaxRate) {
+        self.credits.set_entropy_tax_rate(rate).await;
+        #[cfg(feature = "openraft")]
+        if let Some(ledger) = self.raft_ledger() {
+            ledger.set_entropy_tax_rate(rate).await;
+        }
+    }
+
     /// Get aggregated network gradient view
+    ///
+    /// Nodes isolated by a septal gate are left out.
     pub async fn network_gradient(&self) -> ResourceGradient {
-        self.gradient.get_network_gradient().await
+        self.network_gradient_weighted(|_| 1.0).await
+    }
+
+    /// Get network gradient weighted by each node's reputation
+    ///
+    /// Nodes isolated by a septal gate are left out regardless of reputation.
+    pub async fn network_gradient_weighted<W>(&self, reputation: W) -> ResourceGradient
+    where
+        W: Fn(&NodeId) -> f64,
+    {
+        self.network_gradient_summary(reputation).await.mean
+    }
+
+    /// Weighted mean and min/max/median of the network gradient
+    ///
+    /// Nodes isolated by a septal gate are left out regardless of reputation.
+    pub async fn network_gradient_summary<W>(&self, reputation: W) -> GradientSummary
+    where
+        W: Fn(&NodeId) -> f64,
+    {
+        let isolated: HashSet<NodeId> = self.septal.isolated_nodes().await.into_iter().collect();
+        self.gradient
+            .get_network_gradient_summary(|node| {
+                if isolated.contains(node) {
+                    0.0
+                } else {
+                    reputation(node)
+                }
+            })
+            .await
+    }
+
+    /// How a node's resource availability has been changing
+    pub async fn gradient_trend(&self, node: &NodeId) -> GradientTrend {
+        self.gradient.trend(node).await
+    }
+
+    /// Average resource trend across nodes with fresh gradients
+    pub async fn network_trend(&self) -> GradientTrend {
+        self.gradient.network_trend().await
     }
 
     /// Get number of active nodes with fresh gradients
@@ -190,11 +293,21 @@ impl EnrBridge {
             debug!(count = pruned, "Pruned stale gradients");
         }
 
+        let stale_queries = self.credits.prune_stale_queries().await;
+        if stale_queries > 0 {
+            debug!(count = stale_queries, "Pruned stale balance queries");
+        }
+
         // Check election progress
         if let Err(e) = self.election.check_election_progress().await {
             debug!("Election progress check: {}", e);
         }
 
+        // Heartbeat as nexus, or replace a silent one
+        if let Err(e) = self.election.check_nexus_health().await {
+            warn!("Nexus health check failed: {}", e);
+        }
+
         // Attempt recovery for isolated nodes
         let recoveries = self.septal.attempt_recoveries().await;
         if !recoveries.is_empty() {
@@ -207,6 +320,11 @@ impl EnrBridge {
         self.election.trigger_election(region_id).await
     }
 
+    /// Set a region's membership so election quorum counts every member
+    pub async fn set_election_participants(&self, region_id: String, peers: Vec<NodeId>) {
+        self.election.set_participants(region_id, peers).await;
+    }
+
     /// Get current nexus for this node's region
     pub async fn current_nexus(&self) -> Option<NodeId> {
         self.election.current_nexus().await
@@ -222,6 +340,11 @@ impl EnrBridge {
         self.election.update_metrics(metrics).await;
     }
 
+    /// Subscribe to election events such as nexus failover
+    pub fn subscribe_election_events(&self) -> tokio::sync::broadcast::Receiver<ElectionEvent> {
+        self.election.subscribe_events()
+    }
+
     /// Check if an election is in progress
     pub async fn election_in_progress(&self) -> bool {
         self.election.election_in_progress().await
@@ -285,6 +408,80 @@ impl EnrBridge {
     pub async fn septal_stats(&self) -> SeptalStats {
         self.septal.stats().await
     }
+
+    /// Subscribe to septal gate state changes, local and remote
+    pub fn subscribe_septal_events(&self) -> tokio::sync::broadcast::Receiver<SeptalStateMsg> {
+        self.septal.subscribe_events()
+    }
+
+    /// Subscribe to the septal gate changes this node decided on
+    pub fn subscribe_local_septal_events(
+        &self,
+    ) -> tokio::sync::broadcast::Receiver<SeptalStateMsg> {
+        self.septal.subscribe_local_events()
+    }
+
+    /// Save septal gate state so isolation survives a restart
+    pub fn save_septal_state(&self, path: impl AsRef<std::path::Path>) -> Result<(), SeptalError> {
+        self.septal.save(path)
+    }
+
+    /// Restore septal gate state saved by [`save_septal_state`](Self::save_septal_state)
+    pub fn load_septal_state(&self, path: impl AsRef<std::path::Path>) -> Result<(), SeptalError> {
+        self.septal.load(path)
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Raft Migration
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    /// Attach the Raft ledger to audit the optimistic ledger against
+    ///
+    /// Attach it before running the `NetworkService`, which then ticks the
+    /// ledger and schedules its revival pool payouts. Returns false if a
+    /// ledger was already attached.
+    #[cfg(feature = "openraft")]
+    pub fn attach_raft_ledger(
+        &self,
+        ledger: std::sync::Arc<crate::raft::RaftCreditLedger>,
+    ) -> bool {
+        self.raft_ledger.set(ledger).is_ok()
+    }
+
+    /// The attached Raft ledger, if any
+    #[cfg(feature = "openraft")]
+    pub fn raft_ledger(&self) -> Option<&std::sync::Arc<crate::raft::RaftCreditLedger>> {
+        self.raft_ledger.get()
+    }
+
+    /// Accounts whose optimistic balance differs from the Raft-committed one
+    ///
+    /// Empty when no Raft ledger is attached.
+    #[cfg(feature = "openraft")]
+    pub async fn audit_balances(&self) -> Vec<crate::raft::BalanceDiscrepancy> {
+        let Some(ledger) = self.raft_ledger() else {
+            return Vec::new();
+        };
+        crate::raft::compare_balances(
+            &self.credits.all_balances().await,
+            &ledger.all_balances().await,
+        )
+    }
+}
+
+/// Degraded while the bridge hears no gradients or has isolated every peer
+#[async_trait::async_trait]
+impl HealthCheck for EnrBridge {
+    async fn health(&self) -> HealthStatus {
+        let septal = self.septal_stats().await;
+        if septal.total_gates > 0 && septal.closed_gates == septal.total_gates {
+            return HealthStatus::degraded("every septal gate is closed");
+        }
+        if self.active_node_count().await == 0 {
+            return HealthStatus::degraded("no fresh resource gradients");
+        }
+        HealthStatus::Ok
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -293,9 +490,19 @@ pub enum HandleError {
     Decode(#[from] messages::DecodeError),
 }
 
+/// Topics whose messages [`EnrBridge::handle_message`] understands
+pub const BRIDGE_TOPICS: [&str; 4] = [GRADIENT_TOPIC, CREDIT_TOPIC, ELECTION_TOPIC, SEPTAL_TOPIC];
+
 /// Helper to get gossipsub topics for subscription
+///
+/// With the `openraft` feature this includes the Raft ledger topic, whose
+/// messages go to the `RaftCreditLedger` rather than the bridge.
 pub fn enr_topics() -> Vec<&'static str> {
-    vec![GRADIENT_TOPIC, CREDIT_TOPIC, ELECTION_TOPIC, SEPTAL_TOPIC]
+    #[allow(unused_mut)]
+    let mut topics = BRIDGE_TOPICS.to_vec();
+    #[cfg(feature = "openraft")]
+    topics.push(crate::raft::RAFT_TOPIC);
+    topics
 }
 
 #[cfg(test)]
@@ -317,11 +524,16 @@ mod tests {
         (f, counter)
     }
 
+    fn node(seed: u8) -> (SigningKey, NodeId) {
+        let key = SigningKey::from_bytes(&[seed; 32]);
+        let id = node_id(&key);
+        (key, id)
+    }
+
     #[tokio::test]
     async fn test_bridge_creation() {
-        let node = NodeId::from_bytes([1u8; 32]);
         let (publish, _) = mock_publish();
-        let bridge = EnrBridge::new(node, publish);
+        let bridge = EnrBridge::new(node(1).0, publish);
 
         // Should have initial credits
         let balance = bridge.local_balance().await;
@@ -330,11 +542,11 @@ mod tests {
 
     #[tokio::test]
     async fn test_gradient_broadcast_and_handle() {
-        let node1 = NodeId::from_bytes([1u8; 32]);
-        let node2 = NodeId::from_bytes([2u8; 32]);
+        let (key1, node1) = node(1);
+        let key2 = node(2).0;
         let (publish, counter) = mock_publish();
-        let bridge1 = EnrBridge::new(node1, publish.clone());
-        let bridge2 = EnrBridge::new(node2, publish);
+        let bridge1 = EnrBridge::new(key1.clone(), publish.clone());
+        let bridge2 = EnrBridge::new(key2, publish);
 
         // Node1 broadcasts gradient
         let gradient = ResourceGradient {
@@ -345,28 +557,65 @@ mod tests {
         bridge1.broadcast_gradient(gradient).await.unwrap();
         assert_eq!(counter.load(Ordering::SeqCst), 1);
 
+        // Healthy only once it hears from another node
+        assert!(!bridge2.health().await.is_ok());
+
         // Simulate bridge2 receiving the message
-        let msg = EnrMessage::GradientUpdate(messages::GradientUpdate {
+        let mut update = messages::GradientUpdate {
             source: node1,
             gradient,
             timestamp: univrs_enr::Timestamp::now(),
             signature: vec![],
-        });
-        let bytes = msg.encode().unwrap();
+        };
+        update.sign(&key1).unwrap();
+        let bytes = EnrMessage::GradientUpdate(update).encode().unwrap();
         bridge2.handle_message(&bytes).await.unwrap();
 
         // Bridge2 should now see the gradient
         let net = bridge2.network_gradient().await;
         assert!((net.cpu_available - 0.42).abs() < 0.001);
+        assert_eq!(bridge2.health().await, HealthStatus::Ok);
+    }
+
+    #[tokio::test]
+    async fn test_network_gradient_skips_isolated_nodes() {
+        let (key1, node1) = node(1);
+        let (publish, _) = mock_publish();
+        let bridge = EnrBridge::new(node(0).0, publish);
+
+        for (key, cpu) in [(key1, 0.9), (node(2).0, 0.3)] {
+            let mut update = messages::GradientUpdate {
+                source: node_id(&key),
+                gradient: ResourceGradient {
+                    cpu_available: cpu,
+                    ..Default::default()
+                },
+                timestamp: univrs_enr::Timestamp::now(),
+                signature: vec![],
+            };
+            update.sign(&key).unwrap();
+            let bytes = EnrMessage::GradientUpdate(update).encode().unwrap();
+            bridge.handle_message(&bytes).await.unwrap();
+        }
+        assert!((bridge.network_gradient().await.cpu_available - 0.6).abs() < 0.001);
+
+        // Isolating node 1 removes its gradient from the aggregate
+        for _ in 0..5 {
+            bridge
+                .record_peer_failure(node1, "connection timeout")
+                .await;
+        }
+        assert!((bridge.network_gradient().await.cpu_available - 0.3).abs() < 0.001);
+        assert_eq!(bridge.network_gradient_summary(|_| 1.0).await.nodes, 1);
     }
 
     #[tokio::test]
     async fn test_credit_transfer_roundtrip() {
-        let node1 = NodeId::from_bytes([1u8; 32]);
-        let node2 = NodeId::from_bytes([2u8; 32]);
+        let (key1, node1) = node(1);
+        let (key2, node2) = node(2);
         let (publish, counter) = mock_publish();
-        let bridge1 = EnrBridge::new(node1, publish.clone());
-        let bridge2 = EnrBridge::new(node2, publish);
+        let bridge1 = EnrBridge::new(key1.clone(), publish.clone());
+        let bridge2 = EnrBridge::new(key2, publish);
 
         // Transfer from node1 to node2
         bridge1
@@ -385,23 +634,75 @@ mod tests {
             Credits::new(100),
             Credits::new(2),
         );
-        let msg = EnrMessage::CreditTransfer(messages::CreditTransferMsg {
+        let mut msg = messages::CreditTransferMsg {
             transfer,
             nonce: 1,
             signature: vec![],
-        });
-        let bytes = msg.encode().unwrap();
+        };
+        msg.sign(&key1).unwrap();
+        let bytes = EnrMessage::CreditTransfer(msg).encode().unwrap();
         bridge2.handle_message(&bytes).await.unwrap();
 
         // Node2 balance: 1000 + 100 = 1100
         assert_eq!(bridge2.local_balance().await.amount, 1100);
     }
 
+    #[tokio::test]
+    async fn test_handle_message_routes_election() {
+        let (publish, _) = mock_publish();
+        let bridge = EnrBridge::new(node(1).0, publish);
+        assert!(!bridge.election_in_progress().await);
+
+        let announcement =
+            messages::ElectionMessage::Announcement(messages::ElectionAnnouncement {
+                election_id: 7,
+                initiator: node(2).1,
+                region_id: "region-a".to_string(),
+                timestamp: univrs_enr::Timestamp::now(),
+            });
+        let bytes = EnrMessage::Election(announcement).encode().unwrap();
+        bridge.handle_message(&bytes).await.unwrap();
+
+        assert!(bridge.election_in_progress().await);
+    }
+
+    #[cfg(feature = "openraft")]
+    #[tokio::test]
+    async fn test_audit_balances() {
+        let (key1, node1) = node(1);
+        let node2 = node(2).1;
+        let (publish, _) = mock_publish();
+        let bridge = EnrBridge::new(key1, publish.clone());
+        assert!(bridge.audit_balances().await.is_empty());
+
+        let ledger = crate::raft::RaftCreditLedger::new_single_node(node1, publish)
+            .await
+            .unwrap();
+        ledger
+            .grant_credits(node1, Credits::new(INITIAL_NODE_CREDITS))
+            .await
+            .unwrap();
+        assert!(bridge.attach_raft_ledger(Arc::new(ledger)));
+        assert!(bridge.audit_balances().await.is_empty());
+
+        // An optimistic transfer Raft never saw shows up as drift
+        bridge
+            .transfer_credits(node2, Credits::new(100))
+            .await
+            .unwrap();
+        let drift: Vec<i128> = bridge
+            .audit_balances()
+            .await
+            .iter()
+            .map(|d| d.drift())
+            .collect();
+        assert_eq!(drift, vec![-102, 100]);
+    }
+
     #[tokio::test]
     async fn test_malformed_message() {
-        let node = NodeId::from_bytes([1u8; 32]);
         let (publish, _) = mock_publish();
-        let bridge = EnrBridge::new(node, publish);
+        let bridge = EnrBridge::new(node(1).0, publish);
 
         // Random bytes should fail to decode
         let result = bridge.handle_message(&[0xFF, 0xFF, 0xFF]).await;
@@ -419,10 +720,9 @@ mod tests {
 
     #[tokio::test]
     async fn test_septal_gate_integration() {
-        let node = NodeId::from_bytes([1u8; 32]);
-        let peer = NodeId::from_bytes([2u8; 32]);
+        let peer = node(2).1;
         let (publish, _) = mock_publish();
-        let bridge = EnrBridge::new(node, publish);
+        let bridge = EnrBridge::new(node(1).0, publish);
 
         // Initially traffic is allowed
         assert!(bridge.allows_traffic(&peer).await);
@@ -442,4 +742,46 @@ mod tests {
         assert_eq!(stats.isolated_nodes, 1);
         assert_eq!(stats.closed_gates, 1);
     }
+
+    #[tokio::test]
+    async fn test_query_remote_balance() {
+        let (key1, node1) = node(1);
+        let (key2, node2) = node(2);
+
+        // Capture what each bridge publishes so it can be delivered to the other
+        let (tx1, mut rx1) = tokio::sync::mpsc::unbounded_channel();
+        let (tx2, mut rx2) = tokio::sync::mpsc::unbounded_channel();
+        let bridge1 = Arc::new(EnrBridge::new(key1, move |_topic, bytes| {
+            tx1.send(bytes).map_err(|e| e.to_string())
+        }));
+        let bridge2 = EnrBridge::new(key2, move |_topic, bytes| {
+            tx2.send(bytes).map_err(|e| e.to_string())
+        });
+        bridge2.credits.ensure_account(node1).await;
+        bridge2
+            .transfer_credits(node1, Credits::new(100))
+            .await
+            .unwrap();
+        rx2.recv().await.unwrap();
+
+        let query = tokio::spawn({
+            let bridge1 = bridge1.clone();
+            async move { bridge1.query_balance(node2).await }
+        });
+
+        // Deliver the query to node2 and its response back to node1
+        bridge2
+            .handle_message(&rx1.recv().await.unwrap())
+            .await
+            .unwrap();
+        bridge1
+            .handle_message(&rx2.recv().await.unwrap())
+            .await
+            .unwrap();
+
+        // Node2 balance: 1000 - 100 - 2 (tax) = 898
+        let balance = query.await.unwrap().unwrap();
+        assert_eq!(balance.amount, 898);
+        assert_eq!(bridge1.credits.pending_query_count().await, 0);
+    }
 }
@@ -8,10 +8,23 @@
 //! 2. Candidacy: Eligible nodes submit candidacy
 //! 3. Voting: All nodes vote for their preferred candidate
 //! 4. Result: Winner is announced and confirmed
+//!
+//! Quorum is measured against the region's participants. These are seeded
+//! when an election starts from the membership list given to
+//! [`DistributedElection::set_participants`], or failing that from the
+//! peers subscribed to the election topic, so a partition holding a
+//! minority of the region cannot elect a nexus on its own.
+//!
+//! Once elected, the nexus broadcasts a `NexusHeartbeat` every
+//! [`NEXUS_HEARTBEAT_INTERVAL_MS`]. Leaves that hear nothing for
+//! [`NEXUS_HEARTBEAT_TIMEOUT_MS`] drop the nexus, emit
+//! [`ElectionEvent::NexusFailover`] and start a new election.
 
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, RwLock};
 use tracing::{debug, info, warn};
 use univrs_enr::{
     core::{NodeId, Timestamp},
@@ -22,7 +35,7 @@ use univrs_enr::{
 
 use crate::enr_bridge::messages::{
     ElectionAnnouncement, ElectionMessage, ElectionResult, ElectionVote, EnrMessage,
-    NexusCandidacy, ELECTION_TOPIC,
+    NexusCandidacy, NexusHeartbeat, ELECTION_TOPIC,
 };
 
 /// Election timeout in milliseconds
@@ -37,6 +50,12 @@ pub const VOTING_PHASE_MS: u64 = 15_000;
 /// Minimum votes required for valid election (as fraction of participants)
 pub const MIN_VOTE_FRACTION: f64 = 0.5;
 
+/// Interval between heartbeats from the nexus in milliseconds
+pub const NEXUS_HEARTBEAT_INTERVAL_MS: u64 = 5_000;
+
+/// Silence after which leaves replace the nexus, in milliseconds
+pub const NEXUS_HEARTBEAT_TIMEOUT_MS: u64 = 15_000;
+
 /// Callback type for publishing to gossipsub
 pub type PublishFn = Box<dyn Fn(String, Vec<u8>) -> Result<(), String> + Send + Sync>;
 
@@ -53,6 +72,29 @@ pub enum ElectionPhase {
     Confirming,
 }
 
+/// Election lifecycle events
+#[derive(Debug, Clone, PartialEq)]
+pub enum ElectionEvent {
+    /// The nexus stopped heartbeating and a new election was started
+    NexusFailover {
+        /// Region that lost its nexus
+        region_id: String,
+        /// Nexus that went silent
+        failed_nexus: NodeId,
+        /// Election started to replace it
+        election_id: u64,
+    },
+}
+
+/// Heartbeat bookkeeping for the current nexus
+#[derive(Debug, Clone)]
+struct NexusLiveness {
+    /// Region the nexus was elected for
+    region_id: String,
+    /// Last heartbeat heard, or sent if we are the nexus
+    last_heartbeat: Instant,
+}
+
 /// Active election state
 #[derive(Debug, Clone)]
 pub struct ActiveElection {
@@ -107,6 +149,9 @@ impl ActiveElection {
     }
 
     /// Tally votes and determine winner
+    ///
+    /// Ties go to the candidate with the higher election score, then to
+    /// the smaller `NodeId`, so every node picks the same winner.
     pub fn tally_votes(&self) -> Option<NodeId> {
         if self.votes.is_empty() {
             return None;
@@ -121,10 +166,29 @@ impl ActiveElection {
         // Find candidate with most votes
         vote_counts
             .into_iter()
-            .max_by_key(|(_, count)| *count)
+            .max_by(|(a, a_count), (b, b_count)| {
+                a_count
+                    .cmp(b_count)
+                    .then_with(|| self.compare_candidates(a, b))
+            })
             .map(|(node, _)| node)
     }
 
+    /// Order two candidates by election score, then by smaller `NodeId`
+    ///
+    /// The better candidate compares greater. Nodes we never saw a
+    /// candidacy from rank below every known candidate.
+    pub fn compare_candidates(&self, a: &NodeId, b: &NodeId) -> Ordering {
+        let score = |node: &NodeId| {
+            self.candidates
+                .get(node)
+                .map_or(f64::NEG_INFINITY, |c| c.election_score)
+        };
+        score(a)
+            .total_cmp(&score(b))
+            .then_with(|| b.to_bytes().cmp(&a.to_bytes()))
+    }
+
     /// Check if we have enough votes for a valid election
     pub fn has_quorum(&self) -> bool {
         if self.participants.is_empty() {
@@ -149,6 +213,18 @@ pub struct DistributedElection {
     local_metrics: Arc<RwLock<LocalNodeMetrics>>,
     /// Next election ID
     next_election_id: Arc<RwLock<u64>>,
+    /// Known membership per region
+    region_members: Arc<RwLock<HashMap<String, Vec<NodeId>>>>,
+    /// Peers subscribed to the election topic, for regions without a membership list
+    topic_peers: Arc<RwLock<Vec<NodeId>>>,
+    /// Heartbeat state of the current nexus
+    liveness: Arc<RwLock<Option<NexusLiveness>>>,
+    /// How often the nexus sends heartbeats
+    heartbeat_interval: Duration,
+    /// How long leaves wait for a heartbeat before failing over
+    heartbeat_timeout: Duration,
+    /// Election lifecycle events
+    events: broadcast::Sender<ElectionEvent>,
     /// Callback to publish to gossipsub
     publish_fn: PublishFn,
 }
@@ -194,10 +270,88 @@ impl DistributedElection {
             current_role: Arc::new(RwLock::new(NexusRole::default())),
             local_metrics: Arc::new(RwLock::new(LocalNodeMetrics::default())),
             next_election_id: Arc::new(RwLock::new(1)),
+            region_members: Arc::new(RwLock::new(HashMap::new())),
+            topic_peers: Arc::new(RwLock::new(Vec::new())),
+            liveness: Arc::new(RwLock::new(None)),
+            heartbeat_interval: Duration::from_millis(NEXUS_HEARTBEAT_INTERVAL_MS),
+            heartbeat_timeout: Duration::from_millis(NEXUS_HEARTBEAT_TIMEOUT_MS),
+            events: broadcast::channel(16).0,
             publish_fn: Box::new(publish_fn),
         }
     }
 
+    /// Set how often this node sends heartbeats while it is nexus
+    pub fn with_heartbeat_interval(mut self, interval: Duration) -> Self {
+        self.heartbeat_interval = interval;
+        self
+    }
+
+    /// Set how long to wait for a nexus heartbeat before re-electing
+    pub fn with_heartbeat_timeout(mut self, timeout: Duration) -> Self {
+        self.heartbeat_timeout = timeout;
+        self
+    }
+
+    /// Subscribe to election lifecycle events
+    pub fn subscribe_events(&self) -> broadcast::Receiver<ElectionEvent> {
+        self.events.subscribe()
+    }
+
+    /// Set the membership of a region
+    ///
+    /// Elections for the region count quorum against these nodes (plus
+    /// this one). An election already running for the region is updated.
+    pub async fn set_participants(&self, region_id: impl Into<String>, peers: Vec<NodeId>) {
+        let region_id = region_id.into();
+        self.region_members
+            .write()
+            .await
+            .insert(region_id.clone(), peers);
+        self.seed_active_participants(Some(&region_id)).await;
+    }
+
+    /// Set the peers currently subscribed to the election topic
+    ///
+    /// Used as the participant list for regions without explicit membership.
+    pub async fn set_topic_peers(&self, peers: Vec<NodeId>) {
+        *self.topic_peers.write().await = peers;
+        self.seed_active_participants(None).await;
+    }
+
+    /// Known participants of a region, including this node
+    async fn participants_for(&self, region_id: &str) -> Vec<NodeId> {
+        let mut participants = match self.region_members.read().await.get(region_id) {
+            Some(members) => members.clone(),
+            None => self.topic_peers.read().await.clone(),
+        };
+        if !participants.contains(&self.local_node) {
+            participants.push(self.local_node);
+        }
+        participants
+    }
+
+    /// Add known participants to the active election
+    ///
+    /// With `region_id` set, only an election for that region is touched.
+    async fn seed_active_participants(&self, region_id: Option<&str>) {
+        let election_region = match &*self.active_election.read().await {
+            Some(e) if region_id.map_or(true, |r| r == e.region_id) => e.region_id.clone(),
+            _ => return,
+        };
+        let participants = self.participants_for(&election_region).await;
+
+        let mut election = self.active_election.write().await;
+        if let Some(ref mut e) = *election {
+            if e.region_id == election_region {
+                for node in participants {
+                    if !e.participants.contains(&node) {
+                        e.participants.push(node);
+                    }
+                }
+            }
+        }
+    }
+
     /// Update local node metrics
     pub async fn update_metrics(&self, metrics: LocalNodeMetrics) {
         let mut m = self.local_metrics.write().await;
@@ -240,7 +394,8 @@ impl DistributedElection {
         };
 
         // Create new election
-        let election = ActiveElection::new(election_id, self.local_node, region_id.clone());
+        let mut election = ActiveElection::new(election_id, self.local_node, region_id.clone());
+        election.participants = self.participants_for(&region_id).await;
         {
             let mut active = self.active_election.write().await;
             *active = Some(election);
@@ -351,11 +506,12 @@ impl DistributedElection {
         }
 
         // Start tracking this election
-        let election = ActiveElection::new(
+        let mut election = ActiveElection::new(
             announcement.election_id,
             announcement.initiator,
             announcement.region_id,
         );
+        election.participants = self.participants_for(&election.region_id).await;
 
         {
             let mut active = self.active_election.write().await;
@@ -422,13 +578,9 @@ impl DistributedElection {
                 Some(e) if e.election_id == election_id => {
                     // Find candidate with highest score
                     e.candidates
-                        .values()
-                        .max_by(|a, b| {
-                            a.election_score
-                                .partial_cmp(&b.election_score)
-                                .unwrap_or(std::cmp::Ordering::Equal)
-                        })
-                        .map(|c| c.node)
+                        .keys()
+                        .max_by(|a, b| e.compare_candidates(a, b))
+                        .copied()
                 }
                 _ => None,
             }
@@ -559,6 +711,7 @@ impl DistributedElection {
                     if !e.has_quorum() {
                         return Err(ElectionError::InsufficientVotes);
                     }
+                    // Deterministic, so nodes finalizing independently agree
                     let winner = e.tally_votes();
                     (e.election_id, winner, e.region_id.clone())
                 }
@@ -591,6 +744,8 @@ impl DistributedElection {
             }
         }
 
+        self.watch_nexus(region_id.clone()).await;
+
         // Clear election state
         {
             let mut election = self.active_election.write().await;
@@ -662,6 +817,14 @@ impl DistributedElection {
             }
         }
 
+        // Later elections must not reuse this ID
+        {
+            let mut id = self.next_election_id.write().await;
+            *id = (*id).max(result.election_id + 1);
+        }
+
+        self.watch_nexus(result.region_id.clone()).await;
+
         // Clear election state
         {
             let mut election = self.active_election.write().await;
@@ -688,7 +851,93 @@ impl DistributedElection {
             ElectionMessage::Candidacy(cand) => self.handle_candidacy(cand).await,
             ElectionMessage::Vote(vote) => self.handle_vote(vote).await,
             ElectionMessage::Result(result) => self.handle_result(result).await,
+            ElectionMessage::Heartbeat(heartbeat) => self.handle_heartbeat(heartbeat).await,
+        }
+    }
+
+    /// Start heartbeat tracking for a newly elected nexus
+    async fn watch_nexus(&self, region_id: String) {
+        *self.liveness.write().await = Some(NexusLiveness {
+            region_id,
+            last_heartbeat: Instant::now(),
+        });
+    }
+
+    /// Handle a heartbeat from a nexus
+    pub async fn handle_heartbeat(&self, heartbeat: NexusHeartbeat) -> Result<(), ElectionError> {
+        if *self.current_nexus.read().await != Some(heartbeat.nexus) {
+            return Ok(()); // Not our nexus
+        }
+
+        if let Some(liveness) = self.liveness.write().await.as_mut() {
+            liveness.last_heartbeat = Instant::now();
+        }
+
+        Ok(())
+    }
+
+    /// Send a heartbeat if we are the nexus, or replace a silent nexus
+    ///
+    /// Should be called periodically. Returns the ID of the election
+    /// started if the nexus stopped heartbeating.
+    pub async fn check_nexus_health(&self) -> Result<Option<u64>, ElectionError> {
+        let Some(nexus) = *self.current_nexus.read().await else {
+            return Ok(None);
+        };
+        let Some(liveness) = self.liveness.read().await.clone() else {
+            return Ok(None);
+        };
+        let silent_for = liveness.last_heartbeat.elapsed();
+
+        if nexus == self.local_node {
+            if silent_for >= self.heartbeat_interval {
+                self.send_heartbeat(liveness.region_id).await?;
+            }
+            return Ok(None);
+        }
+
+        if silent_for < self.heartbeat_timeout || self.election_in_progress().await {
+            return Ok(None);
+        }
+
+        warn!(
+            nexus = %nexus,
+            region = %liveness.region_id,
+            silent_ms = silent_for.as_millis() as u64,
+            "Nexus heartbeat lost, starting re-election"
+        );
+
+        *self.current_nexus.write().await = None;
+        *self.current_role.write().await = NexusRole::default();
+        *self.liveness.write().await = None;
+
+        let election_id = self.trigger_election(liveness.region_id.clone()).await?;
+        let _ = self.events.send(ElectionEvent::NexusFailover {
+            region_id: liveness.region_id,
+            failed_nexus: nexus,
+            election_id,
+        });
+
+        Ok(Some(election_id))
+    }
+
+    /// Broadcast a heartbeat as nexus of `region_id`
+    async fn send_heartbeat(&self, region_id: String) -> Result<(), ElectionError> {
+        let heartbeat = NexusHeartbeat {
+            nexus: self.local_node,
+            region_id,
+            timestamp: Timestamp::now(),
+        };
+
+        let msg = EnrMessage::Election(ElectionMessage::Heartbeat(heartbeat));
+        let bytes = msg.encode().map_err(ElectionError::Encode)?;
+        (self.publish_fn)(ELECTION_TOPIC.to_string(), bytes).map_err(ElectionError::Publish)?;
+
+        if let Some(liveness) = self.liveness.write().await.as_mut() {
+            liveness.last_heartbeat = Instant::now();
         }
+
+        Ok(())
     }
 
     /// Check election timeouts and advance phases
@@ -985,6 +1234,224 @@ mod tests {
         assert_eq!(winner, Some(node2));
     }
 
+    fn candidate(node: NodeId, election_score: f64) -> NexusCandidate {
+        NexusCandidate {
+            node,
+            uptime: 0.99,
+            bandwidth: 50_000_000,
+            reputation: 0.9,
+            current_leaf_count: 10,
+            election_score,
+        }
+    }
+
+    #[test]
+    fn test_tied_tally_is_deterministic() {
+        let voters: Vec<NodeId> = (10..14).map(|i| NodeId::from_bytes([i; 32])).collect();
+        let low = NodeId::from_bytes([1u8; 32]);
+        let high = NodeId::from_bytes([2u8; 32]);
+
+        // Each node builds its own view with votes arriving in a different order
+        let tally = |scores: (f64, f64), rotation: usize| {
+            let mut election = ActiveElection::new(1, voters[0], "region".to_string());
+            election.candidates.insert(low, candidate(low, scores.0));
+            election.candidates.insert(high, candidate(high, scores.1));
+            for i in 0..voters.len() {
+                let voter = voters[(i + rotation) % voters.len()];
+                let choice = if voter.to_bytes()[0] % 2 == 0 {
+                    low
+                } else {
+                    high
+                };
+                election.votes.insert(voter, choice);
+            }
+            election.tally_votes()
+        };
+
+        for rotation in 0..voters.len() {
+            // Two votes each: the higher score wins
+            assert_eq!(tally((0.8, 0.9), rotation), Some(high));
+            assert_eq!(tally((0.9, 0.8), rotation), Some(low));
+            // Same score: the smaller NodeId wins
+            assert_eq!(tally((0.9, 0.9), rotation), Some(low));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_finalize_tie_agrees_across_nodes() {
+        let low = NodeId::from_bytes([1u8; 32]);
+        let high = NodeId::from_bytes([2u8; 32]);
+        let mut winners = Vec::new();
+
+        for local in [low, high, NodeId::from_bytes([3u8; 32])] {
+            let (publish, _) = mock_publish();
+            let election = DistributedElection::new(local, publish);
+            {
+                let mut active = election.active_election.write().await;
+                let mut e = ActiveElection::new(1, low, "region-1".to_string());
+                e.candidates.insert(low, candidate(low, 0.9));
+                e.candidates.insert(high, candidate(high, 0.9));
+                e.votes.insert(low, high);
+                e.votes.insert(high, low);
+                e.phase = ElectionPhase::Voting;
+                *active = Some(e);
+            }
+            winners.push(election.finalize_election().await.unwrap());
+        }
+
+        assert!(winners.iter().all(|winner| *winner == Some(low)));
+    }
+
+    #[tokio::test]
+    async fn test_minority_partition_has_no_quorum() {
+        let nodes: Vec<NodeId> = (1..=5).map(|i| NodeId::from_bytes([i; 32])).collect();
+        let (publish, _) = mock_publish();
+        let election = DistributedElection::new(nodes[0], publish);
+        election.set_participants("region-1", nodes.clone()).await;
+        election
+            .trigger_election("region-1".to_string())
+            .await
+            .unwrap();
+
+        // Only the two nodes on our side of the partition vote
+        for voter in &nodes[..2] {
+            election
+                .handle_vote(ElectionVote {
+                    election_id: 1,
+                    voter: *voter,
+                    candidate: nodes[1],
+                    timestamp: Timestamp::now(),
+                })
+                .await
+                .unwrap();
+        }
+        assert!(matches!(
+            election.finalize_election().await,
+            Err(ElectionError::InsufficientVotes)
+        ));
+
+        // A third vote makes a majority of five
+        election
+            .handle_vote(ElectionVote {
+                election_id: 1,
+                voter: nodes[2],
+                candidate: nodes[1],
+                timestamp: Timestamp::now(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(election.finalize_election().await.unwrap(), Some(nodes[1]));
+    }
+
+    #[tokio::test]
+    async fn test_participants_seeded_from_topic_peers() {
+        let local = NodeId::from_bytes([1u8; 32]);
+        let peers: Vec<NodeId> = (2..=4).map(|i| NodeId::from_bytes([i; 32])).collect();
+        let (publish, _) = mock_publish();
+        let election = DistributedElection::new(local, publish);
+
+        election
+            .handle_announcement(ElectionAnnouncement {
+                election_id: 1,
+                initiator: peers[0],
+                region_id: "region-1".to_string(),
+                timestamp: Timestamp::now(),
+            })
+            .await
+            .unwrap();
+        // Peers seen after the election started are added to it
+        election.set_topic_peers(peers.clone()).await;
+
+        let active = election.active_election.read().await;
+        let participants = &active.as_ref().unwrap().participants;
+        assert_eq!(participants.len(), 4);
+        assert!(participants.contains(&local));
+        assert!(peers.iter().all(|peer| participants.contains(peer)));
+    }
+
+    fn result(winner: NodeId) -> ElectionResult {
+        ElectionResult {
+            election_id: 1,
+            winner,
+            region_id: "region-1".to_string(),
+            vote_count: 3,
+            timestamp: Timestamp::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_nexus_sends_heartbeats() {
+        let node = NodeId::from_bytes([1u8; 32]);
+        let (publish, counter) = mock_publish();
+        let election = DistributedElection::new(node, publish)
+            .with_heartbeat_interval(Duration::from_millis(20));
+        election.handle_result(result(node)).await.unwrap();
+
+        election.check_nexus_health().await.unwrap();
+        assert_eq!(counter.load(Ordering::SeqCst), 0);
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        election.check_nexus_health().await.unwrap();
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_silent_nexus_is_replaced() {
+        let old_nexus = NodeId::from_bytes([1u8; 32]);
+        let leaf = NodeId::from_bytes([2u8; 32]);
+        let (publish, _) = mock_publish();
+        let election = DistributedElection::new(leaf, publish)
+            .with_heartbeat_timeout(Duration::from_millis(30));
+        let mut events = election.subscribe_events();
+        election
+            .update_metrics(LocalNodeMetrics {
+                uptime: 0.99,
+                bandwidth: 50_000_000,
+                reputation: 0.9,
+                connection_count: 25,
+            })
+            .await;
+        election.handle_result(result(old_nexus)).await.unwrap();
+
+        // Heartbeats keep the nexus in place
+        for _ in 0..3 {
+            tokio::time::sleep(Duration::from_millis(15)).await;
+            election
+                .handle_heartbeat(NexusHeartbeat {
+                    nexus: old_nexus,
+                    region_id: "region-1".to_string(),
+                    timestamp: Timestamp::now(),
+                })
+                .await
+                .unwrap();
+            assert_eq!(election.check_nexus_health().await.unwrap(), None);
+        }
+        assert_eq!(election.current_nexus().await, Some(old_nexus));
+
+        // The nexus goes silent
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        let election_id = election
+            .check_nexus_health()
+            .await
+            .unwrap()
+            .expect("no re-election started");
+        assert_eq!(election_id, 2);
+        assert_eq!(election.current_nexus().await, None);
+        assert_eq!(
+            events.try_recv().unwrap(),
+            ElectionEvent::NexusFailover {
+                region_id: "region-1".to_string(),
+                failed_nexus: old_nexus,
+                election_id,
+            }
+        );
+
+        // The surviving node wins the re-election
+        election.cast_vote(election_id).await.unwrap();
+        assert_eq!(election.finalize_election().await.unwrap(), Some(leaf));
+        assert_eq!(election.current_nexus().await, Some(leaf));
+    }
+
     #[test]
     fn test_local_metrics_eligibility() {
         let eligible = LocalNodeMetrics {
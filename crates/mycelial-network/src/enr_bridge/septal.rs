@@ -20,6 +20,24 @@
 //!                                  └──[recovery test fails]──► Closed
 //! ```
 //!
+//! The recovery test is a series of `SeptalHealthProbe`s sent while the
+//! gate is half-open. Once enough consecutive healthy `SeptalHealthResponse`s
+//! arrive the gate reopens; an unhealthy answer, or silence for the probe
+//! timeout, closes it again until the next recovery window. How long a gate
+//! stays closed, how many successes it needs and how many probes may be in
+//! flight at once come from a [`SeptalRecoveryConfig`], which can be set per
+//! peer:
+//!
+//! ```rust,ignore
+//! // Economics-critical peers must prove themselves three times
+//! manager
+//!     .set_peer_recovery_config(ledger_peer, SeptalRecoveryConfig {
+//!         required_successes: 3,
+//!         ..Default::default()
+//!     })
+//!     .await?;
+//! ```
+//!
 //! ## Example
 //!
 //! ```rust,ignore
@@ -39,8 +57,12 @@
 //! ```
 
 use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast;
 use tracing::{debug, info, warn};
 use univrs_enr::{
     core::{NodeId, Timestamp},
@@ -55,6 +77,60 @@ use super::messages::{
     SEPTAL_TOPIC,
 };
 
+/// How long a half-open gate waits for a health response
+pub const HEALTH_PROBE_TIMEOUT_MS: u64 = 5_000;
+
+/// How long a closed gate waits before going half-open
+pub const RECOVERY_TIMEOUT_MS: u64 = 30_000;
+
+/// Upper bound on `SeptalRecoveryConfig::required_successes`
+pub const MAX_REQUIRED_SUCCESSES: u32 = 10;
+
+/// Recovery behaviour of a septal gate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeptalRecoveryConfig {
+    /// Time a gate stays closed before going half-open (ms)
+    pub recovery_timeout_ms: u64,
+    /// Time to wait for each health response (ms)
+    pub probe_timeout_ms: u64,
+    /// Consecutive healthy responses needed to reopen the gate
+    pub required_successes: u32,
+    /// Health probes allowed in flight at once while half-open
+    pub max_concurrent_probes: u32,
+}
+
+impl SeptalRecoveryConfig {
+    /// Check that timeouts are non-zero and counts are in range
+    ///
+    /// More concurrent probes than required successes would only waste
+    /// bandwidth, so `max_concurrent_probes` may not exceed it.
+    pub fn is_valid(&self) -> bool {
+        self.recovery_timeout_ms > 0
+            && self.probe_timeout_ms > 0
+            && (1..=MAX_REQUIRED_SUCCESSES).contains(&self.required_successes)
+            && (1..=self.required_successes).contains(&self.max_concurrent_probes)
+    }
+
+    fn recovery_timeout(&self) -> Duration {
+        Duration::from_millis(self.recovery_timeout_ms)
+    }
+
+    fn probe_timeout(&self) -> Duration {
+        Duration::from_millis(self.probe_timeout_ms)
+    }
+}
+
+impl Default for SeptalRecoveryConfig {
+    fn default() -> Self {
+        Self {
+            recovery_timeout_ms: RECOVERY_TIMEOUT_MS,
+            probe_timeout_ms: HEALTH_PROBE_TIMEOUT_MS,
+            required_successes: 1,
+            max_concurrent_probes: 1,
+        }
+    }
+}
+
 /// Publish function type for gossipsub
 type PublishFn = Arc<dyn Fn(String, Vec<u8>) -> Result<(), String> + Send + Sync>;
 
@@ -75,6 +151,69 @@ pub struct SeptalGateManager {
     transitions: Arc<RwLock<Vec<SeptalGateTransition>>>,
     /// Gossipsub publish callback
     publish_fn: PublishFn,
+    /// Outstanding health probes: request_id -> probe
+    pending_probes: Arc<RwLock<HashMap<u64, PendingProbe>>>,
+    /// Recovery config for gates without a per-peer override
+    recovery_config: Arc<RwLock<SeptalRecoveryConfig>>,
+    /// Per-peer recovery config overrides
+    peer_recovery_configs: Arc<RwLock<HashMap<NodeId, SeptalRecoveryConfig>>>,
+    /// Recovery progress of gates that are not open
+    recovery_progress: Arc<RwLock<HashMap<NodeId, RecoveryProgress>>>,
+    /// Gate state changes, local and remote
+    events: broadcast::Sender<SeptalStateMsg>,
+    /// Gate state changes this node decided on
+    local_events: broadcast::Sender<SeptalStateMsg>,
+}
+
+/// A health probe awaiting its response
+#[derive(Debug, Clone, Copy)]
+struct PendingProbe {
+    /// Probed peer
+    peer: NodeId,
+    /// When the probe was sent
+    sent_at: Instant,
+}
+
+/// Where a closed or half-open gate is in its recovery
+#[derive(Debug, Clone, Copy)]
+struct RecoveryProgress {
+    /// When the gate last closed
+    closed_at: Instant,
+    /// Healthy responses since the gate went half-open
+    successes: u32,
+}
+
+impl RecoveryProgress {
+    fn closed_now() -> Self {
+        Self {
+            closed_at: Instant::now(),
+            successes: 0,
+        }
+    }
+}
+
+/// Gate state written by [`SeptalGateManager::save`]
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedSeptal {
+    gates: Vec<PersistedGate>,
+    /// Nodes blocked by a Woronin body
+    isolated: Vec<NodeId>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PersistedGate {
+    node: NodeId,
+    state: SeptalGateState,
+    failure_count: u32,
+    /// When the gate last closed (ms since the Unix epoch)
+    closed_at_ms: Option<u64>,
+}
+
+/// Wall-clock time in ms since the Unix epoch
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
 }
 
 impl SeptalGateManager {
@@ -90,9 +229,39 @@ impl SeptalGateManager {
             config: Arc::new(RwLock::new(SeptalGateConfig::default())),
             transitions: Arc::new(RwLock::new(Vec::new())),
             publish_fn: Arc::new(publish_fn),
+            pending_probes: Arc::new(RwLock::new(HashMap::new())),
+            recovery_config: Arc::new(RwLock::new(SeptalRecoveryConfig::default())),
+            peer_recovery_configs: Arc::new(RwLock::new(HashMap::new())),
+            recovery_progress: Arc::new(RwLock::new(HashMap::new())),
+            events: broadcast::channel(64).0,
+            local_events: broadcast::channel(64).0,
         }
     }
 
+    /// Subscribe to gate state changes
+    ///
+    /// Covers transitions this node makes and ones applied from gossip that
+    /// changed a gate's state.
+    pub fn subscribe_events(&self) -> broadcast::Receiver<SeptalStateMsg> {
+        self.events.subscribe()
+    }
+
+    /// Subscribe to the gate state changes this node decided on
+    ///
+    /// Unlike [`subscribe_events`](Self::subscribe_events), leaves out
+    /// changes learned from gossip, which any peer can claim.
+    pub fn subscribe_local_events(&self) -> broadcast::Receiver<SeptalStateMsg> {
+        self.local_events.subscribe()
+    }
+
+    /// Set how long half-open gates wait for a health response
+    ///
+    /// Applies to gates without a per-peer recovery config.
+    pub fn with_probe_timeout(self, timeout: Duration) -> Self {
+        self.recovery_config.write().probe_timeout_ms = timeout.as_millis() as u64;
+        self
+    }
+
     /// Record a failure for a peer node
     ///
     /// If failures exceed threshold, the gate closes and
@@ -118,6 +287,7 @@ impl SeptalGateManager {
             };
 
             gate.trip();
+            self.mark_closed(peer);
 
             // Activate Woronin body
             {
@@ -222,16 +392,16 @@ impl SeptalGateManager {
 
     /// Attempt recovery for isolated nodes
     ///
-    /// Should be called periodically. Transitions closed gates
-    /// to half-open after timeout, and tests recovery.
+    /// Should be called periodically. Transitions closed gates to
+    /// half-open after timeout and probes them, and closes half-open
+    /// gates whose probe went unanswered.
     pub async fn attempt_recoveries(&self) -> Vec<RecoveryResult> {
         let mut results = Vec::new();
         let mut gates = self.gates.write();
-        let mut woronin = self.woronin.write();
         let config = self.config.read().clone();
 
         for (node_id, gate) in gates.iter_mut() {
-            let result = self.try_recovery(gate, &mut woronin, &config).await;
+            let result = self.try_recovery(gate, &config).await;
             if result != RecoveryResult::NotNeeded && result != RecoveryResult::TooSoon {
                 debug!(
                     peer = %node_id,
@@ -242,6 +412,11 @@ impl SeptalGateManager {
             }
         }
 
+        // Drop unanswered probes that no half-open gate is waiting on
+        self.pending_probes.write().retain(|_, probe| {
+            probe.sent_at.elapsed() < self.recovery_config_for(&probe.peer).probe_timeout()
+        });
+
         results
     }
 
@@ -249,118 +424,189 @@ impl SeptalGateManager {
     async fn try_recovery(
         &self,
         gate: &mut SeptalGate,
-        woronin: &mut WoroninManager,
         _config: &SeptalGateConfig,
     ) -> RecoveryResult {
+        let recovery = self.recovery_config_for(&gate.node);
+
         match gate.state {
             SeptalGateState::Open => RecoveryResult::NotNeeded,
             SeptalGateState::Closed => {
-                // Check if timeout elapsed
-                if gate.attempt_half_open() {
-                    let transition = SeptalGateTransition {
+                let closed_for = self
+                    .recovery_progress
+                    .write()
+                    .entry(gate.node)
+                    .or_insert_with(RecoveryProgress::closed_now)
+                    .closed_at
+                    .elapsed();
+                if closed_for < recovery.recovery_timeout() {
+                    return RecoveryResult::StillClosed;
+                }
+
+                gate.state = SeptalGateState::HalfOpen;
+                if let Some(progress) = self.recovery_progress.write().get_mut(&gate.node) {
+                    progress.successes = 0;
+                }
+
+                info!(
+                    peer = %gate.node,
+                    required_successes = recovery.required_successes,
+                    "Gate entering half-open state for recovery test"
+                );
+
+                self.record_transition(
+                    gate.node,
+                    SeptalGateTransition {
                         from_state: SeptalGateState::Closed,
                         to_state: SeptalGateState::HalfOpen,
                         reason: "Recovery timeout elapsed".to_string(),
                         timestamp: Timestamp::now(),
-                    };
+                    },
+                );
 
-                    info!(
-                        peer = %gate.node,
-                        "Gate entering half-open state for recovery test"
-                    );
+                // Start the recovery test
+                self.top_up_probes(gate.node, &recovery);
 
-                    // Store transition and broadcast
-                    {
-                        let mut transitions = self.transitions.write();
-                        transitions.push(transition.clone());
-                    }
+                RecoveryResult::EnteredHalfOpen
+            }
+            SeptalGateState::HalfOpen => {
+                let timed_out = {
+                    let mut pending = self.pending_probes.write();
+                    let before = pending.len();
+                    pending.retain(|_, probe| {
+                        probe.peer != gate.node
+                            || probe.sent_at.elapsed() < recovery.probe_timeout()
+                    });
+                    before - pending.len()
+                };
+
+                if timed_out > 0 {
+                    return self.fail_recovery(gate, "Health probe timed out");
+                }
 
-                    // Broadcast asynchronously (fire and forget for now)
-                    let node = gate.node;
-                    let publish_fn = self.publish_fn.clone();
-                    let msg = EnrMessage::Septal(SeptalMessage::StateChange(SeptalStateMsg {
-                        node,
-                        from_state: transition.from_state,
-                        to_state: transition.to_state,
-                        reason: transition.reason.clone(),
-                        timestamp: transition.timestamp,
-                    }));
-
-                    if let Ok(bytes) = msg.encode() {
-                        let _ = publish_fn(SEPTAL_TOPIC.to_string(), bytes);
-                    }
+                // Still waiting; also covers half-open gates without a
+                // probe, e.g. after a remote state change
+                self.top_up_probes(gate.node, &recovery);
+                RecoveryResult::TooSoon
+            }
+        }
+    }
 
-                    RecoveryResult::EnteredHalfOpen
-                } else {
-                    RecoveryResult::StillClosed
-                }
+    /// Send probes until the half-open gate of `peer` has as many in flight
+    /// as its config allows, or as many as it still needs
+    fn top_up_probes(&self, peer: NodeId, recovery: &SeptalRecoveryConfig) {
+        let successes = self
+            .recovery_progress
+            .read()
+            .get(&peer)
+            .map_or(0, |progress| progress.successes);
+        let in_flight = self
+            .pending_probes
+            .read()
+            .values()
+            .filter(|probe| probe.peer == peer)
+            .count() as u32;
+        let wanted = recovery
+            .max_concurrent_probes
+            .min(recovery.required_successes.saturating_sub(successes));
+
+        for _ in in_flight..wanted {
+            if let Err(e) = self.send_probe(peer) {
+                warn!(peer = %peer, error = %e, "Failed to send health probe");
+                break;
             }
-            SeptalGateState::HalfOpen => {
-                // For now, use a simple health check based on no recent failures
-                // In production, this would ping the node or check metrics
-                let healthy = gate.failure_count == 0;
-
-                if healthy {
-                    gate.recover();
-                    woronin.deactivate(&gate.node);
-
-                    let transition = SeptalGateTransition {
-                        from_state: SeptalGateState::HalfOpen,
-                        to_state: SeptalGateState::Open,
-                        reason: "Recovery test passed".to_string(),
-                        timestamp: Timestamp::now(),
-                    };
+        }
+    }
 
-                    info!(
-                        peer = %gate.node,
-                        "Gate recovered - peer no longer isolated"
-                    );
+    /// Start the recovery timer of a gate that just closed
+    fn mark_closed(&self, peer: NodeId) {
+        self.recovery_progress
+            .write()
+            .insert(peer, RecoveryProgress::closed_now());
+    }
 
-                    {
-                        let mut transitions = self.transitions.write();
-                        transitions.push(transition.clone());
-                    }
+    /// Forget outstanding probes and recovery progress for `peer`
+    fn clear_recovery(&self, peer: &NodeId) {
+        self.pending_probes
+            .write()
+            .retain(|_, probe| probe.peer != *peer);
+        self.recovery_progress.write().remove(peer);
+    }
 
-                    // Broadcast recovery
-                    let node = gate.node;
-                    let publish_fn = self.publish_fn.clone();
-                    let msg = EnrMessage::Septal(SeptalMessage::StateChange(SeptalStateMsg {
-                        node,
-                        from_state: transition.from_state,
-                        to_state: transition.to_state,
-                        reason: transition.reason.clone(),
-                        timestamp: transition.timestamp,
-                    }));
-
-                    if let Ok(bytes) = msg.encode() {
-                        let _ = publish_fn(SEPTAL_TOPIC.to_string(), bytes);
-                    }
+    /// Reopen a half-open gate after a passed recovery test
+    fn recover(&self, gate: &mut SeptalGate, woronin: &mut WoroninManager) -> RecoveryResult {
+        gate.recover();
+        woronin.deactivate(&gate.node);
+        self.clear_recovery(&gate.node);
 
-                    RecoveryResult::Recovered
-                } else {
-                    gate.fail_recovery();
+        info!(
+            peer = %gate.node,
+            "Gate recovered - peer no longer isolated"
+        );
 
-                    let transition = SeptalGateTransition {
-                        from_state: SeptalGateState::HalfOpen,
-                        to_state: SeptalGateState::Closed,
-                        reason: "Recovery test failed".to_string(),
-                        timestamp: Timestamp::now(),
-                    };
+        self.record_transition(
+            gate.node,
+            SeptalGateTransition {
+                from_state: SeptalGateState::HalfOpen,
+                to_state: SeptalGateState::Open,
+                reason: "Recovery test passed".to_string(),
+                timestamp: Timestamp::now(),
+            },
+        );
 
-                    warn!(
-                        peer = %gate.node,
-                        "Recovery test failed - peer remains isolated"
-                    );
+        RecoveryResult::Recovered
+    }
 
-                    {
-                        let mut transitions = self.transitions.write();
-                        transitions.push(transition.clone());
-                    }
+    /// Close a half-open gate again after a failed recovery test
+    fn fail_recovery(&self, gate: &mut SeptalGate, reason: &str) -> RecoveryResult {
+        gate.fail_recovery();
+        self.clear_recovery(&gate.node);
+        self.mark_closed(gate.node);
 
-                    RecoveryResult::RecoveryFailed
-                }
+        warn!(
+            peer = %gate.node,
+            reason,
+            "Recovery test failed - peer remains isolated"
+        );
+
+        self.record_transition(
+            gate.node,
+            SeptalGateTransition {
+                from_state: SeptalGateState::HalfOpen,
+                to_state: SeptalGateState::Closed,
+                reason: format!("Recovery test failed: {}", reason),
+                timestamp: Timestamp::now(),
+            },
+        );
+
+        RecoveryResult::RecoveryFailed
+    }
+
+    /// Store a transition and broadcast it (fire and forget)
+    fn record_transition(&self, node: NodeId, transition: SeptalGateTransition) {
+        let msg = EnrMessage::Septal(SeptalMessage::StateChange(SeptalStateMsg {
+            node,
+            from_state: transition.from_state,
+            to_state: transition.to_state,
+            reason: transition.reason.clone(),
+            timestamp: transition.timestamp,
+        }));
+
+        {
+            let mut transitions = self.transitions.write();
+            transitions.push(transition);
+            // Keep last 100 transitions
+            if transitions.len() > 100 {
+                transitions.remove(0);
             }
         }
+
+        if let Ok(bytes) = msg.encode() {
+            let _ = (self.publish_fn)(SEPTAL_TOPIC.to_string(), bytes);
+        }
+        if let EnrMessage::Septal(SeptalMessage::StateChange(change)) = msg {
+            let _ = self.local_events.send(change.clone());
+            let _ = self.events.send(change);
+        }
     }
 
     /// Handle incoming septal message from gossip
@@ -380,6 +626,7 @@ impl SeptalGateManager {
             .or_insert_with(|| SeptalGate::new(msg.node));
 
         // Apply the state change
+        let changed = gate.state != msg.to_state;
         gate.state = msg.to_state;
 
         // Update Woronin body
@@ -389,12 +636,21 @@ impl SeptalGateManager {
                 if !woronin.is_isolated(&msg.node) {
                     woronin.activate(msg.node, &msg.reason);
                 }
+                if changed {
+                    self.mark_closed(msg.node);
+                }
             }
             SeptalGateState::Open => {
                 woronin.deactivate(&msg.node);
+                self.clear_recovery(&msg.node);
             }
             SeptalGateState::HalfOpen => {
                 // Keep Woronin active during half-open
+                if changed {
+                    if let Some(progress) = self.recovery_progress.write().get_mut(&msg.node) {
+                        progress.successes = 0;
+                    }
+                }
             }
         }
 
@@ -406,11 +662,19 @@ impl SeptalGateManager {
             "Applied remote state change"
         );
 
+        if changed {
+            let _ = self.events.send(msg);
+        }
         Ok(())
     }
 
     /// Handle health probe request
     async fn handle_health_probe(&self, probe: SeptalHealthProbe) -> Result<(), SeptalError> {
+        // Probes are broadcast; only the target answers
+        if probe.target != self.local_node {
+            return Ok(());
+        }
+
         // Respond with our health status
         let response = SeptalHealthResponse {
             request_id: probe.request_id,
@@ -432,7 +696,42 @@ impl SeptalGateManager {
         &self,
         response: SeptalHealthResponse,
     ) -> Result<(), SeptalError> {
-        if response.is_healthy {
+        let probe = self.pending_probes.write().remove(&response.request_id);
+        if let Some(probe) = probe.filter(|probe| probe.peer == response.node) {
+            // Answer to a recovery test
+            let mut gates = self.gates.write();
+            let mut woronin = self.woronin.write();
+            if let Some(gate) = gates
+                .get_mut(&probe.peer)
+                .filter(|gate| gate.state == SeptalGateState::HalfOpen)
+            {
+                if response.is_healthy {
+                    let recovery = self.recovery_config_for(&probe.peer);
+                    let successes = {
+                        let mut progress = self.recovery_progress.write();
+                        let progress = progress
+                            .entry(probe.peer)
+                            .or_insert_with(RecoveryProgress::closed_now);
+                        progress.successes += 1;
+                        progress.successes
+                    };
+
+                    if successes >= recovery.required_successes {
+                        self.recover(gate, &mut woronin);
+                    } else {
+                        debug!(
+                            peer = %probe.peer,
+                            successes,
+                            required = recovery.required_successes,
+                            "Recovery probe passed"
+                        );
+                        self.top_up_probes(probe.peer, &recovery);
+                    }
+                } else {
+                    self.fail_recovery(gate, "Peer reported unhealthy");
+                }
+            }
+        } else if response.is_healthy {
             // Reset failure count for healthy peer
             let mut gates = self.gates.write();
             if let Some(gate) = gates.get_mut(&response.node) {
@@ -469,12 +768,23 @@ impl SeptalGateManager {
                 warn!(error = ?e, "Failed to encode state change");
             }
         }
+        if let EnrMessage::Septal(SeptalMessage::StateChange(change)) = msg {
+            let _ = self.local_events.send(change.clone());
+            let _ = self.events.send(change);
+        }
     }
 
     /// Send health probe to a peer
     pub async fn probe_health(&self, peer: NodeId) -> Result<(), SeptalError> {
+        self.send_probe(peer)?;
+        Ok(())
+    }
+
+    /// Publish a health probe and track it until answered or timed out
+    fn send_probe(&self, peer: NodeId) -> Result<u64, SeptalError> {
+        let request_id = rand::random();
         let probe = SeptalHealthProbe {
-            request_id: rand::random(),
+            request_id,
             target: peer,
             timestamp: Timestamp::now(),
         };
@@ -483,7 +793,16 @@ impl SeptalGateManager {
         let bytes = msg.encode().map_err(|_| SeptalError::EncodeFailed)?;
         (self.publish_fn)(SEPTAL_TOPIC.to_string(), bytes).map_err(SeptalError::PublishFailed)?;
 
-        Ok(())
+        self.pending_probes.write().insert(
+            request_id,
+            PendingProbe {
+                peer,
+                sent_at: Instant::now(),
+            },
+        );
+
+        debug!(peer = %peer, request_id, "Sent health probe");
+        Ok(request_id)
     }
 
     /// Get recent transitions for observability
@@ -502,6 +821,144 @@ impl SeptalGateManager {
     pub async fn get_config(&self) -> SeptalGateConfig {
         self.config.read().clone()
     }
+
+    /// Set the recovery config for gates without a per-peer override
+    pub async fn set_recovery_config(
+        &self,
+        config: SeptalRecoveryConfig,
+    ) -> Result<(), SeptalError> {
+        if !config.is_valid() {
+            return Err(SeptalError::InvalidConfig);
+        }
+        *self.recovery_config.write() = config;
+        Ok(())
+    }
+
+    /// Set the recovery config for one peer's gate
+    pub async fn set_peer_recovery_config(
+        &self,
+        peer: NodeId,
+        config: SeptalRecoveryConfig,
+    ) -> Result<(), SeptalError> {
+        if !config.is_valid() {
+            return Err(SeptalError::InvalidConfig);
+        }
+        self.peer_recovery_configs.write().insert(peer, config);
+        Ok(())
+    }
+
+    /// Return a peer's gate to the default recovery config
+    pub async fn clear_peer_recovery_config(&self, peer: &NodeId) {
+        self.peer_recovery_configs.write().remove(peer);
+    }
+
+    /// Get the recovery config that applies to a peer's gate
+    pub async fn get_recovery_config(&self, peer: &NodeId) -> SeptalRecoveryConfig {
+        self.recovery_config_for(peer)
+    }
+
+    /// Write gate states, failure counts and isolation to `path`
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SeptalError> {
+        let state = {
+            let gates = self.gates.read();
+            let woronin = self.woronin.read();
+            let progress = self.recovery_progress.read();
+            let now_ms = unix_millis();
+
+            PersistedSeptal {
+                gates: gates
+                    .values()
+                    .map(|gate| PersistedGate {
+                        node: gate.node,
+                        state: gate.state,
+                        failure_count: gate.failure_count,
+                        closed_at_ms: progress.get(&gate.node).map(|progress| {
+                            now_ms.saturating_sub(progress.closed_at.elapsed().as_millis() as u64)
+                        }),
+                    })
+                    .collect(),
+                isolated: woronin.isolated_nodes(),
+            }
+        };
+
+        let json = serde_json::to_vec(&state).map_err(|e| SeptalError::Format(e.to_string()))?;
+        let path = path.as_ref();
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, json)?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Restore gates written by [`save`](Self::save)
+    ///
+    /// A missing file leaves the manager untouched. Closed gates keep
+    /// counting their recovery timeout from when they originally closed, so
+    /// one whose timeout ran out while the node was down comes back
+    /// half-open. Set recovery configs before loading.
+    pub fn load(&self, path: impl AsRef<Path>) -> Result<(), SeptalError> {
+        let state: PersistedSeptal = match std::fs::read(path.as_ref()) {
+            Ok(json) => {
+                serde_json::from_slice(&json).map_err(|e| SeptalError::Format(e.to_string()))?
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let now_ms = unix_millis();
+        let mut gates = self.gates.write();
+        let mut woronin = self.woronin.write();
+        let mut progress = self.recovery_progress.write();
+
+        for saved in state.gates {
+            let mut gate = SeptalGate::new(saved.node);
+            gate.state = saved.state;
+            gate.failure_count = saved.failure_count;
+
+            if gate.state != SeptalGateState::Open {
+                let closed_for = Duration::from_millis(
+                    now_ms.saturating_sub(saved.closed_at_ms.unwrap_or(now_ms)),
+                );
+                if gate.state == SeptalGateState::Closed
+                    && closed_for >= self.recovery_config_for(&saved.node).recovery_timeout()
+                {
+                    gate.state = SeptalGateState::HalfOpen;
+                }
+                progress.insert(
+                    saved.node,
+                    RecoveryProgress {
+                        closed_at: Instant::now()
+                            .checked_sub(closed_for)
+                            .unwrap_or_else(Instant::now),
+                        successes: 0,
+                    },
+                );
+            }
+
+            gates.insert(saved.node, gate);
+        }
+
+        for node in state.isolated {
+            if !woronin.is_isolated(&node) {
+                woronin.activate(node, "Restored after restart");
+            }
+        }
+
+        info!(
+            gates = gates.len(),
+            isolated = woronin.isolated_nodes().len(),
+            "Restored septal gate state"
+        );
+
+        Ok(())
+    }
+
+    fn recovery_config_for(&self, peer: &NodeId) -> SeptalRecoveryConfig {
+        self.peer_recovery_configs
+            .read()
+            .get(peer)
+            .copied()
+            .unwrap_or_else(|| *self.recovery_config.read())
+    }
 }
 
 /// Septal gate statistics
@@ -523,6 +980,10 @@ pub enum SeptalError {
     PublishFailed(String),
     #[error("Invalid configuration")]
     InvalidConfig,
+    #[error("Gate state I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Malformed gate state: {0}")]
+    Format(String),
 }
 
 #[cfg(test)]
@@ -543,6 +1004,424 @@ mod tests {
         (f, counter)
     }
 
+    /// Publish callback that keeps every published message
+    fn capture_publish() -> (
+        impl Fn(String, Vec<u8>) -> Result<(), String> + Clone,
+        Arc<parking_lot::Mutex<Vec<EnrMessage>>>,
+    ) {
+        let published = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let p = published.clone();
+        let f = move |_topic: String, bytes: Vec<u8>| {
+            p.lock().push(EnrMessage::decode(&bytes).unwrap());
+            Ok(())
+        };
+        (f, published)
+    }
+
+    /// Put `peer`'s gate into half-open the way a remote node would
+    async fn half_open(manager: &SeptalGateManager, peer: NodeId) {
+        for to_state in [SeptalGateState::Closed, SeptalGateState::HalfOpen] {
+            let msg = SeptalStateMsg {
+                node: peer,
+                from_state: SeptalGateState::Open,
+                to_state,
+                reason: "Remote".to_string(),
+                timestamp: Timestamp::now(),
+            };
+            manager
+                .handle_message(SeptalMessage::StateChange(msg))
+                .await
+                .unwrap();
+        }
+    }
+
+    fn probes_to(
+        published: &parking_lot::Mutex<Vec<EnrMessage>>,
+        target: NodeId,
+    ) -> Vec<SeptalHealthProbe> {
+        published
+            .lock()
+            .iter()
+            .filter_map(|msg| match msg {
+                EnrMessage::Septal(SeptalMessage::HealthProbe(probe)) if probe.target == target => {
+                    Some(probe.clone())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn last_probe(published: &parking_lot::Mutex<Vec<EnrMessage>>) -> SeptalHealthProbe {
+        published
+            .lock()
+            .iter()
+            .rev()
+            .find_map(|msg| match msg {
+                EnrMessage::Septal(SeptalMessage::HealthProbe(probe)) => Some(probe.clone()),
+                _ => None,
+            })
+            .expect("no health probe sent")
+    }
+
+    async fn answer(manager: &SeptalGateManager, probe: &SeptalHealthProbe) {
+        let response = SeptalHealthResponse {
+            request_id: probe.request_id,
+            node: probe.target,
+            is_healthy: true,
+            failure_count: 0,
+            timestamp: Timestamp::now(),
+        };
+        manager
+            .handle_message(SeptalMessage::HealthResponse(response))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_local_events_leave_out_gossip() {
+        let node = NodeId::from_bytes([1u8; 32]);
+        let peer = NodeId::from_bytes([2u8; 32]);
+        let (publish, _) = mock_publish();
+        let manager = SeptalGateManager::new(node, publish);
+        let mut all = manager.subscribe_events();
+        let mut local = manager.subscribe_local_events();
+
+        // A remote claim that some gate closed is not our decision
+        half_open(&manager, NodeId::from_bytes([3u8; 32])).await;
+        assert_eq!(all.try_recv().unwrap().to_state, SeptalGateState::Closed);
+        assert!(local.try_recv().is_err());
+
+        let mut closed = None;
+        for _ in 0..100 {
+            closed = manager.record_failure(peer, "timeout").await;
+            if closed.is_some() {
+                break;
+            }
+        }
+        assert!(closed.is_some());
+        let change = local.try_recv().unwrap();
+        assert_eq!(change.node, peer);
+        assert_eq!(change.to_state, SeptalGateState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_responsive_peer_recovers() {
+        let node = NodeId::from_bytes([1u8; 32]);
+        let peer = NodeId::from_bytes([2u8; 32]);
+        let (publish, published) = capture_publish();
+        let manager = SeptalGateManager::new(node, publish);
+        half_open(&manager, peer).await;
+        assert!(manager.is_isolated(&peer).await);
+
+        // The half-open gate is tested with a probe, not decided on the spot
+        assert!(manager.attempt_recoveries().await.is_empty());
+        let probe = last_probe(&published);
+        assert_eq!(probe.target, peer);
+        assert_eq!(
+            manager.get_gate_state(&peer).await,
+            SeptalGateState::HalfOpen
+        );
+
+        let response = SeptalHealthResponse {
+            request_id: probe.request_id,
+            node: peer,
+            is_healthy: true,
+            failure_count: 0,
+            timestamp: Timestamp::now(),
+        };
+        manager
+            .handle_message(SeptalMessage::HealthResponse(response))
+            .await
+            .unwrap();
+
+        assert_eq!(manager.get_gate_state(&peer).await, SeptalGateState::Open);
+        assert!(!manager.is_isolated(&peer).await);
+    }
+
+    #[tokio::test]
+    async fn test_silent_peer_stays_closed() {
+        let node = NodeId::from_bytes([1u8; 32]);
+        let peer = NodeId::from_bytes([2u8; 32]);
+        let (publish, published) = capture_publish();
+        let manager =
+            SeptalGateManager::new(node, publish).with_probe_timeout(Duration::from_millis(20));
+        half_open(&manager, peer).await;
+
+        assert!(manager.attempt_recoveries().await.is_empty());
+        let probe = last_probe(&published);
+
+        // Nobody answers before the timeout
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        let results = manager.attempt_recoveries().await;
+        assert_eq!(results, vec![RecoveryResult::RecoveryFailed]);
+        assert_eq!(manager.get_gate_state(&peer).await, SeptalGateState::Closed);
+        assert!(manager.is_isolated(&peer).await);
+
+        // A late answer no longer reopens the gate
+        let response = SeptalHealthResponse {
+            request_id: probe.request_id,
+            node: peer,
+            is_healthy: true,
+            failure_count: 0,
+            timestamp: Timestamp::now(),
+        };
+        manager
+            .handle_message(SeptalMessage::HealthResponse(response))
+            .await
+            .unwrap();
+        assert_eq!(manager.get_gate_state(&peer).await, SeptalGateState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_only_target_answers_probe() {
+        let node = NodeId::from_bytes([1u8; 32]);
+        let (publish, counter) = mock_publish();
+        let manager = SeptalGateManager::new(node, publish);
+
+        let probe = |target| SeptalHealthProbe {
+            request_id: 7,
+            target,
+            timestamp: Timestamp::now(),
+        };
+        manager
+            .handle_message(SeptalMessage::HealthProbe(probe(NodeId::from_bytes(
+                [3u8; 32],
+            ))))
+            .await
+            .unwrap();
+        assert_eq!(counter.load(Ordering::SeqCst), 0);
+
+        manager
+            .handle_message(SeptalMessage::HealthProbe(probe(node)))
+            .await
+            .unwrap();
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_recovery_strictness_per_peer() {
+        let node = NodeId::from_bytes([1u8; 32]);
+        let ledger = NodeId::from_bytes([2u8; 32]);
+        let chat = NodeId::from_bytes([3u8; 32]);
+        let (publish, published) = capture_publish();
+        let manager = SeptalGateManager::new(node, publish);
+        manager
+            .set_peer_recovery_config(
+                ledger,
+                SeptalRecoveryConfig {
+                    required_successes: 3,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        half_open(&manager, ledger).await;
+        half_open(&manager, chat).await;
+        manager.attempt_recoveries().await;
+
+        // One healthy answer is enough for the chat peer
+        answer(&manager, &probes_to(&published, chat)[0]).await;
+        assert_eq!(manager.get_gate_state(&chat).await, SeptalGateState::Open);
+
+        // The ledger peer is probed again after each success until it has three
+        for answered in 1..=3 {
+            let probes = probes_to(&published, ledger);
+            assert_eq!(probes.len(), answered);
+            assert_eq!(
+                manager.get_gate_state(&ledger).await,
+                SeptalGateState::HalfOpen
+            );
+            answer(&manager, probes.last().unwrap()).await;
+        }
+        assert_eq!(manager.get_gate_state(&ledger).await, SeptalGateState::Open);
+        assert!(!manager.is_isolated(&ledger).await);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_recovery_probes() {
+        let node = NodeId::from_bytes([1u8; 32]);
+        let peer = NodeId::from_bytes([2u8; 32]);
+        let (publish, published) = capture_publish();
+        let manager = SeptalGateManager::new(node, publish);
+        manager
+            .set_recovery_config(SeptalRecoveryConfig {
+                required_successes: 3,
+                max_concurrent_probes: 3,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        half_open(&manager, peer).await;
+
+        manager.attempt_recoveries().await;
+        let probes = probes_to(&published, peer);
+        assert_eq!(probes.len(), 3);
+
+        // Nothing more is sent while all three are outstanding
+        manager.attempt_recoveries().await;
+        assert_eq!(probes_to(&published, peer).len(), 3);
+
+        for probe in &probes {
+            answer(&manager, probe).await;
+        }
+        assert_eq!(manager.get_gate_state(&peer).await, SeptalGateState::Open);
+    }
+
+    #[tokio::test]
+    async fn test_recovery_timeout_is_configurable() {
+        let node = NodeId::from_bytes([1u8; 32]);
+        let peer = NodeId::from_bytes([2u8; 32]);
+        let (publish, published) = capture_publish();
+        let manager = SeptalGateManager::new(node, publish);
+        manager
+            .set_peer_recovery_config(
+                peer,
+                SeptalRecoveryConfig {
+                    recovery_timeout_ms: 20,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        for _ in 0..FAILURE_THRESHOLD {
+            manager.record_failure(peer, "Timeout").await;
+        }
+        assert_eq!(
+            manager.attempt_recoveries().await,
+            vec![RecoveryResult::StillClosed]
+        );
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(
+            manager.attempt_recoveries().await,
+            vec![RecoveryResult::EnteredHalfOpen]
+        );
+        assert_eq!(
+            manager.get_gate_state(&peer).await,
+            SeptalGateState::HalfOpen
+        );
+        assert_eq!(probes_to(&published, peer).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_recovery_config_validation() {
+        let node = NodeId::from_bytes([1u8; 32]);
+        let peer = NodeId::from_bytes([2u8; 32]);
+        let (publish, _) = mock_publish();
+        let manager = SeptalGateManager::new(node, publish);
+
+        assert!(SeptalRecoveryConfig::default().is_valid());
+        let invalid = [
+            SeptalRecoveryConfig {
+                required_successes: 0,
+                ..Default::default()
+            },
+            SeptalRecoveryConfig {
+                required_successes: MAX_REQUIRED_SUCCESSES + 1,
+                ..Default::default()
+            },
+            SeptalRecoveryConfig {
+                required_successes: 2,
+                max_concurrent_probes: 3,
+                ..Default::default()
+            },
+            SeptalRecoveryConfig {
+                probe_timeout_ms: 0,
+                ..Default::default()
+            },
+        ];
+
+        for config in invalid {
+            assert!(matches!(
+                manager.set_peer_recovery_config(peer, config).await,
+                Err(SeptalError::InvalidConfig)
+            ));
+        }
+        assert_eq!(
+            manager.get_recovery_config(&peer).await,
+            SeptalRecoveryConfig::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_closed_gate_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("septal.json");
+        let node = NodeId::from_bytes([1u8; 32]);
+        let peer = NodeId::from_bytes([2u8; 32]);
+        let recovery = SeptalRecoveryConfig {
+            recovery_timeout_ms: 50,
+            ..Default::default()
+        };
+
+        let (publish, _) = mock_publish();
+        let manager = SeptalGateManager::new(node, publish);
+        manager.set_recovery_config(recovery).await.unwrap();
+        for _ in 0..FAILURE_THRESHOLD {
+            manager.record_failure(peer, "Timeout").await;
+        }
+        manager.save(&path).unwrap();
+        drop(manager);
+
+        let (publish, _) = mock_publish();
+        let restarted = SeptalGateManager::new(node, publish);
+        restarted.set_recovery_config(recovery).await.unwrap();
+        restarted.load(&path).unwrap();
+        assert_eq!(
+            restarted.get_gate_state(&peer).await,
+            SeptalGateState::Closed
+        );
+        assert!(restarted.is_isolated(&peer).await);
+        assert_eq!(
+            restarted.attempt_recoveries().await,
+            vec![RecoveryResult::StillClosed]
+        );
+
+        // The recovery timeout runs from the original closure
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(
+            restarted.attempt_recoveries().await,
+            vec![RecoveryResult::EnteredHalfOpen]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gate_past_timeout_loads_half_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("septal.json");
+        let node = NodeId::from_bytes([1u8; 32]);
+        let peer = NodeId::from_bytes([2u8; 32]);
+        let recovery = SeptalRecoveryConfig {
+            recovery_timeout_ms: 20,
+            ..Default::default()
+        };
+
+        let (publish, _) = mock_publish();
+        let manager = SeptalGateManager::new(node, publish);
+        manager.set_recovery_config(recovery).await.unwrap();
+        for _ in 0..FAILURE_THRESHOLD {
+            manager.record_failure(peer, "Timeout").await;
+        }
+        manager.save(&path).unwrap();
+
+        // Down for longer than the recovery timeout
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        let (publish, _) = mock_publish();
+        let restarted = SeptalGateManager::new(node, publish);
+        restarted.set_recovery_config(recovery).await.unwrap();
+        restarted.load(&path).unwrap();
+
+        assert_eq!(
+            restarted.get_gate_state(&peer).await,
+            SeptalGateState::HalfOpen
+        );
+        assert!(restarted.is_isolated(&peer).await);
+
+        // Missing state is not an error
+        restarted.load(dir.path().join("missing.json")).unwrap();
+    }
+
     #[tokio::test]
     async fn test_manager_creation() {
         let node = NodeId::from_bytes([1u8; 32]);
@@ -0,0 +1,111 @@
+//! Ed25519 Signing for ENR Messages
+//!
+//! A node's `NodeId` is its Ed25519 public key, so any node can check that
+//! a gradient or transfer really came from the node it names without a key
+//! directory. Messages are signed over their canonical CBOR encoding with
+//! the signature field left out.
+
+use ed25519_dalek::{Signature, Signer, Verifier, VerifyingKey};
+use libp2p::{multiaddr::multihash::Multihash, PeerId};
+use univrs_enr::core::NodeId;
+
+pub use ed25519_dalek::SigningKey;
+
+/// The `NodeId` a signing key speaks for
+pub fn node_id(key: &SigningKey) -> NodeId {
+    NodeId::from_bytes(key.verifying_key().to_bytes())
+}
+
+/// Signing key for the ENR bridge from a libp2p identity
+///
+/// Returns `None` for non-Ed25519 identities.
+pub fn node_key_from_keypair(keypair: &libp2p::identity::Keypair) -> Option<SigningKey> {
+    let secret = keypair.clone().try_into_ed25519().ok()?.secret();
+    SigningKey::try_from(secret.as_ref()).ok()
+}
+
+/// `NodeId` of a peer with an Ed25519 identity
+///
+/// Ed25519 public keys are inlined in the peer ID, so no lookup is needed.
+pub fn node_id_from_peer_id(peer_id: &PeerId) -> Option<NodeId> {
+    let multihash = Multihash::<64>::from(*peer_id);
+    let public = libp2p::identity::PublicKey::try_decode_protobuf(multihash.digest()).ok()?;
+    Some(NodeId::from_bytes(
+        public.try_into_ed25519().ok()?.to_bytes(),
+    ))
+}
+
+/// Peer ID of the node with the given `NodeId`
+///
+/// The inverse of [`node_id_from_peer_id`]; `None` if the ID isn't a valid
+/// Ed25519 public key.
+pub fn peer_id_from_node_id(node: &NodeId) -> Option<PeerId> {
+    let public = libp2p::identity::ed25519::PublicKey::try_from_bytes(&node.to_bytes()).ok()?;
+    Some(libp2p::identity::PublicKey::from(public).to_peer_id())
+}
+
+/// Sign `message` with `key`
+pub(crate) fn sign(key: &SigningKey, message: &[u8]) -> Vec<u8> {
+    key.sign(message).to_bytes().to_vec()
+}
+
+/// Check that `signature` over `message` was made by `signer`
+pub(crate) fn verify(
+    signer: &NodeId,
+    message: &[u8],
+    signature: &[u8],
+) -> Result<(), SignatureError> {
+    if signature.is_empty() {
+        return Err(SignatureError::Missing);
+    }
+    let key = VerifyingKey::from_bytes(&signer.to_bytes()).map_err(|_| SignatureError::BadKey)?;
+    let signature = Signature::from_slice(signature).map_err(|_| SignatureError::Malformed)?;
+    key.verify(message, &signature)
+        .map_err(|_| SignatureError::Invalid)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SignatureError {
+    #[error("Message is not signed")]
+    Missing,
+    #[error("Signature is malformed")]
+    Malformed,
+    #[error("Sender id is not a valid Ed25519 public key")]
+    BadKey,
+    #[error("Signature does not match message")]
+    Invalid,
+    #[error("Encoding error: {0}")]
+    Encode(#[from] crate::enr_bridge::messages::EncodeError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let key = SigningKey::from_bytes(&[1u8; 32]);
+        let node = node_id(&key);
+
+        let signature = sign(&key, b"hello");
+        assert!(verify(&node, b"hello", &signature).is_ok());
+        assert!(matches!(
+            verify(&node, b"hellp", &signature),
+            Err(SignatureError::Invalid)
+        ));
+        assert!(matches!(
+            verify(&node, b"hello", &[]),
+            Err(SignatureError::Missing)
+        ));
+    }
+
+    #[test]
+    fn test_node_id_from_peer_id() {
+        let keypair = libp2p::identity::Keypair::generate_ed25519();
+        let key = node_key_from_keypair(&keypair).unwrap();
+        let peer_id = keypair.public().to_peer_id();
+
+        assert_eq!(node_id_from_peer_id(&peer_id), Some(node_id(&key)));
+        assert_eq!(peer_id_from_node_id(&node_id(&key)), Some(peer_id));
+    }
+}
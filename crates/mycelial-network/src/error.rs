@@ -34,10 +34,22 @@ pub enum NetworkError {
     #[error("Message too large: {size} bytes (max: {max})")]
     MessageTooLarge { size: usize, max: usize },
 
+    /// A message fragment was malformed or didn't fit with its message
+    #[error("Invalid fragment: {0}")]
+    InvalidFragment(String),
+
     /// Topic not subscribed
     #[error("Not subscribed to topic: {0}")]
     NotSubscribed(String),
 
+    /// No provider could supply the content
+    #[error("Content not found: {0}")]
+    ContentNotFound(String),
+
+    /// Fetched content did not hash to its ID
+    #[error("Content hash mismatch: {0}")]
+    ContentMismatch(String),
+
     /// Peer not found
     #[error("Peer not found: {0}")]
     PeerNotFound(String),
@@ -58,6 +70,14 @@ pub enum NetworkError {
     #[error("Network service already started")]
     AlreadyStarted,
 
+    /// The network service has stopped (or panicked) and can't take commands
+    #[error("Network service is not running")]
+    ServiceUnavailable,
+
+    /// A publish was refused for exceeding the topic's rate limit
+    #[error("Publishing to {topic} is rate limited; retry in {retry_after_ms}ms")]
+    RateLimited { topic: String, retry_after_ms: u64 },
+
     /// Timeout
     #[error("Operation timed out after {duration_ms}ms")]
     Timeout { duration_ms: u64 },
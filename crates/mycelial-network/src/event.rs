@@ -5,7 +5,14 @@
 
 use chrono::{DateTime, Utc};
 use libp2p::{gossipsub::MessageId, Multiaddr, PeerId};
+use mycelial_core::content::ContentId;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::peer::PeerBan;
+use crate::rate_limit::TopicRateUsage;
+use crate::validation::Rejection;
 
 /// Events emitted by the network service
 #[derive(Debug, Clone)]
@@ -27,6 +34,12 @@ pub enum NetworkEvent {
         address: Multiaddr,
     },
 
+    /// Stopped listening on an address, e.g. when an interface went down
+    ListenAddrExpired {
+        /// The address we no longer listen on
+        address: Multiaddr,
+    },
+
     /// A new peer connected
     PeerConnected {
         /// The connected peer's ID
@@ -57,6 +70,22 @@ pub enum NetworkEvent {
         observed_addr: Multiaddr,
     },
 
+    /// A ping to a peer completed
+    PingResult {
+        /// The peer pinged
+        peer_id: PeerId,
+        /// Round-trip time
+        rtt: Duration,
+    },
+
+    /// A peer stopped answering pings and was disconnected
+    PeerUnresponsive {
+        /// The peer
+        peer_id: PeerId,
+        /// Consecutive pings it failed
+        failures: u32,
+    },
+
     /// A gossipsub message was received
     MessageReceived {
         /// Message ID
@@ -83,6 +112,21 @@ pub enum NetworkEvent {
         topic: String,
     },
 
+    /// A topic was unsubscribed after going without traffic; it is joined
+    /// again the next time we publish to it
+    TopicIdle {
+        /// The topic
+        topic: String,
+        /// How long it had been silent
+        idle: Duration,
+    },
+
+    /// A topic unsubscribed for being idle was joined again for a publish
+    TopicResumed {
+        /// The topic
+        topic: String,
+    },
+
     /// A peer subscribed to a topic we're subscribed to
     PeerSubscribed {
         /// The peer's ID
@@ -113,6 +157,73 @@ pub enum NetworkEvent {
         key: Vec<u8>,
     },
 
+    /// A peer's DHT put or provider announcement was dropped because its
+    /// reputation is too low
+    RecordRejected {
+        /// The key
+        key: Vec<u8>,
+        /// The peer that sent the record
+        peer_id: PeerId,
+        /// The peer's reputation at the time
+        reputation: f64,
+    },
+
+    /// A message was relayed but not delivered because its source's
+    /// reputation is below `min_message_reputation` on a filtered topic
+    MessageFiltered {
+        /// Topic the message was published to
+        topic: String,
+        /// The message's source
+        peer_id: PeerId,
+        /// The source's reputation at the time
+        reputation: f64,
+    },
+
+    /// A message was refused by a validator, and neither relayed nor
+    /// delivered
+    ///
+    /// An application validator refusing a fragmented message only keeps it
+    /// from delivery, as its fragments have already been relayed.
+    /// Messages refused for their source's low reputation are reported as
+    /// [`MessageFiltered`](NetworkEvent::MessageFiltered) instead.
+    MessageRejected {
+        /// Topic the message was published to
+        topic: String,
+        /// The message's source, or the peer that relayed it if unsigned
+        peer_id: PeerId,
+        /// Name of the validator that refused it
+        validator: String,
+        /// Why it was refused
+        reason: Rejection,
+    },
+
+    /// A peer was banned; its connections are closed and refused until the
+    /// ban lifts
+    PeerBanned {
+        /// The banned peer
+        peer_id: PeerId,
+        /// The ban placed on it
+        ban: PeerBan,
+    },
+
+    /// A peer's ban was lifted by hand
+    PeerUnbanned {
+        /// The peer
+        peer_id: PeerId,
+    },
+
+    /// A chunk of content being fetched in several chunks arrived
+    ContentFetchProgress {
+        /// The content being fetched
+        id: ContentId,
+        /// The provider sending it
+        peer_id: PeerId,
+        /// Chunks received so far
+        received: u32,
+        /// Number of chunks in the content
+        total: u32,
+    },
+
     /// Peer discovered via mDNS
     MdnsDiscovered {
         /// Discovered peers
@@ -149,6 +260,22 @@ pub enum NetworkEvent {
         outbound: bool,
     },
 
+    /// First successful connection to one of the configured bootstrap peers
+    BootstrapConnected {
+        /// The bootstrap peer's ID
+        peer_id: PeerId,
+        /// The bootstrap address that was dialed
+        address: Multiaddr,
+    },
+
+    /// A Kademlia bootstrap refreshed another part of the routing table
+    BootstrapProgress {
+        /// Buckets still to refresh; 0 once the bootstrap is done
+        remaining: u32,
+        /// Peers now in the routing table
+        routing_table_size: usize,
+    },
+
     /// Connection closed
     ConnectionClosed {
         /// The peer's ID
@@ -158,6 +285,84 @@ pub enum NetworkEvent {
         /// Reason for closure
         cause: Option<String>,
     },
+
+    /// AutoNAT changed its view of our public reachability
+    NatStatusChanged {
+        /// The new status
+        status: NatStatus,
+    },
+
+    /// A reliable publish found no peers to send to and will be retried
+    PublishRetrying {
+        /// The topic
+        topic: String,
+        /// Attempts made so far
+        attempt: u32,
+        /// Delay before the next attempt
+        retry_in: Duration,
+    },
+
+    /// A reliable publish was given up on; the message was not sent
+    PublishFailed {
+        /// The topic
+        topic: String,
+        /// Attempts made
+        attempts: u32,
+        /// Why it was given up on
+        error: String,
+    },
+
+    /// Gossipsub grafted or pruned mesh peers on a topic we're subscribed to
+    MeshUpdated {
+        /// The topic
+        topic: String,
+        /// Peers in our mesh for the topic
+        mesh_peers: usize,
+        /// All known peers subscribed to the topic
+        all_peers: usize,
+    },
+}
+
+/// Gossipsub mesh state for one topic
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MeshStatus {
+    /// Peers in our mesh for the topic; publishes go to these
+    pub mesh_peers: usize,
+    /// All known peers subscribed to the topic
+    pub all_peers: usize,
+}
+
+impl MeshStatus {
+    /// Whether a publish on the topic will reach anyone
+    ///
+    /// Subscribers outside the mesh are only reached once gossipsub grafts
+    /// them, so a topic with subscribers but no mesh is not yet healthy.
+    pub fn is_formed(&self) -> bool {
+        self.mesh_peers > 0
+    }
+}
+
+/// Whether this node is dialable from the public internet, as probed by
+/// AutoNAT
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NatStatus {
+    /// Other peers can dial us directly
+    Public,
+    /// We are behind a NAT or firewall
+    Private,
+    /// Not enough probes yet
+    #[default]
+    Unknown,
+}
+
+impl From<&libp2p::autonat::NatStatus> for NatStatus {
+    fn from(status: &libp2p::autonat::NatStatus) -> Self {
+        match status {
+            libp2p::autonat::NatStatus::Public(_) => NatStatus::Public,
+            libp2p::autonat::NatStatus::Private => NatStatus::Private,
+            libp2p::autonat::NatStatus::Unknown => NatStatus::Unknown,
+        }
+    }
 }
 
 impl NetworkEvent {
@@ -168,8 +373,12 @@ impl NetworkEvent {
             NetworkEvent::PeerConnected { .. }
                 | NetworkEvent::PeerDisconnected { .. }
                 | NetworkEvent::PeerIdentified { .. }
+                | NetworkEvent::PeerUnresponsive { .. }
                 | NetworkEvent::ConnectionEstablished { .. }
                 | NetworkEvent::ConnectionClosed { .. }
+                | NetworkEvent::BootstrapConnected { .. }
+                | NetworkEvent::PeerBanned { .. }
+                | NetworkEvent::PeerUnbanned { .. }
         )
     }
 
@@ -185,6 +394,7 @@ impl NetworkEvent {
             NetworkEvent::MdnsDiscovered { .. }
                 | NetworkEvent::MdnsExpired { .. }
                 | NetworkEvent::RecordFound { .. }
+                | NetworkEvent::BootstrapProgress { .. }
         )
     }
 
@@ -194,11 +404,20 @@ impl NetworkEvent {
             NetworkEvent::PeerConnected { peer_id, .. } => Some(peer_id),
             NetworkEvent::PeerDisconnected { peer_id, .. } => Some(peer_id),
             NetworkEvent::PeerIdentified { peer_id, .. } => Some(peer_id),
+            NetworkEvent::PingResult { peer_id, .. } => Some(peer_id),
+            NetworkEvent::PeerUnresponsive { peer_id, .. } => Some(peer_id),
             NetworkEvent::PeerSubscribed { peer_id, .. } => Some(peer_id),
             NetworkEvent::PeerUnsubscribed { peer_id, .. } => Some(peer_id),
             NetworkEvent::Dialing { peer_id } => Some(peer_id),
             NetworkEvent::ConnectionEstablished { peer_id, .. } => Some(peer_id),
             NetworkEvent::ConnectionClosed { peer_id, .. } => Some(peer_id),
+            NetworkEvent::BootstrapConnected { peer_id, .. } => Some(peer_id),
+            NetworkEvent::RecordRejected { peer_id, .. } => Some(peer_id),
+            NetworkEvent::MessageFiltered { peer_id, .. } => Some(peer_id),
+            NetworkEvent::MessageRejected { peer_id, .. } => Some(peer_id),
+            NetworkEvent::PeerBanned { peer_id, .. } => Some(peer_id),
+            NetworkEvent::PeerUnbanned { peer_id } => Some(peer_id),
+            NetworkEvent::ContentFetchProgress { peer_id, .. } => Some(peer_id),
             NetworkEvent::MessageReceived { source, .. } => source.as_ref(),
             _ => None,
         }
@@ -222,4 +441,7 @@ pub struct NetworkStats {
     pub subscribed_topics: usize,
     /// Uptime in seconds
     pub uptime_secs: u64,
+    /// Publish rate limit usage of each rate-limited topic published to
+    #[serde(default)]
+    pub publish_rate: BTreeMap<String, TopicRateUsage>,
 }
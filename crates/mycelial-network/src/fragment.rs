@@ -0,0 +1,403 @@
+//! Fragmenting gossip messages larger than the transmit limit
+//!
+//! Gossipsub drops anything above `max_message_size`, so the service splits
+//! larger publishes into fragments that each fit and the receiving service
+//! buffers them until the last one arrives, then delivers the whole payload
+//! as a single `MessageReceived`. This is the IP counterpart of the LoRa
+//! chunking in `mycelial-meshtastic`.
+//!
+//! # Fragment format
+//!
+//! - Bytes 0-3: magic `MFRG`
+//! - Bytes 4-11: message ID (the start of the payload's
+//!   [`payload_id`](mycelial_core::message::payload_id))
+//! - Bytes 12-13: fragment index, big-endian
+//! - Bytes 14-15: total fragments, big-endian
+//! - Bytes 16-19: total payload length, big-endian
+//! - Bytes 20+: fragment data
+//!
+//! The message ID is derived from the payload, so publishing the same large
+//! payload twice yields the same fragments and gossipsub deduplicates them
+//! just as it would the unfragmented message.
+//!
+//! Incomplete messages are held in a bounded buffer: a peer can only have a
+//! few in flight, they expire after a timeout, and the oldest are evicted
+//! when the buffer is full, so a peer sending only partial fragments can't
+//! exhaust memory.
+
+use libp2p::PeerId;
+use mycelial_core::message::payload_id;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+use crate::error::{NetworkError, Result};
+
+/// Marks a gossip payload as a fragment
+const MAGIC: &[u8; 4] = b"MFRG";
+
+/// Fragment header size in bytes
+pub const HEADER_SIZE: usize = 20;
+
+/// Room left in each gossip message for the fragment header and the
+/// gossipsub envelope (source, sequence number, topic and signature)
+pub const ENVELOPE_OVERHEAD: usize = 1024;
+
+/// Incomplete messages a single source may have buffered at once
+const MAX_PENDING_PER_SOURCE: usize = 4;
+
+/// A decoded fragment header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Header {
+    message_id: u64,
+    index: u16,
+    total: u16,
+    total_len: u32,
+}
+
+/// Whether a gossip payload is a fragment rather than a whole message
+pub fn is_fragment(data: &[u8]) -> bool {
+    data.len() >= HEADER_SIZE && data.starts_with(MAGIC)
+}
+
+/// Split `data` into fragments carrying at most `fragment_size` bytes each
+///
+/// Fails if that would take more than `u16::MAX` fragments or the payload
+/// is longer than `u32::MAX` bytes.
+pub fn split(data: &[u8], fragment_size: usize) -> Result<Vec<Vec<u8>>> {
+    let fragment_size = fragment_size.max(1);
+    let total = data.len().div_ceil(fragment_size);
+    let max = fragment_size.saturating_mul(u16::MAX as usize);
+    if total > u16::MAX as usize || u32::try_from(data.len()).is_err() {
+        return Err(NetworkError::MessageTooLarge {
+            size: data.len(),
+            max: max.min(u32::MAX as usize),
+        });
+    }
+
+    let mut id = [0u8; 8];
+    id.copy_from_slice(&payload_id(data)[..8]);
+    let message_id = u64::from_be_bytes(id);
+
+    Ok(data
+        .chunks(fragment_size)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let mut fragment = Vec::with_capacity(HEADER_SIZE + chunk.len());
+            fragment.extend_from_slice(MAGIC);
+            fragment.extend_from_slice(&message_id.to_be_bytes());
+            fragment.extend_from_slice(&(index as u16).to_be_bytes());
+            fragment.extend_from_slice(&(total as u16).to_be_bytes());
+            fragment.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            fragment.extend_from_slice(chunk);
+            fragment
+        })
+        .collect())
+}
+
+/// Decode a fragment's header, returning it with the fragment data
+fn decode(data: &[u8]) -> Result<(Header, &[u8])> {
+    if !is_fragment(data) {
+        return Err(NetworkError::InvalidFragment("not a fragment".into()));
+    }
+    let u16_at = |at: usize| u16::from_be_bytes([data[at], data[at + 1]]);
+    let mut id = [0u8; 8];
+    id.copy_from_slice(&data[4..12]);
+    let header = Header {
+        message_id: u64::from_be_bytes(id),
+        index: u16_at(12),
+        total: u16_at(14),
+        total_len: u32::from_be_bytes([data[16], data[17], data[18], data[19]]),
+    };
+    let body = &data[HEADER_SIZE..];
+
+    if header.total == 0 || header.index >= header.total {
+        return Err(NetworkError::InvalidFragment(format!(
+            "fragment {} of {}",
+            header.index, header.total
+        )));
+    }
+    // Every fragment carries at least one byte
+    if body.is_empty() || header.total as usize > header.total_len as usize {
+        return Err(NetworkError::InvalidFragment(format!(
+            "{} fragments can't make up {} bytes",
+            header.total, header.total_len
+        )));
+    }
+    Ok((header, body))
+}
+
+/// A message whose fragments are still arriving
+struct Pending {
+    total: u16,
+    total_len: u32,
+    fragments: BTreeMap<u16, Vec<u8>>,
+    received_bytes: usize,
+    started: Instant,
+}
+
+/// Buffers fragments until their message is complete
+///
+/// Messages are keyed by their source and message ID, so fragments from
+/// different peers never mix.
+pub struct FragmentBuffer {
+    /// Largest payload that will be reassembled
+    max_message_size: usize,
+    /// Total fragment bytes held across all incomplete messages
+    max_buffered_bytes: usize,
+    /// How long an incomplete message is kept
+    timeout: Duration,
+    pending: HashMap<(Option<PeerId>, u64), Pending>,
+    buffered_bytes: usize,
+}
+
+impl FragmentBuffer {
+    /// Create a buffer reassembling payloads of up to `max_message_size`
+    /// bytes, holding at most `max_buffered_bytes` and dropping incomplete
+    /// messages after `timeout`
+    pub fn new(max_message_size: usize, max_buffered_bytes: usize, timeout: Duration) -> Self {
+        Self {
+            max_message_size,
+            max_buffered_bytes,
+            timeout,
+            pending: HashMap::new(),
+            buffered_bytes: 0,
+        }
+    }
+
+    /// Add a fragment received from `source`
+    ///
+    /// Returns the whole payload once its last fragment arrives, `None`
+    /// while fragments are missing, and an error for a fragment that is
+    /// malformed, too large or inconsistent with the others (in which case
+    /// its message is dropped).
+    pub fn insert(
+        &mut self,
+        source: Option<PeerId>,
+        fragment: &[u8],
+        now: Instant,
+    ) -> Result<Option<Vec<u8>>> {
+        self.expire(now);
+
+        let (header, body) = decode(fragment)?;
+        if header.total_len as usize > self.max_message_size {
+            return Err(NetworkError::MessageTooLarge {
+                size: header.total_len as usize,
+                max: self.max_message_size,
+            });
+        }
+
+        let key = (source, header.message_id);
+        if !self.pending.contains_key(&key) {
+            let from_source = self.pending.keys().filter(|(s, _)| *s == source).count();
+            if from_source >= MAX_PENDING_PER_SOURCE {
+                return Err(NetworkError::InvalidFragment(format!(
+                    "{:?} already has {} incomplete messages",
+                    source, from_source
+                )));
+            }
+        }
+        self.make_room(body.len(), &key)?;
+
+        let pending = self.pending.entry(key).or_insert_with(|| Pending {
+            total: header.total,
+            total_len: header.total_len,
+            fragments: BTreeMap::new(),
+            received_bytes: 0,
+            started: now,
+        });
+        if pending.total != header.total || pending.total_len != header.total_len {
+            self.remove(&key);
+            return Err(NetworkError::InvalidFragment(
+                "fragment disagrees with earlier fragments of its message".into(),
+            ));
+        }
+        if pending.fragments.contains_key(&header.index) {
+            return Ok(None);
+        }
+        if pending.received_bytes + body.len() > pending.total_len as usize {
+            self.remove(&key);
+            return Err(NetworkError::InvalidFragment(
+                "fragments exceed the declared length".into(),
+            ));
+        }
+
+        pending.fragments.insert(header.index, body.to_vec());
+        pending.received_bytes += body.len();
+        self.buffered_bytes += body.len();
+        if pending.fragments.len() < pending.total as usize {
+            return Ok(None);
+        }
+
+        let pending = self.remove(&key).expect("pending message was just updated");
+        if pending.received_bytes != pending.total_len as usize {
+            return Err(NetworkError::InvalidFragment(
+                "fragments fall short of the declared length".into(),
+            ));
+        }
+        let data: Vec<u8> = pending.fragments.into_values().flatten().collect();
+        if payload_id(&data)[..8] != header.message_id.to_be_bytes() {
+            return Err(NetworkError::InvalidFragment(
+                "reassembled payload doesn't match its message ID".into(),
+            ));
+        }
+        debug!(
+            "Reassembled {} byte message from {} fragments",
+            data.len(),
+            header.total
+        );
+        Ok(Some(data))
+    }
+
+    /// Number of messages still waiting for fragments
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Fragment bytes currently buffered
+    pub fn buffered_bytes(&self) -> usize {
+        self.buffered_bytes
+    }
+
+    /// Drop incomplete messages older than the timeout
+    fn expire(&mut self, now: Instant) {
+        let expired: Vec<_> = self
+            .pending
+            .iter()
+            .filter(|(_, p)| now.saturating_duration_since(p.started) >= self.timeout)
+            .map(|(key, _)| *key)
+            .collect();
+        for key in expired {
+            if let Some(pending) = self.remove(&key) {
+                debug!(
+                    "Dropped incomplete message from {:?} ({}/{} fragments)",
+                    key.0,
+                    pending.fragments.len(),
+                    pending.total
+                );
+            }
+        }
+    }
+
+    /// Evict the oldest other messages until `len` more bytes fit
+    fn make_room(&mut self, len: usize, keep: &(Option<PeerId>, u64)) -> Result<()> {
+        while self.buffered_bytes + len > self.max_buffered_bytes {
+            let oldest = self
+                .pending
+                .iter()
+                .filter(|(key, _)| *key != keep)
+                .min_by_key(|(_, p)| p.started)
+                .map(|(key, _)| *key);
+            match oldest {
+                Some(key) => {
+                    debug!("Fragment buffer full, evicting message from {:?}", key.0);
+                    self.remove(&key);
+                }
+                None => {
+                    return Err(NetworkError::InvalidFragment(
+                        "message doesn't fit in the fragment buffer".into(),
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn remove(&mut self, key: &(Option<PeerId>, u64)) -> Option<Pending> {
+        let pending = self.pending.remove(key)?;
+        self.buffered_bytes -= pending.received_bytes;
+        Some(pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buffer() -> FragmentBuffer {
+        FragmentBuffer::new(1024, 2048, Duration::from_secs(30))
+    }
+
+    #[test]
+    fn test_split_and_reassemble_out_of_order() {
+        let data: Vec<u8> = (0..1000).map(|i| i as u8).collect();
+        let mut fragments = split(&data, 300).unwrap();
+        assert_eq!(fragments.len(), 4);
+        assert!(fragments.iter().all(|f| is_fragment(f)));
+        assert!(!is_fragment(&data));
+
+        fragments.reverse();
+        let mut buffer = buffer();
+        let source = Some(PeerId::random());
+        let now = Instant::now();
+        for fragment in &fragments[..3] {
+            assert_eq!(buffer.insert(source, fragment, now).unwrap(), None);
+        }
+        // A duplicate fragment is ignored
+        assert_eq!(buffer.insert(source, &fragments[0], now).unwrap(), None);
+        assert_eq!(buffer.pending_count(), 1);
+
+        assert_eq!(
+            buffer.insert(source, &fragments[3], now).unwrap(),
+            Some(data)
+        );
+        assert_eq!(buffer.pending_count(), 0);
+        assert_eq!(buffer.buffered_bytes(), 0);
+    }
+
+    #[test]
+    fn test_fragments_from_different_sources_do_not_mix() {
+        let data = vec![7u8; 600];
+        let fragments = split(&data, 300).unwrap();
+        let (alice, bob) = (Some(PeerId::random()), Some(PeerId::random()));
+        let now = Instant::now();
+
+        let mut buffer = buffer();
+        assert_eq!(buffer.insert(alice, &fragments[0], now).unwrap(), None);
+        assert_eq!(buffer.insert(bob, &fragments[1], now).unwrap(), None);
+        assert_eq!(buffer.pending_count(), 2);
+        assert_eq!(
+            buffer.insert(alice, &fragments[1], now).unwrap(),
+            Some(data)
+        );
+    }
+
+    #[test]
+    fn test_partial_messages_are_bounded() {
+        let mut buffer = buffer();
+        let source = Some(PeerId::random());
+        let now = Instant::now();
+
+        // Oversized and malformed fragments are refused outright
+        let big = split(&[1u8; 2000], 500).unwrap();
+        assert!(buffer.insert(source, &big[0], now).is_err());
+        let mut bad = split(&[1u8; 600], 300).unwrap().remove(0);
+        bad[14..16].copy_from_slice(&0u16.to_be_bytes());
+        assert!(buffer.insert(source, &bad, now).is_err());
+
+        // A source can only have a few messages in flight
+        for i in 0..MAX_PENDING_PER_SOURCE {
+            let fragments = split(&[i as u8; 400], 200).unwrap();
+            assert_eq!(buffer.insert(source, &fragments[0], now).unwrap(), None);
+        }
+        let fragments = split(&[0xff; 400], 200).unwrap();
+        assert!(buffer.insert(source, &fragments[0], now).is_err());
+        assert_eq!(buffer.buffered_bytes(), 800);
+
+        // A full buffer evicts the oldest messages
+        let other = Some(PeerId::random());
+        let later = now + Duration::from_secs(1);
+        for byte in [2u8, 3, 4] {
+            let fragments = split(&[byte; 1000], 500).unwrap();
+            assert_eq!(buffer.insert(other, &fragments[0], later).unwrap(), None);
+        }
+        assert_eq!(buffer.pending_count(), 5);
+        assert_eq!(buffer.buffered_bytes(), 1900);
+
+        // Incomplete messages expire
+        let expired = later + Duration::from_secs(30);
+        let fragments = split(&[5u8; 400], 200).unwrap();
+        assert_eq!(buffer.insert(other, &fragments[0], expired).unwrap(), None);
+        assert_eq!(buffer.pending_count(), 1);
+    }
+}
@@ -11,6 +11,10 @@
 //! - **Kademlia DHT**: Distributed hash table for peer discovery and data storage
 //! - **mDNS**: Local network peer discovery
 //! - **Identify**: Peer identification protocol
+//! - **AutoNAT**: Public reachability probing, with relay reservations when
+//!   we are behind a NAT
+//! - **Content exchange**: Chunked, hash-verified content fetches from
+//!   providers found in the DHT
 //! - **Noise**: Encryption for all connections
 //! - **QUIC/TCP**: Multiple transport options
 //!
@@ -52,12 +56,20 @@
 
 pub mod behaviour;
 pub mod config;
+pub mod content;
 pub mod economics;
 pub mod error;
 pub mod event;
+pub mod fragment;
 pub mod peer;
+pub mod rate_limit;
 pub mod service;
 pub mod transport;
+pub mod validation;
+
+// Synchronous wrapper for non-async callers
+#[cfg(feature = "blocking")]
+pub mod blocking;
 
 // ENR bridge module (requires univrs-compat feature for full univrs-enr integration)
 #[cfg(feature = "univrs-compat")]
@@ -72,23 +84,40 @@ pub mod raft;
 
 // Re-exports
 pub use behaviour::{topics, MycelialBehaviour, MycelialBehaviourEvent};
-pub use config::NetworkConfig;
+pub use config::{IpStack, NetworkConfig, RateLimit, TopicAcl};
+pub use content::{ContentRequest, ContentResponse, MemoryContentStore, CONTENT_PROTOCOL};
 pub use economics::{
     economics_topics, is_economics_topic, parse_economics_message, EconomicsEvent, EconomicsHandler,
 };
 pub use error::{NetworkError, Result};
-pub use event::{NetworkEvent, NetworkStats};
-pub use peer::{ConnectionState, PeerInfo, PeerManager};
-pub use service::{NetworkCommand, NetworkHandle, NetworkService};
-pub use transport::{create_transport, extract_peer_id, parse_multiaddr, TransportConfig};
+pub use event::{MeshStatus, NatStatus, NetworkEvent, NetworkStats};
+pub use peer::{ConnectionState, PeerBan, PeerInfo, PeerManager, SEPTAL_BAN_REASON};
+pub use rate_limit::TopicRateUsage;
+pub use service::{NetworkCommand, NetworkHandle, NetworkService, RecordReputationFn};
+pub use transport::{
+    create_transport, create_transport_with_relay, extract_peer_id, is_dns_multiaddr,
+    parse_multiaddr, resolve_dnsaddr, TransportConfig,
+};
+pub use validation::{MessageValidator, Rejection, ValidationPipeline};
 
 // Partition testing re-exports
 pub use partition::{PartitionId, PartitionSimulator, PartitionStats};
 
 // Test utilities - available with test-utils feature or in tests
-// TODO: Add test_utils module to service when needed
-// #[cfg(any(test, feature = "test-utils"))]
-// pub use service::test_utils;
+#[cfg(any(test, feature = "test-utils"))]
+pub use service::test_utils;
+
+/// Optional features this build of the crate was compiled with
+pub const FEATURES: &[&str] = &[
+    #[cfg(feature = "blocking")]
+    "blocking",
+    #[cfg(feature = "univrs-compat")]
+    "univrs-compat",
+    #[cfg(feature = "openraft")]
+    "openraft",
+    #[cfg(feature = "webrtc")]
+    "webrtc",
+];
 
 // Re-export libp2p types commonly used
 pub use libp2p::identity::Keypair;
@@ -111,5 +140,296 @@ mod tests {
     fn test_local_test_config() {
         let config = NetworkConfig::local_test(5000);
         assert_eq!(config.listen_addresses[0], "/ip4/127.0.0.1/tcp/5000");
+        assert_eq!(config.transports(), vec!["tcp"]);
+    }
+
+    #[test]
+    fn test_bootstrap_backoff() {
+        let config = NetworkConfig::default();
+        assert_eq!(config.bootstrap_backoff(1).as_secs(), 1);
+        assert_eq!(config.bootstrap_backoff(2).as_secs(), 2);
+        assert_eq!(config.bootstrap_backoff(4).as_secs(), 8);
+        assert_eq!(config.bootstrap_backoff(10).as_secs(), 60);
+        assert_eq!(config.bootstrap_backoff(100).as_secs(), 60);
+    }
+
+    #[test]
+    fn test_config_requires_a_transport() {
+        let mut config = NetworkConfig::default();
+        assert!(config.validate().is_ok());
+
+        config.enable_quic = false;
+        assert!(config.validate().is_ok());
+
+        config.enable_tcp = false;
+        assert!(matches!(config.validate(), Err(NetworkError::Config(_))));
+    }
+
+    #[test]
+    fn test_dual_stack_listen_addresses() {
+        let config = NetworkConfig::default().with_ip_stack(IpStack::Dual, 4001, 4002);
+        assert_eq!(
+            config.listen_addresses,
+            vec![
+                "/ip4/0.0.0.0/tcp/4001",
+                "/ip6/::/tcp/4001",
+                "/ip4/0.0.0.0/udp/4002/quic-v1",
+                "/ip6/::/udp/4002/quic-v1",
+            ]
+        );
+        assert!(config.validate().is_ok());
+
+        assert_eq!(
+            IpStack::V6.listen_addresses("/udp/0/webrtc-direct"),
+            vec!["/ip6/::/udp/0/webrtc-direct"]
+        );
+    }
+
+    #[test]
+    fn test_kademlia_intervals() {
+        let mut config = NetworkConfig::default();
+        assert_eq!(
+            config.kademlia_bootstrap_interval(),
+            Some(std::time::Duration::from_secs(300))
+        );
+
+        config.kademlia_refresh_interval_secs = 0;
+        assert_eq!(config.kademlia_refresh_interval(), None);
+    }
+
+    #[test]
+    fn test_fragmentation_config() {
+        let mut config = NetworkConfig::default();
+        assert_eq!(
+            config.fragment_size(),
+            config.max_message_size - fragment::ENVELOPE_OVERHEAD
+        );
+
+        config.fragment_buffer_size = config.max_fragmented_message_size - 1;
+        assert!(config.validate().is_err());
+
+        config.max_message_size = 512;
+        config.fragment_buffer_size = config.max_fragmented_message_size;
+        assert!(config.validate().is_err());
+
+        // Without fragmentation the small message size is fine
+        config.max_fragmented_message_size = 0;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_topic_idle_timeout() {
+        let mut config = NetworkConfig::default();
+        assert_eq!(config.topic_idle_timeout(), None);
+
+        config.topic_idle_timeout_secs = 3600;
+        assert_eq!(
+            config.topic_idle_timeout(),
+            Some(std::time::Duration::from_secs(3600))
+        );
+    }
+
+    #[test]
+    fn test_ban_config() {
+        let mut config = NetworkConfig::default();
+        assert_eq!(config.ban_reputation, 0.0);
+        assert_eq!(config.ban_duration(), std::time::Duration::from_secs(3600));
+
+        config.ban_reputation = 1.5;
+        assert!(config.validate().is_err());
+
+        config.ban_reputation = 0.2;
+        config.ban_duration_secs = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_gossip_timing_config() {
+        let mut config = NetworkConfig::default();
+        assert_eq!(
+            config.heartbeat_interval(),
+            std::time::Duration::from_secs(1)
+        );
+        assert!(config.validate().is_ok());
+
+        // A slow link: fewer heartbeats, with history and dedup to match
+        config.heartbeat_interval_ms = 15_000;
+        assert!(config.validate().is_err());
+        config.duplicate_cache_time_secs = 120;
+        assert!(config.validate().is_ok());
+
+        config.history_gossip = config.history_length + 1;
+        assert!(config.validate().is_err());
+        config.history_gossip = 3;
+
+        config.heartbeat_interval_ms = 10;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_rejects_zero_timeouts() {
+        let mut config = NetworkConfig::default();
+        assert!(config.validate().is_ok());
+
+        config.handshake_timeout_secs = 0;
+        assert!(config.validate().is_err());
+
+        let config = NetworkConfig {
+            idle_timeout_secs: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = NetworkConfig {
+            ping_max_failures: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_webrtc_listen_address_requires_transport() {
+        let mut config = NetworkConfig::default();
+        config
+            .listen_addresses
+            .push("/ip4/0.0.0.0/udp/4002/webrtc-direct".to_string());
+        assert!(config.validate().is_err());
+
+        config.enable_webrtc = true;
+        assert_eq!(config.validate().is_ok(), cfg!(feature = "webrtc"));
+    }
+
+    #[test]
+    fn test_economics_requires_univrs_compat() {
+        let mut config = NetworkConfig::default();
+        assert_eq!(config.enable_economics, cfg!(feature = "univrs-compat"));
+        assert!(config.validate().is_ok());
+
+        config.enable_economics = true;
+        assert_eq!(config.validate().is_ok(), cfg!(feature = "univrs-compat"));
+    }
+
+    #[test]
+    fn test_reputation_thresholds_range() {
+        let mut config = NetworkConfig::default();
+        assert_eq!(config.min_record_reputation, 0.0);
+        assert_eq!(config.min_message_reputation, 0.0);
+        assert!(config.reputation_filtered_topics.is_empty());
+
+        config.min_record_reputation = 0.6;
+        config.min_message_reputation = 0.4;
+        config
+            .reputation_filtered_topics
+            .insert("/mycelial/1.0.0/credit".into());
+        assert!(config.validate().is_ok());
+
+        config.min_record_reputation = 1.5;
+        assert!(config.validate().is_err());
+
+        config.min_record_reputation = 0.6;
+        config.min_message_reputation = -0.1;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_topic_acl() {
+        let allowed = libp2p::PeerId::random();
+        let other = libp2p::PeerId::random();
+
+        let acl = TopicAcl::allowlist([allowed]);
+        assert!(acl.check(&allowed, || 0.0).is_ok());
+        assert!(acl.check(&other, || 1.0).is_err());
+
+        let acl = acl.with_min_reputation(0.7);
+        assert!(acl.check(&other, || 0.8).is_ok());
+        assert!(acl.check(&other, || 0.6).is_err());
+
+        let mut config = NetworkConfig::default();
+        config
+            .topic_acls
+            .insert(topics::GOVERNANCE.to_string(), acl);
+        assert!(config.validate().is_ok());
+
+        config.topic_acls.insert(
+            topics::ECONOMICS.to_string(),
+            TopicAcl::default().with_min_reputation(2.0),
+        );
+        assert!(config.validate().is_err());
+
+        config.topic_acls.remove(topics::ECONOMICS);
+        config
+            .topic_acls
+            .get_mut(topics::GOVERNANCE)
+            .unwrap()
+            .allowed_peers
+            .insert("not-a-peer-id".to_string());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_mesh_params_validation() {
+        let mut config = NetworkConfig::default();
+        assert!(config.validate().is_ok());
+
+        // Gossipsub defaults for large deployments
+        config.mesh_n = 6;
+        config.mesh_n_low = 4;
+        config.mesh_n_high = 12;
+        config.mesh_outbound_min = 2;
+        assert!(config.validate().is_ok());
+
+        config.mesh_n_low = 8;
+        assert!(config.validate().is_err());
+
+        config.mesh_n_low = 4;
+        config.mesh_outbound_min = 4;
+        assert!(config.validate().is_err());
+
+        config.mesh_outbound_min = 2;
+        config.gossip_factor = 1.5;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_explicit_peers() {
+        let peer = libp2p::PeerId::random();
+        let mut config = NetworkConfig::default();
+        config.explicit_peers = vec![format!("/ip4/10.0.0.1/tcp/4001/p2p/{}", peer)];
+        assert!(config.validate().is_ok());
+
+        let explicit = config.explicit_peer_addresses().unwrap();
+        assert_eq!(explicit.len(), 1);
+        assert_eq!(explicit[0].0, peer);
+
+        // Without a peer ID there is nothing to tell gossipsub
+        config.explicit_peers = vec!["/ip4/10.0.0.1/tcp/4001".to_string()];
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_publish_rate_limits() {
+        let mut config = NetworkConfig::default();
+        assert_eq!(config.rate_limit_for("chat"), None);
+
+        config.publish_rate_limit = Some(RateLimit::new(5.0, 10));
+        config
+            .topic_rate_limits
+            .insert("economics".to_string(), RateLimit::new(50.0, 100));
+        assert!(config.validate().is_ok());
+        assert_eq!(config.rate_limit_for("chat"), Some(RateLimit::new(5.0, 10)));
+        assert_eq!(
+            config.rate_limit_for("economics"),
+            Some(RateLimit::new(50.0, 100))
+        );
+
+        config.publish_rate_limit = Some(RateLimit::new(0.0, 10));
+        assert!(config.validate().is_err());
+        config.publish_rate_limit = Some(RateLimit::new(5.0, 0));
+        assert!(config.validate().is_err());
+        config.publish_rate_limit = None;
+        config
+            .topic_rate_limits
+            .insert("chat".to_string(), RateLimit::new(f64::NAN, 1));
+        assert!(config.validate().is_err());
     }
 }
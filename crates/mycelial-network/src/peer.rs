@@ -2,14 +2,39 @@
 //!
 //! This module provides peer tracking, connection state management,
 //! and peer scoring.
+//!
+//! Besides the interaction score, each peer has a connection quality built
+//! from its smoothed ping round-trip time and how often it has connected or
+//! disconnected in the last hour. Quality steers which peers gossipsub keeps
+//! in its mesh and which address a peer is dialed on.
+//!
+//! Peers can also be banned, for a while or for good. The service refuses
+//! connections from banned peers and drops their messages.
 
 use chrono::{DateTime, Utc};
 use libp2p::{Multiaddr, PeerId};
+use mycelial_core::location::{cluster_by_location, Location, PeerCluster};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::time::Duration;
 
+/// Weight of each new RTT sample in the smoothed RTT
+const RTT_SMOOTHING: f64 = 0.2;
+
+/// RTT (ms) at which the latency half of quality drops to 0.5
+const QUALITY_RTT_MS: f64 = 100.0;
+
+/// Connects plus disconnects per hour at which the stability half of
+/// quality drops to 0.5
+const QUALITY_CHURN_PER_HOUR: f64 = 4.0;
+
+/// Quality of a peer nothing is known about
+pub const NEUTRAL_QUALITY: f64 = 0.5;
+
+/// Window churn is counted over, in seconds
+const CHURN_WINDOW_SECS: i64 = 60 * 60;
+
 /// Information about a connected peer
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PeerInfo {
@@ -35,6 +60,18 @@ pub struct PeerInfo {
     pub successful_interactions: u64,
     /// Number of failed interactions
     pub failed_interactions: u64,
+    /// Smoothed ping round-trip time in milliseconds, once measured
+    #[serde(default)]
+    pub rtt_ms: Option<f64>,
+    /// Smoothed ping round-trip time per address, in milliseconds
+    #[serde(default)]
+    pub address_rtt_ms: HashMap<String, f64>,
+    /// When the peer connected or disconnected within the last hour
+    #[serde(default)]
+    pub connection_changes: VecDeque<DateTime<Utc>>,
+    /// Where the peer says it is, if it has told us
+    #[serde(default)]
+    pub location: Option<Location>,
 }
 
 impl PeerInfo {
@@ -53,6 +90,10 @@ impl PeerInfo {
             score: 0.5, // Neutral starting score
             successful_interactions: 0,
             failed_interactions: 0,
+            rtt_ms: None,
+            address_rtt_ms: HashMap::new(),
+            connection_changes: VecDeque::new(),
+            location: None,
         }
     }
 
@@ -94,6 +135,93 @@ impl PeerInfo {
         }
     }
 
+    /// Fold a ping round-trip time into the smoothed RTT
+    ///
+    /// `addr` is the address of the connection the ping went over, if known.
+    pub fn record_rtt(&mut self, addr: Option<&Multiaddr>, rtt: Duration) {
+        let sample = rtt.as_secs_f64() * 1000.0;
+        let smooth = |current: Option<f64>| match current {
+            Some(current) => current + RTT_SMOOTHING * (sample - current),
+            None => sample,
+        };
+
+        self.rtt_ms = Some(smooth(self.rtt_ms));
+        if let Some(addr) = addr {
+            let addr = addr.to_string();
+            let rtt = smooth(self.address_rtt_ms.get(&addr).copied());
+            self.address_rtt_ms.insert(addr, rtt);
+        }
+    }
+
+    /// Note that the peer connected or disconnected
+    pub fn record_connection_change(&mut self) {
+        let now = Utc::now();
+        let window = chrono::Duration::seconds(CHURN_WINDOW_SECS);
+        self.connection_changes.push_back(now);
+        while self
+            .connection_changes
+            .front()
+            .is_some_and(|at| now.signed_duration_since(*at) > window)
+        {
+            self.connection_changes.pop_front();
+        }
+    }
+
+    /// Connects plus disconnects in the last hour
+    pub fn churn_per_hour(&self) -> usize {
+        let now = Utc::now();
+        let window = chrono::Duration::seconds(CHURN_WINDOW_SECS);
+        self.connection_changes
+            .iter()
+            .filter(|at| now.signed_duration_since(**at) <= window)
+            .count()
+    }
+
+    /// Connection quality from 0.0 (slow and flapping) to 1.0
+    ///
+    /// The average of a latency factor, which halves at 100ms of smoothed
+    /// RTT (and is neutral before the first ping), and a stability factor,
+    /// which halves at four connects or disconnects an hour.
+    pub fn quality(&self) -> f64 {
+        let latency = self
+            .rtt_ms
+            .map(|rtt| QUALITY_RTT_MS / (QUALITY_RTT_MS + rtt))
+            .unwrap_or(NEUTRAL_QUALITY);
+        let churn = self.churn_per_hour() as f64;
+        let stability = QUALITY_CHURN_PER_HOUR / (QUALITY_CHURN_PER_HOUR + churn);
+        (latency + stability) / 2.0
+    }
+
+    /// The address to dial this peer on
+    ///
+    /// The known address with the lowest measured RTT, or the most recently
+    /// learned address if none has been measured. IPv4 and IPv6 addresses
+    /// are treated alike, except that unmeasured IPv6 link-local addresses
+    /// without a scope are only used when nothing else is known.
+    pub fn best_address(&self) -> Option<Multiaddr> {
+        let measured = self
+            .addresses
+            .iter()
+            .filter_map(|addr| Some((addr, *self.address_rtt_ms.get(addr)?)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .and_then(|(addr, _)| addr.parse().ok());
+        if measured.is_some() {
+            return measured;
+        }
+
+        let known: Vec<Multiaddr> = self
+            .addresses
+            .iter()
+            .filter_map(|addr| crate::transport::parse_multiaddr(addr).ok())
+            .collect();
+        known
+            .iter()
+            .rev()
+            .find(|addr| !crate::transport::is_unscoped_link_local(addr))
+            .or_else(|| known.last())
+            .cloned()
+    }
+
     /// Check if peer is trusted (score above threshold)
     pub fn is_trusted(&self, threshold: f64) -> bool {
         self.score >= threshold
@@ -120,10 +248,48 @@ pub enum ConnectionState {
     Banned,
 }
 
+/// Reason given for bans placed because a peer's septal gate closed
+pub const SEPTAL_BAN_REASON: &str = "septal gate closed";
+
+/// A ban on a peer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerBan {
+    /// The banned peer
+    pub peer_id: String,
+    /// Why it was banned
+    pub reason: String,
+    /// When the ban was placed
+    pub banned_at: DateTime<Utc>,
+    /// When the ban lifts, or `None` if it lasts until lifted by hand
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl PeerBan {
+    /// Ban `peer_id` from now for `duration` (`None`, or a duration too
+    /// long to represent, for good)
+    pub fn new(peer_id: PeerId, duration: Option<Duration>, reason: impl Into<String>) -> Self {
+        let banned_at = Utc::now();
+        Self {
+            peer_id: peer_id.to_string(),
+            reason: reason.into(),
+            banned_at,
+            until: duration
+                .and_then(|d| banned_at.checked_add_signed(chrono::Duration::from_std(d).ok()?)),
+        }
+    }
+
+    /// Whether the ban is still in force at `now`
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.until.is_none_or(|until| until > now)
+    }
+}
+
 /// Manages known peers and their state
 pub struct PeerManager {
     /// Known peers
     peers: RwLock<HashMap<PeerId, PeerInfo>>,
+    /// Banned peers, including bans that have since expired
+    bans: RwLock<HashMap<PeerId, PeerBan>>,
     /// Maximum number of peers to track
     max_peers: usize,
     /// Trust threshold for considering a peer trusted
@@ -135,6 +301,7 @@ impl PeerManager {
     pub fn new(max_peers: usize, trust_threshold: f64) -> Self {
         Self {
             peers: RwLock::new(HashMap::new()),
+            bans: RwLock::new(HashMap::new()),
             max_peers,
             trust_threshold,
         }
@@ -165,8 +332,14 @@ impl PeerManager {
     }
 
     /// Set peer connection state
+    ///
+    /// Moving into or out of `Connected` counts toward the peer's churn.
     pub fn set_state(&self, peer_id: PeerId, state: ConnectionState) {
         self.update(peer_id, |info| {
+            let connected = ConnectionState::Connected;
+            if (info.state == connected) != (state == connected) {
+                info.record_connection_change();
+            }
             info.state = state;
             info.touch();
         });
@@ -200,6 +373,51 @@ impl PeerManager {
         });
     }
 
+    /// Record a ping round-trip time
+    pub fn record_rtt(&self, peer_id: PeerId, addr: Option<&Multiaddr>, rtt: Duration) {
+        self.update(peer_id, |info| info.record_rtt(addr, rtt));
+    }
+
+    /// Connection quality of a peer, or [`NEUTRAL_QUALITY`] if unknown
+    pub fn quality(&self, peer_id: &PeerId) -> f64 {
+        self.peers
+            .read()
+            .get(peer_id)
+            .map(PeerInfo::quality)
+            .unwrap_or(NEUTRAL_QUALITY)
+    }
+
+    /// Connection quality of every known peer
+    pub fn qualities(&self) -> HashMap<PeerId, f64> {
+        self.peers
+            .read()
+            .iter()
+            .map(|(id, info)| (*id, info.quality()))
+            .collect()
+    }
+
+    /// Record where a peer says it is
+    pub fn set_location(&self, peer_id: PeerId, location: Location) {
+        self.update(peer_id, |info| info.location = Some(location));
+    }
+
+    /// Known peers grouped by the geohash cell of their location, see
+    /// [`cluster_by_location`]
+    pub fn clusters(&self, precision: usize) -> Vec<PeerCluster> {
+        let peers = self.peers.read();
+        cluster_by_location(
+            peers
+                .values()
+                .map(|info| (info.peer_id.clone(), info.location.as_ref())),
+            precision,
+        )
+    }
+
+    /// The address to dial a peer on, see [`PeerInfo::best_address`]
+    pub fn best_address(&self, peer_id: &PeerId) -> Option<Multiaddr> {
+        self.peers.read().get(peer_id)?.best_address()
+    }
+
     /// Record a successful interaction
     pub fn record_success(&self, peer_id: PeerId) {
         self.update(peer_id, |info| info.record_success());
@@ -259,21 +477,59 @@ impl PeerManager {
         self.peers.read().len()
     }
 
-    /// Ban a peer
-    pub fn ban(&self, peer_id: PeerId) {
+    /// Ban a peer for `duration`, or until unbanned if `None`
+    ///
+    /// Replaces any earlier ban on the peer and zeroes its score.
+    pub fn ban(&self, peer_id: PeerId, duration: Option<Duration>, reason: &str) -> PeerBan {
+        let ban = PeerBan::new(peer_id, duration, reason);
+        self.restore_ban(peer_id, ban.clone());
+        ban
+    }
+
+    /// Put back a ban placed earlier, e.g. one loaded from storage
+    pub fn restore_ban(&self, peer_id: PeerId, ban: PeerBan) {
         self.update(peer_id, |info| {
             info.state = ConnectionState::Banned;
             info.score = 0.0;
         });
+        self.bans.write().insert(peer_id, ban);
+    }
+
+    /// Lift a peer's ban, returning it if there was one in force
+    pub fn unban(&self, peer_id: &PeerId) -> Option<PeerBan> {
+        let ban = self.bans.write().remove(peer_id)?;
+        self.peers.write().entry(*peer_id).and_modify(|info| {
+            if info.state == ConnectionState::Banned {
+                info.state = ConnectionState::Disconnected;
+            }
+        });
+        ban.is_active(Utc::now()).then_some(ban)
     }
 
     /// Check if a peer is banned
     pub fn is_banned(&self, peer_id: &PeerId) -> bool {
-        self.peers
+        self.ban_of(peer_id).is_some()
+    }
+
+    /// The ban in force on a peer, if any
+    pub fn ban_of(&self, peer_id: &PeerId) -> Option<PeerBan> {
+        self.bans
             .read()
             .get(peer_id)
-            .map(|info| info.state == ConnectionState::Banned)
-            .unwrap_or(false)
+            .filter(|ban| ban.is_active(Utc::now()))
+            .cloned()
+    }
+
+    /// Every ban in force, most recent first
+    ///
+    /// Expired bans are forgotten along the way.
+    pub fn bans(&self) -> Vec<PeerBan> {
+        let now = Utc::now();
+        let mut bans = self.bans.write();
+        bans.retain(|_, ban| ban.is_active(now));
+        let mut active: Vec<PeerBan> = bans.values().cloned().collect();
+        active.sort_by(|a, b| b.banned_at.cmp(&a.banned_at));
+        active
     }
 
     /// Prune stale peers
@@ -355,7 +611,92 @@ mod tests {
         assert_eq!(manager.connected_count(), 1);
 
         // Ban
-        manager.ban(peer_id);
+        manager.ban(peer_id, None, "spam");
         assert!(manager.is_banned(&peer_id));
+        assert_eq!(manager.get_state(&peer_id), Some(ConnectionState::Banned));
+    }
+
+    #[test]
+    fn test_peer_clusters() {
+        let manager = PeerManager::default();
+        let (near, also_near, unplaced) = (random_peer_id(), random_peer_id(), random_peer_id());
+
+        manager.set_location(near, Location::new(51.5074, -0.1278));
+        manager.set_location(also_near, Location::new(51.5155, -0.0922));
+        manager.get_or_create(unplaced);
+
+        let clusters = manager.clusters(4);
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].count, 2);
+        assert!(clusters[0].centroid.is_some());
+        assert_eq!(clusters[1].peers, vec![unplaced.to_string()]);
+    }
+
+    #[test]
+    fn test_bans_expire_and_lift() {
+        let manager = PeerManager::default();
+        let spammer = random_peer_id();
+        let flaky = random_peer_id();
+
+        manager.ban(spammer, Some(Duration::from_secs(3600)), "spam");
+        manager.ban(flaky, Some(Duration::ZERO), "flaky");
+        assert!(manager.is_banned(&spammer));
+        assert!(!manager.is_banned(&flaky));
+
+        let bans = manager.bans();
+        assert_eq!(bans.len(), 1);
+        assert_eq!(bans[0].peer_id, spammer.to_string());
+        assert_eq!(bans[0].reason, "spam");
+
+        assert_eq!(
+            manager.unban(&spammer).map(|ban| ban.reason),
+            Some("spam".into())
+        );
+        assert!(!manager.is_banned(&spammer));
+        assert_eq!(
+            manager.get_state(&spammer),
+            Some(ConnectionState::Disconnected)
+        );
+        assert!(manager.unban(&spammer).is_none());
+    }
+
+    #[test]
+    fn test_peer_quality() {
+        let manager = PeerManager::default();
+        let steady = random_peer_id();
+        let flapping = random_peer_id();
+        assert_eq!(manager.quality(&steady), NEUTRAL_QUALITY);
+
+        let near: Multiaddr = "/ip4/10.0.0.1/tcp/4001".parse().unwrap();
+        let far: Multiaddr = "/ip4/203.0.113.7/tcp/4001".parse().unwrap();
+        manager.add_address(steady, near.clone());
+        manager.add_address(steady, far.clone());
+        manager.set_state(steady, ConnectionState::Connected);
+        manager.record_rtt(steady, Some(&near), Duration::from_millis(10));
+        manager.record_rtt(steady, Some(&far), Duration::from_millis(300));
+        assert_eq!(manager.best_address(&steady), Some(near));
+
+        // Unscoped link-local addresses are a last resort
+        let dual = random_peer_id();
+        let global: Multiaddr = "/ip6/2001:db8::7/tcp/4001".parse().unwrap();
+        let link_local: Multiaddr = "/ip6/fe80::7/tcp/4001".parse().unwrap();
+        manager.add_address(dual, global.clone());
+        manager.add_address(dual, link_local.clone());
+        assert_eq!(manager.best_address(&dual), Some(global));
+        manager.record_rtt(dual, Some(&link_local), Duration::from_millis(5));
+        assert_eq!(manager.best_address(&dual), Some(link_local));
+
+        for _ in 0..5 {
+            manager.set_state(flapping, ConnectionState::Connected);
+            manager.set_state(flapping, ConnectionState::Disconnected);
+            manager.record_rtt(flapping, None, Duration::from_millis(10));
+        }
+        assert_eq!(manager.get(&flapping).unwrap().churn_per_hour(), 10);
+        // Failed dials don't count as churn
+        manager.set_state(flapping, ConnectionState::Failed);
+        assert_eq!(manager.get(&flapping).unwrap().churn_per_hour(), 10);
+
+        assert!(manager.quality(&steady) > manager.quality(&flapping));
+        assert!(manager.quality(&flapping) > 0.0);
     }
 }
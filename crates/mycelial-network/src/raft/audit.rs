@@ -0,0 +1,90 @@
+//! Balance audit between the optimistic ledger and Raft
+//!
+//! While the network migrates from the MVP's optimistic `CreditSynchronizer`
+//! to the Raft ledger, both run side by side and can drift apart (a lost
+//! gossip message, a transfer the leader rejected). The audit lists every
+//! account whose balances disagree so operators can see the drift.
+
+use std::collections::{HashMap, HashSet};
+use univrs_enr::core::{AccountId, Credits};
+
+/// An account whose optimistic and committed balances differ
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BalanceDiscrepancy {
+    /// The account in question
+    pub account: AccountId,
+    /// Balance in the local optimistic ledger (zero if it has no entry)
+    pub optimistic: Credits,
+    /// Balance committed by Raft (zero if it has no entry)
+    pub committed: Credits,
+}
+
+impl BalanceDiscrepancy {
+    /// Optimistic minus committed balance
+    pub fn drift(&self) -> i128 {
+        i128::from(self.optimistic.amount) - i128::from(self.committed.amount)
+    }
+}
+
+/// Compare two views of the ledger account by account
+///
+/// Accounts missing from one side count as a zero balance there. The result
+/// is ordered by the size of the drift, largest first.
+pub fn compare_balances(
+    optimistic: &HashMap<AccountId, Credits>,
+    committed: &HashMap<AccountId, Credits>,
+) -> Vec<BalanceDiscrepancy> {
+    let accounts: HashSet<&AccountId> = optimistic.keys().chain(committed.keys()).collect();
+
+    let mut discrepancies: Vec<BalanceDiscrepancy> = accounts
+        .into_iter()
+        .filter_map(|account| {
+            let optimistic = optimistic.get(account).copied().unwrap_or(Credits::ZERO);
+            let committed = committed.get(account).copied().unwrap_or(Credits::ZERO);
+            (optimistic.amount != committed.amount).then(|| BalanceDiscrepancy {
+                account: account.clone(),
+                optimistic,
+                committed,
+            })
+        })
+        .collect();
+    discrepancies.sort_by_key(|d| std::cmp::Reverse(d.drift().unsigned_abs()));
+    discrepancies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use univrs_enr::core::NodeId;
+
+    fn account(seed: u8) -> AccountId {
+        AccountId::node_account(NodeId::from_bytes([seed; 32]))
+    }
+
+    #[test]
+    fn test_compare_balances() {
+        let optimistic = HashMap::from([
+            (account(1), Credits::new(1000)),
+            (account(2), Credits::new(898)),
+            (account(3), Credits::new(50)),
+        ]);
+        let committed = HashMap::from([
+            (account(1), Credits::new(1000)),
+            (account(2), Credits::new(1000)),
+            (account(4), Credits::new(10)),
+        ]);
+
+        let discrepancies = compare_balances(&optimistic, &committed);
+        assert_eq!(discrepancies.len(), 3);
+
+        // Largest drift first; agreeing accounts are left out
+        assert_eq!(discrepancies[0].account, account(2));
+        assert_eq!(discrepancies[0].drift(), -102);
+        assert_eq!(discrepancies[1].account, account(3));
+        assert_eq!(discrepancies[1].committed.amount, 0);
+        assert_eq!(discrepancies[2].account, account(4));
+        assert_eq!(discrepancies[2].drift(), -10);
+
+        assert!(compare_balances(&optimistic, &optimistic).is_empty());
+    }
+}
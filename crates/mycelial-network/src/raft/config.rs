@@ -1,5 +1,7 @@
 //! Raft configuration options
 
+use crate::enr_bridge::DEFAULT_ENTROPY_TAX_RATE;
+
 /// Configuration for the Raft consensus layer
 #[derive(Debug, Clone)]
 pub struct RaftConfig {
@@ -11,10 +13,16 @@ pub struct RaftConfig {
     pub election_timeout_max: u64,
     /// Maximum entries per append request
     pub max_payload_entries: u64,
+    /// How long a proposal waits for a quorum in milliseconds
+    pub propose_timeout: u64,
+    /// Applied log entries that trigger a snapshot (0 disables snapshots)
+    pub snapshot_threshold: u64,
     /// Enable heartbeat (set false for testing)
     pub enable_heartbeat: bool,
     /// Enable leader election (set false for testing)
     pub enable_elect: bool,
+    /// Share of each transfer paid to the revival pool, in [0, 1)
+    pub entropy_tax_rate: f64,
 }
 
 impl Default for RaftConfig {
@@ -24,8 +32,11 @@ impl Default for RaftConfig {
             election_timeout_min: 300,
             election_timeout_max: 500,
             max_payload_entries: 100,
+            propose_timeout: 5000,
+            snapshot_threshold: 1000,
             enable_heartbeat: true,
             enable_elect: true,
+            entropy_tax_rate: DEFAULT_ENTROPY_TAX_RATE,
         }
     }
 }
@@ -38,8 +49,11 @@ impl RaftConfig {
             election_timeout_min: 150,
             election_timeout_max: 300,
             max_payload_entries: 10,
+            propose_timeout: 1000,
+            snapshot_threshold: 5,
             enable_heartbeat: true,
             enable_elect: true,
+            entropy_tax_rate: DEFAULT_ENTROPY_TAX_RATE,
         }
     }
 
@@ -50,8 +64,11 @@ impl RaftConfig {
             election_timeout_min: 150,
             election_timeout_max: 300,
             max_payload_entries: 200,
+            propose_timeout: 2000,
+            snapshot_threshold: 1000,
             enable_heartbeat: true,
             enable_elect: true,
+            entropy_tax_rate: DEFAULT_ENTROPY_TAX_RATE,
         }
     }
 
@@ -62,8 +79,11 @@ impl RaftConfig {
             election_timeout_min: 1500,
             election_timeout_max: 3000,
             max_payload_entries: 50,
+            propose_timeout: 15000,
+            snapshot_threshold: 500,
             enable_heartbeat: true,
             enable_elect: true,
+            entropy_tax_rate: DEFAULT_ENTROPY_TAX_RATE,
         }
     }
 }
@@ -0,0 +1,138 @@
+//! Leader election for the Raft credit ledger
+//!
+//! Followers expect a heartbeat from the leader at least once per election
+//! timeout, randomized between `election_timeout_min` and
+//! `election_timeout_max` so that followers rarely time out together. A
+//! follower that hears nothing increments the term, votes for itself and
+//! asks the others for their vote with `RequestVote`. Each node grants one
+//! vote per term, and only to a candidate whose log is at least as up to
+//! date as its own, so a new leader always holds every committed entry. The
+//! candidate that collects votes from a majority becomes leader.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+use univrs_enr::core::NodeId;
+
+use super::RaftConfig;
+
+/// Request for votes from a candidate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestVote {
+    /// Candidate asking for votes
+    pub candidate: NodeId,
+    /// Term the candidate is standing in
+    pub term: u64,
+    /// Index of the candidate's last log entry
+    pub last_log_index: u64,
+    /// Term of the candidate's last log entry
+    pub last_log_term: u64,
+}
+
+/// Answer to a `RequestVote`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoteResponse {
+    /// Voting node
+    pub from: NodeId,
+    /// Candidate the vote is for
+    pub to: NodeId,
+    /// Voter's term
+    pub term: u64,
+    /// Whether the vote was granted
+    pub granted: bool,
+}
+
+/// Vote bookkeeping and election timers for one node
+#[derive(Debug)]
+pub(crate) struct ElectionState {
+    /// Term of our last vote and the candidate it went to
+    pub voted_for: Option<(u64, NodeId)>,
+    /// Votes collected while standing as candidate
+    pub votes: Option<HashSet<NodeId>>,
+    /// When to start an election without word from a leader
+    pub deadline: Instant,
+    /// When the leader last sent a heartbeat
+    pub last_heartbeat: Instant,
+}
+
+impl ElectionState {
+    /// Fresh state with an election timer started now
+    pub fn new(config: &RaftConfig) -> Self {
+        let now = Instant::now();
+        Self {
+            voted_for: None,
+            votes: None,
+            deadline: now + election_timeout(config),
+            last_heartbeat: now,
+        }
+    }
+
+    /// Restart the election timer
+    pub fn reset_deadline(&mut self, config: &RaftConfig) {
+        self.deadline = Instant::now() + election_timeout(config);
+    }
+
+    /// Whether we may vote for `candidate` in `term`
+    pub fn can_vote_for(&self, term: u64, candidate: NodeId) -> bool {
+        match self.voted_for {
+            Some((voted_term, voted)) if voted_term == term => voted == candidate,
+            _ => true,
+        }
+    }
+}
+
+/// Random election timeout within the configured range
+pub(crate) fn election_timeout(config: &RaftConfig) -> Duration {
+    let min = config.election_timeout_min;
+    let max = config.election_timeout_max.max(min);
+    Duration::from_millis(rand::thread_rng().gen_range(min..=max))
+}
+
+/// Whether a candidate's log is at least as up to date as ours
+pub(crate) fn log_up_to_date(
+    candidate_last_term: u64,
+    candidate_last_index: u64,
+    our_last_term: u64,
+    our_last_index: u64,
+) -> bool {
+    (candidate_last_term, candidate_last_index) >= (our_last_term, our_last_index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_election_timeout_in_range() {
+        let config = RaftConfig::for_testing();
+        for _ in 0..100 {
+            let timeout = election_timeout(&config).as_millis() as u64;
+            assert!(timeout >= config.election_timeout_min);
+            assert!(timeout <= config.election_timeout_max);
+        }
+    }
+
+    #[test]
+    fn test_one_vote_per_term() {
+        let mut state = ElectionState::new(&RaftConfig::for_testing());
+        let a = NodeId::from_bytes([1u8; 32]);
+        let b = NodeId::from_bytes([2u8; 32]);
+
+        assert!(state.can_vote_for(2, a));
+        state.voted_for = Some((2, a));
+        assert!(state.can_vote_for(2, a));
+        assert!(!state.can_vote_for(2, b));
+        assert!(state.can_vote_for(3, b));
+    }
+
+    #[test]
+    fn test_log_up_to_date() {
+        // Later last term wins regardless of length
+        assert!(log_up_to_date(3, 1, 2, 10));
+        assert!(!log_up_to_date(2, 10, 3, 1));
+        // Same term: longer or equal log wins
+        assert!(log_up_to_date(2, 5, 2, 5));
+        assert!(!log_up_to_date(2, 4, 2, 5));
+    }
+}
@@ -22,20 +22,48 @@
 //!
 //! This is the initial scaffold for OpenRaft integration.
 //! Full implementation in progress per docs/OpenRaft/README.md
+//!
+//! Until then, the ledger runs a minimal Raft itself: the leader replicates
+//! its log (see [`replication`]) and a proposal is only applied once a
+//! majority of the cluster holds it; followers that stop hearing from the
+//! leader elect a new one (see [`election`]). With a [`RaftStore`], the
+//! term, log and snapshots are persisted in sled so a node recovers its
+//! committed balances after a restart (see [`storage`]).
+//!
+//! During the migration the optimistic ledger keeps running alongside; use
+//! [`compare_balances`] (or `EnrBridge::audit_balances`) to spot drift.
+//!
+//! Entropy tax collected in the revival pool is paid back out through
+//! committed distributions (see [`revival`]).
 
+mod audit;
 mod config;
+mod election;
+mod replication;
+mod revival;
+mod storage;
 mod types;
 
+pub use audit::{compare_balances, BalanceDiscrepancy};
 pub use config::RaftConfig;
-pub use types::{CreditCommand, CreditResponse};
-
-use std::collections::HashMap;
+pub use election::{RequestVote, VoteResponse};
+pub use replication::{
+    AppendEntries, AppendResponse, InstallSnapshot, RaftMessage, RequestEntries,
+};
+pub use revival::{plan_distribution, DistributionStrategy};
+pub use storage::{RaftStore, RecoveredState};
+pub use types::{CreditCommand, CreditResponse, RaftSnapshot};
+
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::{Duration, Instant};
+use tokio::sync::{oneshot, Mutex, RwLock};
 use tracing::{debug, info, warn};
 use univrs_enr::core::{AccountId, CreditTransfer, Credits, NodeId};
 
-use crate::enr_bridge::credits::TransferError;
+use crate::enr_bridge::credits::{EntropyTaxRate, TransferError};
+use election::{log_up_to_date, ElectionState};
+use replication::{quorum_index, FollowerProgress, ReplicationLog};
 
 /// Callback type for publishing to gossipsub
 pub type PublishFn = Box<dyn Fn(String, Vec<u8>) -> Result<(), String> + Send + Sync>;
@@ -43,10 +71,26 @@ pub type PublishFn = Box<dyn Fn(String, Vec<u8>) -> Result<(), String> + Send +
 /// Gossipsub topic for Raft protocol messages
 pub const RAFT_TOPIC: &str = "/vudo/enr/raft/1.0.0";
 
+/// Log, follower progress and proposals waiting for commit
+#[derive(Default)]
+struct ReplicationState {
+    /// Replicated command log
+    log: ReplicationLog,
+    /// Per-follower replication progress (leader only)
+    peers: HashMap<NodeId, FollowerProgress>,
+    /// Proposals waiting for their entry to commit, by log index
+    waiters: HashMap<u64, oneshot::Sender<CreditResponse>>,
+    /// Latest snapshot, covering the log up to `log.snapshot_index()`
+    snapshot: Option<RaftSnapshot>,
+}
+
 /// Raft-based credit ledger with distributed consensus
 ///
-/// Sprint 1 implementation uses local state with Raft message broadcasting.
-/// Full OpenRaft integration will be completed in Sprint 2.
+/// Sprint 1 implementation replicates a command log over gossipsub and
+/// elects leaders with randomized timeouts; commands are applied once a
+/// majority stores them. The owner drives timers by calling
+/// [`tick`](Self::tick). Full OpenRaft integration will be completed in
+/// Sprint 2.
 pub struct RaftCreditLedger {
     /// Local node ID
     local_node: NodeId,
@@ -62,8 +106,16 @@ pub struct RaftCreditLedger {
     is_leader: Arc<RwLock<bool>>,
     /// Current term
     current_term: Arc<RwLock<u64>>,
-    /// Log index
-    log_index: Arc<RwLock<u64>>,
+    /// Leader of the current term, if known
+    leader_id: Arc<RwLock<Option<NodeId>>>,
+    /// Replicated log and commit tracking
+    replication: Arc<Mutex<ReplicationState>>,
+    /// Votes and election timers
+    election: Arc<Mutex<ElectionState>>,
+    /// Durable storage for the log and state, if any
+    store: Option<RaftStore>,
+    /// Share of each transfer we propose that goes to the revival pool
+    tax_rate: Arc<RwLock<EntropyTaxRate>>,
 }
 
 impl RaftCreditLedger {
@@ -85,53 +137,492 @@ impl RaftCreditLedger {
     ) -> Result<Self, RaftError> {
         info!(node = %node_id, bootstrap, "Creating RaftCreditLedger");
 
+        let tax_rate = EntropyTaxRate::new(config.entropy_tax_rate)
+            .map_err(|e| RaftError::Config(e.to_string()))?;
+
         let ledger = Self {
             local_node: node_id,
             balances: Arc::new(RwLock::new(HashMap::new())),
             revival_pool: Arc::new(RwLock::new(Credits::ZERO)),
             publish_fn: Box::new(publish_fn),
-            config,
             is_leader: Arc::new(RwLock::new(bootstrap)), // Bootstrap node starts as leader
             current_term: Arc::new(RwLock::new(1)),
-            log_index: Arc::new(RwLock::new(0)),
+            leader_id: Arc::new(RwLock::new(bootstrap.then_some(node_id))),
+            replication: Arc::new(Mutex::new(ReplicationState::default())),
+            election: Arc::new(Mutex::new(ElectionState::new(&config))),
+            config,
+            store: None,
+            tax_rate: Arc::new(RwLock::new(tax_rate)),
         };
 
         Ok(ledger)
     }
 
+    /// Create a Raft node that persists its log and state in `store`
+    ///
+    /// Anything already in the store is recovered first: the term and vote,
+    /// the log, and the balances as of the last committed entry.
+    pub async fn with_store(
+        node_id: NodeId,
+        publish_fn: impl Fn(String, Vec<u8>) -> Result<(), String> + Send + Sync + 'static,
+        config: RaftConfig,
+        bootstrap: bool,
+        store: RaftStore,
+    ) -> Result<Self, RaftError> {
+        let recovered = store.load()?;
+        let mut ledger = Self::new_with_config(node_id, publish_fn, config, bootstrap).await?;
+        ledger.store = Some(store);
+        ledger.recover(recovered).await;
+        Ok(ledger)
+    }
+
+    /// Restore persisted state and replay committed entries
+    async fn recover(&self, recovered: RecoveredState) {
+        *self.current_term.write().await = recovered.term.max(1);
+        self.election.lock().await.voted_for = recovered.voted_for;
+
+        let mut state = self.replication.lock().await;
+        let (snapshot_index, snapshot_term) = match &recovered.snapshot {
+            Some(snapshot) => {
+                *self.balances.write().await = snapshot.balances.clone();
+                *self.revival_pool.write().await = snapshot.revival_pool;
+                (snapshot.last_index, snapshot.last_term)
+            }
+            None => (0, 0),
+        };
+        state.log = ReplicationLog::restore(
+            snapshot_index,
+            snapshot_term,
+            recovered.entries,
+            recovered.commit_index,
+        );
+        state.snapshot = recovered.snapshot;
+        self.apply_committed(&mut state).await;
+
+        info!(
+            term = recovered.term,
+            last_index = state.log.last_index(),
+            commit_index = state.log.commit_index(),
+            "Recovered Raft state from disk"
+        );
+    }
+
+    /// Add another member of the cluster
+    ///
+    /// Every node should know all other members: the leader replicates to
+    /// them, and proposals and elections need a majority of them.
+    pub async fn add_peer(&self, node: NodeId) {
+        if node == self.local_node {
+            return;
+        }
+        let mut state = self.replication.lock().await;
+        let last_index = state.log.last_index();
+        state
+            .peers
+            .entry(node)
+            .or_insert_with(|| FollowerProgress::new(last_index));
+    }
+
+    /// Other cluster members known to this node
+    pub async fn peers(&self) -> Vec<NodeId> {
+        self.replication
+            .lock()
+            .await
+            .peers
+            .keys()
+            .copied()
+            .collect()
+    }
+
     /// Propose a credit command to the Raft cluster
+    ///
+    /// Returns once a majority of the cluster has stored the entry and it has
+    /// been applied. If no quorum acknowledges it within the configured
+    /// `propose_timeout`, [`RaftError::QuorumTimeout`] is returned; the entry
+    /// stays in the log and may still commit later. On a follower,
+    /// [`RaftError::NotLeader`] names the leader to redirect to, if known.
     pub async fn propose(&self, command: CreditCommand) -> Result<CreditResponse, RaftError> {
         // Check if we're the leader
         if !*self.is_leader.read().await {
-            return Err(RaftError::NotLeader);
+            return Err(RaftError::NotLeader {
+                leader: self.leader().await,
+            });
         }
 
         debug!(?command, "Proposing command");
 
-        // Increment log index
-        let log_idx = {
-            let mut idx = self.log_index.write().await;
-            *idx += 1;
-            *idx
+        let term = *self.current_term.read().await;
+        let (index, response_rx, append) = {
+            let mut state = self.replication.lock().await;
+            let prev_index = state.log.last_index();
+            let prev_term = state.log.last_term();
+            let entry = RaftLogEntry {
+                term,
+                index: prev_index + 1,
+                command,
+            };
+            if let Some(store) = &self.store {
+                store.replace_from(entry.index, std::slice::from_ref(&entry))?;
+            }
+            state.log.push(entry.clone());
+
+            let (tx, rx) = oneshot::channel();
+            state.waiters.insert(entry.index, tx);
+
+            let append = AppendEntries {
+                leader: self.local_node,
+                to: None,
+                term,
+                prev_index,
+                prev_term,
+                entries: vec![entry],
+                leader_commit: state.log.commit_index(),
+            };
+
+            // Without followers the leader alone is a majority
+            self.advance_commit(&mut state, term).await;
+            (prev_index + 1, rx, append)
         };
 
-        // Apply command locally
-        let response = self.apply_command(&command).await;
+        self.publish(&RaftMessage::AppendEntries(append));
+
+        let timeout = Duration::from_millis(self.config.propose_timeout);
+        match tokio::time::timeout(timeout, response_rx).await {
+            Ok(Ok(response)) => Ok(response),
+            Ok(Err(_)) => Err(RaftError::Propose(format!(
+                "entry {} was discarded before commit",
+                index
+            ))),
+            Err(_) => {
+                self.replication.lock().await.waiters.remove(&index);
+                Err(RaftError::QuorumTimeout {
+                    index,
+                    timeout_ms: self.config.propose_timeout,
+                })
+            }
+        }
+    }
+
+    /// Broadcast an empty append so followers learn the commit index
+    ///
+    /// Followers that have fallen behind notice the gap and request the
+    /// entries they are missing. Does nothing on followers.
+    pub async fn heartbeat(&self) {
+        if !self.is_leader().await {
+            return;
+        }
 
-        // Broadcast to followers (in full implementation, wait for quorum)
-        let msg = RaftLogEntry {
-            term: *self.current_term.read().await,
-            index: log_idx,
-            command: command.clone(),
+        let term = *self.current_term.read().await;
+        let append = {
+            let state = self.replication.lock().await;
+            AppendEntries {
+                leader: self.local_node,
+                to: None,
+                term,
+                prev_index: state.log.last_index(),
+                prev_term: state.log.last_term(),
+                entries: Vec::new(),
+                leader_commit: state.log.commit_index(),
+            }
         };
+        self.publish(&RaftMessage::AppendEntries(append));
+    }
+
+    /// Drive heartbeats and elections
+    ///
+    /// Call this periodically, more often than `heartbeat_interval`. The
+    /// leader sends a heartbeat when one is due; any other node that has not
+    /// heard from a leader within its election timeout starts an election.
+    pub async fn tick(&self) {
+        let now = Instant::now();
 
-        if let Ok(bytes) = bincode::serialize(&msg) {
-            if let Err(e) = (self.publish_fn)(RAFT_TOPIC.to_string(), bytes) {
-                warn!("Failed to broadcast Raft entry: {}", e);
+        if self.is_leader().await {
+            if !self.config.enable_heartbeat {
+                return;
             }
+            let interval = Duration::from_millis(self.config.heartbeat_interval);
+            let due = {
+                let mut election = self.election.lock().await;
+                let due = now.duration_since(election.last_heartbeat) >= interval;
+                if due {
+                    election.last_heartbeat = now;
+                }
+                due
+            };
+            if due {
+                self.heartbeat().await;
+            }
+            return;
+        }
+
+        if self.config.enable_elect && self.election.lock().await.deadline <= now {
+            self.start_election().await;
+        }
+    }
+
+    /// Call [`tick`](Self::tick) twice per heartbeat interval, forever
+    pub async fn run_ticks(self: Arc<Self>) {
+        let period = Duration::from_millis((self.config.heartbeat_interval / 2).max(1));
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            self.tick().await;
+        }
+    }
+
+    /// Stand for leader in a new term
+    async fn start_election(&self) {
+        let term = {
+            let mut current = self.current_term.write().await;
+            *current += 1;
+            *current
+        };
+        *self.leader_id.write().await = None;
+
+        let (last_log_index, last_log_term) = {
+            let state = self.replication.lock().await;
+            (state.log.last_index(), state.log.last_term())
+        };
+        {
+            let mut election = self.election.lock().await;
+            election.voted_for = Some((term, self.local_node));
+            election.votes = Some(HashSet::from([self.local_node]));
+            election.reset_deadline(&self.config);
         }
+        self.persist_hard_state(term, Some((term, self.local_node)));
+
+        info!(term, "No word from a leader, starting election");
+        self.publish(&RaftMessage::RequestVote(RequestVote {
+            candidate: self.local_node,
+            term,
+            last_log_index,
+            last_log_term,
+        }));
+
+        // A cluster of one elects itself
+        self.check_votes(term).await;
+    }
 
-        Ok(response)
+    /// Become leader if a majority has voted for us in `term`
+    async fn check_votes(&self, term: u64) {
+        let cluster_size = self.replication.lock().await.peers.len() + 1;
+        let won = {
+            let mut election = self.election.lock().await;
+            let won = election
+                .votes
+                .as_ref()
+                .is_some_and(|votes| votes.len() > cluster_size / 2);
+            if won {
+                election.votes = None;
+                election.last_heartbeat = Instant::now();
+            }
+            won
+        };
+
+        if won && *self.current_term.read().await == term {
+            self.become_leader(term).await;
+        }
+    }
+
+    /// Take over as leader and assert leadership with a no-op entry
+    ///
+    /// Entries from earlier terms only commit once an entry of the new term
+    /// does, so the no-op also settles anything the old leader left pending.
+    async fn become_leader(&self, term: u64) {
+        *self.is_leader.write().await = true;
+        *self.leader_id.write().await = Some(self.local_node);
+        info!(term, "Elected Raft leader");
+
+        let append = {
+            let mut state = self.replication.lock().await;
+            let prev_index = state.log.last_index();
+            let prev_term = state.log.last_term();
+            for progress in state.peers.values_mut() {
+                *progress = FollowerProgress::new(prev_index);
+            }
+
+            let entry = RaftLogEntry {
+                term,
+                index: prev_index + 1,
+                command: CreditCommand::Noop,
+            };
+            state.log.push(entry.clone());
+            if let Err(e) = self.persist_entries(&state, entry.index) {
+                warn!("Failed to persist Raft entry: {}", e);
+            }
+
+            let append = AppendEntries {
+                leader: self.local_node,
+                to: None,
+                term,
+                prev_index,
+                prev_term,
+                entries: vec![entry],
+                leader_commit: state.log.commit_index(),
+            };
+            self.advance_commit(&mut state, term).await;
+            append
+        };
+
+        self.publish(&RaftMessage::AppendEntries(append));
+    }
+
+    /// Commit entries stored on a majority and apply them
+    async fn advance_commit(&self, state: &mut ReplicationState, term: u64) {
+        let quorum = quorum_index(
+            state.peers.values().map(|p| p.match_index),
+            state.log.last_index(),
+        );
+        // Only entries from the current term are committed by counting
+        if quorum > state.log.commit_index() && state.log.term_at(quorum) == Some(term) {
+            state.log.commit_to(quorum);
+        }
+        self.apply_committed(state).await;
+    }
+
+    /// Apply newly committed entries and answer waiting proposals
+    async fn apply_committed(&self, state: &mut ReplicationState) {
+        let committed = state.log.take_committed();
+        if committed.is_empty() {
+            return;
+        }
+        if let Some(store) = &self.store {
+            if let Err(e) = store.save_commit(state.log.commit_index()) {
+                warn!("Failed to persist Raft commit index: {}", e);
+            }
+        }
+
+        for entry in committed {
+            let response = self.apply_command(&entry.command).await;
+            if let Some(tx) = state.waiters.remove(&entry.index) {
+                let _ = tx.send(response);
+            }
+        }
+
+        let threshold = self.config.snapshot_threshold;
+        if threshold > 0 && state.log.applied_since_snapshot() >= threshold {
+            self.take_snapshot(state).await;
+        }
+    }
+
+    /// Capture the applied state and drop the entries it covers
+    async fn take_snapshot(&self, state: &mut ReplicationState) {
+        let (last_index, last_term) = state.log.compact();
+        let snapshot = RaftSnapshot {
+            last_index,
+            last_term,
+            balances: self.balances.read().await.clone(),
+            revival_pool: *self.revival_pool.read().await,
+        };
+        info!(last_index, last_term, "Took Raft snapshot, compacted log");
+        if let Some(store) = &self.store {
+            if let Err(e) = store.save_snapshot(&snapshot) {
+                warn!("Failed to persist Raft snapshot: {}", e);
+            }
+        }
+        state.snapshot = Some(snapshot);
+    }
+
+    /// Replace the ledger state with a snapshot from the leader
+    async fn restore_snapshot(&self, state: &mut ReplicationState, snapshot: RaftSnapshot) {
+        state
+            .log
+            .install_snapshot(snapshot.last_index, snapshot.last_term);
+        *self.balances.write().await = snapshot.balances.clone();
+        *self.revival_pool.write().await = snapshot.revival_pool;
+        info!(
+            last_index = snapshot.last_index,
+            "Installed Raft snapshot from leader"
+        );
+        if let Some(store) = &self.store {
+            let persisted = store
+                .save_snapshot(&snapshot)
+                .and_then(|()| self.persist_entries(state, snapshot.last_index + 1));
+            if let Err(e) = persisted {
+                warn!("Failed to persist installed Raft snapshot: {}", e);
+            }
+        }
+        state.snapshot = Some(snapshot);
+    }
+
+    /// Latest snapshot taken or installed by this node
+    pub async fn snapshot(&self) -> Option<RaftSnapshot> {
+        self.replication.lock().await.snapshot.clone()
+    }
+
+    /// Send a follower the leader's log from its next index
+    ///
+    /// If those entries were compacted away, the snapshot is sent instead.
+    fn send_catch_up(&self, state: &ReplicationState, follower: NodeId, term: u64) {
+        let Some(progress) = state.peers.get(&follower) else {
+            return;
+        };
+        if progress.next_index <= state.log.snapshot_index() {
+            if let Some(snapshot) = &state.snapshot {
+                debug!(
+                    follower = %follower,
+                    last_index = snapshot.last_index,
+                    "Sending snapshot to lagging follower"
+                );
+                self.publish(&RaftMessage::InstallSnapshot(InstallSnapshot {
+                    leader: self.local_node,
+                    to: follower,
+                    term,
+                    snapshot: snapshot.clone(),
+                }));
+            }
+            return;
+        }
+        let prev_index = progress.next_index - 1;
+        let append = AppendEntries {
+            leader: self.local_node,
+            to: Some(follower),
+            term,
+            prev_index,
+            prev_term: state.log.term_at(prev_index).unwrap_or(0),
+            entries: state.log.entries_from(
+                progress.next_index,
+                self.config.max_payload_entries as usize,
+            ),
+            leader_commit: state.log.commit_index(),
+        };
+        debug!(
+            follower = %follower,
+            from = progress.next_index,
+            count = append.entries.len(),
+            "Sending catch-up entries"
+        );
+        self.publish(&RaftMessage::AppendEntries(append));
+    }
+
+    /// Persist the log from `index` onwards, if backed by a store
+    fn persist_entries(&self, state: &ReplicationState, index: u64) -> Result<(), RaftError> {
+        match &self.store {
+            Some(store) => store.replace_from(index, &state.log.entries_from(index, usize::MAX)),
+            None => Ok(()),
+        }
+    }
+
+    /// Persist the term and vote, if backed by a store
+    fn persist_hard_state(&self, term: u64, voted_for: Option<(u64, NodeId)>) {
+        if let Some(store) = &self.store {
+            if let Err(e) = store.save_hard_state(term, voted_for) {
+                warn!("Failed to persist Raft term: {}", e);
+            }
+        }
+    }
+
+    /// Publish a Raft message to the cluster
+    fn publish(&self, msg: &RaftMessage) {
+        match msg.encode() {
+            Ok(bytes) => {
+                if let Err(e) = (self.publish_fn)(RAFT_TOPIC.to_string(), bytes) {
+                    warn!("Failed to broadcast Raft message: {}", e);
+                }
+            }
+            Err(e) => warn!("Failed to encode Raft message: {}", e),
+        }
     }
 
     /// Apply a command to the state machine
@@ -153,10 +644,44 @@ impl RaftCreditLedger {
                 debug!(node = %node, reason = %reason, "Recorded failure");
                 CreditResponse::FailureRecorded
             }
+            CreditCommand::DistributeRevival { payouts } => {
+                CreditResponse::Distribution(self.apply_distribution(payouts).await)
+            }
             CreditCommand::Noop => CreditResponse::Noop,
         }
     }
 
+    /// Move revival pool credits to the recipients' accounts
+    async fn apply_distribution(&self, payouts: &[(NodeId, Credits)]) -> Result<Credits, String> {
+        let total = payouts
+            .iter()
+            .fold(Credits::ZERO, |acc, (_, c)| acc.saturating_add(*c));
+
+        let mut pool = self.revival_pool.write().await;
+        if pool.amount < total.amount {
+            return Err(format!(
+                "Revival pool holds {}, payouts need {}",
+                pool.amount, total.amount
+            ));
+        }
+        *pool = pool.saturating_sub(total);
+        drop(pool);
+
+        let mut balances = self.balances.write().await;
+        for (node, amount) in payouts {
+            let account = AccountId::node_account(*node);
+            let current = balances.get(&account).copied().unwrap_or(Credits::ZERO);
+            balances.insert(account, current.saturating_add(*amount));
+        }
+
+        info!(
+            recipients = payouts.len(),
+            total = total.amount,
+            "Distributed revival pool"
+        );
+        Ok(total)
+    }
+
     /// Apply a credit transfer
     async fn apply_transfer(&self, transfer: &CreditTransfer) -> Result<(), TransferError> {
         let mut balances = self.balances.write().await;
@@ -218,7 +743,7 @@ impl RaftCreditLedger {
             AccountId::node_account(self.local_node),
             AccountId::node_account(to),
             amount,
-            univrs_enr::revival::calculate_entropy_tax(amount),
+            self.entropy_tax_rate().await.tax(amount),
         );
 
         let response = self
@@ -252,18 +777,73 @@ impl RaftCreditLedger {
         Ok(())
     }
 
+    /// Pay out the revival pool to `recipients`
+    ///
+    /// The leader splits the pool according to `strategy` and commits the
+    /// payouts as one entry. Returns the credits paid out, which is zero
+    /// (and nothing is proposed) when the pool is empty or no recipient
+    /// qualifies.
+    pub async fn distribute_revival(
+        &self,
+        recipients: &[NodeId],
+        strategy: DistributionStrategy,
+    ) -> Result<Credits, RaftError> {
+        let payouts = plan_distribution(
+            self.revival_pool().await,
+            recipients,
+            &strategy,
+            &*self.balances.read().await,
+        );
+        if payouts.is_empty() {
+            return Ok(Credits::ZERO);
+        }
+
+        match self
+            .propose(CreditCommand::DistributeRevival { payouts })
+            .await?
+        {
+            CreditResponse::Distribution(result) => result.map_err(RaftError::Propose),
+            _ => Err(RaftError::Propose("Unexpected response".into())),
+        }
+    }
+
+    /// Pay out the revival pool to all cluster members every `period`
+    ///
+    /// Only the leader distributes; on other nodes each round is skipped.
+    pub async fn run_revival_distribution(
+        self: Arc<Self>,
+        period: Duration,
+        strategy: DistributionStrategy,
+    ) {
+        let mut interval = tokio::time::interval(period);
+        // The first tick completes immediately
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            if !self.is_leader().await {
+                continue;
+            }
+
+            let mut members = self.peers().await;
+            members.push(self.local_node);
+            match self.distribute_revival(&members, strategy.clone()).await {
+                Ok(total) if !total.is_zero() => {
+                    debug!(total = total.amount, "Scheduled revival distribution")
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Scheduled revival distribution failed: {}", e),
+            }
+        }
+    }
+
     /// Check if this node is the Raft leader
     pub async fn is_leader(&self) -> bool {
         *self.is_leader.read().await
     }
 
-    /// Get the current Raft leader (self if leader, None otherwise for now)
+    /// Get the current Raft leader, if known
     pub async fn leader(&self) -> Option<NodeId> {
-        if self.is_leader().await {
-            Some(self.local_node)
-        } else {
-            None
-        }
+        *self.leader_id.read().await
     }
 
     /// Get all known account balances
@@ -284,23 +864,257 @@ impl RaftCreditLedger {
         *self.revival_pool.read().await
     }
 
+    /// The entropy tax rate charged on transfers this node proposes
+    pub async fn entropy_tax_rate(&self) -> EntropyTaxRate {
+        *self.tax_rate.read().await
+    }
+
+    /// Change the entropy tax rate
+    ///
+    /// The tax is fixed into each transfer when it is proposed, so entries
+    /// already in the log keep the rate they were proposed with.
+    pub async fn set_entropy_tax_rate(&self, rate: EntropyTaxRate) {
+        *self.tax_rate.write().await = rate;
+    }
+
     /// Handle incoming Raft message from gossipsub
     pub async fn handle_message(&self, bytes: &[u8]) -> Result<(), RaftError> {
-        let entry: RaftLogEntry =
-            bincode::deserialize(bytes).map_err(|e| RaftError::Decode(e.to_string()))?;
+        let msg = RaftMessage::decode(bytes).map_err(|e| RaftError::Decode(e.to_string()))?;
+
+        match msg {
+            RaftMessage::AppendEntries(append) => self.handle_append(append).await,
+            RaftMessage::AppendResponse(response) => self.handle_append_response(response).await,
+            RaftMessage::RequestEntries(request) => self.handle_request_entries(request).await,
+            RaftMessage::InstallSnapshot(install) => self.handle_install_snapshot(install).await,
+            RaftMessage::RequestVote(request) => self.handle_request_vote(request).await,
+            RaftMessage::VoteResponse(response) => self.handle_vote_response(response).await,
+        }
+
+        Ok(())
+    }
+
+    /// Adopt a newer term seen from another node, stepping down if leader
+    ///
+    /// Returns `false` if the message is from an older term and must be ignored.
+    async fn observe_term(&self, term: u64) -> bool {
+        let mut current = self.current_term.write().await;
+        if term < *current {
+            return false;
+        }
+        if term > *current {
+            *current = term;
+            let mut is_leader = self.is_leader.write().await;
+            if *is_leader {
+                warn!(term, "Newer term observed, stepping down");
+                *is_leader = false;
+            }
+            drop(is_leader);
+            *self.leader_id.write().await = None;
+
+            let mut election = self.election.lock().await;
+            election.votes = None;
+            election.reset_deadline(&self.config);
+            self.persist_hard_state(term, election.voted_for);
+        }
+        true
+    }
+
+    /// Grant or refuse a vote to a candidate
+    async fn handle_request_vote(&self, request: RequestVote) {
+        if request.candidate == self.local_node {
+            return;
+        }
+        let current_term = self.observe_term(request.term).await;
+        let term = *self.current_term.read().await;
+
+        let (last_index, last_term) = {
+            let state = self.replication.lock().await;
+            (state.log.last_index(), state.log.last_term())
+        };
+        let up_to_date = log_up_to_date(
+            request.last_log_term,
+            request.last_log_index,
+            last_term,
+            last_index,
+        );
+
+        let granted = {
+            let mut election = self.election.lock().await;
+            let granted =
+                current_term && up_to_date && election.can_vote_for(term, request.candidate);
+            if granted {
+                election.voted_for = Some((term, request.candidate));
+                election.reset_deadline(&self.config);
+                self.persist_hard_state(term, election.voted_for);
+            }
+            granted
+        };
 
         debug!(
-            term = entry.term,
-            index = entry.index,
-            "Received Raft entry"
+            candidate = %request.candidate,
+            term,
+            granted,
+            "Answering vote request"
         );
+        self.publish(&RaftMessage::VoteResponse(VoteResponse {
+            from: self.local_node,
+            to: request.candidate,
+            term,
+            granted,
+        }));
+    }
 
-        // If we're not the leader, apply the entry
-        if !self.is_leader().await {
-            self.apply_command(&entry.command).await;
+    /// Count a vote for our candidacy
+    async fn handle_vote_response(&self, response: VoteResponse) {
+        if response.to != self.local_node || !self.observe_term(response.term).await {
+            return;
+        }
+        if !response.granted || response.term != *self.current_term.read().await {
+            return;
         }
 
-        Ok(())
+        if let Some(votes) = self.election.lock().await.votes.as_mut() {
+            votes.insert(response.from);
+        }
+        self.check_votes(response.term).await;
+    }
+
+    /// Accept `leader` as the leader of `term` on hearing from it
+    ///
+    /// Returns `false` if the message is stale or we lead this term ourselves.
+    async fn follow(&self, leader: NodeId, term: u64) -> bool {
+        if !self.observe_term(term).await {
+            debug!(term, "Ignoring message from stale leader");
+            return false;
+        }
+        if self.is_leader().await {
+            warn!(other = %leader, "Another leader in our term, ignoring");
+            return false;
+        }
+        *self.leader_id.write().await = Some(leader);
+
+        // A leader exists for this term: stop any candidacy and wait again
+        let mut election = self.election.lock().await;
+        election.votes = None;
+        election.reset_deadline(&self.config);
+        true
+    }
+
+    /// Follower: replace our log and state with the leader's snapshot
+    async fn handle_install_snapshot(&self, install: InstallSnapshot) {
+        if install.to != self.local_node || !self.follow(install.leader, install.term).await {
+            return;
+        }
+
+        let match_index = install.snapshot.last_index;
+        {
+            let mut state = self.replication.lock().await;
+            // Ignore snapshots we are already past
+            if match_index > state.log.commit_index() {
+                self.restore_snapshot(&mut state, install.snapshot).await;
+            }
+        }
+
+        self.publish(&RaftMessage::AppendResponse(AppendResponse {
+            from: self.local_node,
+            term: install.term,
+            match_index,
+        }));
+    }
+
+    /// Follower: store entries from the leader and acknowledge them
+    async fn handle_append(&self, append: AppendEntries) {
+        if append.leader == self.local_node || append.to.is_some_and(|to| to != self.local_node) {
+            return;
+        }
+        if !self.follow(append.leader, append.term).await {
+            return;
+        }
+
+        debug!(
+            term = append.term,
+            prev_index = append.prev_index,
+            entries = append.entries.len(),
+            "Received Raft entries"
+        );
+
+        let reply = {
+            let mut state = self.replication.lock().await;
+            if state
+                .log
+                .append_from_leader(append.prev_index, append.prev_term, &append.entries)
+            {
+                // Entries must be durable before they are acknowledged
+                if let Err(e) = self.persist_entries(&state, append.prev_index + 1) {
+                    warn!("Failed to persist Raft entries: {}", e);
+                    return;
+                }
+                let match_index = append.prev_index + append.entries.len() as u64;
+                state.log.commit_to(append.leader_commit.min(match_index));
+                self.apply_committed(&mut state).await;
+
+                RaftMessage::AppendResponse(AppendResponse {
+                    from: self.local_node,
+                    term: append.term,
+                    match_index,
+                })
+            } else {
+                // Missed entries: ask for everything from where the logs may diverge
+                let next_index = (state.log.last_index() + 1).min(append.prev_index);
+                info!(
+                    next_index,
+                    prev_index = append.prev_index,
+                    "Gap in Raft log, requesting missing entries"
+                );
+                RaftMessage::RequestEntries(RequestEntries {
+                    from: self.local_node,
+                    term: append.term,
+                    next_index,
+                })
+            }
+        };
+
+        self.publish(&reply);
+    }
+
+    /// Leader: record a follower's progress and commit what a majority holds
+    async fn handle_append_response(&self, response: AppendResponse) {
+        if !self.observe_term(response.term).await || !self.is_leader().await {
+            return;
+        }
+
+        let term = *self.current_term.read().await;
+        let mut state = self.replication.lock().await;
+        let last_index = state.log.last_index();
+        let Some(progress) = state.peers.get_mut(&response.from) else {
+            debug!(from = %response.from, "Ignoring response from unknown peer");
+            return;
+        };
+        progress.match_index = progress.match_index.max(response.match_index);
+        progress.next_index = progress.match_index + 1;
+        let behind = progress.next_index <= last_index;
+
+        self.advance_commit(&mut state, term).await;
+        if behind {
+            self.send_catch_up(&state, response.from, term);
+        }
+    }
+
+    /// Leader: resend the log to a follower that detected a gap
+    async fn handle_request_entries(&self, request: RequestEntries) {
+        if !self.observe_term(request.term).await || !self.is_leader().await {
+            return;
+        }
+
+        let term = *self.current_term.read().await;
+        let mut state = self.replication.lock().await;
+        let last_index = state.log.last_index();
+        let Some(progress) = state.peers.get_mut(&request.from) else {
+            debug!(from = %request.from, "Ignoring entry request from unknown peer");
+            return;
+        };
+        progress.next_index = request.next_index.clamp(1, last_index + 1);
+        self.send_catch_up(&state, request.from, term);
     }
 }
 
@@ -318,8 +1132,8 @@ pub struct RaftLogEntry {
 /// Errors that can occur in Raft operations
 #[derive(Debug, thiserror::Error)]
 pub enum RaftError {
-    #[error("Not the leader")]
-    NotLeader,
+    #[error("Not the leader (leader: {leader:?})")]
+    NotLeader { leader: Option<NodeId> },
     #[error("Configuration error: {0}")]
     Config(String),
     #[error("Initialization error: {0}")]
@@ -328,6 +1142,8 @@ pub enum RaftError {
     Bootstrap(String),
     #[error("Propose error: {0}")]
     Propose(String),
+    #[error("Entry {index} not committed by a quorum within {timeout_ms}ms")]
+    QuorumTimeout { index: u64, timeout_ms: u64 },
     #[error("Storage error: {0}")]
     Storage(String),
     #[error("Network error: {0}")]
@@ -339,7 +1155,7 @@ pub enum RaftError {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
     /// Initial credits for test nodes (matches INITIAL_NODE_CREDITS)
     const TEST_INITIAL_CREDITS: u64 = 1000;
@@ -441,4 +1257,367 @@ mod tests {
         let result = ledger.transfer(node, Credits::new(100)).await;
         assert!(matches!(result, Err(TransferError::SelfTransfer)));
     }
+
+    #[tokio::test]
+    async fn test_distribute_revival_conserves_supply() {
+        let node1 = NodeId::from_bytes([1u8; 32]);
+        let node2 = NodeId::from_bytes([2u8; 32]);
+        let node3 = NodeId::from_bytes([3u8; 32]);
+        let (publish, _) = mock_publish();
+
+        let ledger = RaftCreditLedger::new_single_node(node1, publish)
+            .await
+            .unwrap();
+        ledger
+            .grant_credits(node1, Credits::new(TEST_INITIAL_CREDITS))
+            .await
+            .unwrap();
+        ledger.transfer(node2, Credits::new(500)).await.unwrap();
+        ledger.transfer(node3, Credits::new(50)).await.unwrap();
+
+        let pool = ledger.revival_pool().await;
+        assert_eq!(pool.amount, 11);
+        let before = ledger.total_supply().await.saturating_add(pool);
+
+        let paid = ledger
+            .distribute_revival(&[node1, node2, node3], DistributionStrategy::Equal)
+            .await
+            .unwrap();
+        assert_eq!(paid, pool);
+        assert!(ledger.revival_pool().await.is_zero());
+        assert_eq!(ledger.total_supply().await, before);
+
+        // Nothing left to pay out
+        let paid = ledger
+            .distribute_revival(&[node1], DistributionStrategy::Equal)
+            .await
+            .unwrap();
+        assert!(paid.is_zero());
+
+        // The state machine refuses payouts the pool can't cover
+        let response = ledger
+            .propose(CreditCommand::DistributeRevival {
+                payouts: vec![(node2, Credits::new(1))],
+            })
+            .await
+            .unwrap();
+        assert!(matches!(response, CreditResponse::Distribution(Err(_))));
+    }
+
+    #[tokio::test]
+    async fn test_configured_entropy_tax_rate() {
+        let node1 = NodeId::from_bytes([1u8; 32]);
+        let node2 = NodeId::from_bytes([2u8; 32]);
+
+        let mut config = RaftConfig {
+            entropy_tax_rate: 1.0,
+            ..RaftConfig::default()
+        };
+        let (publish, _) = mock_publish();
+        let result = RaftCreditLedger::new_with_config(node1, publish, config.clone(), true).await;
+        assert!(matches!(result, Err(RaftError::Config(_))));
+
+        config.entropy_tax_rate = 0.1;
+        let (publish, _) = mock_publish();
+        let ledger = RaftCreditLedger::new_with_config(node1, publish, config, true)
+            .await
+            .unwrap();
+        ledger
+            .grant_credits(node1, Credits::new(TEST_INITIAL_CREDITS))
+            .await
+            .unwrap();
+
+        ledger.transfer(node2, Credits::new(100)).await.unwrap();
+        assert_eq!(ledger.revival_pool().await.amount, 10);
+
+        ledger
+            .set_entropy_tax_rate(EntropyTaxRate::new(0.05).unwrap())
+            .await;
+        ledger.transfer(node2, Credits::new(100)).await.unwrap();
+        assert_eq!(ledger.revival_pool().await.amount, 15);
+        assert_eq!(ledger.local_balance().await.amount, 1000 - 110 - 105);
+    }
+
+    /// Three ledgers wired through an in-memory broadcast; a ledger marked
+    /// offline neither sends nor receives
+    async fn cluster() -> (Vec<Arc<RaftCreditLedger>>, Vec<Arc<AtomicBool>>) {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<(usize, Vec<u8>)>();
+        let mut config = RaftConfig::for_testing();
+        config.propose_timeout = 500;
+
+        let mut ledgers = Vec::new();
+        for i in 0..3 {
+            let tx = tx.clone();
+            let publish = move |_topic: String, bytes: Vec<u8>| {
+                tx.send((i, bytes)).map_err(|e| e.to_string())
+            };
+            let ledger = RaftCreditLedger::new_with_config(
+                NodeId::from_bytes([i as u8 + 1; 32]),
+                publish,
+                config.clone(),
+                i == 0,
+            )
+            .await
+            .unwrap();
+            ledgers.push(Arc::new(ledger));
+        }
+        for ledger in &ledgers {
+            for peer in &ledgers {
+                ledger.add_peer(peer.local_node).await;
+            }
+        }
+
+        let dropping: Vec<_> = (0..3).map(|_| Arc::new(AtomicBool::new(false))).collect();
+        let (nodes, drops) = (ledgers.clone(), dropping.clone());
+        tokio::spawn(async move {
+            while let Some((from, bytes)) = rx.recv().await {
+                if drops[from].load(Ordering::SeqCst) {
+                    continue;
+                }
+                for (i, node) in nodes.iter().enumerate() {
+                    if i != from && !drops[i].load(Ordering::SeqCst) {
+                        node.handle_message(&bytes).await.unwrap();
+                    }
+                }
+            }
+        });
+
+        (ledgers, dropping)
+    }
+
+    #[tokio::test]
+    async fn test_lagging_follower_catches_up() {
+        let (ledgers, dropping) = cluster().await;
+        let leader = &ledgers[0];
+        let node2 = ledgers[1].local_node;
+        let node3 = ledgers[2].local_node;
+
+        leader
+            .grant_credits(leader.local_node, Credits::new(1000))
+            .await
+            .unwrap();
+
+        // The third ledger misses two entries; the other follower still
+        // makes a quorum
+        dropping[2].store(true, Ordering::SeqCst);
+        leader.transfer(node2, Credits::new(100)).await.unwrap();
+        leader.grant_credits(node3, Credits::new(50)).await.unwrap();
+        assert!(ledgers[2]
+            .get_balance(&AccountId::node_account(node2))
+            .await
+            .is_zero());
+
+        // Back online: the next entry exposes the gap and it catches up
+        dropping[2].store(false, Ordering::SeqCst);
+        leader.transfer(node3, Credits::new(10)).await.unwrap();
+        leader.heartbeat().await;
+
+        let expected = leader.all_balances().await;
+        tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let mut converged = true;
+                for follower in &ledgers[1..] {
+                    converged &= follower.all_balances().await == expected
+                        && follower.revival_pool().await == leader.revival_pool().await;
+                }
+                if converged {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("followers did not converge");
+
+        assert_eq!(ledgers[2].leader().await, Some(leader.local_node));
+    }
+
+    #[tokio::test]
+    async fn test_propose_fails_without_quorum() {
+        let (ledgers, dropping) = cluster().await;
+        dropping[1].store(true, Ordering::SeqCst);
+        dropping[2].store(true, Ordering::SeqCst);
+
+        let leader = &ledgers[0];
+        let result = leader
+            .grant_credits(leader.local_node, Credits::new(1000))
+            .await;
+        assert!(matches!(
+            result,
+            Err(RaftError::QuorumTimeout { index: 1, .. })
+        ));
+        assert!(leader.local_balance().await.is_zero());
+    }
+
+    #[tokio::test]
+    async fn test_follower_redirects_to_leader() {
+        let (ledgers, _) = cluster().await;
+        let leader = &ledgers[0];
+        leader
+            .grant_credits(leader.local_node, Credits::new(1000))
+            .await
+            .unwrap();
+
+        let result = ledgers[1]
+            .grant_credits(ledgers[1].local_node, Credits::new(1000))
+            .await;
+        assert!(matches!(
+            result,
+            Err(RaftError::NotLeader { leader: Some(id) }) if id == leader.local_node
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_new_leader_elected_after_leader_fails() {
+        let (ledgers, offline) = cluster().await;
+        let old_leader = &ledgers[0];
+        let node2 = ledgers[1].local_node;
+        let node3 = ledgers[2].local_node;
+
+        old_leader
+            .grant_credits(node2, Credits::new(1000))
+            .await
+            .unwrap();
+        old_leader
+            .grant_credits(node3, Credits::new(500))
+            .await
+            .unwrap();
+
+        // The leader dies; the followers' timers run out and they elect
+        offline[0].store(true, Ordering::SeqCst);
+        let new_leader = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                for follower in &ledgers[1..] {
+                    follower.tick().await;
+                    if follower.is_leader().await {
+                        return follower.clone();
+                    }
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("no leader elected");
+        assert!(*new_leader.current_term.read().await > 1);
+
+        // The new leader commits with the remaining follower
+        let other = if new_leader.local_node == node2 {
+            node3
+        } else {
+            node2
+        };
+        new_leader.transfer(other, Credits::new(100)).await.unwrap();
+        new_leader.heartbeat().await;
+
+        let survivors = &ledgers[1..];
+        tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let a = survivors[0].all_balances().await;
+                let b = survivors[1].all_balances().await;
+                if a == b && a.len() == 2 && survivors[1].leader().await.is_some() {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("survivors did not converge");
+
+        // Earlier grants survived the change of leader
+        let total = survivors[0]
+            .total_supply()
+            .await
+            .saturating_add(survivors[0].revival_pool().await);
+        assert_eq!(total.amount, 1500);
+        for survivor in survivors {
+            assert_eq!(survivor.leader().await, Some(new_leader.local_node));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_late_follower_installs_snapshot() {
+        let (ledgers, offline) = cluster().await;
+        let leader = &ledgers[0];
+        let late = &ledgers[2];
+
+        // The third ledger is absent while the log grows past the threshold
+        offline[2].store(true, Ordering::SeqCst);
+        leader
+            .grant_credits(leader.local_node, Credits::new(1000))
+            .await
+            .unwrap();
+        for i in 1..=6 {
+            leader
+                .transfer(ledgers[1].local_node, Credits::new(i * 10))
+                .await
+                .unwrap();
+        }
+        let snapshot = leader.snapshot().await.expect("leader took no snapshot");
+        assert!(snapshot.last_index >= 5);
+
+        // It joins, finds a gap and is sent the snapshot
+        offline[2].store(false, Ordering::SeqCst);
+        leader.heartbeat().await;
+
+        let expected = leader.all_balances().await;
+        tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if late.all_balances().await == expected
+                    && late.revival_pool().await == leader.revival_pool().await
+                {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("late follower did not catch up");
+
+        let installed = late.snapshot().await.expect("no snapshot installed");
+        assert!(installed.last_index >= snapshot.last_index);
+    }
+
+    #[tokio::test]
+    async fn test_recovers_committed_transfers_after_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let node1 = NodeId::from_bytes([1u8; 32]);
+        let node2 = NodeId::from_bytes([2u8; 32]);
+        let mut config = RaftConfig::for_testing();
+        config.snapshot_threshold = 3;
+
+        let (balances, pool) = {
+            let (publish, _) = mock_publish();
+            let store = RaftStore::open(dir.path()).unwrap();
+            let ledger = RaftCreditLedger::with_store(node1, publish, config.clone(), true, store)
+                .await
+                .unwrap();
+
+            ledger
+                .grant_credits(node1, Credits::new(TEST_INITIAL_CREDITS))
+                .await
+                .unwrap();
+            for amount in [100, 50, 25] {
+                ledger.transfer(node2, Credits::new(amount)).await.unwrap();
+            }
+
+            // Entries 1-3 are in the snapshot, entry 4 only in the log
+            assert_eq!(ledger.snapshot().await.unwrap().last_index, 3);
+            (ledger.all_balances().await, ledger.revival_pool().await)
+        };
+
+        // The ledger and its store were dropped as if the process died
+        let (publish, _) = mock_publish();
+        let store = RaftStore::open(dir.path()).unwrap();
+        let ledger = RaftCreditLedger::with_store(node1, publish, config, true, store)
+            .await
+            .unwrap();
+
+        assert_eq!(ledger.all_balances().await, balances);
+        assert_eq!(ledger.revival_pool().await, pool);
+        assert_eq!(ledger.replication.lock().await.log.last_index(), 4);
+
+        // The log continues where it left off
+        ledger.transfer(node2, Credits::new(10)).await.unwrap();
+        assert_eq!(ledger.replication.lock().await.log.last_index(), 5);
+    }
 }
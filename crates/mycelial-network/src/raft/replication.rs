@@ -0,0 +1,397 @@
+//! Log replication for the Raft credit ledger
+//!
+//! The leader appends each proposal to its log and sends it to followers in
+//! an `AppendEntries` message. An entry is committed, and applied to the
+//! credit state machine, once a majority of the cluster has acknowledged it.
+//!
+//! Every `AppendEntries` names the entry preceding the new ones
+//! (`prev_index`/`prev_term`). A follower that does not hold that entry has
+//! missed messages, so it rejects the append and asks the leader to resend
+//! from its own log end with `RequestEntries`. The leader tracks a next index
+//! per follower and replays the log from there.
+//!
+//! Once `snapshot_threshold` applied entries have accumulated, each node
+//! captures its ledger state in a [`RaftSnapshot`] and drops those entries. A
+//! follower that needs entries the leader no longer holds is sent the
+//! snapshot instead (`InstallSnapshot`) and replays only what follows it.
+
+use serde::{Deserialize, Serialize};
+use univrs_enr::core::NodeId;
+
+use super::election::{RequestVote, VoteResponse};
+use super::{RaftLogEntry, RaftSnapshot};
+
+/// Raft protocol messages exchanged over gossipsub
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RaftMessage {
+    /// Leader → followers: new entries and the commit index
+    AppendEntries(AppendEntries),
+    /// Follower → leader: entries up to `match_index` are stored
+    AppendResponse(AppendResponse),
+    /// Follower → leader: resend the log from `next_index`
+    RequestEntries(RequestEntries),
+    /// Leader → follower: replace the log prefix with a snapshot
+    InstallSnapshot(InstallSnapshot),
+    /// Candidate → all: vote for me in `term`
+    RequestVote(RequestVote),
+    /// Voter → candidate: vote granted or refused
+    VoteResponse(VoteResponse),
+}
+
+impl RaftMessage {
+    /// Encode message to bytes
+    pub fn encode(&self) -> Result<Vec<u8>, bincode::Error> {
+        bincode::serialize(self)
+    }
+
+    /// Decode message from bytes
+    pub fn decode(bytes: &[u8]) -> Result<Self, bincode::Error> {
+        bincode::deserialize(bytes)
+    }
+}
+
+/// Entries sent from the leader
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppendEntries {
+    /// Leader sending the entries
+    pub leader: NodeId,
+    /// Follower this catch-up is meant for, or `None` for all followers
+    pub to: Option<NodeId>,
+    /// Leader's term
+    pub term: u64,
+    /// Index of the entry preceding `entries`
+    pub prev_index: u64,
+    /// Term of the entry at `prev_index`
+    pub prev_term: u64,
+    /// Entries to append, empty for a heartbeat
+    pub entries: Vec<RaftLogEntry>,
+    /// Highest index the leader has committed
+    pub leader_commit: u64,
+}
+
+/// Acknowledgement of an `AppendEntries`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppendResponse {
+    /// Acknowledging follower
+    pub from: NodeId,
+    /// Follower's term
+    pub term: u64,
+    /// Highest index known to match the leader's log
+    pub match_index: u64,
+}
+
+/// Request for entries a follower is missing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestEntries {
+    /// Requesting follower
+    pub from: NodeId,
+    /// Follower's term
+    pub term: u64,
+    /// First index the follower needs
+    pub next_index: u64,
+}
+
+/// Snapshot sent to a follower that needs compacted entries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallSnapshot {
+    /// Leader sending the snapshot
+    pub leader: NodeId,
+    /// Follower the snapshot is for
+    pub to: NodeId,
+    /// Leader's term
+    pub term: u64,
+    /// The snapshot
+    pub snapshot: RaftSnapshot,
+}
+
+/// Leader's view of one follower's log
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FollowerProgress {
+    /// Next index to send
+    pub next_index: u64,
+    /// Highest index known to be replicated
+    pub match_index: u64,
+}
+
+impl FollowerProgress {
+    /// Progress for a follower whose log is not yet known
+    pub fn new(leader_last_index: u64) -> Self {
+        Self {
+            next_index: leader_last_index + 1,
+            match_index: 0,
+        }
+    }
+}
+
+/// Ordered log of entries with commit and apply positions
+///
+/// Indices start at 1. Applied entries can be compacted into a snapshot, after
+/// which the log starts just past `snapshot_index`; index 0 stands for the
+/// empty log with term 0.
+#[derive(Debug, Default)]
+pub(crate) struct ReplicationLog {
+    /// Entries after the snapshot
+    entries: Vec<RaftLogEntry>,
+    /// Index of the last entry covered by the snapshot
+    snapshot_index: u64,
+    /// Term of the entry at `snapshot_index`
+    snapshot_term: u64,
+    commit_index: u64,
+    last_applied: u64,
+}
+
+impl ReplicationLog {
+    /// Rebuild a log from persisted state
+    ///
+    /// Entries up to the snapshot count as applied; committed entries after
+    /// it are left for [`take_committed`](Self::take_committed) to replay.
+    pub fn restore(
+        snapshot_index: u64,
+        snapshot_term: u64,
+        entries: Vec<RaftLogEntry>,
+        commit_index: u64,
+    ) -> Self {
+        let mut log = Self {
+            entries,
+            snapshot_index,
+            snapshot_term,
+            commit_index: snapshot_index,
+            last_applied: snapshot_index,
+        };
+        log.commit_to(commit_index);
+        log
+    }
+
+    /// Index of the last entry
+    pub fn last_index(&self) -> u64 {
+        self.snapshot_index + self.entries.len() as u64
+    }
+
+    /// Term of the last entry
+    pub fn last_term(&self) -> u64 {
+        self.entries.last().map_or(self.snapshot_term, |e| e.term)
+    }
+
+    /// Highest committed index
+    pub fn commit_index(&self) -> u64 {
+        self.commit_index
+    }
+
+    /// Index of the last entry replaced by a snapshot
+    pub fn snapshot_index(&self) -> u64 {
+        self.snapshot_index
+    }
+
+    /// Applied entries still held in the log
+    pub fn applied_since_snapshot(&self) -> u64 {
+        self.last_applied - self.snapshot_index
+    }
+
+    /// Term of the entry at `index`, if present
+    pub fn term_at(&self, index: u64) -> Option<u64> {
+        if index == self.snapshot_index {
+            return Some(self.snapshot_term);
+        }
+        let offset = index.checked_sub(self.snapshot_index + 1)?;
+        self.entries.get(offset as usize).map(|e| e.term)
+    }
+
+    /// Up to `max` entries starting at `index`, which must follow the snapshot
+    pub fn entries_from(&self, index: u64, max: usize) -> Vec<RaftLogEntry> {
+        let start = index.saturating_sub(self.snapshot_index + 1) as usize;
+        self.entries.iter().skip(start).take(max).cloned().collect()
+    }
+
+    /// Append a new entry on the leader
+    pub fn push(&mut self, entry: RaftLogEntry) {
+        debug_assert_eq!(entry.index, self.last_index() + 1);
+        self.entries.push(entry);
+    }
+
+    /// Append entries received from the leader after `prev_index`
+    ///
+    /// Returns `false` without changing the log if the entry at `prev_index`
+    /// is missing or from another term. Existing entries that conflict with
+    /// the leader's are discarded along with everything after them.
+    pub fn append_from_leader(
+        &mut self,
+        mut prev_index: u64,
+        mut prev_term: u64,
+        mut entries: &[RaftLogEntry],
+    ) -> bool {
+        // Entries covered by our snapshot are committed and cannot conflict
+        if prev_index < self.snapshot_index {
+            let covered = ((self.snapshot_index - prev_index) as usize).min(entries.len());
+            entries = &entries[covered..];
+            prev_index = self.snapshot_index;
+            prev_term = self.snapshot_term;
+        }
+        if self.term_at(prev_index) != Some(prev_term) {
+            return false;
+        }
+
+        for entry in entries {
+            match self.term_at(entry.index) {
+                Some(term) if term == entry.term => continue,
+                Some(_) => self
+                    .entries
+                    .truncate((entry.index - self.snapshot_index - 1) as usize),
+                None => {}
+            }
+            self.entries.push(entry.clone());
+        }
+        true
+    }
+
+    /// Raise the commit index, never past the end of the log
+    pub fn commit_to(&mut self, index: u64) {
+        self.commit_index = self.commit_index.max(index.min(self.last_index()));
+    }
+
+    /// Committed entries not yet applied, marking them applied
+    pub fn take_committed(&mut self) -> Vec<RaftLogEntry> {
+        let start = (self.last_applied - self.snapshot_index) as usize;
+        let end = (self.commit_index - self.snapshot_index) as usize;
+        self.last_applied = self.commit_index;
+        self.entries[start..end].to_vec()
+    }
+
+    /// Drop applied entries after they have been captured in a snapshot
+    ///
+    /// Returns the index and term of the last entry dropped.
+    pub fn compact(&mut self) -> (u64, u64) {
+        let index = self.last_applied;
+        let term = self.term_at(index).unwrap_or(self.snapshot_term);
+        self.entries.drain(..(index - self.snapshot_index) as usize);
+        self.snapshot_index = index;
+        self.snapshot_term = term;
+        (index, term)
+    }
+
+    /// Replace the log up to `index` with a snapshot received from the leader
+    ///
+    /// Entries after the snapshot are kept if the log agrees with it at
+    /// `index`; otherwise the whole log is discarded.
+    pub fn install_snapshot(&mut self, index: u64, term: u64) {
+        if index < self.last_index() && self.term_at(index) == Some(term) {
+            self.entries.drain(..(index - self.snapshot_index) as usize);
+        } else {
+            self.entries.clear();
+        }
+        self.snapshot_index = index;
+        self.snapshot_term = term;
+        self.commit_index = self.commit_index.max(index);
+        self.last_applied = index;
+    }
+}
+
+/// Highest index stored on a majority of the cluster
+///
+/// `match_indexes` holds one value per follower; the leader's own
+/// `leader_last_index` is counted as well.
+pub(crate) fn quorum_index(
+    match_indexes: impl Iterator<Item = u64>,
+    leader_last_index: u64,
+) -> u64 {
+    let mut indexes: Vec<u64> = match_indexes.collect();
+    indexes.push(leader_last_index);
+    indexes.sort_unstable_by(|a, b| b.cmp(a));
+    // With n members, the (n/2 + 1)-th highest index is held by a majority
+    indexes[indexes.len() / 2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raft::CreditCommand;
+
+    fn entry(term: u64, index: u64) -> RaftLogEntry {
+        RaftLogEntry {
+            term,
+            index,
+            command: CreditCommand::Noop,
+        }
+    }
+
+    #[test]
+    fn test_append_rejects_gap() {
+        let mut log = ReplicationLog::default();
+        assert!(log.append_from_leader(0, 0, &[entry(1, 1)]));
+
+        // Entry 2 was missed
+        assert!(!log.append_from_leader(2, 1, &[entry(1, 3)]));
+        assert_eq!(log.last_index(), 1);
+
+        assert!(log.append_from_leader(1, 1, &[entry(1, 2), entry(1, 3)]));
+        assert_eq!(log.last_index(), 3);
+    }
+
+    #[test]
+    fn test_append_replaces_conflicting_entries() {
+        let mut log = ReplicationLog::default();
+        assert!(log.append_from_leader(0, 0, &[entry(1, 1), entry(1, 2), entry(1, 3)]));
+
+        assert!(log.append_from_leader(1, 1, &[entry(2, 2)]));
+        assert_eq!(log.last_index(), 2);
+        assert_eq!(log.term_at(2), Some(2));
+    }
+
+    #[test]
+    fn test_commit_and_take() {
+        let mut log = ReplicationLog::default();
+        log.push(entry(1, 1));
+        log.push(entry(1, 2));
+
+        log.commit_to(5);
+        assert_eq!(log.commit_index(), 2);
+        assert_eq!(log.take_committed().len(), 2);
+        assert!(log.take_committed().is_empty());
+    }
+
+    #[test]
+    fn test_compact_keeps_indices() {
+        let mut log = ReplicationLog::default();
+        for index in 1..=4 {
+            log.push(entry(1, index));
+        }
+        log.commit_to(3);
+        assert_eq!(log.take_committed().len(), 3);
+
+        assert_eq!(log.compact(), (3, 1));
+        assert_eq!(log.last_index(), 4);
+        assert_eq!(log.term_at(3), Some(1));
+        assert_eq!(log.term_at(2), None);
+        assert_eq!(log.entries_from(4, 10)[0].index, 4);
+
+        // Resent entries already in the snapshot are skipped
+        assert!(log.append_from_leader(
+            1,
+            1,
+            &[entry(1, 2), entry(1, 3), entry(1, 4), entry(1, 5)]
+        ));
+        assert_eq!(log.last_index(), 5);
+    }
+
+    #[test]
+    fn test_install_snapshot_replaces_log() {
+        let mut log = ReplicationLog::default();
+        log.push(entry(1, 1));
+
+        log.install_snapshot(10, 2);
+        assert_eq!(log.last_index(), 10);
+        assert_eq!(log.last_term(), 2);
+        assert_eq!(log.commit_index(), 10);
+        assert!(log.take_committed().is_empty());
+        assert!(log.append_from_leader(10, 2, &[entry(2, 11)]));
+    }
+
+    #[test]
+    fn test_quorum_index() {
+        // Leader alone
+        assert_eq!(quorum_index(std::iter::empty(), 4), 4);
+        // Three members: leader and one follower form a majority
+        assert_eq!(quorum_index([3, 0].into_iter(), 4), 3);
+        // Five members need three
+        assert_eq!(quorum_index([4, 2, 1, 0].into_iter(), 4), 2);
+    }
+}
@@ -0,0 +1,158 @@
+//! Paying out the revival pool
+//!
+//! Entropy tax collects in the revival pool. A distribution splits the pool
+//! between a set of recipients and is committed as a single Raft entry, so
+//! every node credits the same amounts. Shares are worked out by the leader
+//! when it proposes the distribution; the state machine only checks that
+//! the pool covers them.
+
+use std::collections::{HashMap, HashSet};
+use univrs_enr::core::{AccountId, Credits, NodeId};
+
+/// How the revival pool is split between recipients
+#[derive(Debug, Clone, PartialEq)]
+pub enum DistributionStrategy {
+    /// Every recipient gets the same share
+    Equal,
+    /// Shares in proportion to each recipient's reputation
+    ///
+    /// Recipients without a (positive) reputation get nothing.
+    ReputationWeighted(HashMap<NodeId, f64>),
+    /// Only recipients whose balance is below `threshold`, in proportion to
+    /// how far below it they are
+    LowBalance { threshold: Credits },
+}
+
+/// Work out each recipient's share of `pool`
+///
+/// Shares are rounded down and the leftover credits go to the recipients
+/// with the largest remainders, so the whole pool is paid out whenever at
+/// least one recipient qualifies. Recipients getting nothing are left out.
+pub fn plan_distribution(
+    pool: Credits,
+    recipients: &[NodeId],
+    strategy: &DistributionStrategy,
+    balances: &HashMap<AccountId, Credits>,
+) -> Vec<(NodeId, Credits)> {
+    let mut seen = HashSet::new();
+    let weights: Vec<(NodeId, f64)> = recipients
+        .iter()
+        .filter(|node| seen.insert(**node))
+        .map(|node| {
+            let weight = match strategy {
+                DistributionStrategy::Equal => 1.0,
+                DistributionStrategy::ReputationWeighted(reputations) => {
+                    reputations.get(node).copied().unwrap_or(0.0)
+                }
+                DistributionStrategy::LowBalance { threshold } => {
+                    let balance = balances
+                        .get(&AccountId::node_account(*node))
+                        .copied()
+                        .unwrap_or(Credits::ZERO);
+                    threshold.amount.saturating_sub(balance.amount) as f64
+                }
+            };
+            (*node, weight)
+        })
+        .filter(|(_, weight)| weight.is_finite() && *weight > 0.0)
+        .collect();
+
+    if pool.is_zero() || weights.is_empty() {
+        return Vec::new();
+    }
+    let total_weight: f64 = weights.iter().map(|(_, w)| w).sum();
+
+    let mut shares: Vec<(NodeId, u64, f64)> = weights
+        .iter()
+        .map(|(node, weight)| {
+            let exact = pool.amount as f64 * weight / total_weight;
+            let whole = (exact.floor() as u64).min(pool.amount);
+            (*node, whole, exact - whole as f64)
+        })
+        .collect();
+
+    let paid: u64 = shares.iter().map(|(_, whole, _)| whole).sum();
+    let leftover = pool.amount.saturating_sub(paid);
+    let mut by_remainder: Vec<usize> = (0..shares.len()).collect();
+    by_remainder.sort_by(|a, b| shares[*b].2.total_cmp(&shares[*a].2));
+    for &i in by_remainder.iter().cycle().take(leftover as usize) {
+        shares[i].1 += 1;
+    }
+
+    shares
+        .into_iter()
+        .filter(|(_, amount, _)| *amount > 0)
+        .map(|(node, amount, _)| (node, Credits::new(amount)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(seed: u8) -> NodeId {
+        NodeId::from_bytes([seed; 32])
+    }
+
+    fn total(payouts: &[(NodeId, Credits)]) -> u64 {
+        payouts.iter().map(|(_, c)| c.amount).sum()
+    }
+
+    #[test]
+    fn test_equal_split_pays_out_whole_pool() {
+        let recipients = [node(1), node(2), node(3), node(1)];
+        let payouts = plan_distribution(
+            Credits::new(100),
+            &recipients,
+            &DistributionStrategy::Equal,
+            &HashMap::new(),
+        );
+
+        assert_eq!(payouts.len(), 3);
+        assert_eq!(total(&payouts), 100);
+        assert!(payouts
+            .iter()
+            .all(|(_, c)| c.amount == 33 || c.amount == 34));
+    }
+
+    #[test]
+    fn test_weighted_and_low_balance_splits() {
+        let reputations = HashMap::from([(node(1), 0.75), (node(2), 0.25)]);
+        let payouts = plan_distribution(
+            Credits::new(100),
+            &[node(1), node(2), node(3)],
+            &DistributionStrategy::ReputationWeighted(reputations),
+            &HashMap::new(),
+        );
+        assert_eq!(
+            payouts,
+            vec![(node(1), Credits::new(75)), (node(2), Credits::new(25))]
+        );
+
+        let balances = HashMap::from([
+            (AccountId::node_account(node(1)), Credits::new(1000)),
+            (AccountId::node_account(node(2)), Credits::new(400)),
+            (AccountId::node_account(node(3)), Credits::new(100)),
+        ]);
+        let payouts = plan_distribution(
+            Credits::new(60),
+            &[node(1), node(2), node(3)],
+            &DistributionStrategy::LowBalance {
+                threshold: Credits::new(500),
+            },
+            &balances,
+        );
+        assert_eq!(
+            payouts,
+            vec![(node(2), Credits::new(12)), (node(3), Credits::new(48))]
+        );
+
+        assert!(plan_distribution(
+            Credits::ZERO,
+            &[node(1)],
+            &DistributionStrategy::Equal,
+            &HashMap::new()
+        )
+        .is_empty());
+    }
+}
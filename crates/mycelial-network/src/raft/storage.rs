@@ -0,0 +1,233 @@
+//! Durable storage for the Raft credit ledger
+//!
+//! [`RaftStore`] keeps everything a ledger needs to survive a restart in a
+//! sled database:
+//!
+//! - the current term and the vote cast in it,
+//! - the log entries not yet covered by a snapshot, keyed by index,
+//! - the commit index,
+//! - the latest [`RaftSnapshot`].
+//!
+//! Balances are not stored separately: on startup the ledger restores the
+//! snapshot and replays committed entries after it.
+
+use std::path::Path;
+use univrs_enr::core::NodeId;
+
+use super::{RaftError, RaftLogEntry, RaftSnapshot};
+
+/// Key of the term and vote in the metadata tree
+const HARD_STATE_KEY: &[u8] = b"hard_state";
+
+/// Key of the commit index in the metadata tree
+const COMMIT_KEY: &[u8] = b"commit";
+
+/// Key of the latest snapshot in the metadata tree
+const SNAPSHOT_KEY: &[u8] = b"snapshot";
+
+/// Term and vote, which must never go backwards across restarts
+type HardState = (u64, Option<(u64, NodeId)>);
+
+/// State read back from a [`RaftStore`]
+#[derive(Debug, Clone, Default)]
+pub struct RecoveredState {
+    /// Last persisted term
+    pub term: u64,
+    /// Vote cast in `term`, with the term it was cast in
+    pub voted_for: Option<(u64, NodeId)>,
+    /// Highest index known to be committed
+    pub commit_index: u64,
+    /// Latest snapshot
+    pub snapshot: Option<RaftSnapshot>,
+    /// Log entries after the snapshot, in index order
+    pub entries: Vec<RaftLogEntry>,
+}
+
+/// sled-backed log and state store
+#[derive(Clone)]
+pub struct RaftStore {
+    db: sled::Db,
+    log: sled::Tree,
+    meta: sled::Tree,
+}
+
+impl RaftStore {
+    /// Open or create a store at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, RaftError> {
+        Self::from_db(sled::open(path).map_err(storage_error)?)
+    }
+
+    /// Open a store that is deleted when dropped (for testing)
+    pub fn temporary() -> Result<Self, RaftError> {
+        let db = sled::Config::new()
+            .temporary(true)
+            .open()
+            .map_err(storage_error)?;
+        Self::from_db(db)
+    }
+
+    fn from_db(db: sled::Db) -> Result<Self, RaftError> {
+        Ok(Self {
+            log: db.open_tree("log").map_err(storage_error)?,
+            meta: db.open_tree("meta").map_err(storage_error)?,
+            db,
+        })
+    }
+
+    /// Read back everything persisted so far
+    pub fn load(&self) -> Result<RecoveredState, RaftError> {
+        let (term, voted_for) = self.get::<HardState>(HARD_STATE_KEY)?.unwrap_or_default();
+        let commit_index = self.get::<u64>(COMMIT_KEY)?.unwrap_or(0);
+        let snapshot = self.get::<RaftSnapshot>(SNAPSHOT_KEY)?;
+
+        let entries = self
+            .log
+            .iter()
+            .values()
+            .map(|value| {
+                let value = value.map_err(storage_error)?;
+                bincode::deserialize(&value).map_err(|e| RaftError::Storage(e.to_string()))
+            })
+            .collect::<Result<Vec<RaftLogEntry>, _>>()?;
+
+        Ok(RecoveredState {
+            term,
+            voted_for,
+            commit_index,
+            snapshot,
+            entries,
+        })
+    }
+
+    /// Persist the current term and vote
+    pub fn save_hard_state(
+        &self,
+        term: u64,
+        voted_for: Option<(u64, NodeId)>,
+    ) -> Result<(), RaftError> {
+        self.put(HARD_STATE_KEY, &(term, voted_for))
+    }
+
+    /// Persist the commit index
+    pub fn save_commit(&self, index: u64) -> Result<(), RaftError> {
+        self.put(COMMIT_KEY, &index)
+    }
+
+    /// Replace the log from `index` onwards with `entries`
+    pub fn replace_from(&self, index: u64, entries: &[RaftLogEntry]) -> Result<(), RaftError> {
+        let mut batch = sled::Batch::default();
+        for key in self.log.range(index.to_be_bytes()..).keys() {
+            batch.remove(key.map_err(storage_error)?);
+        }
+        for entry in entries {
+            let value = bincode::serialize(entry).map_err(|e| RaftError::Storage(e.to_string()))?;
+            batch.insert(entry.index.to_be_bytes().to_vec(), value);
+        }
+        self.log.apply_batch(batch).map_err(storage_error)?;
+        self.flush()
+    }
+
+    /// Persist a snapshot and drop the log entries it covers
+    pub fn save_snapshot(&self, snapshot: &RaftSnapshot) -> Result<(), RaftError> {
+        self.put(SNAPSHOT_KEY, snapshot)?;
+
+        let mut batch = sled::Batch::default();
+        for key in self.log.range(..=snapshot.last_index.to_be_bytes()).keys() {
+            batch.remove(key.map_err(storage_error)?);
+        }
+        self.log.apply_batch(batch).map_err(storage_error)?;
+        self.flush()
+    }
+
+    fn get<T: serde::de::DeserializeOwned>(&self, key: &[u8]) -> Result<Option<T>, RaftError> {
+        self.meta
+            .get(key)
+            .map_err(storage_error)?
+            .map(|value| bincode::deserialize(&value))
+            .transpose()
+            .map_err(|e| RaftError::Storage(e.to_string()))
+    }
+
+    fn put<T: serde::Serialize>(&self, key: &[u8], value: &T) -> Result<(), RaftError> {
+        let value = bincode::serialize(value).map_err(|e| RaftError::Storage(e.to_string()))?;
+        self.meta.insert(key, value).map_err(storage_error)?;
+        self.flush()
+    }
+
+    /// Wait until written data is durable on disk
+    fn flush(&self) -> Result<(), RaftError> {
+        self.db.flush().map_err(storage_error)?;
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for RaftStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RaftStore")
+            .field("entries", &self.log.len())
+            .finish()
+    }
+}
+
+fn storage_error(e: sled::Error) -> RaftError {
+    RaftError::Storage(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raft::CreditCommand;
+    use univrs_enr::core::Credits;
+
+    fn entry(term: u64, index: u64) -> RaftLogEntry {
+        RaftLogEntry {
+            term,
+            index,
+            command: CreditCommand::Noop,
+        }
+    }
+
+    #[test]
+    fn test_store_round_trip() {
+        let store = RaftStore::temporary().unwrap();
+        let voter = NodeId::from_bytes([7u8; 32]);
+
+        store.save_hard_state(3, Some((3, voter))).unwrap();
+        store
+            .replace_from(1, &[entry(1, 1), entry(1, 2), entry(2, 3)])
+            .unwrap();
+        store.save_commit(2).unwrap();
+
+        // A conflicting suffix is replaced
+        store.replace_from(3, &[entry(3, 3)]).unwrap();
+
+        let state = store.load().unwrap();
+        assert_eq!(state.term, 3);
+        assert_eq!(state.voted_for, Some((3, voter)));
+        assert_eq!(state.commit_index, 2);
+        assert_eq!(state.entries.len(), 3);
+        assert_eq!(state.entries[2].term, 3);
+    }
+
+    #[test]
+    fn test_snapshot_drops_covered_entries() {
+        let store = RaftStore::temporary().unwrap();
+        store
+            .replace_from(1, &[entry(1, 1), entry(1, 2), entry(1, 3)])
+            .unwrap();
+
+        store
+            .save_snapshot(&RaftSnapshot {
+                last_index: 2,
+                last_term: 1,
+                balances: Default::default(),
+                revival_pool: Credits::new(4),
+            })
+            .unwrap();
+
+        let state = store.load().unwrap();
+        assert_eq!(state.snapshot.unwrap().revival_pool.amount, 4);
+        assert_eq!(state.entries.len(), 1);
+        assert_eq!(state.entries[0].index, 3);
+    }
+}
@@ -19,6 +19,8 @@ pub enum CreditCommand {
         reason: String,
         timestamp: Timestamp,
     },
+    /// Pay out the revival pool
+    DistributeRevival { payouts: Vec<(NodeId, Credits)> },
     /// No-op command (for testing/heartbeat)
     Noop,
 }
@@ -32,6 +34,8 @@ pub enum CreditResponse {
     Grant,
     /// Response for a failure record
     FailureRecorded,
+    /// Response for a revival distribution (total paid out, or error message)
+    Distribution(Result<Credits, String>),
     /// Response for no-op
     Noop,
 }
@@ -57,6 +61,19 @@ impl Default for CreditSnapshot {
     }
 }
 
+/// Ledger state as of a log index, replacing the entries up to it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RaftSnapshot {
+    /// Index of the last entry the snapshot includes
+    pub last_index: u64,
+    /// Term of the entry at `last_index`
+    pub last_term: u64,
+    /// Account balances after applying `last_index`
+    pub balances: std::collections::HashMap<AccountId, Credits>,
+    /// Revival pool balance after applying `last_index`
+    pub revival_pool: Credits,
+}
+
 /// Convert ENR NodeId to u64 (uses first 8 bytes)
 pub fn node_id_to_u64(node_id: NodeId) -> u64 {
     let bytes = node_id.to_bytes();
@@ -0,0 +1,218 @@
+//! Per-topic publish rate limiting
+//!
+//! Each topic with a [`RateLimit`] gets a token bucket, filled lazily on
+//! its first publish. [`NetworkHandle::publish`] takes a token before
+//! handing the message to the service and fails with
+//! [`NetworkError::RateLimited`] when the bucket is empty, so a flooding
+//! component finds out instead of silently burning the node's gossipsub
+//! score. A publish that never reaches the service gets its token back and
+//! isn't counted as published.
+//!
+//! [`NetworkHandle::publish`]: crate::NetworkHandle::publish
+//! [`NetworkError::RateLimited`]: crate::NetworkError::RateLimited
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+
+use crate::config::{NetworkConfig, RateLimit};
+
+/// Tokens refilled at a steady rate, up to a burst
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    limit: RateLimit,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    /// A full bucket for `limit`
+    pub fn new(limit: RateLimit, now: Instant) -> Self {
+        Self {
+            limit,
+            tokens: f64::from(limit.burst),
+            updated: now,
+        }
+    }
+
+    /// Tokens available at `now`
+    pub fn available(&mut self, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * self.limit.per_second).min(f64::from(self.limit.burst));
+        self.updated = now;
+        self.tokens
+    }
+
+    /// Return a token taken for something that didn't happen
+    pub fn refund(&mut self) {
+        self.tokens = (self.tokens + 1.0).min(f64::from(self.limit.burst));
+    }
+
+    /// Take a token, or say how long until one is available
+    pub fn try_take(&mut self, now: Instant) -> Result<(), Duration> {
+        let available = self.available(now);
+        if available >= 1.0 {
+            self.tokens -= 1.0;
+            return Ok(());
+        }
+        Err(Duration::from_secs_f64(
+            (1.0 - available) / self.limit.per_second,
+        ))
+    }
+}
+
+/// How much of a topic's publish rate limit is in use
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TopicRateUsage {
+    /// Messages that could be published right now
+    pub available: f64,
+    /// The topic's burst size
+    pub burst: u32,
+    /// The topic's sustained messages per second
+    pub per_second: f64,
+    /// Publishes handed to the service so far
+    pub published: u64,
+    /// Publishes refused for exceeding the limit
+    pub rate_limited: u64,
+}
+
+/// A topic's bucket and counters
+#[derive(Debug)]
+struct TopicBucket {
+    bucket: TokenBucket,
+    published: u64,
+    rate_limited: u64,
+}
+
+/// Rate limits for every topic we publish to
+#[derive(Debug, Default)]
+pub(crate) struct PublishLimiter {
+    default: Option<RateLimit>,
+    limits: BTreeMap<String, RateLimit>,
+    topics: HashMap<String, TopicBucket>,
+}
+
+impl PublishLimiter {
+    /// Limits as configured in `config`
+    pub(crate) fn new(config: &NetworkConfig) -> Self {
+        Self {
+            default: config.publish_rate_limit,
+            limits: config.topic_rate_limits.clone(),
+            topics: HashMap::new(),
+        }
+    }
+
+    /// Take a token for a publish to `topic`, or say how long until one is
+    /// available
+    pub(crate) fn check(&mut self, topic: &str, now: Instant) -> Result<(), Duration> {
+        let Some(limit) = self.limits.get(topic).copied().or(self.default) else {
+            return Ok(());
+        };
+        let entry = self
+            .topics
+            .entry(topic.to_string())
+            .or_insert_with(|| TopicBucket {
+                bucket: TokenBucket::new(limit, now),
+                published: 0,
+                rate_limited: 0,
+            });
+        entry
+            .bucket
+            .try_take(now)
+            .inspect_err(|_| entry.rate_limited += 1)
+    }
+
+    /// Settle the token [`check`](Self::check) took for a publish to
+    /// `topic`: count the publish if it was `sent`, or give the token back
+    pub(crate) fn settle(&mut self, topic: &str, sent: bool) {
+        let Some(entry) = self.topics.get_mut(topic) else {
+            return;
+        };
+        if sent {
+            entry.published += 1;
+        } else {
+            entry.bucket.refund();
+        }
+    }
+
+    /// Usage of every rate-limited topic published to so far
+    pub(crate) fn usage(&mut self, now: Instant) -> BTreeMap<String, TopicRateUsage> {
+        self.topics
+            .iter_mut()
+            .map(|(topic, entry)| {
+                let usage = TopicRateUsage {
+                    available: entry.bucket.available(now),
+                    burst: entry.bucket.limit.burst,
+                    per_second: entry.bucket.limit.per_second,
+                    published: entry.published,
+                    rate_limited: entry.rate_limited,
+                };
+                (topic.clone(), usage)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_bucket_refills() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(RateLimit::new(2.0, 3), start);
+
+        // A full burst, then nothing until the bucket refills
+        for _ in 0..3 {
+            bucket.try_take(start).unwrap();
+        }
+        assert_eq!(bucket.try_take(start), Err(Duration::from_millis(500)));
+
+        bucket.try_take(start + Duration::from_millis(500)).unwrap();
+        assert!(bucket.try_take(start + Duration::from_millis(500)).is_err());
+
+        // Never refills beyond the burst
+        assert_eq!(bucket.available(start + Duration::from_secs(60)), 3.0);
+    }
+
+    #[test]
+    fn test_publish_limiter_per_topic() {
+        let mut config = NetworkConfig::default();
+        config.publish_rate_limit = Some(RateLimit::new(1.0, 1));
+        config
+            .topic_rate_limits
+            .insert("economics".to_string(), RateLimit::new(10.0, 5));
+        let mut limiter = PublishLimiter::new(&config);
+        let now = Instant::now();
+
+        limiter.check("chat", now).unwrap();
+        limiter.settle("chat", true);
+        assert!(limiter.check("chat", now).is_err());
+        for _ in 0..5 {
+            limiter.check("economics", now).unwrap();
+            limiter.settle("economics", true);
+        }
+        assert!(limiter.check("economics", now).is_err());
+
+        // A publish that wasn't sent isn't counted and gives its token back
+        limiter.check("votes", now).unwrap();
+        limiter.settle("votes", false);
+        limiter.check("votes", now).unwrap();
+
+        let usage = limiter.usage(now);
+        assert_eq!(usage["chat"].published, 1);
+        assert_eq!(usage["chat"].rate_limited, 1);
+        assert_eq!(usage["economics"].burst, 5);
+        assert_eq!(usage["economics"].available, 0.0);
+        assert_eq!(usage["economics"].published, 5);
+        assert_eq!(usage["votes"].published, 0);
+
+        // Without limits everything goes through, untracked
+        let mut unlimited = PublishLimiter::default();
+        for _ in 0..100 {
+            unlimited.check("chat", now).unwrap();
+        }
+        assert!(unlimited.usage(now).is_empty());
+    }
+}
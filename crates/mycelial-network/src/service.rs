@@ -3,25 +3,52 @@
 //! The NetworkService manages the libp2p swarm, handles events,
 //! and provides a high-level API for network operations.
 
+use async_trait::async_trait;
 use futures::StreamExt;
-use libp2p::{gossipsub, identify, kad, mdns, swarm::SwarmEvent, Multiaddr, PeerId, Swarm};
-use parking_lot::RwLock;
-use std::collections::HashSet;
+use libp2p::core::transport::ListenerId;
+use libp2p::multiaddr::Protocol;
+use libp2p::swarm::{dial_opts::DialOpts, ConnectionId, SwarmEvent};
+use libp2p::{
+    autonat, gossipsub, identify, kad, mdns, ping, relay, request_response, Multiaddr, PeerId,
+    Swarm,
+};
+use mycelial_core::content::{Content, ContentId};
+use mycelial_core::health::{HealthCheck, HealthStatus};
+use mycelial_core::message::{correlation_id, payload_id};
+use mycelial_core::{ContentStore, Reputation};
+use parking_lot::{Mutex, RwLock};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::{broadcast, mpsc};
-use tracing::{debug, info, warn};
+use tracing::{debug, info, info_span, warn, Instrument};
 
 use crate::behaviour::{MycelialBehaviour, MycelialBehaviourEvent};
 use crate::config::NetworkConfig;
+use crate::content::{
+    self, ContentRequest, ContentResponse, MemoryContentStore, MAX_CONTENT_CHUNKS,
+};
 #[cfg(feature = "univrs-compat")]
-use crate::enr_bridge::{EnrBridge, CREDIT_TOPIC, ELECTION_TOPIC, GRADIENT_TOPIC, SEPTAL_TOPIC};
+use crate::enr_bridge::{
+    enr_topics, node_id_from_peer_id, node_key_from_keypair, peer_id_from_node_id, EnrBridge,
+    SeptalStateMsg, BRIDGE_TOPICS, ELECTION_TOPIC,
+};
 use crate::error::{NetworkError, Result};
-use crate::event::{NetworkEvent, NetworkStats};
-use crate::peer::{ConnectionState, PeerManager};
+use crate::event::{MeshStatus, NatStatus, NetworkEvent, NetworkStats};
+use crate::fragment::{self, FragmentBuffer};
+use crate::peer::{ConnectionState, PeerBan, PeerManager};
+use crate::rate_limit::PublishLimiter;
 use crate::transport::{self, TransportConfig};
-#[cfg(feature = "univrs-compat")]
-use univrs_enr::core::NodeId;
+use crate::validation::{
+    InboundMessage, MessageValidator, RejectAction, Rejection, ValidationPipeline,
+};
+
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+
+/// Reputation (0.0 - 1.0) of a peer, or `None` if unknown
+pub type RecordReputationFn = Box<dyn Fn(&PeerId) -> Option<f64> + Send + Sync>;
 
 /// Commands sent to the network service
 #[derive(Debug)]
@@ -36,10 +63,59 @@ pub enum NetworkCommand {
     Unsubscribe { topic: String },
     /// Publish a message
     Publish { topic: String, data: Vec<u8> },
-    /// Store a value in the DHT
-    PutRecord { key: Vec<u8>, value: Vec<u8> },
-    /// Get a value from the DHT
-    GetRecord { key: Vec<u8> },
+    /// Publish a message, retrying with backoff until the topic has peers
+    /// to send it to or `deadline` has passed
+    PublishReliable {
+        topic: String,
+        data: Vec<u8>,
+        deadline: Duration,
+    },
+    /// Store a value in the DHT, reporting the outcome on `response` if set
+    PutRecord {
+        key: Vec<u8>,
+        value: Vec<u8>,
+        response: Option<tokio::sync::oneshot::Sender<Result<()>>>,
+    },
+    /// Get a value from the DHT, reporting the outcome on `response` if set
+    GetRecord {
+        key: Vec<u8>,
+        response: Option<tokio::sync::oneshot::Sender<Result<Option<Vec<u8>>>>>,
+    },
+    /// Serve a piece of content and announce us as a provider in the DHT
+    ProvideContent { content: Content },
+    /// Stop serving a piece of content and withdraw our provider record
+    StopProviding { id: ContentId },
+    /// Pin or unpin a piece of content in the content store, reporting
+    /// whether it is stored
+    PinContent {
+        id: ContentId,
+        pinned: bool,
+        response: tokio::sync::oneshot::Sender<Result<bool>>,
+    },
+    /// Find the providers of a piece of content, best-scored first
+    FindProviders {
+        id: ContentId,
+        response: tokio::sync::oneshot::Sender<Result<Vec<PeerId>>>,
+    },
+    /// Look for the providers of a piece of content, ourselves included,
+    /// for at most `timeout` and until `enough` are found
+    ProbeProviders {
+        id: ContentId,
+        timeout: Duration,
+        enough: Option<usize>,
+        response: tokio::sync::oneshot::Sender<Result<Vec<PeerId>>>,
+    },
+    /// Request one chunk of a piece of content from a provider
+    RequestContent {
+        peer_id: PeerId,
+        request: ContentRequest,
+        response: tokio::sync::oneshot::Sender<Result<ContentResponse>>,
+    },
+    /// Get a peer's reputation
+    GetReputation {
+        peer_id: PeerId,
+        response: tokio::sync::oneshot::Sender<Option<Reputation>>,
+    },
     /// Get connected peers
     GetPeers {
         response: tokio::sync::oneshot::Sender<Vec<PeerId>>,
@@ -48,6 +124,44 @@ pub enum NetworkCommand {
     GetStats {
         response: tokio::sync::oneshot::Sender<NetworkStats>,
     },
+    /// Get our AutoNAT reachability
+    GetNatStatus {
+        response: tokio::sync::oneshot::Sender<NatStatus>,
+    },
+    /// Get the gossipsub mesh state for a topic
+    GetMeshStatus {
+        topic: String,
+        response: tokio::sync::oneshot::Sender<MeshStatus>,
+    },
+    /// Get the topics we are subscribed to
+    GetSubscribedTopics {
+        response: tokio::sync::oneshot::Sender<Vec<String>>,
+    },
+    /// Get the connection quality of every known peer
+    GetPeerQualities {
+        response: tokio::sync::oneshot::Sender<HashMap<PeerId, f64>>,
+    },
+    /// Ban a peer for `duration` (`None` until unbanned), closing its
+    /// connections
+    BanPeer {
+        peer_id: PeerId,
+        duration: Option<Duration>,
+        reason: String,
+    },
+    /// Lift a peer's ban, reporting on `response` whether it was banned
+    UnbanPeer {
+        peer_id: PeerId,
+        response: Option<tokio::sync::oneshot::Sender<bool>>,
+    },
+    /// Get the bans in force
+    GetBannedPeers {
+        response: tokio::sync::oneshot::Sender<Vec<PeerBan>>,
+    },
+    /// Keep exchanging messages with a peer whatever its score, redialing
+    /// it at `address` whenever it disconnects
+    AddExplicitPeer { peer_id: PeerId, address: Multiaddr },
+    /// Stop treating a peer as explicit
+    RemoveExplicitPeer { peer_id: PeerId },
     /// Block a peer (partition testing)
     BlockPeer { peer_id: PeerId },
     /// Unblock a specific peer (partition testing)
@@ -56,6 +170,66 @@ pub enum NetworkCommand {
     UnblockAllPeers,
     /// Shutdown
     Shutdown,
+    /// Graceful shutdown: disconnect peers, then acknowledge once the loop stops
+    ShutdownGraceful {
+        response: tokio::sync::oneshot::Sender<()>,
+    },
+}
+
+/// How often a caller waiting on the service re-checks that it is still running
+const LIVENESS_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Longest gap between checks for idle topics
+const TOPIC_IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long a provider probe searches the DHT before answering
+pub const PROVIDER_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often provider probes are checked against their deadline
+const PROVIDER_PROBE_CHECK_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Delay before the first retry of a reliable publish
+const PUBLISH_RETRY_INITIAL: Duration = Duration::from_millis(250);
+
+/// Longest delay between retries of a reliable publish
+const PUBLISH_RETRY_MAX: Duration = Duration::from_secs(8);
+
+/// How often the outbox is checked for publishes due a retry
+const PUBLISH_RETRY_CHECK_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Longest a reliable publish is retried; longer deadlines are cut to this
+const PUBLISH_DEADLINE_MAX: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Most reliable publishes waiting to be retried at once
+const PUBLISH_OUTBOX_CAPACITY: usize = 1024;
+
+/// How long the ENR bridge's publishes are retried while the mesh forms
+#[cfg(feature = "univrs-compat")]
+const ENR_PUBLISH_DEADLINE: Duration = Duration::from_secs(30);
+
+/// How long a health check waits for the service to answer
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Liveness flag owned by the service, cleared when the service is dropped
+///
+/// `run()` consumes the service, so the flag is cleared both when the event
+/// loop returns and when it unwinds from a panic.
+struct Liveness(Arc<AtomicBool>);
+
+impl Liveness {
+    fn new() -> Self {
+        Self(Arc::new(AtomicBool::new(true)))
+    }
+
+    fn flag(&self) -> Arc<AtomicBool> {
+        self.0.clone()
+    }
+}
+
+impl Drop for Liveness {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
 }
 
 /// Handle for interacting with the network service
@@ -63,6 +237,8 @@ pub enum NetworkCommand {
 pub struct NetworkHandle {
     command_tx: mpsc::Sender<NetworkCommand>,
     local_peer_id: PeerId,
+    alive: Arc<AtomicBool>,
+    publish_limiter: Arc<Mutex<PublishLimiter>>,
 }
 
 impl NetworkHandle {
@@ -71,126 +247,801 @@ impl NetworkHandle {
         self.local_peer_id
     }
 
+    /// Whether the network service is still running
+    ///
+    /// Once this returns `false`, every command fails with
+    /// [`NetworkError::ServiceUnavailable`].
+    pub fn is_alive(&self) -> bool {
+        self.alive.load(Ordering::Acquire)
+    }
+
     /// Dial a peer by multiaddr
     pub async fn dial(&self, address: Multiaddr) -> Result<()> {
-        self.command_tx
-            .send(NetworkCommand::Dial { address })
-            .await
-            .map_err(|_| NetworkError::Channel("Failed to send dial command".into()))
+        self.send(NetworkCommand::Dial { address }, "dial").await
     }
 
     /// Disconnect from a peer
     pub async fn disconnect(&self, peer_id: PeerId) -> Result<()> {
-        self.command_tx
-            .send(NetworkCommand::Disconnect { peer_id })
+        self.send(NetworkCommand::Disconnect { peer_id }, "disconnect")
             .await
-            .map_err(|_| NetworkError::Channel("Failed to send disconnect command".into()))
     }
 
     /// Subscribe to a gossipsub topic
     pub async fn subscribe(&self, topic: impl Into<String>) -> Result<()> {
-        self.command_tx
-            .send(NetworkCommand::Subscribe {
+        self.send(
+            NetworkCommand::Subscribe {
                 topic: topic.into(),
-            })
-            .await
-            .map_err(|_| NetworkError::Channel("Failed to send subscribe command".into()))
+            },
+            "subscribe",
+        )
+        .await
     }
 
     /// Unsubscribe from a gossipsub topic
     pub async fn unsubscribe(&self, topic: impl Into<String>) -> Result<()> {
-        self.command_tx
-            .send(NetworkCommand::Unsubscribe {
+        self.send(
+            NetworkCommand::Unsubscribe {
                 topic: topic.into(),
-            })
-            .await
-            .map_err(|_| NetworkError::Channel("Failed to send unsubscribe command".into()))
+            },
+            "unsubscribe",
+        )
+        .await
     }
 
     /// Publish a message to a gossipsub topic
+    ///
+    /// Fails with [`NetworkError::RateLimited`] if the topic's publish rate
+    /// limit is used up.
     pub async fn publish(&self, topic: impl Into<String>, data: Vec<u8>) -> Result<()> {
-        self.command_tx
-            .send(NetworkCommand::Publish {
-                topic: topic.into(),
-                data,
+        let topic = topic.into();
+        self.take_publish_token(&topic)?;
+        let result = self
+            .send(
+                NetworkCommand::Publish {
+                    topic: topic.clone(),
+                    data,
+                },
+                "publish",
+            )
+            .await;
+        self.settle_publish_token(&topic, result.is_ok());
+        result
+    }
+
+    /// Publish a message, retrying until the topic has peers or `deadline`
+    /// passes
+    ///
+    /// Messages published before the mesh forms, such as right after
+    /// startup, are held in an outbox instead of being dropped. Returns once
+    /// the service has the message; each retry is reported as
+    /// [`NetworkEvent::PublishRetrying`], and a message still unsent at the
+    /// deadline as [`NetworkEvent::PublishFailed`].
+    ///
+    /// Deadlines are capped at a day, and at most 1024 publishes wait to be
+    /// retried at once; publishes beyond that fail straight away.
+    ///
+    /// Rate limited like [`publish`](Self::publish); retries don't count
+    /// against the limit again.
+    pub async fn publish_reliable(
+        &self,
+        topic: impl Into<String>,
+        data: Vec<u8>,
+        deadline: Duration,
+    ) -> Result<()> {
+        let topic = topic.into();
+        self.take_publish_token(&topic)?;
+        let result = self
+            .send(
+                NetworkCommand::PublishReliable {
+                    topic: topic.clone(),
+                    data,
+                    deadline,
+                },
+                "publish_reliable",
+            )
+            .await;
+        self.settle_publish_token(&topic, result.is_ok());
+        result
+    }
+
+    /// Take a token from `topic`'s publish rate limit
+    fn take_publish_token(&self, topic: &str) -> Result<()> {
+        self.publish_limiter
+            .lock()
+            .check(topic, Instant::now())
+            .map_err(|retry_after| NetworkError::RateLimited {
+                topic: topic.to_string(),
+                retry_after_ms: (retry_after.as_secs_f64() * 1000.0).ceil() as u64,
             })
-            .await
-            .map_err(|_| NetworkError::Channel("Failed to send publish command".into()))
     }
 
-    /// Store a value in the DHT
+    /// Count a publish to `topic` once `sent` to the service, or give back
+    /// its token if it never got there
+    fn settle_publish_token(&self, topic: &str, sent: bool) {
+        self.publish_limiter.lock().settle(topic, sent);
+    }
+
+    /// Store a value in the DHT, waiting until the Kademlia query completes
     pub async fn put_record(&self, key: Vec<u8>, value: Vec<u8>) -> Result<()> {
-        self.command_tx
-            .send(NetworkCommand::PutRecord { key, value })
-            .await
-            .map_err(|_| NetworkError::Channel("Failed to send put_record command".into()))
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.send(
+            NetworkCommand::PutRecord {
+                key,
+                value,
+                response: Some(tx),
+            },
+            "put_record",
+        )
+        .await?;
+
+        self.response(rx, "put_record result").await?
+    }
+
+    /// Store a value in the DHT without waiting
+    ///
+    /// The outcome arrives as a [`NetworkEvent::RecordStored`] event.
+    pub async fn put_record_detached(&self, key: Vec<u8>, value: Vec<u8>) -> Result<()> {
+        self.send(
+            NetworkCommand::PutRecord {
+                key,
+                value,
+                response: None,
+            },
+            "put_record",
+        )
+        .await
     }
 
     /// Get a value from the DHT
-    pub async fn get_record(&self, key: Vec<u8>) -> Result<()> {
-        self.command_tx
-            .send(NetworkCommand::GetRecord { key })
+    ///
+    /// Resolves to the first record found, or `None` if no peer has one.
+    pub async fn get_record(&self, key: Vec<u8>) -> Result<Option<Vec<u8>>> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.send(
+            NetworkCommand::GetRecord {
+                key,
+                response: Some(tx),
+            },
+            "get_record",
+        )
+        .await?;
+
+        self.response(rx, "get_record result").await?
+    }
+
+    /// Look up a value in the DHT without waiting
+    ///
+    /// Records arrive as [`NetworkEvent::RecordFound`] events.
+    pub async fn get_record_detached(&self, key: Vec<u8>) -> Result<()> {
+        self.send(
+            NetworkCommand::GetRecord {
+                key,
+                response: None,
+            },
+            "get_record",
+        )
+        .await
+    }
+
+    /// Serve a piece of content to other peers
+    ///
+    /// The content is kept in the service's content store and we are
+    /// announced as a provider in the DHT, so
+    /// [`fetch_content`](Self::fetch_content) on other nodes can find it.
+    pub async fn provide_content(&self, content: Content) -> Result<()> {
+        self.send(
+            NetworkCommand::ProvideContent { content },
+            "provide_content",
+        )
+        .await
+    }
+
+    /// Stop serving a piece of content, e.g. after evicting it from storage
+    ///
+    /// Our provider record is withdrawn from the DHT, so other nodes stop
+    /// being pointed at us for it.
+    pub async fn stop_providing(&self, id: ContentId) -> Result<()> {
+        self.send(NetworkCommand::StopProviding { id }, "stop_providing")
             .await
-            .map_err(|_| NetworkError::Channel("Failed to send get_record command".into()))
+    }
+
+    /// Keep a piece of content from being evicted by the content store's GC
+    ///
+    /// Returns whether the content is stored.
+    pub async fn pin_content(&self, id: ContentId) -> Result<bool> {
+        self.set_pinned(id, true, "pin_content").await
+    }
+
+    /// Let the content store's GC evict a piece of content again
+    ///
+    /// Returns whether the content is stored.
+    pub async fn unpin_content(&self, id: ContentId) -> Result<bool> {
+        self.set_pinned(id, false, "unpin_content").await
+    }
+
+    async fn set_pinned(&self, id: ContentId, pinned: bool, name: &str) -> Result<bool> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.send(
+            NetworkCommand::PinContent {
+                id,
+                pinned,
+                response: tx,
+            },
+            name,
+        )
+        .await?;
+        self.response(rx, "pin result").await?
+    }
+
+    /// Find the providers of a piece of content, best-scored first
+    pub async fn find_providers(&self, id: ContentId) -> Result<Vec<PeerId>> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.send(
+            NetworkCommand::FindProviders { id, response: tx },
+            "find_providers",
+        )
+        .await?;
+
+        self.response(rx, "providers").await?
+    }
+
+    /// Whether anyone, ourselves included, provides a piece of content
+    ///
+    /// Answers as soon as one provider is known, and gives up after
+    /// [`PROVIDER_PROBE_TIMEOUT`]; much cheaper than fetching the content.
+    pub async fn has_providers(&self, id: ContentId) -> Result<bool> {
+        let providers = self.probe_providers(id, Some(1)).await?;
+        Ok(!providers.is_empty())
+    }
+
+    /// Count the providers of a piece of content, ourselves included
+    ///
+    /// Counts those found within [`PROVIDER_PROBE_TIMEOUT`], so a node can
+    /// re-announce or replicate content whose count falls below a target.
+    pub async fn provider_count(&self, id: ContentId) -> Result<usize> {
+        Ok(self.probe_providers(id, None).await?.len())
+    }
+
+    /// Look for providers until `enough` are found or the probe times out
+    async fn probe_providers(&self, id: ContentId, enough: Option<usize>) -> Result<Vec<PeerId>> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.send(
+            NetworkCommand::ProbeProviders {
+                id,
+                timeout: PROVIDER_PROBE_TIMEOUT,
+                enough,
+                response: tx,
+            },
+            "probe_providers",
+        )
+        .await?;
+
+        self.response(rx, "providers").await?
+    }
+
+    /// Fetch a piece of content from whichever provider can supply it
+    ///
+    /// Providers are found through the DHT and tried best-scored first. A
+    /// provider that fails, or whose content doesn't hash to `id`, is skipped
+    /// for the next one. Content larger than one chunk reports
+    /// [`NetworkEvent::ContentFetchProgress`] events as it arrives.
+    pub async fn fetch_content(&self, id: ContentId) -> Result<Content> {
+        let providers = self.find_providers(id).await?;
+        debug!("Found {} providers for {}", providers.len(), id);
+
+        for peer_id in providers {
+            match self.fetch_content_from(peer_id, id).await {
+                Ok(content) => return Ok(content),
+                Err(e) => debug!("Fetching {} from {} failed: {}", id, peer_id, e),
+            }
+        }
+
+        Err(NetworkError::ContentNotFound(id.to_string()))
+    }
+
+    /// Fetch every chunk of a piece of content from one provider and verify it
+    async fn fetch_content_from(&self, peer_id: PeerId, id: ContentId) -> Result<Content> {
+        let (total, content_type, metadata, mut data) = match self
+            .request_content(peer_id, ContentRequest { id, chunk: 0 })
+            .await?
+        {
+            ContentResponse::Chunk {
+                index: 0,
+                total,
+                content_type,
+                metadata,
+                data,
+            } if total <= MAX_CONTENT_CHUNKS => (total, content_type, metadata, data),
+            ContentResponse::Chunk { .. } => {
+                return Err(NetworkError::Serialization(format!(
+                    "Malformed first chunk of {} from {}",
+                    id, peer_id
+                )))
+            }
+            ContentResponse::NotFound => {
+                return Err(NetworkError::ContentNotFound(id.to_string()));
+            }
+        };
+
+        for chunk in 1..total {
+            match self
+                .request_content(peer_id, ContentRequest { id, chunk })
+                .await?
+            {
+                ContentResponse::Chunk {
+                    index,
+                    total: chunk_total,
+                    data: chunk_data,
+                    ..
+                } if index == chunk && chunk_total == total => data.extend(chunk_data),
+                ContentResponse::Chunk { .. } => {
+                    return Err(NetworkError::Serialization(format!(
+                        "Unexpected chunk of {} from {} (wanted {} of {})",
+                        id, peer_id, chunk, total
+                    )))
+                }
+                ContentResponse::NotFound => {
+                    return Err(NetworkError::ContentNotFound(id.to_string()));
+                }
+            }
+        }
+
+        if !id.verify(&data) {
+            return Err(NetworkError::ContentMismatch(format!(
+                "{} from {}",
+                id, peer_id
+            )));
+        }
+
+        Ok(Content {
+            id,
+            data,
+            content_type,
+            metadata,
+        })
+    }
+
+    /// Request one chunk of a piece of content from a provider
+    async fn request_content(
+        &self,
+        peer_id: PeerId,
+        request: ContentRequest,
+    ) -> Result<ContentResponse> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.send(
+            NetworkCommand::RequestContent {
+                peer_id,
+                request,
+                response: tx,
+            },
+            "request_content",
+        )
+        .await?;
+
+        self.response(rx, "content").await?
+    }
+
+    /// Get a peer's reputation, or `None` if we know nothing about it
+    ///
+    /// Uses the same source as the service's own filtering: the lookup set
+    /// with [`NetworkService::set_record_reputation`], falling back to the
+    /// peer manager's score.
+    pub async fn reputation_of(&self, peer_id: PeerId) -> Result<Option<Reputation>> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.send(
+            NetworkCommand::GetReputation {
+                peer_id,
+                response: tx,
+            },
+            "reputation_of",
+        )
+        .await?;
+
+        self.response(rx, "reputation").await
     }
 
     /// Get list of connected peers
     pub async fn get_peers(&self) -> Result<Vec<PeerId>> {
         let (tx, rx) = tokio::sync::oneshot::channel();
-        self.command_tx
-            .send(NetworkCommand::GetPeers { response: tx })
-            .await
-            .map_err(|_| NetworkError::Channel("Failed to send get_peers command".into()))?;
+        self.send(NetworkCommand::GetPeers { response: tx }, "get_peers")
+            .await?;
 
-        rx.await
-            .map_err(|_| NetworkError::Channel("Failed to receive peers".into()))
+        self.response(rx, "peers").await
     }
 
     /// Get network statistics
     pub async fn get_stats(&self) -> Result<NetworkStats> {
         let (tx, rx) = tokio::sync::oneshot::channel();
-        self.command_tx
-            .send(NetworkCommand::GetStats { response: tx })
-            .await
-            .map_err(|_| NetworkError::Channel("Failed to send get_stats command".into()))?;
+        self.send(NetworkCommand::GetStats { response: tx }, "get_stats")
+            .await?;
+
+        self.response(rx, "stats").await
+    }
+
+    /// Get the topics we are subscribed to, sorted
+    pub async fn subscribed_topics(&self) -> Result<Vec<String>> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.send(
+            NetworkCommand::GetSubscribedTopics { response: tx },
+            "subscribed_topics",
+        )
+        .await?;
+
+        self.response(rx, "subscribed topics").await
+    }
+
+    /// Get the connection quality (0.0 - 1.0) of every known peer
+    pub async fn peer_qualities(&self) -> Result<HashMap<PeerId, f64>> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.send(
+            NetworkCommand::GetPeerQualities { response: tx },
+            "peer_qualities",
+        )
+        .await?;
+
+        self.response(rx, "peer qualities").await
+    }
+
+    /// Get whether AutoNAT finds us publicly reachable
+    pub async fn nat_status(&self) -> Result<NatStatus> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.send(NetworkCommand::GetNatStatus { response: tx }, "nat_status")
+            .await?;
+
+        self.response(rx, "NAT status").await
+    }
+
+    /// Get how many peers are in our gossipsub mesh for `topic`
+    pub async fn mesh_status(&self, topic: impl Into<String>) -> Result<MeshStatus> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.send(
+            NetworkCommand::GetMeshStatus {
+                topic: topic.into(),
+                response: tx,
+            },
+            "mesh_status",
+        )
+        .await?;
 
-        rx.await
-            .map_err(|_| NetworkError::Channel("Failed to receive stats".into()))
+        self.response(rx, "mesh status").await
     }
 
     /// Shutdown the network service
     pub async fn shutdown(&self) -> Result<()> {
-        self.command_tx
-            .send(NetworkCommand::Shutdown)
+        self.send(NetworkCommand::Shutdown, "shutdown").await
+    }
+
+    /// Gracefully shutdown the network service
+    ///
+    /// Unlike [`shutdown`](Self::shutdown), this waits (up to `timeout`) for the
+    /// service to disconnect its peers and leave the event loop.
+    pub async fn shutdown_graceful(&self, timeout: std::time::Duration) -> Result<()> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.send(
+            NetworkCommand::ShutdownGraceful { response: tx },
+            "shutdown",
+        )
+        .await?;
+
+        tokio::time::timeout(timeout, rx)
             .await
-            .map_err(|_| NetworkError::Channel("Failed to send shutdown command".into()))
+            .map_err(|_| NetworkError::Timeout {
+                duration_ms: timeout.as_millis() as u64,
+            })?
+            .map_err(|_| self.channel_error("Failed to receive shutdown ack"))
+    }
+
+    /// Ban a peer for `duration`, or until unbanned if `None`
+    ///
+    /// The peer is disconnected, and refused until the ban lifts.
+    pub async fn ban_peer(
+        &self,
+        peer_id: PeerId,
+        duration: Option<Duration>,
+        reason: impl Into<String>,
+    ) -> Result<()> {
+        self.send(
+            NetworkCommand::BanPeer {
+                peer_id,
+                duration,
+                reason: reason.into(),
+            },
+            "ban_peer",
+        )
+        .await
+    }
+
+    /// Lift a peer's ban, returning whether it was banned
+    pub async fn unban_peer(&self, peer_id: PeerId) -> Result<bool> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.send(
+            NetworkCommand::UnbanPeer {
+                peer_id,
+                response: Some(tx),
+            },
+            "unban_peer",
+        )
+        .await?;
+
+        self.response(rx, "unban result").await
+    }
+
+    /// Get the bans in force, most recent first
+    pub async fn banned_peers(&self) -> Result<Vec<PeerBan>> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.send(
+            NetworkCommand::GetBannedPeers { response: tx },
+            "banned_peers",
+        )
+        .await?;
+
+        self.response(rx, "banned peers").await
+    }
+
+    /// Always exchange messages with a peer, whatever its score
+    ///
+    /// Gossipsub sends an explicit peer every message on the topics it
+    /// shares with us and never prunes it. It is dialed at `address` now
+    /// and whenever it disconnects, until removed.
+    pub async fn add_explicit_peer(&self, peer_id: PeerId, address: Multiaddr) -> Result<()> {
+        self.send(
+            NetworkCommand::AddExplicitPeer { peer_id, address },
+            "add_explicit_peer",
+        )
+        .await
+    }
+
+    /// Stop treating a peer as explicit, leaving any connection open
+    pub async fn remove_explicit_peer(&self, peer_id: PeerId) -> Result<()> {
+        self.send(
+            NetworkCommand::RemoveExplicitPeer { peer_id },
+            "remove_explicit_peer",
+        )
+        .await
     }
 
     /// Block a peer - prevents receiving messages from this peer (partition testing)
     pub async fn block_peer(&self, peer_id: PeerId) -> Result<()> {
-        self.command_tx
-            .send(NetworkCommand::BlockPeer { peer_id })
+        self.send(NetworkCommand::BlockPeer { peer_id }, "block_peer")
             .await
-            .map_err(|_| NetworkError::Channel("Failed to send block_peer command".into()))
     }
 
     /// Unblock a specific peer (partition testing)
     pub async fn unblock_peer(&self, peer_id: PeerId) -> Result<()> {
-        self.command_tx
-            .send(NetworkCommand::UnblockPeer { peer_id })
+        self.send(NetworkCommand::UnblockPeer { peer_id }, "unblock_peer")
             .await
-            .map_err(|_| NetworkError::Channel("Failed to send unblock_peer command".into()))
     }
 
     /// Unblock all peers - restores normal message flow (partition testing)
     pub async fn unblock_all_peers(&self) -> Result<()> {
+        self.send(NetworkCommand::UnblockAllPeers, "unblock_all_peers")
+            .await
+    }
+
+    /// Send a command, failing fast if the service has stopped
+    async fn send(&self, command: NetworkCommand, name: &str) -> Result<()> {
+        if !self.is_alive() {
+            return Err(NetworkError::ServiceUnavailable);
+        }
         self.command_tx
-            .send(NetworkCommand::UnblockAllPeers)
+            .send(command)
             .await
-            .map_err(|_| NetworkError::Channel("Failed to send unblock_all_peers command".into()))
+            .map_err(|_| self.channel_error(&format!("Failed to send {} command", name)))
+    }
+
+    /// Wait for the service to answer a command
+    ///
+    /// Gives up with [`NetworkError::ServiceUnavailable`] if the service stops
+    /// while we wait, rather than hanging on a response that will never come.
+    async fn response<T>(
+        &self,
+        mut rx: tokio::sync::oneshot::Receiver<T>,
+        what: &str,
+    ) -> Result<T> {
+        loop {
+            match tokio::time::timeout(LIVENESS_CHECK_INTERVAL, &mut rx).await {
+                Ok(result) => {
+                    return result
+                        .map_err(|_| self.channel_error(&format!("Failed to receive {}", what)))
+                }
+                Err(_) if !self.is_alive() => return Err(NetworkError::ServiceUnavailable),
+                Err(_) => {}
+            }
+        }
+    }
+
+    /// Channel failure, reported as the service being gone if it has stopped
+    fn channel_error(&self, message: &str) -> NetworkError {
+        if self.is_alive() {
+            NetworkError::Channel(message.to_string())
+        } else {
+            NetworkError::ServiceUnavailable
+        }
+    }
+}
+
+/// The network is down once the service stops, and degraded while it is
+/// slow to answer or has no peers to talk to
+#[async_trait]
+impl HealthCheck for NetworkHandle {
+    async fn health(&self) -> HealthStatus {
+        if !self.is_alive() {
+            return HealthStatus::down("network service stopped");
+        }
+        match tokio::time::timeout(HEALTH_CHECK_TIMEOUT, self.get_stats()).await {
+            Ok(Ok(stats)) if stats.connected_peers == 0 => {
+                HealthStatus::degraded("no connected peers")
+            }
+            Ok(Ok(_)) => HealthStatus::Ok,
+            Ok(Err(NetworkError::ServiceUnavailable)) => {
+                HealthStatus::down("network service stopped")
+            }
+            Ok(Err(e)) => HealthStatus::degraded(format!("network service error: {}", e)),
+            Err(_) => HealthStatus::degraded("network service not responding"),
+        }
+    }
+}
+
+/// Ban peers while the gate this node keeps for them is closed
+///
+/// `changes` must carry only this node's own gate decisions: a peer
+/// gossiping that some other node's gate closed must not get it banned
+/// here. Bans last at most `duration`, so one outlives a missed reopening
+/// (e.g. across a restart) only that long. Only bans placed for a closed
+/// gate are lifted when it reopens, so an operator's ban outlasts the gate.
+/// Runs until the service goes away.
+#[cfg(feature = "univrs-compat")]
+async fn ban_on_closed_gates(
+    mut changes: broadcast::Receiver<SeptalStateMsg>,
+    peer_manager: Arc<PeerManager>,
+    command_tx: mpsc::Sender<NetworkCommand>,
+    local_peer_id: PeerId,
+    duration: Duration,
+) {
+    use crate::peer::SEPTAL_BAN_REASON;
+    use univrs_enr::septal::SeptalGateState;
+
+    loop {
+        let change = match changes.recv().await {
+            Ok(change) => change,
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                warn!("Missed {} septal gate changes", skipped);
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+        let Some(peer_id) = peer_id_from_node_id(&change.node) else {
+            continue;
+        };
+        if peer_id == local_peer_id {
+            continue;
+        }
+
+        let command = if change.to_state == SeptalGateState::Closed {
+            NetworkCommand::BanPeer {
+                peer_id,
+                duration: Some(duration),
+                reason: SEPTAL_BAN_REASON.to_string(),
+            }
+        } else if peer_manager
+            .ban_of(&peer_id)
+            .is_some_and(|ban| ban.reason == SEPTAL_BAN_REASON)
+        {
+            NetworkCommand::UnbanPeer {
+                peer_id,
+                response: None,
+            }
+        } else {
+            continue;
+        };
+        if command_tx.send(command).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// An interval whose first tick is one period away rather than immediate
+///
+/// `None` gives an interval that is never polled (its `select!` branch is
+/// disabled), so the period is irrelevant.
+fn delayed_interval(period: Option<Duration>) -> tokio::time::Interval {
+    let period = period.unwrap_or(Duration::from_secs(3600));
+    tokio::time::interval_at(tokio::time::Instant::now() + period, period)
+}
+
+/// Addresses to dial a peer on: its best known address, then the configured one
+fn dial_addresses(best: Option<Multiaddr>, configured: &Multiaddr) -> Vec<Multiaddr> {
+    match best {
+        Some(best) if best != *configured => vec![best, configured.clone()],
+        _ => vec![configured.clone()],
+    }
+}
+
+/// Redial state for a bootstrap peer that has not connected yet
+struct BootstrapDial {
+    /// Bootstrap address from the config
+    address: Multiaddr,
+    /// Number of dial attempts made so far
+    attempts: u32,
+    /// Earliest time the next attempt may start
+    next_attempt: Instant,
+    /// Connection ID of the dial currently in progress
+    in_flight: Option<ConnectionId>,
+}
+
+/// Redial state for a peer gossipsub treats as explicit
+struct ExplicitPeer {
+    /// Address to dial it at
+    address: Multiaddr,
+    /// Dial attempts since it was last connected
+    attempts: u32,
+    /// When to dial it next, while it is disconnected
+    next_attempt: Option<Instant>,
+}
+
+/// A reliable publish waiting in the outbox
+struct PendingPublish {
+    topic: String,
+    data: Vec<u8>,
+    /// Publish attempts so far
+    attempts: u32,
+    /// When to try again
+    next_attempt: Instant,
+    /// When to give up
+    deadline: Instant,
+}
+
+/// Delay before the next try of a reliable publish after `attempts` tries
+///
+/// Doubles with each attempt, capped at [`PUBLISH_RETRY_MAX`].
+fn publish_retry_backoff(attempts: u32) -> Duration {
+    let factor = 1u32 << attempts.saturating_sub(1).min(16);
+    PUBLISH_RETRY_INITIAL
+        .saturating_mul(factor)
+        .min(PUBLISH_RETRY_MAX)
+}
+
+/// A provider lookup still collecting results
+struct PendingProviders {
+    /// Providers found so far
+    providers: HashSet<PeerId>,
+    /// Caller waiting for the full list
+    response: tokio::sync::oneshot::Sender<Result<Vec<PeerId>>>,
+    /// Whether we count among the providers
+    include_local: bool,
+    /// When to stop looking and answer with what was found
+    deadline: Option<Instant>,
+    /// Number of providers after which to stop looking
+    enough: Option<usize>,
+}
+
+impl PendingProviders {
+    /// A lookup that runs until Kademlia finishes it
+    fn new(response: tokio::sync::oneshot::Sender<Result<Vec<PeerId>>>) -> Self {
+        Self {
+            providers: HashSet::new(),
+            response,
+            include_local: false,
+            deadline: None,
+            enough: None,
+        }
+    }
+
+    /// Whether enough providers have been found to answer
+    fn has_enough(&self) -> bool {
+        self.enough
+            .is_some_and(|enough| self.providers.len() >= enough)
     }
 }
 
+/// A content chunk read from the store, waiting to be sent to the peer
+/// that asked for it
+struct ServedChunk {
+    peer: PeerId,
+    channel: request_response::ResponseChannel<ContentResponse>,
+    response: ContentResponse,
+}
+
 /// The network service manages all P2P networking
 pub struct NetworkService {
     /// The libp2p swarm
@@ -219,6 +1070,63 @@ pub struct NetworkService {
     enr_bridge: Arc<EnrBridge>,
     /// Blocked peers for partition testing
     blocked_peers: HashSet<PeerId>,
+    /// Bootstrap peers still being retried (cleared once one connects)
+    bootstrap_dials: Vec<BootstrapDial>,
+    /// Peers kept connected and sent every message, whatever their score
+    explicit_peers: HashMap<PeerId, ExplicitPeer>,
+    /// Reliable publishes waiting for peers to send them to
+    outbox: Vec<PendingPublish>,
+    /// Checks every inbound gossip message must pass
+    validators: ValidationPipeline,
+    /// Application checks, run on whole payloads
+    app_validators: ValidationPipeline,
+    /// The handles' publish rate limits, for reporting usage in stats
+    publish_limiter: Arc<Mutex<PublishLimiter>>,
+    /// Last reachability reported by AutoNAT
+    nat_status: NatStatus,
+    /// Last reported mesh state per subscribed topic
+    mesh_status: HashMap<String, MeshStatus>,
+    /// Reputation lookup for inbound DHT puts and filtered topics, overriding
+    /// the peer manager
+    record_reputation: Option<RecordReputationFn>,
+    /// Callers waiting on a DHT put, by Kademlia query
+    pending_puts: HashMap<kad::QueryId, tokio::sync::oneshot::Sender<Result<()>>>,
+    /// Callers waiting on a DHT get, by Kademlia query
+    pending_gets: HashMap<kad::QueryId, tokio::sync::oneshot::Sender<Result<Option<Vec<u8>>>>>,
+    /// Relay circuit listeners held while we are behind a NAT
+    relay_listeners: Vec<ListenerId>,
+    /// Where the content we serve is kept
+    content_store: Arc<dyn ContentStore>,
+    /// Content we are announced as a provider of
+    provided_content: HashSet<ContentId>,
+    /// Chunks read from the content store, to answer their requests with
+    served_tx: mpsc::Sender<ServedChunk>,
+    served_rx: mpsc::Receiver<ServedChunk>,
+    /// Callers waiting on a provider lookup, with the providers found so far
+    pending_providers: HashMap<kad::QueryId, PendingProviders>,
+    /// Callers waiting on a content chunk, by request
+    pending_content: HashMap<
+        request_response::OutboundRequestId,
+        (
+            ContentId,
+            tokio::sync::oneshot::Sender<Result<ContentResponse>>,
+        ),
+    >,
+    /// Addresses of our outbound connections, for per-address ping RTTs
+    connection_addrs: HashMap<ConnectionId, Multiaddr>,
+    /// Consecutive failed pings per connected peer
+    ping_failures: HashMap<PeerId, u32>,
+    /// Fragments of oversized messages still being reassembled
+    fragments: FragmentBuffer,
+    /// Built-in topics that are never unsubscribed for being idle
+    protected_topics: HashSet<String>,
+    /// When each subscribed topic last saw a message or publish
+    topic_activity: HashMap<String, Instant>,
+    /// Topics unsubscribed for being idle, rejoined on publish
+    idle_topics: HashSet<String>,
+    /// Cleared on drop so handles stop waiting on a dead service
+    #[allow(dead_code)]
+    liveness: Liveness,
 }
 
 impl NetworkService {
@@ -260,6 +1168,8 @@ impl NetworkService {
         broadcast::Receiver<NetworkEvent>,
         Arc<EnrBridge>,
     )> {
+        config.validate()?;
+
         let local_peer_id = keypair.public().to_peer_id();
         info!("Local peer ID: {}", local_peer_id);
 
@@ -267,54 +1177,90 @@ impl NetworkService {
         let transport_config = TransportConfig {
             enable_tcp: config.enable_tcp,
             enable_quic: config.enable_quic,
+            enable_webrtc: config.enable_webrtc,
+            handshake_timeout: config.handshake_timeout(),
+            idle_connection_timeout: config.idle_timeout(),
             ..Default::default()
         };
-        let transport = transport::create_transport(&keypair, &transport_config)?;
+        let (relay_transport, relay_client) = relay::client::new(local_peer_id);
+        let transport =
+            transport::create_transport_with_relay(&keypair, &transport_config, relay_transport)?;
 
         // Create behaviour
-        let behaviour = MycelialBehaviour::new(&keypair, &config)?;
+        let behaviour = MycelialBehaviour::new(&keypair, &config, relay_client)?;
 
         // Create swarm
         let swarm = Swarm::new(
             transport,
             behaviour,
             local_peer_id,
-            libp2p::swarm::Config::with_tokio_executor(),
+            libp2p::swarm::Config::with_tokio_executor()
+                .with_idle_connection_timeout(transport_config.idle_connection_timeout),
         );
 
         // Create channels
         let (event_tx, event_rx) = broadcast::channel(1024);
         let (command_tx, command_rx) = mpsc::channel(256);
+        let (served_tx, served_rx) = mpsc::channel(256);
 
+        let liveness = Liveness::new();
         let handle = NetworkHandle {
             command_tx: command_tx.clone(),
             local_peer_id,
+            alive: liveness.flag(),
+            publish_limiter: Arc::new(Mutex::new(PublishLimiter::new(&config))),
         };
 
         // Create ENR bridge with publish callback (requires univrs-compat feature)
         #[cfg(feature = "univrs-compat")]
         let enr_bridge = {
-            // Convert PeerId to NodeId (use peer_id bytes, padded/truncated to 32)
-            let peer_id_bytes = local_peer_id.to_bytes();
-            let mut node_id_bytes = [0u8; 32];
-            let len = peer_id_bytes.len().min(32);
-            node_id_bytes[..len].copy_from_slice(&peer_id_bytes[..len]);
-            let local_node_id = NodeId::from_bytes(node_id_bytes);
-
-            // Create publish callback that uses the command channel
-            let publish_tx = command_tx.clone();
+            // ENR messages are signed with the node's identity key, whose
+            // public half doubles as the NodeId
+            let signing_key = node_key_from_keypair(&keypair).ok_or_else(|| {
+                NetworkError::Config("ENR bridge requires an Ed25519 identity key".into())
+            })?;
+
+            // Create publish callback that uses the command channel, under
+            // the same rate limits as the handle's publishes
+            let publish_handle = handle.clone();
             let publish_fn = move |topic: String, data: Vec<u8>| {
+                publish_handle
+                    .take_publish_token(&topic)
+                    .map_err(|e| e.to_string())?;
                 // Use try_send which is non-blocking and works in any context
                 // This may fail if the channel is full, but that's acceptable
                 // for gossip messages which can be retried
-                publish_tx
-                    .try_send(NetworkCommand::Publish { topic, data })
-                    .map_err(|e| e.to_string())
+                let result = publish_handle
+                    .command_tx
+                    .try_send(NetworkCommand::PublishReliable {
+                        topic: topic.clone(),
+                        data,
+                        deadline: ENR_PUBLISH_DEADLINE,
+                    })
+                    .map_err(|e| e.to_string());
+                publish_handle.settle_publish_token(&topic, result.is_ok());
+                result
             };
 
-            Arc::new(EnrBridge::new(local_node_id, publish_fn))
+            let mut bridge = EnrBridge::new(signing_key, publish_fn);
+            if let Some(path) = &config.credit_nonce_path {
+                bridge = bridge.with_nonce_store(path).map_err(|e| {
+                    NetworkError::Config(format!(
+                        "Cannot open credit nonce store {}: {}",
+                        path.display(),
+                        e
+                    ))
+                })?;
+            }
+            Arc::new(bridge)
         };
 
+        let fragments = FragmentBuffer::new(
+            config.max_fragmented_message_size,
+            config.fragment_buffer_size,
+            config.fragment_timeout(),
+        );
+        let validators = ValidationPipeline::from_config(&config);
         let service = Self {
             swarm,
             config,
@@ -329,6 +1275,31 @@ impl NetworkService {
             #[cfg(feature = "univrs-compat")]
             enr_bridge,
             blocked_peers: HashSet::new(),
+            bootstrap_dials: Vec::new(),
+            explicit_peers: HashMap::new(),
+            outbox: Vec::new(),
+            validators,
+            app_validators: ValidationPipeline::new(),
+            publish_limiter: handle.publish_limiter.clone(),
+            nat_status: NatStatus::Unknown,
+            mesh_status: HashMap::new(),
+            record_reputation: None,
+            pending_puts: HashMap::new(),
+            pending_gets: HashMap::new(),
+            relay_listeners: Vec::new(),
+            content_store: Arc::new(MemoryContentStore::default()),
+            provided_content: HashSet::new(),
+            served_tx,
+            served_rx,
+            pending_providers: HashMap::new(),
+            pending_content: HashMap::new(),
+            connection_addrs: HashMap::new(),
+            ping_failures: HashMap::new(),
+            fragments,
+            protected_topics: HashSet::new(),
+            topic_activity: HashMap::new(),
+            idle_topics: HashSet::new(),
+            liveness,
         };
 
         #[cfg(feature = "univrs-compat")]
@@ -346,6 +1317,8 @@ impl NetworkService {
         keypair: libp2p::identity::Keypair,
         config: NetworkConfig,
     ) -> Result<(Self, NetworkHandle, broadcast::Receiver<NetworkEvent>)> {
+        config.validate()?;
+
         let local_peer_id = keypair.public().to_peer_id();
         info!("Local peer ID: {}", local_peer_id);
 
@@ -353,30 +1326,46 @@ impl NetworkService {
         let transport_config = TransportConfig {
             enable_tcp: config.enable_tcp,
             enable_quic: config.enable_quic,
+            enable_webrtc: config.enable_webrtc,
+            handshake_timeout: config.handshake_timeout(),
+            idle_connection_timeout: config.idle_timeout(),
             ..Default::default()
         };
-        let transport = transport::create_transport(&keypair, &transport_config)?;
+        let (relay_transport, relay_client) = relay::client::new(local_peer_id);
+        let transport =
+            transport::create_transport_with_relay(&keypair, &transport_config, relay_transport)?;
 
         // Create behaviour
-        let behaviour = MycelialBehaviour::new(&keypair, &config)?;
+        let behaviour = MycelialBehaviour::new(&keypair, &config, relay_client)?;
 
         // Create swarm
         let swarm = Swarm::new(
             transport,
             behaviour,
             local_peer_id,
-            libp2p::swarm::Config::with_tokio_executor(),
+            libp2p::swarm::Config::with_tokio_executor()
+                .with_idle_connection_timeout(transport_config.idle_connection_timeout),
         );
 
         // Create channels
         let (event_tx, event_rx) = broadcast::channel(1024);
         let (command_tx, command_rx) = mpsc::channel(256);
+        let (served_tx, served_rx) = mpsc::channel(256);
 
+        let liveness = Liveness::new();
         let handle = NetworkHandle {
             command_tx: command_tx.clone(),
             local_peer_id,
+            alive: liveness.flag(),
+            publish_limiter: Arc::new(Mutex::new(PublishLimiter::new(&config))),
         };
 
+        let fragments = FragmentBuffer::new(
+            config.max_fragmented_message_size,
+            config.fragment_buffer_size,
+            config.fragment_timeout(),
+        );
+        let validators = ValidationPipeline::from_config(&config);
         let service = Self {
             swarm,
             config,
@@ -389,6 +1378,31 @@ impl NetworkService {
             start_time: Instant::now(),
             running: false,
             blocked_peers: HashSet::new(),
+            bootstrap_dials: Vec::new(),
+            explicit_peers: HashMap::new(),
+            outbox: Vec::new(),
+            validators,
+            app_validators: ValidationPipeline::new(),
+            publish_limiter: handle.publish_limiter.clone(),
+            nat_status: NatStatus::Unknown,
+            mesh_status: HashMap::new(),
+            record_reputation: None,
+            pending_puts: HashMap::new(),
+            pending_gets: HashMap::new(),
+            relay_listeners: Vec::new(),
+            content_store: Arc::new(MemoryContentStore::default()),
+            provided_content: HashSet::new(),
+            served_tx,
+            served_rx,
+            pending_providers: HashMap::new(),
+            pending_content: HashMap::new(),
+            connection_addrs: HashMap::new(),
+            ping_failures: HashMap::new(),
+            fragments,
+            protected_topics: HashSet::new(),
+            topic_activity: HashMap::new(),
+            idle_topics: HashSet::new(),
+            liveness,
         };
 
         Ok((service, handle, event_rx))
@@ -405,15 +1419,217 @@ impl NetworkService {
         &self.enr_bridge
     }
 
+    /// Use `reputation` to vet peers that put DHT records or publish on
+    /// reputation-filtered topics
+    ///
+    /// Peers it returns `None` for fall back to the peer manager's score.
+    pub fn set_record_reputation<F>(&mut self, reputation: F)
+    where
+        F: Fn(&PeerId) -> Option<f64> + Send + Sync + 'static,
+    {
+        self.record_reputation = Some(Box::new(reputation));
+    }
+
+    /// Keep the content we serve in `store` instead of in memory
+    ///
+    /// Content provided before this call stays in the previous store and is
+    /// no longer served.
+    pub fn set_content_store(&mut self, store: Arc<dyn ContentStore>) {
+        self.content_store = store;
+    }
+
+    /// Run `validator` on inbound gossip, after the built-in validators and
+    /// any added before it
+    ///
+    /// It sees whole payloads: a fragmented message is validated once
+    /// reassembled, and can then only be kept from delivery, as its
+    /// fragments have already been relayed.
+    pub fn add_validator(&mut self, validator: impl MessageValidator + 'static) {
+        self.app_validators.push(validator);
+    }
+
+    /// Reputation of a peer putting a DHT record or publishing a message
+    fn record_reputation(&self, peer_id: &PeerId) -> f64 {
+        self.peer_reputation(peer_id)
+            .map(|reputation| reputation.score)
+            .unwrap_or(0.0)
+    }
+
+    /// A peer's reputation, with interaction counts from the peer manager
+    fn peer_reputation(&self, peer_id: &PeerId) -> Option<Reputation> {
+        let info = self.peer_manager.get(peer_id);
+        let score = self
+            .record_reputation
+            .as_ref()
+            .and_then(|reputation| reputation(peer_id))
+            .or_else(|| info.as_ref().map(|info| info.score))?;
+
+        let mut reputation = Reputation::new(score);
+        if let Some(info) = info {
+            reputation.successful_interactions = info.successful_interactions;
+            reputation.failed_interactions = info.failed_interactions;
+        }
+        Some(reputation)
+    }
+
+    /// Run the validators on an inbound gossip message
+    ///
+    /// The application validators only run on a `whole` message, not on a
+    /// fragment.
+    fn validate_message(
+        &self,
+        topic: &str,
+        source: Option<&PeerId>,
+        relayer: &PeerId,
+        data: &[u8],
+        whole: bool,
+    ) -> std::result::Result<(), (String, Rejection)> {
+        let reputation = |peer_id: &PeerId| self.record_reputation(peer_id);
+        let message = InboundMessage::new(topic, source, relayer, data, &reputation);
+        let built_in = self.validators.validate(&message);
+        let relayed = match &built_in {
+            Ok(()) => true,
+            Err((_, rejection)) => rejection.action() == RejectAction::Relay,
+        };
+        if !whole || !relayed {
+            return built_in;
+        }
+        self.app_validators.validate(&message).and(built_in)
+    }
+
+    /// Run the application validators on a reassembled message
+    fn validate_reassembled(
+        &self,
+        topic: &str,
+        source: Option<&PeerId>,
+        relayer: &PeerId,
+        data: &[u8],
+    ) -> std::result::Result<(), (String, Rejection)> {
+        let reputation = |peer_id: &PeerId| self.record_reputation(peer_id);
+        self.app_validators.validate(&InboundMessage::new(
+            topic,
+            source,
+            relayer,
+            data,
+            &reputation,
+        ))
+    }
+
+    /// Report a message a validator refused
+    ///
+    /// Unsigned messages have no author to blame, so the peer that relayed
+    /// them is reported instead. Low reputation is the source's standing
+    /// rather than a fault, so it isn't counted against the peer.
+    fn report_rejection(
+        &self,
+        topic: &str,
+        source: Option<PeerId>,
+        relayer: PeerId,
+        validator: String,
+        rejection: Rejection,
+    ) {
+        let peer_id = source.unwrap_or(relayer);
+        debug!(
+            "{} validator refused message on {} from {}: {}",
+            validator, topic, peer_id, rejection
+        );
+        let event = match rejection {
+            Rejection::LowReputation { reputation, .. } => NetworkEvent::MessageFiltered {
+                topic: topic.to_string(),
+                peer_id,
+                reputation,
+            },
+            reason => {
+                self.peer_manager.record_failure(peer_id);
+                NetworkEvent::MessageRejected {
+                    topic: topic.to_string(),
+                    peer_id,
+                    validator,
+                    reason,
+                }
+            }
+        };
+        let _ = self.event_tx.send(event);
+    }
+
+    /// Whether `source` is reputable enough to store DHT data under `key`,
+    /// reporting it if not
+    fn admit_inbound_record(&self, source: PeerId, key: &kad::RecordKey) -> bool {
+        let reputation = self.record_reputation(&source);
+        if reputation >= self.config.min_record_reputation {
+            return true;
+        }
+        debug!(
+            "Rejected DHT record {:?} from {} (reputation {:.2} < {:.2})",
+            key, source, reputation, self.config.min_record_reputation
+        );
+        let _ = self.event_tx.send(NetworkEvent::RecordRejected {
+            key: key.to_vec(),
+            peer_id: source,
+            reputation,
+        });
+        false
+    }
+
+    /// Store an inbound DHT put if its sender is reputable enough
+    fn handle_inbound_record(&mut self, source: PeerId, record: kad::Record) {
+        if !self.admit_inbound_record(source, &record.key) {
+            return;
+        }
+        if let Err(e) = self.swarm.behaviour_mut().store_inbound_record(record) {
+            warn!("Failed to store DHT record from {}: {}", source, e);
+        }
+    }
+
+    /// Store an inbound provider announcement if its sender is reputable
+    /// enough
+    fn handle_inbound_provider(&mut self, source: PeerId, record: kad::ProviderRecord) {
+        if !self.admit_inbound_record(source, &record.key) {
+            return;
+        }
+        if let Err(e) = self.swarm.behaviour_mut().store_inbound_provider(record) {
+            warn!("Failed to store provider record from {}: {}", source, e);
+        }
+    }
+
+    /// Ban a peer, closing its connections and reporting the ban
+    fn ban_peer(&mut self, peer_id: PeerId, duration: Option<Duration>, reason: &str) {
+        let ban = self.peer_manager.ban(peer_id, duration, reason);
+        warn!("Banned peer {}: {}", peer_id, reason);
+        let _ = self.swarm.disconnect_peer_id(peer_id);
+        let _ = self
+            .event_tx
+            .send(NetworkEvent::PeerBanned { peer_id, ban });
+    }
+
+    /// Ban `peer_id` if its reputation has fallen below `ban_reputation`
+    ///
+    /// Peers we know nothing about are left alone.
+    fn ban_if_disreputable(&mut self, peer_id: PeerId) {
+        if self.config.ban_reputation <= 0.0 || self.peer_manager.is_banned(&peer_id) {
+            return;
+        }
+        let Some(reputation) = self.peer_reputation(&peer_id) else {
+            return;
+        };
+        if reputation.score >= self.config.ban_reputation {
+            return;
+        }
+
+        let reason = format!(
+            "reputation {:.2} below {:.2}",
+            reputation.score, self.config.ban_reputation
+        );
+        self.ban_peer(peer_id, Some(self.config.ban_duration()), &reason);
+    }
+
     /// Start the network service
     pub async fn run(mut self) -> Result<()> {
         info!("Starting network service");
 
         // Start listening on configured addresses
         for addr_str in &self.config.listen_addresses.clone() {
-            let addr: Multiaddr = addr_str
-                .parse()
-                .map_err(|e| NetworkError::InvalidMultiaddr(format!("{}: {}", addr_str, e)))?;
+            let addr = transport::parse_multiaddr(addr_str)?;
 
             self.swarm
                 .listen_on(addr.clone())
@@ -443,24 +1659,30 @@ impl NetworkService {
             "/mycelial/1.0.0/resource",   // Resource sharing metrics
         ];
 
-        // ENR bridge topics (only with univrs-compat feature)
+        // ENR economics topics (gradients, credits, elections, septal gates,
+        // Raft), only when enabled
         #[cfg(feature = "univrs-compat")]
-        let enr_topics = [
-            GRADIENT_TOPIC, // Resource gradient broadcasts
-            CREDIT_TOPIC,   // Credit transfers
-            ELECTION_TOPIC, // Nexus election
-            SEPTAL_TOPIC,   // Septal gate (circuit breaker)
-        ];
+        let economics_topics = if self.config.enable_economics {
+            enr_topics()
+        } else {
+            Vec::new()
+        };
         #[cfg(not(feature = "univrs-compat"))]
-        let enr_topics: [&str; 0] = [];
+        let economics_topics: Vec<&str> = Vec::new();
 
-        // Combine all topics
-        let topics: Vec<&str> = core_topics
+        // Combine all topics, plus those restored from a previous run
+        self.protected_topics = core_topics
+            .iter()
+            .chain(economics_topics.iter())
+            .map(|topic| topic.to_string())
+            .collect();
+        let topics: Vec<String> = self
+            .protected_topics
             .iter()
-            .copied()
-            .chain(enr_topics.iter().copied())
+            .cloned()
+            .chain(self.config.subscribed_topics.iter().cloned())
             .collect();
-        for topic_str in topics {
+        for topic_str in &topics {
             let topic = libp2p::gossipsub::IdentTopic::new(topic_str);
             match self.swarm.behaviour_mut().gossipsub.subscribe(&topic) {
                 Ok(true) => {
@@ -469,6 +1691,8 @@ impl NetworkService {
                         topic_str
                     );
                     self.subscribed_topics.insert(topic_str.to_string());
+                    self.topic_activity
+                        .insert(topic_str.to_string(), Instant::now());
                     // Emit event so AppState gets updated
                     let _ = self.event_tx.send(NetworkEvent::Subscribed {
                         topic: topic_str.to_string(),
@@ -479,9 +1703,10 @@ impl NetworkService {
             }
         }
 
-        // Connect to bootstrap peers
+        // Connect to bootstrap peers (retried with backoff until one connects)
+        let now = Instant::now();
         for addr_str in &self.config.bootstrap_peers.clone() {
-            let addr: Multiaddr = match addr_str.parse() {
+            let addr = match transport::parse_multiaddr(addr_str) {
                 Ok(a) => a,
                 Err(e) => {
                     warn!("Invalid bootstrap address {}: {}", addr_str, e);
@@ -489,12 +1714,77 @@ impl NetworkService {
                 }
             };
 
-            if let Err(e) = self.swarm.dial(addr.clone()) {
-                warn!("Failed to dial bootstrap peer {}: {:?}", addr, e);
-            } else {
-                info!("Dialing bootstrap peer {}", addr);
+            // A /dnsaddr may publish several bootstrap peers; dial each one.
+            // If the lookup fails, the DNS transport retries it on dial.
+            let addrs = match transport::resolve_dnsaddr(&addr).await {
+                Ok(addrs) => addrs,
+                Err(e) => {
+                    warn!("Could not expand bootstrap address {}: {}", addr, e);
+                    vec![addr]
+                }
+            };
+
+            for address in addrs {
+                self.bootstrap_dials.push(BootstrapDial {
+                    address,
+                    attempts: 0,
+                    next_attempt: now,
+                    in_flight: None,
+                });
             }
         }
+        self.dial_due_bootstraps();
+        let mut bootstrap_tick = tokio::time::interval(Duration::from_secs(1));
+
+        // Explicit peers were checked by `validate`
+        for (peer_id, address) in self.config.explicit_peer_addresses().unwrap_or_default() {
+            self.add_explicit_peer(peer_id, address);
+        }
+        let mut explicit_peer_tick = tokio::time::interval(Duration::from_secs(1));
+        let mut provider_probe_tick = tokio::time::interval(PROVIDER_PROBE_CHECK_INTERVAL);
+        let mut outbox_tick = tokio::time::interval(PUBLISH_RETRY_CHECK_INTERVAL);
+        // Gossipsub doesn't report grafts and prunes, so check the mesh once
+        // per heartbeat
+        let mut mesh_tick = tokio::time::interval(Duration::from_secs(1));
+        // The first bootstrap runs once a bootstrap peer connects; these
+        // keep the routing table fresh afterwards
+        let kad_bootstrap_interval = self.config.kademlia_bootstrap_interval();
+        let mut kad_bootstrap_tick = delayed_interval(kad_bootstrap_interval);
+        let kad_refresh_interval = self.config.kademlia_refresh_interval();
+        let mut kad_refresh_tick = delayed_interval(kad_refresh_interval);
+        let topic_idle_timeout = self.config.topic_idle_timeout();
+        let mut topic_idle_tick = delayed_interval(
+            topic_idle_timeout.map(|timeout| timeout.min(TOPIC_IDLE_CHECK_INTERVAL)),
+        );
+
+        // A peer whose septal gate we close is banned until it reopens
+        #[cfg(feature = "univrs-compat")]
+        if self.config.enable_economics {
+            tokio::spawn(ban_on_closed_gates(
+                self.enr_bridge.subscribe_local_septal_events(),
+                self.peer_manager.clone(),
+                self.command_tx.clone(),
+                *self.swarm.local_peer_id(),
+                self.config.ban_duration(),
+            ));
+        }
+
+        // An attached Raft ledger needs ticking to elect a leader and
+        // replicate, and its leader pays out the revival pool on a schedule
+        #[cfg(all(feature = "univrs-compat", feature = "openraft"))]
+        let raft_tasks: Vec<tokio::task::JoinHandle<()>> = match self.enr_bridge.raft_ledger() {
+            Some(ledger) if self.config.enable_economics => {
+                let mut tasks = vec![tokio::spawn(ledger.clone().run_ticks())];
+                if let Some(period) = self.config.revival_distribution_interval() {
+                    tasks.push(tokio::spawn(ledger.clone().run_revival_distribution(
+                        period,
+                        crate::raft::DistributionStrategy::Equal,
+                    )));
+                }
+                tasks
+            }
+            _ => Vec::new(),
+        };
 
         self.running = true;
 
@@ -518,6 +1808,51 @@ impl NetworkService {
                         break;
                     }
                 }
+
+                // Answer content requests once their chunk is read
+                Some(served) = self.served_rx.recv() => {
+                    self.send_content_response(served.peer, served.channel, served.response);
+                }
+
+                // Retry unreachable bootstrap peers
+                _ = bootstrap_tick.tick(), if !self.bootstrap_dials.is_empty() => {
+                    self.dial_due_bootstraps();
+                }
+
+                // Redial disconnected explicit peers
+                _ = explicit_peer_tick.tick(), if self.has_disconnected_explicit_peers() => {
+                    self.dial_due_explicit_peers();
+                }
+
+                // Answer provider probes that ran out of time
+                _ = provider_probe_tick.tick(), if self.has_provider_probes() => {
+                    self.finish_overdue_provider_probes();
+                }
+
+                // Retry reliable publishes still waiting for peers
+                _ = outbox_tick.tick(), if !self.outbox.is_empty() => {
+                    self.retry_due_publishes();
+                }
+
+                // Report mesh changes
+                _ = mesh_tick.tick() => {
+                    self.refresh_mesh_status();
+                }
+
+                // Rebuild the Kademlia routing table
+                _ = kad_bootstrap_tick.tick(), if kad_bootstrap_interval.is_some() => {
+                    self.bootstrap_kademlia();
+                }
+
+                // Refresh routing table buckets between bootstraps
+                _ = kad_refresh_tick.tick(), if kad_refresh_interval.is_some() => {
+                    self.refresh_kademlia();
+                }
+
+                // Leave topics that have gone quiet
+                _ = topic_idle_tick.tick(), if topic_idle_timeout.is_some() => {
+                    self.unsubscribe_idle_topics();
+                }
             }
 
             // Update stats
@@ -529,6 +1864,11 @@ impl NetworkService {
             }
         }
 
+        #[cfg(all(feature = "univrs-compat", feature = "openraft"))]
+        for task in raft_tasks {
+            task.abort();
+        }
+
         self.running = false;
         let _ = self.event_tx.send(NetworkEvent::Stopped);
         info!("Network service stopped");
@@ -536,6 +1876,287 @@ impl NetworkService {
         Ok(())
     }
 
+    /// Current gossipsub mesh state for a topic
+    fn current_mesh_status(&self, topic: &str) -> MeshStatus {
+        let behaviour = self.swarm.behaviour();
+        MeshStatus {
+            mesh_peers: behaviour.mesh_peers(topic).len(),
+            all_peers: behaviour.all_peers_on_topic(topic).len(),
+        }
+    }
+
+    /// Clear a peer's ping failures, telling its septal gate it recovered
+    fn handle_ping_success(&mut self, peer_id: PeerId) {
+        if self.ping_failures.remove(&peer_id).is_none() {
+            return;
+        }
+
+        #[cfg(feature = "univrs-compat")]
+        if let Some(node) = node_id_from_peer_id(&peer_id) {
+            let bridge = self.enr_bridge.clone();
+            tokio::spawn(async move { bridge.record_peer_success(node).await });
+        }
+    }
+
+    /// Count a failed ping, disconnecting the peer once it has failed
+    /// `ping_max_failures` in a row
+    ///
+    /// A dead connection is noticed here well before the idle timeout
+    /// closes it. The failure also counts against the peer's septal gate.
+    fn handle_ping_failure(&mut self, peer_id: PeerId) {
+        let failures = self.ping_failures.entry(peer_id).or_insert(0);
+        *failures += 1;
+        let failures = *failures;
+        if failures < self.config.ping_max_failures {
+            return;
+        }
+
+        warn!(
+            "Peer {} failed {} pings in a row, disconnecting",
+            peer_id, failures
+        );
+        self.ping_failures.remove(&peer_id);
+        self.peer_manager
+            .set_state(peer_id, ConnectionState::Disconnected);
+        self.refresh_peer_quality(peer_id);
+        let _ = self.swarm.disconnect_peer_id(peer_id);
+        let _ = self
+            .event_tx
+            .send(NetworkEvent::PeerUnresponsive { peer_id, failures });
+
+        #[cfg(feature = "univrs-compat")]
+        if let Some(node) = node_id_from_peer_id(&peer_id) {
+            let bridge = self.enr_bridge.clone();
+            tokio::spawn(async move {
+                bridge
+                    .record_peer_failure(node, "unresponsive to ping")
+                    .await
+            });
+        }
+    }
+
+    /// Pass a peer's current connection quality on to gossipsub
+    fn refresh_peer_quality(&mut self, peer_id: PeerId) {
+        let quality = self.peer_manager.quality(&peer_id);
+        self.swarm
+            .behaviour_mut()
+            .set_peer_quality(&peer_id, quality);
+    }
+
+    /// Unsubscribe from unprotected topics without traffic for
+    /// `topic_idle_timeout_secs`
+    fn unsubscribe_idle_topics(&mut self) {
+        let Some(timeout) = self.config.topic_idle_timeout() else {
+            return;
+        };
+        let idle: Vec<(String, Duration)> = self
+            .subscribed_topics
+            .iter()
+            .filter(|topic| !self.protected_topics.contains(*topic))
+            .filter_map(|topic| {
+                let silent = self.topic_activity.get(topic)?.elapsed();
+                (silent >= timeout).then(|| (topic.clone(), silent))
+            })
+            .collect();
+
+        for (topic, silent) in idle {
+            if let Err(e) = self.swarm.behaviour_mut().unsubscribe(&topic) {
+                warn!("Failed to unsubscribe from idle topic {}: {:?}", topic, e);
+                continue;
+            }
+            info!(
+                "Unsubscribed from {} after {}s without traffic",
+                topic,
+                silent.as_secs()
+            );
+            self.subscribed_topics.remove(&topic);
+            self.topic_activity.remove(&topic);
+            self.idle_topics.insert(topic.clone());
+            let _ = self.event_tx.send(NetworkEvent::TopicIdle {
+                topic,
+                idle: silent,
+            });
+        }
+    }
+
+    /// Rejoin a topic unsubscribed for being idle, ahead of publishing to it
+    fn resume_idle_topic(&mut self, topic: &str) {
+        if !self.idle_topics.remove(topic) {
+            return;
+        }
+        if let Err(e) = self.swarm.behaviour_mut().subscribe(topic) {
+            warn!("Failed to resubscribe to idle topic {}: {:?}", topic, e);
+            return;
+        }
+        debug!("Resubscribed to idle topic {} to publish", topic);
+        self.subscribed_topics.insert(topic.to_string());
+        let _ = self.event_tx.send(NetworkEvent::TopicResumed {
+            topic: topic.to_string(),
+        });
+    }
+
+    /// Emit `MeshUpdated` for every subscribed topic whose mesh changed
+    fn refresh_mesh_status(&mut self) {
+        self.mesh_status
+            .retain(|topic, _| self.subscribed_topics.contains(topic));
+
+        for topic in &self.subscribed_topics {
+            let status = self.current_mesh_status(topic);
+            if self.mesh_status.get(topic) == Some(&status) {
+                continue;
+            }
+
+            debug!(
+                "Mesh for '{}': {} mesh peers of {} subscribers",
+                topic, status.mesh_peers, status.all_peers
+            );
+            self.mesh_status.insert(topic.clone(), status);
+            let _ = self.event_tx.send(NetworkEvent::MeshUpdated {
+                topic: topic.clone(),
+                mesh_peers: status.mesh_peers,
+                all_peers: status.all_peers,
+            });
+        }
+    }
+
+    /// Start a Kademlia bootstrap, unless the routing table is empty
+    fn bootstrap_kademlia(&mut self) {
+        if !self.config.enable_kademlia {
+            return;
+        }
+        match self.swarm.behaviour_mut().bootstrap() {
+            Ok(_) => debug!("Started Kademlia bootstrap"),
+            Err(e) => debug!("Skipping Kademlia bootstrap: {}", e),
+        }
+    }
+
+    /// Look up a random peer ID, refreshing the buckets the lookup passes
+    /// through
+    fn refresh_kademlia(&mut self) {
+        if !self.config.enable_kademlia {
+            return;
+        }
+        let target = PeerId::random();
+        debug!("Refreshing Kademlia buckets towards {}", target);
+        self.swarm
+            .behaviour_mut()
+            .get_closest_peers(target.to_bytes());
+    }
+
+    /// Dial every bootstrap peer whose backoff has elapsed
+    fn dial_due_bootstraps(&mut self) {
+        let now = Instant::now();
+        for dial in &mut self.bootstrap_dials {
+            if dial.in_flight.is_some() || dial.next_attempt > now {
+                continue;
+            }
+
+            // A bootstrap peer we've met before may have a faster address
+            let opts = match crate::transport::extract_peer_id(&dial.address) {
+                Some(peer_id) => DialOpts::peer_id(peer_id)
+                    .addresses(dial_addresses(
+                        self.peer_manager.best_address(&peer_id),
+                        &dial.address,
+                    ))
+                    .build(),
+                None => DialOpts::from(dial.address.clone()),
+            };
+            let connection_id = opts.connection_id();
+            dial.attempts += 1;
+
+            match self.swarm.dial(opts) {
+                Ok(()) => {
+                    info!(
+                        "Dialing bootstrap peer {} (attempt {})",
+                        dial.address, dial.attempts
+                    );
+                    dial.in_flight = Some(connection_id);
+                }
+                Err(e) => {
+                    let delay = self.config.bootstrap_backoff(dial.attempts);
+                    warn!(
+                        "Failed to dial bootstrap peer {}: {:?} (retrying in {:?})",
+                        dial.address, e, delay
+                    );
+                    dial.next_attempt = now + delay;
+                }
+            }
+        }
+    }
+
+    /// Schedule a retry if `connection_id` was a failed bootstrap dial
+    fn handle_bootstrap_failure(&mut self, connection_id: ConnectionId) {
+        let now = Instant::now();
+        if let Some(dial) = self
+            .bootstrap_dials
+            .iter_mut()
+            .find(|d| d.in_flight == Some(connection_id))
+        {
+            let delay = self.config.bootstrap_backoff(dial.attempts);
+            info!(
+                "Bootstrap peer {} unreachable, retrying in {:?}",
+                dial.address, delay
+            );
+            dial.in_flight = None;
+            dial.next_attempt = now + delay;
+        }
+    }
+
+    /// Make `peer_id` an explicit gossipsub peer, dialing it if needed
+    fn add_explicit_peer(&mut self, peer_id: PeerId, address: Multiaddr) {
+        info!("Adding explicit peer {} at {}", peer_id, address);
+        self.swarm.behaviour_mut().add_explicit_peer(&peer_id);
+        let next_attempt = (!self.swarm.is_connected(&peer_id)).then(Instant::now);
+        self.explicit_peers.insert(
+            peer_id,
+            ExplicitPeer {
+                address,
+                attempts: 0,
+                next_attempt,
+            },
+        );
+        self.dial_due_explicit_peers();
+    }
+
+    /// Whether any explicit peer is waiting to be redialed
+    fn has_disconnected_explicit_peers(&self) -> bool {
+        self.explicit_peers
+            .values()
+            .any(|peer| peer.next_attempt.is_some())
+    }
+
+    /// Dial every disconnected explicit peer whose backoff has elapsed
+    ///
+    /// Banned and blocked peers are left alone until the ban or block lifts.
+    fn dial_due_explicit_peers(&mut self) {
+        let now = Instant::now();
+        for (peer_id, peer) in &mut self.explicit_peers {
+            if peer.next_attempt.is_none_or(|at| at > now)
+                || self.blocked_peers.contains(peer_id)
+                || self.peer_manager.is_banned(peer_id)
+            {
+                continue;
+            }
+
+            peer.attempts += 1;
+            // Retried after the backoff unless the dial connects first
+            peer.next_attempt = Some(now + self.config.bootstrap_backoff(peer.attempts));
+            let opts = DialOpts::peer_id(*peer_id)
+                .addresses(dial_addresses(
+                    self.peer_manager.best_address(peer_id),
+                    &peer.address,
+                ))
+                .build();
+            match self.swarm.dial(opts) {
+                Ok(()) => debug!(
+                    "Dialing explicit peer {} at {} (attempt {})",
+                    peer_id, peer.address, peer.attempts
+                ),
+                Err(e) => debug!("Failed to dial explicit peer {}: {}", peer_id, e),
+            }
+        }
+    }
+
     /// Handle a swarm event
     async fn handle_swarm_event(&mut self, event: SwarmEvent<MycelialBehaviourEvent>) {
         match event {
@@ -545,6 +2166,7 @@ impl NetworkService {
 
             SwarmEvent::ConnectionEstablished {
                 peer_id,
+                connection_id,
                 num_established,
                 endpoint,
                 ..
@@ -555,14 +2177,27 @@ impl NetworkService {
                     let _ = self.swarm.disconnect_peer_id(peer_id);
                     return;
                 }
+                if self.peer_manager.is_banned(&peer_id) {
+                    debug!("Refusing connection from banned peer {}", peer_id);
+                    let _ = self.swarm.disconnect_peer_id(peer_id);
+                    return;
+                }
 
                 debug!("Connection established with {}", peer_id);
+                if let Some(explicit) = self.explicit_peers.get_mut(&peer_id) {
+                    explicit.attempts = 0;
+                    explicit.next_attempt = None;
+                }
 
                 self.peer_manager
                     .set_state(peer_id, ConnectionState::Connected);
+                self.refresh_peer_quality(peer_id);
 
                 let addr = endpoint.get_remote_address();
                 self.peer_manager.add_address(peer_id, addr.clone());
+                if endpoint.is_dialer() {
+                    self.connection_addrs.insert(connection_id, addr.clone());
+                }
 
                 let _ = self.event_tx.send(NetworkEvent::ConnectionEstablished {
                     peer_id,
@@ -570,6 +2205,29 @@ impl NetworkService {
                     outbound: endpoint.is_dialer(),
                 });
 
+                // The first bootstrap to connect ends all bootstrap retries
+                if let Some(dial) = self
+                    .bootstrap_dials
+                    .iter()
+                    .find(|d| d.in_flight == Some(connection_id))
+                {
+                    info!(
+                        "Connected to bootstrap peer {} at {}",
+                        peer_id, dial.address
+                    );
+                    let _ = self.event_tx.send(NetworkEvent::BootstrapConnected {
+                        peer_id,
+                        address: dial.address.clone(),
+                    });
+                    self.bootstrap_dials.clear();
+
+                    // Fill the routing table from the bootstrap peer
+                    self.swarm
+                        .behaviour_mut()
+                        .add_address(&peer_id, addr.clone());
+                    self.bootstrap_kademlia();
+                }
+
                 if num_established.get() == 1 {
                     let _ = self.event_tx.send(NetworkEvent::PeerConnected {
                         peer_id,
@@ -580,15 +2238,23 @@ impl NetworkService {
 
             SwarmEvent::ConnectionClosed {
                 peer_id,
+                connection_id,
                 num_established,
                 cause,
                 ..
             } => {
                 debug!("Connection closed with {}: {:?}", peer_id, cause);
+                self.connection_addrs.remove(&connection_id);
 
                 if num_established == 0 {
+                    if let Some(explicit) = self.explicit_peers.get_mut(&peer_id) {
+                        info!("Explicit peer {} disconnected, redialing", peer_id);
+                        explicit.next_attempt = Some(Instant::now());
+                    }
+                    self.ping_failures.remove(&peer_id);
                     self.peer_manager
                         .set_state(peer_id, ConnectionState::Disconnected);
+                    self.refresh_peer_quality(peer_id);
 
                     let _ = self.event_tx.send(NetworkEvent::PeerDisconnected {
                         peer_id,
@@ -608,7 +2274,21 @@ impl NetworkService {
                 let _ = self.event_tx.send(NetworkEvent::ListeningOn { address });
             }
 
-            SwarmEvent::OutgoingConnectionError { peer_id, error, .. } => {
+            SwarmEvent::ExpiredListenAddr { address, .. } => {
+                info!("No longer listening on {}", address);
+                let _ = self
+                    .event_tx
+                    .send(NetworkEvent::ListenAddrExpired { address });
+            }
+
+            SwarmEvent::OutgoingConnectionError {
+                peer_id,
+                connection_id,
+                error,
+                ..
+            } => {
+                self.handle_bootstrap_failure(connection_id);
+
                 if let Some(peer_id) = peer_id {
                     // Only mark as failed if not already connected or connecting
                     // Dial errors for secondary addresses shouldn't affect existing connections,
@@ -659,10 +2339,64 @@ impl NetworkService {
     async fn handle_behaviour_event(&mut self, event: MycelialBehaviourEvent) {
         match event {
             MycelialBehaviourEvent::Gossipsub(gossipsub::Event::Message {
-                propagation_source: _,
+                propagation_source,
                 message_id,
                 message,
             }) => {
+                let topic_str = message.topic.to_string();
+                if let Some(last_activity) = self.topic_activity.get_mut(&topic_str) {
+                    *last_activity = Instant::now();
+                }
+
+                // Neither deliver nor relay messages from banned peers, or
+                // messages the validators refuse
+                if let Some(source) = message.source {
+                    self.ban_if_disreputable(source);
+                }
+                let banned = message
+                    .source
+                    .is_some_and(|source| self.peer_manager.is_banned(&source));
+                let fragmented = fragment::is_fragment(&message.data);
+                let refused = if banned {
+                    None
+                } else {
+                    self.validate_message(
+                        &topic_str,
+                        message.source.as_ref(),
+                        &propagation_source,
+                        &message.data,
+                        !fragmented,
+                    )
+                    .err()
+                };
+                let acceptance = match &refused {
+                    _ if banned => gossipsub::MessageAcceptance::Ignore,
+                    Some((_, rejection)) if rejection.action() == RejectAction::Reject => {
+                        gossipsub::MessageAcceptance::Reject
+                    }
+                    // Relayed, but not delivered below
+                    Some(_) | None => gossipsub::MessageAcceptance::Accept,
+                };
+                self.swarm.behaviour_mut().report_message(
+                    &message_id,
+                    &propagation_source,
+                    acceptance,
+                );
+                if banned {
+                    debug!("Dropping message on {} from banned peer", topic_str);
+                    return;
+                }
+                if let Some((validator, rejection)) = refused {
+                    self.report_rejection(
+                        &topic_str,
+                        message.source,
+                        propagation_source,
+                        validator,
+                        rejection,
+                    );
+                    return;
+                }
+
                 // Filter messages from blocked peers (partition testing)
                 if let Some(source) = &message.source {
                     if self.blocked_peers.contains(source) {
@@ -674,7 +2408,12 @@ impl NetworkService {
                     }
                 }
 
-                let topic_str = message.topic.to_string();
+                let span = info_span!(
+                    "gossip_message",
+                    correlation_id = %correlation_id(&message_id.0),
+                    topic = %topic_str
+                );
+                let _entered = span.enter();
                 debug!(
                     "Received message on topic {} from {:?}",
                     topic_str, message.source
@@ -686,27 +2425,82 @@ impl NetworkService {
                     stats.bytes_received += message.data.len() as u64;
                 }
 
+                // Hold fragments back until their whole message is here
+                let (message_id, data) = if fragmented {
+                    match self
+                        .fragments
+                        .insert(message.source, &message.data, Instant::now())
+                    {
+                        Ok(Some(data)) => {
+                            if let Err((validator, rejection)) = self.validate_reassembled(
+                                &topic_str,
+                                message.source.as_ref(),
+                                &propagation_source,
+                                &data,
+                            ) {
+                                self.report_rejection(
+                                    &topic_str,
+                                    message.source,
+                                    propagation_source,
+                                    validator,
+                                    rejection,
+                                );
+                                return;
+                            }
+                            (gossipsub::MessageId::from(payload_id(&data)), data)
+                        }
+                        Ok(None) => return,
+                        Err(e) => {
+                            debug!(
+                                "Dropped fragment on {} from {:?}: {}",
+                                topic_str, message.source, e
+                            );
+                            if let Some(source) = &message.source {
+                                self.peer_manager.record_failure(*source);
+                            }
+                            return;
+                        }
+                    }
+                } else {
+                    (message_id, message.data)
+                };
+
                 // Route ENR messages to the bridge handler (requires univrs-compat feature)
                 #[cfg(feature = "univrs-compat")]
-                if topic_str == GRADIENT_TOPIC
-                    || topic_str == CREDIT_TOPIC
-                    || topic_str == ELECTION_TOPIC
-                    || topic_str == SEPTAL_TOPIC
-                {
+                if self.config.enable_economics && BRIDGE_TOPICS.contains(&topic_str.as_str()) {
                     let bridge = self.enr_bridge.clone();
-                    let data = message.data.clone();
-                    tokio::spawn(async move {
-                        if let Err(e) = bridge.handle_message(&data).await {
-                            warn!("Failed to handle ENR message: {}", e);
+                    let data = data.clone();
+                    tokio::spawn(
+                        async move {
+                            if let Err(e) = bridge.handle_message(&data).await {
+                                warn!("Failed to handle ENR message: {}", e);
+                            }
                         }
-                    });
+                        .instrument(span.clone()),
+                    );
+                }
+
+                // Raft ledger traffic goes to the attached ledger, if any
+                #[cfg(all(feature = "univrs-compat", feature = "openraft"))]
+                if self.config.enable_economics && topic_str == crate::raft::RAFT_TOPIC {
+                    if let Some(ledger) = self.enr_bridge.raft_ledger().cloned() {
+                        let data = data.clone();
+                        tokio::spawn(
+                            async move {
+                                if let Err(e) = ledger.handle_message(&data).await {
+                                    warn!("Failed to handle Raft message: {}", e);
+                                }
+                            }
+                            .instrument(span.clone()),
+                        );
+                    }
                 }
 
                 let _ = self.event_tx.send(NetworkEvent::MessageReceived {
                     message_id,
                     topic: topic_str,
                     source: message.source,
-                    data: message.data,
+                    data,
                     timestamp: chrono::Utc::now(),
                 });
             }
@@ -729,6 +2523,11 @@ impl NetworkService {
                     debug!("Current mesh peers for '{}': {:?}", topic_str, mesh_peers);
                 }
 
+                #[cfg(feature = "univrs-compat")]
+                if topic_str == ELECTION_TOPIC {
+                    self.sync_election_peers();
+                }
+
                 let _ = self.event_tx.send(NetworkEvent::PeerSubscribed {
                     peer_id,
                     topic: topic_str,
@@ -749,6 +2548,11 @@ impl NetworkService {
                     mesh_peers.len()
                 );
 
+                #[cfg(feature = "univrs-compat")]
+                if topic_str == ELECTION_TOPIC {
+                    self.sync_election_peers();
+                }
+
                 let _ = self.event_tx.send(NetworkEvent::PeerUnsubscribed {
                     peer_id,
                     topic: topic_str,
@@ -788,11 +2592,59 @@ impl NetworkService {
                 });
             }
 
+            MycelialBehaviourEvent::Ping(ping::Event {
+                peer,
+                connection,
+                result,
+            }) => match result {
+                Ok(rtt) => {
+                    let addr = self.connection_addrs.get(&connection);
+                    self.peer_manager.record_rtt(peer, addr, rtt);
+                    self.refresh_peer_quality(peer);
+                    self.handle_ping_success(peer);
+                    let _ = self
+                        .event_tx
+                        .send(NetworkEvent::PingResult { peer_id: peer, rtt });
+                }
+                Err(e) => {
+                    debug!("Ping to {} failed: {}", peer, e);
+                    self.handle_ping_failure(peer);
+                }
+            },
+
+            MycelialBehaviourEvent::Kademlia(kad::Event::InboundRequest {
+                request:
+                    kad::InboundRequest::PutRecord {
+                        source,
+                        record: Some(record),
+                        ..
+                    },
+            }) => {
+                self.handle_inbound_record(source, record);
+            }
+
+            MycelialBehaviourEvent::Kademlia(kad::Event::InboundRequest {
+                request:
+                    kad::InboundRequest::AddProvider {
+                        record: Some(record),
+                    },
+            }) => {
+                self.handle_inbound_provider(record.provider, record);
+            }
+
             MycelialBehaviourEvent::Kademlia(kad::Event::OutboundQueryProgressed {
+                id,
                 result: kad::QueryResult::GetRecord(Ok(kad::GetRecordOk::FoundRecord(record))),
                 ..
             }) => {
                 debug!("Found DHT record: {:?}", record.record.key);
+                if let Some(response) = self.pending_gets.remove(&id) {
+                    let _ = response.send(Ok(Some(record.record.value.clone())));
+                    // The caller only wants one record
+                    if let Some(mut query) = self.swarm.behaviour_mut().kademlia.query_mut(&id) {
+                        query.finish();
+                    }
+                }
                 let _ = self.event_tx.send(NetworkEvent::RecordFound {
                     key: record.record.key.to_vec(),
                     value: record.record.value,
@@ -800,13 +2652,62 @@ impl NetworkService {
             }
 
             MycelialBehaviourEvent::Kademlia(kad::Event::OutboundQueryProgressed {
-                result: kad::QueryResult::PutRecord(Ok(kad::PutRecordOk { key })),
+                id,
+                result:
+                    kad::QueryResult::GetRecord(Ok(kad::GetRecordOk::FinishedWithNoAdditionalRecord {
+                        ..
+                    })),
                 ..
             }) => {
-                debug!("Stored DHT record: {:?}", key);
-                let _ = self
-                    .event_tx
-                    .send(NetworkEvent::RecordStored { key: key.to_vec() });
+                if let Some(response) = self.pending_gets.remove(&id) {
+                    let _ = response.send(Ok(None));
+                }
+            }
+
+            MycelialBehaviourEvent::Kademlia(kad::Event::OutboundQueryProgressed {
+                id,
+                result: kad::QueryResult::GetRecord(Err(e)),
+                ..
+            }) => {
+                debug!("DHT get failed: {:?}", e);
+                if let Some(response) = self.pending_gets.remove(&id) {
+                    let result = match e {
+                        kad::GetRecordError::NotFound { .. } => Ok(None),
+                        e => Err(NetworkError::Kademlia(format!(
+                            "Get record failed: {:?}",
+                            e
+                        ))),
+                    };
+                    let _ = response.send(result);
+                }
+            }
+
+            MycelialBehaviourEvent::Kademlia(kad::Event::OutboundQueryProgressed {
+                id,
+                result: kad::QueryResult::PutRecord(Ok(kad::PutRecordOk { key })),
+                ..
+            }) => {
+                debug!("Stored DHT record: {:?}", key);
+                if let Some(response) = self.pending_puts.remove(&id) {
+                    let _ = response.send(Ok(()));
+                }
+                let _ = self
+                    .event_tx
+                    .send(NetworkEvent::RecordStored { key: key.to_vec() });
+            }
+
+            MycelialBehaviourEvent::Kademlia(kad::Event::OutboundQueryProgressed {
+                id,
+                result: kad::QueryResult::PutRecord(Err(e)),
+                ..
+            }) => {
+                warn!("DHT put failed: {:?}", e);
+                if let Some(response) = self.pending_puts.remove(&id) {
+                    let _ = response.send(Err(NetworkError::Kademlia(format!(
+                        "Put record failed: {:?}",
+                        e
+                    ))));
+                }
             }
 
             MycelialBehaviourEvent::Mdns(mdns::Event::Discovered(peers)) => {
@@ -836,10 +2737,541 @@ impl NetworkService {
                     .send(NetworkEvent::MdnsExpired { peers: expired });
             }
 
+            MycelialBehaviourEvent::Autonat(autonat::Event::StatusChanged { new, .. }) => {
+                let status = NatStatus::from(&new);
+                if status != self.nat_status {
+                    info!("NAT status changed: {:?} -> {:?}", self.nat_status, status);
+                    self.nat_status = status;
+                    self.update_relay_reservations();
+                    let _ = self
+                        .event_tx
+                        .send(NetworkEvent::NatStatusChanged { status });
+                }
+            }
+
+            MycelialBehaviourEvent::RelayClient(relay::client::Event::ReservationReqAccepted {
+                relay_peer_id,
+                ..
+            }) => {
+                info!("Relay reservation accepted by {}", relay_peer_id);
+            }
+
+            MycelialBehaviourEvent::Kademlia(kad::Event::OutboundQueryProgressed {
+                id,
+                result: kad::QueryResult::GetProviders(result),
+                step,
+                ..
+            }) => {
+                self.handle_providers_progress(id, result, step.last);
+            }
+
+            MycelialBehaviourEvent::Kademlia(kad::Event::OutboundQueryProgressed {
+                result: kad::QueryResult::Bootstrap(result),
+                ..
+            }) => {
+                let remaining = match result {
+                    Ok(kad::BootstrapOk { num_remaining, .. }) => num_remaining,
+                    Err(kad::BootstrapError::Timeout { num_remaining, .. }) => {
+                        debug!("Kademlia bootstrap step timed out");
+                        num_remaining.unwrap_or(0)
+                    }
+                };
+                let routing_table_size = self.swarm.behaviour_mut().routing_table_size();
+                if remaining == 0 {
+                    info!(
+                        "Kademlia bootstrap finished with {} peers in the routing table",
+                        routing_table_size
+                    );
+                }
+                let _ = self.event_tx.send(NetworkEvent::BootstrapProgress {
+                    remaining,
+                    routing_table_size,
+                });
+            }
+
+            MycelialBehaviourEvent::Kademlia(kad::Event::OutboundQueryProgressed {
+                result: kad::QueryResult::StartProviding(Err(e)),
+                ..
+            }) => {
+                warn!("Failed to announce content provider record: {:?}", e);
+            }
+
+            MycelialBehaviourEvent::Content(event) => {
+                self.handle_content_event(event);
+            }
+
+            _ => {}
+        }
+    }
+
+    /// Start a bounded provider lookup, counting ourselves if we provide `id`
+    fn probe_providers(
+        &mut self,
+        id: ContentId,
+        timeout: Duration,
+        enough: Option<usize>,
+        response: tokio::sync::oneshot::Sender<Result<Vec<PeerId>>>,
+    ) {
+        let local_peer_id = *self.swarm.local_peer_id();
+        let mut pending = PendingProviders {
+            include_local: true,
+            deadline: Some(Instant::now() + timeout),
+            enough,
+            ..PendingProviders::new(response)
+        };
+        if self.provided_content.contains(&id) {
+            pending.providers.insert(local_peer_id);
+            if pending.has_enough() {
+                let _ = pending.response.send(Ok(vec![local_peer_id]));
+                return;
+            }
+        }
+
+        let query_id = self.swarm.behaviour_mut().get_providers(&id);
+        self.pending_providers.insert(query_id, pending);
+    }
+
+    /// Whether a provider probe is waiting on its deadline
+    fn has_provider_probes(&self) -> bool {
+        self.pending_providers
+            .values()
+            .any(|pending| pending.deadline.is_some())
+    }
+
+    /// Finish provider probes whose deadline has passed
+    ///
+    /// Kademlia then reports the lookup as done, which answers the caller
+    /// with the providers found so far.
+    fn finish_overdue_provider_probes(&mut self) {
+        let now = Instant::now();
+        let overdue: Vec<_> = self
+            .pending_providers
+            .iter_mut()
+            .filter(|(_, pending)| pending.deadline.is_some_and(|deadline| deadline <= now))
+            .map(|(query_id, pending)| {
+                pending.deadline = None;
+                *query_id
+            })
+            .collect();
+        for query_id in overdue {
+            debug!("Provider probe timed out, finishing lookup");
+            self.swarm.behaviour_mut().finish_query(&query_id);
+        }
+    }
+
+    /// Collect providers from a provider lookup, answering once it finishes
+    fn handle_providers_progress(
+        &mut self,
+        id: kad::QueryId,
+        result: std::result::Result<kad::GetProvidersOk, kad::GetProvidersError>,
+        last: bool,
+    ) {
+        let Some(pending) = self.pending_providers.get_mut(&id) else {
+            return;
+        };
+
+        match result {
+            Ok(kad::GetProvidersOk::FoundProviders { providers, .. }) => {
+                pending.providers.extend(providers);
+                if pending.has_enough() && !last {
+                    pending.enough = None;
+                    self.swarm.behaviour_mut().finish_query(&id);
+                }
+            }
+            Ok(kad::GetProvidersOk::FinishedWithNoAdditionalRecord { .. }) => {}
+            // A timeout still leaves the providers found so far
+            Err(e) => debug!("Provider lookup ended early: {:?}", e),
+        }
+
+        if !last {
+            return;
+        }
+        if let Some(pending) = self.pending_providers.remove(&id) {
+            let local_peer_id = *self.swarm.local_peer_id();
+            let mut providers: Vec<_> = pending
+                .providers
+                .into_iter()
+                .filter(|peer_id| pending.include_local || *peer_id != local_peer_id)
+                .map(|peer_id| {
+                    let score = self
+                        .peer_manager
+                        .get(&peer_id)
+                        .map(|info| info.score)
+                        .unwrap_or(0.0);
+                    (peer_id, score)
+                })
+                .collect();
+            providers.sort_by(|a, b| b.1.total_cmp(&a.1));
+            let _ = pending.response.send(Ok(providers
+                .into_iter()
+                .map(|(peer_id, _)| peer_id)
+                .collect()));
+        }
+    }
+
+    /// Answer an inbound content request
+    fn send_content_response(
+        &mut self,
+        peer: PeerId,
+        channel: request_response::ResponseChannel<ContentResponse>,
+        response: ContentResponse,
+    ) {
+        if let ContentResponse::Chunk { index, .. } = &response {
+            debug!("Serving chunk {} to {}", index, peer);
+        }
+        if self
+            .swarm
+            .behaviour_mut()
+            .content
+            .send_response(channel, response)
+            .is_err()
+        {
+            debug!("Content request from {} closed before we answered", peer);
+        }
+    }
+
+    /// Serve inbound content requests and hand responses to their callers
+    fn handle_content_event(
+        &mut self,
+        event: request_response::Event<ContentRequest, ContentResponse>,
+    ) {
+        match event {
+            request_response::Event::Message {
+                peer,
+                message:
+                    request_response::Message::Request {
+                        request, channel, ..
+                    },
+                ..
+            } => {
+                if !self.provided_content.contains(&request.id) {
+                    self.send_content_response(peer, channel, ContentResponse::NotFound);
+                    return;
+                }
+
+                // Read from the store off the event loop; the chunk comes
+                // back through `served_rx`
+                let store = self.content_store.clone();
+                let served_tx = self.served_tx.clone();
+                tokio::spawn(async move {
+                    let response = match store.fetch_content(&request.id).await {
+                        Ok(Some(content)) => content::chunk_response(&content, request.chunk),
+                        Ok(None) => ContentResponse::NotFound,
+                        Err(e) => {
+                            warn!(
+                                "Failed to read {} from the content store: {}",
+                                request.id, e
+                            );
+                            ContentResponse::NotFound
+                        }
+                    };
+                    let _ = served_tx
+                        .send(ServedChunk {
+                            peer,
+                            channel,
+                            response,
+                        })
+                        .await;
+                });
+            }
+
+            request_response::Event::Message {
+                peer,
+                message:
+                    request_response::Message::Response {
+                        request_id,
+                        response,
+                    },
+                ..
+            } => {
+                let Some((id, caller)) = self.pending_content.remove(&request_id) else {
+                    return;
+                };
+                if let ContentResponse::Chunk { index, total, .. } = &response {
+                    if *total > 1 {
+                        let _ = self.event_tx.send(NetworkEvent::ContentFetchProgress {
+                            id,
+                            peer_id: peer,
+                            received: index + 1,
+                            total: *total,
+                        });
+                    }
+                }
+                let _ = caller.send(Ok(response));
+            }
+
+            request_response::Event::OutboundFailure {
+                peer,
+                request_id,
+                error,
+                ..
+            } => {
+                debug!("Content request to {} failed: {}", peer, error);
+                if let Some((_, caller)) = self.pending_content.remove(&request_id) {
+                    let _ = caller.send(Err(NetworkError::Transport(format!(
+                        "Content request to {} failed: {}",
+                        peer, error
+                    ))));
+                }
+            }
+
+            request_response::Event::InboundFailure { peer, error, .. } => {
+                debug!("Inbound content request from {} failed: {}", peer, error);
+            }
+
+            request_response::Event::ResponseSent { .. } => {}
+        }
+    }
+
+    /// Hold relay reservations while AutoNAT says we are private
+    ///
+    /// Reservations are released once we turn out to be publicly reachable;
+    /// an unknown status leaves things as they are.
+    fn update_relay_reservations(&mut self) {
+        match self.nat_status {
+            NatStatus::Private if self.relay_listeners.is_empty() => {
+                for addr_str in &self.config.relay_addresses {
+                    let addr: Multiaddr = match addr_str.parse() {
+                        Ok(a) => a,
+                        Err(e) => {
+                            warn!("Invalid relay address {}: {}", addr_str, e);
+                            continue;
+                        }
+                    };
+                    match self.swarm.listen_on(addr.with(Protocol::P2pCircuit)) {
+                        Ok(id) => {
+                            info!("Requesting relay reservation on {}", addr_str);
+                            self.relay_listeners.push(id);
+                        }
+                        Err(e) => warn!("Failed to listen via relay {}: {}", addr_str, e),
+                    }
+                }
+            }
+            NatStatus::Public => {
+                for id in self.relay_listeners.drain(..) {
+                    self.swarm.remove_listener(id);
+                }
+            }
             _ => {}
         }
     }
 
+    /// Tell the election which peers are subscribed to the election topic
+    #[cfg(feature = "univrs-compat")]
+    fn sync_election_peers(&self) {
+        let peers: Vec<_> = self
+            .swarm
+            .behaviour()
+            .all_peers_on_topic(ELECTION_TOPIC)
+            .iter()
+            .filter_map(node_id_from_peer_id)
+            .collect();
+        let bridge = self.enr_bridge.clone();
+        tokio::spawn(async move {
+            bridge.election.set_topic_peers(peers).await;
+        });
+    }
+
+    /// Publish a message, fragmenting it if it is too large for one
+    ///
+    /// Returns whether every part of it was published.
+    fn publish_message(&mut self, topic: &str, data: Vec<u8>) -> bool {
+        self.resume_idle_topic(topic);
+        if self.subscribed_topics.contains(topic) {
+            self.topic_activity
+                .insert(topic.to_string(), Instant::now());
+        }
+        if self.is_oversized(data.len()) {
+            warn!(
+                "Not publishing {} bytes to '{}': larger than max_fragmented_message_size ({})",
+                data.len(),
+                topic,
+                self.config.max_fragmented_message_size
+            );
+            return false;
+        }
+        let fragment_size = self.config.fragment_size();
+        if data.len() <= fragment_size || self.config.max_fragmented_message_size == 0 {
+            return self.publish_gossip(topic, data);
+        }
+        match fragment::split(&data, fragment_size) {
+            Ok(fragments) => {
+                debug!(
+                    "Publishing {} bytes to '{}' in {} fragments",
+                    data.len(),
+                    topic,
+                    fragments.len()
+                );
+                let mut published = true;
+                for fragment in fragments {
+                    published &= self.publish_gossip(topic, fragment);
+                }
+                published
+            }
+            Err(e) => {
+                warn!("Failed to fragment message for '{}': {}", topic, e);
+                false
+            }
+        }
+    }
+
+    /// Whether a message of `len` bytes is too large to publish, even in
+    /// fragments
+    fn is_oversized(&self, len: usize) -> bool {
+        let max = self.config.max_fragmented_message_size;
+        max > 0 && len > max && len > self.config.fragment_size()
+    }
+
+    /// Whether a publish on `topic` has anyone to go to
+    ///
+    /// Topics we subscribe to publish through the mesh; others go to the
+    /// fanout, drawn from every peer subscribed to the topic.
+    fn can_publish(&self, topic: &str) -> bool {
+        let status = self.current_mesh_status(topic);
+        if self.subscribed_topics.contains(topic) {
+            status.is_formed()
+        } else {
+            status.all_peers > 0
+        }
+    }
+
+    /// Try a reliable publish, putting it back in the outbox if it can't go
+    /// out yet and reporting it failed once its deadline has passed
+    fn try_reliable_publish(&mut self, mut pending: PendingPublish) {
+        if self.is_oversized(pending.data.len()) {
+            let _ = self.event_tx.send(NetworkEvent::PublishFailed {
+                topic: pending.topic,
+                attempts: pending.attempts,
+                error: format!(
+                    "{} bytes is larger than max_fragmented_message_size",
+                    pending.data.len()
+                ),
+            });
+            return;
+        }
+
+        // Rejoin an idle topic now so its mesh can form before the retry
+        self.resume_idle_topic(&pending.topic);
+        pending.attempts += 1;
+        if self.can_publish(&pending.topic)
+            && self.publish_message(&pending.topic, pending.data.clone())
+        {
+            return;
+        }
+
+        let now = Instant::now();
+        if now >= pending.deadline {
+            warn!(
+                "Giving up publishing to '{}' after {} attempts",
+                pending.topic, pending.attempts
+            );
+            let _ = self.event_tx.send(NetworkEvent::PublishFailed {
+                topic: pending.topic,
+                attempts: pending.attempts,
+                error: "no peers to publish to before the deadline".to_string(),
+            });
+            return;
+        }
+
+        if self.outbox.len() >= PUBLISH_OUTBOX_CAPACITY {
+            warn!(
+                "Publish outbox full; dropping message for '{}'",
+                pending.topic
+            );
+            let _ = self.event_tx.send(NetworkEvent::PublishFailed {
+                topic: pending.topic,
+                attempts: pending.attempts,
+                error: "publish outbox full".to_string(),
+            });
+            return;
+        }
+
+        // One last try at the deadline rather than none past it
+        pending.next_attempt =
+            (now + publish_retry_backoff(pending.attempts)).min(pending.deadline);
+        let retry_in = pending.next_attempt - now;
+        debug!(
+            "Retrying publish to '{}' in {:?} (attempt {})",
+            pending.topic, retry_in, pending.attempts
+        );
+        let _ = self.event_tx.send(NetworkEvent::PublishRetrying {
+            topic: pending.topic.clone(),
+            attempt: pending.attempts,
+            retry_in,
+        });
+        self.outbox.push(pending);
+    }
+
+    /// Try every reliable publish whose backoff has elapsed
+    fn retry_due_publishes(&mut self) {
+        let now = Instant::now();
+        let (due, waiting) = std::mem::take(&mut self.outbox)
+            .into_iter()
+            .partition(|pending| pending.next_attempt <= now);
+        self.outbox = waiting;
+        for pending in due {
+            self.try_reliable_publish(pending);
+        }
+    }
+
+    /// Publish one gossip message, logging the mesh it goes out on
+    ///
+    /// Returns whether gossipsub accepted it, now or earlier: message IDs
+    /// are content addressed, so a retry of something already published is
+    /// a duplicate.
+    fn publish_gossip(&mut self, topic: &str, data: Vec<u8>) -> bool {
+        // Log mesh status before publishing for debugging
+        let mesh_peers = self.swarm.behaviour().mesh_peers(topic);
+        let all_peers = self.swarm.behaviour().all_peers_on_topic(topic);
+
+        info!(
+            "Publishing to '{}' | {} bytes | Mesh peers: {} | Total subscribers: {}",
+            topic,
+            data.len(),
+            mesh_peers.len(),
+            all_peers.len()
+        );
+
+        if mesh_peers.is_empty() && !all_peers.is_empty() {
+            warn!(
+                "Warning: Publishing to '{}' with 0 mesh peers but {} subscribed peers. \
+                Mesh may not have formed yet (check mesh_n/mesh_n_low config).",
+                topic,
+                all_peers.len()
+            );
+        }
+
+        if !mesh_peers.is_empty() {
+            debug!("Mesh peers for '{}': {:?}", topic, mesh_peers);
+        }
+
+        match self.swarm.behaviour_mut().publish(topic, data.clone()) {
+            Ok(msg_id) => {
+                info!(
+                    "Published message {} to '{}' via {} mesh peers",
+                    msg_id,
+                    topic,
+                    mesh_peers.len()
+                );
+                let mut stats = self.stats.write();
+                stats.messages_sent += 1;
+                stats.bytes_sent += data.len() as u64;
+                true
+            }
+            Err(gossipsub::PublishError::Duplicate) => {
+                debug!("Message to '{}' was already published", topic);
+                true
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to publish to '{}': {:?} | Mesh peers: {} | Consider waiting for mesh formation",
+                    topic, e, mesh_peers.len()
+                );
+                false
+            }
+        }
+    }
+
     /// Handle a command, returns false if should shutdown
     async fn handle_command(&mut self, cmd: NetworkCommand) -> bool {
         match cmd {
@@ -859,6 +3291,8 @@ impl NetworkService {
                 if let Err(e) = self.swarm.behaviour_mut().subscribe(&topic) {
                     warn!("Failed to subscribe to {}: {:?}", topic, e);
                 } else {
+                    self.idle_topics.remove(&topic);
+                    self.topic_activity.insert(topic.clone(), Instant::now());
                     self.subscribed_topics.insert(topic.clone());
                     let _ = self.event_tx.send(NetworkEvent::Subscribed { topic });
                 }
@@ -868,66 +3302,128 @@ impl NetworkService {
                 if let Err(e) = self.swarm.behaviour_mut().unsubscribe(&topic) {
                     warn!("Failed to unsubscribe from {}: {:?}", topic, e);
                 } else {
+                    self.idle_topics.remove(&topic);
+                    self.topic_activity.remove(&topic);
                     self.subscribed_topics.remove(&topic);
                     let _ = self.event_tx.send(NetworkEvent::Unsubscribed { topic });
                 }
             }
 
             NetworkCommand::Publish { topic, data } => {
-                // Log mesh status before publishing for debugging
-                let mesh_peers = self.swarm.behaviour().mesh_peers(&topic);
-                let all_peers = self.swarm.behaviour().all_peers_on_topic(&topic);
+                self.publish_message(&topic, data);
+            }
 
-                info!(
-                    "Publishing to '{}' | {} bytes | Mesh peers: {} | Total subscribers: {}",
+            NetworkCommand::PublishReliable {
+                topic,
+                data,
+                deadline,
+            } => {
+                let now = Instant::now();
+                self.try_reliable_publish(PendingPublish {
                     topic,
-                    data.len(),
-                    mesh_peers.len(),
-                    all_peers.len()
-                );
+                    data,
+                    attempts: 0,
+                    next_attempt: now,
+                    deadline: now + deadline.min(PUBLISH_DEADLINE_MAX),
+                });
+            }
 
-                if mesh_peers.is_empty() && !all_peers.is_empty() {
-                    warn!(
-                        "Warning: Publishing to '{}' with 0 mesh peers but {} subscribed peers. \
-                        Mesh may not have formed yet (check mesh_n/mesh_n_low config).",
-                        topic,
-                        all_peers.len()
-                    );
+            NetworkCommand::PutRecord {
+                key,
+                value,
+                response,
+            } => match self.swarm.behaviour_mut().put_record(key, value) {
+                Ok(query_id) => {
+                    if let Some(response) = response {
+                        self.pending_puts.insert(query_id, response);
+                    }
+                }
+                Err(e) => {
+                    warn!("Failed to put DHT record: {:?}", e);
+                    if let Some(response) = response {
+                        let _ = response.send(Err(e));
+                    }
                 }
+            },
 
-                if !mesh_peers.is_empty() {
-                    debug!("Mesh peers for '{}': {:?}", topic, mesh_peers);
+            NetworkCommand::GetRecord { key, response } => {
+                let query_id = self.swarm.behaviour_mut().get_record(key);
+                if let Some(response) = response {
+                    self.pending_gets.insert(query_id, response);
                 }
+            }
 
-                match self.swarm.behaviour_mut().publish(&topic, data.clone()) {
-                    Ok(msg_id) => {
-                        info!(
-                            "Published message {} to '{}' via {} mesh peers",
-                            msg_id,
-                            topic,
-                            mesh_peers.len()
-                        );
-                        let mut stats = self.stats.write();
-                        stats.messages_sent += 1;
-                        stats.bytes_sent += data.len() as u64;
-                    }
-                    Err(e) => {
-                        warn!(
-                            "Failed to publish to '{}': {:?} | Mesh peers: {} | Consider waiting for mesh formation",
-                            topic, e, mesh_peers.len()
-                        );
-                    }
+            NetworkCommand::ProvideContent { content } => {
+                let id = content.id;
+                if let Err(e) = self.swarm.behaviour_mut().start_providing(&id) {
+                    warn!("Failed to announce {} as provided: {:?}", id, e);
                 }
+                self.provided_content.insert(id);
+                let store = self.content_store.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = store.put_content(&content).await {
+                        warn!("Failed to store provided content {}: {}", id, e);
+                    }
+                });
             }
 
-            NetworkCommand::PutRecord { key, value } => {
-                if let Err(e) = self.swarm.behaviour_mut().put_record(key, value) {
-                    warn!("Failed to put DHT record: {:?}", e);
+            NetworkCommand::StopProviding { id } => {
+                self.swarm.behaviour_mut().stop_providing(&id);
+                if self.provided_content.remove(&id) {
+                    debug!("Stopped providing {}", id);
                 }
+                let store = self.content_store.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = store.remove_content(&id).await {
+                        warn!("Failed to remove {} from the content store: {}", id, e);
+                    }
+                });
             }
 
-            NetworkCommand::GetRecord { key } => {
-                self.swarm.behaviour_mut().get_record(key);
+            NetworkCommand::PinContent {
+                id,
+                pinned,
+                response,
+            } => {
+                let store = self.content_store.clone();
+                tokio::spawn(async move {
+                    let result = store
+                        .set_pinned(&id, pinned)
+                        .await
+                        .map_err(|e| NetworkError::Internal(e.to_string()));
+                    let _ = response.send(result);
+                });
+            }
+
+            NetworkCommand::FindProviders { id, response } => {
+                let query_id = self.swarm.behaviour_mut().get_providers(&id);
+                self.pending_providers
+                    .insert(query_id, PendingProviders::new(response));
+            }
+
+            NetworkCommand::ProbeProviders {
+                id,
+                timeout,
+                enough,
+                response,
+            } => self.probe_providers(id, timeout, enough, response),
+
+            NetworkCommand::RequestContent {
+                peer_id,
+                request,
+                response,
+            } => {
+                let id = request.id;
+                let request_id = self
+                    .swarm
+                    .behaviour_mut()
+                    .content
+                    .send_request(&peer_id, request);
+                self.pending_content.insert(request_id, (id, response));
+            }
+
+            NetworkCommand::GetReputation { peer_id, response } => {
+                let _ = response.send(self.peer_reputation(&peer_id));
             }
 
             NetworkCommand::GetPeers { response } => {
@@ -936,10 +3432,61 @@ impl NetworkService {
             }
 
             NetworkCommand::GetStats { response } => {
-                let stats = self.stats.read().clone();
+                let mut stats = self.stats.read().clone();
+                stats.publish_rate = self.publish_limiter.lock().usage(Instant::now());
                 let _ = response.send(stats);
             }
 
+            NetworkCommand::GetNatStatus { response } => {
+                let _ = response.send(self.nat_status);
+            }
+
+            NetworkCommand::GetMeshStatus { topic, response } => {
+                let _ = response.send(self.current_mesh_status(&topic));
+            }
+
+            NetworkCommand::GetSubscribedTopics { response } => {
+                let mut topics: Vec<_> = self.subscribed_topics.iter().cloned().collect();
+                topics.sort();
+                let _ = response.send(topics);
+            }
+
+            NetworkCommand::GetPeerQualities { response } => {
+                let _ = response.send(self.peer_manager.qualities());
+            }
+
+            NetworkCommand::BanPeer {
+                peer_id,
+                duration,
+                reason,
+            } => self.ban_peer(peer_id, duration, &reason),
+
+            NetworkCommand::UnbanPeer { peer_id, response } => {
+                let lifted = self.peer_manager.unban(&peer_id).is_some();
+                if lifted {
+                    info!("Lifted ban on peer {}", peer_id);
+                    let _ = self.event_tx.send(NetworkEvent::PeerUnbanned { peer_id });
+                }
+                if let Some(response) = response {
+                    let _ = response.send(lifted);
+                }
+            }
+
+            NetworkCommand::GetBannedPeers { response } => {
+                let _ = response.send(self.peer_manager.bans());
+            }
+
+            NetworkCommand::AddExplicitPeer { peer_id, address } => {
+                self.add_explicit_peer(peer_id, address);
+            }
+
+            NetworkCommand::RemoveExplicitPeer { peer_id } => {
+                if self.explicit_peers.remove(&peer_id).is_some() {
+                    info!("Removed explicit peer {}", peer_id);
+                }
+                self.swarm.behaviour_mut().remove_explicit_peer(&peer_id);
+            }
+
             // Partition testing commands
             NetworkCommand::BlockPeer { peer_id } => {
                 self.blocked_peers.insert(peer_id);
@@ -963,6 +3510,19 @@ impl NetworkService {
                 info!("Shutdown requested");
                 return false;
             }
+
+            NetworkCommand::ShutdownGraceful { response } => {
+                let peers = self.peer_manager.connected_peers();
+                info!(
+                    "Graceful shutdown requested, disconnecting {} peers",
+                    peers.len()
+                );
+                for peer_id in peers {
+                    let _ = self.swarm.disconnect_peer_id(peer_id);
+                }
+                let _ = response.send(());
+                return false;
+            }
         }
 
         true
@@ -975,6 +3535,7 @@ impl NetworkService {
 /// - 10.255.255.254 (WSL magic IP)
 /// - 172.17.x.x (Docker bridge)
 /// - 172.28.x.x, 172.29.x.x (WSL internal bridges)
+/// - IPv6 link-local addresses without a zone (undialable off-link)
 ///
 /// Allows:
 /// - 127.0.0.1 (localhost)
@@ -983,6 +3544,10 @@ impl NetworkService {
 fn is_routable_address(addr: &Multiaddr) -> bool {
     use std::net::Ipv4Addr;
 
+    if transport::is_unscoped_link_local(addr) {
+        return false;
+    }
+
     for protocol in addr.iter() {
         if let libp2p::multiaddr::Protocol::Ip4(ip) = protocol {
             // Always allow localhost
@@ -1010,6 +3575,231 @@ fn is_routable_address(addr: &Multiaddr) -> bool {
         }
     }
 
-    // Allow non-IPv4 addresses (IPv6, QUIC, etc.)
+    // Allow other non-IPv4 addresses (IPv6, DNS, etc.)
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::test_utils::MockNetworkHandle;
+    use super::*;
+
+    /// A handle whose commands the test answers itself
+    fn scripted_handle() -> (NetworkHandle, mpsc::Receiver<NetworkCommand>) {
+        let (command_tx, command_rx) = mpsc::channel(16);
+        let handle = NetworkHandle {
+            command_tx,
+            local_peer_id: PeerId::random(),
+            alive: Arc::new(AtomicBool::new(true)),
+            publish_limiter: Arc::default(),
+        };
+        (handle, command_rx)
+    }
+
+    #[tokio::test]
+    async fn test_network_handle_forwards_commands() {
+        let mock = MockNetworkHandle::new();
+        let handle = mock.handle();
+        let other = handle.clone();
+
+        handle.subscribe("chat").await.unwrap();
+        other.subscribe("news").await.unwrap();
+        handle.unsubscribe("news").await.unwrap();
+        let address: Multiaddr = "/ip4/127.0.0.1/tcp/9000".parse().unwrap();
+        handle.dial(address.clone()).await.unwrap();
+        handle
+            .put_record_detached(b"key".to_vec(), b"value".to_vec())
+            .await
+            .unwrap();
+
+        // A query is answered after every earlier command
+        assert_eq!(handle.subscribed_topics().await.unwrap(), vec!["chat"]);
+        assert_eq!(mock.dialed(), vec![address]);
+        assert_eq!(
+            handle.get_record(b"key".to_vec()).await.unwrap(),
+            Some(b"value".to_vec())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_network_handle_publish_reliable() {
+        let (handle, mut commands) = scripted_handle();
+
+        handle
+            .publish_reliable("test-topic", b"data".to_vec(), Duration::from_secs(30))
+            .await
+            .unwrap();
+
+        match commands.recv().await.unwrap() {
+            NetworkCommand::PublishReliable {
+                topic,
+                data,
+                deadline,
+            } => {
+                assert_eq!(topic, "test-topic");
+                assert_eq!(data, b"data");
+                assert_eq!(deadline, Duration::from_secs(30));
+            }
+            other => panic!("expected PublishReliable, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_publish_retry_backoff() {
+        assert_eq!(publish_retry_backoff(1), PUBLISH_RETRY_INITIAL);
+        assert_eq!(publish_retry_backoff(2), PUBLISH_RETRY_INITIAL * 2);
+        assert_eq!(publish_retry_backoff(3), PUBLISH_RETRY_INITIAL * 4);
+        assert_eq!(publish_retry_backoff(100), PUBLISH_RETRY_MAX);
+    }
+
+    #[test]
+    fn test_dial_addresses_prefer_best() {
+        let configured: Multiaddr = "/ip4/10.0.0.1/tcp/9000".parse().unwrap();
+        let best: Multiaddr = "/ip4/192.168.1.5/tcp/9000".parse().unwrap();
+
+        assert_eq!(
+            dial_addresses(Some(best.clone()), &configured),
+            vec![best, configured.clone()]
+        );
+        assert_eq!(
+            dial_addresses(Some(configured.clone()), &configured),
+            vec![configured.clone()]
+        );
+        assert_eq!(dial_addresses(None, &configured), vec![configured]);
+    }
+
+    #[tokio::test]
+    async fn test_network_handle_provider_probe() {
+        let (handle, mut commands) = scripted_handle();
+        let content_id = ContentId::hash(b"replicated");
+        let provider = PeerId::random();
+
+        tokio::spawn(async move {
+            while let Some(command) = commands.recv().await {
+                if let NetworkCommand::ProbeProviders {
+                    id,
+                    timeout,
+                    enough,
+                    response,
+                } = command
+                {
+                    assert_eq!(id, content_id);
+                    assert_eq!(timeout, PROVIDER_PROBE_TIMEOUT);
+                    let found = match enough {
+                        Some(enough) => vec![provider; enough],
+                        None => vec![provider, PeerId::random()],
+                    };
+                    let _ = response.send(Ok(found));
+                }
+            }
+        });
+
+        assert!(handle.has_providers(content_id).await.unwrap());
+        assert_eq!(handle.provider_count(content_id).await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_network_handle_pins_provided_content() {
+        let mock = MockNetworkHandle::new();
+        let content = Content::text("keep me");
+
+        assert!(!mock.pin_content(content.id).await.unwrap());
+        mock.provide_content(content.clone()).await.unwrap();
+        assert!(mock.pin_content(content.id).await.unwrap());
+        assert!(mock.unpin_content(content.id).await.unwrap());
+
+        mock.stop_providing(content.id).await.unwrap();
+        assert!(!mock.pin_content(content.id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_network_handle_nat_and_mesh_status() {
+        let (handle, mut commands) = scripted_handle();
+
+        tokio::spawn(async move {
+            while let Some(command) = commands.recv().await {
+                match command {
+                    NetworkCommand::GetNatStatus { response } => {
+                        let _ = response.send(NatStatus::Private);
+                    }
+                    NetworkCommand::GetMeshStatus { topic, response } => {
+                        assert_eq!(topic, "chat");
+                        let _ = response.send(MeshStatus {
+                            mesh_peers: 0,
+                            all_peers: 3,
+                        });
+                    }
+                    other => panic!("unexpected command: {:?}", other),
+                }
+            }
+        });
+
+        assert_eq!(handle.nat_status().await.unwrap(), NatStatus::Private);
+        assert_eq!(NatStatus::default(), NatStatus::Unknown);
+
+        let status = handle.mesh_status("chat").await.unwrap();
+        assert_eq!(status.all_peers, 3);
+        assert!(!status.is_formed());
+    }
+
+    #[tokio::test]
+    async fn test_network_handle_error_on_closed_channel() {
+        let (handle, commands) = scripted_handle();
+        drop(commands);
+
+        // Still flagged alive, so reported as a channel failure
+        assert!(matches!(
+            handle.subscribe("topic").await,
+            Err(NetworkError::Channel(_))
+        ));
+    }
+
+    #[cfg(feature = "univrs-compat")]
+    #[tokio::test]
+    async fn test_network_handle_detects_stopped_service() {
+        let keypair = libp2p::identity::Keypair::generate_ed25519();
+        let (service, handle, _events, _enr_bridge) =
+            NetworkService::new(keypair, NetworkConfig::local_test(0)).unwrap();
+        assert!(handle.is_alive());
+
+        // Simulate the service task dying without processing any commands
+        tokio::spawn(async move {
+            let _service = service;
+            panic!("network service crashed");
+        })
+        .await
+        .unwrap_err();
+
+        assert!(!handle.is_alive());
+        assert!(matches!(
+            handle.subscribe("topic").await,
+            Err(NetworkError::ServiceUnavailable)
+        ));
+        assert!(matches!(
+            handle.get_peers().await,
+            Err(NetworkError::ServiceUnavailable)
+        ));
+    }
+
+    #[test]
+    fn test_routable_addresses() {
+        let routable = |addr: &str| is_routable_address(&addr.parse().unwrap());
+
+        // Localhost, standard private ranges and public addresses
+        assert!(routable("/ip4/127.0.0.1/tcp/9000"));
+        assert!(routable("/ip4/192.168.1.1/tcp/9000"));
+        assert!(routable("/ip4/10.0.0.1/tcp/9000"));
+        assert!(routable("/ip4/8.8.8.8/tcp/9000"));
+
+        // Docker and WSL bridges
+        assert!(!routable("/ip4/172.17.0.1/tcp/9000"));
+        assert!(!routable("/ip4/10.255.255.254/tcp/9000"));
+        assert!(!routable("/ip4/172.28.0.1/tcp/9000"));
+        assert!(!routable("/ip4/172.29.0.1/tcp/9000"));
+
+        // IPv6 link-local addresses only with a zone
+        assert!(routable("/ip6/::1/tcp/9000"));
+        assert!(!routable("/ip6/fe80::1/tcp/9000"));
+        assert!(routable("/ip6zone/eth0/ip6/fe80::1/tcp/9000"));
+    }
+}
@@ -0,0 +1,483 @@
+//! A stand-in network for testing code built on [`NetworkHandle`]
+//!
+//! [`MockNetworkHandle`] hands out a real `NetworkHandle` whose commands
+//! are answered by an in-memory task instead of a libp2p swarm. Publishes
+//! are recorded so tests can assert on them, and tests inject the
+//! [`NetworkEvent`]s the code under test would otherwise receive from the
+//! service.
+//!
+//! ```rust,ignore
+//! use mycelial_network::test_utils::MockNetworkHandle;
+//!
+//! #[tokio::test]
+//! async fn test_publishes_greeting() {
+//!     let mock = MockNetworkHandle::new();
+//!     greet(&mock.handle()).await;
+//!     mock.assert_published("/mycelial/1.0.0/chat", b"hello");
+//! }
+//! ```
+
+use super::{Liveness, NetworkCommand, NetworkHandle};
+use crate::content::ContentResponse;
+use crate::event::{MeshStatus, NatStatus, NetworkEvent, NetworkStats};
+use crate::peer::PeerBan;
+use crate::rate_limit::PublishLimiter;
+use libp2p::gossipsub::MessageId;
+use libp2p::PeerId;
+use mycelial_core::content::ContentId;
+use parking_lot::Mutex;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::ops::Deref;
+use std::sync::Arc;
+use tokio::sync::{broadcast, mpsc};
+
+/// A message published through a [`MockNetworkHandle`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublishedMessage {
+    /// Topic it was published to
+    pub topic: String,
+    /// Message bytes
+    pub data: Vec<u8>,
+}
+
+/// What the mock network has seen and will answer with
+#[derive(Default)]
+struct MockState {
+    published: Vec<PublishedMessage>,
+    subscribed: BTreeSet<String>,
+    records: HashMap<Vec<u8>, Vec<u8>>,
+    peers: Vec<PeerId>,
+    dialed: Vec<libp2p::Multiaddr>,
+    bans: HashMap<PeerId, PeerBan>,
+    provided: HashSet<ContentId>,
+}
+
+/// A [`NetworkHandle`] backed by an in-memory network
+///
+/// Dereferences to the handle, so it can be used wherever the code under
+/// test calls handle methods. Commands are answered as if by a node with no
+/// connections: DHT records and bans round-trip through local maps, provided
+/// content can be pinned but is never found, and peers are whatever [`set_peers`](Self::set_peers)
+/// says.
+///
+/// Must be created inside a Tokio runtime.
+pub struct MockNetworkHandle {
+    handle: NetworkHandle,
+    event_tx: broadcast::Sender<NetworkEvent>,
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockNetworkHandle {
+    /// Create a mock network with a random local peer ID
+    pub fn new() -> Self {
+        Self::with_peer_id(PeerId::random())
+    }
+
+    /// Create a mock network with the given local peer ID
+    pub fn with_peer_id(local_peer_id: PeerId) -> Self {
+        let (command_tx, command_rx) = mpsc::channel(256);
+        let (event_tx, _) = broadcast::channel(1024);
+        let state = Arc::new(Mutex::new(MockState::default()));
+
+        let liveness = Liveness::new();
+        let handle = NetworkHandle {
+            command_tx,
+            local_peer_id,
+            alive: liveness.flag(),
+            publish_limiter: Arc::new(Mutex::new(PublishLimiter::default())),
+        };
+        tokio::spawn(run_mock(command_rx, state.clone(), liveness));
+
+        Self {
+            handle,
+            event_tx,
+            state,
+        }
+    }
+
+    /// A handle to pass to the code under test
+    pub fn handle(&self) -> NetworkHandle {
+        self.handle.clone()
+    }
+
+    /// Receive the events injected with [`inject_event`](Self::inject_event)
+    ///
+    /// Stands in for the event receiver `NetworkService::new` returns.
+    pub fn events(&self) -> broadcast::Receiver<NetworkEvent> {
+        self.event_tx.subscribe()
+    }
+
+    /// Deliver an event to every receiver from [`events`](Self::events)
+    ///
+    /// Returns the number of receivers it reached.
+    pub fn inject_event(&self, event: NetworkEvent) -> usize {
+        self.event_tx.send(event).unwrap_or(0)
+    }
+
+    /// Deliver a gossipsub message on `topic` as if `source` had sent it
+    pub fn inject_message(
+        &self,
+        topic: impl Into<String>,
+        source: Option<PeerId>,
+        data: Vec<u8>,
+    ) -> usize {
+        let message_id = MessageId::from(mycelial_core::message::payload_id(&data));
+        self.inject_event(NetworkEvent::MessageReceived {
+            message_id,
+            topic: topic.into(),
+            source,
+            data,
+            timestamp: chrono::Utc::now(),
+        })
+    }
+
+    /// Peers returned by `get_peers`
+    pub fn set_peers(&self, peers: Vec<PeerId>) {
+        self.state.lock().peers = peers;
+    }
+
+    /// Everything published so far, oldest first
+    pub fn published(&self) -> Vec<PublishedMessage> {
+        self.state.lock().published.clone()
+    }
+
+    /// Payloads published to `topic`, oldest first
+    pub fn published_to(&self, topic: &str) -> Vec<Vec<u8>> {
+        self.state
+            .lock()
+            .published
+            .iter()
+            .filter(|m| m.topic == topic)
+            .map(|m| m.data.clone())
+            .collect()
+    }
+
+    /// Forget the messages published so far
+    pub fn clear_published(&self) {
+        self.state.lock().published.clear();
+    }
+
+    /// Topics currently subscribed to, sorted
+    pub fn subscriptions(&self) -> Vec<String> {
+        self.state.lock().subscribed.iter().cloned().collect()
+    }
+
+    /// Addresses dialed so far
+    pub fn dialed(&self) -> Vec<libp2p::Multiaddr> {
+        self.state.lock().dialed.clone()
+    }
+
+    /// Panic unless `data` was published to `topic`
+    #[track_caller]
+    pub fn assert_published(&self, topic: &str, data: &[u8]) {
+        let published = self.published();
+        assert!(
+            published.iter().any(|m| m.topic == topic && m.data == data),
+            "expected {} bytes published to '{}', got {:?}",
+            data.len(),
+            topic,
+            summarize(&published)
+        );
+    }
+
+    /// Panic unless a message matching `predicate` was published to `topic`
+    ///
+    /// Useful when the payload isn't byte-for-byte predictable, e.g. a
+    /// serialized message carrying a timestamp.
+    #[track_caller]
+    pub fn assert_published_where(&self, topic: &str, predicate: impl Fn(&[u8]) -> bool) {
+        let published = self.published();
+        assert!(
+            published
+                .iter()
+                .any(|m| m.topic == topic && predicate(&m.data)),
+            "expected a matching message published to '{}', got {:?}",
+            topic,
+            summarize(&published)
+        );
+    }
+
+    /// Panic if anything was published to `topic`
+    #[track_caller]
+    pub fn assert_nothing_published_to(&self, topic: &str) {
+        let count = self.published_to(topic).len();
+        assert_eq!(
+            count, 0,
+            "expected nothing published to '{}', got {} messages",
+            topic, count
+        );
+    }
+}
+
+impl Default for MockNetworkHandle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for MockNetworkHandle {
+    type Target = NetworkHandle;
+
+    fn deref(&self) -> &NetworkHandle {
+        &self.handle
+    }
+}
+
+/// Topic and size of each published message, for assertion failures
+fn summarize(published: &[PublishedMessage]) -> Vec<(String, usize)> {
+    published
+        .iter()
+        .map(|m| (m.topic.clone(), m.data.len()))
+        .collect()
+}
+
+/// Answer commands until shutdown or until every handle is dropped
+async fn run_mock(
+    mut command_rx: mpsc::Receiver<NetworkCommand>,
+    state: Arc<Mutex<MockState>>,
+    liveness: Liveness,
+) {
+    let mut shutdown_ack = None;
+    while let Some(command) = command_rx.recv().await {
+        let mut mock = state.lock();
+        match command {
+            NetworkCommand::Publish { topic, data }
+            | NetworkCommand::PublishReliable { topic, data, .. } => {
+                mock.published.push(PublishedMessage { topic, data });
+            }
+            NetworkCommand::Subscribe { topic } => {
+                mock.subscribed.insert(topic);
+            }
+            NetworkCommand::Unsubscribe { topic } => {
+                mock.subscribed.remove(&topic);
+            }
+            NetworkCommand::Dial { address } => mock.dialed.push(address),
+            NetworkCommand::PutRecord {
+                key,
+                value,
+                response,
+            } => {
+                mock.records.insert(key, value);
+                if let Some(response) = response {
+                    let _ = response.send(Ok(()));
+                }
+            }
+            NetworkCommand::GetRecord { key, response } => {
+                if let Some(response) = response {
+                    let _ = response.send(Ok(mock.records.get(&key).cloned()));
+                }
+            }
+            NetworkCommand::FindProviders { response, .. }
+            | NetworkCommand::ProbeProviders { response, .. } => {
+                let _ = response.send(Ok(Vec::new()));
+            }
+            NetworkCommand::RequestContent { response, .. } => {
+                let _ = response.send(Ok(ContentResponse::NotFound));
+            }
+            NetworkCommand::GetReputation { response, .. } => {
+                let _ = response.send(None);
+            }
+            NetworkCommand::GetPeers { response } => {
+                let _ = response.send(mock.peers.clone());
+            }
+            NetworkCommand::GetStats { response } => {
+                let _ = response.send(NetworkStats {
+                    connected_peers: mock.peers.len(),
+                    messages_sent: mock.published.len() as u64,
+                    subscribed_topics: mock.subscribed.len(),
+                    ..NetworkStats::default()
+                });
+            }
+            NetworkCommand::GetNatStatus { response } => {
+                let _ = response.send(NatStatus::Unknown);
+            }
+            NetworkCommand::GetMeshStatus { response, .. } => {
+                let _ = response.send(MeshStatus::default());
+            }
+            NetworkCommand::GetSubscribedTopics { response } => {
+                let _ = response.send(mock.subscribed.iter().cloned().collect());
+            }
+            NetworkCommand::GetPeerQualities { response } => {
+                let _ = response.send(HashMap::new());
+            }
+            NetworkCommand::BanPeer {
+                peer_id,
+                duration,
+                reason,
+            } => {
+                mock.bans
+                    .insert(peer_id, PeerBan::new(peer_id, duration, reason));
+            }
+            NetworkCommand::UnbanPeer { peer_id, response } => {
+                let banned = mock.bans.remove(&peer_id).is_some();
+                if let Some(response) = response {
+                    let _ = response.send(banned);
+                }
+            }
+            NetworkCommand::GetBannedPeers { response } => {
+                let now = chrono::Utc::now();
+                let mut bans: Vec<_> = mock
+                    .bans
+                    .values()
+                    .filter(|ban| ban.is_active(now))
+                    .cloned()
+                    .collect();
+                bans.sort_by(|a, b| b.banned_at.cmp(&a.banned_at));
+                let _ = response.send(bans);
+            }
+            NetworkCommand::Shutdown => break,
+            NetworkCommand::ShutdownGraceful { response } => {
+                shutdown_ack = Some(response);
+                break;
+            }
+            NetworkCommand::ProvideContent { content } => {
+                mock.provided.insert(content.id);
+            }
+            NetworkCommand::StopProviding { id } => {
+                mock.provided.remove(&id);
+            }
+            NetworkCommand::PinContent { id, response, .. } => {
+                let _ = response.send(Ok(mock.provided.contains(&id)));
+            }
+            NetworkCommand::Disconnect { .. }
+            | NetworkCommand::AddExplicitPeer { .. }
+            | NetworkCommand::RemoveExplicitPeer { .. }
+            | NetworkCommand::BlockPeer { .. }
+            | NetworkCommand::UnblockPeer { .. }
+            | NetworkCommand::UnblockAllPeers => {}
+        }
+    }
+
+    // Handles see the network as stopped before the shutdown is acknowledged
+    drop(liveness);
+    if let Some(ack) = shutdown_ack {
+        let _ = ack.send(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_mock_records_publishes() {
+        let mock = MockNetworkHandle::new();
+        let handle = mock.handle();
+
+        handle.subscribe("chat").await.unwrap();
+        handle.publish("chat", b"hello".to_vec()).await.unwrap();
+        mock.publish("other", b"bye".to_vec()).await.unwrap();
+        mock.publish_reliable("chat", b"again".to_vec(), Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        // Commands are processed in order, so a query sees earlier publishes
+        assert_eq!(handle.subscribed_topics().await.unwrap(), vec!["chat"]);
+        assert_eq!(mock.subscriptions(), vec!["chat"]);
+        mock.assert_published("chat", b"hello");
+        mock.assert_published("chat", b"again");
+        mock.assert_published_where("other", |data| data.starts_with(b"by"));
+        mock.assert_nothing_published_to("missing");
+        assert_eq!(
+            mock.published_to("chat"),
+            vec![b"hello".to_vec(), b"again".to_vec()]
+        );
+
+        handle
+            .put_record(b"k".to_vec(), b"v".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(
+            handle.get_record(b"k".to_vec()).await.unwrap(),
+            Some(b"v".to_vec())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_publish_rate_limited() {
+        use crate::config::{NetworkConfig, RateLimit};
+        use crate::error::NetworkError;
+
+        let mock = MockNetworkHandle::new();
+        let handle = mock.handle();
+        let mut config = NetworkConfig::default();
+        config
+            .topic_rate_limits
+            .insert("chat".to_string(), RateLimit::new(1.0, 2));
+        *handle.publish_limiter.lock() = PublishLimiter::new(&config);
+
+        handle.publish("chat", b"one".to_vec()).await.unwrap();
+        handle
+            .publish_reliable("chat", b"two".to_vec(), Duration::from_secs(5))
+            .await
+            .unwrap();
+        match handle.publish("chat", b"three".to_vec()).await {
+            Err(NetworkError::RateLimited {
+                topic,
+                retry_after_ms,
+            }) => {
+                assert_eq!(topic, "chat");
+                assert!(retry_after_ms > 0 && retry_after_ms <= 1000);
+            }
+            other => panic!("expected RateLimited, got {:?}", other),
+        }
+        // Other topics aren't limited
+        handle.publish("other", b"free".to_vec()).await.unwrap();
+
+        // The refused publish never reached the network
+        assert_eq!(
+            mock.published_to("chat"),
+            vec![b"one".to_vec(), b"two".to_vec()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mock_injects_events_and_shuts_down() {
+        let mock = MockNetworkHandle::new();
+        let mut events = mock.events();
+
+        let source = PeerId::random();
+        assert_eq!(mock.inject_message("chat", Some(source), b"hi".to_vec()), 1);
+        match events.recv().await.unwrap() {
+            NetworkEvent::MessageReceived {
+                topic,
+                source: from,
+                data,
+                ..
+            } => {
+                assert_eq!(topic, "chat");
+                assert_eq!(from, Some(source));
+                assert_eq!(data, b"hi");
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        mock.shutdown_graceful(std::time::Duration::from_secs(1))
+            .await
+            .unwrap();
+        assert!(!mock.is_alive());
+    }
+
+    #[tokio::test]
+    async fn test_network_health() {
+        use mycelial_core::health::{HealthCheck, HealthStatus};
+
+        let mock = MockNetworkHandle::new();
+        assert_eq!(
+            mock.handle().health().await,
+            HealthStatus::degraded("no connected peers")
+        );
+
+        mock.set_peers(vec![PeerId::random()]);
+        assert_eq!(mock.handle().health().await, HealthStatus::Ok);
+
+        mock.shutdown_graceful(std::time::Duration::from_secs(1))
+            .await
+            .unwrap();
+        assert!(matches!(
+            mock.handle().health().await,
+            HealthStatus::Down(_)
+        ));
+    }
+}
@@ -1,22 +1,41 @@
 //! Transport layer configuration and creation
 //!
 //! This module provides transport configuration for TCP, QUIC, and WebSocket
-//! with Noise encryption and Yamux multiplexing.
+//! with Noise encryption and Yamux multiplexing. With the `webrtc` feature,
+//! nodes can also listen on `/webrtc-direct` addresses that browsers dial
+//! without a relay.
 
-use libp2p::{core::upgrade, identity::Keypair, noise, yamux, PeerId, Transport};
+use libp2p::{
+    core::{transport::timeout::TransportTimeout, upgrade},
+    identity::Keypair,
+    multiaddr::Protocol,
+    noise, yamux, PeerId, Transport,
+};
 use std::time::Duration;
+use tracing::{debug, warn};
 
 use crate::error::{NetworkError, Result};
 
 /// Transport configuration
+///
+/// The defaults suit ordinary internet links. Raise `handshake_timeout` for
+/// high-latency paths (e.g. peers behind a LoRa gateway) so slow but valid
+/// handshakes are not cut off.
 #[derive(Debug, Clone)]
 pub struct TransportConfig {
     /// Enable TCP transport
     pub enable_tcp: bool,
     /// Enable QUIC transport
     pub enable_quic: bool,
-    /// Connection timeout
+    /// Enable the WebRTC direct transport (requires the `webrtc` feature)
+    pub enable_webrtc: bool,
+    /// Time allowed to establish the TCP connection
     pub connection_timeout: Duration,
+    /// Time allowed for the security and multiplexer handshake once
+    /// connected (Noise + Yamux over TCP, the QUIC handshake)
+    pub handshake_timeout: Duration,
+    /// How long a connection may go without traffic before it is closed
+    pub idle_connection_timeout: Duration,
     /// Maximum number of inbound streams per connection
     pub max_inbound_streams: usize,
     /// Maximum number of outbound streams per connection
@@ -28,13 +47,49 @@ impl Default for TransportConfig {
         Self {
             enable_tcp: true,
             enable_quic: true,
+            enable_webrtc: false,
             connection_timeout: Duration::from_secs(30),
+            handshake_timeout: Duration::from_secs(20),
+            idle_connection_timeout: Duration::from_secs(30),
             max_inbound_streams: 256,
             max_outbound_streams: 256,
         }
     }
 }
 
+impl TransportConfig {
+    /// Check that at least one transport is enabled and timeouts are non-zero
+    pub fn validate(&self) -> Result<()> {
+        if !self.enable_tcp && !self.enable_quic {
+            return Err(NetworkError::Config(
+                "At least one transport (TCP or QUIC) must be enabled".into(),
+            ));
+        }
+        if self.enable_webrtc && !cfg!(feature = "webrtc") {
+            return Err(NetworkError::Config(
+                "WebRTC transport requires the `webrtc` feature".into(),
+            ));
+        }
+        if self.connection_timeout.is_zero()
+            || self.handshake_timeout.is_zero()
+            || self.idle_connection_timeout.is_zero()
+        {
+            return Err(NetworkError::Config(
+                "Transport timeouts must be non-zero".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn quic_config(&self, keypair: &Keypair) -> libp2p::quic::Config {
+        let mut quic_config = libp2p::quic::Config::new(keypair);
+        quic_config.handshake_timeout = self.handshake_timeout;
+        quic_config.max_idle_timeout =
+            u32::try_from(self.idle_connection_timeout.as_millis()).unwrap_or(u32::MAX);
+        quic_config
+    }
+}
+
 /// Create a TCP transport with Noise encryption and Yamux multiplexing
 pub fn create_tcp_transport(
     _keypair: &Keypair,
@@ -48,15 +103,106 @@ pub fn create_tcp_transport(
 /// Create the full transport stack
 ///
 /// This creates a transport that supports:
-/// - TCP with Noise encryption and Yamux multiplexing
+/// - TCP with Noise encryption and Yamux multiplexing (if enabled)
 /// - QUIC (if enabled)
+/// - WebRTC direct (if enabled and built with the `webrtc` feature)
 /// - DNS resolution
+///
+/// Returns a configuration error if neither TCP nor QUIC is enabled or a
+/// timeout is zero.
 pub fn create_transport(
     keypair: &Keypair,
     config: &TransportConfig,
 ) -> Result<libp2p::core::transport::Boxed<(PeerId, libp2p::core::muxing::StreamMuxerBox)>> {
-    // Create TCP transport
-    let tcp = libp2p::tcp::tokio::Transport::new(libp2p::tcp::Config::default().nodelay(true));
+    config.validate()?;
+
+    let transport = create_tcp_quic_transport(keypair, config)?;
+
+    #[cfg(feature = "webrtc")]
+    if config.enable_webrtc {
+        return add_webrtc_transport(transport, keypair);
+    }
+
+    Ok(transport)
+}
+
+/// Create the full transport stack plus relayed (`/p2p-circuit`) connections
+///
+/// `relay` is the transport half of [`libp2p::relay::client::new`]; relayed
+/// connections get the same Noise and Yamux upgrade as TCP.
+pub fn create_transport_with_relay(
+    keypair: &Keypair,
+    config: &TransportConfig,
+    relay: libp2p::relay::client::Transport,
+) -> Result<libp2p::core::transport::Boxed<(PeerId, libp2p::core::muxing::StreamMuxerBox)>> {
+    let transport = create_transport(keypair, config)?;
+
+    let noise_config = noise::Config::new(keypair)
+        .map_err(|e| NetworkError::Config(format!("Noise config error: {:?}", e)))?;
+    let relayed = relay
+        .upgrade(upgrade::Version::V1)
+        .authenticate(noise_config)
+        .multiplex(yamux::Config::default())
+        .timeout(config.handshake_timeout);
+
+    Ok(transport
+        .or_transport(relayed)
+        .map(|either, _| match either {
+            futures::future::Either::Left(output) => output,
+            futures::future::Either::Right((peer_id, muxer)) => {
+                (peer_id, libp2p::core::muxing::StreamMuxerBox::new(muxer))
+            }
+        })
+        .boxed())
+}
+
+/// Add WebRTC direct alongside an existing transport
+///
+/// The DTLS certificate is generated per process, so the `/certhash` in the
+/// advertised address changes on restart.
+#[cfg(feature = "webrtc")]
+fn add_webrtc_transport(
+    transport: libp2p::core::transport::Boxed<(PeerId, libp2p::core::muxing::StreamMuxerBox)>,
+    keypair: &Keypair,
+) -> Result<libp2p::core::transport::Boxed<(PeerId, libp2p::core::muxing::StreamMuxerBox)>> {
+    let certificate = libp2p_webrtc::tokio::Certificate::generate(&mut rand::thread_rng())
+        .map_err(|e| NetworkError::Config(format!("WebRTC certificate error: {:?}", e)))?;
+    let webrtc = libp2p_webrtc::tokio::Transport::new(keypair.clone(), certificate);
+
+    Ok(transport
+        .or_transport(webrtc)
+        .map(|either, _| match either {
+            futures::future::Either::Left(output) => output,
+            futures::future::Either::Right((peer_id, connection)) => (
+                peer_id,
+                libp2p::core::muxing::StreamMuxerBox::new(connection),
+            ),
+        })
+        .boxed())
+}
+
+/// TCP and/or QUIC with DNS resolution
+fn create_tcp_quic_transport(
+    keypair: &Keypair,
+    config: &TransportConfig,
+) -> Result<libp2p::core::transport::Boxed<(PeerId, libp2p::core::muxing::StreamMuxerBox)>> {
+    // QUIC only (no TCP upgrade stack needed)
+    if !config.enable_tcp {
+        let quic_config = config.quic_config(keypair);
+        let transport = libp2p::quic::tokio::Transport::new(quic_config)
+            .map(|(peer_id, muxer), _| (peer_id, libp2p::core::muxing::StreamMuxerBox::new(muxer)));
+
+        let dns_transport = libp2p::dns::tokio::Transport::system(transport)
+            .map_err(|e| NetworkError::Config(format!("DNS config error: {:?}", e)))?;
+
+        return Ok(dns_transport.boxed());
+    }
+
+    // Create TCP transport, bounding the connect itself
+    let tcp = TransportTimeout::new(
+        libp2p::tcp::tokio::Transport::new(libp2p::tcp::Config::default().nodelay(true)),
+        config.connection_timeout,
+    );
 
     // Add Noise encryption
     let noise_config = noise::Config::new(keypair)
@@ -65,16 +211,17 @@ pub fn create_transport(
     // Add Yamux multiplexing
     let yamux_config = yamux::Config::default();
 
-    // Build authenticated transport
+    // Build authenticated transport. The upgrade timeout covers the connect
+    // as well, so the handshake gets its own budget on top of it.
     let tcp_authenticated = tcp
         .upgrade(upgrade::Version::V1)
         .authenticate(noise_config)
         .multiplex(yamux_config)
-        .timeout(config.connection_timeout);
+        .timeout(config.connection_timeout + config.handshake_timeout);
 
     // Optionally add QUIC
     if config.enable_quic {
-        let quic_config = libp2p::quic::Config::new(keypair);
+        let quic_config = config.quic_config(keypair);
         let quic = libp2p::quic::tokio::Transport::new(quic_config);
 
         // Combine TCP and QUIC
@@ -107,12 +254,55 @@ pub fn create_transport(
 }
 
 /// Parse a multiaddr string
+///
+/// Hostnames are given as `/dns`, `/dns4`, `/dns6` or `/dnsaddr`; the
+/// transport from [`create_transport`] resolves them when dialing. IPv6
+/// link-local addresses may carry their scope the way operators usually
+/// write it, `/ip6/fe80::1%eth0/...`, which becomes the multiaddr form
+/// `/ip6zone/eth0/ip6/fe80::1/...`.
 pub fn parse_multiaddr(addr: &str) -> Result<libp2p::Multiaddr> {
-    addr.parse()
+    scope_to_ip6zone(addr)
+        .parse()
         .map_err(|e| NetworkError::InvalidMultiaddr(format!("{}: {}", addr, e)))
 }
 
+/// Move `%scope` suffixes on `/ip6` components into an `/ip6zone`
+fn scope_to_ip6zone(addr: &str) -> String {
+    let mut parts = addr.split('/').peekable();
+    let mut out = Vec::new();
+    while let Some(part) = parts.next() {
+        if part == "ip6" {
+            if let Some((ip, zone)) = parts.peek().and_then(|next| next.split_once('%')) {
+                out.extend(["ip6zone", zone, "ip6", ip]);
+                parts.next();
+                continue;
+            }
+        }
+        out.push(part);
+    }
+    out.join("/")
+}
+
+/// Whether a multiaddr is an IPv6 link-local address with no `/ip6zone`
+///
+/// Such an address (as learned from a connection or identify, which don't
+/// carry the scope) can't be dialed on a host with several interfaces.
+pub fn is_unscoped_link_local(addr: &libp2p::Multiaddr) -> bool {
+    let mut zoned = false;
+    for protocol in addr.iter() {
+        match protocol {
+            Protocol::Ip6zone(_) => zoned = true,
+            Protocol::Ip6(ip) => return !zoned && (ip.segments()[0] & 0xffc0) == 0xfe80,
+            _ => {}
+        }
+    }
+    false
+}
+
 /// Extract peer ID from a multiaddr if present
+///
+/// A `/dnsaddr` without a `/p2p` suffix may stand for several peers; use
+/// [`resolve_dnsaddr`] to find them.
 pub fn extract_peer_id(addr: &libp2p::Multiaddr) -> Option<PeerId> {
     addr.iter().find_map(|p| {
         if let libp2p::multiaddr::Protocol::P2p(peer_id) = p {
@@ -122,3 +312,145 @@ pub fn extract_peer_id(addr: &libp2p::Multiaddr) -> Option<PeerId> {
         }
     })
 }
+
+/// Check whether a multiaddr names a host rather than an IP
+pub fn is_dns_multiaddr(addr: &libp2p::Multiaddr) -> bool {
+    matches!(
+        addr.iter().next(),
+        Some(Protocol::Dns(_) | Protocol::Dns4(_) | Protocol::Dns6(_) | Protocol::Dnsaddr(_))
+    )
+}
+
+/// Most `/dnsaddr` lookups followed for one address
+const MAX_DNSADDR_LOOKUPS: usize = 16;
+
+/// Expand a `/dnsaddr` multiaddr into the addresses it publishes
+///
+/// Follows nested `/dnsaddr` records and, if `addr` ends in `/p2p/<id>`,
+/// keeps only that peer's addresses. Any other multiaddr is returned as is.
+pub async fn resolve_dnsaddr(addr: &libp2p::Multiaddr) -> Result<Vec<libp2p::Multiaddr>> {
+    if !matches!(addr.iter().next(), Some(Protocol::Dnsaddr(_))) {
+        return Ok(vec![addr.clone()]);
+    }
+
+    let resolver = hickory_resolver::TokioAsyncResolver::tokio_from_system_conf()
+        .map_err(|e| NetworkError::Config(format!("DNS config error: {}", e)))?;
+    let wanted = extract_peer_id(addr);
+
+    let mut pending = vec![addr.clone()];
+    let mut resolved = Vec::new();
+    let mut lookups = 0;
+
+    while let Some(next) = pending.pop() {
+        let host = match next.iter().next() {
+            Some(Protocol::Dnsaddr(host)) => Some(host.to_string()),
+            _ => None,
+        };
+        let Some(host) = host else {
+            resolved.push(next);
+            continue;
+        };
+        if lookups == MAX_DNSADDR_LOOKUPS {
+            warn!("Too many /dnsaddr lookups for {}", addr);
+            break;
+        }
+        lookups += 1;
+
+        let name = format!("_dnsaddr.{}", host);
+        let records = match resolver.txt_lookup(name.as_str()).await {
+            Ok(lookup) => lookup
+                .iter()
+                .map(|txt| {
+                    txt.txt_data()
+                        .iter()
+                        .map(|part| String::from_utf8_lossy(part))
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>(),
+            Err(e) => {
+                debug!("TXT lookup for {} failed: {}", name, e);
+                continue;
+            }
+        };
+        pending.extend(dnsaddr_entries(&records, wanted));
+    }
+
+    if resolved.is_empty() {
+        return Err(NetworkError::InvalidMultiaddr(format!(
+            "{}: no addresses published",
+            addr
+        )));
+    }
+    Ok(resolved)
+}
+
+/// Multiaddrs from `dnsaddr=` TXT records, optionally for one peer only
+fn dnsaddr_entries(records: &[String], peer: Option<PeerId>) -> Vec<libp2p::Multiaddr> {
+    records
+        .iter()
+        .filter_map(|record| record.strip_prefix("dnsaddr="))
+        .filter_map(|entry| entry.parse::<libp2p::Multiaddr>().ok())
+        .filter(|entry| peer.is_none() || extract_peer_id(entry) == peer)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_rejects_zero_handshake_timeout() {
+        assert!(TransportConfig::default().validate().is_ok());
+
+        let config = TransportConfig {
+            handshake_timeout: Duration::ZERO,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_dns_multiaddrs() {
+        let dns = parse_multiaddr("/dns4/bootstrap.example.com/tcp/9000").unwrap();
+        let ip = parse_multiaddr("/ip4/1.2.3.4/tcp/9000").unwrap();
+
+        assert!(is_dns_multiaddr(&dns));
+        assert!(!is_dns_multiaddr(&ip));
+        assert!(parse_multiaddr("/ip4/bootstrap.example.com/tcp/9000").is_err());
+    }
+
+    #[test]
+    fn test_ipv6_multiaddrs() {
+        let any = parse_multiaddr("/ip6/::/tcp/9000").unwrap();
+        assert_eq!(any.to_string(), "/ip6/::/tcp/9000");
+        assert!(!is_unscoped_link_local(&any));
+
+        let scoped = parse_multiaddr("/ip6/fe80::1%eth0/udp/9000/quic-v1").unwrap();
+        assert_eq!(
+            scoped.to_string(),
+            "/ip6zone/eth0/ip6/fe80::1/udp/9000/quic-v1"
+        );
+        assert!(!is_unscoped_link_local(&scoped));
+
+        let unscoped = parse_multiaddr("/ip6/fe80::1/tcp/9000").unwrap();
+        assert!(is_unscoped_link_local(&unscoped));
+    }
+
+    #[test]
+    fn test_dnsaddr_entries_filter_by_peer() {
+        let a = Keypair::generate_ed25519().public().to_peer_id();
+        let b = Keypair::generate_ed25519().public().to_peer_id();
+        let records = vec![
+            format!("dnsaddr=/ip4/1.2.3.4/tcp/9000/p2p/{}", a),
+            format!("dnsaddr=/ip4/5.6.7.8/tcp/9000/p2p/{}", b),
+            "v=spf1 -all".to_string(),
+            "dnsaddr=not-a-multiaddr".to_string(),
+        ];
+
+        assert_eq!(dnsaddr_entries(&records, None).len(), 2);
+
+        let only_b = dnsaddr_entries(&records, Some(b));
+        assert_eq!(only_b.len(), 1);
+        assert_eq!(extract_peer_id(&only_b[0]), Some(b));
+    }
+}
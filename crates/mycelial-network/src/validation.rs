@@ -0,0 +1,419 @@
+//! Validation of inbound gossip messages
+//!
+//! Every gossip message the service receives runs through a
+//! [`ValidationPipeline`] before it is relayed or delivered. Each
+//! [`MessageValidator`] checks one concern; the pipeline runs them in order
+//! and stops at the first [`Rejection`] that refuses to relay the message.
+//! The rejection says why the message was refused and whether gossipsub
+//! should hold it against the sender.
+//!
+//! [`ValidationPipeline::from_config`] builds the built-in validators from
+//! a [`NetworkConfig`]: message size, signature, topic ACLs and source
+//! reputation, in that order. They judge each gossip message, so they see
+//! the fragments of a fragmented message one by one. Applications add their
+//! own with [`NetworkService::add_validator`](crate::NetworkService::add_validator);
+//! those always see whole payloads, after the built-in validators pass
+//! them. A fragmented message is only whole once it has been relayed, so
+//! an application validator refusing one just keeps it from delivery.
+//!
+//! ```rust
+//! use mycelial_network::validation::{InboundMessage, MessageValidator, Rejection};
+//!
+//! /// Only accept JSON on the chat topic
+//! struct JsonChat;
+//!
+//! impl MessageValidator for JsonChat {
+//!     fn name(&self) -> &str {
+//!         "json-chat"
+//!     }
+//!
+//!     fn validate(&self, message: &InboundMessage<'_>) -> Result<(), Rejection> {
+//!         if message.topic != "/mycelial/1.0.0/chat" {
+//!             return Ok(());
+//!         }
+//!         serde_json::from_slice::<serde_json::Value>(message.data)
+//!             .map(|_| ())
+//!             .map_err(|e| Rejection::custom(self.name(), e.to_string()))
+//!     }
+//! }
+//! ```
+
+use libp2p::PeerId;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use crate::config::{NetworkConfig, TopicAcl};
+
+/// A gossip message awaiting validation
+pub struct InboundMessage<'a> {
+    /// Topic it was published to
+    pub topic: &'a str,
+    /// Its author, if it is signed
+    pub source: Option<&'a PeerId>,
+    /// The peer that forwarded it to us
+    pub propagation_source: &'a PeerId,
+    /// Message bytes: a single fragment of a fragmented message for the
+    /// built-in validators, the whole payload for application validators
+    pub data: &'a [u8],
+    reputation: &'a dyn Fn(&PeerId) -> f64,
+}
+
+impl<'a> InboundMessage<'a> {
+    /// Describe a message, with `reputation` giving a peer's current score
+    pub fn new(
+        topic: &'a str,
+        source: Option<&'a PeerId>,
+        propagation_source: &'a PeerId,
+        data: &'a [u8],
+        reputation: &'a dyn Fn(&PeerId) -> f64,
+    ) -> Self {
+        Self {
+            topic,
+            source,
+            propagation_source,
+            data,
+            reputation,
+        }
+    }
+
+    /// A peer's reputation (0.0 - 1.0), 0.0 if we know nothing of it
+    pub fn reputation(&self, peer_id: &PeerId) -> f64 {
+        (self.reputation)(peer_id)
+    }
+}
+
+/// What gossipsub does with a refused message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RejectAction {
+    /// Don't relay it, and penalize the peer that forwarded it
+    Reject,
+    /// Relay it as usual, but don't deliver it here
+    Relay,
+}
+
+/// Why a message was refused
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum Rejection {
+    /// Larger than the configured maximum
+    #[error("message of {size} bytes exceeds the {max} byte limit")]
+    TooLarge { size: usize, max: usize },
+    /// No author signature
+    #[error("unsigned message")]
+    Unsigned,
+    /// The topic's ACL doesn't admit the source
+    #[error("not admitted to the topic: {0}")]
+    NotAdmitted(String),
+    /// The source's reputation is too low for the topic
+    #[error("reputation {reputation:.2} < {min:.2}")]
+    LowReputation { reputation: f64, min: f64 },
+    /// Refused by an application validator
+    #[error("{validator}: {reason}")]
+    Custom { validator: String, reason: String },
+}
+
+impl Rejection {
+    /// A rejection from an application validator
+    pub fn custom(validator: impl Into<String>, reason: impl Into<String>) -> Self {
+        Rejection::Custom {
+            validator: validator.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// What gossipsub should do with the message
+    ///
+    /// A low reputation is the source's standing rather than anything wrong
+    /// with the message, so it is still relayed for peers that trust the
+    /// source more, and the forwarding peer isn't penalized for it.
+    pub fn action(&self) -> RejectAction {
+        match self {
+            Rejection::LowReputation { .. } => RejectAction::Relay,
+            _ => RejectAction::Reject,
+        }
+    }
+}
+
+/// One check on inbound gossip
+///
+/// Validators run on the service's event loop for every message, so they
+/// must be quick and must not block.
+pub trait MessageValidator: Send + Sync {
+    /// Name reported with the validator's rejections
+    fn name(&self) -> &str;
+
+    /// Accept the message, or say why not
+    fn validate(&self, message: &InboundMessage<'_>) -> Result<(), Rejection>;
+}
+
+/// Rejects messages larger than `max_bytes`
+#[derive(Debug, Clone)]
+pub struct MaxSize {
+    pub max_bytes: usize,
+}
+
+impl MessageValidator for MaxSize {
+    fn name(&self) -> &str {
+        "size"
+    }
+
+    fn validate(&self, message: &InboundMessage<'_>) -> Result<(), Rejection> {
+        if message.data.len() > self.max_bytes {
+            return Err(Rejection::TooLarge {
+                size: message.data.len(),
+                max: self.max_bytes,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Rejects messages without an author signature
+///
+/// Gossipsub verifies the signatures themselves; this makes sure every
+/// message delivered has an author the later checks can judge.
+#[derive(Debug, Clone, Default)]
+pub struct RequireSigned;
+
+impl MessageValidator for RequireSigned {
+    fn name(&self) -> &str {
+        "signature"
+    }
+
+    fn validate(&self, message: &InboundMessage<'_>) -> Result<(), Rejection> {
+        match message.source {
+            Some(_) => Ok(()),
+            None => Err(Rejection::Unsigned),
+        }
+    }
+}
+
+/// Rejects messages from sources a topic's ACL doesn't admit
+#[derive(Debug, Clone, Default)]
+pub struct TopicAcls {
+    pub acls: BTreeMap<String, TopicAcl>,
+}
+
+impl MessageValidator for TopicAcls {
+    fn name(&self) -> &str {
+        "topic-acl"
+    }
+
+    fn validate(&self, message: &InboundMessage<'_>) -> Result<(), Rejection> {
+        let Some(acl) = self.acls.get(message.topic) else {
+            return Ok(());
+        };
+        // An unsigned message's author can't be checked
+        let source = message.source.ok_or(Rejection::Unsigned)?;
+        acl.check(source, || message.reputation(source))
+            .map_err(Rejection::NotAdmitted)
+    }
+}
+
+/// Keeps messages on `topics` whose source's reputation is below `min`
+/// from delivery, without stopping them being relayed
+#[derive(Debug, Clone, Default)]
+pub struct MinReputation {
+    pub topics: BTreeSet<String>,
+    pub min: f64,
+}
+
+impl MessageValidator for MinReputation {
+    fn name(&self) -> &str {
+        "reputation"
+    }
+
+    fn validate(&self, message: &InboundMessage<'_>) -> Result<(), Rejection> {
+        if !self.topics.contains(message.topic) {
+            return Ok(());
+        }
+        let Some(source) = message.source else {
+            return Ok(());
+        };
+        let reputation = message.reputation(source);
+        if reputation < self.min {
+            return Err(Rejection::LowReputation {
+                reputation,
+                min: self.min,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Validators run in order on each inbound message
+#[derive(Clone, Default)]
+pub struct ValidationPipeline {
+    validators: Vec<Arc<dyn MessageValidator>>,
+}
+
+impl ValidationPipeline {
+    /// A pipeline with no validators, accepting everything
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The built-in validators `config` asks for
+    ///
+    /// Size and signature are always checked; ACLs and reputation only
+    /// when some topic is configured for them.
+    pub fn from_config(config: &NetworkConfig) -> Self {
+        let mut pipeline = Self::new()
+            .with(MaxSize {
+                max_bytes: config.max_message_size,
+            })
+            .with(RequireSigned);
+        if !config.topic_acls.is_empty() {
+            pipeline.push(TopicAcls {
+                acls: config.topic_acls.clone(),
+            });
+        }
+        if !config.reputation_filtered_topics.is_empty() {
+            pipeline.push(MinReputation {
+                topics: config.reputation_filtered_topics.clone(),
+                min: config.min_message_reputation,
+            });
+        }
+        pipeline
+    }
+
+    /// Add a validator after the existing ones
+    pub fn push(&mut self, validator: impl MessageValidator + 'static) {
+        self.validators.push(Arc::new(validator));
+    }
+
+    /// Builder form of [`push`](Self::push)
+    pub fn with(mut self, validator: impl MessageValidator + 'static) -> Self {
+        self.push(validator);
+        self
+    }
+
+    /// Names of the validators, in the order they run
+    pub fn names(&self) -> Vec<&str> {
+        self.validators.iter().map(|v| v.name()).collect()
+    }
+
+    /// Run the validators until one refuses to relay the message
+    ///
+    /// Returns the refusing validator's name with its reason. A refusal
+    /// that still relays the message doesn't stop the later validators,
+    /// which may refuse it outright; otherwise the first one is returned.
+    pub fn validate(&self, message: &InboundMessage<'_>) -> Result<(), (String, Rejection)> {
+        let mut relayed = None;
+        for validator in &self.validators {
+            match validator.validate(message) {
+                Ok(()) => {}
+                Err(rejection) if rejection.action() == RejectAction::Relay => {
+                    relayed.get_or_insert((validator.name().to_string(), rejection));
+                }
+                Err(rejection) => return Err((validator.name().to_string(), rejection)),
+            }
+        }
+        relayed.map_or(Ok(()), Err)
+    }
+}
+
+impl std::fmt::Debug for ValidationPipeline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(
+        pipeline: &ValidationPipeline,
+        topic: &str,
+        source: Option<&PeerId>,
+        data: &[u8],
+        reputation: f64,
+    ) -> Result<(), (String, Rejection)> {
+        let relayer = PeerId::random();
+        let reputation = move |_: &PeerId| reputation;
+        pipeline.validate(&InboundMessage::new(
+            topic,
+            source,
+            &relayer,
+            data,
+            &reputation,
+        ))
+    }
+
+    #[test]
+    fn test_pipeline_from_config() {
+        let mut config = NetworkConfig::default();
+        config.max_message_size = 8;
+        config.topic_acls.insert(
+            "gov".to_string(),
+            TopicAcl::allowlist([]).with_min_reputation(0.5),
+        );
+        config
+            .reputation_filtered_topics
+            .insert("credit".to_string());
+        config.min_message_reputation = 0.3;
+        let pipeline = ValidationPipeline::from_config(&config);
+        assert_eq!(
+            pipeline.names(),
+            vec!["size", "signature", "topic-acl", "reputation"]
+        );
+
+        let source = PeerId::random();
+        assert!(validate(&pipeline, "chat", Some(&source), b"hi", 0.0).is_ok());
+
+        // The first refusal wins
+        let (validator, rejection) =
+            validate(&pipeline, "gov", None, b"far too long", 0.0).unwrap_err();
+        assert_eq!(validator, "size");
+        assert_eq!(rejection, Rejection::TooLarge { size: 12, max: 8 });
+        assert_eq!(rejection.action(), RejectAction::Reject);
+
+        let (validator, _) = validate(&pipeline, "chat", None, b"hi", 1.0).unwrap_err();
+        assert_eq!(validator, "signature");
+
+        let (validator, rejection) =
+            validate(&pipeline, "gov", Some(&source), b"hi", 0.4).unwrap_err();
+        assert_eq!(validator, "topic-acl");
+        assert!(matches!(rejection, Rejection::NotAdmitted(_)));
+
+        let (validator, rejection) =
+            validate(&pipeline, "credit", Some(&source), b"hi", 0.2).unwrap_err();
+        assert_eq!(validator, "reputation");
+        assert_eq!(rejection.action(), RejectAction::Relay);
+        assert!(validate(&pipeline, "credit", Some(&source), b"hi", 0.3).is_ok());
+
+        // A validator refusing to relay outranks an earlier low reputation
+        let pipeline = pipeline.with(MaxSize { max_bytes: 1 });
+        let (validator, rejection) =
+            validate(&pipeline, "credit", Some(&source), b"hi", 0.2).unwrap_err();
+        assert_eq!(validator, "size");
+        assert_eq!(rejection.action(), RejectAction::Reject);
+    }
+
+    #[test]
+    fn test_custom_validator() {
+        struct NoEmpty;
+
+        impl MessageValidator for NoEmpty {
+            fn name(&self) -> &str {
+                "no-empty"
+            }
+
+            fn validate(&self, message: &InboundMessage<'_>) -> Result<(), Rejection> {
+                if message.data.is_empty() {
+                    return Err(Rejection::custom(self.name(), "empty message"));
+                }
+                Ok(())
+            }
+        }
+
+        let pipeline = ValidationPipeline::new().with(NoEmpty);
+        let source = PeerId::random();
+        assert!(validate(&pipeline, "chat", Some(&source), b"hi", 0.0).is_ok());
+
+        let (validator, rejection) =
+            validate(&pipeline, "chat", Some(&source), b"", 0.0).unwrap_err();
+        assert_eq!(validator, "no-empty");
+        assert_eq!(rejection.to_string(), "no-empty: empty message");
+    }
+}
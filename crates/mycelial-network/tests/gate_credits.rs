@@ -8,10 +8,10 @@ mod helpers;
 
 use std::time::Duration;
 use tokio::time::timeout;
-use univrs_enr::core::{Credits, NodeId};
+use univrs_enr::core::Credits;
 
 use helpers::TestCluster;
-use mycelial_network::enr_bridge::INITIAL_NODE_CREDITS;
+use mycelial_network::enr_bridge::{node_id_from_peer_id, INITIAL_NODE_CREDITS};
 
 /// Test credit transfer between two nodes with 2% tax
 ///
@@ -58,11 +58,7 @@ async fn test_credit_transfer_with_tax() {
 
     // Get receiver's NodeId from their bridge
     let receiver_peer_id = cluster.node(1).handle.local_peer_id();
-    let peer_id_bytes = receiver_peer_id.to_bytes();
-    let mut node_id_bytes = [0u8; 32];
-    let len = peer_id_bytes.len().min(32);
-    node_id_bytes[..len].copy_from_slice(&peer_id_bytes[..len]);
-    let receiver_node_id = NodeId::from_bytes(node_id_bytes);
+    let receiver_node_id = node_id_from_peer_id(&receiver_peer_id).expect("Ed25519 peer id");
 
     // Transfer 100 credits from Node 0 to Node 1
     let transfer_amount = Credits::new(100);
@@ -143,11 +139,7 @@ async fn test_self_transfer_rejected() {
 
     // Get node 0's own NodeId
     let self_peer_id = cluster.node(0).handle.local_peer_id();
-    let peer_id_bytes = self_peer_id.to_bytes();
-    let mut node_id_bytes = [0u8; 32];
-    let len = peer_id_bytes.len().min(32);
-    node_id_bytes[..len].copy_from_slice(&peer_id_bytes[..len]);
-    let self_node_id = NodeId::from_bytes(node_id_bytes);
+    let self_node_id = node_id_from_peer_id(&self_peer_id).expect("Ed25519 peer id");
 
     // Attempt self-transfer
     let result = cluster
@@ -187,11 +179,7 @@ async fn test_insufficient_balance_rejected() {
 
     // Get receiver's NodeId
     let receiver_peer_id = cluster.node(1).handle.local_peer_id();
-    let peer_id_bytes = receiver_peer_id.to_bytes();
-    let mut node_id_bytes = [0u8; 32];
-    let len = peer_id_bytes.len().min(32);
-    node_id_bytes[..len].copy_from_slice(&peer_id_bytes[..len]);
-    let receiver_node_id = NodeId::from_bytes(node_id_bytes);
+    let receiver_node_id = node_id_from_peer_id(&receiver_peer_id).expect("Ed25519 peer id");
 
     // Attempt to transfer more than balance (initial is 1000)
     let result = cluster
@@ -234,18 +222,10 @@ async fn test_multiple_transfers() {
 
     // Get NodeIds
     let node1_peer_id = cluster.node(1).handle.local_peer_id();
-    let peer_id_bytes = node1_peer_id.to_bytes();
-    let mut node_id_bytes = [0u8; 32];
-    let len = peer_id_bytes.len().min(32);
-    node_id_bytes[..len].copy_from_slice(&peer_id_bytes[..len]);
-    let node1_id = NodeId::from_bytes(node_id_bytes);
+    let node1_id = node_id_from_peer_id(&node1_peer_id).expect("Ed25519 peer id");
 
     let node2_peer_id = cluster.node(2).handle.local_peer_id();
-    let peer_id_bytes = node2_peer_id.to_bytes();
-    let mut node_id_bytes = [0u8; 32];
-    let len = peer_id_bytes.len().min(32);
-    node_id_bytes[..len].copy_from_slice(&peer_id_bytes[..len]);
-    let node2_id = NodeId::from_bytes(node_id_bytes);
+    let node2_id = node_id_from_peer_id(&node2_peer_id).expect("Ed25519 peer id");
 
     // Transfer 1: Node 0 -> Node 1 (100 credits)
     cluster
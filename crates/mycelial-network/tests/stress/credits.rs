@@ -13,15 +13,11 @@ use tokio::time::timeout;
 use univrs_enr::core::{Credits, NodeId};
 
 use crate::helpers::TestCluster;
-use mycelial_network::enr_bridge::INITIAL_NODE_CREDITS;
+use mycelial_network::enr_bridge::{node_id_from_peer_id, INITIAL_NODE_CREDITS};
 
 /// Helper to convert PeerId to NodeId
 fn peer_id_to_node_id(peer_id: &libp2p::PeerId) -> NodeId {
-    let peer_id_bytes = peer_id.to_bytes();
-    let mut node_id_bytes = [0u8; 32];
-    let len = peer_id_bytes.len().min(32);
-    node_id_bytes[..len].copy_from_slice(&peer_id_bytes[..len]);
-    NodeId::from_bytes(node_id_bytes)
+    node_id_from_peer_id(peer_id).expect("Ed25519 peer id")
 }
 
 /// Test basic credit transfer between 2 nodes
@@ -6,32 +6,53 @@
 //! - REST API for peer and network information
 
 mod server;
+mod snapshot;
 
-use clap::Parser;
+use async_trait::async_trait;
+use clap::{Parser, ValueEnum};
 use parking_lot::RwLock;
 use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::broadcast;
-use tracing::{error, info, warn, Level};
+use tracing::{debug, error, info, info_span, warn, Instrument, Level, Span};
 use tracing_subscriber::FmtSubscriber;
 
+use mycelial_core::content::Content;
+use mycelial_core::identity::write_secret_file;
+use mycelial_core::location::Location;
+use mycelial_core::message::{correlation_id, Message, MessageType};
 use mycelial_core::peer::{PeerId, PeerInfo};
 use mycelial_core::reputation::Reputation;
-use mycelial_network::enr_bridge::{
-    EnrMessage, CREDIT_TOPIC, ELECTION_TOPIC, GRADIENT_TOPIC, SEPTAL_TOPIC,
-};
+use mycelial_core::{HandlerRegistry, MessageHandler};
+use mycelial_network::enr_bridge::{EnrMessage, EntropyTaxRate, BRIDGE_TOPICS};
 use mycelial_network::{is_economics_topic, parse_economics_message, EconomicsEvent};
 use mycelial_network::{
-    Keypair, Libp2pPeerId, NetworkConfig, NetworkEvent, NetworkHandle, NetworkService,
+    topics, IpStack, Keypair, Libp2pPeerId, NetworkConfig, NetworkEvent, NetworkHandle,
+    NetworkService, PeerBan, PeerManager, SEPTAL_BAN_REASON,
 };
-use mycelial_state::SqliteStore;
+use mycelial_state::{SqliteStore, StoredPeerBan};
 use server::economics_state::{
-    CreditLine, EconomicsStateManager, Proposal, ProposalStatus, ResourceContribution, Vote,
-    VoteType, Vouch,
+    CreditLine, EconomicsStateManager, ParameterChange, Proposal, ProposalStatus,
+    ResourceContribution, Vote, VoteType, Vouch,
 };
 use server::messages::{ContributorEntry, WsMessage};
 
+/// How often economics state is snapshotted to SQLite
+const ECONOMICS_PERSIST_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How often proposal deadlines are checked and expired proposals tallied
+const PROPOSAL_TALLY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How often expired vouches are dropped from reputation calculations
+const VOUCH_EXPIRY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How often interest is compounded onto outstanding credit line balances
+const INTEREST_ACCRUAL_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// How often stored content is garbage collected
+const CONTENT_GC_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
 #[derive(Parser)]
 #[command(name = "mycelial-node")]
 #[command(about = "Mycelial P2P network node with dashboard server")]
@@ -40,14 +61,33 @@ struct Args {
     #[arg(long)]
     bootstrap: bool,
 
-    /// Connect to existing node (multiaddr format)
+    /// Transports to listen on (use `tcp` where UDP is blocked)
+    #[arg(long, value_enum, default_value_t = TransportMode::Both)]
+    transport: TransportMode,
+
+    /// Connect to existing node (multiaddr format, repeat for several bootstraps)
     #[arg(long, short)]
-    connect: Option<String>,
+    connect: Vec<String>,
+
+    /// Peer to always exchange gossip with and redial when it drops
+    /// (multiaddr ending in /p2p/<peer id>, repeat for several)
+    #[arg(long = "explicit-peer")]
+    explicit_peers: Vec<String>,
 
     /// P2P listen port (0 = auto-assign, bootstrap default: 9000, peer default: 0)
     #[arg(long)]
     port: Option<u16>,
 
+    /// Also listen on IPv6 (`--ipv6`), or only on IPv6 (`--ipv6 only`)
+    #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "dual")]
+    ipv6: Option<Ipv6Mode>,
+
+    /// UDP port for WebRTC direct, so browsers can connect without a relay
+    /// Requires the 'webrtc' feature to be enabled at compile time
+    #[cfg(feature = "webrtc")]
+    #[arg(long)]
+    webrtc_port: Option<u16>,
+
     /// Dashboard HTTP server port (0 = auto-assign, bootstrap default: 8080, peer default: 0)
     #[arg(long)]
     http_port: Option<u16>,
@@ -64,12 +104,147 @@ struct Args {
     #[arg(long, short)]
     verbose: bool,
 
+    /// Don't join the ENR economics topics (gradients, credits, elections)
+    #[arg(long)]
+    no_economics: bool,
+
+    /// File recording seen credit transfer nonces, so replays stay rejected
+    /// across restarts (default: the database path with `.nonces.json`)
+    #[arg(long)]
+    credit_nonce_file: Option<std::path::PathBuf>,
+
+    /// File septal gate state is kept in, so isolated peers stay isolated
+    /// across restarts (default: the database path with `.septal.json`)
+    #[arg(long)]
+    septal_state_file: Option<std::path::PathBuf>,
+
+    /// Share of each credit transfer paid to the revival pool, in [0, 1),
+    /// until a governance proposal sets it
+    #[arg(long)]
+    entropy_tax_rate: Option<f64>,
+
+    /// BIP39 mnemonic file for a stable node identity (created if missing)
+    ///
+    /// The passphrase, if any, is read from MYCELIAL_MNEMONIC_PASSPHRASE.
+    #[arg(long, conflicts_with = "identity_file")]
+    mnemonic_file: Option<std::path::PathBuf>,
+
+    /// Encrypted key file for a stable node identity (created if missing)
+    ///
+    /// The passphrase is read from MYCELIAL_IDENTITY_PASSPHRASE.
+    #[arg(long)]
+    identity_file: Option<std::path::PathBuf>,
+
+    /// Browser origin allowed to call the dashboard API (repeatable, `*` for any)
+    ///
+    /// Defaults to pages served from localhost.
+    #[arg(long = "cors-origin")]
+    cors_origins: Vec<String>,
+
+    /// HTTP method allowed cross-origin (repeatable, default GET, POST, PUT, DELETE)
+    #[arg(long = "cors-method")]
+    cors_methods: Vec<String>,
+
+    /// Request header allowed cross-origin (repeatable, default content-type, authorization)
+    #[arg(long = "cors-header")]
+    cors_headers: Vec<String>,
+
+    /// Latitude of this node, reported by /api/info
+    #[arg(long, requires = "longitude", allow_hyphen_values = true)]
+    latitude: Option<f64>,
+
+    /// Longitude of this node, reported by /api/info
+    #[arg(long, requires = "latitude", allow_hyphen_values = true)]
+    longitude: Option<f64>,
+
+    /// Require a bearer token on the dashboard API and WebSocket
+    ///
+    /// The token is read from this file, or generated into it if missing.
+    #[arg(long)]
+    api_token_file: Option<std::path::PathBuf>,
+
+    /// Leave topics after this many seconds without traffic, rejoining on
+    /// publish (0 = never; core topics are always kept)
+    #[arg(long, default_value_t = 0)]
+    topic_idle_timeout_secs: u64,
+
+    /// Disk budget for stored content in MiB; GC evicts unpinned content
+    /// beyond it, least recently used first
+    #[arg(long, default_value_t = 1024)]
+    content_budget_mb: u64,
+
+    /// Days unpinned content may go unread before GC evicts it
+    #[arg(long, default_value_t = 30)]
+    content_max_age_days: u64,
+
+    /// Write the database and identity to a snapshot archive, then exit
+    ///
+    /// Needs `--identity-file` or `--mnemonic-file` so the peer ID survives.
+    #[arg(long, value_name = "ARCHIVE", conflicts_with = "import")]
+    export: Option<std::path::PathBuf>,
+
+    /// Restore a snapshot archive into a fresh node before starting
+    ///
+    /// `--db` and the identity file the snapshot holds must not exist yet.
+    #[arg(long, value_name = "ARCHIVE")]
+    import: Option<std::path::PathBuf>,
+
     /// Enable Meshtastic LoRa bridge with serial port (e.g., /dev/ttyUSB0)
     /// Requires the 'meshtastic-serial' feature to be enabled at compile time
     #[arg(long)]
     meshtastic: Option<String>,
 }
 
+/// Which libp2p transports the node listens on
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum TransportMode {
+    /// TCP only
+    Tcp,
+    /// QUIC (UDP) only
+    Quic,
+    /// TCP and QUIC
+    Both,
+}
+
+impl TransportMode {
+    fn tcp(self) -> bool {
+        matches!(self, TransportMode::Tcp | TransportMode::Both)
+    }
+
+    fn quic(self) -> bool {
+        matches!(self, TransportMode::Quic | TransportMode::Both)
+    }
+}
+
+impl std::fmt::Display for TransportMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransportMode::Tcp => write!(f, "TCP"),
+            TransportMode::Quic => write!(f, "QUIC"),
+            TransportMode::Both => write!(f, "TCP + QUIC"),
+        }
+    }
+}
+
+/// How the node uses IPv6
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Ipv6Mode {
+    /// IPv4 and IPv6
+    Dual,
+    /// IPv6 only
+    Only,
+}
+
+impl Ipv6Mode {
+    fn ip_stack(mode: Option<Self>) -> IpStack {
+        match mode {
+            None => IpStack::V4,
+            Some(Ipv6Mode::Dual) => IpStack::Dual,
+            Some(Ipv6Mode::Only) => IpStack::V6,
+        }
+    }
+}
+
 /// Application state shared across handlers
 pub struct AppState {
     /// Local peer ID (mycelial-core format)
@@ -92,6 +267,47 @@ pub struct AppState {
     pub economics: EconomicsStateManager,
     /// ENR bridge for economic primitives (gradients, credits, elections, septal gates)
     pub enr_bridge: Arc<mycelial_network::enr_bridge::EnrBridge>,
+    /// Handlers for core messages, by message type
+    pub handlers: HandlerRegistry,
+    /// Token the dashboard API requires, if any
+    pub api_token: Option<String>,
+    /// Where the operator says this node is
+    pub location: Option<Location>,
+    /// Transports the node listens on
+    pub transports: Vec<&'static str>,
+    /// Whether the node joined the economics topics
+    pub economics_enabled: bool,
+    /// Full multiaddrs (with `/p2p/<peer id>`) the node is listening on
+    pub listen_addrs: RwLock<Vec<String>>,
+}
+
+#[cfg(test)]
+impl AppState {
+    /// State for handler tests, on `network` and an in-memory store
+    pub(crate) async fn for_test(network: NetworkHandle) -> Arc<Self> {
+        let signing_key = mycelial_network::enr_bridge::SigningKey::from_bytes(&[7; 32]);
+        Arc::new(AppState {
+            local_peer_id: PeerId::from(network.local_peer_id()),
+            network,
+            store: SqliteStore::new(":memory:").await.unwrap(),
+            event_tx: broadcast::channel(16).0,
+            message_count: AtomicU64::new(0),
+            start_time: Instant::now(),
+            node_name: "test-node".to_string(),
+            subscribed_topics: RwLock::new(Vec::new()),
+            economics: EconomicsStateManager::new(),
+            enr_bridge: Arc::new(mycelial_network::enr_bridge::EnrBridge::new(
+                signing_key,
+                |_, _| Ok(()),
+            )),
+            handlers: HandlerRegistry::new(),
+            api_token: None,
+            location: None,
+            transports: vec!["tcp"],
+            economics_enabled: true,
+            listen_addrs: RwLock::new(Vec::new()),
+        })
+    }
 }
 
 #[tokio::main]
@@ -107,6 +323,10 @@ async fn main() -> anyhow::Result<()> {
     let subscriber = FmtSubscriber::builder().with_max_level(level).finish();
     tracing::subscriber::set_global_default(subscriber)?;
 
+    if let Some(path) = &args.export {
+        return export_snapshot(&args, path).await;
+    }
+
     // Determine ports based on bootstrap flag and user input
     // Bootstrap nodes: default to 9000/8080 for predictable addresses
     // Peer nodes: default to 0 (OS auto-assigns) for easy multi-node testing
@@ -120,12 +340,44 @@ async fn main() -> anyhow::Result<()> {
         info!("Running as BOOTSTRAP node");
     }
 
-    // Generate keypair
-    let keypair = Keypair::generate_ed25519();
+    // Check the CORS settings before anything starts
+    let cors = server::CorsConfig {
+        origins: args.cors_origins.clone(),
+        methods: args.cors_methods.clone(),
+        headers: args.cors_headers.clone(),
+    }
+    .layer()?;
+    if args.cors_origins.iter().any(|o| o == "*") {
+        warn!("Dashboard API accepts requests from any origin");
+    }
+    if let (Some(lat), Some(lon)) = (args.latitude, args.longitude) {
+        if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+            anyhow::bail!("Location {}, {} is out of range", lat, lon);
+        }
+    }
+    let api_token = args
+        .api_token_file
+        .as_deref()
+        .map(load_api_token)
+        .transpose()?;
+
+    // Restore a snapshot before anything reads the identity or database
+    if let Some(path) = &args.import {
+        import_snapshot(&args, path)?;
+    }
+
+    // Load a persisted identity, or use a throwaway one
+    let keypair = if let Some(path) = &args.mnemonic_file {
+        load_mnemonic_keypair(path)?
+    } else if let Some(path) = &args.identity_file {
+        load_identity_file(path)?
+    } else {
+        Keypair::generate_ed25519()
+    };
     let libp2p_peer_id = keypair.public().to_peer_id();
 
     // Convert to mycelial-core PeerId (base58 encoded)
-    let local_peer_id = PeerId(libp2p_peer_id.to_base58());
+    let local_peer_id = PeerId::from(libp2p_peer_id);
 
     info!("Local peer ID: {}", local_peer_id);
 
@@ -136,36 +388,160 @@ async fn main() -> anyhow::Result<()> {
 
     // Configure network
     // Port 0 tells the OS to assign an available port automatically
+    let ip_stack = Ipv6Mode::ip_stack(args.ipv6);
     let mut config = NetworkConfig::default();
-    config.listen_addresses = vec![
-        format!("/ip4/0.0.0.0/tcp/{}", p2p_port),
-        format!(
-            "/ip4/0.0.0.0/udp/{}/quic-v1",
-            if p2p_port == 0 { 0 } else { p2p_port + 1 }
-        ),
-    ];
+    config.enable_tcp = args.transport.tcp();
+    config.enable_quic = args.transport.quic();
+    config.topic_idle_timeout_secs = args.topic_idle_timeout_secs;
+    config.enable_economics = !args.no_economics;
+    config.credit_nonce_path = Some(
+        args.credit_nonce_file
+            .clone()
+            .unwrap_or_else(|| format!("{}.nonces.json", args.db).into()),
+    );
+    config.listen_addresses.clear();
+    if config.enable_tcp {
+        config
+            .listen_addresses
+            .extend(ip_stack.listen_addresses(&format!("tcp/{}", p2p_port)));
+    }
+    if config.enable_quic {
+        // QUIC uses the next port when sharing with TCP so both can bind
+        let quic_port = match p2p_port {
+            0 => 0,
+            p if config.enable_tcp => p + 1,
+            p => p,
+        };
+        config
+            .listen_addresses
+            .extend(ip_stack.listen_addresses(&format!("udp/{}/quic-v1", quic_port)));
+    }
+    #[cfg(feature = "webrtc")]
+    if let Some(webrtc_port) = args.webrtc_port {
+        config.enable_webrtc = true;
+        config
+            .listen_addresses
+            .extend(ip_stack.listen_addresses(&format!("udp/{}/webrtc-direct", webrtc_port)));
+    }
+    config
+        .validate()
+        .map_err(|e| anyhow::anyhow!("Invalid --transport: {}", e))?;
 
+    info!("Transport: {} ({:?})", args.transport, ip_stack);
     if p2p_port == 0 {
         info!("P2P port: auto-assign (OS will select available port)");
     } else {
-        info!("P2P port: {} (TCP), {} (QUIC)", p2p_port, p2p_port + 1);
+        match args.transport {
+            TransportMode::Tcp => info!("P2P port: {} (TCP)", p2p_port),
+            TransportMode::Quic => info!("P2P port: {} (QUIC)", p2p_port),
+            TransportMode::Both => {
+                info!("P2P port: {} (TCP), {} (QUIC)", p2p_port, p2p_port + 1)
+            }
+        }
     }
 
-    if let Some(ref addr) = args.connect {
+    for addr in &args.connect {
         config.bootstrap_peers.push(addr.clone());
         info!("Will connect to bootstrap peer: {}", addr);
     }
+    config.explicit_peers = args.explicit_peers.clone();
+
+    // Rejoin the topics we were subscribed to before the last shutdown
+    config.subscribed_topics = store.list_subscribed_topics().await?.into_iter().collect();
+    if !config.subscribed_topics.is_empty() {
+        info!(
+            "Restoring {} topic subscriptions",
+            config.subscribed_topics.len()
+        );
+    }
+
+    // Reported by /api/info
+    let transports = config.transports();
+    let economics_enabled = config.enable_economics;
 
     // Create network service
     // With univrs-compat feature (default), EnrBridge is returned for direct access
-    let (network_service, network_handle, mut event_rx, enr_bridge) =
+    let (mut network_service, network_handle, mut event_rx, enr_bridge) =
         NetworkService::new(keypair.clone(), config)?;
 
     info!("Network service created (EnrBridge enabled)");
 
+    // Serve content from the database, where GC keeps it within budget
+    network_service.set_content_store(Arc::new(store.clone()));
+
+    // Keep refusing peers banned before the last shutdown
+    let bans = store.list_peer_bans().await?;
+    for stored in bans {
+        // Septal bans follow gate state and lapse on their own; older
+        // builds stored them with no expiry
+        if stored.reason == SEPTAL_BAN_REASON {
+            store.remove_peer_ban(&stored.peer_id).await?;
+            continue;
+        }
+        match stored.peer_id.parse::<Libp2pPeerId>() {
+            Ok(peer_id) => network_service.peer_manager().restore_ban(
+                peer_id,
+                PeerBan {
+                    peer_id: stored.peer_id,
+                    reason: stored.reason,
+                    banned_at: stored.banned_at,
+                    until: stored.until,
+                },
+            ),
+            Err(e) => warn!("Skipping ban on invalid peer ID {}: {}", stored.peer_id, e),
+        }
+    }
+
+    // Keep isolating peers whose gates were closed before the last shutdown
+    let septal_state_path = args
+        .septal_state_file
+        .clone()
+        .unwrap_or_else(|| format!("{}.septal.json", args.db).into());
+    if let Err(e) = enr_bridge.load_septal_state(&septal_state_path) {
+        warn!(
+            "Failed to restore septal gates from {}: {}",
+            septal_state_path.display(),
+            e
+        );
+    }
+
+    // A rate set by governance outranks the operator's default
+    let governed_tax_rate = store.load_entropy_tax_rate().await?;
+    if let Some(rate) = governed_tax_rate.or(args.entropy_tax_rate) {
+        enr_bridge
+            .set_entropy_tax_rate(EntropyTaxRate::new(rate)?)
+            .await;
+    }
+
+    // Restore persisted economics state so the dashboard isn't empty after a restart
+    let economics = match EconomicsStateManager::load(&store).await {
+        Ok(economics) => {
+            let summary = economics.get_summary();
+            info!(
+                "Restored economics state: {} credit lines, {} proposals, {} vouches",
+                summary.credit_line_count, summary.total_proposal_count, summary.vouch_count
+            );
+            economics
+        }
+        Err(e) => {
+            warn!("Failed to restore economics state, starting empty: {}", e);
+            EconomicsStateManager::new()
+        }
+    };
+
     // Create broadcast channel for WebSocket events
     let (event_tx, _) = broadcast::channel(256);
 
+    // Core messages are routed by type to the handlers registered here
+    let mut handlers = HandlerRegistry::new();
+    handlers.register(
+        MessageType::Discovery,
+        Box::new(PeerLocationHandler {
+            store: store.clone(),
+            peer_manager: network_service.peer_manager().clone(),
+        }),
+    );
+
     // Create shared state
     let state = Arc::new(AppState {
         local_peer_id: local_peer_id.clone(),
@@ -176,8 +552,17 @@ async fn main() -> anyhow::Result<()> {
         start_time: Instant::now(),
         node_name: args.name.clone(),
         subscribed_topics: RwLock::new(Vec::new()),
-        economics: EconomicsStateManager::new(),
+        economics,
         enr_bridge,
+        handlers,
+        api_token,
+        location: args
+            .latitude
+            .zip(args.longitude)
+            .map(|(lat, lon)| Location::new(lat, lon)),
+        transports,
+        economics_enabled,
+        listen_addrs: RwLock::new(Vec::new()),
     });
 
     // Spawn network service
@@ -187,12 +572,103 @@ async fn main() -> anyhow::Result<()> {
         }
     });
 
+    // Save septal gates whenever one changes state
+    let septal_bridge = state.enr_bridge.clone();
+    let septal_save_path = septal_state_path.clone();
+    tokio::spawn(async move {
+        let mut events = septal_bridge.subscribe_septal_events();
+        loop {
+            match events.recv().await {
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+            if let Err(e) = septal_bridge.save_septal_state(&septal_save_path) {
+                warn!("Failed to save septal gates: {}", e);
+            }
+        }
+    });
+
+    // Periodically snapshot economics state to the database
+    let persist_state = state.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(ECONOMICS_PERSIST_INTERVAL);
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            if let Err(e) = persist_state.economics.persist(&persist_state.store).await {
+                warn!("Failed to persist economics state: {}", e);
+            }
+        }
+    });
+
+    // Keep stored content within budget, withdrawing evicted content from the DHT
+    let gc_state = state.clone();
+    let content_budget = args.content_budget_mb.saturating_mul(1024 * 1024);
+    let content_max_age =
+        Duration::from_secs(args.content_max_age_days.saturating_mul(24 * 60 * 60));
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(CONTENT_GC_INTERVAL);
+        loop {
+            interval.tick().await;
+            let stats = match gc_state
+                .store
+                .gc_content(content_budget, content_max_age)
+                .await
+            {
+                Ok(stats) => stats,
+                Err(e) => {
+                    warn!("Content GC failed: {}", e);
+                    continue;
+                }
+            };
+            for id in stats.evicted {
+                if let Err(e) = gc_state.network.stop_providing(id).await {
+                    warn!("Failed to withdraw provider record for {}: {}", id, e);
+                }
+            }
+        }
+    });
+
+    // Close and tally proposals as their deadlines pass
+    tokio::spawn(server::economics_state::run_proposal_tally(
+        state.clone(),
+        PROPOSAL_TALLY_INTERVAL,
+    ));
+
+    // Stop counting vouches once they expire
+    tokio::spawn(server::economics_state::run_vouch_expiry(
+        state.clone(),
+        VOUCH_EXPIRY_INTERVAL,
+    ));
+
+    // Charge interest on credit lines
+    tokio::spawn(server::economics_state::run_interest_accrual(
+        state.clone(),
+        INTEREST_ACCRUAL_INTERVAL,
+    ));
+
+    // Mirror ENR ledger and septal gate changes to the dashboard
+    tokio::spawn(forward_enr_events(state.clone()));
+
     // Spawn network event handler
     let event_state = state.clone();
     let peer_id_for_events = libp2p_peer_id;
     tokio::spawn(async move {
         while let Ok(event) = event_rx.recv().await {
-            handle_network_event(event, &event_state, peer_id_for_events).await;
+            // Tie the node's handling of a message to the network's logs for it
+            let span = match &event {
+                NetworkEvent::MessageReceived {
+                    message_id, topic, ..
+                } => info_span!(
+                    "message",
+                    correlation_id = %correlation_id(&message_id.0),
+                    topic = %topic
+                ),
+                _ => Span::none(),
+            };
+            handle_network_event(event, &event_state, peer_id_for_events)
+                .instrument(span)
+                .await;
         }
     });
 
@@ -258,7 +734,12 @@ async fn main() -> anyhow::Result<()> {
     }
 
     // Start HTTP server - bind to requested port (0 = auto-assign)
-    let http_bind_addr = format!("0.0.0.0:{}", http_port);
+    // With IPv6 the dashboard binds the IPv6 wildcard, which also accepts
+    // IPv4 where the OS allows dual-stack sockets
+    let http_bind_addr = match ip_stack {
+        IpStack::V4 => format!("0.0.0.0:{}", http_port),
+        IpStack::V6 | IpStack::Dual => format!("[::]:{}", http_port),
+    };
     let listener = tokio::net::TcpListener::bind(&http_bind_addr).await?;
 
     // Get the actual bound address (important when port was 0)
@@ -277,12 +758,353 @@ async fn main() -> anyhow::Result<()> {
     info!("  REST API: http://127.0.0.1:{}/api/", actual_http_port);
     info!("═══════════════════════════════════════════════════════════");
 
-    let app = server::create_router(state);
-    axum::serve(listener, app).await?;
+    let app = server::create_router(state.clone(), cors);
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+
+    // Server has drained in-flight requests; tear down the network and storage
+    info!("Shutting down...");
+    if let Err(e) = network_handle
+        .shutdown_graceful(Duration::from_secs(5))
+        .await
+    {
+        warn!("Network did not shut down cleanly: {}", e);
+    }
+    if let Err(e) = state.economics.persist(&state.store).await {
+        warn!("Failed to persist economics state: {}", e);
+    }
+    if let Err(e) = state.enr_bridge.save_septal_state(&septal_state_path) {
+        warn!("Failed to save septal gates: {}", e);
+    }
+    if let Err(e) = state.store.close().await {
+        warn!("Failed to flush database: {}", e);
+    }
+
+    info!("═══════════════════════════════════════════════════════════");
+    info!("  Shutdown complete");
+    info!("  Uptime: {}s", state.start_time.elapsed().as_secs());
+    info!(
+        "  Messages handled: {}",
+        state
+            .message_count
+            .load(std::sync::atomic::Ordering::Relaxed)
+    );
+    info!("═══════════════════════════════════════════════════════════");
 
     Ok(())
 }
 
+/// Environment variable holding the passphrase for `--mnemonic-file`
+const MNEMONIC_PASSPHRASE_ENV: &str = "MYCELIAL_MNEMONIC_PASSPHRASE";
+
+/// Derive the node keypair from a mnemonic file, creating the file if needed
+fn load_mnemonic_keypair(path: &std::path::Path) -> anyhow::Result<Keypair> {
+    let passphrase = std::env::var(MNEMONIC_PASSPHRASE_ENV).unwrap_or_default();
+
+    let phrase = if path.exists() {
+        std::fs::read_to_string(path)?.trim().to_string()
+    } else {
+        let phrase = mycelial_core::generate_mnemonic();
+        write_secret_file(path, format!("{}\n", phrase).as_bytes())?;
+        warn!(
+            "Generated a new identity mnemonic in {}; back it up to keep this node's peer ID",
+            path.display()
+        );
+        phrase
+    };
+
+    let secret = mycelial_core::identity::mnemonic_secret(&phrase, &passphrase)
+        .map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?;
+    Ok(Keypair::ed25519_from_bytes(secret)?)
+}
+
+/// Environment variable holding the passphrase for `--identity-file`
+const IDENTITY_PASSPHRASE_ENV: &str = "MYCELIAL_IDENTITY_PASSPHRASE";
+
+/// Load the node keypair from an encrypted key file, creating it if needed
+fn load_identity_file(path: &std::path::Path) -> anyhow::Result<Keypair> {
+    use mycelial_core::KeypairExt;
+
+    let passphrase = std::env::var(IDENTITY_PASSPHRASE_ENV).unwrap_or_default();
+    if passphrase.is_empty() {
+        warn!(
+            "{} is not set; the identity file is encrypted with an empty passphrase",
+            IDENTITY_PASSPHRASE_ENV
+        );
+    }
+
+    let identity = if path.exists() {
+        mycelial_core::Keypair::load_encrypted(path, &passphrase)
+            .map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?
+    } else {
+        let identity = mycelial_core::Keypair::generate();
+        identity.save_encrypted(path, &passphrase)?;
+        info!("Saved new node identity to {}", path.display());
+        identity
+    };
+    Ok(Keypair::ed25519_from_bytes(identity.to_bytes())?)
+}
+
+/// The identity file the node was started with, and how it is stored
+fn identity_source(args: &Args) -> Option<(snapshot::IdentityKind, &std::path::Path)> {
+    if let Some(path) = &args.mnemonic_file {
+        Some((snapshot::IdentityKind::Mnemonic, path))
+    } else {
+        args.identity_file
+            .as_deref()
+            .map(|path| (snapshot::IdentityKind::KeyFile, path))
+    }
+}
+
+/// Load the node keypair from an existing identity file of either kind
+fn load_identity(kind: snapshot::IdentityKind, path: &std::path::Path) -> anyhow::Result<Keypair> {
+    match kind {
+        snapshot::IdentityKind::Mnemonic => load_mnemonic_keypair(path),
+        snapshot::IdentityKind::KeyFile => load_identity_file(path),
+    }
+}
+
+/// Bundle the database and identity into a snapshot archive at `path`
+async fn export_snapshot(args: &Args, path: &std::path::Path) -> anyhow::Result<()> {
+    let Some((kind, identity_path)) = identity_source(args) else {
+        anyhow::bail!("--export needs --identity-file or --mnemonic-file to keep the peer ID");
+    };
+    if !identity_path.exists() {
+        anyhow::bail!("Identity file {} does not exist", identity_path.display());
+    }
+    if !std::path::Path::new(&args.db).exists() {
+        anyhow::bail!("Database {} does not exist", args.db);
+    }
+
+    let keypair = load_identity(kind, identity_path)?;
+    let identity = std::fs::read(identity_path)?;
+
+    // Copy through SQLite so a node running on the database can't tear it
+    let store = SqliteStore::new(&format!("sqlite:{}", args.db)).await?;
+    let topics = store.list_subscribed_topics().await?;
+    let copy_path =
+        std::path::PathBuf::from(format!("{}.export-{}", args.db, uuid::Uuid::new_v4()));
+    store.backup_to(&copy_path).await?;
+    store.close().await?;
+    let database = std::fs::read(&copy_path);
+    let _ = std::fs::remove_file(&copy_path);
+
+    let peer_id = keypair.public().to_peer_id();
+    snapshot::Snapshot::new(peer_id.to_string(), kind, identity, database?, topics).write(path)?;
+    info!("Exported snapshot of {} to {}", peer_id, path.display());
+    Ok(())
+}
+
+/// Restore the database and identity from the snapshot archive at `path`
+///
+/// Refuses to overwrite either, so a snapshot can only seed a fresh node.
+/// Nothing is restored unless the identity decrypts to the snapshot's peer
+/// ID with the configured passphrase.
+fn import_snapshot(args: &Args, path: &std::path::Path) -> anyhow::Result<snapshot::Manifest> {
+    let snapshot = snapshot::Snapshot::read(path)?;
+    let kind = snapshot.manifest.identity;
+
+    let identity_path = match identity_source(args) {
+        Some((source_kind, path)) if source_kind == kind => path,
+        _ => anyhow::bail!(
+            "Pass {} to say where to restore the snapshot's identity",
+            kind.flag()
+        ),
+    };
+    let db_path = std::path::Path::new(&args.db);
+    for existing in [identity_path, db_path] {
+        if existing.exists() {
+            anyhow::bail!("Refusing to import over existing {}", existing.display());
+        }
+    }
+
+    // Check the identity under a temporary name, so a wrong passphrase
+    // leaves nothing behind
+    let staged = std::path::PathBuf::from(format!(
+        "{}.import-{}",
+        identity_path.display(),
+        uuid::Uuid::new_v4()
+    ));
+    write_secret_file(&staged, &snapshot.identity)?;
+    let restored = load_identity(kind, &staged)
+        .map(|keypair| keypair.public().to_peer_id())
+        .and_then(|peer_id| {
+            if peer_id.to_string() != snapshot.manifest.peer_id {
+                anyhow::bail!(
+                    "Restored identity gives peer ID {}, but the snapshot was taken from {}; \
+                     check the identity passphrase",
+                    peer_id,
+                    snapshot.manifest.peer_id
+                );
+            }
+            write_secret_file(db_path, &snapshot.database)?;
+            std::fs::rename(&staged, identity_path).map_err(|e| {
+                let _ = std::fs::remove_file(db_path);
+                e.into()
+            })
+        });
+    if let Err(e) = restored {
+        let _ = std::fs::remove_file(&staged);
+        return Err(e);
+    }
+    info!(
+        "Imported snapshot of {} taken {} ({} topics)",
+        snapshot.manifest.peer_id,
+        snapshot.manifest.created_at,
+        snapshot.manifest.subscribed_topics.len()
+    );
+    Ok(snapshot.manifest)
+}
+
+/// Read the dashboard API token, generating one if the file doesn't exist
+fn load_api_token(path: &std::path::Path) -> anyhow::Result<String> {
+    if path.exists() {
+        let token = std::fs::read_to_string(path)?.trim().to_string();
+        if token.is_empty() {
+            anyhow::bail!("{}: API token file is empty", path.display());
+        }
+        return Ok(token);
+    }
+
+    let token = hex::encode(rand::random::<[u8; 32]>());
+    write_secret_file(path, format!("{}\n", token).as_bytes())?;
+    info!("Generated a new API token in {}", path.display());
+    Ok(token)
+}
+
+/// Resolve when the process receives Ctrl-C
+async fn shutdown_signal() {
+    if let Err(e) = tokio::signal::ctrl_c().await {
+        error!("Failed to listen for Ctrl-C: {}", e);
+        // Never resolve so the server keeps running
+        std::future::pending::<()>().await;
+    }
+    info!("Received Ctrl-C, starting graceful shutdown");
+}
+
+/// Records the locations carried by Discovery messages
+///
+/// Positions bridged from LoRa GPS arrive this way.
+struct PeerLocationHandler {
+    store: SqliteStore,
+    peer_manager: Arc<PeerManager>,
+}
+
+#[async_trait]
+impl MessageHandler for PeerLocationHandler {
+    async fn handle(
+        &self,
+        message: Message,
+        _from: PeerId,
+    ) -> mycelial_core::Result<Option<Message>> {
+        if let Ok(location) = serde_cbor::from_slice::<Location>(&message.payload) {
+            // LoRa nodes have no libp2p identity and are only stored
+            if let Ok(peer_id) = message.sender.as_str().parse::<Libp2pPeerId>() {
+                self.peer_manager.set_location(peer_id, location.clone());
+            }
+            record_peer_location(&self.store, message.sender, location).await;
+        }
+        Ok(None)
+    }
+}
+
+/// Attach a reported location to a peer, creating the peer if it is new
+///
+/// LoRa nodes reach us only through the bridge, so they may never have
+/// connected directly.
+async fn record_peer_location(store: &SqliteStore, peer_id: PeerId, location: Location) {
+    debug!(
+        "Peer {} reported location {:.5}, {:.5}",
+        peer_id.short(),
+        location.latitude,
+        location.longitude
+    );
+
+    let result = match store.get_peer(peer_id.as_str()).await {
+        Ok(Some(_)) => {
+            store
+                .update_peer_location(peer_id.as_str(), &location)
+                .await
+        }
+        Ok(None) => {
+            let now = chrono::Utc::now();
+            let peer_info = PeerInfo {
+                public_key: peer_id.to_string(),
+                name: Some(format!("Peer-{}", peer_id.short())),
+                id: peer_id,
+                addresses: vec![],
+                first_seen: now,
+                last_seen: now,
+                location: Some(location),
+            };
+            store
+                .upsert_peer(&peer_info, Some(&Reputation::default()))
+                .await
+        }
+        Err(e) => Err(e),
+    };
+
+    if let Err(e) = result {
+        warn!("Failed to store peer location: {}", e);
+    }
+}
+
+/// Send every applied credit transfer and septal gate change to the dashboard
+///
+/// Covers this node's own transfers and gate transitions as well as the
+/// ones learned from gossip, and only once the bridge has accepted them.
+async fn forward_enr_events(state: Arc<AppState>) {
+    let mut transfers = state.enr_bridge.subscribe_credit_events();
+    let mut septal = state.enr_bridge.subscribe_septal_events();
+    let local_node = state.enr_bridge.local_node_id();
+
+    loop {
+        tokio::select! {
+            transfer = transfers.recv() => match transfer {
+                Ok(msg) => {
+                    let transfer = msg.transfer;
+                    let timestamp = chrono::Utc::now().timestamp_millis();
+                    let _ = state.event_tx.send(WsMessage::EnrCreditTransfer {
+                        from: transfer.from.node.to_string(),
+                        to: transfer.to.node.to_string(),
+                        amount: transfer.amount.amount,
+                        tax: transfer.entropy_cost.amount,
+                        nonce: msg.nonce,
+                        timestamp,
+                    });
+                    if transfer.from.node == local_node || transfer.to.node == local_node {
+                        let _ = state.event_tx.send(WsMessage::EnrBalanceUpdate {
+                            node_id: state.local_peer_id.to_string(),
+                            balance: state.enr_bridge.local_balance().await.amount,
+                            timestamp,
+                        });
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    warn!("Dashboard missed {} credit transfers", missed);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            change = septal.recv() => match change {
+                Ok(change) => {
+                    let _ = state.event_tx.send(WsMessage::SeptalStateChange {
+                        node_id: change.node.to_string(),
+                        from_state: format!("{:?}", change.from_state),
+                        to_state: format!("{:?}", change.to_state),
+                        reason: change.reason,
+                        timestamp: change.timestamp.millis as i64,
+                    });
+                }
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    warn!("Dashboard missed {} septal gate changes", missed);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+        }
+    }
+}
+
 /// Handle events from the P2P network
 async fn handle_network_event(event: NetworkEvent, state: &AppState, local_peer_id: Libp2pPeerId) {
     match event {
@@ -292,8 +1114,8 @@ async fn handle_network_event(event: NetworkEvent, state: &AppState, local_peer_
         } => {
             info!("Peer connected: {} (total: {})", peer_id, num_connections);
 
-            let core_peer_id = PeerId(peer_id.to_base58());
-            let short_id = &peer_id.to_base58()[..8.min(peer_id.to_base58().len())];
+            let core_peer_id = PeerId::from(peer_id);
+            let short_id = core_peer_id.short();
 
             // Create peer info
             // Use peer_id's base58 as public_key (PeerId is derived from public key)
@@ -304,6 +1126,7 @@ async fn handle_network_event(event: NetworkEvent, state: &AppState, local_peer_
                 first_seen: chrono::Utc::now(),
                 last_seen: chrono::Utc::now(),
                 name: Some(format!("Peer-{}", short_id)),
+                location: None,
             };
 
             // Store peer with default reputation
@@ -355,6 +1178,11 @@ async fn handle_network_event(event: NetworkEvent, state: &AppState, local_peer_
             // Check if this is an economics protocol message
             if is_economics_topic(&topic) {
                 if let Some(econ_event) = parse_economics_message(&topic, &data) {
+                    // Only the named author may vouch, vote or spend in their name
+                    if let Err(reason) = econ_event.verify_source(source.as_ref()) {
+                        warn!("Dropping unverified message on {}: {}", topic, reason);
+                        return;
+                    }
                     match econ_event {
                         EconomicsEvent::Vouch(vouch_msg) => {
                             use mycelial_protocol::VouchMessage;
@@ -368,6 +1196,9 @@ async fn handle_network_event(event: NetworkEvent, state: &AppState, local_peer_
                                         weight: req.stake,
                                         accepted: false, // Pending until ack
                                         created_at: ts,
+                                        expires_at: req
+                                            .expires_at
+                                            .map(|expires_at| expires_at.timestamp_millis()),
                                     });
 
                                     let _ = state.event_tx.send(WsMessage::VouchRequest {
@@ -395,11 +1226,34 @@ async fn handle_network_event(event: NetworkEvent, state: &AppState, local_peer_
                                     });
                                 }
                                 VouchMessage::ReputationUpdate(update) => {
+                                    // Anyone may send these, so show our own
+                                    // score for the peer rather than theirs
+                                    let new_score = state.economics.get_reputation(&update.peer_id);
                                     let _ = state.event_tx.send(WsMessage::ReputationUpdate {
                                         peer_id: update.peer_id,
-                                        new_score: update.score,
+                                        new_score,
                                     });
                                 }
+                                VouchMessage::VouchRevoke(revoke) => {
+                                    let removed = state.economics.revoke_vouch(
+                                        &revoke.vouch_id.to_string(),
+                                        &revoke.voucher,
+                                    );
+                                    match removed {
+                                        Some(removed) if removed.vouch.accepted => {
+                                            let _ =
+                                                state.event_tx.send(WsMessage::ReputationUpdate {
+                                                    peer_id: removed.vouch.vouchee,
+                                                    new_score: removed.reputation,
+                                                });
+                                        }
+                                        Some(_) => {}
+                                        None => debug!(
+                                            "Ignoring revocation of unknown vouch {} from {}",
+                                            revoke.vouch_id, revoke.voucher
+                                        ),
+                                    }
+                                }
                             }
                         }
                         EconomicsEvent::Credit(credit_msg) => {
@@ -417,6 +1271,10 @@ async fn handle_network_event(event: NetworkEvent, state: &AppState, local_peer_
                                         balance: 0.0,
                                         created_at: ts,
                                         updated_at: ts,
+                                        interest_rate: line.interest_rate,
+                                        accrued_interest: 0.0,
+                                        last_accrued: ts,
+                                        history: Vec::new(),
                                     });
 
                                     let _ = state.event_tx.send(WsMessage::CreditLine {
@@ -439,9 +1297,11 @@ async fn handle_network_event(event: NetworkEvent, state: &AppState, local_peer_
                                         // transfer.from is debtor, transfer.to is creditor
                                         // Debtor paying back - decrease balance
                                         let new_balance = (line.balance - transfer.amount).max(0.0);
-                                        state
-                                            .economics
-                                            .update_credit_balance(&line.id, new_balance);
+                                        state.economics.update_credit_balance(
+                                            &line.id,
+                                            new_balance,
+                                            transfer.memo.clone(),
+                                        );
                                     } else if let Some(line) = state
                                         .economics
                                         .get_credit_line_between(&transfer.from, &transfer.to)
@@ -450,9 +1310,11 @@ async fn handle_network_event(event: NetworkEvent, state: &AppState, local_peer_
                                         // Extending credit - increase balance
                                         let new_balance =
                                             (line.balance + transfer.amount).min(line.limit);
-                                        state
-                                            .economics
-                                            .update_credit_balance(&line.id, new_balance);
+                                        state.economics.update_credit_balance(
+                                            &line.id,
+                                            new_balance,
+                                            transfer.memo.clone(),
+                                        );
                                     }
 
                                     let _ = state.event_tx.send(WsMessage::CreditTransfer {
@@ -479,7 +1341,7 @@ async fn handle_network_event(event: NetworkEvent, state: &AppState, local_peer_
                             }
                         }
                         EconomicsEvent::Governance(gov_msg) => {
-                            use mycelial_protocol::GovernanceMessage;
+                            use mycelial_protocol::{GovernanceMessage, ProposalType};
                             match gov_msg {
                                 GovernanceMessage::CreateProposal(proposal) => {
                                     let proposal_id = proposal.id.to_string();
@@ -497,9 +1359,22 @@ async fn handle_network_event(event: NetworkEvent, state: &AppState, local_peer_
                                         yes_votes: 0.0,
                                         no_votes: 0.0,
                                         quorum: proposal.quorum,
+                                        threshold: proposal.threshold,
                                         deadline: deadline_ms,
                                         created_at: ts,
                                         votes: std::collections::HashMap::new(),
+                                        tally: None,
+                                        parameter_change: match &proposal.proposal_type {
+                                            ProposalType::ParameterChange {
+                                                parameter,
+                                                new_value,
+                                                ..
+                                            } => Some(ParameterChange {
+                                                parameter: parameter.clone(),
+                                                new_value: new_value.clone(),
+                                            }),
+                                            _ => None,
+                                        },
                                     });
 
                                     let _ = state.event_tx.send(WsMessage::Proposal {
@@ -520,12 +1395,11 @@ async fn handle_network_event(event: NetworkEvent, state: &AppState, local_peer_
                                     let proposal_id = vote.proposal_id.to_string();
 
                                     // Parse vote type
-                                    let vote_type =
-                                        match format!("{:?}", vote.vote).to_lowercase().as_str() {
-                                            "yes" => VoteType::Yes,
-                                            "no" => VoteType::No,
-                                            _ => VoteType::Abstain,
-                                        };
+                                    let vote_type = match vote.vote {
+                                        mycelial_protocol::Vote::For => VoteType::Yes,
+                                        mycelial_protocol::Vote::Against => VoteType::No,
+                                        mycelial_protocol::Vote::Abstain => VoteType::Abstain,
+                                    };
 
                                     // Record vote in state
                                     state.economics.record_vote(
@@ -548,18 +1422,25 @@ async fn handle_network_event(event: NetworkEvent, state: &AppState, local_peer_
                                     });
                                 }
                                 GovernanceMessage::ProposalUpdate(update) => {
-                                    // votes_for/against are f64 (weighted), convert to u32 counts
+                                    // Anyone may send these, so only use them as
+                                    // a cue to show our own tally of the votes
+                                    let Some(proposal) = state
+                                        .economics
+                                        .get_proposal(&update.proposal_id.to_string())
+                                    else {
+                                        return;
+                                    };
                                     let _ = state.event_tx.send(WsMessage::Proposal {
-                                        id: update.proposal_id.to_string(),
-                                        proposer: "".to_string(),
-                                        title: "".to_string(),
-                                        description: "".to_string(),
-                                        proposal_type: "".to_string(),
-                                        status: format!("{:?}", update.status),
-                                        yes_votes: update.votes_for as u32,
-                                        no_votes: update.votes_against as u32,
-                                        quorum: 0,
-                                        deadline: 0,
+                                        id: proposal.id,
+                                        proposer: proposal.proposer,
+                                        title: proposal.title,
+                                        description: proposal.description,
+                                        proposal_type: proposal.proposal_type,
+                                        status: proposal.status.to_string(),
+                                        yes_votes: proposal.yes_votes as u32,
+                                        no_votes: proposal.no_votes as u32,
+                                        quorum: (proposal.quorum * 100.0) as u32,
+                                        deadline: proposal.deadline,
                                         timestamp: ts,
                                     });
                                 }
@@ -577,11 +1458,14 @@ async fn handle_network_event(event: NetworkEvent, state: &AppState, local_peer_
                                     // Record contribution in state
                                     state.economics.record_resource_contribution(
                                         ResourceContribution {
+                                            id: contrib.id.to_string(),
                                             peer_id: contrib.peer_id.clone(),
                                             resource_type: resource_type.clone(),
                                             amount: contrib.amount,
                                             unit: contrib.unit.clone(),
                                             timestamp: ts,
+                                            verified_amount: 0.0,
+                                            consumers: Vec::new(),
                                         },
                                     );
 
@@ -594,14 +1478,19 @@ async fn handle_network_event(event: NetworkEvent, state: &AppState, local_peer_
                                         timestamp: ts,
                                     });
                                 }
-                                ResourceMessage::PoolUpdate(pool) => {
-                                    let contributors: Vec<ContributorEntry> = pool
-                                        .top_contributors
-                                        .iter()
-                                        .map(|c| ContributorEntry {
-                                            peer_id: c.peer_id.clone(),
-                                            contribution: c.contribution_score,
-                                            percentage: 0.0, // Not available in protocol type
+                                ResourceMessage::PoolUpdate(_) => {
+                                    // Anyone may send these, so show the pool as
+                                    // corroborated by the receipts we've seen
+                                    let pool = state.economics.get_resource_pool();
+                                    let verified = state.economics.get_verified_contributors();
+                                    let total: f64 =
+                                        verified.iter().map(|(_, amount)| amount).sum();
+                                    let contributors: Vec<ContributorEntry> = verified
+                                        .into_iter()
+                                        .map(|(peer_id, contribution)| ContributorEntry {
+                                            peer_id,
+                                            contribution,
+                                            percentage: contribution / total * 100.0,
                                         })
                                         .collect();
                                     let _ = state.event_tx.send(WsMessage::ResourcePoolUpdate {
@@ -615,17 +1504,42 @@ async fn handle_network_event(event: NetworkEvent, state: &AppState, local_peer_
                                 ResourceMessage::Metrics(_) => {
                                     // Handle resource metrics if needed
                                 }
+                                ResourceMessage::Receipt(receipt) => {
+                                    let consumer_connected = state
+                                        .network
+                                        .get_peers()
+                                        .await
+                                        .unwrap_or_default()
+                                        .into_iter()
+                                        .any(|peer| {
+                                            PeerId::from(peer).as_str() == receipt.consumer
+                                        });
+                                    match state
+                                        .economics
+                                        .record_receipt(&receipt, consumer_connected)
+                                    {
+                                        Some(contribution) => debug!(
+                                            "Contribution {} from {} verified to {:.2}/{:.2} {}",
+                                            contribution.id,
+                                            contribution.peer_id,
+                                            contribution.verified_amount,
+                                            contribution.amount,
+                                            contribution.unit
+                                        ),
+                                        None => debug!(
+                                            "Ignoring receipt for contribution {} from {}",
+                                            receipt.contribution_id, receipt.consumer
+                                        ),
+                                    }
+                                }
                             }
                         }
                     }
                 }
             }
-            // Check if this is an ENR bridge message
-            else if topic == GRADIENT_TOPIC
-                || topic == CREDIT_TOPIC
-                || topic == ELECTION_TOPIC
-                || topic == SEPTAL_TOPIC
-            {
+            // ENR messages are already routed by the network service's
+            // EnrBridge; here they are only mirrored to the dashboard
+            else if BRIDGE_TOPICS.contains(&topic.as_str()) {
                 match EnrMessage::decode(&data) {
                     Ok(enr_msg) => {
                         use mycelial_network::enr_bridge::messages::*;
@@ -640,15 +1554,8 @@ async fn handle_network_event(event: NetworkEvent, state: &AppState, local_peer_
                                     timestamp: update.timestamp.millis as i64,
                                 });
                             }
-                            EnrMessage::CreditTransfer(transfer_msg) => {
-                                let _ = state.event_tx.send(WsMessage::EnrCreditTransfer {
-                                    from: format!("{}", transfer_msg.transfer.from.node),
-                                    to: format!("{}", transfer_msg.transfer.to.node),
-                                    amount: transfer_msg.transfer.amount.amount,
-                                    tax: transfer_msg.transfer.entropy_cost.amount,
-                                    nonce: transfer_msg.nonce,
-                                    timestamp: ts,
-                                });
+                            EnrMessage::CreditTransfer(_) => {
+                                // Announced once applied, by forward_enr_events
                             }
                             EnrMessage::BalanceQuery(_) => {
                                 // Balance queries are internal, no dashboard broadcast
@@ -699,18 +1606,15 @@ async fn handle_network_event(event: NetworkEvent, state: &AppState, local_peer_
                                             timestamp: result.timestamp.millis as i64,
                                         });
                                     }
+                                    ElectionMessage::Heartbeat(_) => {
+                                        // Heartbeats are internal, no dashboard broadcast
+                                    }
                                 }
                             }
                             EnrMessage::Septal(septal_msg) => {
                                 match septal_msg {
-                                    SeptalMessage::StateChange(change) => {
-                                        let _ = state.event_tx.send(WsMessage::SeptalStateChange {
-                                            node_id: change.node.to_string(),
-                                            from_state: format!("{:?}", change.from_state),
-                                            to_state: format!("{:?}", change.to_state),
-                                            reason: change.reason,
-                                            timestamp: change.timestamp.millis as i64,
-                                        });
+                                    SeptalMessage::StateChange(_) => {
+                                        // Announced once applied, by forward_enr_events
                                     }
                                     SeptalMessage::HealthProbe(_) => {
                                         // Health probes are internal, no dashboard broadcast
@@ -733,14 +1637,45 @@ async fn handle_network_event(event: NetworkEvent, state: &AppState, local_peer_
                     }
                 }
             }
+            // Core messages go to the handlers registered for their type
+            else if topic == topics::ANNOUNCE {
+                if let Ok(message) = serde_cbor::from_slice::<Message>(&data) {
+                    let from = source
+                        .map(PeerId::from)
+                        .unwrap_or_else(|| message.sender.clone());
+
+                    match state.handlers.dispatch(message, from).await {
+                        Ok(responses) => {
+                            for response in responses {
+                                let Ok(bytes) = serde_cbor::to_vec(&response) else {
+                                    continue;
+                                };
+                                if let Err(e) = state.network.publish(&topic, bytes).await {
+                                    warn!("Failed to publish handler response: {}", e);
+                                }
+                            }
+                        }
+                        Err(e) => warn!("Failed to handle message on {}: {}", topic, e),
+                    }
+                }
+            }
             // Try to parse as chat message (handles chat, content, direct, and room topics)
             else if topic.contains("chat")
                 || topic.contains("content")
                 || topic.contains("direct")
                 || topic.contains("room")
             {
-                if let Ok(content) = String::from_utf8(data.clone()) {
-                    let short_from = &from_id[..8.min(from_id.len())];
+                // Content-wrapped payloads say whether they are text; skip binary ones
+                let text = match serde_json::from_slice::<Content>(&data) {
+                    Ok(content) => content.as_str().map(str::to_string),
+                    Err(_) => String::from_utf8(data.clone()).ok(),
+                };
+
+                if let Some(content) = text {
+                    let from_name = source.map(PeerId::from).map_or_else(
+                        || "Peer-unknown".to_string(),
+                        |sender| format!("Peer-{}", sender.short()),
+                    );
 
                     // Extract room_id from topic if it's a room message
                     // Topic format: /mycelial/1.0.0/room/{room_id}
@@ -753,12 +1688,15 @@ async fn handle_network_event(event: NetworkEvent, state: &AppState, local_peer_
                     let _ = state.event_tx.send(WsMessage::ChatMessage {
                         id: message_id.to_string(),
                         from: from_id.clone(),
-                        from_name: format!("Peer-{}", short_from),
+                        from_name,
                         to: None,
                         room_id,
                         content,
                         timestamp: ts,
+                        verified: source.is_some(),
                     });
+                } else {
+                    debug!("Skipping non-text message on {}", topic);
                 }
             }
         }
@@ -771,15 +1709,33 @@ async fn handle_network_event(event: NetworkEvent, state: &AppState, local_peer_
             info!("  Full multiaddr (use this to connect):");
             info!("    {}", full_multiaddr);
             info!("═══════════════════════════════════════════════════════════");
+            let mut listen_addrs = state.listen_addrs.write();
+            if !listen_addrs.contains(&full_multiaddr) {
+                listen_addrs.push(full_multiaddr);
+            }
+        }
+
+        NetworkEvent::ListenAddrExpired { address } => {
+            let full_multiaddr = format!("{}/p2p/{}", address, local_peer_id);
+            state
+                .listen_addrs
+                .write()
+                .retain(|addr| *addr != full_multiaddr);
         }
 
         NetworkEvent::Subscribed { topic } => {
             info!("Subscribed to topic: {}", topic);
+            if let Err(e) = state.store.add_subscribed_topic(&topic).await {
+                warn!("Failed to persist subscription to {}: {}", topic, e);
+            }
             state.subscribed_topics.write().push(topic);
         }
 
         NetworkEvent::Unsubscribed { topic } => {
             info!("Unsubscribed from topic: {}", topic);
+            if let Err(e) = state.store.remove_subscribed_topic(&topic).await {
+                warn!("Failed to forget subscription to {}: {}", topic, e);
+            }
             state.subscribed_topics.write().retain(|t| t != &topic);
         }
 
@@ -806,6 +1762,78 @@ async fn handle_network_event(event: NetworkEvent, state: &AppState, local_peer_
             error: _,
         } => {}
 
+        NetworkEvent::TopicIdle { topic, idle } => {
+            info!(
+                "Left idle topic {} after {}s without traffic",
+                topic,
+                idle.as_secs()
+            );
+        }
+
+        NetworkEvent::TopicResumed { topic } => {
+            info!("Rejoined idle topic {} to publish", topic);
+        }
+
+        NetworkEvent::MeshUpdated {
+            topic,
+            mesh_peers,
+            all_peers,
+        } => {
+            debug!(
+                "Mesh for {}: {} of {} subscribers",
+                topic, mesh_peers, all_peers
+            );
+            let _ = state.event_tx.send(WsMessage::MeshUpdated {
+                topic,
+                mesh_peers,
+                all_peers,
+            });
+        }
+
+        NetworkEvent::PublishFailed {
+            topic,
+            attempts,
+            error,
+        } => {
+            warn!(
+                "Dropped message for {} after {} attempts: {}",
+                topic, attempts, error
+            );
+        }
+
+        NetworkEvent::BootstrapConnected { peer_id, address } => {
+            info!("Bootstrap connected: {} via {}", peer_id, address);
+        }
+
+        // Septal bans last only as long as the gate's state, so aren't kept
+        NetworkEvent::PeerBanned { peer_id, ban } if ban.reason != SEPTAL_BAN_REASON => {
+            let stored = StoredPeerBan {
+                peer_id: ban.peer_id,
+                reason: ban.reason,
+                banned_at: ban.banned_at,
+                until: ban.until,
+            };
+            if let Err(e) = state.store.save_peer_ban(&stored).await {
+                warn!("Failed to persist ban on {}: {}", peer_id, e);
+            }
+        }
+
+        NetworkEvent::PeerUnbanned { peer_id } => {
+            if let Err(e) = state.store.remove_peer_ban(&peer_id.to_string()).await {
+                warn!("Failed to forget ban on {}: {}", peer_id, e);
+            }
+        }
+
+        NetworkEvent::BootstrapProgress {
+            remaining,
+            routing_table_size,
+        } => {
+            debug!(
+                "Kademlia bootstrap: {} buckets left, {} peers in routing table",
+                remaining, routing_table_size
+            );
+        }
+
         NetworkEvent::MdnsDiscovered { peers } => {
             for (peer_id, addr) in &peers {
                 info!("mDNS discovered: {} at {}", peer_id, addr);
@@ -815,3 +1843,83 @@ async fn handle_network_event(event: NetworkEvent, state: &AppState, local_peer_
         _ => {}
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_snapshot_export_import() {
+        let dir = tempfile::tempdir().unwrap();
+        let args = |db: &str, identity: &str| {
+            let db = dir.path().join(db);
+            let identity = dir.path().join(identity);
+            Args::parse_from([
+                "mycelial-node".as_ref(),
+                "--db".as_ref(),
+                db.as_os_str(),
+                "--identity-file".as_ref(),
+                identity.as_os_str(),
+            ])
+        };
+
+        // A node with an identity and a database
+        let source = args("source.db", "source.key");
+        let source_key = source.identity_file.as_deref().unwrap();
+        let peer_id = load_identity_file(source_key)
+            .unwrap()
+            .public()
+            .to_peer_id();
+        let store = SqliteStore::new(&format!("sqlite:{}?mode=rwc", source.db))
+            .await
+            .unwrap();
+        store
+            .add_subscribed_topic("/mycelial/1.0.0/chat")
+            .await
+            .unwrap();
+        store.close().await.unwrap();
+
+        let archive = dir.path().join("snapshot.tar");
+        export_snapshot(&source, &archive).await.unwrap();
+
+        // A fresh node takes over the peer ID and the database
+        let restored = args("restored.db", "restored.key");
+        let manifest = import_snapshot(&restored, &archive).unwrap();
+        assert_eq!(manifest.peer_id, peer_id.to_string());
+        let restored_key = restored.identity_file.as_deref().unwrap();
+        let keypair = load_identity_file(restored_key).unwrap();
+        assert_eq!(keypair.public().to_peer_id(), peer_id);
+        let store = SqliteStore::new(&format!("sqlite:{}", restored.db))
+            .await
+            .unwrap();
+        assert_eq!(
+            store.list_subscribed_topics().await.unwrap(),
+            vec!["/mycelial/1.0.0/chat"]
+        );
+        store.close().await.unwrap();
+
+        // Never over an existing node
+        assert!(import_snapshot(&restored, &archive).is_err());
+
+        // An identity that isn't the snapshot's peer leaves nothing behind
+        let snapshot = snapshot::Snapshot::read(&archive).unwrap();
+        let mismatched = dir.path().join("mismatched.tar");
+        snapshot::Snapshot::new(
+            Libp2pPeerId::random().to_string(),
+            snapshot::IdentityKind::KeyFile,
+            snapshot.identity,
+            snapshot.database,
+            Vec::new(),
+        )
+        .write(&mismatched)
+        .unwrap();
+        let other = args("other.db", "other.key");
+        assert!(import_snapshot(&other, &mismatched).is_err());
+        let leftovers: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .filter(|name| name.starts_with("other"))
+            .collect();
+        assert!(leftovers.is_empty(), "left behind {:?}", leftovers);
+    }
+}
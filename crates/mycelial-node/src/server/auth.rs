@@ -0,0 +1,57 @@
+//! Bearer-token authentication for the dashboard API
+//!
+//! Off unless the node is started with an API token. When on, every route
+//! except `/health` needs the token, either as `Authorization: Bearer <token>`
+//! or, for browser WebSockets that can't set headers, as `?token=<token>`.
+
+use axum::{
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+
+use crate::AppState;
+
+/// Reject requests that don't carry the node's API token
+pub async fn require_token(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(expected) = &state.api_token else {
+        return next.run(request).await;
+    };
+    match presented_token(&request) {
+        Some(token) if tokens_match(token, expected) => next.run(request).await,
+        _ => StatusCode::UNAUTHORIZED.into_response(),
+    }
+}
+
+/// The token a request carries, from its header or query string
+fn presented_token(request: &Request) -> Option<&str> {
+    let from_header = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    from_header.or_else(|| {
+        request
+            .uri()
+            .query()?
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("token="))
+    })
+}
+
+/// Compare tokens without leaking how much of a guess was right
+fn tokens_match(presented: &str, expected: &str) -> bool {
+    presented.len() == expected.len()
+        && presented
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
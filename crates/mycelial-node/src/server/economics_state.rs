@@ -5,14 +5,38 @@
 //! - Active governance proposals
 //! - Vouch relationships
 //! - Resource contributions
-
+//!
+//! The full state can be snapshotted to SQLite with [`EconomicsStateManager::persist`]
+//! and restored on startup with [`EconomicsStateManager::load`].
+
+use chrono::{DateTime, Utc};
+use mycelial_core::credit::{self, CreditEntry, CreditEntryKind};
+use mycelial_network::enr_bridge::EntropyTaxRate;
+use mycelial_protocol::{
+    governance, topics, GovernanceMessage, ProposalExecuted, ResourceReceipt, TallyOutcome,
+    Vote as ProtocolVote,
+};
+use mycelial_state::{EconomicsSnapshot, SqliteStore, StateError, ECONOMICS_SCHEMA_VERSION};
 use parking_lot::RwLock;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::time::Instant;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use super::messages::WsMessage;
+use crate::AppState;
+
+/// Reputation a consumer that isn't connected to us needs for its
+/// resource receipts to count
+pub const MIN_RECEIPT_CONSUMER_REPUTATION: f64 = 0.6;
+
+/// Economics schema version from which contributions carry the amount
+/// corroborated by receipts
+const RECEIPT_SCHEMA_VERSION: i64 = 2;
 
 /// Credit line between two peers
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreditLine {
     pub id: String,
     pub creditor: String,
@@ -21,10 +45,37 @@ pub struct CreditLine {
     pub balance: f64,
     pub created_at: i64,
     pub updated_at: i64,
+    /// Annual interest rate charged on the outstanding balance
+    #[serde(default)]
+    pub interest_rate: f64,
+    /// Total interest the debtor has been charged on this line
+    #[serde(default)]
+    pub accrued_interest: f64,
+    /// When interest was last charged, in milliseconds since the epoch
+    ///
+    /// 0 in snapshots from before it was kept, when `updated_at` stands in.
+    #[serde(default)]
+    pub last_accrued: i64,
+    /// Transfers and interest on this line, oldest first, capped at
+    /// [`credit::MAX_CREDIT_HISTORY`] entries
+    #[serde(default)]
+    pub history: Vec<CreditEntry>,
 }
 
-/// Governance proposal
+/// A peer's transfers on one credit line over a period
 #[derive(Debug, Clone, Serialize)]
+pub struct CreditStatement {
+    pub line_id: String,
+    pub creditor: String,
+    pub debtor: String,
+    pub limit: f64,
+    /// Balance before the first entry of the period
+    pub opening_balance: f64,
+    pub entries: Vec<CreditEntry>,
+}
+
+/// Governance proposal
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Proposal {
     pub id: String,
     pub proposer: String,
@@ -35,12 +86,114 @@ pub struct Proposal {
     pub yes_votes: f64,
     pub no_votes: f64,
     pub quorum: f64,
+    /// Fraction of yes/(yes+no) weight required to pass
+    #[serde(default = "default_threshold")]
+    pub threshold: f64,
     pub deadline: i64,
     pub created_at: i64,
     pub votes: HashMap<String, Vote>,
+    /// Final tally, set once the proposal is closed
+    #[serde(default)]
+    pub tally: Option<ProposalTally>,
+    /// Network parameter the proposal changes if it passes
+    #[serde(default)]
+    pub parameter_change: Option<ParameterChange>,
+}
+
+fn default_threshold() -> f64 {
+    0.5
+}
+
+/// Governance parameter naming the revival-pool tax on credit transfers
+pub const ENTROPY_TAX_PARAMETER: &str = "entropy_tax_rate";
+
+/// How long economics messages are retried while the topic has no peers,
+/// e.g. right after startup
+pub const ECONOMICS_PUBLISH_DEADLINE: Duration = Duration::from_secs(60);
+
+/// A network parameter change carried by a proposal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParameterChange {
+    pub parameter: String,
+    pub new_value: String,
+}
+
+impl ParameterChange {
+    /// The new entropy tax rate, if this change sets one
+    pub fn entropy_tax_rate(&self) -> Option<f64> {
+        if self.parameter != ENTROPY_TAX_PARAMETER {
+            return None;
+        }
+        self.new_value.trim().parse().ok()
+    }
+}
+
+/// Result of tallying a proposal's votes at its deadline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProposalTally {
+    pub yes_weight: f64,
+    pub no_weight: f64,
+    pub abstain_weight: f64,
+    pub voter_count: usize,
+    /// Whether total participating weight (including abstentions) met quorum
+    pub quorum_reached: bool,
+    /// yes / (yes + no), or 0 when nobody voted yes or no
+    pub approval: f64,
+    /// Yes and no weight were exactly equal
+    pub tie: bool,
+    pub outcome: ProposalStatus,
+    pub closed_at: i64,
+}
+
+impl ProposalTally {
+    /// Tally the votes recorded on a proposal
+    ///
+    /// Uses [`governance::tally_weighted`], so the quorum and threshold are
+    /// enforced exactly as every other peer enforces them:
+    ///
+    /// - Below quorum (including no votes at all) the proposal is `Expired`.
+    /// - A tie never passes, regardless of threshold, and is `Rejected`.
+    /// - Otherwise it is `Passed` if approval meets the threshold, else `Rejected`.
+    pub fn compute(proposal: &Proposal, now: i64) -> Self {
+        let votes: Vec<_> = proposal
+            .votes
+            .values()
+            .map(|vote| {
+                let vote_type = match vote.vote_type {
+                    VoteType::Yes => ProtocolVote::For,
+                    VoteType::No => ProtocolVote::Against,
+                    VoteType::Abstain => ProtocolVote::Abstain,
+                };
+                (vote_type, vote.weight)
+            })
+            .collect();
+        let result = governance::tally_weighted(
+            proposal.quorum,
+            proposal.threshold,
+            votes.iter().map(|(vote, weight)| (vote, *weight)),
+        );
+
+        let outcome = match result.outcome {
+            TallyOutcome::Passed => ProposalStatus::Passed,
+            TallyOutcome::Failed => ProposalStatus::Rejected,
+            TallyOutcome::NoQuorum => ProposalStatus::Expired,
+        };
+
+        Self {
+            yes_weight: result.votes_for,
+            no_weight: result.votes_against,
+            abstain_weight: result.votes_abstain,
+            voter_count: proposal.votes.len(),
+            quorum_reached: result.quorum_reached(),
+            approval: result.approval,
+            tie: result.tie,
+            outcome,
+            closed_at: now,
+        }
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ProposalStatus {
     Active,
     Passed,
@@ -62,7 +215,7 @@ impl std::fmt::Display for ProposalStatus {
 }
 
 /// Vote on a proposal
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Vote {
     pub voter: String,
     pub vote_type: VoteType,
@@ -70,7 +223,7 @@ pub struct Vote {
     pub timestamp: i64,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum VoteType {
     Yes,
     No,
@@ -78,7 +231,7 @@ pub enum VoteType {
 }
 
 /// Vouch relationship
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Vouch {
     pub id: String,
     pub voucher: String,
@@ -86,27 +239,80 @@ pub struct Vouch {
     pub weight: f64,
     pub accepted: bool,
     pub created_at: i64,
+    /// When the vouch stops counting toward reputation (ms), if ever
+    #[serde(default)]
+    pub expires_at: Option<i64>,
+}
+
+impl Vouch {
+    /// Whether the vouch has expired by `now` (ms)
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+/// A vouch that was expired or revoked, with its effect on the vouchee
+#[derive(Debug, Clone)]
+pub struct RemovedVouch {
+    pub vouch: Vouch,
+    /// Vouchee's reputation before the vouch was removed
+    pub previous_reputation: f64,
+    /// Vouchee's reputation recomputed from their remaining vouches
+    pub reputation: f64,
 }
 
 /// Resource contribution from a peer
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResourceContribution {
+    /// Protocol contribution ID that receipts refer to
+    #[serde(default)]
+    pub id: String,
     pub peer_id: String,
     pub resource_type: String,
+    /// Amount the contributor claims
     pub amount: f64,
     pub unit: String,
     pub timestamp: i64,
+    /// Amount corroborated by consumer receipts (never more than `amount`)
+    #[serde(default)]
+    pub verified_amount: f64,
+    /// Consumers whose receipts have been counted
+    #[serde(default)]
+    pub consumers: Vec<String>,
+}
+
+impl ResourceContribution {
+    /// Whether none of the claim has been corroborated yet
+    pub fn is_pending(&self) -> bool {
+        self.verified_amount <= 0.0
+    }
 }
 
 /// Aggregated resource pool
+///
+/// Totals only include amounts corroborated by consumer receipts.
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct ResourcePool {
     pub total_bandwidth: f64,
     pub total_compute: f64,
     pub total_storage: f64,
+    /// Contributions still waiting for their first receipt
+    pub pending_contributions: usize,
     pub contributions: Vec<ResourceContribution>,
 }
 
+impl ResourcePool {
+    /// Add a verified amount to the total for its resource type
+    fn add_verified(&mut self, resource_type: &str, amount: f64) {
+        match resource_type.to_lowercase().as_str() {
+            "bandwidth" => self.total_bandwidth += amount,
+            "compute" | "cpu" => self.total_compute += amount,
+            "storage" => self.total_storage += amount,
+            _ => {}
+        }
+    }
+}
+
 /// Per-peer economics state manager
 pub struct EconomicsStateManager {
     /// Credit lines indexed by line ID
@@ -171,14 +377,103 @@ impl EconomicsStateManager {
         self.credit_lines.read().get(&id).cloned()
     }
 
-    /// Update credit line balance after transfer
-    pub fn update_credit_balance(&self, line_id: &str, new_balance: f64) {
+    /// Update credit line balance after transfer, recording it in the line's history
+    pub fn update_credit_balance(&self, line_id: &str, new_balance: f64, memo: Option<String>) {
         if let Some(line) = self.credit_lines.write().get_mut(line_id) {
+            let now = Utc::now();
+            credit::record_entry(
+                &mut line.history,
+                CreditEntry {
+                    timestamp: now,
+                    kind: CreditEntryKind::Transfer,
+                    amount: new_balance - line.balance,
+                    balance: new_balance,
+                    memo,
+                },
+            );
             line.balance = new_balance;
-            line.updated_at = chrono::Utc::now().timestamp_millis();
+            line.updated_at = now.timestamp_millis();
         }
     }
 
+    /// Compound interest onto every line with a positive rate and balance
+    /// for the time since it was last charged, returning the lines that
+    /// changed
+    ///
+    /// Lines remember when they were last charged, so the first call after
+    /// a restart also charges for the time the node was down.
+    pub fn accrue_credit_interest(&self, now: DateTime<Utc>) -> Vec<CreditLine> {
+        let now_ms = now.timestamp_millis();
+        let mut updated = Vec::new();
+        for line in self.credit_lines.write().values_mut() {
+            let since = if line.last_accrued > 0 {
+                line.last_accrued
+            } else {
+                line.updated_at
+            };
+            line.last_accrued = now_ms;
+            let elapsed = Duration::from_millis(now_ms.saturating_sub(since).max(0) as u64);
+            let interest = credit::compound_interest(line.balance, line.interest_rate, elapsed);
+            if interest <= 0.0 {
+                continue;
+            }
+
+            line.balance += interest;
+            line.accrued_interest += interest;
+            credit::record_entry(
+                &mut line.history,
+                CreditEntry {
+                    timestamp: now,
+                    kind: CreditEntryKind::Interest,
+                    amount: interest,
+                    balance: line.balance,
+                    memo: None,
+                },
+            );
+            line.updated_at = now_ms;
+            updated.push(line.clone());
+        }
+        updated
+    }
+
+    /// Statements for every credit line a peer is party to, covering
+    /// transfers between `from` and `to` (inclusive)
+    pub fn get_credit_statements(
+        &self,
+        peer_id: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Vec<CreditStatement> {
+        self.credit_lines
+            .read()
+            .values()
+            .filter(|l| l.creditor == peer_id || l.debtor == peer_id)
+            .map(|line| {
+                let entries = credit::statement(&line.history, from, to);
+                let opening_balance = entries
+                    .first()
+                    .map(|entry| entry.balance - entry.amount)
+                    .unwrap_or_else(|| {
+                        // No transfers in the period: the balance is whatever
+                        // the last earlier transfer left
+                        line.history
+                            .iter()
+                            .rev()
+                            .find(|entry| entry.timestamp < from)
+                            .map_or(0.0, |entry| entry.balance)
+                    });
+                CreditStatement {
+                    line_id: line.id.clone(),
+                    creditor: line.creditor.clone(),
+                    debtor: line.debtor.clone(),
+                    limit: line.limit,
+                    opening_balance,
+                    entries,
+                }
+            })
+            .collect()
+    }
+
     /// Get all credit lines for a peer (as creditor or debtor)
     pub fn get_credit_lines_for_peer(&self, peer_id: &str) -> Vec<CreditLine> {
         self.credit_lines
@@ -209,8 +504,24 @@ impl EconomicsStateManager {
     }
 
     /// Record a vote on a proposal
+    ///
+    /// Votes on closed proposals are ignored. A voter voting again replaces
+    /// their earlier vote.
     pub fn record_vote(&self, proposal_id: &str, vote: Vote) {
         if let Some(proposal) = self.proposals.write().get_mut(proposal_id) {
+            if proposal.status != ProposalStatus::Active {
+                return;
+            }
+
+            // Undo a previous vote from the same voter
+            if let Some(previous) = proposal.votes.get(&vote.voter) {
+                match previous.vote_type {
+                    VoteType::Yes => proposal.yes_votes -= previous.weight,
+                    VoteType::No => proposal.no_votes -= previous.weight,
+                    VoteType::Abstain => {}
+                }
+            }
+
             // Update vote counts
             match vote.vote_type {
                 VoteType::Yes => proposal.yes_votes += vote.weight,
@@ -220,27 +531,23 @@ impl EconomicsStateManager {
             proposal.votes.insert(vote.voter.clone(), vote);
 
             // Check if proposal should be resolved
-            self.check_proposal_resolution(proposal);
+            let now = chrono::Utc::now().timestamp_millis();
+            Self::close_if_expired(proposal, now);
         }
     }
 
-    /// Check if proposal has reached quorum and update status
-    fn check_proposal_resolution(&self, proposal: &mut Proposal) {
-        let total_votes = proposal.yes_votes + proposal.no_votes;
-        let now = chrono::Utc::now().timestamp_millis();
-
-        // Check expiry
-        if now > proposal.deadline {
-            if total_votes >= proposal.quorum {
-                proposal.status = if proposal.yes_votes > proposal.no_votes {
-                    ProposalStatus::Passed
-                } else {
-                    ProposalStatus::Rejected
-                };
-            } else {
-                proposal.status = ProposalStatus::Expired;
-            }
+    /// Tally and close an active proposal whose deadline has passed
+    ///
+    /// Returns true if the proposal was closed.
+    fn close_if_expired(proposal: &mut Proposal, now: i64) -> bool {
+        if proposal.status != ProposalStatus::Active || now <= proposal.deadline {
+            return false;
         }
+
+        let tally = ProposalTally::compute(proposal, now);
+        proposal.status = tally.outcome.clone();
+        proposal.tally = Some(tally);
+        true
     }
 
     /// Update proposal status
@@ -267,23 +574,18 @@ impl EconomicsStateManager {
 
     /// Check and expire old proposals
     pub fn expire_old_proposals(&self) {
-        let now = chrono::Utc::now().timestamp_millis();
-        let mut proposals = self.proposals.write();
-
-        for proposal in proposals.values_mut() {
-            if proposal.status == ProposalStatus::Active && now > proposal.deadline {
-                let total_votes = proposal.yes_votes + proposal.no_votes;
-                if total_votes >= proposal.quorum {
-                    proposal.status = if proposal.yes_votes > proposal.no_votes {
-                        ProposalStatus::Passed
-                    } else {
-                        ProposalStatus::Rejected
-                    };
-                } else {
-                    proposal.status = ProposalStatus::Expired;
-                }
-            }
-        }
+        self.close_expired_proposals(chrono::Utc::now().timestamp_millis());
+    }
+
+    /// Tally every active proposal whose deadline is before `now`
+    ///
+    /// Returns the proposals that were closed, with their tally filled in.
+    pub fn close_expired_proposals(&self, now: i64) -> Vec<Proposal> {
+        self.proposals
+            .write()
+            .values_mut()
+            .filter_map(|proposal| Self::close_if_expired(proposal, now).then(|| proposal.clone()))
+            .collect()
     }
 
     // ─────────────────────────────────────────────────────────────────────────────
@@ -320,12 +622,71 @@ impl EconomicsStateManager {
         None
     }
 
-    /// Get vouches for a peer (as vouchee)
+    /// Revoke a vouch on behalf of its voucher
+    ///
+    /// Returns `None` if there is no such vouch or `voucher` didn't give it.
+    pub fn revoke_vouch(&self, vouch_id: &str, voucher: &str) -> Option<RemovedVouch> {
+        let is_voucher = self
+            .vouches
+            .read()
+            .get(vouch_id)
+            .is_some_and(|v| v.voucher == voucher);
+        if !is_voucher {
+            return None;
+        }
+        self.remove_vouch(vouch_id)
+    }
+
+    /// Remove every vouch that has expired by `now` (ms)
+    pub fn expire_vouches(&self, now: i64) -> Vec<RemovedVouch> {
+        let expired: Vec<String> = self
+            .vouches
+            .read()
+            .values()
+            .filter(|v| v.is_expired(now))
+            .map(|v| v.id.clone())
+            .collect();
+        expired
+            .iter()
+            .filter_map(|id| self.remove_vouch(id))
+            .collect()
+    }
+
+    /// Drop a vouch and recompute its vouchee's reputation without it
+    fn remove_vouch(&self, vouch_id: &str) -> Option<RemovedVouch> {
+        let vouch = self.vouches.write().remove(vouch_id)?;
+        let peer_key = format!("{}-{}", vouch.voucher, vouch.vouchee);
+        let mut by_peers = self.vouches_by_peers.write();
+        if by_peers.get(&peer_key).is_some_and(|id| id == vouch_id) {
+            by_peers.remove(&peer_key);
+        }
+        drop(by_peers);
+
+        let previous_reputation = self.get_reputation(&vouch.vouchee);
+        let reputation = if vouch.accepted {
+            let reputation = self.calculate_reputation(&vouch.vouchee);
+            self.reputations
+                .write()
+                .insert(vouch.vouchee.clone(), reputation);
+            reputation
+        } else {
+            previous_reputation
+        };
+
+        Some(RemovedVouch {
+            vouch,
+            previous_reputation,
+            reputation,
+        })
+    }
+
+    /// Get live vouches for a peer (as vouchee)
     pub fn get_vouches_for_peer(&self, peer_id: &str) -> Vec<Vouch> {
+        let now = chrono::Utc::now().timestamp_millis();
         self.vouches
             .read()
             .values()
-            .filter(|v| v.vouchee == peer_id && v.accepted)
+            .filter(|v| v.vouchee == peer_id && v.accepted && !v.is_expired(now))
             .cloned()
             .collect()
     }
@@ -440,25 +801,109 @@ impl EconomicsStateManager {
     // ─────────────────────────────────────────────────────────────────────────────
 
     /// Record a resource contribution
+    ///
+    /// Only the part already corroborated by receipts counts toward the pool
+    /// totals; a fresh claim stays pending until [`Self::record_receipt`].
     pub fn record_resource_contribution(&self, contribution: ResourceContribution) {
         let mut pool = self.resource_pool.write();
 
-        // Update totals based on resource type
-        match contribution.resource_type.to_lowercase().as_str() {
-            "bandwidth" => pool.total_bandwidth += contribution.amount,
-            "compute" | "cpu" => pool.total_compute += contribution.amount,
-            "storage" => pool.total_storage += contribution.amount,
-            _ => {}
+        pool.add_verified(&contribution.resource_type, contribution.verified_amount);
+        if contribution.is_pending() {
+            pool.pending_contributions += 1;
         }
-
         pool.contributions.push(contribution);
     }
 
+    /// Whether receipts signed by `peer_id` are trusted without the peer
+    /// being connected, from reputation earned through vouches
+    fn is_reputable_consumer(&self, peer_id: &str) -> bool {
+        self.reputations
+            .read()
+            .get(peer_id)
+            .is_some_and(|&reputation| reputation >= MIN_RECEIPT_CONSUMER_REPUTATION)
+    }
+
+    /// Count a consumer's signed receipt toward the contribution it names
+    ///
+    /// The receipt is ignored unless its signature checks out, it comes from
+    /// a peer other than the contributor that is either `consumer_connected`
+    /// to us or vouched to at least [`MIN_RECEIPT_CONSUMER_REPUTATION`], and
+    /// that consumer hasn't already been counted for the contribution. A
+    /// signature alone proves nothing: a contributor can mint keys to sign
+    /// its own receipts. The verified amount is capped at what the
+    /// contributor claimed. Returns the updated contribution.
+    pub fn record_receipt(
+        &self,
+        receipt: &ResourceReceipt,
+        consumer_connected: bool,
+    ) -> Option<ResourceContribution> {
+        if receipt.consumer == receipt.contributor
+            || !receipt.amount.is_finite()
+            || receipt.amount <= 0.0
+        {
+            return None;
+        }
+        if !consumer_connected && !self.is_reputable_consumer(&receipt.consumer) {
+            debug!(
+                "Ignoring receipt for contribution {} from unknown consumer {}",
+                receipt.contribution_id, receipt.consumer
+            );
+            return None;
+        }
+        if let Err(e) = receipt.verify() {
+            warn!(
+                "Rejecting receipt for contribution {} from {}: {}",
+                receipt.contribution_id, receipt.consumer, e
+            );
+            return None;
+        }
+
+        let contribution_id = receipt.contribution_id.to_string();
+        let mut pool = self.resource_pool.write();
+        let contribution = pool
+            .contributions
+            .iter_mut()
+            .find(|c| c.id == contribution_id && c.peer_id == receipt.contributor)?;
+        if contribution.consumers.contains(&receipt.consumer) {
+            return None;
+        }
+
+        let was_pending = contribution.is_pending();
+        let added = receipt
+            .amount
+            .min(contribution.amount - contribution.verified_amount)
+            .max(0.0);
+        contribution.verified_amount += added;
+        contribution.consumers.push(receipt.consumer.clone());
+        let updated = contribution.clone();
+
+        pool.add_verified(&updated.resource_type, added);
+        if was_pending {
+            pool.pending_contributions -= 1;
+        }
+        Some(updated)
+    }
+
     /// Get resource pool summary
     pub fn get_resource_pool(&self) -> ResourcePool {
         self.resource_pool.read().clone()
     }
 
+    /// Verified contribution per peer, largest first
+    pub fn get_verified_contributors(&self) -> Vec<(String, f64)> {
+        let mut totals: HashMap<String, f64> = HashMap::new();
+        for contribution in &self.resource_pool.read().contributions {
+            *totals.entry(contribution.peer_id.clone()).or_default() +=
+                contribution.verified_amount;
+        }
+        let mut contributors: Vec<_> = totals
+            .into_iter()
+            .filter(|(_, verified)| *verified > 0.0)
+            .collect();
+        contributors.sort_by(|a, b| b.1.total_cmp(&a.1));
+        contributors
+    }
+
     /// Get contributions by peer
     pub fn get_contributions_by_peer(&self, peer_id: &str) -> Vec<ResourceContribution> {
         self.resource_pool
@@ -470,6 +915,94 @@ impl EconomicsStateManager {
             .collect()
     }
 
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Persistence
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    /// Save the full economics state to the store, replacing any previous snapshot
+    pub async fn persist(&self, store: &SqliteStore) -> Result<(), StateError> {
+        fn to_json<T: Serialize>(value: &T) -> Result<String, StateError> {
+            serde_json::to_string(value).map_err(|e| StateError::Serialization(e.to_string()))
+        }
+
+        // Serialize under the locks, then release them before touching the database
+        let snapshot = EconomicsSnapshot {
+            version: ECONOMICS_SCHEMA_VERSION,
+            credit_lines: self
+                .credit_lines
+                .read()
+                .iter()
+                .map(|(id, line)| Ok((id.clone(), to_json(line)?)))
+                .collect::<Result<_, StateError>>()?,
+            proposals: self
+                .proposals
+                .read()
+                .iter()
+                .map(|(id, proposal)| Ok((id.clone(), to_json(proposal)?)))
+                .collect::<Result<_, StateError>>()?,
+            vouches: self
+                .vouches
+                .read()
+                .iter()
+                .map(|(id, vouch)| Ok((id.clone(), to_json(vouch)?)))
+                .collect::<Result<_, StateError>>()?,
+            contributions: self
+                .resource_pool
+                .read()
+                .contributions
+                .iter()
+                .map(to_json)
+                .collect::<Result<_, StateError>>()?,
+            reputations: self
+                .reputations
+                .read()
+                .iter()
+                .map(|(peer, score)| (peer.clone(), *score))
+                .collect(),
+        };
+
+        store.save_economics_snapshot(&snapshot).await
+    }
+
+    /// Restore economics state from the store
+    ///
+    /// Returns an empty manager if nothing has been persisted yet.
+    pub async fn load(store: &SqliteStore) -> Result<Self, StateError> {
+        fn from_json<T: for<'de> Deserialize<'de>>(json: &str) -> Result<T, StateError> {
+            serde_json::from_str(json).map_err(|e| StateError::Deserialization(e.to_string()))
+        }
+
+        let manager = Self::new();
+        let snapshot = match store.load_economics_snapshot().await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(manager),
+        };
+
+        for (_, json) in &snapshot.credit_lines {
+            manager.upsert_credit_line(from_json(json)?);
+        }
+        for (_, json) in &snapshot.proposals {
+            manager.add_proposal(from_json(json)?);
+        }
+        for (_, json) in &snapshot.vouches {
+            manager.add_vouch(from_json(json)?);
+        }
+        for json in &snapshot.contributions {
+            let mut contribution: ResourceContribution = from_json(json)?;
+            // Snapshots from before receipts counted every claim in full
+            if snapshot.version < RECEIPT_SCHEMA_VERSION {
+                contribution.verified_amount = contribution.amount;
+            }
+            manager.record_resource_contribution(contribution);
+        }
+        manager
+            .reputations
+            .write()
+            .extend(snapshot.reputations.into_iter());
+
+        Ok(manager)
+    }
+
     // ─────────────────────────────────────────────────────────────────────────────
     // Statistics
     // ─────────────────────────────────────────────────────────────────────────────
@@ -501,6 +1034,168 @@ impl Default for EconomicsStateManager {
     }
 }
 
+/// Periodically close proposals whose deadline has passed
+///
+/// Each closed proposal is pushed to the dashboard, and if this node is the
+/// proposer a `ProposalExecuted` message is published so other peers learn the
+/// outcome.
+pub async fn run_proposal_tally(state: Arc<AppState>, period: Duration) {
+    let mut interval = tokio::time::interval(period);
+    loop {
+        interval.tick().await;
+
+        let now = chrono::Utc::now().timestamp_millis();
+        for proposal in state.economics.close_expired_proposals(now) {
+            let tally = match proposal.tally.as_ref() {
+                Some(tally) => tally,
+                None => continue,
+            };
+            info!(
+                "Proposal {} closed as {} (yes {:.2}, no {:.2}, abstain {:.2})",
+                proposal.id,
+                proposal.status,
+                tally.yes_weight,
+                tally.no_weight,
+                tally.abstain_weight
+            );
+
+            let _ = state.event_tx.send(WsMessage::Proposal {
+                id: proposal.id.clone(),
+                proposer: proposal.proposer.clone(),
+                title: proposal.title.clone(),
+                description: proposal.description.clone(),
+                proposal_type: proposal.proposal_type.clone(),
+                status: proposal.status.to_string(),
+                yes_votes: tally.yes_weight as u32,
+                no_votes: tally.no_weight as u32,
+                quorum: (proposal.quorum * 100.0) as u32,
+                deadline: proposal.deadline,
+                timestamp: now,
+            });
+
+            if proposal.status == ProposalStatus::Passed {
+                apply_parameter_change(&state, &proposal).await;
+            }
+
+            if proposal.proposer != state.local_peer_id.to_string() {
+                continue;
+            }
+            let proposal_id = match uuid::Uuid::parse_str(&proposal.id) {
+                Ok(id) => id,
+                Err(_) => continue,
+            };
+            let msg = GovernanceMessage::ProposalExecuted(ProposalExecuted {
+                proposal_id,
+                success: proposal.status == ProposalStatus::Passed,
+                result: format!(
+                    "{}: approval {:.0}%, quorum {}",
+                    proposal.status,
+                    tally.approval * 100.0,
+                    if tally.quorum_reached {
+                        "reached"
+                    } else {
+                        "not reached"
+                    }
+                ),
+                timestamp: chrono::Utc::now(),
+            });
+            match serde_json::to_vec(&msg) {
+                Ok(data) => {
+                    if let Err(e) = state
+                        .network
+                        .publish_reliable(topics::GOVERNANCE, data, ECONOMICS_PUBLISH_DEADLINE)
+                        .await
+                    {
+                        warn!("Failed to publish proposal result: {}", e);
+                    }
+                }
+                Err(e) => warn!("Failed to serialize proposal result: {}", e),
+            }
+        }
+    }
+}
+
+/// Put a passed proposal's parameter change into effect
+async fn apply_parameter_change(state: &AppState, proposal: &Proposal) {
+    let Some(change) = proposal.parameter_change.as_ref() else {
+        return;
+    };
+    if change.parameter != ENTROPY_TAX_PARAMETER {
+        return;
+    }
+
+    match change
+        .entropy_tax_rate()
+        .ok_or_else(|| format!("not a number: {}", change.new_value))
+        .and_then(|rate| EntropyTaxRate::new(rate).map_err(|e| e.to_string()))
+    {
+        Ok(rate) => {
+            state.enr_bridge.set_entropy_tax_rate(rate).await;
+            info!(
+                "Proposal {} set the entropy tax rate to {}",
+                proposal.id,
+                rate.get()
+            );
+            // Keep charging the governed rate after a restart
+            if let Err(e) = state.store.save_entropy_tax_rate(rate.get()).await {
+                warn!("Failed to persist the entropy tax rate: {}", e);
+            }
+        }
+        Err(e) => warn!(
+            "Ignoring entropy tax change from proposal {}: {}",
+            proposal.id, e
+        ),
+    }
+}
+
+/// Periodically charge interest on outstanding credit line balances and
+/// broadcast the new balances to dashboard clients
+///
+/// The first round runs straight away, charging for any time the node was
+/// down since the lines were last charged.
+pub async fn run_interest_accrual(state: Arc<AppState>, period: Duration) {
+    let mut interval = tokio::time::interval(period);
+    loop {
+        interval.tick().await;
+
+        let now = chrono::Utc::now();
+        let timestamp = now.timestamp_millis();
+        for line in state.economics.accrue_credit_interest(now) {
+            let _ = state.event_tx.send(WsMessage::CreditLine {
+                id: line.id,
+                creditor: line.creditor,
+                debtor: line.debtor,
+                limit: line.limit,
+                balance: line.balance,
+                timestamp,
+            });
+        }
+    }
+}
+
+/// Periodically drop expired vouches and broadcast the vouchees' recomputed
+/// reputations
+pub async fn run_vouch_expiry(state: Arc<AppState>, period: Duration) {
+    let mut interval = tokio::time::interval(period);
+    loop {
+        interval.tick().await;
+
+        let now = chrono::Utc::now().timestamp_millis();
+        for removed in state.economics.expire_vouches(now) {
+            info!(
+                "Vouch {} from {} for {} expired",
+                removed.vouch.id, removed.vouch.voucher, removed.vouch.vouchee
+            );
+            if removed.vouch.accepted {
+                let _ = state.event_tx.send(WsMessage::ReputationUpdate {
+                    peer_id: removed.vouch.vouchee,
+                    new_score: removed.reputation,
+                });
+            }
+        }
+    }
+}
+
 /// Summary of economics state
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct EconomicsSummary {
@@ -516,6 +1211,7 @@ pub struct EconomicsSummary {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use mycelial_core::{Keypair, PeerId};
 
     #[test]
     fn test_credit_line_operations() {
@@ -529,6 +1225,10 @@ mod tests {
             balance: 0.0,
             created_at: 0,
             updated_at: 0,
+            interest_rate: 0.0,
+            accrued_interest: 0.0,
+            last_accrued: 0,
+            history: Vec::new(),
         };
 
         manager.upsert_credit_line(line.clone());
@@ -537,8 +1237,28 @@ mod tests {
         assert!(manager.get_credit_line_between("alice", "bob").is_some());
         assert!(manager.get_credit_line_between("bob", "alice").is_none());
 
-        manager.update_credit_balance("line1", 50.0);
+        let start = Utc::now();
+        manager.update_credit_balance("line1", 50.0, Some("seed loan".into()));
         assert_eq!(manager.get_credit_line("line1").unwrap().balance, 50.0);
+        manager.update_credit_balance("line1", 20.0, None);
+
+        let statements = manager.get_credit_statements("bob", start, Utc::now());
+        assert_eq!(statements.len(), 1);
+        let statement = &statements[0];
+        assert_eq!(statement.opening_balance, 0.0);
+        assert_eq!(statement.entries.len(), 2);
+        assert_eq!(statement.entries[0].memo.as_deref(), Some("seed loan"));
+        assert_eq!(statement.entries[1].amount, -30.0);
+        assert_eq!(statement.entries[1].balance, 20.0);
+
+        // A period after the transfers opens at the final balance
+        let later = Utc::now() + chrono::Duration::hours(1);
+        let statements = manager.get_credit_statements("alice", later, later);
+        assert!(statements[0].entries.is_empty());
+        assert_eq!(statements[0].opening_balance, 20.0);
+        assert!(manager
+            .get_credit_statements("carol", start, later)
+            .is_empty());
     }
 
     #[test]
@@ -555,9 +1275,12 @@ mod tests {
             yes_votes: 0.0,
             no_votes: 0.0,
             quorum: 0.5,
+            threshold: 0.5,
             deadline: chrono::Utc::now().timestamp_millis() + 86400000,
             created_at: chrono::Utc::now().timestamp_millis(),
             votes: HashMap::new(),
+            tally: None,
+            parameter_change: None,
         };
 
         manager.add_proposal(proposal);
@@ -574,6 +1297,139 @@ mod tests {
         assert_eq!(manager.get_proposal("prop1").unwrap().yes_votes, 1.0);
     }
 
+    fn proposal_with_votes(votes: &[(&str, VoteType, f64)]) -> Proposal {
+        Proposal {
+            id: "prop1".to_string(),
+            proposer: "alice".to_string(),
+            title: "Test Proposal".to_string(),
+            description: "A test".to_string(),
+            proposal_type: "text".to_string(),
+            status: ProposalStatus::Active,
+            yes_votes: 0.0,
+            no_votes: 0.0,
+            quorum: 1.0,
+            threshold: 0.5,
+            deadline: 1_000,
+            created_at: 0,
+            votes: votes
+                .iter()
+                .map(|(voter, vote_type, weight)| {
+                    (
+                        voter.to_string(),
+                        Vote {
+                            voter: voter.to_string(),
+                            vote_type: vote_type.clone(),
+                            weight: *weight,
+                            timestamp: 0,
+                        },
+                    )
+                })
+                .collect(),
+            tally: None,
+            parameter_change: None,
+        }
+    }
+
+    #[test]
+    fn test_parameter_change_entropy_tax_rate() {
+        let change = ParameterChange {
+            parameter: ENTROPY_TAX_PARAMETER.to_string(),
+            new_value: "0.05".to_string(),
+        };
+        assert_eq!(change.entropy_tax_rate(), Some(0.05));
+
+        let other = ParameterChange {
+            parameter: "max_peers".to_string(),
+            new_value: "0.05".to_string(),
+        };
+        assert_eq!(other.entropy_tax_rate(), None);
+    }
+
+    #[test]
+    fn test_proposal_tally_outcomes() {
+        let passed = proposal_with_votes(&[("a", VoteType::Yes, 1.0), ("b", VoteType::No, 0.5)]);
+        let tally = ProposalTally::compute(&passed, 2_000);
+        assert_eq!(tally.outcome, ProposalStatus::Passed);
+        assert!(tally.quorum_reached);
+
+        let tie = proposal_with_votes(&[("a", VoteType::Yes, 1.0), ("b", VoteType::No, 1.0)]);
+        let tally = ProposalTally::compute(&tie, 2_000);
+        assert!(tally.tie);
+        assert_eq!(tally.outcome, ProposalStatus::Rejected);
+
+        let no_quorum = proposal_with_votes(&[("a", VoteType::Yes, 0.5)]);
+        assert_eq!(
+            ProposalTally::compute(&no_quorum, 2_000).outcome,
+            ProposalStatus::Expired
+        );
+
+        let no_votes = proposal_with_votes(&[]);
+        assert_eq!(
+            ProposalTally::compute(&no_votes, 2_000).outcome,
+            ProposalStatus::Expired
+        );
+
+        // Abstentions count toward quorum but not approval
+        let abstained =
+            proposal_with_votes(&[("a", VoteType::Abstain, 1.0), ("b", VoteType::No, 0.2)]);
+        let tally = ProposalTally::compute(&abstained, 2_000);
+        assert!(tally.quorum_reached);
+        assert_eq!(tally.outcome, ProposalStatus::Rejected);
+
+        // Turnout exactly at quorum and approval exactly at threshold both count
+        let mut boundary =
+            proposal_with_votes(&[("a", VoteType::Yes, 0.75), ("b", VoteType::No, 0.25)]);
+        boundary.threshold = 0.75;
+        let tally = ProposalTally::compute(&boundary, 2_000);
+        assert!(tally.quorum_reached);
+        assert_eq!(tally.approval, 0.75);
+        assert_eq!(tally.outcome, ProposalStatus::Passed);
+    }
+
+    #[test]
+    fn test_close_expired_proposals() {
+        let manager = EconomicsStateManager::new();
+        manager.add_proposal(proposal_with_votes(&[("a", VoteType::Yes, 2.0)]));
+
+        // Before the deadline nothing closes
+        assert!(manager.close_expired_proposals(500).is_empty());
+
+        let closed = manager.close_expired_proposals(2_000);
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].status, ProposalStatus::Passed);
+
+        let stored = manager.get_proposal("prop1").unwrap();
+        assert_eq!(stored.tally.unwrap().outcome, ProposalStatus::Passed);
+        assert!(manager.get_active_proposals().is_empty());
+
+        // Closed proposals are not closed again
+        assert!(manager.close_expired_proposals(3_000).is_empty());
+    }
+
+    #[test]
+    fn test_revote_replaces_previous_vote() {
+        let manager = EconomicsStateManager::new();
+        let mut proposal = proposal_with_votes(&[]);
+        proposal.deadline = i64::MAX;
+        manager.add_proposal(proposal);
+
+        for vote_type in [VoteType::Yes, VoteType::No] {
+            manager.record_vote(
+                "prop1",
+                Vote {
+                    voter: "bob".to_string(),
+                    vote_type,
+                    weight: 1.0,
+                    timestamp: 0,
+                },
+            );
+        }
+
+        let proposal = manager.get_proposal("prop1").unwrap();
+        assert_eq!(proposal.yes_votes, 0.0);
+        assert_eq!(proposal.no_votes, 1.0);
+    }
+
     #[test]
     fn test_vouch_operations() {
         let manager = EconomicsStateManager::new();
@@ -585,6 +1441,7 @@ mod tests {
             weight: 0.8,
             accepted: false,
             created_at: 0,
+            expires_at: None,
         };
 
         manager.add_vouch(vouch);
@@ -598,23 +1455,101 @@ mod tests {
         assert!(rep > 0.5); // Should increase from default
     }
 
+    #[test]
+    fn test_vouch_expiry_and_revocation() {
+        let manager = EconomicsStateManager::new();
+        let vouch = |id: &str, voucher: &str, weight: f64, expires_at: Option<i64>| Vouch {
+            id: id.to_string(),
+            voucher: voucher.to_string(),
+            vouchee: "bob".to_string(),
+            weight,
+            accepted: false,
+            created_at: 0,
+            expires_at,
+        };
+        manager.add_vouch(vouch("vouch1", "alice", 0.9, Some(1_000)));
+        manager.add_vouch(vouch("vouch2", "carol", 0.3, None));
+        manager.respond_to_vouch("vouch1", true);
+        manager.respond_to_vouch("vouch2", true);
+
+        // Nothing has expired yet
+        assert!(manager.expire_vouches(500).is_empty());
+
+        let expired = manager.expire_vouches(1_000);
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].vouch.id, "vouch1");
+        assert_eq!(expired[0].reputation, 0.3);
+        assert_eq!(manager.get_reputation("bob"), 0.3);
+        assert!(manager.get_vouch("vouch1").is_none());
+
+        // Only the voucher can revoke
+        assert!(manager.revoke_vouch("vouch2", "mallory").is_none());
+        let revoked = manager.revoke_vouch("vouch2", "carol").unwrap();
+        assert_eq!(revoked.previous_reputation, 0.3);
+        assert_eq!(revoked.reputation, 0.5);
+        assert!(manager.get_vouches_for_peer("bob").is_empty());
+    }
+
     #[test]
     fn test_resource_contributions() {
         let manager = EconomicsStateManager::new();
 
+        let claim = mycelial_protocol::ResourceContribution::new(
+            PeerId::from_public_key(&Keypair::generate().public_key()).to_string(),
+            mycelial_protocol::ResourceType::Bandwidth,
+            100.0,
+            "mbps".to_string(),
+        );
         let contrib = ResourceContribution {
-            peer_id: "alice".to_string(),
+            id: claim.id.to_string(),
+            peer_id: claim.peer_id.clone(),
             resource_type: "bandwidth".to_string(),
             amount: 100.0,
             unit: "mbps".to_string(),
             timestamp: 0,
+            verified_amount: 0.0,
+            consumers: Vec::new(),
         };
 
         manager.record_resource_contribution(contrib);
 
+        // Unverified claims are pending and don't count
         let pool = manager.get_resource_pool();
-        assert_eq!(pool.total_bandwidth, 100.0);
+        assert_eq!(pool.total_bandwidth, 0.0);
+        assert_eq!(pool.pending_contributions, 1);
         assert_eq!(pool.contributions.len(), 1);
+
+        // Receipts from consumers we know nothing about could be self-signed
+        let consumer = Keypair::generate();
+        let receipt = ResourceReceipt::sign(&claim, 60.0, &consumer);
+        assert!(manager.record_receipt(&receipt, false).is_none());
+
+        let updated = manager.record_receipt(&receipt, true).unwrap();
+        assert_eq!(updated.verified_amount, 60.0);
+
+        // The same consumer can't count twice
+        assert!(manager.record_receipt(&receipt, true).is_none());
+
+        // A tampered receipt is rejected
+        let mut inflated = ResourceReceipt::sign(&claim, 10.0, &Keypair::generate());
+        inflated.amount = 1_000.0;
+        assert!(manager.record_receipt(&inflated, true).is_none());
+
+        // A vouched-for consumer counts without being connected, and
+        // receipts never verify more than was claimed
+        let reputable = ResourceReceipt::sign(&claim, 80.0, &Keypair::generate());
+        manager
+            .reputations
+            .write()
+            .insert(reputable.consumer.clone(), MIN_RECEIPT_CONSUMER_REPUTATION);
+        manager.record_receipt(&reputable, false).unwrap();
+        let pool = manager.get_resource_pool();
+        assert_eq!(pool.total_bandwidth, 100.0);
+        assert_eq!(pool.pending_contributions, 0);
+        assert_eq!(
+            manager.get_verified_contributors(),
+            vec![(claim.peer_id.clone(), 100.0)]
+        );
     }
 
     #[test]
@@ -659,6 +1594,132 @@ mod tests {
         assert!(last_active.contains_key("bob"));
     }
 
+    #[test]
+    fn test_accrue_credit_interest() {
+        let manager = EconomicsStateManager::new();
+        let line = |id: &str, debtor: &str, interest_rate: f64| CreditLine {
+            id: id.to_string(),
+            creditor: "alice".to_string(),
+            debtor: debtor.to_string(),
+            limit: 100.0,
+            balance: 50.0,
+            created_at: 0,
+            updated_at: 0,
+            interest_rate,
+            accrued_interest: 0.0,
+            last_accrued: 0,
+            history: Vec::new(),
+        };
+        manager.upsert_credit_line(line("line1", "bob", 0.1));
+        manager.upsert_credit_line(line("line2", "carol", 0.0));
+
+        // A year since the lines were last touched, e.g. across a restart
+        let year = chrono::Duration::hours(365 * 24 + 6);
+        let now = DateTime::<Utc>::from_timestamp_millis(0).unwrap() + year;
+        let updated = manager.accrue_credit_interest(now);
+        assert_eq!(updated.len(), 1);
+        assert_eq!(updated[0].id, "line1");
+        assert!(manager.accrue_credit_interest(now).is_empty());
+
+        let line1 = manager.get_credit_line("line1").unwrap();
+        assert!((line1.balance - 55.0).abs() < 1e-9);
+        assert!((line1.accrued_interest - 5.0).abs() < 1e-9);
+        assert_eq!(line1.history[0].kind, CreditEntryKind::Interest);
+        assert_eq!(manager.get_credit_line("line2").unwrap().balance, 50.0);
+    }
+
+    #[tokio::test]
+    async fn test_persist_and_load() {
+        let store = SqliteStore::new(":memory:").await.unwrap();
+        let manager = EconomicsStateManager::new();
+
+        manager.upsert_credit_line(CreditLine {
+            id: "line1".to_string(),
+            creditor: "alice".to_string(),
+            debtor: "bob".to_string(),
+            limit: 100.0,
+            balance: 25.0,
+            created_at: 0,
+            updated_at: 0,
+            interest_rate: 0.0,
+            accrued_interest: 0.0,
+            last_accrued: 0,
+            history: Vec::new(),
+        });
+        manager.add_vouch(Vouch {
+            id: "vouch1".to_string(),
+            voucher: "alice".to_string(),
+            vouchee: "bob".to_string(),
+            weight: 0.8,
+            accepted: false,
+            created_at: 0,
+            expires_at: None,
+        });
+        manager.respond_to_vouch("vouch1", true);
+        manager.record_resource_contribution(ResourceContribution {
+            id: "contrib1".to_string(),
+            peer_id: "alice".to_string(),
+            resource_type: "storage".to_string(),
+            amount: 10.0,
+            unit: "gb".to_string(),
+            timestamp: 0,
+            verified_amount: 10.0,
+            consumers: vec!["bob".to_string()],
+        });
+
+        manager.persist(&store).await.unwrap();
+        let restored = EconomicsStateManager::load(&store).await.unwrap();
+
+        assert_eq!(
+            restored
+                .get_credit_line_between("alice", "bob")
+                .unwrap()
+                .balance,
+            25.0
+        );
+        assert!(restored.get_vouch("vouch1").unwrap().accepted);
+        assert_eq!(restored.get_resource_pool().total_storage, 10.0);
+        assert_eq!(
+            restored.get_reputation("bob"),
+            manager.get_reputation("bob")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_migrates_unverified_contributions() {
+        let store = SqliteStore::new(":memory:").await.unwrap();
+        let legacy = serde_json::json!({
+            "peer_id": "alice",
+            "resource_type": "storage",
+            "amount": 10.0,
+            "unit": "gb",
+            "timestamp": 0,
+        });
+        store
+            .save_economics_snapshot(&EconomicsSnapshot {
+                version: 1,
+                contributions: vec![legacy.to_string()],
+                ..EconomicsSnapshot::default()
+            })
+            .await
+            .unwrap();
+
+        // Claims counted before receipts existed keep counting
+        let pool = EconomicsStateManager::load(&store)
+            .await
+            .unwrap()
+            .get_resource_pool();
+        assert_eq!(pool.total_storage, 10.0);
+        assert_eq!(pool.pending_contributions, 0);
+    }
+
+    #[tokio::test]
+    async fn test_load_empty_store() {
+        let store = SqliteStore::new(":memory:").await.unwrap();
+        let restored = EconomicsStateManager::load(&store).await.unwrap();
+        assert_eq!(restored.get_summary().credit_line_count, 0);
+    }
+
     #[test]
     fn test_low_reputation_peers() {
         let manager = EconomicsStateManager::new();
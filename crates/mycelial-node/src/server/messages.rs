@@ -4,6 +4,7 @@
 //! and dashboard clients. Includes support for economics protocols (vouch, credit,
 //! governance, resource).
 
+use mycelial_core::location::Location;
 use mycelial_core::peer::PeerInfo;
 use serde::{Deserialize, Serialize};
 
@@ -29,6 +30,8 @@ pub enum WsMessage {
         room_id: Option<String>,
         content: String,
         timestamp: i64,
+        /// Whether `from` is backed by the sender's gossipsub signature
+        verified: bool,
     },
 
     /// A peer's reputation was updated
@@ -239,6 +242,13 @@ pub enum WsMessage {
         failure_count: u32,
         timestamp: i64,
     },
+
+    /// Gossipsub mesh size changed for a topic
+    MeshUpdated {
+        topic: String,
+        mesh_peers: usize,
+        all_peers: usize,
+    },
 }
 
 /// Entry in the peers list
@@ -248,6 +258,11 @@ pub struct PeerListEntry {
     pub name: Option<String>,
     pub reputation: f64,
     pub addresses: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<Location>,
+    /// Connection quality (0.0 - 1.0), for peers the network knows
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<f64>,
 }
 
 impl From<(PeerInfo, mycelial_core::reputation::Reputation)> for PeerListEntry {
@@ -257,6 +272,8 @@ impl From<(PeerInfo, mycelial_core::reputation::Reputation)> for PeerListEntry {
             name: info.name,
             reputation: rep.score,
             addresses: info.addresses,
+            location: info.location,
+            quality: None,
         }
     }
 }
@@ -3,30 +3,148 @@
 //! This module provides the WebSocket and REST API server for the
 //! mycelial node dashboard.
 
+pub mod auth;
 pub mod economics_state;
 pub mod messages;
 pub mod rest;
 pub mod websocket;
 
-use axum::{routing::get, Router};
+use anyhow::{bail, Context};
+use axum::http::{HeaderName, HeaderValue, Method, Uri};
+use axum::{
+    middleware,
+    routing::{delete, get, put},
+    Router,
+};
 use std::sync::Arc;
-use tower_http::cors::{Any, CorsLayer};
+use tower_http::cors::{AllowOrigin, CorsLayer};
 
 use crate::AppState;
 
+/// Methods allowed cross-origin unless configured otherwise
+const DEFAULT_CORS_METHODS: &[&str] = &["GET", "POST", "PUT", "DELETE"];
+
+/// Request headers allowed cross-origin unless configured otherwise
+const DEFAULT_CORS_HEADERS: &[&str] = &["content-type", "authorization"];
+
+/// Which browser origins may call the dashboard API
+#[derive(Debug, Clone, Default)]
+pub struct CorsConfig {
+    /// Allowed origins (`scheme://host[:port]`), or `*` for any origin
+    ///
+    /// Empty allows only pages served from localhost.
+    pub origins: Vec<String>,
+    /// Allowed methods; empty means `DEFAULT_CORS_METHODS`
+    pub methods: Vec<String>,
+    /// Allowed request headers; empty means `content-type` and `authorization`
+    pub headers: Vec<String>,
+}
+
+impl CorsConfig {
+    /// Build the CORS layer, rejecting malformed origins, methods or headers
+    pub fn layer(&self) -> anyhow::Result<CorsLayer> {
+        let origin = if self.origins.iter().any(|o| o == "*") {
+            AllowOrigin::any()
+        } else if self.origins.is_empty() {
+            AllowOrigin::predicate(|origin: &HeaderValue, _| {
+                origin.to_str().is_ok_and(is_localhost_origin)
+            })
+        } else {
+            let origins = self
+                .origins
+                .iter()
+                .map(|o| parse_origin(o))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            AllowOrigin::list(origins)
+        };
+
+        let methods = or_default(&self.methods, DEFAULT_CORS_METHODS)
+            .into_iter()
+            .map(|m| {
+                Method::from_bytes(m.as_bytes()).with_context(|| format!("Invalid method {:?}", m))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let headers = or_default(&self.headers, DEFAULT_CORS_HEADERS)
+            .into_iter()
+            .map(|h| {
+                HeaderName::from_bytes(h.as_bytes())
+                    .with_context(|| format!("Invalid header {:?}", h))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(CorsLayer::new()
+            .allow_origin(origin)
+            .allow_methods(methods)
+            .allow_headers(headers))
+    }
+}
+
+/// The configured values, or the defaults when none are configured
+fn or_default<'a>(configured: &'a [String], defaults: &[&'a str]) -> Vec<&'a str> {
+    if configured.is_empty() {
+        defaults.to_vec()
+    } else {
+        configured.iter().map(String::as_str).collect()
+    }
+}
+
+/// Parse an origin as browsers send it: `scheme://host[:port]`, nothing more
+fn parse_origin(origin: &str) -> anyhow::Result<HeaderValue> {
+    let uri: Uri = origin
+        .parse()
+        .with_context(|| format!("Invalid CORS origin {:?}", origin))?;
+    let (Some(scheme), Some(authority)) = (uri.scheme_str(), uri.authority()) else {
+        bail!("CORS origin {:?} needs a scheme and host", origin);
+    };
+    if !matches!(scheme, "http" | "https") {
+        bail!("CORS origin {:?} must be http or https", origin);
+    }
+    if !matches!(uri.path(), "" | "/") || uri.query().is_some() {
+        bail!("CORS origin {:?} must not have a path", origin);
+    }
+    Ok(HeaderValue::from_str(&format!(
+        "{}://{}",
+        scheme, authority
+    ))?)
+}
+
+/// Whether an origin is a page served from this machine, on any port
+fn is_localhost_origin(origin: &str) -> bool {
+    let Ok(uri) = origin.parse::<Uri>() else {
+        return false;
+    };
+    matches!(uri.scheme_str(), Some("http" | "https"))
+        && matches!(
+            uri.host(),
+            Some("localhost" | "127.0.0.1" | "[::1]" | "::1")
+        )
+}
+
 /// Create the server router
-pub fn create_router(state: Arc<AppState>) -> Router {
-    Router::new()
-        // Health check
-        .route("/health", get(rest::health))
+pub fn create_router(state: Arc<AppState>, cors: CorsLayer) -> Router {
+    let router = Router::new()
         // Node info
         .route("/api/info", get(rest::node_info))
         // WebSocket endpoint
         .route("/ws", get(websocket::ws_handler))
         // REST endpoints
         .route("/api/peers", get(rest::list_peers))
+        .route("/api/peers/clusters", get(rest::list_peer_clusters))
         .route("/api/peer/:id", get(rest::get_peer))
         .route("/api/stats", get(rest::get_stats))
+        // Peer bans, for operators to review and lift
+        .route("/api/bans", get(rest::list_bans).post(rest::ban_peer))
+        .route("/api/bans/:peer_id", delete(rest::unban_peer))
+        // Raw DHT records, for debugging content distribution
+        .route(
+            "/api/dht/:key",
+            get(rest::get_dht_record).put(rest::put_dht_record),
+        )
+        // Pins that keep provided content safe from GC
+        .route(
+            "/api/content/:id/pin",
+            put(rest::pin_content).delete(rest::unpin_content),
+        )
         // Economics API endpoints
         .route("/api/economics", get(rest::get_economics_summary))
         .route("/api/economics/credit-lines", get(rest::list_credit_lines))
@@ -34,6 +152,10 @@ pub fn create_router(state: Arc<AppState>) -> Router {
             "/api/economics/credit-lines/:peer_id",
             get(rest::get_credit_lines_for_peer),
         )
+        .route(
+            "/api/economics/statement/:peer_id",
+            get(rest::get_credit_statements),
+        )
         .route("/api/economics/proposals", get(rest::list_proposals))
         .route(
             "/api/economics/proposals/active",
@@ -57,12 +179,23 @@ pub fn create_router(state: Arc<AppState>) -> Router {
             "/api/economics/peer/:peer_id",
             get(rest::get_peer_economics),
         )
-        // CORS for dashboard
-        .layer(
-            CorsLayer::new()
-                .allow_origin(Any)
-                .allow_methods(Any)
-                .allow_headers(Any),
-        )
+        // ENR credit balances
+        .route("/api/enr/balance", get(rest::get_local_balance))
+        .route("/api/enr/balance/:node_id", get(rest::get_node_balance))
+        .route("/api/enr/entropy-tax", get(rest::get_entropy_tax));
+
+    // Drift between the optimistic and Raft credit ledgers
+    #[cfg(feature = "openraft")]
+    let router = router.route("/api/economics/audit", get(rest::get_balance_audit));
+
+    router
+        // Everything above needs the API token, if the node has one
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::require_token,
+        ))
+        // Health check
+        .route("/health", get(rest::health))
+        .layer(cors)
         .with_state(state)
 }
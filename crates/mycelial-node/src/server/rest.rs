@@ -1,34 +1,98 @@
 //! REST API endpoints
 
 use axum::{
-    extract::{Path, State},
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::StatusCode,
     Json,
 };
-use serde::Serialize;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use mycelial_core::content::ContentId;
+use mycelial_core::health::{HealthCheck, HealthLevel, HealthReport};
+use mycelial_core::location::{cluster_by_location, Location, PeerCluster};
+use mycelial_network::enr_bridge::QueryError;
+use mycelial_network::{Libp2pPeerId, PeerBan};
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use std::time::Duration;
+use univrs_enr::core::NodeId;
 
-use super::economics_state::{CreditLine, EconomicsSummary, Proposal, ResourcePool, Vouch};
+use super::economics_state::{
+    CreditLine, CreditStatement, EconomicsSummary, Proposal, ResourcePool, Vouch,
+};
 use super::messages::PeerListEntry;
 use crate::AppState;
 
 /// List all peers
 pub async fn list_peers(State(state): State<Arc<AppState>>) -> Json<Vec<PeerListEntry>> {
     let peers = state.store.list_peers().await.unwrap_or_default();
-    let entries: Vec<PeerListEntry> = peers.into_iter().map(Into::into).collect();
+    let mut entries: Vec<PeerListEntry> = peers.into_iter().map(Into::into).collect();
+    add_peer_quality(&state, &mut entries).await;
     Json(entries)
 }
 
+/// Fill in connection quality for the peers the network knows
+///
+/// Entries are left without a quality if the network can't be asked.
+pub(super) async fn add_peer_quality(state: &AppState, entries: &mut [PeerListEntry]) {
+    let Ok(qualities) = state.network.peer_qualities().await else {
+        return;
+    };
+    let qualities: std::collections::HashMap<String, f64> = qualities
+        .into_iter()
+        .map(|(peer_id, quality)| (peer_id.to_string(), quality))
+        .collect();
+    for entry in entries {
+        entry.quality = qualities.get(&entry.id).copied();
+    }
+}
+
 /// Get specific peer
 pub async fn get_peer(
     State(state): State<Arc<AppState>>,
     Path(id): Path<String>,
 ) -> Json<Option<PeerListEntry>> {
     match state.store.get_peer(&id).await {
-        Ok(Some((info, rep))) => Json(Some(PeerListEntry::from((info, rep)))),
+        Ok(Some((info, rep))) => {
+            let mut entry = PeerListEntry::from((info, rep));
+            add_peer_quality(&state, std::slice::from_mut(&mut entry)).await;
+            Json(Some(entry))
+        }
         _ => Json(None),
     }
 }
 
+/// Geohash length peers are clustered by when the request doesn't say
+const DEFAULT_CLUSTER_PRECISION: usize = 4;
+
+/// Cell size for peer clusters
+///
+/// `precision` is the geohash length: 1 gives continent-sized cells, 4
+/// cells of about 40 km, 6 about 1 km.
+#[derive(Debug, Deserialize)]
+pub struct ClusterQuery {
+    pub precision: Option<usize>,
+}
+
+/// Known peers grouped by location, for the dashboard map
+///
+/// Includes LoRa nodes whose positions arrived over the Meshtastic bridge;
+/// peers that never reported a location share an `unknown` cluster.
+pub async fn list_peer_clusters(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ClusterQuery>,
+) -> Json<Vec<PeerCluster>> {
+    let peers = state.store.list_peers().await.unwrap_or_default();
+    let precision = query.precision.unwrap_or(DEFAULT_CLUSTER_PRECISION);
+    Json(cluster_by_location(
+        peers
+            .iter()
+            .map(|(info, _)| (info.id.as_str().to_string(), info.location.as_ref())),
+        precision,
+    ))
+}
+
 /// Network statistics
 #[derive(Serialize)]
 pub struct NetworkStats {
@@ -52,27 +116,233 @@ pub async fn get_stats(State(state): State<Arc<AppState>>) -> Json<NetworkStats>
     })
 }
 
-/// Health check endpoint
-pub async fn health() -> &'static str {
-    "OK"
+/// Health of each subsystem and of the node as a whole
+///
+/// Answers 503 only when something is down, so probes that just check for
+/// success still pass while the node is degraded (e.g. has no peers yet).
+pub async fn health(State(state): State<Arc<AppState>>) -> (StatusCode, Json<HealthReport>) {
+    let (network, storage, economics) = tokio::join!(
+        state.network.health(),
+        state.store.health(),
+        state.enr_bridge.health()
+    );
+    let mut components = vec![
+        ("network".to_string(), network),
+        ("storage".to_string(), storage),
+    ];
+    if state.economics_enabled {
+        components.push(("economics".to_string(), economics));
+    }
+    let report = HealthReport::new(components);
+    let code = match report.status {
+        HealthLevel::Down => StatusCode::SERVICE_UNAVAILABLE,
+        HealthLevel::Ok | HealthLevel::Degraded => StatusCode::OK,
+    };
+    (code, Json(report))
 }
 
 /// Node info endpoint
+///
+/// Enough for a client to check compatibility before using
+/// protocol-specific endpoints.
 #[derive(Serialize)]
 pub struct NodeInfo {
     pub version: &'static str,
+    pub protocol_version: &'static str,
     pub name: String,
     pub peer_id: String,
+    /// Optional cargo features compiled in
+    pub features: Vec<&'static str>,
+    /// Whether the node joined the economics topics
+    pub economics: bool,
+    pub transports: Vec<&'static str>,
+    /// Listen addresses, each ending in `/p2p/<peer_id>`
+    pub listen_addrs: Vec<String>,
+    pub location: Option<Location>,
 }
 
 pub async fn node_info(State(state): State<Arc<AppState>>) -> Json<NodeInfo> {
+    let mut features = mycelial_network::FEATURES.to_vec();
+    if cfg!(feature = "meshtastic") {
+        features.push("meshtastic");
+    }
+
     Json(NodeInfo {
         version: env!("CARGO_PKG_VERSION"),
+        protocol_version: mycelial_core::PROTOCOL_VERSION,
         name: state.node_name.clone(),
         peer_id: state.local_peer_id.to_string(),
+        features,
+        economics: state.economics_enabled,
+        transports: state.transports.clone(),
+        listen_addrs: state.listen_addrs.read().clone(),
+        location: state.location.clone(),
     })
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Peer Ban Endpoints
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// List the bans in force, most recent first
+pub async fn list_bans(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<PeerBan>>, StatusCode> {
+    state
+        .network
+        .banned_peers()
+        .await
+        .map(Json)
+        .map_err(|_| StatusCode::SERVICE_UNAVAILABLE)
+}
+
+/// Request to ban a peer
+#[derive(Deserialize)]
+pub struct BanRequest {
+    pub peer_id: String,
+    /// How long the ban lasts; omitted for a ban that lasts until lifted
+    pub duration_secs: Option<u64>,
+    #[serde(default = "default_ban_reason")]
+    pub reason: String,
+}
+
+fn default_ban_reason() -> String {
+    "banned by operator".to_string()
+}
+
+/// Ban a peer, disconnecting it
+///
+/// 400 if the peer ID is malformed.
+pub async fn ban_peer(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<BanRequest>,
+) -> StatusCode {
+    let Ok(peer_id) = request.peer_id.parse::<Libp2pPeerId>() else {
+        return StatusCode::BAD_REQUEST;
+    };
+    let duration = request.duration_secs.map(Duration::from_secs);
+    match state
+        .network
+        .ban_peer(peer_id, duration, request.reason)
+        .await
+    {
+        Ok(()) => StatusCode::NO_CONTENT,
+        Err(_) => StatusCode::SERVICE_UNAVAILABLE,
+    }
+}
+
+/// Lift a peer's ban
+///
+/// 404 if the peer isn't banned.
+pub async fn unban_peer(
+    State(state): State<Arc<AppState>>,
+    Path(peer_id): Path<String>,
+) -> StatusCode {
+    let Ok(peer_id) = peer_id.parse::<Libp2pPeerId>() else {
+        return StatusCode::BAD_REQUEST;
+    };
+    match state.network.unban_peer(peer_id).await {
+        Ok(true) => StatusCode::NO_CONTENT,
+        Ok(false) => StatusCode::NOT_FOUND,
+        Err(_) => StatusCode::SERVICE_UNAVAILABLE,
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// DHT API Endpoints
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// How long a DHT lookup or store may take before the request gives up
+const DHT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A DHT record, with its value base64-encoded
+#[derive(Serialize)]
+pub struct DhtRecord {
+    pub key: String,
+    pub value: String,
+}
+
+/// Look up a DHT record by key
+///
+/// 404 if no peer returns the record before [`DHT_TIMEOUT`].
+pub async fn get_dht_record(
+    State(state): State<Arc<AppState>>,
+    Path(key): Path<String>,
+) -> Result<Json<DhtRecord>, StatusCode> {
+    let lookup = state.network.get_record(key.as_bytes().to_vec());
+    match tokio::time::timeout(DHT_TIMEOUT, lookup).await {
+        Ok(Ok(Some(value))) => Ok(Json(DhtRecord {
+            key,
+            value: base64::engine::general_purpose::STANDARD.encode(value),
+        })),
+        Ok(Ok(None)) | Err(_) => Err(StatusCode::NOT_FOUND),
+        Ok(Err(e)) => {
+            tracing::warn!("DHT lookup for {} failed: {}", key, e);
+            Err(StatusCode::BAD_GATEWAY)
+        }
+    }
+}
+
+/// Store the request body as the DHT record for `key`
+///
+/// Answers once the record is stored, 504 if that takes longer than
+/// [`DHT_TIMEOUT`].
+pub async fn put_dht_record(
+    State(state): State<Arc<AppState>>,
+    Path(key): Path<String>,
+    value: Bytes,
+) -> StatusCode {
+    let store = state
+        .network
+        .put_record(key.as_bytes().to_vec(), value.to_vec());
+    match tokio::time::timeout(DHT_TIMEOUT, store).await {
+        Ok(Ok(())) => StatusCode::NO_CONTENT,
+        Ok(Err(e)) => {
+            tracing::warn!("DHT store for {} failed: {}", key, e);
+            StatusCode::BAD_GATEWAY
+        }
+        Err(_) => StatusCode::GATEWAY_TIMEOUT,
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Content API Endpoints
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Keep a piece of provided content from being evicted by content GC
+///
+/// 404 if the content isn't stored.
+pub async fn pin_content(State(state): State<Arc<AppState>>, Path(id): Path<String>) -> StatusCode {
+    let Ok(id) = id.parse::<ContentId>() else {
+        return StatusCode::BAD_REQUEST;
+    };
+    pin_status(state.network.pin_content(id).await)
+}
+
+/// Let content GC evict a piece of provided content again
+///
+/// 404 if the content isn't stored.
+pub async fn unpin_content(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> StatusCode {
+    let Ok(id) = id.parse::<ContentId>() else {
+        return StatusCode::BAD_REQUEST;
+    };
+    pin_status(state.network.unpin_content(id).await)
+}
+
+fn pin_status(result: mycelial_network::Result<bool>) -> StatusCode {
+    match result {
+        Ok(true) => StatusCode::NO_CONTENT,
+        Ok(false) => StatusCode::NOT_FOUND,
+        Err(e) => {
+            tracing::warn!("Failed to change content pin: {}", e);
+            StatusCode::SERVICE_UNAVAILABLE
+        }
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Economics API Endpoints
 // ─────────────────────────────────────────────────────────────────────────────
@@ -95,6 +365,32 @@ pub async fn get_credit_lines_for_peer(
     Json(state.economics.get_credit_lines_for_peer(&peer_id))
 }
 
+/// Period covered by a credit statement, as Unix timestamps in milliseconds
+///
+/// Both ends are optional: a statement defaults to everything up to now.
+#[derive(Debug, Deserialize)]
+pub struct StatementPeriod {
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+}
+
+/// Get credit statements for a peer, one per credit line
+pub async fn get_credit_statements(
+    State(state): State<Arc<AppState>>,
+    Path(peer_id): Path<String>,
+    Query(period): Query<StatementPeriod>,
+) -> Json<Vec<CreditStatement>> {
+    let from = period
+        .from
+        .and_then(DateTime::<Utc>::from_timestamp_millis)
+        .unwrap_or(DateTime::<Utc>::MIN_UTC);
+    let to = period
+        .to
+        .and_then(DateTime::<Utc>::from_timestamp_millis)
+        .unwrap_or_else(Utc::now);
+    Json(state.economics.get_credit_statements(&peer_id, from, to))
+}
+
 /// List all proposals
 pub async fn list_proposals(State(state): State<Arc<AppState>>) -> Json<Vec<Proposal>> {
     Json(state.economics.get_all_proposals())
@@ -165,3 +461,250 @@ pub async fn get_peer_economics(
         vouches_given: state.economics.get_vouches_from_peer(&peer_id),
     })
 }
+
+/// ENR credit balance of a node
+#[derive(Serialize)]
+pub struct EnrBalance {
+    pub node_id: String,
+    pub balance: u64,
+}
+
+/// Get this node's ENR credit balance
+pub async fn get_local_balance(State(state): State<Arc<AppState>>) -> Json<EnrBalance> {
+    let node_id = state.enr_bridge.local_node_id();
+    Json(EnrBalance {
+        node_id: hex::encode(node_id.to_bytes()),
+        balance: state.enr_bridge.local_balance().await.amount,
+    })
+}
+
+/// Query a remote node's ENR credit balance
+///
+/// 400 unless the node ID is 64 hex characters, 404 if the node doesn't
+/// answer in time.
+pub async fn get_node_balance(
+    State(state): State<Arc<AppState>>,
+    Path(node_id): Path<String>,
+) -> Result<Json<EnrBalance>, StatusCode> {
+    let node = hex::decode(&node_id)
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .map(NodeId::from_bytes)
+        .ok_or(StatusCode::BAD_REQUEST)?;
+    match state.enr_bridge.query_balance(node).await {
+        Ok(balance) => Ok(Json(EnrBalance {
+            node_id,
+            balance: balance.amount,
+        })),
+        Err(QueryError::Timeout { .. }) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::warn!("Balance query for {} failed: {}", node_id, e);
+            Err(StatusCode::BAD_GATEWAY)
+        }
+    }
+}
+
+/// Entropy tax charged on this node's credit transfers
+#[derive(Serialize)]
+pub struct EntropyTax {
+    pub rate: f64,
+}
+
+/// Get the entropy tax rate paid to the revival pool
+pub async fn get_entropy_tax(State(state): State<Arc<AppState>>) -> Json<EntropyTax> {
+    Json(EntropyTax {
+        rate: state.enr_bridge.entropy_tax_rate().await.get(),
+    })
+}
+
+/// An account whose optimistic and Raft-committed balances differ
+#[cfg(feature = "openraft")]
+#[derive(Serialize)]
+pub struct BalanceAuditEntry {
+    pub node_id: String,
+    pub optimistic: u64,
+    pub committed: u64,
+    pub drift: i128,
+}
+
+/// Compare the optimistic credit ledger against the Raft ledger
+///
+/// Empty when the balances agree; 501 if no Raft ledger is attached to
+/// compare against.
+#[cfg(feature = "openraft")]
+pub async fn get_balance_audit(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<BalanceAuditEntry>>, StatusCode> {
+    if state.enr_bridge.raft_ledger().is_none() {
+        return Err(StatusCode::NOT_IMPLEMENTED);
+    }
+    let entries = state
+        .enr_bridge
+        .audit_balances()
+        .await
+        .into_iter()
+        .map(|d| BalanceAuditEntry {
+            node_id: hex::encode(d.account.node.to_bytes()),
+            optimistic: d.optimistic.amount,
+            committed: d.committed.amount,
+            drift: d.drift(),
+        })
+        .collect();
+    Ok(Json(entries))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mycelial_core::content::Content;
+    use mycelial_core::health::HealthStatus;
+    use mycelial_network::test_utils::MockNetworkHandle;
+    use mycelial_network::{Multiaddr, NetworkEvent};
+
+    #[tokio::test]
+    async fn test_health_reports_each_component() {
+        let mock = MockNetworkHandle::new();
+        let state = AppState::for_test(mock.handle()).await;
+
+        // No peers and no gradients yet: degraded, but still answering 200
+        let (code, Json(report)) = health(State(state)).await;
+        assert_eq!(code, StatusCode::OK);
+        assert_eq!(report.status, HealthLevel::Degraded);
+        assert_eq!(report.components["storage"], HealthStatus::Ok);
+        assert_eq!(
+            report.components["network"],
+            HealthStatus::degraded("no connected peers")
+        );
+        assert_eq!(
+            report.components["economics"],
+            HealthStatus::degraded("no fresh resource gradients")
+        );
+
+        // Down once the network service is gone
+        let state = AppState::for_test(mock.handle()).await;
+        mock.shutdown_graceful(std::time::Duration::from_secs(1))
+            .await
+            .unwrap();
+        let (code, Json(report)) = health(State(state)).await;
+        assert_eq!(code, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(report.status, HealthLevel::Down);
+    }
+
+    #[tokio::test]
+    async fn test_node_balance_status() {
+        let mock = MockNetworkHandle::new();
+        let state = AppState::for_test(mock.handle()).await;
+
+        let bad = get_node_balance(State(state.clone()), Path("not-a-node".to_string())).await;
+        assert_eq!(bad.err(), Some(StatusCode::BAD_REQUEST));
+
+        let local = hex::encode(state.enr_bridge.local_node_id().to_bytes());
+        let Json(balance) = get_node_balance(State(state), Path(local.clone()))
+            .await
+            .unwrap();
+        assert_eq!(balance.node_id, local);
+    }
+
+    #[tokio::test]
+    async fn test_node_info() {
+        let mock = MockNetworkHandle::new();
+        let state = AppState::for_test(mock.handle()).await;
+        let local_peer_id = mock.handle().local_peer_id();
+        let address: Multiaddr = "/ip4/127.0.0.1/tcp/9000".parse().unwrap();
+
+        // Reported twice, listed once
+        for _ in 0..2 {
+            let event = NetworkEvent::ListeningOn {
+                address: address.clone(),
+            };
+            crate::handle_network_event(event, &state, local_peer_id).await;
+        }
+
+        let Json(info) = node_info(State(state.clone())).await;
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(info.protocol_version, mycelial_core::PROTOCOL_VERSION);
+        assert_eq!(info.name, "test-node");
+        assert_eq!(info.peer_id, local_peer_id.to_string());
+        assert!(info.economics);
+        assert_eq!(info.transports, vec!["tcp"]);
+        assert_eq!(
+            info.listen_addrs,
+            vec![format!("{}/p2p/{}", address, local_peer_id)]
+        );
+
+        let event = NetworkEvent::ListenAddrExpired { address };
+        crate::handle_network_event(event, &state, local_peer_id).await;
+        let Json(info) = node_info(State(state)).await;
+        assert!(info.listen_addrs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_ban_endpoints() {
+        let mock = MockNetworkHandle::new();
+        let state = AppState::for_test(mock.handle()).await;
+        let peer_id = Libp2pPeerId::random();
+
+        let code = ban_peer(
+            State(state.clone()),
+            Json(BanRequest {
+                peer_id: peer_id.to_string(),
+                duration_secs: Some(600),
+                reason: default_ban_reason(),
+            }),
+        )
+        .await;
+        assert_eq!(code, StatusCode::NO_CONTENT);
+
+        let Json(bans) = list_bans(State(state.clone())).await.unwrap();
+        assert_eq!(bans.len(), 1);
+        assert_eq!(bans[0].peer_id, peer_id.to_string());
+        assert_eq!(bans[0].reason, "banned by operator");
+        assert!(bans[0].until.is_some());
+
+        let code = ban_peer(
+            State(state.clone()),
+            Json(BanRequest {
+                peer_id: "not-a-peer-id".to_string(),
+                duration_secs: None,
+                reason: default_ban_reason(),
+            }),
+        )
+        .await;
+        assert_eq!(code, StatusCode::BAD_REQUEST);
+
+        let unban = |id: String| unban_peer(State(state.clone()), Path(id));
+        assert_eq!(unban(peer_id.to_string()).await, StatusCode::NO_CONTENT);
+        assert_eq!(unban(peer_id.to_string()).await, StatusCode::NOT_FOUND);
+        assert_eq!(unban("nonsense".to_string()).await, StatusCode::BAD_REQUEST);
+        assert!(list_bans(State(state)).await.unwrap().0.is_empty());
+    }
+
+    #[cfg(feature = "openraft")]
+    #[tokio::test]
+    async fn test_balance_audit_needs_raft_ledger() {
+        let mock = MockNetworkHandle::new();
+        let state = AppState::for_test(mock.handle()).await;
+
+        let result = get_balance_audit(State(state)).await;
+        assert!(matches!(result, Err(StatusCode::NOT_IMPLEMENTED)));
+    }
+
+    #[tokio::test]
+    async fn test_content_pin_endpoints() {
+        let mock = MockNetworkHandle::new();
+        let state = AppState::for_test(mock.handle()).await;
+        let content = Content::text("keep me");
+        let id = content.id.to_string();
+
+        let pin = |id: String| pin_content(State(state.clone()), Path(id));
+        assert_eq!(pin(id.clone()).await, StatusCode::NOT_FOUND);
+        assert_eq!(pin("nonsense".to_string()).await, StatusCode::BAD_REQUEST);
+
+        mock.provide_content(content).await.unwrap();
+        assert_eq!(pin(id.clone()).await, StatusCode::NO_CONTENT);
+        assert_eq!(
+            unpin_content(State(state.clone()), Path(id)).await,
+            StatusCode::NO_CONTENT
+        );
+    }
+}
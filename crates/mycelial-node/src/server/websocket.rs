@@ -15,7 +15,9 @@ use std::sync::Arc;
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
+use super::economics_state::ECONOMICS_PUBLISH_DEADLINE;
 use super::messages::{ClientMessage, PeerListEntry, WsMessage};
+use super::rest::add_peer_quality;
 use crate::AppState;
 use mycelial_protocol::{
     topics, CastVote as ProtocolCastVote, CreateCreditLine as ProtocolCreateCreditLine,
@@ -51,7 +53,8 @@ async fn handle_socket(socket: WebSocket, state: Arc<AppState>) {
     // Send initial peer list
     match state.store.list_peers().await {
         Ok(peers) => {
-            let entries: Vec<PeerListEntry> = peers.into_iter().map(Into::into).collect();
+            let mut entries: Vec<PeerListEntry> = peers.into_iter().map(Into::into).collect();
+            add_peer_quality(&state, &mut entries).await;
             let init_msg = WsMessage::PeersList { peers: entries };
             if let Ok(json) = serde_json::to_string(&init_msg) {
                 let _ = sender.send(Message::Text(json)).await;
@@ -160,6 +163,7 @@ async fn handle_client_message(msg: ClientMessage, state: &AppState) {
                             room_id: room_id.clone(),
                             content: content.clone(),
                             timestamp,
+                            verified: true,
                         };
 
                         if let Err(e) = state.event_tx.send(echo_msg) {
@@ -178,7 +182,8 @@ async fn handle_client_message(msg: ClientMessage, state: &AppState) {
         ClientMessage::GetPeers => {
             // Peer list is sent on connect, but can be requested again
             if let Ok(peers) = state.store.list_peers().await {
-                let entries: Vec<PeerListEntry> = peers.into_iter().map(Into::into).collect();
+                let mut entries: Vec<PeerListEntry> = peers.into_iter().map(Into::into).collect();
+                add_peer_quality(state, &mut entries).await;
                 let msg = WsMessage::PeersList { peers: entries };
                 let _ = state.event_tx.send(msg);
             }
@@ -226,7 +231,11 @@ async fn handle_client_message(msg: ClientMessage, state: &AppState) {
             // Serialize and publish to network
             match serde_json::to_vec(&vouch_msg) {
                 Ok(data) => {
-                    if let Err(e) = state.network.publish(topics::VOUCH, data).await {
+                    if let Err(e) = state
+                        .network
+                        .publish_reliable(topics::VOUCH, data, ECONOMICS_PUBLISH_DEADLINE)
+                        .await
+                    {
                         error!("Failed to publish vouch request: {}", e);
                     } else {
                         info!("Vouch request published successfully");
@@ -276,7 +285,11 @@ async fn handle_client_message(msg: ClientMessage, state: &AppState) {
 
             match serde_json::to_vec(&ack_msg) {
                 Ok(data) => {
-                    if let Err(e) = state.network.publish(topics::VOUCH, data).await {
+                    if let Err(e) = state
+                        .network
+                        .publish_reliable(topics::VOUCH, data, ECONOMICS_PUBLISH_DEADLINE)
+                        .await
+                    {
                         error!("Failed to publish vouch ack: {}", e);
                     } else {
                         let echo_msg = WsMessage::VouchAck {
@@ -308,7 +321,11 @@ async fn handle_client_message(msg: ClientMessage, state: &AppState) {
 
             match serde_json::to_vec(&credit_msg) {
                 Ok(data) => {
-                    if let Err(e) = state.network.publish(topics::CREDIT, data).await {
+                    if let Err(e) = state
+                        .network
+                        .publish_reliable(topics::CREDIT, data, ECONOMICS_PUBLISH_DEADLINE)
+                        .await
+                    {
                         error!("Failed to publish credit line: {}", e);
                     } else {
                         let echo_msg = WsMessage::CreditLine {
@@ -349,7 +366,11 @@ async fn handle_client_message(msg: ClientMessage, state: &AppState) {
 
             match serde_json::to_vec(&transfer_msg) {
                 Ok(data) => {
-                    if let Err(e) = state.network.publish(topics::CREDIT, data).await {
+                    if let Err(e) = state
+                        .network
+                        .publish_reliable(topics::CREDIT, data, ECONOMICS_PUBLISH_DEADLINE)
+                        .await
+                    {
                         error!("Failed to publish credit transfer: {}", e);
                     } else {
                         let echo_msg = WsMessage::CreditTransfer {
@@ -386,7 +407,11 @@ async fn handle_client_message(msg: ClientMessage, state: &AppState) {
 
             match serde_json::to_vec(&proposal_msg) {
                 Ok(data) => {
-                    if let Err(e) = state.network.publish(topics::GOVERNANCE, data).await {
+                    if let Err(e) = state
+                        .network
+                        .publish_reliable(topics::GOVERNANCE, data, ECONOMICS_PUBLISH_DEADLINE)
+                        .await
+                    {
                         error!("Failed to publish proposal: {}", e);
                     } else {
                         let echo_msg = WsMessage::Proposal {
@@ -447,7 +472,11 @@ async fn handle_client_message(msg: ClientMessage, state: &AppState) {
 
             match serde_json::to_vec(&vote_msg) {
                 Ok(data) => {
-                    if let Err(e) = state.network.publish(topics::GOVERNANCE, data).await {
+                    if let Err(e) = state
+                        .network
+                        .publish_reliable(topics::GOVERNANCE, data, ECONOMICS_PUBLISH_DEADLINE)
+                        .await
+                    {
                         error!("Failed to publish vote: {}", e);
                     } else {
                         let echo_msg = WsMessage::VoteCast {
@@ -495,7 +524,11 @@ async fn handle_client_message(msg: ClientMessage, state: &AppState) {
 
             match serde_json::to_vec(&resource_msg) {
                 Ok(data) => {
-                    if let Err(e) = state.network.publish(topics::RESOURCE, data).await {
+                    if let Err(e) = state
+                        .network
+                        .publish_reliable(topics::RESOURCE, data, ECONOMICS_PUBLISH_DEADLINE)
+                        .await
+                    {
                         error!("Failed to publish resource contribution: {}", e);
                     } else {
                         let echo_msg = WsMessage::ResourceContribution {
@@ -816,41 +849,16 @@ async fn handle_client_message(msg: ClientMessage, state: &AppState) {
         ClientMessage::SendEnrCredit { to, amount } => {
             info!("SendEnrCredit: to='{}', amount={}", to, amount);
 
-            let timestamp = chrono::Utc::now().timestamp_millis();
-            static NONCE: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
-            let nonce = NONCE.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-
             // Parse recipient NodeId from hex string
             match parse_node_id(&to) {
                 Ok(to_node) => {
                     let credits = Credits::new(amount);
 
-                    // Transfer credits via EnrBridge
+                    // Transfer credits via EnrBridge; the dashboard hears about
+                    // it, and the new balance, from the bridge's credit events
                     match state.enr_bridge.transfer_credits(to_node, credits).await {
                         Ok(()) => {
                             info!("Credit transfer successful: {} -> {}", amount, to);
-
-                            // Get actual balance after transfer
-                            let balance = state.enr_bridge.local_balance().await;
-                            let tax = amount / 50; // 2% entropy tax
-
-                            let transfer_msg = WsMessage::EnrCreditTransfer {
-                                from: state.local_peer_id.to_string(),
-                                to: to.clone(),
-                                amount,
-                                tax,
-                                nonce,
-                                timestamp,
-                            };
-                            let _ = state.event_tx.send(transfer_msg);
-
-                            // Send actual balance update
-                            let balance_msg = WsMessage::EnrBalanceUpdate {
-                                node_id: state.local_peer_id.to_string(),
-                                balance: balance.amount,
-                                timestamp,
-                            };
-                            let _ = state.event_tx.send(balance_msg);
                         }
                         Err(e) => {
                             error!("Failed to transfer credits: {}", e);
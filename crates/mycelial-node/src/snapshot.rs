@@ -0,0 +1,248 @@
+//! Whole-node snapshots for backup and migration
+//!
+//! `--export` bundles the database and the identity file into one tar
+//! archive, and `--import` restores them into a fresh node, which then
+//! starts with the same peer ID. Subscribed topics and the economics state
+//! are kept in the database, so they travel with it.
+//!
+//! The archive starts with `manifest.json`, which records the snapshot
+//! format version and the SHA-256 of every other entry. Import refuses
+//! archives from a newer format and archives whose contents don't match
+//! their checksums.
+
+use anyhow::{bail, Context};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::Path;
+
+/// Snapshot format written by this build; older formats can be imported
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Archive entry holding the manifest
+const MANIFEST_ENTRY: &str = "manifest.json";
+
+/// Archive entry holding the SQLite database
+const DATABASE_ENTRY: &str = "state.db";
+
+/// How the node identity was stored when the snapshot was taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdentityKind {
+    /// A BIP39 mnemonic file (`--mnemonic-file`)
+    Mnemonic,
+    /// An encrypted key file (`--identity-file`)
+    KeyFile,
+}
+
+impl IdentityKind {
+    /// Archive entry holding the identity file
+    fn entry(self) -> &'static str {
+        match self {
+            IdentityKind::Mnemonic => "identity/mnemonic",
+            IdentityKind::KeyFile => "identity/key",
+        }
+    }
+
+    /// The flag naming where this kind of identity lives
+    pub fn flag(self) -> &'static str {
+        match self {
+            IdentityKind::Mnemonic => "--mnemonic-file",
+            IdentityKind::KeyFile => "--identity-file",
+        }
+    }
+}
+
+/// Description of a snapshot, stored as its first entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Snapshot format version
+    pub version: u32,
+    /// Version of the node that took it
+    pub node_version: String,
+    /// When it was taken
+    pub created_at: DateTime<Utc>,
+    /// Peer ID of the node it was taken from
+    pub peer_id: String,
+    /// How the identity is stored
+    pub identity: IdentityKind,
+    /// Topics the node was subscribed to, for reference
+    pub subscribed_topics: Vec<String>,
+    /// Hex SHA-256 of every other entry, by entry name
+    pub checksums: BTreeMap<String, String>,
+}
+
+/// A node's state, as bundled into an archive
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    /// What the snapshot holds
+    pub manifest: Manifest,
+    /// Contents of the SQLite database file
+    pub database: Vec<u8>,
+    /// Contents of the identity file
+    pub identity: Vec<u8>,
+}
+
+impl Snapshot {
+    /// Bundle a database and identity file, checksumming both
+    pub fn new(
+        peer_id: String,
+        identity_kind: IdentityKind,
+        identity: Vec<u8>,
+        database: Vec<u8>,
+        subscribed_topics: Vec<String>,
+    ) -> Self {
+        let checksums = BTreeMap::from([
+            (DATABASE_ENTRY.to_string(), sha256_hex(&database)),
+            (identity_kind.entry().to_string(), sha256_hex(&identity)),
+        ]);
+        Self {
+            manifest: Manifest {
+                version: SNAPSHOT_VERSION,
+                node_version: env!("CARGO_PKG_VERSION").to_string(),
+                created_at: Utc::now(),
+                peer_id,
+                identity: identity_kind,
+                subscribed_topics,
+                checksums,
+            },
+            database,
+            identity,
+        }
+    }
+
+    /// Write the snapshot as a tar archive at `path`
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        let file = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+
+        let mut archive = tar::Builder::new(file);
+        let manifest = serde_json::to_vec_pretty(&self.manifest)?;
+        for (name, data) in [
+            (MANIFEST_ENTRY, manifest.as_slice()),
+            (DATABASE_ENTRY, self.database.as_slice()),
+            (self.manifest.identity.entry(), self.identity.as_slice()),
+        ] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o600);
+            header.set_mtime(self.manifest.created_at.timestamp().max(0) as u64);
+            header.set_cksum();
+            archive.append_data(&mut header, name, data)?;
+        }
+        archive.into_inner()?.sync_all()?;
+        Ok(())
+    }
+
+    /// Read the archive at `path`, checking its version and checksums
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+
+        let mut entries = BTreeMap::new();
+        for entry in tar::Archive::new(file).entries()? {
+            let mut entry = entry?;
+            let name = entry.path()?.to_string_lossy().into_owned();
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data)?;
+            entries.insert(name, data);
+        }
+
+        let manifest: Manifest = match entries.remove(MANIFEST_ENTRY) {
+            Some(data) => serde_json::from_slice(&data).context("Invalid snapshot manifest")?,
+            None => bail!("{} is not a node snapshot (no manifest)", path.display()),
+        };
+        if manifest.version > SNAPSHOT_VERSION {
+            bail!(
+                "Snapshot format {} is newer than this node supports ({})",
+                manifest.version,
+                SNAPSHOT_VERSION
+            );
+        }
+
+        for (name, expected) in &manifest.checksums {
+            let Some(data) = entries.get(name) else {
+                bail!("Snapshot is missing {}", name);
+            };
+            if sha256_hex(data) != *expected {
+                bail!("Snapshot entry {} is corrupt (checksum mismatch)", name);
+            }
+        }
+        // Everything restored must have been checksummed
+        for name in [DATABASE_ENTRY, manifest.identity.entry()] {
+            if !manifest.checksums.contains_key(name) {
+                bail!("Snapshot has no checksum for {}", name);
+            }
+        }
+        let database = entries.remove(DATABASE_ENTRY).unwrap_or_default();
+        let identity = entries
+            .remove(manifest.identity.entry())
+            .unwrap_or_default();
+
+        Ok(Self {
+            manifest,
+            database,
+            identity,
+        })
+    }
+}
+
+/// Lowercase hex SHA-256 of `data`
+fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot() -> Snapshot {
+        Snapshot::new(
+            "12D3KooWTestPeerId".to_string(),
+            IdentityKind::KeyFile,
+            b"encrypted key".to_vec(),
+            b"SQLite format 3\0".to_vec(),
+            vec!["/mycelial/1.0.0/chat".to_string()],
+        )
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snapshot.tar");
+        let original = snapshot();
+        original.write(&path).unwrap();
+
+        // Never overwrites an existing archive
+        assert!(original.write(&path).is_err());
+
+        let restored = Snapshot::read(&path).unwrap();
+        assert_eq!(restored.manifest, original.manifest);
+        assert_eq!(restored.database, original.database);
+        assert_eq!(restored.identity, original.identity);
+    }
+
+    #[test]
+    fn test_snapshot_rejects_corruption_and_newer_formats() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut corrupt = snapshot();
+        corrupt.database = b"tampered".to_vec();
+        let path = dir.path().join("corrupt.tar");
+        corrupt.write(&path).unwrap();
+        let err = Snapshot::read(&path).unwrap_err();
+        assert!(err.to_string().contains("checksum mismatch"), "{}", err);
+
+        let mut newer = snapshot();
+        newer.manifest.version = SNAPSHOT_VERSION + 1;
+        let path = dir.path().join("newer.tar");
+        newer.write(&path).unwrap();
+        let err = Snapshot::read(&path).unwrap_err();
+        assert!(err.to_string().contains("newer"), "{}", err);
+    }
+}
@@ -94,7 +94,8 @@ fn test_chat_message_matches_typescript() {
         "to": null,
         "room_id": null,
         "content": "Hello world!",
-        "timestamp": 1703683200000_i64
+        "timestamp": 1703683200000_i64,
+        "verified": true
     });
 
     // Required fields
@@ -107,6 +108,7 @@ fn test_chat_message_matches_typescript() {
     assert!(msg["from_name"].is_string() || msg["from_name"].is_null());
     assert!(msg["to"].is_string() || msg["to"].is_null());
     assert!(msg["room_id"].is_string() || msg["room_id"].is_null());
+    assert!(msg["verified"].is_boolean());
 }
 
 #[test]
@@ -5,30 +5,8 @@
 
 use serde_json::json;
 
-// ============ Health Endpoint Tests ============
-
-#[test]
-fn test_health_endpoint_returns_ok() {
-    // The health endpoint should return "OK"
-    let response = "OK";
-    assert_eq!(response, "OK");
-}
-
 // ============ Node Info Response Tests ============
 
-#[test]
-fn test_node_info_structure() {
-    let node_info = json!({
-        "version": "0.1.0",
-        "name": "TestNode",
-        "peer_id": "12D3KooWTestPeerId"
-    });
-
-    assert!(node_info["version"].is_string());
-    assert!(node_info["name"].is_string());
-    assert!(node_info["peer_id"].is_string());
-}
-
 #[test]
 fn test_node_info_version_format() {
     // Version should follow semver format
@@ -171,6 +149,33 @@ fn test_get_peer_not_found() {
     assert!(peer.is_none());
 }
 
+// ============ Peer Cluster Response Tests ============
+
+#[test]
+fn test_peer_cluster_structure() {
+    let clusters = json!([
+        {
+            "cell": "gcpv",
+            "count": 2,
+            "centroid": {"latitude": 51.51, "longitude": -0.11, "altitude": null, "precision": null},
+            "peers": ["12D3KooWLondonA", "12D3KooWLondonB"]
+        },
+        {
+            "cell": "unknown",
+            "count": 1,
+            "centroid": null,
+            "peers": ["12D3KooWNowhere"]
+        }
+    ]);
+
+    for cluster in clusters.as_array().unwrap() {
+        let peers = cluster["peers"].as_array().unwrap();
+        assert_eq!(cluster["count"].as_u64().unwrap() as usize, peers.len());
+        // Only the unknown bucket lacks a centroid
+        assert_eq!(cluster["centroid"].is_null(), cluster["cell"] == "unknown");
+    }
+}
+
 // ============ API Response Content Type Tests ============
 
 #[test]
@@ -0,0 +1,248 @@
+//! Tallying governance proposals
+//!
+//! A proposal's `quorum` is the total vote weight (including abstentions)
+//! that must take part for the result to count, and its `threshold` is the
+//! fraction of for/(for + against) weight needed to pass. Both are checked
+//! inclusively: turnout exactly at quorum and approval exactly at threshold
+//! are enough.
+
+use crate::messages::{CastVote, CreateProposal, Vote};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Outcome of tallying a proposal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TallyOutcome {
+    /// Quorum was reached and approval met the threshold
+    Passed,
+    /// Quorum was reached but approval fell short (or the vote tied)
+    Failed,
+    /// Too little weight took part for the result to count
+    NoQuorum,
+}
+
+/// Result of tallying a proposal's votes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TallyResult {
+    /// Total weight voting for
+    pub votes_for: f64,
+    /// Total weight voting against
+    pub votes_against: f64,
+    /// Total weight abstaining
+    pub votes_abstain: f64,
+    /// Number of voters counted
+    pub voter_count: u32,
+    /// Total weight that took part, abstentions included
+    pub turnout: f64,
+    /// Turnout required for the result to count
+    pub quorum: f64,
+    /// votes_for / (votes_for + votes_against), or 0 when nobody voted either way
+    pub approval: f64,
+    /// Approval required to pass
+    pub threshold: f64,
+    /// For and against weight were exactly equal
+    pub tie: bool,
+    /// What the numbers add up to
+    pub outcome: TallyOutcome,
+}
+
+impl TallyResult {
+    /// Whether turnout met the quorum
+    pub fn quorum_reached(&self) -> bool {
+        self.outcome != TallyOutcome::NoQuorum
+    }
+}
+
+/// Tally the votes cast on `proposal`
+///
+/// Votes for other proposals are ignored, and when a voter has voted more
+/// than once only their latest vote counts.
+pub fn tally(proposal: &CreateProposal, votes: &[CastVote]) -> TallyResult {
+    let mut latest: HashMap<&str, &CastVote> = HashMap::new();
+    for vote in votes.iter().filter(|v| v.proposal_id == proposal.id) {
+        latest
+            .entry(vote.voter.as_str())
+            .and_modify(|current| {
+                if vote.timestamp >= current.timestamp {
+                    *current = vote;
+                }
+            })
+            .or_insert(vote);
+    }
+
+    tally_weighted(
+        proposal.quorum,
+        proposal.threshold,
+        latest.values().map(|v| (&v.vote, v.weight)),
+    )
+}
+
+/// Tally one `(vote, weight)` pair per voter against a quorum and threshold
+///
+/// Non-finite and negative weights count as zero. A tie never passes,
+/// whatever the threshold, and neither does a vote where everyone abstained.
+pub fn tally_weighted<'a>(
+    quorum: f64,
+    threshold: f64,
+    votes: impl IntoIterator<Item = (&'a Vote, f64)>,
+) -> TallyResult {
+    let mut votes_for = 0.0;
+    let mut votes_against = 0.0;
+    let mut votes_abstain = 0.0;
+    let mut voter_count = 0;
+    for (vote, weight) in votes {
+        let weight = if weight.is_finite() {
+            weight.max(0.0)
+        } else {
+            0.0
+        };
+        match vote {
+            Vote::For => votes_for += weight,
+            Vote::Against => votes_against += weight,
+            Vote::Abstain => votes_abstain += weight,
+        }
+        voter_count += 1;
+    }
+
+    let turnout = votes_for + votes_against + votes_abstain;
+    let decided = votes_for + votes_against;
+    let approval = if decided > 0.0 {
+        votes_for / decided
+    } else {
+        0.0
+    };
+    let tie = decided > 0.0 && votes_for == votes_against;
+
+    let outcome = if voter_count == 0 || turnout < quorum {
+        TallyOutcome::NoQuorum
+    } else if decided == 0.0 || tie || approval < threshold {
+        TallyOutcome::Failed
+    } else {
+        TallyOutcome::Passed
+    };
+
+    TallyResult {
+        votes_for,
+        votes_against,
+        votes_abstain,
+        voter_count,
+        turnout,
+        quorum,
+        approval,
+        threshold,
+        tie,
+        outcome,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn proposal(quorum: f64, threshold: f64) -> CreateProposal {
+        CreateProposal::new(
+            "alice".to_string(),
+            "Test".to_string(),
+            "A test".to_string(),
+        )
+        .with_quorum(quorum)
+        .with_threshold(threshold)
+    }
+
+    fn vote(proposal: &CreateProposal, voter: &str, vote: Vote, weight: f64) -> CastVote {
+        CastVote::new(proposal.id, voter.to_string(), vote, weight)
+    }
+
+    #[test]
+    fn test_tally_exactly_at_quorum() {
+        let p = proposal(0.5, 0.5);
+
+        let at = tally(&p, &[vote(&p, "a", Vote::For, 0.5)]);
+        assert_eq!(at.turnout, 0.5);
+        assert!(at.quorum_reached());
+        assert_eq!(at.outcome, TallyOutcome::Passed);
+
+        let below = tally(&p, &[vote(&p, "a", Vote::For, 0.25)]);
+        assert_eq!(below.outcome, TallyOutcome::NoQuorum);
+
+        // Abstentions count toward turnout
+        let abstained = tally(
+            &p,
+            &[
+                vote(&p, "a", Vote::Abstain, 0.25),
+                vote(&p, "b", Vote::For, 0.25),
+            ],
+        );
+        assert_eq!(abstained.turnout, 0.5);
+        assert_eq!(abstained.outcome, TallyOutcome::Passed);
+    }
+
+    #[test]
+    fn test_tally_exactly_at_threshold() {
+        let p = proposal(0.0, 0.75);
+
+        let at = tally(
+            &p,
+            &[
+                vote(&p, "a", Vote::For, 0.75),
+                vote(&p, "b", Vote::Against, 0.25),
+            ],
+        );
+        assert_eq!(at.approval, 0.75);
+        assert_eq!(at.outcome, TallyOutcome::Passed);
+
+        let below = tally(
+            &p,
+            &[
+                vote(&p, "a", Vote::For, 0.5),
+                vote(&p, "b", Vote::Against, 0.25),
+            ],
+        );
+        assert!(below.approval < 0.75);
+        assert_eq!(below.outcome, TallyOutcome::Failed);
+    }
+
+    #[test]
+    fn test_tally_ties_and_empty_votes() {
+        let p = proposal(0.0, 0.5);
+
+        let tie = tally(
+            &p,
+            &[
+                vote(&p, "a", Vote::For, 0.5),
+                vote(&p, "b", Vote::Against, 0.5),
+            ],
+        );
+        assert!(tie.tie);
+        assert_eq!(tie.outcome, TallyOutcome::Failed);
+
+        assert_eq!(tally(&p, &[]).outcome, TallyOutcome::NoQuorum);
+        assert_eq!(
+            tally(&p, &[vote(&p, "a", Vote::Abstain, 1.0)]).outcome,
+            TallyOutcome::Failed
+        );
+    }
+
+    #[test]
+    fn test_tally_counts_latest_vote_per_voter() {
+        let p = proposal(0.0, 0.5);
+        let other = proposal(0.0, 0.5);
+
+        let mut changed = vote(&p, "a", Vote::Against, 1.0);
+        changed.timestamp = Utc::now() + Duration::seconds(1);
+        let result = tally(
+            &p,
+            &[
+                vote(&p, "a", Vote::For, 1.0),
+                changed,
+                vote(&other, "b", Vote::For, 1.0),
+            ],
+        );
+        assert_eq!(result.voter_count, 1);
+        assert_eq!(result.votes_for, 0.0);
+        assert_eq!(result.votes_against, 1.0);
+        assert_eq!(result.outcome, TallyOutcome::Failed);
+    }
+}
@@ -11,6 +11,9 @@
 //! - [`messages::GovernanceMessage`] - Governance proposals and voting
 //! - [`messages::ResourceMessage`] - Resource sharing metrics
 //!
+//! Proposals are decided with [`governance::tally`], which enforces their
+//! quorum and approval threshold.
+//!
 //! # Gossipsub Topics
 //!
 //! Use [`messages::topics`] for the topic names:
@@ -20,8 +23,11 @@
 //! - `/mycelial/1.0.0/resource` - Resource metrics
 
 pub mod codec;
+pub mod governance;
 pub mod messages;
 
+pub use governance::{tally, TallyOutcome, TallyResult};
+
 // Re-export message types for convenience
 pub use messages::{
     // Topics
@@ -51,6 +57,7 @@ pub use messages::{
     ResourceMessage,
     ResourceMetrics,
     ResourcePoolUpdate,
+    ResourceReceipt,
     ResourceType,
     StorageMetrics,
     Vote,
@@ -58,6 +65,7 @@ pub use messages::{
     // Vouch protocol
     VouchMessage,
     VouchRequest,
+    VouchRevoke,
 };
 
 use mycelial_core::{Message, MycelialError, Result};
@@ -4,6 +4,7 @@
 //! for the Mycelial Economics system: vouching, credits, governance, and resources.
 
 use chrono::{DateTime, Utc};
+use mycelial_core::{Keypair, KeypairExt, PeerId, PublicKeyExt, SignatureBytes};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -33,6 +34,22 @@ pub enum VouchMessage {
     VouchAck(VouchAck),
     /// Reputation update notification
     ReputationUpdate(ReputationUpdate),
+    /// Voucher withdrawing an earlier vouch
+    VouchRevoke(VouchRevoke),
+}
+
+impl VouchMessage {
+    /// Peer the message claims to be sent by
+    ///
+    /// `None` for notifications that don't speak for a particular peer.
+    pub fn author(&self) -> Option<&str> {
+        match self {
+            Self::VouchRequest(req) => Some(&req.voucher),
+            Self::VouchAck(ack) => Some(&ack.from),
+            Self::VouchRevoke(revoke) => Some(&revoke.voucher),
+            Self::ReputationUpdate(_) => None,
+        }
+    }
 }
 
 /// A vouch request from one peer to another
@@ -96,6 +113,28 @@ pub struct VouchAck {
     pub timestamp: DateTime<Utc>,
 }
 
+/// Revocation of a vouch by the peer that gave it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VouchRevoke {
+    /// The vouch being revoked
+    pub vouch_id: Uuid,
+    /// Peer that gave the vouch (only they may revoke it)
+    pub voucher: String,
+    /// Timestamp
+    pub timestamp: DateTime<Utc>,
+}
+
+impl VouchRevoke {
+    /// Create a new revocation
+    pub fn new(vouch_id: Uuid, voucher: String) -> Self {
+        Self {
+            vouch_id,
+            voucher,
+            timestamp: Utc::now(),
+        }
+    }
+}
+
 /// Reputation update notification
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReputationUpdate {
@@ -123,6 +162,8 @@ pub enum ReputationChangeReason {
     FailedInteraction,
     /// Vouch expired
     VouchExpired { voucher: String },
+    /// Vouch revoked by the voucher
+    VouchRevoked { voucher: String },
     /// Initial reputation for new peer
     Initial,
     /// Governance participation
@@ -151,6 +192,20 @@ pub enum CreditMessage {
     LineUpdate(CreditLineUpdate),
 }
 
+impl CreditMessage {
+    /// Peer the message claims to be sent by
+    ///
+    /// `None` for notifications that don't speak for a particular peer.
+    pub fn author(&self) -> Option<&str> {
+        match self {
+            Self::CreateLine(line) => Some(&line.creditor),
+            Self::LineAck(ack) => Some(&ack.from),
+            Self::Transfer(transfer) => Some(&transfer.from),
+            Self::TransferAck(_) | Self::LineUpdate(_) => None,
+        }
+    }
+}
+
 /// Request to create a credit line
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateCreditLine {
@@ -290,6 +345,19 @@ pub enum GovernanceMessage {
     ProposalExecuted(ProposalExecuted),
 }
 
+impl GovernanceMessage {
+    /// Peer the message claims to be sent by
+    ///
+    /// `None` for notifications that don't speak for a particular peer.
+    pub fn author(&self) -> Option<&str> {
+        match self {
+            Self::CreateProposal(proposal) => Some(&proposal.proposer),
+            Self::CastVote(vote) => Some(&vote.voter),
+            Self::ProposalUpdate(_) | Self::ProposalExecuted(_) => None,
+        }
+    }
+}
+
 /// Create a new governance proposal
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateProposal {
@@ -489,6 +557,22 @@ pub enum ResourceMessage {
     Metrics(ResourceMetrics),
     /// Resource pool update
     PoolUpdate(ResourcePoolUpdate),
+    /// A consumer corroborating a contribution
+    Receipt(ResourceReceipt),
+}
+
+impl ResourceMessage {
+    /// Peer the message claims to be sent by
+    ///
+    /// `None` for pool updates, and for receipts, which carry the consumer's
+    /// own signature and may be relayed by anyone.
+    pub fn author(&self) -> Option<&str> {
+        match self {
+            Self::Contribution(contribution) => Some(&contribution.peer_id),
+            Self::Metrics(metrics) => Some(&metrics.peer_id),
+            Self::PoolUpdate(_) | Self::Receipt(_) => None,
+        }
+    }
 }
 
 /// Report of resource contribution
@@ -531,6 +615,61 @@ impl ResourceContribution {
     }
 }
 
+/// Signed confirmation from a consuming peer that a contribution was provided
+///
+/// The consumer's peer ID inlines their Ed25519 key, so anyone can check the
+/// signature without a key exchange.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceReceipt {
+    /// Contribution being corroborated
+    pub contribution_id: Uuid,
+    /// Peer that provided the resource
+    pub contributor: String,
+    /// Peer that consumed the resource and signed the receipt
+    pub consumer: String,
+    /// Amount the consumer confirms receiving, in the contribution's unit
+    pub amount: f64,
+    /// Timestamp
+    pub timestamp: DateTime<Utc>,
+    /// Consumer's signature over the fields above
+    pub signature: SignatureBytes,
+}
+
+impl ResourceReceipt {
+    /// Sign a receipt for `amount` of `contribution` as the holder of `keypair`
+    pub fn sign(contribution: &ResourceContribution, amount: f64, keypair: &Keypair) -> Self {
+        let mut receipt = Self {
+            contribution_id: contribution.id,
+            contributor: contribution.peer_id.clone(),
+            consumer: PeerId::from_public_key(&keypair.public_key()).to_string(),
+            amount,
+            timestamp: Utc::now(),
+            signature: SignatureBytes([0; 64]),
+        };
+        receipt.signature = keypair.sign_bytes(&receipt.signing_bytes());
+        receipt
+    }
+
+    /// Check that the receipt was signed by its consumer
+    pub fn verify(&self) -> mycelial_core::Result<()> {
+        PeerId::parse(&self.consumer)?
+            .to_public_key()?
+            .verify_bytes(&self.signing_bytes(), &self.signature)
+    }
+
+    /// Bytes covered by the signature
+    fn signing_bytes(&self) -> Vec<u8> {
+        serde_cbor::to_vec(&(
+            &self.contribution_id,
+            &self.contributor,
+            &self.consumer,
+            self.amount,
+            self.timestamp.timestamp_millis(),
+        ))
+        .expect("receipt fields always serialize")
+    }
+}
+
 /// Type of resource
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -810,4 +949,73 @@ mod tests {
             panic!("Wrong variant");
         }
     }
+
+    #[test]
+    fn test_resource_receipt_signature() {
+        let contrib = ResourceContribution::new(
+            "alice".to_string(),
+            ResourceType::Bandwidth,
+            100.0,
+            "MB".to_string(),
+        );
+        let keypair = Keypair::generate();
+
+        let receipt = ResourceReceipt::sign(&contrib, 80.0, &keypair);
+        assert_eq!(receipt.contribution_id, contrib.id);
+        assert_eq!(
+            receipt.consumer,
+            PeerId::from_public_key(&keypair.public_key()).to_string()
+        );
+        assert!(receipt.verify().is_ok());
+
+        // Survives the trip over the wire
+        let json = serde_json::to_string(&ResourceMessage::Receipt(receipt.clone())).unwrap();
+        match serde_json::from_str(&json).unwrap() {
+            ResourceMessage::Receipt(decoded) => assert!(decoded.verify().is_ok()),
+            _ => panic!("Wrong variant"),
+        }
+
+        // Inflating the amount or claiming another consumer breaks the signature
+        let mut inflated = receipt.clone();
+        inflated.amount = 100.0;
+        assert!(inflated.verify().is_err());
+
+        let mut forged = receipt;
+        forged.consumer = PeerId::from_public_key(&Keypair::generate().public_key()).to_string();
+        assert!(forged.verify().is_err());
+    }
+
+    #[test]
+    fn test_message_authors() {
+        let vouch = VouchMessage::VouchRequest(VouchRequest::new(
+            "alice".to_string(),
+            "bob".to_string(),
+            0.5,
+        ));
+        assert_eq!(vouch.author(), Some("alice"));
+
+        let transfer = CreditMessage::Transfer(CreditTransfer::new(
+            Uuid::new_v4(),
+            "bob".to_string(),
+            "alice".to_string(),
+            10.0,
+        ));
+        assert_eq!(transfer.author(), Some("bob"));
+
+        let vote = GovernanceMessage::CastVote(CastVote::new(
+            Uuid::new_v4(),
+            "carol".to_string(),
+            Vote::For,
+            1.0,
+        ));
+        assert_eq!(vote.author(), Some("carol"));
+
+        let executed = GovernanceMessage::ProposalExecuted(ProposalExecuted {
+            proposal_id: Uuid::new_v4(),
+            success: true,
+            result: "done".to_string(),
+            timestamp: Utc::now(),
+        });
+        assert_eq!(executed.author(), None);
+    }
 }
@@ -0,0 +1,38 @@
+-- Economics state snapshot tables for mycelial-node
+-- Version: 002
+
+-- Snapshot metadata (schema version, last save time)
+CREATE TABLE IF NOT EXISTS economics_meta (
+    key TEXT PRIMARY KEY,
+    value INTEGER NOT NULL
+);
+
+-- Credit lines, one JSON document per line
+CREATE TABLE IF NOT EXISTS economics_credit_lines (
+    id TEXT PRIMARY KEY,
+    data_json TEXT NOT NULL
+);
+
+-- Governance proposals including their recorded votes
+CREATE TABLE IF NOT EXISTS economics_proposals (
+    id TEXT PRIMARY KEY,
+    data_json TEXT NOT NULL
+);
+
+-- Vouch relationships
+CREATE TABLE IF NOT EXISTS economics_vouches (
+    id TEXT PRIMARY KEY,
+    data_json TEXT NOT NULL
+);
+
+-- Resource contributions, kept in arrival order
+CREATE TABLE IF NOT EXISTS economics_contributions (
+    seq INTEGER PRIMARY KEY AUTOINCREMENT,
+    data_json TEXT NOT NULL
+);
+
+-- Derived peer reputations
+CREATE TABLE IF NOT EXISTS economics_reputations (
+    peer_id TEXT PRIMARY KEY,
+    score REAL NOT NULL
+);
@@ -0,0 +1,8 @@
+-- Gossipsub topic subscriptions for mycelial-node
+-- Version: 003
+
+-- Topics the node was subscribed to, restored on restart
+CREATE TABLE IF NOT EXISTS subscribed_topics (
+    topic TEXT PRIMARY KEY,
+    subscribed_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+);
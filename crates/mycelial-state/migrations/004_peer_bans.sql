@@ -0,0 +1,11 @@
+-- Peer bans for mycelial-node
+-- Version: 004
+
+-- Bans placed on peers, restored on restart
+CREATE TABLE IF NOT EXISTS peer_bans (
+    peer_id TEXT PRIMARY KEY,
+    reason TEXT NOT NULL,
+    banned_at INTEGER NOT NULL,
+    -- NULL for bans that last until lifted by hand
+    until INTEGER
+);
@@ -0,0 +1,16 @@
+-- Content store for mycelial-node
+-- Version: 005
+
+-- Content-addressed data kept for other peers, evicted by GC unless pinned
+CREATE TABLE IF NOT EXISTS content (
+    id TEXT PRIMARY KEY,
+    data BLOB NOT NULL,
+    content_type TEXT NOT NULL,
+    metadata_json TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    stored_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+    last_accessed INTEGER NOT NULL,
+    pinned INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE INDEX IF NOT EXISTS idx_content_last_accessed ON content(last_accessed);
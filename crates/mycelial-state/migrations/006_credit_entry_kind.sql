@@ -0,0 +1,6 @@
+-- Credit transaction kinds for mycelial-node
+-- Version: 006
+
+-- Whether a transaction moved credit or charged interest; SQLite has no
+-- ADD COLUMN IF NOT EXISTS, so this only runs when the column is missing
+ALTER TABLE credit_transactions ADD COLUMN kind TEXT NOT NULL DEFAULT 'transfer';
@@ -424,6 +424,11 @@ mod tests {
     use chrono::Utc;
     use mycelial_core::message::MessageType;
     use mycelial_core::peer::PeerId;
+    use mycelial_core::Keypair;
+
+    fn random_peer_id() -> PeerId {
+        PeerId::from_public_key(&Keypair::generate().public_key())
+    }
 
     #[test]
     fn test_memory_cache() {
@@ -441,21 +446,23 @@ mod tests {
     fn test_peer_cache() {
         let cache = PeerCache::new(10);
 
+        let peer_id = random_peer_id();
         let peer_info = PeerInfo {
-            id: PeerId("peer1".to_string()),
+            id: peer_id.clone(),
             public_key: "2wMHpFAjZbL9GkXP8n3E1".to_string(), // base58 encoded
             addresses: vec![],
             first_seen: Utc::now(),
             last_seen: Utc::now(),
             name: None,
+            location: None,
         };
         let reputation = Reputation::new(0.8);
 
         cache.insert(peer_info.clone(), reputation.clone());
 
-        assert!(cache.contains("peer1"));
-        let (info, rep) = cache.get("peer1").unwrap();
-        assert_eq!(info.id.as_str(), "peer1");
+        assert!(cache.contains(peer_id.as_str()));
+        let (info, rep) = cache.get(peer_id.as_str()).unwrap();
+        assert_eq!(info.id, peer_id);
         assert!((rep.score - 0.8).abs() < 0.001);
 
         // Test trusted peers filter
@@ -467,21 +474,18 @@ mod tests {
     fn test_message_cache() {
         let cache = MessageCache::new(10);
 
-        let msg = Message::new(
-            MessageType::Content,
-            PeerId("sender".to_string()),
-            b"Hello".to_vec(),
-        );
+        let sender = random_peer_id();
+        let msg = Message::new(MessageType::Content, sender.clone(), b"Hello".to_vec());
         let msg_id = msg.id;
 
         cache.insert(msg);
 
         assert!(cache.contains(&msg_id));
         let retrieved = cache.get(&msg_id).unwrap();
-        assert_eq!(retrieved.sender.as_str(), "sender");
+        assert_eq!(retrieved.sender, sender);
 
         // Test sender index
-        let from_sender = cache.get_from_sender("sender");
+        let from_sender = cache.get_from_sender(sender.as_str());
         assert_eq!(from_sender.len(), 1);
     }
 
@@ -489,22 +493,21 @@ mod tests {
     fn test_credit_cache() {
         let cache = CreditCache::new(10);
 
-        let rel = CreditRelationship::new(
-            PeerId("creditor".to_string()),
-            PeerId("debtor".to_string()),
-            100.0,
-        );
+        let (creditor, debtor) = (random_peer_id(), random_peer_id());
+        let rel = CreditRelationship::new(creditor.clone(), debtor.clone(), 100.0);
 
         cache.insert(rel);
 
-        let retrieved = cache.get_between("creditor", "debtor").unwrap();
+        let retrieved = cache
+            .get_between(creditor.as_str(), debtor.as_str())
+            .unwrap();
         assert_eq!(retrieved.credit_limit, 100.0);
 
         // Test peer index
-        let for_creditor = cache.get_for_peer("creditor");
+        let for_creditor = cache.get_for_peer(creditor.as_str());
         assert_eq!(for_creditor.len(), 1);
 
-        let for_debtor = cache.get_for_peer("debtor");
+        let for_debtor = cache.get_for_peer(debtor.as_str());
         assert_eq!(for_debtor.len(), 1);
     }
 
@@ -45,6 +45,10 @@ pub enum StateError {
     #[error("Sync error: {0}")]
     Sync(String),
 
+    /// Sync journal error
+    #[error("Journal error: {0}")]
+    Journal(String),
+
     /// Internal error
     #[error("Internal error: {0}")]
     Internal(String),
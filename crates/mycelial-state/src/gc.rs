@@ -0,0 +1,133 @@
+//! Garbage collection for stored content
+//!
+//! Content kept for other peers would otherwise pile up forever. A GC pass
+//! first drops unpinned content nobody has read for `max_age`, then evicts
+//! unpinned content least recently accessed first until what is left fits
+//! in `max_bytes`. Pinned content is never evicted, even if that leaves the
+//! store over budget.
+
+use chrono::{DateTime, Utc};
+use mycelial_core::content::ContentId;
+use serde::Serialize;
+use std::time::Duration;
+
+/// Size and access information about one stored piece of content
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentUsage {
+    /// The content
+    pub id: ContentId,
+    /// Size of its data in bytes
+    pub size: u64,
+    /// When it was last stored or read
+    pub last_accessed: DateTime<Utc>,
+    /// Whether it is exempt from eviction
+    pub pinned: bool,
+}
+
+/// Outcome of a GC pass
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct GcStats {
+    /// Content evicted, oldest access first
+    pub evicted: Vec<ContentId>,
+    /// Bytes freed by the evictions
+    pub bytes_freed: u64,
+    /// Pieces of content left
+    pub remaining_items: usize,
+    /// Bytes of content left
+    pub remaining_bytes: u64,
+    /// Bytes of pinned content, which GC never frees
+    pub pinned_bytes: u64,
+}
+
+/// Choose the content to evict so the rest fits in `max_bytes`
+///
+/// Expired content goes regardless of the budget; after that the least
+/// recently accessed unpinned content goes until the budget is met.
+pub fn plan_gc(
+    entries: &[ContentUsage],
+    max_bytes: u64,
+    max_age: Duration,
+    now: DateTime<Utc>,
+) -> GcStats {
+    let cutoff = chrono::Duration::from_std(max_age)
+        .ok()
+        .and_then(|age| now.checked_sub_signed(age));
+
+    let mut candidates: Vec<&ContentUsage> = entries.iter().filter(|e| !e.pinned).collect();
+    candidates.sort_by_key(|e| e.last_accessed);
+
+    let mut remaining_bytes: u64 = entries.iter().map(|e| e.size).sum();
+    let mut stats = GcStats {
+        pinned_bytes: entries.iter().filter(|e| e.pinned).map(|e| e.size).sum(),
+        ..GcStats::default()
+    };
+    for entry in candidates {
+        let expired = cutoff.is_some_and(|cutoff| entry.last_accessed < cutoff);
+        if !expired && remaining_bytes <= max_bytes {
+            break;
+        }
+        stats.evicted.push(entry.id);
+        stats.bytes_freed += entry.size;
+        remaining_bytes -= entry.size;
+    }
+
+    stats.remaining_items = entries.len() - stats.evicted.len();
+    stats.remaining_bytes = remaining_bytes;
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(seed: u8, size: u64, age_secs: i64, pinned: bool, now: DateTime<Utc>) -> ContentUsage {
+        ContentUsage {
+            id: ContentId::hash(&[seed]),
+            size,
+            last_accessed: now - chrono::Duration::seconds(age_secs),
+            pinned,
+        }
+    }
+
+    #[test]
+    fn test_plan_gc_evicts_least_recently_used_unpinned() {
+        let now = Utc::now();
+        let entries = [
+            usage(1, 100, 50, false, now),
+            usage(2, 100, 300, true, now),
+            usage(3, 100, 200, false, now),
+            usage(4, 100, 10, false, now),
+        ];
+
+        // Over budget by 150 bytes: the two oldest unpinned items go
+        let stats = plan_gc(&entries, 250, Duration::from_secs(3600), now);
+        assert_eq!(stats.evicted, vec![entries[2].id, entries[0].id]);
+        assert_eq!(stats.bytes_freed, 200);
+        assert_eq!(stats.remaining_items, 2);
+        assert_eq!(stats.remaining_bytes, 200);
+        assert_eq!(stats.pinned_bytes, 100);
+
+        // Pinned content stays even when that leaves us over budget
+        let stats = plan_gc(&entries, 0, Duration::from_secs(3600), now);
+        assert_eq!(stats.evicted.len(), 3);
+        assert_eq!(stats.remaining_bytes, 100);
+    }
+
+    #[test]
+    fn test_plan_gc_drops_expired_within_budget() {
+        let now = Utc::now();
+        let entries = [
+            usage(1, 10, 7200, false, now),
+            usage(2, 10, 7200, true, now),
+            usage(3, 10, 60, false, now),
+        ];
+
+        let stats = plan_gc(&entries, u64::MAX, Duration::from_secs(3600), now);
+        assert_eq!(stats.evicted, vec![entries[0].id]);
+        assert_eq!(stats.remaining_items, 2);
+
+        assert!(plan_gc(&entries, u64::MAX, Duration::MAX, now)
+            .evicted
+            .is_empty());
+    }
+}
@@ -0,0 +1,161 @@
+//! Journal of applied state updates, for replaying sync offline
+//!
+//! With a journal enabled (see [`StateSync::enable_journal`]), every update
+//! passed to [`StateSync::apply_update`] is appended to a file as one JSON
+//! line, together with the vector clock at the time and what the resolver
+//! decided to do with it. [`StateSync::replay`] folds those lines back into
+//! a [`SyncState`]; because the recorded decisions are followed rather than
+//! re-derived, the replay reproduces the merge that actually happened, even
+//! where it depended on the store's contents at the time.
+//!
+//! [`StateSync::enable_journal`]: crate::StateSync::enable_journal
+//! [`StateSync::apply_update`]: crate::StateSync::apply_update
+//! [`StateSync::replay`]: crate::StateSync::replay
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use crate::error::{Result, StateError};
+use crate::sync::{StateUpdate, VectorClock};
+
+/// What the resolver did with an incoming update
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Resolution {
+    /// The update won and was written to the store
+    Applied,
+    /// An update at least as new had already been applied
+    Stale,
+    /// The update's counters were no higher than the stored ones
+    Unchanged,
+    /// The update refers to a peer the store doesn't know
+    UnknownPeer,
+}
+
+impl Resolution {
+    /// Whether the update changed the state
+    pub fn is_applied(self) -> bool {
+        self == Resolution::Applied
+    }
+}
+
+/// One line of the journal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// When the update was resolved
+    pub recorded_at: DateTime<Utc>,
+    /// The local vector clock after resolving it
+    pub clock: VectorClock,
+    /// What the resolver decided
+    pub resolution: Resolution,
+    /// The update itself
+    pub update: StateUpdate,
+}
+
+/// Merged state rebuilt from a journal
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SyncState {
+    /// The last recorded vector clock
+    pub clock: VectorClock,
+    /// The winning update for each key (see [`StateUpdate::key`])
+    ///
+    /// Reputation updates hold the merged grow-only counters rather than
+    /// those of any single update.
+    pub updates: BTreeMap<String, StateUpdate>,
+    /// Entries the resolver applied
+    pub applied: usize,
+    /// Entries the resolver skipped
+    pub skipped: usize,
+}
+
+impl SyncState {
+    /// Fold one journal entry into the state, following its recorded resolution
+    pub fn apply(&mut self, entry: &JournalEntry) {
+        self.clock.merge(&entry.clock);
+        if !entry.resolution.is_applied() {
+            self.skipped += 1;
+            return;
+        }
+        self.applied += 1;
+
+        let merged = match (&entry.update, self.updates.get(&entry.update.key())) {
+            (
+                StateUpdate::ReputationUpdate {
+                    peer_id,
+                    successful_interactions,
+                    failed_interactions,
+                    timestamp,
+                },
+                Some(StateUpdate::ReputationUpdate {
+                    successful_interactions: successful,
+                    failed_interactions: failed,
+                    ..
+                }),
+            ) => StateUpdate::ReputationUpdate {
+                peer_id: peer_id.clone(),
+                successful_interactions: (*successful_interactions).max(*successful),
+                failed_interactions: (*failed_interactions).max(*failed),
+                timestamp: *timestamp,
+            },
+            (update, _) => update.clone(),
+        };
+        self.updates.insert(entry.update.key(), merged);
+    }
+}
+
+/// An append-only journal file
+pub(crate) struct Journal {
+    path: PathBuf,
+    file: File,
+}
+
+impl Journal {
+    /// Open `path` for appending, creating it if needed
+    pub(crate) fn open(path: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| {
+                StateError::Journal(format!("Failed to open {}: {}", path.display(), e))
+            })?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+        })
+    }
+
+    /// Append an entry as a single line
+    pub(crate) fn append(&mut self, entry: &JournalEntry) -> Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        self.file.write_all(&line).map_err(|e| {
+            StateError::Journal(format!("Failed to write {}: {}", self.path.display(), e))
+        })
+    }
+}
+
+/// Read every entry of the journal at `path`, oldest first
+pub fn read_journal(path: &Path) -> Result<Vec<JournalEntry>> {
+    let file = File::open(path)
+        .map_err(|e| StateError::Journal(format!("Failed to open {}: {}", path.display(), e)))?;
+
+    let mut entries = Vec::new();
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| {
+            StateError::Journal(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = serde_json::from_str(&line).map_err(|e| {
+            StateError::Deserialization(format!("{} line {}: {}", path.display(), index + 1, e))
+        })?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
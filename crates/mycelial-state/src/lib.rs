@@ -6,6 +6,8 @@
 //!
 //! - **storage**: SQLite-based persistence with sqlx
 //! - **cache**: LRU in-memory caching for peers, messages, and credit relationships
+//! - **gc**: Eviction planning that keeps stored content within a byte budget
+//! - **journal**: Recording of resolved sync updates for offline replay
 //! - **sync**: State synchronization with vector clocks and CRDT-style merge strategies
 //! - **error**: State-specific error types
 //!
@@ -32,11 +34,15 @@
 
 pub mod cache;
 pub mod error;
+pub mod gc;
+pub mod journal;
 pub mod storage;
 pub mod sync;
 
 // Re-exports for convenience
 pub use cache::{CacheStats, CreditCache, MemoryCache, MessageCache, PeerCache, StateCache};
 pub use error::{Result, StateError};
-pub use storage::SqliteStore;
+pub use gc::GcStats;
+pub use journal::{JournalEntry, Resolution, SyncState};
+pub use storage::{EconomicsSnapshot, SqliteStore, StoredPeerBan, ECONOMICS_SCHEMA_VERSION};
 pub use sync::{PeerInfoUpdate, StateSync, StateUpdate, VectorClock};
@@ -1,28 +1,76 @@
 //! SQLite storage backend implementation
 //!
-//! This module provides persistent storage for peers, messages, and credit
-//! relationships using SQLite with sqlx.
+//! This module provides persistent storage for peers, messages, credit
+//! relationships and content using SQLite with sqlx.
 
 use async_trait::async_trait;
-use chrono::{TimeZone, Utc};
+use chrono::{DateTime, TimeZone, Utc};
 use mycelial_core::{
-    credit::CreditRelationship,
+    content::{Content, ContentId},
+    credit::{CreditEntry, CreditEntryKind, CreditRelationship, MAX_CREDIT_HISTORY},
+    health::{HealthCheck, HealthStatus},
+    location::Location,
     message::{Message, MessageType},
     peer::{PeerId, PeerInfo},
     reputation::{Reputation, ReputationSnapshot},
-    Result as CoreResult, StateStore,
+    ContentStore, Result as CoreResult, StateStore,
 };
 use sqlx::{
     sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions},
     Row,
 };
+use std::path::Path;
 use std::str::FromStr;
+use std::time::Duration;
 use tracing::{debug, info};
 use uuid::Uuid;
 
 use crate::error::{Result, StateError};
+use crate::gc::{self, ContentUsage, GcStats};
+
+/// Current version of the economics snapshot schema
+///
+/// Bump this when the layout of [`EconomicsSnapshot`] records changes so older
+/// binaries refuse to load snapshots they cannot interpret.
+pub const ECONOMICS_SCHEMA_VERSION: i64 = 2;
+
+/// How long the health check waits for the database to answer
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Serialized economics state as stored in the `economics_*` tables
+///
+/// Records are opaque JSON documents keyed by ID; the node owns their format.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EconomicsSnapshot {
+    /// Schema version the records were written with
+    pub version: i64,
+    /// Credit lines as (id, json)
+    pub credit_lines: Vec<(String, String)>,
+    /// Proposals as (id, json)
+    pub proposals: Vec<(String, String)>,
+    /// Vouches as (id, json)
+    pub vouches: Vec<(String, String)>,
+    /// Resource contributions in arrival order
+    pub contributions: Vec<String>,
+    /// Peer reputations as (peer_id, score)
+    pub reputations: Vec<(String, f64)>,
+}
+
+/// A ban on a peer as stored in the `peer_bans` table
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredPeerBan {
+    /// The banned peer
+    pub peer_id: String,
+    /// Why it was banned
+    pub reason: String,
+    /// When the ban was placed
+    pub banned_at: DateTime<Utc>,
+    /// When the ban lifts, or `None` if it lasts until lifted by hand
+    pub until: Option<DateTime<Utc>>,
+}
 
 /// SQLite-based storage backend
+#[derive(Clone)]
 pub struct SqliteStore {
     pool: SqlitePool,
 }
@@ -64,6 +112,44 @@ impl SqliteStore {
             .await
             .map_err(|e| StateError::Migration(e.to_string()))?;
 
+        // Economics snapshot tables
+        sqlx::query(include_str!("../migrations/002_economics.sql"))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| StateError::Migration(e.to_string()))?;
+
+        // Topic subscriptions
+        sqlx::query(include_str!("../migrations/003_topics.sql"))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| StateError::Migration(e.to_string()))?;
+
+        // Peer bans
+        sqlx::query(include_str!("../migrations/004_peer_bans.sql"))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| StateError::Migration(e.to_string()))?;
+
+        // Content store
+        sqlx::query(include_str!("../migrations/005_content.sql"))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| StateError::Migration(e.to_string()))?;
+
+        // Credit transaction kinds, unless a previous run added them
+        let has_kind = sqlx::query(
+            "SELECT 1 FROM pragma_table_info('credit_transactions') WHERE name = 'kind'",
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .is_some();
+        if !has_kind {
+            sqlx::query(include_str!("../migrations/006_credit_entry_kind.sql"))
+                .execute(&self.pool)
+                .await
+                .map_err(|e| StateError::Migration(e.to_string()))?;
+        }
+
         debug!("Migrations completed successfully");
         Ok(())
     }
@@ -73,6 +159,38 @@ impl SqliteStore {
         &self.pool
     }
 
+    /// Flush the WAL into the main database file and close all connections
+    ///
+    /// Call this on shutdown so no `-wal`/`-shm` files are left behind.
+    pub async fn close(&self) -> Result<()> {
+        info!("Closing SQLite store");
+
+        sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+            .execute(&self.pool)
+            .await?;
+        self.pool.close().await;
+
+        Ok(())
+    }
+
+    /// Write a consistent copy of the database to `path`
+    ///
+    /// Safe while the store is in use. The copy is a single self-contained
+    /// file; `path` must not exist yet.
+    pub async fn backup_to(&self, path: &Path) -> Result<()> {
+        if path.exists() {
+            return Err(StateError::InvalidData(format!(
+                "Backup target {} already exists",
+                path.display()
+            )));
+        }
+        sqlx::query("VACUUM INTO ?")
+            .bind(path.to_string_lossy().into_owned())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
     // ========== Peer Operations ==========
 
     /// Store or update a peer
@@ -87,6 +205,11 @@ impl SqliteStore {
         let first_seen = info.first_seen.timestamp();
         let last_seen = info.last_seen.timestamp();
         let display_name = info.name.as_deref();
+        let location_json = info
+            .location
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
 
         let (reputation_score, successful, failed, history_json) = match reputation {
             Some(rep) => (
@@ -101,14 +224,15 @@ impl SqliteStore {
         sqlx::query(
             r#"
             INSERT INTO peers (
-                peer_id, public_key, display_name, addresses_json,
+                peer_id, public_key, display_name, addresses_json, location_json,
                 reputation_score, successful_interactions, failed_interactions,
                 reputation_history_json, first_seen, last_seen
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT(peer_id) DO UPDATE SET
                 public_key = excluded.public_key,
                 display_name = COALESCE(excluded.display_name, peers.display_name),
                 addresses_json = excluded.addresses_json,
+                location_json = COALESCE(excluded.location_json, peers.location_json),
                 reputation_score = excluded.reputation_score,
                 successful_interactions = excluded.successful_interactions,
                 failed_interactions = excluded.failed_interactions,
@@ -121,6 +245,7 @@ impl SqliteStore {
         .bind(public_key)
         .bind(display_name)
         .bind(&addresses_json)
+        .bind(&location_json)
         .bind(reputation_score)
         .bind(successful)
         .bind(failed)
@@ -243,6 +368,32 @@ impl SqliteStore {
         Ok(())
     }
 
+    /// Record the geographic location a peer reported
+    pub async fn update_peer_location(&self, peer_id: &str, location: &Location) -> Result<()> {
+        let location_json = serde_json::to_string(location)?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE peers SET location_json = ?, updated_at = strftime('%s', 'now')
+            WHERE peer_id = ?
+            "#,
+        )
+        .bind(&location_json)
+        .bind(peer_id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(StateError::NotFound {
+                entity: "peer".to_string(),
+                id: peer_id.to_string(),
+            });
+        }
+
+        debug!("Updated location for peer: {}", peer_id);
+        Ok(())
+    }
+
     /// Update peer last seen timestamp
     pub async fn touch_peer(&self, peer_id: &str) -> Result<()> {
         let now = Utc::now().timestamp();
@@ -295,11 +446,17 @@ impl SqliteStore {
         let first_seen: i64 = row.get("first_seen");
         let last_seen: i64 = row.get("last_seen");
 
+        let location_json: Option<String> = row.get("location_json");
+
         let addresses: Vec<String> = serde_json::from_str(&addresses_json)
             .map_err(|e| StateError::Deserialization(e.to_string()))?;
+        let location = location_json
+            .map(|json| serde_json::from_str::<Location>(&json))
+            .transpose()
+            .map_err(|e| StateError::Deserialization(e.to_string()))?;
 
         Ok(PeerInfo {
-            id: PeerId(peer_id),
+            id: parse_peer_id(&peer_id)?,
             public_key,
             addresses,
             first_seen: Utc
@@ -311,6 +468,7 @@ impl SqliteStore {
                 .single()
                 .unwrap_or_else(Utc::now),
             name: display_name,
+            location,
         })
     }
 
@@ -493,8 +651,8 @@ impl SqliteStore {
         Ok(Message {
             id: Uuid::parse_str(&id).map_err(|e| StateError::Deserialization(e.to_string()))?,
             message_type,
-            sender: PeerId(sender),
-            recipient: recipient.map(PeerId),
+            sender: parse_peer_id(&sender)?,
+            recipient: recipient.as_deref().map(parse_peer_id).transpose()?,
             payload,
             timestamp: Utc
                 .timestamp_opt(timestamp, 0)
@@ -507,6 +665,9 @@ impl SqliteStore {
     // ========== Credit Relationship Operations ==========
 
     /// Store or update a credit relationship
+    ///
+    /// The history isn't stored here: `credit_transactions` is the only
+    /// record of it, written with [`record_credit_entry`](Self::record_credit_entry).
     pub async fn upsert_credit_relationship(&self, rel: &CreditRelationship) -> Result<String> {
         let id = format!("{}_{}", rel.creditor.as_str(), rel.debtor.as_str());
         let creditor = rel.creditor.as_str();
@@ -558,7 +719,7 @@ impl SqliteStore {
         .await?;
 
         match row {
-            Some(row) => Ok(Some(self.row_to_credit_relationship(&row)?)),
+            Some(row) => Ok(Some(self.row_to_credit_relationship(&row).await?)),
             None => Ok(None),
         }
     }
@@ -583,7 +744,7 @@ impl SqliteStore {
         .await?;
 
         match row {
-            Some(row) => Ok(Some(self.row_to_credit_relationship(&row)?)),
+            Some(row) => Ok(Some(self.row_to_credit_relationship(&row).await?)),
             None => Ok(None),
         }
     }
@@ -609,7 +770,7 @@ impl SqliteStore {
 
         let mut results = Vec::with_capacity(rows.len());
         for row in rows {
-            results.push(self.row_to_credit_relationship(&row)?);
+            results.push(self.row_to_credit_relationship(&row).await?);
         }
 
         Ok(results)
@@ -630,7 +791,7 @@ impl SqliteStore {
 
         let mut results = Vec::with_capacity(rows.len());
         for row in rows {
-            results.push(self.row_to_credit_relationship(&row)?);
+            results.push(self.row_to_credit_relationship(&row).await?);
         }
 
         Ok(results)
@@ -643,22 +804,42 @@ impl SqliteStore {
         amount: f64,
         balance_after: f64,
         description: Option<&str>,
+    ) -> Result<()> {
+        let entry = CreditEntry {
+            timestamp: Utc::now(),
+            kind: CreditEntryKind::Transfer,
+            amount,
+            balance: balance_after,
+            memo: description.map(str::to_string),
+        };
+        self.record_credit_entry(relationship_id, &entry).await
+    }
+
+    /// Record a transfer or interest entry of a credit relationship's history
+    pub async fn record_credit_entry(
+        &self,
+        relationship_id: &str,
+        entry: &CreditEntry,
     ) -> Result<()> {
         let id = Uuid::new_v4().to_string();
-        let timestamp = Utc::now().timestamp();
+        let kind = match entry.kind {
+            CreditEntryKind::Transfer => "transfer",
+            CreditEntryKind::Interest => "interest",
+        };
 
         sqlx::query(
             r#"
-            INSERT INTO credit_transactions (id, relationship_id, amount, balance_after, description, timestamp)
-            VALUES (?, ?, ?, ?, ?, ?)
+            INSERT INTO credit_transactions (id, relationship_id, amount, balance_after, description, timestamp, kind)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&id)
         .bind(relationship_id)
-        .bind(amount)
-        .bind(balance_after)
-        .bind(description)
-        .bind(timestamp)
+        .bind(entry.amount)
+        .bind(entry.balance)
+        .bind(entry.memo.as_deref())
+        .bind(entry.timestamp.timestamp())
+        .bind(kind)
         .execute(&self.pool)
         .await?;
 
@@ -666,11 +847,78 @@ impl SqliteStore {
         Ok(())
     }
 
-    // Helper to convert row to CreditRelationship
-    fn row_to_credit_relationship(
+    /// Transactions on a credit relationship between `from` and `to`
+    /// (inclusive), oldest first
+    ///
+    /// The transaction description becomes the entry's memo.
+    pub async fn list_credit_transactions(
+        &self,
+        relationship_id: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<CreditEntry>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT amount, balance_after, description, timestamp, kind
+            FROM credit_transactions
+            WHERE relationship_id = ? AND timestamp >= ? AND timestamp <= ?
+            ORDER BY timestamp ASC, rowid ASC
+            "#,
+        )
+        .bind(relationship_id)
+        .bind(from.timestamp())
+        .bind(to.timestamp())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(Self::row_to_credit_entry).collect())
+    }
+
+    /// The last [`MAX_CREDIT_HISTORY`] entries of a credit relationship's
+    /// history, oldest first
+    pub async fn credit_history(&self, relationship_id: &str) -> Result<Vec<CreditEntry>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT amount, balance_after, description, timestamp, kind
+            FROM credit_transactions
+            WHERE relationship_id = ?
+            ORDER BY timestamp DESC, rowid DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(relationship_id)
+        .bind(MAX_CREDIT_HISTORY as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().rev().map(Self::row_to_credit_entry).collect())
+    }
+
+    // Helper to convert row to CreditEntry
+    fn row_to_credit_entry(row: &sqlx::sqlite::SqliteRow) -> CreditEntry {
+        let timestamp: i64 = row.get("timestamp");
+        let kind: String = row.get("kind");
+        CreditEntry {
+            timestamp: Utc
+                .timestamp_opt(timestamp, 0)
+                .single()
+                .unwrap_or_else(Utc::now),
+            kind: match kind.as_str() {
+                "interest" => CreditEntryKind::Interest,
+                _ => CreditEntryKind::Transfer,
+            },
+            amount: row.get("amount"),
+            balance: row.get("balance_after"),
+            memo: row.get("description"),
+        }
+    }
+
+    // Helper to convert row to CreditRelationship, with its history
+    async fn row_to_credit_relationship(
         &self,
         row: &sqlx::sqlite::SqliteRow,
     ) -> Result<CreditRelationship> {
+        let id: String = row.get("id");
         let creditor: String = row.get("creditor_peer_id");
         let debtor: String = row.get("debtor_peer_id");
         let credit_limit: f64 = row.get("credit_limit");
@@ -680,8 +928,8 @@ impl SqliteStore {
         let last_transaction: i64 = row.get("last_transaction");
 
         Ok(CreditRelationship {
-            creditor: PeerId(creditor),
-            debtor: PeerId(debtor),
+            creditor: parse_peer_id(&creditor)?,
+            debtor: parse_peer_id(&debtor)?,
             credit_limit,
             balance,
             active: active != 0,
@@ -693,6 +941,9 @@ impl SqliteStore {
                 .timestamp_opt(last_transaction, 0)
                 .single()
                 .unwrap_or_else(Utc::now),
+            // The schema has no interest column; rates live with the node's credit lines
+            interest_rate: 0.0,
+            history: self.credit_history(&id).await?,
         })
     }
 
@@ -744,6 +995,435 @@ impl SqliteStore {
 
         Ok(())
     }
+
+    // ========== Topic Subscription Operations ==========
+
+    /// Remember a subscribed topic
+    pub async fn add_subscribed_topic(&self, topic: &str) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO subscribed_topics (topic) VALUES (?) ON CONFLICT(topic) DO NOTHING",
+        )
+        .bind(topic)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Forget a topic after unsubscribing
+    pub async fn remove_subscribed_topic(&self, topic: &str) -> Result<()> {
+        sqlx::query("DELETE FROM subscribed_topics WHERE topic = ?")
+            .bind(topic)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// List remembered topics in subscription order
+    pub async fn list_subscribed_topics(&self) -> Result<Vec<String>> {
+        let rows = sqlx::query("SELECT topic FROM subscribed_topics ORDER BY subscribed_at, topic")
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.iter().map(|row| row.get("topic")).collect())
+    }
+
+    // ========== Peer Ban Operations ==========
+
+    /// Store a ban, replacing any earlier ban on the same peer
+    pub async fn save_peer_ban(&self, ban: &StoredPeerBan) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO peer_bans (peer_id, reason, banned_at, until) VALUES (?, ?, ?, ?)
+            ON CONFLICT(peer_id) DO UPDATE SET
+                reason = excluded.reason,
+                banned_at = excluded.banned_at,
+                until = excluded.until
+            "#,
+        )
+        .bind(&ban.peer_id)
+        .bind(&ban.reason)
+        .bind(ban.banned_at.timestamp())
+        .bind(ban.until.map(|until| until.timestamp()))
+        .execute(&self.pool)
+        .await?;
+
+        debug!("Saved ban on peer: {}", ban.peer_id);
+        Ok(())
+    }
+
+    /// Forget a peer's ban after it is lifted
+    pub async fn remove_peer_ban(&self, peer_id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM peer_bans WHERE peer_id = ?")
+            .bind(peer_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// List the bans still in force, dropping those that have expired
+    pub async fn list_peer_bans(&self) -> Result<Vec<StoredPeerBan>> {
+        let now = Utc::now().timestamp();
+        sqlx::query("DELETE FROM peer_bans WHERE until IS NOT NULL AND until <= ?")
+            .bind(now)
+            .execute(&self.pool)
+            .await?;
+
+        let rows = sqlx::query(
+            "SELECT peer_id, reason, banned_at, until FROM peer_bans ORDER BY banned_at",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let banned_at: i64 = row.get("banned_at");
+                let until: Option<i64> = row.get("until");
+                StoredPeerBan {
+                    peer_id: row.get("peer_id"),
+                    reason: row.get("reason"),
+                    banned_at: Utc
+                        .timestamp_opt(banned_at, 0)
+                        .single()
+                        .unwrap_or_else(Utc::now),
+                    until: until.and_then(|until| Utc.timestamp_opt(until, 0).single()),
+                }
+            })
+            .collect())
+    }
+
+    // ========== Content Operations ==========
+
+    /// Store a piece of content, keeping its pin if it was already stored
+    pub async fn store_content(&self, content: &Content) -> Result<()> {
+        let metadata_json = serde_json::to_string(&content.metadata)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO content (id, data, content_type, metadata_json, size, last_accessed)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT(id) DO UPDATE SET last_accessed = excluded.last_accessed
+            "#,
+        )
+        .bind(content.id.to_hex())
+        .bind(&content.data)
+        .bind(&content.content_type)
+        .bind(&metadata_json)
+        .bind(content.data.len() as i64)
+        .bind(Utc::now().timestamp())
+        .execute(&self.pool)
+        .await?;
+
+        debug!("Stored content: {}", content.id);
+        Ok(())
+    }
+
+    /// Get a piece of content, marking it as recently accessed
+    pub async fn get_content(&self, id: &ContentId) -> Result<Option<Content>> {
+        let row = sqlx::query(
+            r#"
+            UPDATE content SET last_accessed = ? WHERE id = ?
+            RETURNING data, content_type, metadata_json
+            "#,
+        )
+        .bind(Utc::now().timestamp())
+        .bind(id.to_hex())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|row| {
+            let metadata_json: String = row.get("metadata_json");
+            Ok(Content {
+                id: *id,
+                data: row.get("data"),
+                content_type: row.get("content_type"),
+                metadata: serde_json::from_str(&metadata_json)
+                    .map_err(|e| StateError::Deserialization(e.to_string()))?,
+            })
+        })
+        .transpose()
+    }
+
+    /// Delete a piece of content, pinned or not, returning whether it was
+    /// stored
+    pub async fn remove_content(&self, id: &ContentId) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM content WHERE id = ?")
+            .bind(id.to_hex())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Exempt a piece of content from GC, returning whether it is stored
+    pub async fn pin_content(&self, id: &ContentId) -> Result<bool> {
+        self.set_content_pinned(id, true).await
+    }
+
+    /// Let GC evict a piece of content again, returning whether it is stored
+    pub async fn unpin_content(&self, id: &ContentId) -> Result<bool> {
+        self.set_content_pinned(id, false).await
+    }
+
+    async fn set_content_pinned(&self, id: &ContentId, pinned: bool) -> Result<bool> {
+        let result = sqlx::query("UPDATE content SET pinned = ? WHERE id = ?")
+            .bind(pinned)
+            .bind(id.to_hex())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Evict unpinned content not accessed within `max_age`, then the least
+    /// recently accessed unpinned content until the rest fits in `max_bytes`
+    ///
+    /// The caller should stop providing the evicted content.
+    pub async fn gc_content(&self, max_bytes: u64, max_age: Duration) -> Result<GcStats> {
+        let mut tx = self.pool.begin().await?;
+        let rows = sqlx::query("SELECT id, size, last_accessed, pinned FROM content")
+            .fetch_all(&mut *tx)
+            .await?;
+        let entries = rows
+            .iter()
+            .map(|row| {
+                let id: String = row.get("id");
+                let size: i64 = row.get("size");
+                let last_accessed: i64 = row.get("last_accessed");
+                Ok(ContentUsage {
+                    id: ContentId::from_hex(&id)
+                        .map_err(|e| StateError::Deserialization(e.to_string()))?,
+                    size: size as u64,
+                    last_accessed: Utc
+                        .timestamp_opt(last_accessed, 0)
+                        .single()
+                        .unwrap_or_else(Utc::now),
+                    pinned: row.get("pinned"),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let plan = gc::plan_gc(&entries, max_bytes, max_age, Utc::now());
+        let mut stats = GcStats {
+            evicted: Vec::with_capacity(plan.evicted.len()),
+            bytes_freed: 0,
+            ..plan
+        };
+        for id in plan.evicted {
+            let size = entries
+                .iter()
+                .find(|entry| entry.id == id)
+                .map_or(0, |entry| entry.size);
+            let deleted = sqlx::query("DELETE FROM content WHERE id = ? AND pinned = 0")
+                .bind(id.to_hex())
+                .execute(&mut *tx)
+                .await?
+                .rows_affected()
+                == 1;
+            if deleted {
+                stats.evicted.push(id);
+                stats.bytes_freed += size;
+            } else {
+                // Pinned since the plan was made, so it stays
+                stats.remaining_items += 1;
+                stats.remaining_bytes += size;
+                stats.pinned_bytes += size;
+            }
+        }
+        tx.commit().await?;
+
+        if !stats.evicted.is_empty() {
+            info!(
+                "Content GC evicted {} items ({} bytes), {} bytes left",
+                stats.evicted.len(),
+                stats.bytes_freed,
+                stats.remaining_bytes
+            );
+        }
+        Ok(stats)
+    }
+
+    // ========== Economics Snapshot Operations ==========
+
+    /// Replace the stored economics state with `snapshot`
+    ///
+    /// All tables are rewritten in a single transaction so a crash mid-save
+    /// leaves the previous snapshot intact.
+    pub async fn save_economics_snapshot(&self, snapshot: &EconomicsSnapshot) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        for table in [
+            "economics_credit_lines",
+            "economics_proposals",
+            "economics_vouches",
+            "economics_contributions",
+            "economics_reputations",
+        ] {
+            sqlx::query(&format!("DELETE FROM {}", table))
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        for (id, json) in &snapshot.credit_lines {
+            sqlx::query("INSERT INTO economics_credit_lines (id, data_json) VALUES (?, ?)")
+                .bind(id)
+                .bind(json)
+                .execute(&mut *tx)
+                .await?;
+        }
+        for (id, json) in &snapshot.proposals {
+            sqlx::query("INSERT INTO economics_proposals (id, data_json) VALUES (?, ?)")
+                .bind(id)
+                .bind(json)
+                .execute(&mut *tx)
+                .await?;
+        }
+        for (id, json) in &snapshot.vouches {
+            sqlx::query("INSERT INTO economics_vouches (id, data_json) VALUES (?, ?)")
+                .bind(id)
+                .bind(json)
+                .execute(&mut *tx)
+                .await?;
+        }
+        for json in &snapshot.contributions {
+            sqlx::query("INSERT INTO economics_contributions (data_json) VALUES (?)")
+                .bind(json)
+                .execute(&mut *tx)
+                .await?;
+        }
+        for (peer_id, score) in &snapshot.reputations {
+            sqlx::query("INSERT INTO economics_reputations (peer_id, score) VALUES (?, ?)")
+                .bind(peer_id)
+                .bind(score)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO economics_meta (key, value)
+            VALUES ('schema_version', ?), ('saved_at', strftime('%s', 'now'))
+            ON CONFLICT(key) DO UPDATE SET value = excluded.value
+            "#,
+        )
+        .bind(snapshot.version)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        debug!(
+            "Saved economics snapshot: {} credit lines, {} proposals, {} vouches",
+            snapshot.credit_lines.len(),
+            snapshot.proposals.len(),
+            snapshot.vouches.len()
+        );
+        Ok(())
+    }
+
+    /// Remember the entropy tax rate set by governance
+    pub async fn save_entropy_tax_rate(&self, rate: f64) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO economics_meta (key, value) VALUES ('entropy_tax_rate', ?)
+            ON CONFLICT(key) DO UPDATE SET value = excluded.value
+            "#,
+        )
+        .bind(rate)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// The entropy tax rate last set by governance, if any
+    pub async fn load_entropy_tax_rate(&self) -> Result<Option<f64>> {
+        // The column is INTEGER, so a rate of 0 would come back as one
+        let row = sqlx::query(
+            r#"
+            SELECT CAST(value AS REAL) AS value FROM economics_meta
+            WHERE key = 'entropy_tax_rate'
+            "#,
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| row.get("value")))
+    }
+
+    /// Load the stored economics state
+    ///
+    /// Returns `None` if no snapshot has been saved yet, and an error if the
+    /// snapshot was written by a newer schema version than this build supports.
+    pub async fn load_economics_snapshot(&self) -> Result<Option<EconomicsSnapshot>> {
+        let version: Option<i64> =
+            sqlx::query("SELECT value FROM economics_meta WHERE key = 'schema_version'")
+                .fetch_optional(&self.pool)
+                .await?
+                .map(|row| row.get("value"));
+
+        let version = match version {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        if version > ECONOMICS_SCHEMA_VERSION {
+            return Err(StateError::InvalidData(format!(
+                "economics snapshot version {} is newer than supported version {}",
+                version, ECONOMICS_SCHEMA_VERSION
+            )));
+        }
+
+        let keyed = |rows: Vec<sqlx::sqlite::SqliteRow>| -> Vec<(String, String)> {
+            rows.iter()
+                .map(|row| (row.get("id"), row.get("data_json")))
+                .collect()
+        };
+
+        let credit_lines = keyed(
+            sqlx::query("SELECT id, data_json FROM economics_credit_lines")
+                .fetch_all(&self.pool)
+                .await?,
+        );
+        let proposals = keyed(
+            sqlx::query("SELECT id, data_json FROM economics_proposals")
+                .fetch_all(&self.pool)
+                .await?,
+        );
+        let vouches = keyed(
+            sqlx::query("SELECT id, data_json FROM economics_vouches")
+                .fetch_all(&self.pool)
+                .await?,
+        );
+        let contributions =
+            sqlx::query("SELECT data_json FROM economics_contributions ORDER BY seq")
+                .fetch_all(&self.pool)
+                .await?
+                .iter()
+                .map(|row| row.get("data_json"))
+                .collect();
+        let reputations = sqlx::query("SELECT peer_id, score FROM economics_reputations")
+            .fetch_all(&self.pool)
+            .await?
+            .iter()
+            .map(|row| (row.get("peer_id"), row.get("score")))
+            .collect();
+
+        Ok(Some(EconomicsSnapshot {
+            version,
+            credit_lines,
+            proposals,
+            vouches,
+            contributions,
+            reputations,
+        }))
+    }
+}
+
+/// Parse a peer ID read back from the database
+fn parse_peer_id(id: &str) -> Result<PeerId> {
+    PeerId::parse(id).map_err(|e| StateError::Deserialization(e.to_string()))
 }
 
 // Implement the core StateStore trait
@@ -777,20 +1457,186 @@ impl StateStore for SqliteStore {
     }
 }
 
+// Serve network content from the GC-managed content table
+#[async_trait]
+impl ContentStore for SqliteStore {
+    async fn put_content(&self, content: &Content) -> CoreResult<()> {
+        self.store_content(content)
+            .await
+            .map_err(|e| mycelial_core::MycelialError::Storage(e.to_string()))
+    }
+
+    async fn fetch_content(&self, id: &ContentId) -> CoreResult<Option<Content>> {
+        self.get_content(id)
+            .await
+            .map_err(|e| mycelial_core::MycelialError::Storage(e.to_string()))
+    }
+
+    async fn remove_content(&self, id: &ContentId) -> CoreResult<bool> {
+        self.remove_content(id)
+            .await
+            .map_err(|e| mycelial_core::MycelialError::Storage(e.to_string()))
+    }
+
+    async fn set_pinned(&self, id: &ContentId, pinned: bool) -> CoreResult<bool> {
+        self.set_content_pinned(id, pinned)
+            .await
+            .map_err(|e| mycelial_core::MycelialError::Storage(e.to_string()))
+    }
+}
+
+/// The store is up while the database answers a trivial query in time
+#[async_trait]
+impl HealthCheck for SqliteStore {
+    async fn health(&self) -> HealthStatus {
+        if self.pool.is_closed() {
+            return HealthStatus::down("database pool closed");
+        }
+        let query = sqlx::query("SELECT 1").execute(&self.pool);
+        match tokio::time::timeout(HEALTH_CHECK_TIMEOUT, query).await {
+            Ok(Ok(_)) => HealthStatus::Ok,
+            Ok(Err(e)) => HealthStatus::down(format!("database query failed: {}", e)),
+            // A busy pool may just be slow, so don't report it down
+            Err(_) => HealthStatus::degraded("database not responding"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use mycelial_core::Keypair;
+
+    fn random_peer_id() -> PeerId {
+        PeerId::from_public_key(&Keypair::generate().public_key())
+    }
 
     async fn create_test_store() -> SqliteStore {
         SqliteStore::new(":memory:").await.unwrap()
     }
 
+    #[tokio::test]
+    async fn test_store_health() {
+        let store = create_test_store().await;
+        assert_eq!(store.health().await, HealthStatus::Ok);
+
+        store.pool.close().await;
+        assert!(matches!(store.health().await, HealthStatus::Down(_)));
+    }
+
+    #[tokio::test]
+    async fn test_backup_to() {
+        let store = create_test_store().await;
+        store.add_subscribed_topic("chat").await.unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("backup.db");
+        store.backup_to(&path).await.unwrap();
+        assert!(store.backup_to(&path).await.is_err());
+
+        let copy = SqliteStore::new(&format!("sqlite:{}", path.display()))
+            .await
+            .unwrap();
+        assert_eq!(copy.list_subscribed_topics().await.unwrap(), vec!["chat"]);
+    }
+
+    #[tokio::test]
+    async fn test_subscribed_topics_round_trip() {
+        let store = create_test_store().await;
+
+        store
+            .add_subscribed_topic("/mycelial/1.0.0/room/a")
+            .await
+            .unwrap();
+        store
+            .add_subscribed_topic("/mycelial/1.0.0/room/b")
+            .await
+            .unwrap();
+        store
+            .add_subscribed_topic("/mycelial/1.0.0/room/a")
+            .await
+            .unwrap();
+        store
+            .remove_subscribed_topic("/mycelial/1.0.0/room/b")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store.list_subscribed_topics().await.unwrap(),
+            vec!["/mycelial/1.0.0/room/a".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_peer_bans_round_trip() {
+        let store = create_test_store().await;
+        let banned_at = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let ban = |peer_id: &str, until: Option<DateTime<Utc>>| StoredPeerBan {
+            peer_id: peer_id.to_string(),
+            reason: "spam".to_string(),
+            banned_at,
+            until,
+        };
+
+        store.save_peer_ban(&ban("forever", None)).await.unwrap();
+        store
+            .save_peer_ban(&ban("expired", Some(banned_at)))
+            .await
+            .unwrap();
+        store
+            .save_peer_ban(&ban(
+                "lifted",
+                Some(Utc::now() + chrono::Duration::hours(1)),
+            ))
+            .await
+            .unwrap();
+        store.remove_peer_ban("lifted").await.unwrap();
+
+        assert_eq!(
+            store.list_peer_bans().await.unwrap(),
+            vec![ban("forever", None)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_content_gc_spares_pinned() {
+        let store = create_test_store().await;
+        let pinned = Content::text("keep me");
+        let stale = Content::text("evict me");
+
+        store.store_content(&pinned).await.unwrap();
+        store.store_content(&stale).await.unwrap();
+        assert!(store.pin_content(&pinned.id).await.unwrap());
+        assert!(!store
+            .pin_content(&Content::text("missing").id)
+            .await
+            .unwrap());
+
+        let stats = store.gc_content(0, Duration::MAX).await.unwrap();
+        assert_eq!(stats.evicted, vec![stale.id]);
+        assert_eq!(stats.remaining_items, 1);
+        assert!(store.get_content(&stale.id).await.unwrap().is_none());
+
+        let kept = store.get_content(&pinned.id).await.unwrap().unwrap();
+        assert_eq!(kept.as_str(), Some("keep me"));
+
+        assert!(store.unpin_content(&pinned.id).await.unwrap());
+        let stats = store.gc_content(0, Duration::MAX).await.unwrap();
+        assert_eq!(stats.evicted, vec![pinned.id]);
+
+        // Removal ignores pins
+        store.store_content(&pinned).await.unwrap();
+        assert!(store.pin_content(&pinned.id).await.unwrap());
+        assert!(store.remove_content(&pinned.id).await.unwrap());
+        assert!(!store.remove_content(&pinned.id).await.unwrap());
+    }
+
     #[tokio::test]
     async fn test_peer_crud() {
         let store = create_test_store().await;
 
         // Create peer info
-        let peer_id = PeerId("test_peer_123".to_string());
+        let peer_id = random_peer_id();
         let peer_info = PeerInfo {
             id: peer_id.clone(),
             public_key: "3mJr7AoUXx2Wqd5s8N4Df".to_string(), // base58 encoded
@@ -798,6 +1644,7 @@ mod tests {
             first_seen: Utc::now(),
             last_seen: Utc::now(),
             name: Some("Test Peer".to_string()),
+            location: None,
         };
 
         let reputation = Reputation::new(0.75);
@@ -809,8 +1656,8 @@ mod tests {
             .unwrap();
 
         // Retrieve peer
-        let (retrieved, rep) = store.get_peer("test_peer_123").await.unwrap().unwrap();
-        assert_eq!(retrieved.id.as_str(), "test_peer_123");
+        let (retrieved, rep) = store.get_peer(peer_id.as_str()).await.unwrap().unwrap();
+        assert_eq!(retrieved.id, peer_id);
         assert_eq!(retrieved.name, Some("Test Peer".to_string()));
         assert!((rep.score - 0.75).abs() < 0.001);
 
@@ -818,9 +1665,22 @@ mod tests {
         let peers = store.list_peers().await.unwrap();
         assert_eq!(peers.len(), 1);
 
+        // A reported location survives later upserts without one
+        let location = Location::new(51.5072, -0.1276);
+        store
+            .update_peer_location(peer_id.as_str(), &location)
+            .await
+            .unwrap();
+        store
+            .upsert_peer(&peer_info, Some(&reputation))
+            .await
+            .unwrap();
+        let (retrieved, _) = store.get_peer(peer_id.as_str()).await.unwrap().unwrap();
+        assert_eq!(retrieved.location, Some(location));
+
         // Delete peer
-        store.delete_peer("test_peer_123").await.unwrap();
-        assert!(store.get_peer("test_peer_123").await.unwrap().is_none());
+        store.delete_peer(peer_id.as_str()).await.unwrap();
+        assert!(store.get_peer(peer_id.as_str()).await.unwrap().is_none());
     }
 
     #[tokio::test]
@@ -828,7 +1688,7 @@ mod tests {
         let store = create_test_store().await;
 
         // First create the sender peer (foreign key requirement)
-        let sender = PeerId("sender_peer".to_string());
+        let sender = random_peer_id();
         let sender_info = PeerInfo {
             id: sender.clone(),
             public_key: "2wMHpFAjZbL9GkXP8n3E1".to_string(), // base58 encoded
@@ -836,6 +1696,7 @@ mod tests {
             first_seen: Utc::now(),
             last_seen: Utc::now(),
             name: Some("Sender".to_string()),
+            location: None,
         };
         store.upsert_peer(&sender_info, None).await.unwrap();
 
@@ -853,11 +1714,11 @@ mod tests {
         // Retrieve message
         let retrieved = store.get_message(&msg_id).await.unwrap().unwrap();
         assert_eq!(retrieved.id, msg_id);
-        assert_eq!(retrieved.sender.as_str(), "sender_peer");
+        assert_eq!(retrieved.sender, sender);
         assert_eq!(retrieved.payload, b"Hello, world!");
 
         // List messages from sender
-        let messages = store.list_messages_from("sender_peer", 10).await.unwrap();
+        let messages = store.list_messages_from(sender.as_str(), 10).await.unwrap();
         assert_eq!(messages.len(), 1);
     }
 
@@ -866,8 +1727,8 @@ mod tests {
         let store = create_test_store().await;
 
         // First create the peer records (foreign key requirement)
-        let creditor = PeerId("creditor_peer".to_string());
-        let debtor = PeerId("debtor_peer".to_string());
+        let creditor = random_peer_id();
+        let debtor = random_peer_id();
 
         let creditor_info = PeerInfo {
             id: creditor.clone(),
@@ -876,6 +1737,7 @@ mod tests {
             first_seen: Utc::now(),
             last_seen: Utc::now(),
             name: Some("Creditor".to_string()),
+            location: None,
         };
         store.upsert_peer(&creditor_info, None).await.unwrap();
 
@@ -886,6 +1748,7 @@ mod tests {
             first_seen: Utc::now(),
             last_seen: Utc::now(),
             name: Some("Debtor".to_string()),
+            location: None,
         };
         store.upsert_peer(&debtor_info, None).await.unwrap();
 
@@ -901,14 +1764,14 @@ mod tests {
             .await
             .unwrap()
             .unwrap();
-        assert_eq!(retrieved.creditor.as_str(), "creditor_peer");
-        assert_eq!(retrieved.debtor.as_str(), "debtor_peer");
+        assert_eq!(retrieved.creditor, creditor);
+        assert_eq!(retrieved.debtor, debtor);
         assert_eq!(retrieved.credit_limit, 100.0);
         assert_eq!(retrieved.balance, 0.0);
 
         // Get between peers
         let retrieved = store
-            .get_credit_relationship_between("creditor_peer", "debtor_peer")
+            .get_credit_relationship_between(creditor.as_str(), debtor.as_str())
             .await
             .unwrap()
             .unwrap();
@@ -916,10 +1779,56 @@ mod tests {
 
         // List for peer
         let rels = store
-            .list_credit_relationships_for("creditor_peer")
+            .list_credit_relationships_for(creditor.as_str())
             .await
             .unwrap();
         assert_eq!(rels.len(), 1);
+
+        // Transaction history
+        store
+            .record_credit_transaction(&rel_id, 30.0, 30.0, Some("rent"))
+            .await
+            .unwrap();
+        store
+            .record_credit_transaction(&rel_id, -10.0, 20.0, None)
+            .await
+            .unwrap();
+        let now = Utc::now();
+        let entries = store
+            .list_credit_transactions(&rel_id, now - chrono::Duration::minutes(1), now)
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].memo.as_deref(), Some("rent"));
+        assert_eq!(entries[1].balance, 20.0);
+
+        // The relationship carries its history, interest included
+        let interest = CreditEntry {
+            // Stored to the second
+            timestamp: Utc.timestamp_opt(now.timestamp(), 0).unwrap(),
+            kind: CreditEntryKind::Interest,
+            amount: 1.0,
+            balance: 21.0,
+            memo: None,
+        };
+        store.record_credit_entry(&rel_id, &interest).await.unwrap();
+        let retrieved = store
+            .get_credit_relationship(&rel_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(retrieved.history.len(), 3);
+        assert_eq!(retrieved.history[0].memo.as_deref(), Some("rent"));
+        assert_eq!(retrieved.history[2], interest);
+        assert!(store
+            .list_credit_transactions(
+                &rel_id,
+                now - chrono::Duration::hours(2),
+                now - chrono::Duration::hours(1)
+            )
+            .await
+            .unwrap()
+            .is_empty());
     }
 
     #[tokio::test]
@@ -951,6 +1860,63 @@ mod tests {
         assert!(store.get_sync_value("test_key").await.unwrap().is_none());
     }
 
+    #[tokio::test]
+    async fn test_economics_snapshot_roundtrip() {
+        let store = create_test_store().await;
+
+        // Nothing saved yet
+        assert!(store.load_economics_snapshot().await.unwrap().is_none());
+
+        let snapshot = EconomicsSnapshot {
+            version: ECONOMICS_SCHEMA_VERSION,
+            credit_lines: vec![("line1".to_string(), r#"{"id":"line1"}"#.to_string())],
+            proposals: vec![("prop1".to_string(), r#"{"id":"prop1"}"#.to_string())],
+            vouches: vec![],
+            contributions: vec!["{}".to_string(), "[]".to_string()],
+            reputations: vec![("alice".to_string(), 0.75)],
+        };
+        store.save_economics_snapshot(&snapshot).await.unwrap();
+
+        let loaded = store.load_economics_snapshot().await.unwrap().unwrap();
+        assert_eq!(loaded, snapshot);
+
+        // Saving again replaces rather than appends
+        store.save_economics_snapshot(&snapshot).await.unwrap();
+        let loaded = store.load_economics_snapshot().await.unwrap().unwrap();
+        assert_eq!(loaded.contributions.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_entropy_tax_rate_roundtrip() {
+        let store = create_test_store().await;
+        assert_eq!(store.load_entropy_tax_rate().await.unwrap(), None);
+
+        store.save_entropy_tax_rate(0.05).await.unwrap();
+        store.save_entropy_tax_rate(0.03).await.unwrap();
+        assert_eq!(store.load_entropy_tax_rate().await.unwrap(), Some(0.03));
+        store.save_entropy_tax_rate(0.0).await.unwrap();
+        assert_eq!(store.load_entropy_tax_rate().await.unwrap(), Some(0.0));
+
+        // The rate is no snapshot of its own
+        assert!(store.load_economics_snapshot().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_economics_snapshot_rejects_newer_version() {
+        let store = create_test_store().await;
+
+        let snapshot = EconomicsSnapshot {
+            version: ECONOMICS_SCHEMA_VERSION + 1,
+            ..Default::default()
+        };
+        store.save_economics_snapshot(&snapshot).await.unwrap();
+
+        assert!(matches!(
+            store.load_economics_snapshot().await,
+            Err(StateError::InvalidData(_))
+        ));
+    }
+
     #[tokio::test]
     async fn test_trusted_peers() {
         let store = create_test_store().await;
@@ -964,7 +1930,7 @@ mod tests {
             "2wMHpFAjZbL9GkXP8n3E4",
         ];
         for (i, test_key) in test_keys.iter().enumerate() {
-            let peer_id = PeerId(format!("peer_{}", i));
+            let peer_id = random_peer_id();
             let peer_info = PeerInfo {
                 id: peer_id.clone(),
                 public_key: test_key.to_string(), // base58 encoded
@@ -972,6 +1938,7 @@ mod tests {
                 first_seen: Utc::now(),
                 last_seen: Utc::now(),
                 name: None,
+                location: None,
             };
             let reputation = Reputation::new(0.2 + (i as f64 * 0.15)); // 0.2, 0.35, 0.5, 0.65, 0.8
 
@@ -983,6 +1950,6 @@ mod tests {
 
         // Get trusted peers (threshold 0.5)
         let trusted = store.list_trusted_peers(0.5).await.unwrap();
-        assert_eq!(trusted.len(), 3); // peer_2, peer_3, peer_4
+        assert_eq!(trusted.len(), 3); // peers 2, 3 and 4
     }
 }
@@ -3,6 +3,9 @@
 //! This module provides mechanisms for synchronizing state between peers
 //! using gossipsub messaging with last-write-wins semantics for simple
 //! fields and grow-only counters for reputation.
+//!
+//! Applied updates can be journaled to a file and replayed offline; see the
+//! [`journal`](crate::journal) module.
 
 use chrono::{DateTime, Utc};
 use mycelial_core::{
@@ -10,18 +13,20 @@ use mycelial_core::{
     peer::{PeerId, PeerInfo},
     reputation::Reputation,
 };
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
-use tracing::debug;
+use tracing::{debug, info, warn};
 
 use crate::cache::StateCache;
 use crate::error::{Result, StateError};
+use crate::journal::{self, Journal, JournalEntry, Resolution, SyncState};
 use crate::storage::SqliteStore;
 
 /// State update types that can be synced across the network
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum StateUpdate {
     /// Peer information update
     PeerUpdate {
@@ -54,8 +59,24 @@ pub enum StateUpdate {
     },
 }
 
+impl StateUpdate {
+    /// The piece of state this update competes for
+    ///
+    /// Updates with the same key are resolved against each other.
+    pub fn key(&self) -> String {
+        match self {
+            StateUpdate::PeerUpdate { peer_id, .. } => format!("peer:{}", peer_id),
+            StateUpdate::ReputationUpdate { peer_id, .. } => format!("reputation:{}", peer_id),
+            StateUpdate::CreditUpdate {
+                creditor, debtor, ..
+            } => format!("credit:{}:{}", creditor, debtor),
+            StateUpdate::KeyValueUpdate { key, .. } => format!("kv:{}", key),
+        }
+    }
+}
+
 /// Peer information that can be synced
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PeerInfoUpdate {
     /// Public key (base58 encoded string)
     pub public_key: String,
@@ -64,7 +85,7 @@ pub struct PeerInfoUpdate {
 }
 
 /// Vector clock for tracking causality
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct VectorClock {
     clocks: HashMap<String, u64>,
 }
@@ -132,6 +153,8 @@ pub struct StateSync {
     pending_updates: RwLock<Vec<StateUpdate>>,
     /// Cache reference for quick lookups
     cache: Arc<StateCache>,
+    /// Where resolved updates are recorded, if anywhere
+    journal: Mutex<Option<Journal>>,
 }
 
 impl StateSync {
@@ -143,7 +166,32 @@ impl StateSync {
             last_seen: RwLock::new(HashMap::new()),
             pending_updates: RwLock::new(Vec::new()),
             cache,
+            journal: Mutex::new(None),
+        }
+    }
+
+    /// Record every update passed to [`apply_update`](Self::apply_update) in `path`
+    ///
+    /// Entries are appended, so a journal can span restarts. Replaces any
+    /// journal enabled before. A failure to write an entry is logged and
+    /// does not fail the update.
+    pub fn enable_journal(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        *self.journal.lock() = Some(Journal::open(path)?);
+        info!("Journaling state sync updates to {}", path.display());
+        Ok(())
+    }
+
+    /// Rebuild the merged state recorded in the journal at `path`
+    ///
+    /// Entries are folded in order following their recorded resolutions, so
+    /// the same journal always yields the same state.
+    pub fn replay(path: impl AsRef<Path>) -> Result<SyncState> {
+        let mut state = SyncState::default();
+        for entry in journal::read_journal(path.as_ref())? {
+            state.apply(&entry);
         }
+        Ok(state)
     }
 
     /// Create a peer update
@@ -200,7 +248,37 @@ impl StateSync {
     }
 
     /// Apply an update received from the network
+    ///
+    /// Returns whether the update changed the state.
     pub async fn apply_update(&self, update: &StateUpdate, store: &SqliteStore) -> Result<bool> {
+        let resolution = self.resolve_update(update, store).await?;
+        self.journal_update(update, resolution);
+        Ok(resolution.is_applied())
+    }
+
+    /// Append a resolved update to the journal, if one is enabled
+    fn journal_update(&self, update: &StateUpdate, resolution: Resolution) {
+        let mut journal = self.journal.lock();
+        let Some(journal) = journal.as_mut() else {
+            return;
+        };
+        let entry = JournalEntry {
+            recorded_at: Utc::now(),
+            clock: self.get_clock(),
+            resolution,
+            update: update.clone(),
+        };
+        if let Err(e) = journal.append(&entry) {
+            warn!("Failed to journal {} update: {}", update.key(), e);
+        }
+    }
+
+    /// Decide an update against the current state, writing it if it wins
+    async fn resolve_update(
+        &self,
+        update: &StateUpdate,
+        store: &SqliteStore,
+    ) -> Result<Resolution> {
         match update {
             StateUpdate::PeerUpdate {
                 peer_id,
@@ -263,7 +341,8 @@ impl StateSync {
         info: &PeerInfoUpdate,
         timestamp: &DateTime<Utc>,
         store: &SqliteStore,
-    ) -> Result<bool> {
+    ) -> Result<Resolution> {
+        let id = PeerId::parse(peer_id).map_err(|e| StateError::InvalidData(e.to_string()))?;
         let update_key = format!("peer:{}", peer_id);
 
         // Check if we have a newer update
@@ -272,7 +351,7 @@ impl StateSync {
             if let Some(last_ts) = last_seen.get(&update_key) {
                 if last_ts >= timestamp {
                     debug!("Skipping stale peer update for {}", peer_id);
-                    return Ok(false);
+                    return Ok(Resolution::Stale);
                 }
             }
         }
@@ -290,12 +369,13 @@ impl StateSync {
             None => {
                 // Create new peer
                 PeerInfo {
-                    id: PeerId(peer_id.to_string()),
+                    id,
                     public_key: info.public_key.clone(),
                     addresses: info.addresses.clone(),
                     first_seen: Utc::now(),
                     last_seen: Utc::now(),
                     name: info.name.clone(),
+                    location: None,
                 }
             }
         };
@@ -310,7 +390,7 @@ impl StateSync {
         self.cache.peers.insert(peer_info, reputation);
 
         debug!("Applied peer update for {}", peer_id);
-        Ok(true)
+        Ok(Resolution::Applied)
     }
 
     /// Apply a reputation update using grow-only counters (max merge)
@@ -321,39 +401,39 @@ impl StateSync {
         failed: u64,
         _timestamp: &DateTime<Utc>,
         store: &SqliteStore,
-    ) -> Result<bool> {
+    ) -> Result<Resolution> {
         // Get existing reputation
         let (peer_info, mut reputation) = match store.get_peer(peer_id).await? {
             Some((info, rep)) => (info, rep),
             None => {
                 debug!("Skipping reputation update for unknown peer {}", peer_id);
-                return Ok(false);
+                return Ok(Resolution::UnknownPeer);
             }
         };
 
         // Grow-only counter merge: take the max
         let updated = successful > reputation.successful_interactions
             || failed > reputation.failed_interactions;
+        if !updated {
+            return Ok(Resolution::Unchanged);
+        }
 
-        if updated {
-            reputation.successful_interactions = reputation.successful_interactions.max(successful);
-            reputation.failed_interactions = reputation.failed_interactions.max(failed);
-
-            // Recalculate score
-            let total = reputation.successful_interactions + reputation.failed_interactions;
-            if total > 0 {
-                reputation.score = reputation.successful_interactions as f64 / total as f64;
-            }
+        reputation.successful_interactions = reputation.successful_interactions.max(successful);
+        reputation.failed_interactions = reputation.failed_interactions.max(failed);
 
-            store.update_peer_reputation(peer_id, &reputation).await?;
+        // Recalculate score
+        let total = reputation.successful_interactions + reputation.failed_interactions;
+        if total > 0 {
+            reputation.score = reputation.successful_interactions as f64 / total as f64;
+        }
 
-            // Update cache
-            self.cache.peers.insert(peer_info, reputation);
+        store.update_peer_reputation(peer_id, &reputation).await?;
 
-            debug!("Applied reputation update for {}", peer_id);
-        }
+        // Update cache
+        self.cache.peers.insert(peer_info, reputation);
 
-        Ok(updated)
+        debug!("Applied reputation update for {}", peer_id);
+        Ok(Resolution::Applied)
     }
 
     /// Apply a credit update using last-write-wins
@@ -367,7 +447,10 @@ impl StateSync {
         active: bool,
         timestamp: &DateTime<Utc>,
         store: &SqliteStore,
-    ) -> Result<bool> {
+    ) -> Result<Resolution> {
+        let parse =
+            |id: &str| PeerId::parse(id).map_err(|e| StateError::InvalidData(e.to_string()));
+        let (creditor_id, debtor_id) = (parse(creditor)?, parse(debtor)?);
         let update_key = format!("credit:{}:{}", creditor, debtor);
 
         // Check if we have a newer update
@@ -376,19 +459,21 @@ impl StateSync {
             if let Some(last_ts) = last_seen.get(&update_key) {
                 if last_ts >= timestamp {
                     debug!("Skipping stale credit update for {}:{}", creditor, debtor);
-                    return Ok(false);
+                    return Ok(Resolution::Stale);
                 }
             }
         }
 
         let relationship = CreditRelationship {
-            creditor: PeerId(creditor.to_string()),
-            debtor: PeerId(debtor.to_string()),
+            creditor: creditor_id,
+            debtor: debtor_id,
             credit_limit,
             balance,
             active,
             established: *timestamp,
             last_transaction: *timestamp,
+            interest_rate: 0.0,
+            history: Vec::new(),
         };
 
         store.upsert_credit_relationship(&relationship).await?;
@@ -400,7 +485,7 @@ impl StateSync {
         self.cache.credits.insert(relationship);
 
         debug!("Applied credit update for {}:{}", creditor, debtor);
-        Ok(true)
+        Ok(Resolution::Applied)
     }
 
     /// Apply a key-value update using version numbers
@@ -411,19 +496,19 @@ impl StateSync {
         version: u64,
         _timestamp: &DateTime<Utc>,
         store: &SqliteStore,
-    ) -> Result<bool> {
+    ) -> Result<Resolution> {
         // Get existing version
         if let Some((_, existing_version)) = store.get_sync_value(key).await? {
             if existing_version as u64 >= version {
                 debug!("Skipping stale key-value update for {}", key);
-                return Ok(false);
+                return Ok(Resolution::Stale);
             }
         }
 
         store.set_sync_value(key, value).await?;
 
         debug!("Applied key-value update for {}", key);
-        Ok(true)
+        Ok(Resolution::Applied)
     }
 
     /// Queue an update to be sent
@@ -461,6 +546,7 @@ impl StateSync {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use mycelial_core::Keypair;
 
     #[test]
     fn test_vector_clock() {
@@ -511,18 +597,19 @@ mod tests {
 
         // Create peer update
         let peer_info = PeerInfo {
-            id: PeerId("test_peer".to_string()),
+            id: PeerId::from_public_key(&Keypair::generate().public_key()),
             public_key: "3mJr7AoUXx2Wqd5s8N4Df".to_string(), // base58 encoded
             addresses: vec![],
             first_seen: Utc::now(),
             last_seen: Utc::now(),
             name: None,
+            location: None,
         };
 
         let update = sync.create_peer_update(&peer_info);
         match update {
             StateUpdate::PeerUpdate { peer_id, .. } => {
-                assert_eq!(peer_id, "test_peer");
+                assert_eq!(peer_id, peer_info.id.as_str());
             }
             _ => panic!("Wrong update type"),
         }
@@ -530,4 +617,67 @@ mod tests {
         // Check vector clock was incremented
         assert_eq!(sync.get_clock().get("local_peer"), 1);
     }
+
+    #[tokio::test]
+    async fn test_journal_replays_resolved_updates() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sync.journal");
+        let store = SqliteStore::new(":memory:").await.unwrap();
+        let sync = StateSync::new("local_peer".to_string(), Arc::new(StateCache::new()));
+        sync.enable_journal(&path).unwrap();
+
+        let now = Utc::now();
+        let remote = PeerId::from_public_key(&Keypair::generate().public_key()).to_string();
+        let peer_update = |name: &str, timestamp| StateUpdate::PeerUpdate {
+            peer_id: remote.clone(),
+            info: PeerInfoUpdate {
+                public_key: "3mJr7AoUXx2Wqd5s8N4Df".to_string(),
+                addresses: vec![],
+                name: Some(name.to_string()),
+            },
+            timestamp,
+        };
+        let reputation_update = |peer_id: &str, successful, failed| StateUpdate::ReputationUpdate {
+            peer_id: peer_id.to_string(),
+            successful_interactions: successful,
+            failed_interactions: failed,
+            timestamp: now,
+        };
+
+        let updates = [
+            reputation_update(&remote, 1, 0),
+            peer_update("new", now),
+            peer_update("old", now - chrono::Duration::seconds(10)),
+            reputation_update(&remote, 5, 1),
+            reputation_update(&remote, 3, 2),
+            reputation_update(&remote, 3, 2),
+        ];
+        for update in &updates {
+            sync.apply_update(update, &store).await.unwrap();
+        }
+
+        let entries = journal::read_journal(&path).unwrap();
+        let resolutions: Vec<_> = entries.iter().map(|e| e.resolution).collect();
+        assert_eq!(
+            resolutions,
+            vec![
+                Resolution::UnknownPeer,
+                Resolution::Applied,
+                Resolution::Stale,
+                Resolution::Applied,
+                Resolution::Applied,
+                Resolution::Unchanged,
+            ]
+        );
+
+        let state = StateSync::replay(&path).unwrap();
+        assert_eq!(state, StateSync::replay(&path).unwrap());
+        assert_eq!((state.applied, state.skipped), (3, 3));
+        assert_eq!(state.updates[&format!("peer:{}", remote)], updates[1]);
+        // Grow-only counters merge across the applied updates
+        assert_eq!(
+            state.updates[&format!("reputation:{}", remote)],
+            reputation_update(&remote, 5, 2)
+        );
+    }
 }
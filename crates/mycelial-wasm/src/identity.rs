@@ -0,0 +1,198 @@
+//! Browser identity - key generation and signing
+//!
+//! Wraps [`mycelial_core::identity`] so a web app can hold an Ed25519
+//! identity and sign messages locally. The secret key stays inside
+//! [`JsKeypair`]; the only way to get it out is
+//! [`export_encrypted`](JsKeypair::export_encrypted), which seals it with
+//! a passphrase.
+//!
+//! ## Example
+//!
+//! ```js
+//! const keypair = generate_keypair();
+//! const signature = keypair.sign(message);
+//! verify(keypair.publicKey, message, signature); // true
+//!
+//! const blob = keypair.exportEncrypted("correct horse");
+//! const restored = JsKeypair.importEncrypted(blob, "correct horse");
+//! ```
+//!
+//! ## Export format
+//!
+//! `version (1) | PBKDF2 rounds (4, big endian) | salt (16) | nonce (12) |
+//! ChaCha20-Poly1305 ciphertext of the 32-byte secret key (48)`
+
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use mycelial_core::identity::{Did, Keypair, PublicKey, Signature};
+use sha2::Sha256;
+use wasm_bindgen::prelude::*;
+
+/// Current export format version
+const EXPORT_VERSION: u8 = 1;
+
+/// PBKDF2-HMAC-SHA256 rounds for new exports
+const KDF_ROUNDS: u32 = 600_000;
+
+/// Most PBKDF2 rounds accepted from an imported blob
+const MAX_KDF_ROUNDS: u32 = 10 * KDF_ROUNDS;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const HEADER_LEN: usize = 1 + 4 + SALT_LEN + NONCE_LEN;
+
+/// Ed25519 identity held on the WASM side
+#[wasm_bindgen]
+pub struct JsKeypair {
+    keypair: Keypair,
+}
+
+/// Generate a new random identity
+#[wasm_bindgen]
+pub fn generate_keypair() -> JsKeypair {
+    JsKeypair {
+        keypair: Keypair::generate(),
+    }
+}
+
+/// Check an Ed25519 signature against a 32-byte public key
+#[wasm_bindgen]
+pub fn verify(public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    let Ok(public_key) = PublicKey::from_bytes(public_key) else {
+        return false;
+    };
+    let Ok(signature) = Signature::from_bytes(signature) else {
+        return false;
+    };
+    public_key.verify(message, &signature)
+}
+
+#[wasm_bindgen]
+impl JsKeypair {
+    /// The did:key identifier for this keypair
+    pub fn did(&self) -> String {
+        Did::from_public_key(&self.keypair.public_key()).to_string()
+    }
+
+    /// The 32-byte public key
+    #[wasm_bindgen(getter, js_name = publicKey)]
+    pub fn public_key(&self) -> Vec<u8> {
+        self.keypair.public_key().as_bytes().to_vec()
+    }
+
+    /// Sign a message, returning the 64-byte signature
+    pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        self.keypair.sign(message).to_bytes().to_vec()
+    }
+
+    /// Export the secret key encrypted under `passphrase`
+    #[wasm_bindgen(js_name = exportEncrypted)]
+    pub fn export_encrypted(&self, passphrase: &str) -> Result<Vec<u8>, JsValue> {
+        let mut secret = self.keypair.to_bytes();
+        let sealed = seal(&secret, passphrase, KDF_ROUNDS);
+        secret.fill(0);
+        sealed.map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Restore a keypair from [`export_encrypted`](Self::export_encrypted)
+    #[wasm_bindgen(js_name = importEncrypted)]
+    pub fn import_encrypted(blob: &[u8], passphrase: &str) -> Result<JsKeypair, JsValue> {
+        let mut secret = open(blob, passphrase).map_err(|e| JsValue::from_str(&e))?;
+        let keypair = Keypair::from_bytes(&secret);
+        secret.fill(0);
+        Ok(Self {
+            keypair: keypair.map_err(|e| JsValue::from_str(&e))?,
+        })
+    }
+}
+
+/// Encrypt a secret key with a key derived from `passphrase`
+fn seal(secret: &[u8; 32], passphrase: &str, rounds: u32) -> Result<Vec<u8>, String> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    getrandom::getrandom(&mut salt).map_err(|e| format!("No randomness: {}", e))?;
+    getrandom::getrandom(&mut nonce).map_err(|e| format!("No randomness: {}", e))?;
+
+    let ciphertext = cipher(passphrase, &salt, rounds)
+        .encrypt(Nonce::from_slice(&nonce), secret.as_slice())
+        .map_err(|_| "Encryption failed".to_string())?;
+
+    let mut blob = Vec::with_capacity(HEADER_LEN + ciphertext.len());
+    blob.push(EXPORT_VERSION);
+    blob.extend_from_slice(&rounds.to_be_bytes());
+    blob.extend_from_slice(&salt);
+    blob.extend_from_slice(&nonce);
+    blob.extend_from_slice(&ciphertext);
+    Ok(blob)
+}
+
+/// Decrypt a blob written by [`seal`]
+fn open(blob: &[u8], passphrase: &str) -> Result<[u8; 32], String> {
+    if blob.len() <= HEADER_LEN {
+        return Err("Exported key is truncated".into());
+    }
+    if blob[0] != EXPORT_VERSION {
+        return Err(format!("Unsupported export version {}", blob[0]));
+    }
+
+    let rounds = u32::from_be_bytes(blob[1..5].try_into().expect("4-byte slice"));
+    if rounds > MAX_KDF_ROUNDS {
+        return Err(format!(
+            "Exported key asks for too many KDF rounds ({})",
+            rounds
+        ));
+    }
+    let salt = &blob[5..5 + SALT_LEN];
+    let nonce = &blob[5 + SALT_LEN..HEADER_LEN];
+
+    let mut plaintext = cipher(passphrase, salt, rounds)
+        .decrypt(Nonce::from_slice(nonce), &blob[HEADER_LEN..])
+        .map_err(|_| "Wrong passphrase or corrupted key".to_string())?;
+    let secret = <[u8; 32]>::try_from(plaintext.as_slice())
+        .map_err(|_| "Exported key has the wrong length".to_string());
+    plaintext.fill(0);
+    secret
+}
+
+fn cipher(passphrase: &str, salt: &[u8], rounds: u32) -> ChaCha20Poly1305 {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, rounds, &mut key);
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+    key.fill(0);
+    cipher
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let keypair = generate_keypair();
+        let signature = keypair.sign(b"hello");
+
+        assert!(verify(&keypair.public_key(), b"hello", &signature));
+        assert!(!verify(&keypair.public_key(), b"hellp", &signature));
+        assert!(!verify(&[0u8; 3], b"hello", &signature));
+        assert!(keypair.did().starts_with("did:key:z"));
+    }
+
+    #[test]
+    fn test_sealed_key_round_trip() {
+        let secret = [7u8; 32];
+        let blob = seal(&secret, "passphrase", 1_000).unwrap();
+
+        assert_eq!(open(&blob, "passphrase").unwrap(), secret);
+        assert!(open(&blob, "wrong").is_err());
+        assert!(open(&blob[..HEADER_LEN], "passphrase").is_err());
+
+        // A blob can't make the import spin for hours
+        let mut crafted = blob.clone();
+        crafted[1..5].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(open(&crafted, "passphrase")
+            .unwrap_err()
+            .contains("KDF rounds"));
+    }
+}
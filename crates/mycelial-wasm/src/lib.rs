@@ -1,6 +1,17 @@
 //! Mycelial WASM - Browser bindings for the mycelial network
 //!
 //! This crate provides WebAssembly bindings for browser-based clients.
+//! [`BrowserPeer`] joins the network over WebSocket from the browser, and
+//! [`JsKeypair`] gives the page its own identity for signing, and
+//! [`IdbStore`] persists peers and messages in IndexedDB.
+
+pub mod identity;
+pub mod peer;
+pub mod store;
+
+pub use identity::{generate_keypair, verify, JsKeypair};
+pub use peer::BrowserPeer;
+pub use store::IdbStore;
 
 use wasm_bindgen::prelude::*;
 
@@ -16,30 +27,3 @@ pub fn init() {
 pub fn greet(name: &str) -> String {
     format!("Hello, {}! Welcome to the Mycelial Network.", name)
 }
-
-/// Peer connection state for browser clients
-#[wasm_bindgen]
-pub struct BrowserPeer {
-    // TODO: Add WebSocket connection state
-}
-
-#[wasm_bindgen]
-impl BrowserPeer {
-    /// Create a new browser peer
-    #[wasm_bindgen(constructor)]
-    pub fn new() -> Self {
-        Self {}
-    }
-
-    /// Connect to a relay server
-    pub async fn connect(&mut self, _relay_url: &str) -> Result<(), JsValue> {
-        // TODO: Implement WebSocket connection
-        Ok(())
-    }
-}
-
-impl Default for BrowserPeer {
-    fn default() -> Self {
-        Self::new()
-    }
-}
@@ -0,0 +1,370 @@
+//! Browser peer - libp2p over WebSocket
+//!
+//! [`BrowserPeer`] dials a relay's `/ws` (or `/wss`) multiaddr through the
+//! browser's WebSocket API, secures the connection with Noise, multiplexes
+//! it with Yamux and joins gossipsub. The swarm runs on the browser event
+//! loop; JS drives it through commands and hears back through a callback.
+//!
+//! ## Example
+//!
+//! ```js
+//! const peer = new BrowserPeer();
+//! peer.onEvent((event) => console.log(event.type, event));
+//! await peer.connect("/dns4/relay.example.org/tcp/443/wss/p2p/12D3KooW...");
+//! await peer.subscribe("/mycelial/1.0.0/chat");
+//! await peer.publish("/mycelial/1.0.0/chat", new TextEncoder().encode("hello"));
+//! ```
+//!
+//! Events are plain objects with a `type` field:
+//!
+//! | `type`         | Fields                             |
+//! |----------------|------------------------------------|
+//! | `connected`    | `peerId`                           |
+//! | `disconnected` | `peerId`                           |
+//! | `subscribed`   | `peerId`, `topic`                  |
+//! | `message`      | `topic`, `source`, `data` (bytes)  |
+//! | `error`        | `message`                          |
+
+use futures::channel::{mpsc, oneshot};
+use futures::{select, StreamExt};
+use libp2p::{
+    core::upgrade,
+    gossipsub::{self, IdentTopic, MessageAuthenticity, MessageId, ValidationMode},
+    identity::Keypair,
+    noise,
+    swarm::SwarmEvent,
+    websocket_websys, yamux, Multiaddr, Swarm, SwarmBuilder, Transport,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+use wasm_bindgen::prelude::*;
+
+/// Largest gossipsub message accepted, matching native nodes
+const MAX_MESSAGE_SIZE: usize = 1024 * 1024;
+
+/// JS callback receiving peer events
+type EventCallback = Rc<RefCell<Option<js_sys::Function>>>;
+
+/// Requests from the JS-facing handle to the swarm task
+enum Command {
+    Subscribe {
+        topic: String,
+        reply: oneshot::Sender<Result<(), String>>,
+    },
+    Unsubscribe {
+        topic: String,
+        reply: oneshot::Sender<Result<(), String>>,
+    },
+    Publish {
+        topic: String,
+        data: Vec<u8>,
+        reply: oneshot::Sender<Result<(), String>>,
+    },
+}
+
+/// Peer connection state for browser clients
+#[wasm_bindgen]
+pub struct BrowserPeer {
+    /// Identity used for Noise and message signing
+    keypair: Keypair,
+    /// Channel to the running swarm, set while connected
+    commands: RefCell<Option<mpsc::UnboundedSender<Command>>>,
+    /// Event callback registered from JS
+    on_event: EventCallback,
+}
+
+#[wasm_bindgen]
+impl BrowserPeer {
+    /// Create a new browser peer with a fresh identity
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self {
+            keypair: Keypair::generate_ed25519(),
+            commands: RefCell::new(None),
+            on_event: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    /// This peer's libp2p peer ID
+    #[wasm_bindgen(getter, js_name = peerId)]
+    pub fn peer_id(&self) -> String {
+        self.keypair.public().to_peer_id().to_string()
+    }
+
+    /// Whether the swarm is running
+    #[wasm_bindgen(getter, js_name = isConnected)]
+    pub fn is_connected(&self) -> bool {
+        self.commands
+            .borrow()
+            .as_ref()
+            .is_some_and(|commands| !commands.is_closed())
+    }
+
+    /// Register the callback that receives peer events
+    #[wasm_bindgen(js_name = onEvent)]
+    pub fn on_event(&self, callback: js_sys::Function) {
+        *self.on_event.borrow_mut() = Some(callback);
+    }
+
+    /// Connect to a relay server
+    ///
+    /// `relay_addr` is the relay's WebSocket multiaddr, e.g.
+    /// `/dns4/relay.example.org/tcp/443/wss/p2p/<peer id>`. Resolves once
+    /// the connection is established.
+    pub async fn connect(&self, relay_addr: String) -> Result<(), JsValue> {
+        if self.is_connected() {
+            return Err(JsValue::from_str("Already connected"));
+        }
+
+        let addr: Multiaddr = relay_addr
+            .parse()
+            .map_err(|e| JsValue::from_str(&format!("Invalid relay address: {}", e)))?;
+
+        let mut swarm = build_swarm(self.keypair.clone())?;
+        swarm
+            .dial(addr)
+            .map_err(|e| JsValue::from_str(&format!("Failed to dial relay: {}", e)))?;
+
+        let (command_tx, command_rx) = mpsc::unbounded();
+        let (ready_tx, ready_rx) = oneshot::channel();
+        wasm_bindgen_futures::spawn_local(run_swarm(
+            swarm,
+            command_rx,
+            self.on_event.clone(),
+            ready_tx,
+        ));
+
+        match ready_rx.await {
+            Ok(Ok(())) => {
+                *self.commands.borrow_mut() = Some(command_tx);
+                Ok(())
+            }
+            Ok(Err(e)) => Err(JsValue::from_str(&format!("Connection failed: {}", e))),
+            Err(_) => Err(JsValue::from_str("Connection failed")),
+        }
+    }
+
+    /// Close all connections and stop the swarm
+    pub fn disconnect(&self) {
+        // Dropping the sender ends the swarm task
+        self.commands.borrow_mut().take();
+    }
+
+    /// Subscribe to a gossipsub topic
+    pub async fn subscribe(&self, topic: String) -> Result<(), JsValue> {
+        self.request(|reply| Command::Subscribe { topic, reply })
+            .await
+    }
+
+    /// Unsubscribe from a gossipsub topic
+    pub async fn unsubscribe(&self, topic: String) -> Result<(), JsValue> {
+        self.request(|reply| Command::Unsubscribe { topic, reply })
+            .await
+    }
+
+    /// Publish bytes to a gossipsub topic
+    pub async fn publish(&self, topic: String, data: Vec<u8>) -> Result<(), JsValue> {
+        self.request(|reply| Command::Publish { topic, data, reply })
+            .await
+    }
+}
+
+impl BrowserPeer {
+    /// Send a command to the swarm task and wait for its answer
+    async fn request(
+        &self,
+        command: impl FnOnce(oneshot::Sender<Result<(), String>>) -> Command,
+    ) -> Result<(), JsValue> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        let commands = self
+            .commands
+            .borrow()
+            .clone()
+            .ok_or_else(|| JsValue::from_str("Not connected"))?;
+        commands
+            .unbounded_send(command(reply_tx))
+            .map_err(|_| JsValue::from_str("Not connected"))?;
+
+        reply_rx
+            .await
+            .map_err(|_| JsValue::from_str("Not connected"))?
+            .map_err(|e| JsValue::from_str(&e))
+    }
+}
+
+impl Default for BrowserPeer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Build a WebSocket + Noise + Yamux swarm running gossipsub
+fn build_swarm(keypair: Keypair) -> Result<Swarm<gossipsub::Behaviour>, JsValue> {
+    Ok(SwarmBuilder::with_existing_identity(keypair)
+        .with_wasm_bindgen()
+        .with_other_transport(|key| {
+            Ok::<_, noise::Error>(
+                websocket_websys::Transport::default()
+                    .upgrade(upgrade::Version::V1)
+                    .authenticate(noise::Config::new(key)?)
+                    .multiplex(yamux::Config::default())
+                    .boxed(),
+            )
+        })
+        .map_err(|e| JsValue::from_str(&format!("Transport error: {}", e)))?
+        .with_behaviour(create_gossipsub)
+        .map_err(|e| JsValue::from_str(&format!("Gossipsub error: {}", e)))?
+        .with_swarm_config(|config| config.with_idle_connection_timeout(Duration::from_secs(60)))
+        .build())
+}
+
+/// Gossipsub configured like native nodes so messages interoperate
+fn create_gossipsub(
+    keypair: &Keypair,
+) -> Result<gossipsub::Behaviour, Box<dyn std::error::Error + Send + Sync>> {
+    // Same message IDs as native nodes, or they would treat our copy of a
+    // message they already relayed as new
+    let message_id_fn = |message: &gossipsub::Message| {
+        MessageId::from(mycelial_core::message::payload_id(&message.data))
+    };
+
+    let config = gossipsub::ConfigBuilder::default()
+        .heartbeat_interval(Duration::from_secs(1))
+        .validation_mode(ValidationMode::Strict)
+        .message_id_fn(message_id_fn)
+        .max_transmit_size(MAX_MESSAGE_SIZE)
+        // A browser usually has a single relay connection
+        .mesh_outbound_min(0)
+        .mesh_n(2)
+        .mesh_n_low(1)
+        .mesh_n_high(4)
+        .build()?;
+
+    Ok(gossipsub::Behaviour::new(
+        MessageAuthenticity::Signed(keypair.clone()),
+        config,
+    )?)
+}
+
+/// Drive the swarm until the `BrowserPeer` disconnects or is dropped
+///
+/// `ready` is answered with the outcome of the first dial.
+async fn run_swarm(
+    mut swarm: Swarm<gossipsub::Behaviour>,
+    mut commands: mpsc::UnboundedReceiver<Command>,
+    on_event: EventCallback,
+    ready: oneshot::Sender<Result<(), String>>,
+) {
+    let mut ready = Some(ready);
+
+    loop {
+        select! {
+            command = commands.next() => match command {
+                Some(command) => handle_command(&mut swarm, command),
+                None => break,
+            },
+            event = swarm.select_next_some() => match event {
+                SwarmEvent::ConnectionEstablished { peer_id, .. } => {
+                    if let Some(ready) = ready.take() {
+                        let _ = ready.send(Ok(()));
+                    }
+                    emit(
+                        &on_event,
+                        "connected",
+                        &[("peerId", peer_id.to_string().into())],
+                    );
+                }
+                SwarmEvent::OutgoingConnectionError { error, .. } => {
+                    if let Some(ready) = ready.take() {
+                        let _ = ready.send(Err(error.to_string()));
+                        break;
+                    }
+                    emit(&on_event, "error", &[("message", error.to_string().into())]);
+                }
+                SwarmEvent::ConnectionClosed {
+                    peer_id,
+                    num_established: 0,
+                    ..
+                } => {
+                    emit(
+                        &on_event,
+                        "disconnected",
+                        &[("peerId", peer_id.to_string().into())],
+                    );
+                }
+                SwarmEvent::Behaviour(gossipsub::Event::Subscribed { peer_id, topic }) => {
+                    emit(
+                        &on_event,
+                        "subscribed",
+                        &[
+                            ("peerId", peer_id.to_string().into()),
+                            ("topic", topic.to_string().into()),
+                        ],
+                    );
+                }
+                SwarmEvent::Behaviour(gossipsub::Event::Message { message, .. }) => {
+                    let source = message
+                        .source
+                        .map_or(JsValue::NULL, |peer| peer.to_string().into());
+                    emit(
+                        &on_event,
+                        "message",
+                        &[
+                            ("topic", message.topic.to_string().into()),
+                            ("source", source),
+                            ("data", js_sys::Uint8Array::from(&message.data[..]).into()),
+                        ],
+                    );
+                }
+                _ => {}
+            },
+        }
+    }
+}
+
+fn handle_command(swarm: &mut Swarm<gossipsub::Behaviour>, command: Command) {
+    match command {
+        Command::Subscribe { topic, reply } => {
+            let result = swarm
+                .behaviour_mut()
+                .subscribe(&IdentTopic::new(topic))
+                .map(|_| ())
+                .map_err(|e| format!("Failed to subscribe: {:?}", e));
+            let _ = reply.send(result);
+        }
+        Command::Unsubscribe { topic, reply } => {
+            let result = swarm
+                .behaviour_mut()
+                .unsubscribe(&IdentTopic::new(topic))
+                .map(|_| ())
+                .map_err(|e| format!("Failed to unsubscribe: {:?}", e));
+            let _ = reply.send(result);
+        }
+        Command::Publish { topic, data, reply } => {
+            let result = swarm
+                .behaviour_mut()
+                .publish(IdentTopic::new(topic), data)
+                .map(|_| ())
+                .map_err(|e| format!("Failed to publish: {:?}", e));
+            let _ = reply.send(result);
+        }
+    }
+}
+
+/// Call the JS event callback with `{ type: kind, ...fields }`
+fn emit(on_event: &EventCallback, kind: &str, fields: &[(&str, JsValue)]) {
+    let Some(callback) = on_event.borrow().clone() else {
+        return;
+    };
+
+    let event = js_sys::Object::new();
+    let _ = js_sys::Reflect::set(&event, &"type".into(), &kind.into());
+    for (key, value) in fields {
+        let _ = js_sys::Reflect::set(&event, &(*key).into(), value);
+    }
+
+    if let Err(e) = callback.call1(&JsValue::NULL, &event) {
+        web_sys::console::error_2(&"BrowserPeer event callback failed:".into(), &e);
+    }
+}
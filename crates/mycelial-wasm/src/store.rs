@@ -0,0 +1,366 @@
+//! IndexedDB-backed state store for browser peers
+//!
+//! [`IdbStore`] keeps peers and messages across page reloads, with the same
+//! peer operations as `SqliteStore` on native nodes. IndexedDB quotas are
+//! small, so each object store is capped: once a write takes it over the
+//! limit, the least recently seen peers or the oldest messages are evicted.
+//!
+//! IndexedDB handles are not `Send`, so `IdbStore` offers the
+//! [`StateStore`](mycelial_core::StateStore) operations as inherent async
+//! methods rather than implementing the trait.
+//!
+//! ## Example
+//!
+//! ```js
+//! const store = await IdbStore.open("mycelial", 1000, 5000);
+//! await store.storePeer(peerInfo);
+//! const peers = await store.listPeers();
+//! ```
+
+use idb::{
+    Database, DatabaseEvent, Factory, IndexParams, KeyPath, ObjectStore, ObjectStoreParams, Query,
+    TransactionMode,
+};
+use mycelial_core::{Message, MycelialError, PeerId, PeerInfo, Reputation, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+/// IndexedDB schema version
+const DB_VERSION: u32 = 1;
+
+const PEERS: &str = "peers";
+const MESSAGES: &str = "messages";
+
+/// Index on each record's `time` field, used for ordering and eviction
+const BY_TIME: &str = "by_time";
+
+/// Default cap on stored peers
+pub const DEFAULT_MAX_PEERS: u32 = 1_000;
+
+/// Default cap on stored messages
+pub const DEFAULT_MAX_MESSAGES: u32 = 5_000;
+
+/// A peer as stored in IndexedDB
+#[derive(Serialize, Deserialize)]
+struct PeerRecord {
+    /// Primary key
+    id: String,
+    /// Last seen (ms since the Unix epoch), the eviction order
+    time: f64,
+    info: PeerInfo,
+    reputation: Reputation,
+}
+
+/// A message as stored in IndexedDB
+#[derive(Serialize, Deserialize)]
+struct MessageRecord {
+    /// Primary key
+    id: String,
+    /// Message timestamp (ms since the Unix epoch), the eviction order
+    time: f64,
+    message: Message,
+}
+
+/// Browser state store over IndexedDB
+#[wasm_bindgen]
+pub struct IdbStore {
+    db: Database,
+    max_peers: u32,
+    max_messages: u32,
+}
+
+impl IdbStore {
+    /// Open (or create) the database `name` with the given size caps
+    pub async fn open_with_limits(name: &str, max_peers: u32, max_messages: u32) -> Result<Self> {
+        let factory = Factory::new().map_err(storage_error)?;
+        let mut request = factory
+            .open(name, Some(DB_VERSION))
+            .map_err(storage_error)?;
+
+        request.on_upgrade_needed(|event| {
+            let Ok(db) = event.database() else {
+                return;
+            };
+            for name in [PEERS, MESSAGES] {
+                let mut params = ObjectStoreParams::new();
+                params.key_path(Some(KeyPath::new_single("id")));
+                if let Ok(store) = db.create_object_store(name, params) {
+                    let mut index = IndexParams::new();
+                    index.unique(false);
+                    let _ = store.create_index(BY_TIME, KeyPath::new_single("time"), Some(index));
+                }
+            }
+        });
+
+        Ok(Self {
+            db: request.await.map_err(storage_error)?,
+            max_peers: max_peers.max(1),
+            max_messages: max_messages.max(1),
+        })
+    }
+
+    // ========== Peer Operations ==========
+
+    /// Store or update a peer, keeping its reputation if already known
+    pub async fn store_peer(&self, info: &PeerInfo) -> Result<()> {
+        let reputation = self
+            .get_peer_with_reputation(&info.id)
+            .await?
+            .map(|(_, reputation)| reputation)
+            .unwrap_or_default();
+        self.upsert_peer(info, &reputation).await
+    }
+
+    /// Store or update a peer with its reputation
+    pub async fn upsert_peer(&self, info: &PeerInfo, reputation: &Reputation) -> Result<()> {
+        let record = PeerRecord {
+            id: info.id.as_str().to_string(),
+            time: info.last_seen.timestamp_millis() as f64,
+            info: info.clone(),
+            reputation: reputation.clone(),
+        };
+        self.put(PEERS, &record, self.max_peers).await
+    }
+
+    /// Get a peer by ID
+    pub async fn get_peer(&self, id: &PeerId) -> Result<Option<PeerInfo>> {
+        Ok(self
+            .get_peer_with_reputation(id)
+            .await?
+            .map(|(info, _)| info))
+    }
+
+    /// Get a peer and its reputation by ID
+    pub async fn get_peer_with_reputation(
+        &self,
+        id: &PeerId,
+    ) -> Result<Option<(PeerInfo, Reputation)>> {
+        Ok(self
+            .get::<PeerRecord>(PEERS, id.as_str())
+            .await?
+            .map(|record| (record.info, record.reputation)))
+    }
+
+    /// List all peers, most recently seen first
+    pub async fn list_peers(&self) -> Result<Vec<PeerInfo>> {
+        Ok(self
+            .newest_first::<PeerRecord>(PEERS, None)
+            .await?
+            .into_iter()
+            .map(|record| record.info)
+            .collect())
+    }
+
+    /// Update peer reputation
+    ///
+    /// Unknown peers are ignored, as with `SqliteStore`.
+    pub async fn update_reputation(&self, id: &PeerId, reputation: &Reputation) -> Result<()> {
+        match self.get::<PeerRecord>(PEERS, id.as_str()).await? {
+            Some(record) => self.upsert_peer(&record.info, reputation).await,
+            None => Ok(()),
+        }
+    }
+
+    // ========== Message Operations ==========
+
+    /// Store a message
+    pub async fn store_message(&self, message: &Message) -> Result<()> {
+        let record = MessageRecord {
+            id: message.id.to_string(),
+            time: message.timestamp.timestamp_millis() as f64,
+            message: message.clone(),
+        };
+        self.put(MESSAGES, &record, self.max_messages).await
+    }
+
+    /// List the most recent messages, newest first
+    pub async fn list_recent_messages(&self, limit: u32) -> Result<Vec<Message>> {
+        Ok(self
+            .newest_first::<MessageRecord>(MESSAGES, Some(limit))
+            .await?
+            .into_iter()
+            .map(|record| record.message)
+            .collect())
+    }
+
+    // ========== IndexedDB helpers ==========
+
+    /// Write a record, then evict the oldest records beyond `max`
+    async fn put<T: Serialize>(&self, store_name: &str, record: &T, max: u32) -> Result<()> {
+        let value = serde_wasm_bindgen::to_value(record).map_err(storage_error)?;
+
+        let tx = self
+            .db
+            .transaction(&[store_name], TransactionMode::ReadWrite)
+            .map_err(storage_error)?;
+        let store = tx.object_store(store_name).map_err(storage_error)?;
+        store
+            .put(&value, None)
+            .map_err(storage_error)?
+            .await
+            .map_err(storage_error)?;
+        evict_oldest(&store, max).await?;
+
+        tx.commit()
+            .map_err(storage_error)?
+            .await
+            .map_err(storage_error)?;
+        Ok(())
+    }
+
+    async fn get<T: DeserializeOwned>(&self, store_name: &str, key: &str) -> Result<Option<T>> {
+        let tx = self
+            .db
+            .transaction(&[store_name], TransactionMode::ReadOnly)
+            .map_err(storage_error)?;
+        let store = tx.object_store(store_name).map_err(storage_error)?;
+        let value = store
+            .get(Query::Key(JsValue::from_str(key)))
+            .map_err(storage_error)?
+            .await
+            .map_err(storage_error)?;
+
+        value
+            .map(|value| serde_wasm_bindgen::from_value(value).map_err(storage_error))
+            .transpose()
+    }
+
+    /// Records in descending `time` order, up to `limit`
+    async fn newest_first<T: DeserializeOwned>(
+        &self,
+        store_name: &str,
+        limit: Option<u32>,
+    ) -> Result<Vec<T>> {
+        let tx = self
+            .db
+            .transaction(&[store_name], TransactionMode::ReadOnly)
+            .map_err(storage_error)?;
+        let store = tx.object_store(store_name).map_err(storage_error)?;
+        let values = store
+            .index(BY_TIME)
+            .map_err(storage_error)?
+            .get_all(None, None)
+            .map_err(storage_error)?
+            .await
+            .map_err(storage_error)?;
+
+        values
+            .into_iter()
+            .rev()
+            .take(limit.map_or(usize::MAX, |limit| limit as usize))
+            .map(|value| serde_wasm_bindgen::from_value(value).map_err(storage_error))
+            .collect()
+    }
+}
+
+#[wasm_bindgen]
+impl IdbStore {
+    /// Open (or create) the database `name`
+    ///
+    /// `maxPeers` and `maxMessages` default to [`DEFAULT_MAX_PEERS`] and
+    /// [`DEFAULT_MAX_MESSAGES`].
+    #[wasm_bindgen(js_name = open)]
+    pub async fn js_open(
+        name: String,
+        max_peers: Option<u32>,
+        max_messages: Option<u32>,
+    ) -> std::result::Result<IdbStore, JsValue> {
+        Self::open_with_limits(
+            &name,
+            max_peers.unwrap_or(DEFAULT_MAX_PEERS),
+            max_messages.unwrap_or(DEFAULT_MAX_MESSAGES),
+        )
+        .await
+        .map_err(js_error)
+    }
+
+    /// Store or update a peer (a `PeerInfo` object)
+    #[wasm_bindgen(js_name = storePeer)]
+    pub async fn js_store_peer(&self, info: JsValue) -> std::result::Result<(), JsValue> {
+        let info: PeerInfo = serde_wasm_bindgen::from_value(info)?;
+        self.store_peer(&info).await.map_err(js_error)
+    }
+
+    /// Get a peer by ID, or `undefined`
+    #[wasm_bindgen(js_name = getPeer)]
+    pub async fn js_get_peer(&self, id: String) -> std::result::Result<JsValue, JsValue> {
+        let id = PeerId::parse(&id).map_err(js_error)?;
+        let peer = self.get_peer(&id).await.map_err(js_error)?;
+        Ok(serde_wasm_bindgen::to_value(&peer)?)
+    }
+
+    /// List all peers, most recently seen first
+    #[wasm_bindgen(js_name = listPeers)]
+    pub async fn js_list_peers(&self) -> std::result::Result<JsValue, JsValue> {
+        let peers = self.list_peers().await.map_err(js_error)?;
+        Ok(serde_wasm_bindgen::to_value(&peers)?)
+    }
+
+    /// Update a peer's reputation (a `Reputation` object)
+    #[wasm_bindgen(js_name = updateReputation)]
+    pub async fn js_update_reputation(
+        &self,
+        id: String,
+        reputation: JsValue,
+    ) -> std::result::Result<(), JsValue> {
+        let reputation: Reputation = serde_wasm_bindgen::from_value(reputation)?;
+        let id = PeerId::parse(&id).map_err(js_error)?;
+        self.update_reputation(&id, &reputation)
+            .await
+            .map_err(js_error)
+    }
+
+    /// Store a message (a `Message` object)
+    #[wasm_bindgen(js_name = storeMessage)]
+    pub async fn js_store_message(&self, message: JsValue) -> std::result::Result<(), JsValue> {
+        let message: Message = serde_wasm_bindgen::from_value(message)?;
+        self.store_message(&message).await.map_err(js_error)
+    }
+
+    /// List the most recent messages, newest first
+    #[wasm_bindgen(js_name = listRecentMessages)]
+    pub async fn js_list_recent_messages(
+        &self,
+        limit: u32,
+    ) -> std::result::Result<JsValue, JsValue> {
+        let messages = self.list_recent_messages(limit).await.map_err(js_error)?;
+        Ok(serde_wasm_bindgen::to_value(&messages)?)
+    }
+}
+
+/// Delete the records with the smallest `time` until at most `max` remain
+async fn evict_oldest(store: &ObjectStore, max: u32) -> Result<()> {
+    let count = store
+        .count(None)
+        .map_err(storage_error)?
+        .await
+        .map_err(storage_error)?;
+    if count <= max {
+        return Ok(());
+    }
+
+    let keys = store
+        .index(BY_TIME)
+        .map_err(storage_error)?
+        .get_all_keys(None, Some(count - max))
+        .map_err(storage_error)?
+        .await
+        .map_err(storage_error)?;
+    for key in keys {
+        store
+            .delete(Query::Key(key))
+            .map_err(storage_error)?
+            .await
+            .map_err(storage_error)?;
+    }
+
+    Ok(())
+}
+
+fn storage_error(e: impl std::fmt::Display) -> MycelialError {
+    MycelialError::Storage(e.to_string())
+}
+
+fn js_error(e: MycelialError) -> JsValue {
+    JsValue::from_str(&e.to_string())
+}
@@ -18,6 +18,7 @@ import { GradientPanel } from '@/components/GradientPanel';
 import { ElectionPanel } from '@/components/ElectionPanel';
 import { SeptalPanel } from '@/components/SeptalPanel';
 import { EnrCreditPanel } from '@/components/EnrCreditPanel';
+import { MeshHealthPanel } from '@/components/MeshHealthPanel';
 import type { NormalizedPeer, GeneratedIdentity, VouchRequest, CreditTransfer, Proposal, Vote } from '@/types';
 
 function App() {
@@ -52,6 +53,8 @@ function App() {
     enrTransfers,
     nodeEnrStates,
     elections,
+    // Gossipsub mesh state
+    meshStatus,
     // ENR Bridge functions
     reportGradient,
     startElection,
@@ -92,6 +95,7 @@ function App() {
   const [showElections, setShowElections] = useState(false);
   const [showSeptal, setShowSeptal] = useState(false);
   const [showEnrCredits, setShowEnrCredits] = useState(false);
+  const [showMesh, setShowMesh] = useState(false);
   const [localIdentity, setLocalIdentity] = useState<GeneratedIdentity | null>(null);
 
   const handleOnboardingComplete = useCallback((identity: GeneratedIdentity) => {
@@ -314,6 +318,19 @@ function App() {
               </svg>
               ENR
             </button>
+            <button
+              onClick={() => setShowMesh(true)}
+              className="btn-outline px-3 py-1.5 rounded-lg text-xs opacity-75 hover:opacity-100 transition-opacity flex items-center gap-1.5"
+              title="Gossipsub Mesh Health"
+            >
+              <svg width="14" height="14" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
+                <circle cx="5" cy="12" r="2" />
+                <circle cx="19" cy="5" r="2" />
+                <circle cx="19" cy="19" r="2" />
+                <path d="M7 11l10-5M7 13l10 5" />
+              </svg>
+              Mesh
+            </button>
             <button
               onClick={() => setShowOnboarding(true)}
               className="btn-outline px-3 py-1.5 rounded-lg text-xs opacity-75 hover:opacity-100 transition-opacity"
@@ -519,6 +536,13 @@ function App() {
           onClose={() => setShowEnrCredits(false)}
         />
       )}
+
+      {showMesh && (
+        <MeshHealthPanel
+          meshStatus={meshStatus}
+          onClose={() => setShowMesh(false)}
+        />
+      )}
     </div>
   );
 }
@@ -125,6 +125,11 @@ export function ChatPanel({ messages, onSendMessage, activeConversation, selecte
                     in {msg.room_id}
                   </span>
                 )}
+                {msg.verified === false && (
+                  <span className="text-xs text-red-400 font-mono" title="Sender could not be verified">
+                    unverified
+                  </span>
+                )}
               </div>
               <p className="text-mycelium-white font-body break-words">{msg.content}</p>
             </div>
@@ -0,0 +1,129 @@
+// src/components/MeshHealthPanel.tsx
+// Displays gossipsub mesh formation per subscribed topic
+
+import { useMemo } from 'react';
+import type { MeshTopicStatus } from '@/types';
+
+interface MeshHealthPanelProps {
+  meshStatus: Map<string, MeshTopicStatus>;
+  onClose?: () => void;
+}
+
+function formatTimestamp(ts: number): string {
+  const seconds = Math.floor((Date.now() - ts) / 1000);
+  if (seconds < 60) return `${seconds}s ago`;
+  if (seconds < 3600) return `${Math.floor(seconds / 60)}m ago`;
+  return `${Math.floor(seconds / 3600)}h ago`;
+}
+
+// A topic is healthy once it has mesh peers; subscribers without a mesh
+// means publishes reach nobody until gossipsub grafts them
+function getMeshHealth(status: MeshTopicStatus): { color: string; bgColor: string; label: string } {
+  if (status.meshPeers > 0) {
+    return { color: 'text-glow-cyan', bgColor: 'bg-glow-cyan/20', label: 'Formed' };
+  }
+  if (status.allPeers > 0) {
+    return { color: 'text-glow-gold', bgColor: 'bg-glow-gold/20', label: 'Forming' };
+  }
+  return { color: 'text-red-400', bgColor: 'bg-red-500/20', label: 'No peers' };
+}
+
+function TopicMeshCard({ status }: { status: MeshTopicStatus }) {
+  const health = getMeshHealth(status);
+  const meshPercent = status.allPeers > 0 ? (status.meshPeers / status.allPeers) * 100 : 0;
+
+  return (
+    <div className="p-4 bg-moss rounded-lg">
+      <div className="flex items-center justify-between mb-3">
+        <div className="min-w-0">
+          <div className="font-display text-mycelium-white truncate" title={status.topic}>
+            {status.topic}
+          </div>
+          <div className="text-xs text-soft-gray">
+            Updated {formatTimestamp(status.lastUpdated)}
+          </div>
+        </div>
+        <div className={`px-3 py-1.5 rounded-full ${health.bgColor}`}>
+          <span className={`text-sm font-display ${health.color}`}>{health.label}</span>
+        </div>
+      </div>
+
+      <div className="grid grid-cols-2 gap-4 text-sm mb-3">
+        <div className="text-center p-2 bg-bark rounded">
+          <div className={health.color}>{status.meshPeers}</div>
+          <div className="text-xs text-soft-gray mt-1">Mesh Peers</div>
+        </div>
+        <div className="text-center p-2 bg-bark rounded">
+          <div className="text-mycelium-white">{status.allPeers}</div>
+          <div className="text-xs text-soft-gray mt-1">Subscribers</div>
+        </div>
+      </div>
+
+      <div className="h-2 bg-bark rounded-full overflow-hidden">
+        <div
+          className="h-full bg-glow-cyan transition-all duration-500"
+          style={{ width: `${meshPercent}%` }}
+        />
+      </div>
+    </div>
+  );
+}
+
+export function MeshHealthPanel({ meshStatus, onClose }: MeshHealthPanelProps) {
+  const topics = useMemo(
+    () => Array.from(meshStatus.values()).sort((a, b) => a.topic.localeCompare(b.topic)),
+    [meshStatus]
+  );
+  const formed = topics.filter(t => t.meshPeers > 0).length;
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center bg-void/80 backdrop-blur-sm">
+      <div className="w-full max-w-3xl max-h-[90vh] bg-forest-floor border border-border-subtle rounded-xl shadow-card overflow-hidden">
+        {/* Header */}
+        <div className="relative px-6 py-4 bg-deep-earth border-b border-border-subtle">
+          <div className="absolute top-0 left-0 right-0 h-1 bg-gradient-to-r from-glow-cyan to-glow-gold" />
+          <div className="flex items-center justify-between">
+            <div>
+              <h2 className="text-xl font-display font-bold text-mycelium-white">
+                Mesh Health
+              </h2>
+              <p className="text-sm text-soft-gray font-body mt-0.5">
+                {formed} of {topics.length} topics have a gossipsub mesh
+              </p>
+            </div>
+            {onClose && (
+              <button
+                onClick={onClose}
+                className="text-soft-gray hover:text-mycelium-white transition-colors"
+              >
+                <svg width="24" height="24" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
+                  <path d="M18 6L6 18M6 6l12 12" />
+                </svg>
+              </button>
+            )}
+          </div>
+        </div>
+
+        {/* Content */}
+        <div className="p-6 overflow-y-auto max-h-[calc(90vh-100px)]">
+          {topics.length === 0 ? (
+            <div className="text-center py-12">
+              <p className="text-soft-gray">
+                No mesh data yet
+              </p>
+              <p className="text-xs text-soft-gray/60 mt-2">
+                Topics appear here as the node reports mesh changes
+              </p>
+            </div>
+          ) : (
+            <div className="grid gap-3 md:grid-cols-2">
+              {topics.map(status => (
+                <TopicMeshCard key={status.topic} status={status} />
+              ))}
+            </div>
+          )}
+        </div>
+      </div>
+    </div>
+  );
+}
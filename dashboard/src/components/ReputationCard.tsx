@@ -98,6 +98,8 @@ export function ReputationCard({
     if (!peer.location) return 'Location not shared';
 
     switch (peer.location.type) {
+      // Locations reported by the node (e.g. from LoRa GPS) carry no type
+      case undefined:
       case 'geographic':
         return `${peer.location.latitude?.toFixed(2)}, ${peer.location.longitude?.toFixed(2)}`;
       case 'logical':
@@ -221,6 +223,16 @@ export function ReputationCard({
           <span>{formatLocation()}</span>
         </div>
 
+        {/* Connection quality */}
+        {peer.quality !== undefined && (
+          <div className="flex items-center justify-between text-sm">
+            <span className="text-soft-gray font-body">Connection quality</span>
+            <span className="font-display text-glow-cyan font-bold">
+              {Math.round(peer.quality * 100)}%
+            </span>
+          </div>
+        )}
+
         {/* Action Buttons */}
         {!isLocalPeer && (
           <div className="flex gap-2 pt-2">
@@ -12,6 +12,7 @@ import type {
   GradientUpdate,
   Election,
 } from '@/types';
+import { authHeaders } from '@/utils/apiAuth';
 
 interface UseEconomicsAPIOptions {
   apiUrl?: string;
@@ -193,11 +194,11 @@ export function useEconomicsAPI(options: UseEconomicsAPIOptions = {}) {
         resourcesRes,
         summaryRes,
       ] = await Promise.allSettled([
-        fetch(`${apiUrlRef.current}/api/economics/credit-lines`),
-        fetch(`${apiUrlRef.current}/api/economics/proposals`),
-        fetch(`${apiUrlRef.current}/api/economics/vouches`),
-        fetch(`${apiUrlRef.current}/api/economics/resources`),
-        fetch(`${apiUrlRef.current}/api/economics`),
+        fetch(`${apiUrlRef.current}/api/economics/credit-lines`, { headers: authHeaders() }),
+        fetch(`${apiUrlRef.current}/api/economics/proposals`, { headers: authHeaders() }),
+        fetch(`${apiUrlRef.current}/api/economics/vouches`, { headers: authHeaders() }),
+        fetch(`${apiUrlRef.current}/api/economics/resources`, { headers: authHeaders() }),
+        fetch(`${apiUrlRef.current}/api/economics`, { headers: authHeaders() }),
       ]);
 
       if (!isMountedRef.current) return;
@@ -318,7 +319,7 @@ export function useEconomicsAPI(options: UseEconomicsAPIOptions = {}) {
   // Fetch economics data for a specific peer
   const fetchPeerEconomics = useCallback(async (peerId: string) => {
     try {
-      const response = await fetch(`${apiUrlRef.current}/api/economics/peer/${peerId}`);
+      const response = await fetch(`${apiUrlRef.current}/api/economics/peer/${peerId}`, { headers: authHeaders() });
       if (response.ok) {
         return await response.json();
       }
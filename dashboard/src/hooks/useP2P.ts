@@ -23,10 +23,12 @@ import type {
   ElectionResult,
   SeptalStateChange,
   SeptalHealthStatus,
+  MeshTopicStatus,
   Election,
   NodeEnrState,
   SeptalState,
 } from '@/types';
+import { authHeaders, withToken } from '@/utils/apiAuth';
 
 interface UseP2POptions {
   url?: string;
@@ -56,6 +58,8 @@ interface P2PState {
   enrTransfers: EnrCreditTransfer[];
   nodeEnrStates: Map<string, NodeEnrState>;
   elections: Map<number, Election>;
+  // Gossipsub mesh state per topic
+  meshStatus: Map<string, MeshTopicStatus>;
 }
 
 // Community conversation ID constant
@@ -139,6 +143,7 @@ function normalizePeer(peer: unknown): NormalizedPeer {
     reputation,
     location: p.location as Location | undefined,
     addresses: (p.addresses || []) as string[],
+    quality: typeof p.quality === 'number' ? p.quality : undefined,
   };
 }
 
@@ -200,13 +205,14 @@ export function useP2P(options: UseP2POptions = {}) {
     enrTransfers: [],
     nodeEnrStates: new Map(),
     elections: new Map(),
+    meshStatus: new Map(),
   });
 
   // Fetch peers from P2P node REST API
   const fetchPeers = useCallback(async () => {
     if (!isMountedRef.current) return;
     try {
-      const response = await fetch(`${apiUrlRef.current}/api/peers`);
+      const response = await fetch(`${apiUrlRef.current}/api/peers`, { headers: authHeaders() });
       if (!response.ok) throw new Error(`HTTP ${response.status}`);
       const data = await response.json();
       const peers = data.peers || data || [];
@@ -231,7 +237,7 @@ export function useP2P(options: UseP2POptions = {}) {
   const fetchInfo = useCallback(async () => {
     if (!isMountedRef.current) return;
     try {
-      const response = await fetch(`${apiUrlRef.current}/api/info`);
+      const response = await fetch(`${apiUrlRef.current}/api/info`, { headers: authHeaders() });
       if (!response.ok) throw new Error(`HTTP ${response.status}`);
       const data = await response.json();
       const peerId = data.peer_id || data.peerId || data.id;
@@ -686,6 +692,22 @@ export function useP2P(options: UseP2POptions = {}) {
         break;
       }
 
+      case 'mesh_updated': {
+        const data = (message.data || message) as Record<string, unknown>;
+        const status: MeshTopicStatus = {
+          topic: data.topic as string,
+          meshPeers: (data.mesh_peers ?? data.meshPeers) as number,
+          allPeers: (data.all_peers ?? data.allPeers) as number,
+          lastUpdated: Date.now(),
+        };
+        setState(s => {
+          const newMeshStatus = new Map(s.meshStatus);
+          newMeshStatus.set(status.topic, status);
+          return { ...s, meshStatus: newMeshStatus };
+        });
+        break;
+      }
+
       default:
         console.log('Unhandled message type:', message.type);
     }
@@ -710,7 +732,7 @@ export function useP2P(options: UseP2POptions = {}) {
     console.log(`Connecting to P2P WebSocket: ${currentUrl} (attempt ${reconnectAttemptsRef.current + 1}/${maxReconnectAttemptsRef.current})`);
 
     try {
-      const ws = new WebSocket(currentUrl);
+      const ws = new WebSocket(withToken(currentUrl));
 
       ws.onopen = () => {
         if (!isMountedRef.current) {
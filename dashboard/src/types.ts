@@ -27,6 +27,8 @@ export interface PeerInfo {
   location?: Location;
   reputation: number | Reputation;
   addresses?: string[];
+  // Connection quality (0-1) from ping RTT and churn, when the node knows it
+  quality?: number;
   created_at?: number;
   last_seen?: number;
 }
@@ -39,6 +41,8 @@ export interface ChatMessage {
   room_id?: string;
   content: string;
   timestamp: number;
+  // False when the node couldn't tie `from` to the sender's signature
+  verified?: boolean;
 }
 
 // Conversation types for enhanced chat
@@ -93,6 +97,7 @@ export interface NormalizedPeer {
   reputation: number;
   location?: Location;
   addresses: string[];
+  quality?: number;
 }
 
 // Phase 6: Onboarding types
@@ -469,6 +474,14 @@ export interface SeptalHealthStatus {
   timestamp: number;
 }
 
+// Gossipsub mesh health for one topic
+export interface MeshTopicStatus {
+  topic: string;
+  meshPeers: number;
+  allPeers: number;
+  lastUpdated: number;
+}
+
 // Combined election state for tracking active elections
 export interface Election {
   id: number;
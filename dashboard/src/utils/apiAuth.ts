@@ -0,0 +1,24 @@
+/**
+ * API token for nodes started with --api-token-file
+ *
+ * Set VITE_P2P_API_TOKEN to the contents of the node's token file. Without it
+ * requests go out unauthenticated, which is what a local node expects.
+ */
+
+const API_TOKEN: string | undefined = import.meta.env.VITE_P2P_API_TOKEN || undefined;
+
+/**
+ * Headers that authenticate a REST request
+ */
+export function authHeaders(): Record<string, string> {
+  return API_TOKEN ? { Authorization: `Bearer ${API_TOKEN}` } : {};
+}
+
+/**
+ * Add the token to a WebSocket URL, since browsers can't set headers on one
+ */
+export function withToken(url: string): string {
+  if (!API_TOKEN) return url;
+  const separator = url.includes('?') ? '&' : '?';
+  return `${url}${separator}token=${encodeURIComponent(API_TOKEN)}`;
+}